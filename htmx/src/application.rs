@@ -3,16 +3,71 @@ use std::{net::TcpListener, path::PathBuf, sync::Arc};
 use actix_cors::Cors;
 use actix_files::Files;
 use actix_web::{dev::Server, web, App, HttpServer};
+use chrono::Utc;
+use chrono_tz::Tz;
 use log::error;
-use models::{events::jobsite::JobsiteReadModelHandler, AppState, JobsiteBroadcast};
-use services::{configuration::Settings, get_connection_pool, get_eventstore_client};
+use models::{
+    events::{
+        attachment::AttachmentReadModelHandler, certification::CertificationReadModelHandler,
+        change_order::ChangeOrderReadModelHandler, client::ClientReadModelHandler,
+        comment::CommentReadModelHandler, cost_code::CostCodeReadModelHandler,
+        crew_assignment::CrewAssignmentReadModelHandler, daily_log::DailyLogReadModelHandler,
+        employee::EmployeeReadModelHandler, equipment::EquipmentReadModelHandler,
+        incident::IncidentReadModelHandler, jobsite::JobsiteReadModelHandler,
+        jobsite_budget::JobsiteBudgetReadModelHandler, leave::LeaveReadModelHandler,
+        material::MaterialReadModelHandler, pay_rate::PayRateReadModelHandler,
+        project::ProjectReadModelHandler, shift::ShiftReadModelHandler,
+        timesheet::TimesheetReadModelHandler,
+    },
+    labor::overtime::{PayRules, PayThresholds},
+    local_time::parse_time_zone,
+    process_managers::{
+        deadlines::Deadlines, jobsite_archival::JobsiteArchival, ProcessManagerRunner,
+    },
+    AppState, AttachmentBroadcast, CertificationBroadcast, ChangeOrderBroadcast, ClientBroadcast,
+    CommentBroadcast, CostCodeBroadcast, CrewAssignmentBroadcast, DailyLogBroadcast,
+    EmployeeBroadcast, EquipmentBroadcast, IncidentBroadcast, JobsiteBroadcast,
+    JobsiteBudgetBroadcast, LeaveBroadcast, MaterialBroadcast, PayRateBroadcast, ProjectBroadcast,
+    ShiftBroadcast, TimesheetBroadcast,
+};
+use services::{
+    configuration::{OvertimeSettings, Settings},
+    get_connection_pool, get_eventstore_client,
+    storage::{AttachmentStorage, LocalFileStorage},
+};
 use sqlx::PgPool;
 use tokio::sync::broadcast;
 use tracing_actix_web::TracingLogger;
 
-use crate::routes::{
-    get_jobsite, get_jobsites, get_landing_page, get_not_found_page, health_check, post_jobsite,
-    put_jobsite, websocket,
+use crate::{
+    jobs::run_scheduler,
+    routes::{
+        get_attachment, get_attachment_thumbnail, get_certifications_page, get_clients_page,
+        get_daily_logs_page, get_employee_calendar, get_employee_calendar_link, get_employees_page,
+        get_equipment_page, get_incidents_page, get_jobsite, get_jobsite_attachments,
+        get_jobsite_budget, get_jobsite_calendar, get_jobsite_calendar_link,
+        get_jobsite_certifications, get_jobsite_change_orders, get_jobsite_clock_in_page,
+        get_jobsite_comments, get_jobsite_cost_codes, get_jobsite_equipment, get_jobsite_geofence,
+        get_jobsite_kiosks, get_jobsite_labor_cost, get_jobsite_materials, get_jobsite_merge,
+        get_jobsite_on_site, get_jobsite_project, get_jobsite_qr_code, get_jobsite_qr_code_png,
+        get_jobsite_qr_code_svg, get_jobsites, get_kiosk_page, get_landing_page, get_leave_page,
+        get_materials_page, get_not_found_page, get_schedule_page, get_schedule_week_page,
+        get_timesheet_page, get_timesheets_page, health_check, post_attachment_remove,
+        post_certification, post_change_order, post_change_order_approve, post_change_order_reject,
+        post_change_order_submit, post_client, post_clock_in, post_clock_out, post_comment_delete,
+        post_comment_edit, post_cost_code, post_cost_code_retire, post_crew_assignment,
+        post_crew_assignment_move, post_crew_assignment_unassign, post_daily_log,
+        post_daily_log_attachment, post_daily_log_delay, post_daily_log_submit, post_employee,
+        post_employee_pin, post_employee_terminate, post_equipment, post_equipment_check_out,
+        post_equipment_return, post_incident, post_incident_close, post_incident_corrective_action,
+        post_incident_investigate, post_jobsite, post_jobsite_archive, post_jobsite_attachment,
+        post_jobsite_budget, post_jobsite_certifications, post_jobsite_clock_in,
+        post_jobsite_comment, post_jobsite_geofence, post_jobsite_kiosk, post_jobsite_kiosk_revoke,
+        post_jobsite_material, post_jobsite_merge, post_jobsite_project, post_jobsite_unmerge,
+        post_kiosk_punch, post_leave, post_leave_approve, post_leave_deny, post_material,
+        post_pay_rate, post_project, post_sync, post_timesheet_approve, post_timesheet_reject,
+        post_timesheet_reopen, post_timesheet_submit, put_jobsite, put_shift, websocket,
+    },
 };
 
 pub async fn run(
@@ -26,6 +81,10 @@ pub async fn run(
     let db_pool_data = web::Data::new(db_pool.clone());
     let eventstore_client_data = web::Data::new(eventstore_client.clone());
     let application_settings_data = web::Data::new(settings.application.clone());
+    let storage_settings_data = web::Data::new(settings.storage.clone());
+    let attachment_storage: Arc<dyn AttachmentStorage> =
+        Arc::new(LocalFileStorage::new(&settings.storage.path));
+    let attachment_storage_data = web::Data::from(attachment_storage);
     let app_state_data = web::Data::new(app_state);
 
     let manifest_dir = env!("CARGO_MANIFEST_DIR");
@@ -52,7 +111,250 @@ pub async fn run(
             .route("/jobsite", web::post().to(post_jobsite))
             .route("/jobsite/{jobsite_id}", web::get().to(get_jobsite))
             .route("/jobsite/{jobsite_id}", web::put().to(put_jobsite))
+            .route("/jobsite/{jobsite_id}/project", web::get().to(get_jobsite_project))
+            .route("/jobsite/{jobsite_id}/project", web::post().to(post_jobsite_project))
+            .route("/jobsite/{jobsite_id}/merge", web::get().to(get_jobsite_merge))
+            .route("/jobsite/{jobsite_id}/merge", web::post().to(post_jobsite_merge))
+            .route("/jobsite/{jobsite_id}/unmerge", web::post().to(post_jobsite_unmerge))
+            .route("/jobsite/{jobsite_id}/archive", web::post().to(post_jobsite_archive))
+            .route("/jobsite/{jobsite_id}/on-site", web::get().to(get_jobsite_on_site))
+            .route(
+                "/jobsite/{jobsite_id}/labor-cost",
+                web::get().to(get_jobsite_labor_cost),
+            )
+            .route("/jobsite/{jobsite_id}/budget", web::get().to(get_jobsite_budget))
+            .route("/jobsite/{jobsite_id}/budget", web::post().to(post_jobsite_budget))
+            .route(
+                "/jobsite/{jobsite_id}/change-orders",
+                web::get().to(get_jobsite_change_orders),
+            )
+            .route(
+                "/jobsite/{jobsite_id}/change-order",
+                web::post().to(post_change_order),
+            )
+            .route(
+                "/change-order/{change_order_id}/submit",
+                web::post().to(post_change_order_submit),
+            )
+            .route(
+                "/change-order/{change_order_id}/approve",
+                web::post().to(post_change_order_approve),
+            )
+            .route(
+                "/change-order/{change_order_id}/reject",
+                web::post().to(post_change_order_reject),
+            )
+            .route(
+                "/jobsite/{jobsite_id}/comments",
+                web::get().to(get_jobsite_comments),
+            )
+            .route(
+                "/jobsite/{jobsite_id}/comment",
+                web::post().to(post_jobsite_comment),
+            )
+            .route("/comment/{comment_id}/edit", web::post().to(post_comment_edit))
+            .route("/comment/{comment_id}/delete", web::post().to(post_comment_delete))
+            .route(
+                "/jobsite/{jobsite_id}/cost-codes",
+                web::get().to(get_jobsite_cost_codes),
+            )
+            .route("/jobsite/{jobsite_id}/cost-code", web::post().to(post_cost_code))
+            .route(
+                "/jobsite/{jobsite_id}/daily-logs",
+                web::get().to(get_daily_logs_page),
+            )
+            .route("/jobsite/{jobsite_id}/daily-log", web::post().to(post_daily_log))
+            .route(
+                "/jobsite/{jobsite_id}/daily-log/{log_date}/delay",
+                web::post().to(post_daily_log_delay),
+            )
+            .route(
+                "/jobsite/{jobsite_id}/daily-log/{log_date}/submit",
+                web::post().to(post_daily_log_submit),
+            )
+            .route(
+                "/jobsite/{jobsite_id}/daily-log/{log_date}/attachment",
+                web::post().to(post_daily_log_attachment),
+            )
+            .route(
+                "/jobsite/{jobsite_id}/attachments",
+                web::get().to(get_jobsite_attachments),
+            )
+            .route(
+                "/jobsite/{jobsite_id}/attachment",
+                web::post().to(post_jobsite_attachment),
+            )
+            .route("/attachment/{attachment_id}", web::get().to(get_attachment))
+            .route(
+                "/attachment/{attachment_id}/thumbnail",
+                web::get().to(get_attachment_thumbnail),
+            )
+            .route(
+                "/attachment/{attachment_id}/remove",
+                web::post().to(post_attachment_remove),
+            )
+            .route(
+                "/jobsite/{jobsite_id}/certifications",
+                web::get().to(get_jobsite_certifications),
+            )
+            .route(
+                "/jobsite/{jobsite_id}/certifications",
+                web::post().to(post_jobsite_certifications),
+            )
+            .route(
+                "/jobsite/{jobsite_id}/geofence",
+                web::get().to(get_jobsite_geofence),
+            )
+            .route(
+                "/jobsite/{jobsite_id}/geofence",
+                web::post().to(post_jobsite_geofence),
+            )
+            .route(
+                "/jobsite/{jobsite_id}/kiosks",
+                web::get().to(get_jobsite_kiosks),
+            )
+            .route("/jobsite/{jobsite_id}/kiosk", web::post().to(post_jobsite_kiosk))
+            .route(
+                "/jobsite/{jobsite_id}/kiosk/{kiosk_id}/revoke",
+                web::post().to(post_jobsite_kiosk_revoke),
+            )
+            .route(
+                "/jobsite/{jobsite_id}/qr-code",
+                web::get().to(get_jobsite_qr_code),
+            )
+            .route(
+                "/jobsite/{jobsite_id}/qr-code.svg",
+                web::get().to(get_jobsite_qr_code_svg),
+            )
+            .route(
+                "/jobsite/{jobsite_id}/qr-code.png",
+                web::get().to(get_jobsite_qr_code_png),
+            )
+            .route(
+                "/clock-in/{jobsite_id}",
+                web::get().to(get_jobsite_clock_in_page),
+            )
+            .route(
+                "/clock-in/{jobsite_id}",
+                web::post().to(post_jobsite_clock_in),
+            )
+            .route("/kiosk/{jobsite_id}", web::get().to(get_kiosk_page))
+            .route("/kiosk/{jobsite_id}/punch", web::post().to(post_kiosk_punch))
+            .route(
+                "/jobsite/{jobsite_id}/calendar-link",
+                web::get().to(get_jobsite_calendar_link),
+            )
+            .route(
+                "/cost-code/{cost_code_id}/retire",
+                web::post().to(post_cost_code_retire),
+            )
             .route("/jobsites", web::get().to(get_jobsites))
+            .route("/clients", web::get().to(get_clients_page))
+            .route("/client", web::post().to(post_client))
+            .route("/project", web::post().to(post_project))
+            .route("/employees", web::get().to(get_employees_page))
+            .route("/employee", web::post().to(post_employee))
+            .route(
+                "/employee/{employee_id}/terminate",
+                web::post().to(post_employee_terminate),
+            )
+            .route("/employee/{employee_id}/pin", web::post().to(post_employee_pin))
+            .route(
+                "/employee/{employee_id}/calendar-link",
+                web::get().to(get_employee_calendar_link),
+            )
+            .route(
+                "/calendar/employee/{employee_id}/{token}/schedule.ics",
+                web::get().to(get_employee_calendar),
+            )
+            .route(
+                "/calendar/jobsite/{jobsite_id}/{token}/crew.ics",
+                web::get().to(get_jobsite_calendar),
+            )
+            .route("/schedule", web::get().to(get_schedule_page))
+            .route("/schedule/{week_start}", web::get().to(get_schedule_week_page))
+            .route("/crew-assignment", web::post().to(post_crew_assignment))
+            .route(
+                "/crew-assignment/{assignment_id}/unassign",
+                web::post().to(post_crew_assignment_unassign),
+            )
+            .route(
+                "/crew-assignment/{assignment_id}/move",
+                web::post().to(post_crew_assignment_move),
+            )
+            .route("/pay-rate", web::post().to(post_pay_rate))
+            .route("/shift/clock-in", web::post().to(post_clock_in))
+            .route("/shift/{shift_id}/clock-out", web::post().to(post_clock_out))
+            .route("/shift/{shift_id}", web::put().to(put_shift))
+            .route("/sync", web::post().to(post_sync))
+            .route("/timesheets", web::get().to(get_timesheets_page))
+            .route(
+                "/timesheet/{employee_id}/{week_start}",
+                web::get().to(get_timesheet_page),
+            )
+            .route(
+                "/timesheet/{employee_id}/{week_start}/submit",
+                web::post().to(post_timesheet_submit),
+            )
+            .route(
+                "/timesheet/{employee_id}/{week_start}/approve",
+                web::post().to(post_timesheet_approve),
+            )
+            .route(
+                "/timesheet/{employee_id}/{week_start}/reject",
+                web::post().to(post_timesheet_reject),
+            )
+            .route(
+                "/timesheet/{employee_id}/{week_start}/reopen",
+                web::post().to(post_timesheet_reopen),
+            )
+            .route("/certifications", web::get().to(get_certifications_page))
+            .route("/certification", web::post().to(post_certification))
+            .route("/leave", web::get().to(get_leave_page))
+            .route("/leave", web::post().to(post_leave))
+            .route(
+                "/leave/{leave_id}/approve",
+                web::post().to(post_leave_approve),
+            )
+            .route("/leave/{leave_id}/deny", web::post().to(post_leave_deny))
+            .route("/equipment", web::get().to(get_equipment_page))
+            .route("/equipment", web::post().to(post_equipment))
+            .route(
+                "/equipment/{equipment_id}/check-out",
+                web::post().to(post_equipment_check_out),
+            )
+            .route(
+                "/equipment/{equipment_id}/return",
+                web::post().to(post_equipment_return),
+            )
+            .route(
+                "/jobsite/{jobsite_id}/equipment",
+                web::get().to(get_jobsite_equipment),
+            )
+            .route("/materials", web::get().to(get_materials_page))
+            .route("/material", web::post().to(post_material))
+            .route(
+                "/jobsite/{jobsite_id}/materials",
+                web::get().to(get_jobsite_materials),
+            )
+            .route(
+                "/jobsite/{jobsite_id}/materials",
+                web::post().to(post_jobsite_material),
+            )
+            .route("/incidents", web::get().to(get_incidents_page))
+            .route("/incident", web::post().to(post_incident))
+            .route(
+                "/incident/{incident_id}/investigate",
+                web::post().to(post_incident_investigate),
+            )
+            .route(
+                "/incident/{incident_id}/corrective-action",
+                web::post().to(post_incident_corrective_action),
+            )
+            .route(
+                "/incident/{incident_id}/close",
+                web::post().to(post_incident_close),
+            )
             .route("/websocket", web::get().to(websocket))
             // Default handler (404)
             .default_service(
@@ -62,6 +364,8 @@ pub async fn run(
             // Get a pointer copy and attach it to the application state
             .app_data(db_pool_data.clone())
             .app_data(application_settings_data.clone())
+            .app_data(storage_settings_data.clone())
+            .app_data(attachment_storage_data.clone())
             .app_data(eventstore_client_data.clone())
             .app_data(app_state_data.clone())
             .service(Files::new("/public", public_path.to_str().unwrap()).prefer_utf8(true))
@@ -76,6 +380,7 @@ async fn run_event_handlers(
     eventstore: Arc<eventstore::Client>,
     db_pool: Arc<PgPool>,
     app_state: AppState,
+    time_zone: Tz,
 ) {
     let jobsite_read_model_handler =
         JobsiteReadModelHandler::new(eventstore.clone(), db_pool.clone(), app_state.clone());
@@ -83,16 +388,200 @@ async fn run_event_handlers(
         jobsite_read_model_handler.subscribe().await;
     });
 
+    let client_read_model_handler =
+        ClientReadModelHandler::new(eventstore.clone(), db_pool.clone(), app_state.clone());
+    let client_read_model_event_handler = tokio::spawn(async move {
+        client_read_model_handler.subscribe().await;
+    });
+
+    let project_read_model_handler =
+        ProjectReadModelHandler::new(eventstore.clone(), db_pool.clone(), app_state.clone());
+    let project_read_model_event_handler = tokio::spawn(async move {
+        project_read_model_handler.subscribe().await;
+    });
+
+    let employee_read_model_handler =
+        EmployeeReadModelHandler::new(eventstore.clone(), db_pool.clone(), app_state.clone());
+    let employee_read_model_event_handler = tokio::spawn(async move {
+        employee_read_model_handler.subscribe().await;
+    });
+
+    let crew_assignment_read_model_handler =
+        CrewAssignmentReadModelHandler::new(eventstore.clone(), db_pool.clone(), app_state.clone());
+    let crew_assignment_read_model_event_handler = tokio::spawn(async move {
+        crew_assignment_read_model_handler.subscribe().await;
+    });
+
+    let shift_read_model_handler =
+        ShiftReadModelHandler::new(eventstore.clone(), db_pool.clone(), app_state.clone());
+    let shift_read_model_event_handler = tokio::spawn(async move {
+        shift_read_model_handler.subscribe().await;
+    });
+
+    let timesheet_read_model_handler =
+        TimesheetReadModelHandler::new(eventstore.clone(), db_pool.clone(), app_state.clone());
+    let timesheet_read_model_event_handler = tokio::spawn(async move {
+        timesheet_read_model_handler.subscribe().await;
+    });
+
+    let pay_rate_read_model_handler =
+        PayRateReadModelHandler::new(eventstore.clone(), db_pool.clone(), app_state.clone());
+    let pay_rate_read_model_event_handler = tokio::spawn(async move {
+        pay_rate_read_model_handler.subscribe().await;
+    });
+
+    let jobsite_budget_read_model_handler =
+        JobsiteBudgetReadModelHandler::new(eventstore.clone(), db_pool.clone(), app_state.clone());
+    let jobsite_budget_read_model_event_handler = tokio::spawn(async move {
+        jobsite_budget_read_model_handler.subscribe().await;
+    });
+
+    let cost_code_read_model_handler =
+        CostCodeReadModelHandler::new(eventstore.clone(), db_pool.clone(), app_state.clone());
+    let cost_code_read_model_event_handler = tokio::spawn(async move {
+        cost_code_read_model_handler.subscribe().await;
+    });
+
+    let daily_log_read_model_handler =
+        DailyLogReadModelHandler::new(eventstore.clone(), db_pool.clone(), app_state.clone());
+    let daily_log_read_model_event_handler = tokio::spawn(async move {
+        daily_log_read_model_handler.subscribe().await;
+    });
+
+    let attachment_read_model_handler =
+        AttachmentReadModelHandler::new(eventstore.clone(), db_pool.clone(), app_state.clone());
+    let attachment_read_model_event_handler = tokio::spawn(async move {
+        attachment_read_model_handler.subscribe().await;
+    });
+
+    let incident_read_model_handler =
+        IncidentReadModelHandler::new(eventstore.clone(), db_pool.clone(), app_state.clone());
+    let incident_read_model_event_handler = tokio::spawn(async move {
+        incident_read_model_handler.subscribe().await;
+    });
+
+    let certification_read_model_handler =
+        CertificationReadModelHandler::new(eventstore.clone(), db_pool.clone(), app_state.clone());
+    let certification_read_model_event_handler = tokio::spawn(async move {
+        certification_read_model_handler.subscribe().await;
+    });
+
+    let leave_read_model_handler =
+        LeaveReadModelHandler::new(eventstore.clone(), db_pool.clone(), app_state.clone());
+    let leave_read_model_event_handler = tokio::spawn(async move {
+        leave_read_model_handler.subscribe().await;
+    });
+
+    let equipment_read_model_handler =
+        EquipmentReadModelHandler::new(eventstore.clone(), db_pool.clone(), app_state.clone());
+    let equipment_read_model_event_handler = tokio::spawn(async move {
+        equipment_read_model_handler.subscribe().await;
+    });
+
+    let material_read_model_handler =
+        MaterialReadModelHandler::new(eventstore.clone(), db_pool.clone(), app_state.clone());
+    let material_read_model_event_handler = tokio::spawn(async move {
+        material_read_model_handler.subscribe().await;
+    });
+
+    let change_order_read_model_handler =
+        ChangeOrderReadModelHandler::new(eventstore.clone(), db_pool.clone(), app_state.clone());
+    let change_order_read_model_event_handler = tokio::spawn(async move {
+        change_order_read_model_handler.subscribe().await;
+    });
+
+    let comment_read_model_handler =
+        CommentReadModelHandler::new(eventstore.clone(), db_pool.clone(), app_state.clone());
+    let comment_read_model_event_handler = tokio::spawn(async move {
+        comment_read_model_handler.subscribe().await;
+    });
+
+    let jobsite_archival_runner =
+        ProcessManagerRunner::new(eventstore.clone(), db_pool.clone(), JobsiteArchival);
+    let jobsite_archival_process_manager = tokio::spawn(async move {
+        jobsite_archival_runner.subscribe().await;
+    });
+
+    let deadlines = Deadlines {
+        time_zone,
+        started_at: Utc::now(),
+    };
+    let deadlines_runner = ProcessManagerRunner::new(eventstore.clone(), db_pool.clone(), deadlines);
+    let deadlines_process_manager = tokio::spawn(async move {
+        deadlines_runner.subscribe().await;
+    });
+
     tokio::select! {
         _ = jobsite_read_model_event_handler => {
             error!("Jobsite read model event handler stopped");
         }
+        _ = client_read_model_event_handler => {
+            error!("Client read model event handler stopped");
+        }
+        _ = project_read_model_event_handler => {
+            error!("Project read model event handler stopped");
+        }
+        _ = employee_read_model_event_handler => {
+            error!("Employee read model event handler stopped");
+        }
+        _ = crew_assignment_read_model_event_handler => {
+            error!("Crew assignment read model event handler stopped");
+        }
+        _ = shift_read_model_event_handler => {
+            error!("Shift read model event handler stopped");
+        }
+        _ = timesheet_read_model_event_handler => {
+            error!("Timesheet read model event handler stopped");
+        }
+        _ = pay_rate_read_model_event_handler => {
+            error!("Pay rate read model event handler stopped");
+        }
+        _ = jobsite_budget_read_model_event_handler => {
+            error!("Jobsite budget read model event handler stopped");
+        }
+        _ = cost_code_read_model_event_handler => {
+            error!("Cost code read model event handler stopped");
+        }
+        _ = daily_log_read_model_event_handler => {
+            error!("Daily log read model event handler stopped");
+        }
+        _ = attachment_read_model_event_handler => {
+            error!("Attachment read model event handler stopped");
+        }
+        _ = incident_read_model_event_handler => {
+            error!("Incident read model event handler stopped");
+        }
+        _ = certification_read_model_event_handler => {
+            error!("Certification read model event handler stopped");
+        }
+        _ = leave_read_model_event_handler => {
+            error!("Leave read model event handler stopped");
+        }
+        _ = equipment_read_model_event_handler => {
+            error!("Equipment read model event handler stopped");
+        }
+        _ = material_read_model_event_handler => {
+            error!("Material read model event handler stopped");
+        }
+        _ = change_order_read_model_event_handler => {
+            error!("Change order read model event handler stopped");
+        }
+        _ = comment_read_model_event_handler => {
+            error!("Comment read model event handler stopped");
+        }
+        _ = jobsite_archival_process_manager => {
+            error!("Jobsite archival process manager stopped");
+        }
+        _ = deadlines_process_manager => {
+            error!("Deadlines process manager stopped");
+        }
     }
 }
 
 pub struct Application {
     server: Server,
     event_handler: tokio::task::JoinHandle<()>,
+    scheduler: tokio::task::JoinHandle<()>,
 }
 
 impl Application {
@@ -103,8 +592,59 @@ impl Application {
         let eventstore_client = get_eventstore_client(&configuration.eventstore);
 
         let (jobsite_tx, _) = broadcast::channel::<JobsiteBroadcast>(16);
+        let (client_tx, _) = broadcast::channel::<ClientBroadcast>(16);
+        let (project_tx, _) = broadcast::channel::<ProjectBroadcast>(16);
+        let (employee_tx, _) = broadcast::channel::<EmployeeBroadcast>(16);
+        let (crew_assignment_tx, _) = broadcast::channel::<CrewAssignmentBroadcast>(16);
+        let (shift_tx, _) = broadcast::channel::<ShiftBroadcast>(16);
+        let (timesheet_tx, _) = broadcast::channel::<TimesheetBroadcast>(16);
+        let (pay_rate_tx, _) = broadcast::channel::<PayRateBroadcast>(16);
+        let (jobsite_budget_tx, _) = broadcast::channel::<JobsiteBudgetBroadcast>(16);
+        let (cost_code_tx, _) = broadcast::channel::<CostCodeBroadcast>(16);
+        let (daily_log_tx, _) = broadcast::channel::<DailyLogBroadcast>(16);
+        let (attachment_tx, _) = broadcast::channel::<AttachmentBroadcast>(16);
+        let (incident_tx, _) = broadcast::channel::<IncidentBroadcast>(16);
+        let (certification_tx, _) = broadcast::channel::<CertificationBroadcast>(16);
+        let (leave_tx, _) = broadcast::channel::<LeaveBroadcast>(16);
+        let (equipment_tx, _) = broadcast::channel::<EquipmentBroadcast>(16);
+        let (material_tx, _) = broadcast::channel::<MaterialBroadcast>(16);
+        let (change_order_tx, _) = broadcast::channel::<ChangeOrderBroadcast>(16);
+        let (comment_tx, _) = broadcast::channel::<CommentBroadcast>(16);
 
-        let app_state = AppState { jobsite_tx };
+        let pay_rules = PayRules::preset(&configuration.labor.pay_rules)
+            .ok_or_else(|| {
+                anyhow::anyhow!("Unknown pay rules: {}", configuration.labor.pay_rules)
+            })?
+            .with_holidays(configuration.labor.holidays.clone())
+            .with_thresholds(pay_thresholds(&configuration.labor.overtime)?);
+
+        let time_zone = parse_time_zone(&configuration.application.time_zone).ok_or_else(|| {
+            anyhow::anyhow!("Unknown time zone: {}", configuration.application.time_zone)
+        })?;
+
+        let app_state = AppState {
+            jobsite_tx,
+            client_tx,
+            project_tx,
+            employee_tx,
+            crew_assignment_tx,
+            shift_tx,
+            timesheet_tx,
+            pay_rate_tx,
+            jobsite_budget_tx,
+            cost_code_tx,
+            daily_log_tx,
+            attachment_tx,
+            incident_tx,
+            certification_tx,
+            leave_tx,
+            equipment_tx,
+            material_tx,
+            change_order_tx,
+            comment_tx,
+            pay_rules,
+            time_zone,
+        };
 
         // sqlx::migrate!("./migrations")
         //     .run(&connection_pool)
@@ -129,8 +669,9 @@ impl Application {
 
         let event_store = Arc::new(eventstore_client);
         let db_pool = Arc::new(connection_pool);
+        let scheduler = tokio::spawn(run_scheduler(event_store.clone(), db_pool.clone()));
         let event_handler = tokio::spawn(async move {
-            run_event_handlers(event_store, db_pool, app_state).await;
+            run_event_handlers(event_store, db_pool, app_state, time_zone).await;
         });
 
         println!("Server running on port {}", port);
@@ -138,6 +679,7 @@ impl Application {
         Ok(Self {
             server,
             event_handler,
+            scheduler,
         })
     }
 
@@ -147,6 +689,7 @@ impl Application {
         let Application {
             server,
             event_handler,
+            scheduler,
         } = self;
 
         tokio::select! {
@@ -156,8 +699,29 @@ impl Application {
             _ = event_handler => {
                 error!("Event handler stopped");
             }
+            _ = scheduler => {
+                error!("Scheduler stopped");
+            }
         }
 
         Ok(())
     }
 }
+
+/// The overtime thresholds set in configuration, in minutes
+fn pay_thresholds(overtime: &OvertimeSettings) -> anyhow::Result<PayThresholds> {
+    let minutes = |hours: Option<f64>| match hours {
+        Some(hours) if !hours.is_finite() || hours < 0.0 => Err(anyhow::anyhow!(
+            "Overtime thresholds must be zero or more hours, not {}",
+            hours
+        )),
+        hours => Ok(hours.map(|hours| (hours * 60.0).round() as i64)),
+    };
+
+    Ok(PayThresholds {
+        daily_overtime_after: minutes(overtime.daily_overtime_after_hours)?,
+        daily_double_time_after: minutes(overtime.daily_double_time_after_hours)?,
+        weekly_overtime_after: minutes(overtime.weekly_overtime_after_hours)?,
+        seventh_day_double_time_after: minutes(overtime.seventh_day_double_time_after_hours)?,
+    })
+}
@@ -0,0 +1,24 @@
+use std::{sync::Arc, time::Duration};
+
+use log::error;
+use models::scheduler::{Scheduler, SystemClock};
+use sqlx::PgPool;
+
+/// How often the scheduler looks for triggers that have come due
+const POLL_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Fire scheduled triggers as they come due, the first look is at startup
+/// so triggers that came due while the application was down fire right
+/// away. Only returns if the application is shutting down.
+pub async fn run_scheduler(eventstore: Arc<eventstore::Client>, db_pool: Arc<PgPool>) {
+    let scheduler = Scheduler::new(eventstore, db_pool, SystemClock);
+    let mut interval = tokio::time::interval(POLL_INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        if let Err(e) = scheduler.fire_due().await {
+            error!("Failed to fire scheduled triggers: {}", e);
+        }
+    }
+}
@@ -2,6 +2,7 @@ use application::Application;
 use services::configuration::get_configuration;
 
 mod application;
+mod jobs;
 mod routes;
 pub mod utils;
 pub mod views;
@@ -0,0 +1,396 @@
+use actix_multipart::Multipart;
+use actix_web::{
+    http::header::{ContentDisposition, DispositionParam, DispositionType},
+    web, HttpResponse,
+};
+use chrono::{NaiveDate, Utc};
+use eventstore::EventData;
+use models::{
+    events::attachment::{AttachmentAdded, AttachmentRemoved},
+    projections::{attachment::Attachment, daily_log::DailyLog, jobsite::Jobsite},
+};
+use services::{
+    configuration::StorageSettings,
+    storage::{detect_content_type, generate_thumbnail, AttachmentStorage},
+};
+use sqlx::PgPool;
+use tokio_stream::StreamExt;
+use uuid::Uuid;
+
+use crate::{
+    utils::{ErrorProps, ErrorPropsCollection, RouteError},
+    views::{components, TemplateRenderer},
+};
+
+/// Longest file name kept on an attachment, in characters
+const MAX_FILE_NAME_LENGTH: usize = 255;
+
+/// A file read out of a multipart upload
+struct Upload {
+    file_name: String,
+    content: Vec<u8>,
+}
+
+/// Read the `file` field of a multipart upload, giving up as soon as it
+/// grows past `max_bytes` rather than buffering the whole thing first.
+/// Errors are messages to show on the form.
+async fn read_upload(mut payload: Multipart, max_bytes: usize) -> Result<Upload, String> {
+    while let Some(field) = payload.next().await {
+        let mut field = field.map_err(|_| "The upload could not be read".to_string())?;
+
+        if field.name() != Some("file") {
+            continue;
+        }
+
+        // Browsers may send a full path, only the last part is a file name
+        let file_name = field
+            .content_disposition()
+            .and_then(|disposition| disposition.get_filename())
+            .and_then(|file_name| file_name.rsplit(['/', '\\']).next())
+            .map(|file_name| {
+                file_name
+                    .trim()
+                    .chars()
+                    .take(MAX_FILE_NAME_LENGTH)
+                    .collect()
+            })
+            .filter(|file_name: &String| !file_name.is_empty())
+            .unwrap_or_else(|| "attachment".to_string());
+
+        let mut content = Vec::new();
+        while let Some(chunk) = field.next().await {
+            let chunk = chunk.map_err(|_| "The upload could not be read".to_string())?;
+
+            if content.len() + chunk.len() > max_bytes {
+                return Err(format!(
+                    "Files can be at most {} MB",
+                    max_bytes / (1024 * 1024)
+                ));
+            }
+
+            content.extend_from_slice(&chunk);
+        }
+
+        if content.is_empty() {
+            return Err("Choose a file to attach".to_string());
+        }
+
+        return Ok(Upload { file_name, content });
+    }
+
+    Err("Choose a file to attach".to_string())
+}
+
+/// Validate and store an upload, then record it against the jobsite, or the
+/// jobsite's daily log for `log_date`
+async fn add_attachment(
+    payload: Multipart,
+    storage: &dyn AttachmentStorage,
+    storage_settings: &StorageSettings,
+    eventstore: &eventstore::Client,
+    jobsite_id: Uuid,
+    log_date: Option<NaiveDate>,
+    error_id: String,
+) -> Result<HttpResponse, RouteError> {
+    let mut errors = vec![ErrorProps::new(error_id.clone())];
+
+    let upload = match read_upload(payload, storage_settings.max_upload_bytes).await {
+        Ok(upload) => upload,
+        Err(message) => {
+            errors.set_error(&error_id, &message)?;
+
+            return Ok(HttpResponse::Ok()
+                .content_type("text/html; charset=utf-8")
+                .body(errors.render_errors()?));
+        }
+    };
+
+    // The browser's content type is only a hint, the bytes decide
+    let Some(content_type) = detect_content_type(&upload.content) else {
+        errors.set_error(
+            &error_id,
+            "Only JPEG, PNG, GIF, WebP and PDF files can be attached",
+        )?;
+
+        return Ok(HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(errors.render_errors()?));
+    };
+
+    let content = upload.content;
+    let content_hash = storage
+        .put(&content)
+        .await
+        .map_err(|e| RouteError::UnexpectedError(e.into()))?;
+
+    let thumbnail_hash = if content_type.starts_with("image/") {
+        let content = content.clone();
+        match web::block(move || generate_thumbnail(&content))
+            .await
+            .map_err(|e| RouteError::UnexpectedError(anyhow::anyhow!(e)))?
+        {
+            Some(thumbnail) => Some(
+                storage
+                    .put(&thumbnail)
+                    .await
+                    .map_err(|e| RouteError::UnexpectedError(e.into()))?,
+            ),
+            None => None,
+        }
+    } else {
+        None
+    };
+
+    let added_event = AttachmentAdded {
+        id: Uuid::new_v4(),
+        jobsite_id,
+        log_date,
+        file_name: upload.file_name,
+        content_type: content_type.to_string(),
+        size_bytes: content.len() as i64,
+        content_hash,
+        thumbnail_hash,
+        added_at: Utc::now(),
+    };
+
+    let event = EventData::json(AttachmentAdded::event_name(), &added_event)
+        .expect("Unable to serialize")
+        .id(Uuid::new_v4());
+
+    eventstore
+        .append_to_stream(
+            Attachment::stream_name(&added_event.id),
+            &Default::default(),
+            event,
+        )
+        .await
+        .expect("Failed to append event");
+
+    Ok(HttpResponse::Created()
+        .content_type("text/html; charset=utf-8")
+        .body(errors.render_errors()?))
+}
+
+pub async fn get_jobsite_attachments(
+    db_pool: web::Data<PgPool>,
+    jobsite_id: web::Path<Uuid>,
+) -> Result<HttpResponse, RouteError> {
+    let jobsite_id = jobsite_id.into_inner();
+
+    let mut transaction = db_pool.begin().await?;
+
+    if Jobsite::get_by_id(&mut transaction, &jobsite_id)
+        .await?
+        .is_none()
+    {
+        return Err(RouteError::NotFound);
+    }
+
+    let attachments = Attachment::get_list_by_jobsite(&mut transaction, &jobsite_id).await?;
+
+    transaction.commit().await?;
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .body(TemplateRenderer::render(move || {
+            components::attachment::JobsiteAttachments(
+                components::attachment::JobsiteAttachmentsProps {
+                    jobsite_id,
+                    attachments,
+                },
+            )
+        })))
+}
+
+pub async fn post_jobsite_attachment(
+    db_pool: web::Data<PgPool>,
+    payload: Multipart,
+    storage: web::Data<dyn AttachmentStorage>,
+    storage_settings: web::Data<StorageSettings>,
+    eventstore: web::Data<eventstore::Client>,
+    jobsite_id: web::Path<Uuid>,
+) -> Result<HttpResponse, RouteError> {
+    let jobsite_id = jobsite_id.into_inner();
+
+    let mut transaction = db_pool.begin().await?;
+
+    if Jobsite::get_by_id(&mut transaction, &jobsite_id)
+        .await?
+        .is_none()
+    {
+        return Err(RouteError::NotFound);
+    }
+
+    transaction.commit().await?;
+
+    add_attachment(
+        payload,
+        storage.get_ref(),
+        &storage_settings,
+        &eventstore,
+        jobsite_id,
+        None,
+        components::attachment::jobsite_attachment_error_id(&jobsite_id),
+    )
+    .await
+}
+
+pub async fn post_daily_log_attachment(
+    db_pool: web::Data<PgPool>,
+    payload: Multipart,
+    storage: web::Data<dyn AttachmentStorage>,
+    storage_settings: web::Data<StorageSettings>,
+    eventstore: web::Data<eventstore::Client>,
+    path: web::Path<(Uuid, NaiveDate)>,
+) -> Result<HttpResponse, RouteError> {
+    let (jobsite_id, log_date) = path.into_inner();
+
+    let mut transaction = db_pool.begin().await?;
+
+    let daily_log = match DailyLog::get(&mut transaction, &jobsite_id, &log_date).await? {
+        Some(daily_log) => daily_log,
+        None => return Err(RouteError::NotFound),
+    };
+
+    transaction.commit().await?;
+
+    if daily_log.is_locked() {
+        return Err(RouteError::UnexpectedError(anyhow::anyhow!(
+            "This day's log has already been submitted"
+        )));
+    }
+
+    add_attachment(
+        payload,
+        storage.get_ref(),
+        &storage_settings,
+        &eventstore,
+        jobsite_id,
+        Some(log_date),
+        components::attachment::daily_log_attachment_error_id(&log_date),
+    )
+    .await
+}
+
+/// Load an attachment that hasn't been removed, and its content from storage
+async fn get_attachment_content(
+    db_pool: &PgPool,
+    storage: &dyn AttachmentStorage,
+    attachment_id: &Uuid,
+    thumbnail: bool,
+) -> Result<(Attachment, Vec<u8>), RouteError> {
+    let mut transaction = db_pool.begin().await?;
+
+    let attachment = match Attachment::get_by_id(&mut transaction, attachment_id).await? {
+        Some(attachment) if attachment.removed_at.is_none() => attachment,
+        _ => return Err(RouteError::NotFound),
+    };
+
+    transaction.commit().await?;
+
+    let hash = if thumbnail {
+        attachment
+            .thumbnail_hash
+            .as_deref()
+            .ok_or(RouteError::NotFound)?
+    } else {
+        &attachment.content_hash
+    };
+
+    let content = storage
+        .get(hash)
+        .await
+        .map_err(|e| RouteError::UnexpectedError(e.into()))?
+        .ok_or(RouteError::NotFound)?;
+
+    Ok((attachment, content))
+}
+
+pub async fn get_attachment(
+    db_pool: web::Data<PgPool>,
+    storage: web::Data<dyn AttachmentStorage>,
+    attachment_id: web::Path<Uuid>,
+) -> Result<HttpResponse, RouteError> {
+    let (attachment, content) =
+        get_attachment_content(&db_pool, storage.get_ref(), &attachment_id, false).await?;
+
+    Ok(HttpResponse::Ok()
+        .content_type(attachment.content_type.as_str())
+        .insert_header(ContentDisposition {
+            disposition: DispositionType::Inline,
+            parameters: vec![DispositionParam::Filename(attachment.file_name)],
+        })
+        .insert_header(("X-Content-Type-Options", "nosniff"))
+        .body(content))
+}
+
+pub async fn get_attachment_thumbnail(
+    db_pool: web::Data<PgPool>,
+    storage: web::Data<dyn AttachmentStorage>,
+    attachment_id: web::Path<Uuid>,
+) -> Result<HttpResponse, RouteError> {
+    let (_, content) =
+        get_attachment_content(&db_pool, storage.get_ref(), &attachment_id, true).await?;
+
+    // Content behind an attachment never changes
+    Ok(HttpResponse::Ok()
+        .content_type("image/png")
+        .insert_header(("Cache-Control", "private, max-age=31536000, immutable"))
+        .body(content))
+}
+
+pub async fn post_attachment_remove(
+    db_pool: web::Data<PgPool>,
+    eventstore: web::Data<eventstore::Client>,
+    attachment_id: web::Path<Uuid>,
+) -> Result<HttpResponse, RouteError> {
+    let mut transaction = db_pool.begin().await?;
+
+    let attachment = match Attachment::get_by_id(&mut transaction, &attachment_id).await? {
+        Some(attachment) => attachment,
+        None => return Err(RouteError::NotFound),
+    };
+
+    // Submitted logs keep their attachments too
+    if let Some(log_date) = attachment.log_date {
+        if let Some(daily_log) =
+            DailyLog::get(&mut transaction, &attachment.jobsite_id, &log_date).await?
+        {
+            if daily_log.is_locked() {
+                return Err(RouteError::UnexpectedError(anyhow::anyhow!(
+                    "This day's log has already been submitted"
+                )));
+            }
+        }
+    }
+
+    transaction.commit().await?;
+
+    if attachment.removed_at.is_some() {
+        return Err(RouteError::UnexpectedError(anyhow::anyhow!(
+            "This attachment has already been removed"
+        )));
+    }
+
+    let removed_event = AttachmentRemoved {
+        id: attachment.id,
+        removed_at: Utc::now(),
+    };
+
+    let event = EventData::json(AttachmentRemoved::event_name(), &removed_event)
+        .expect("Unable to serialize")
+        .id(Uuid::new_v4());
+
+    eventstore
+        .append_to_stream(
+            Attachment::stream_name(&attachment.id),
+            &Default::default(),
+            event,
+        )
+        .await
+        .expect("Failed to append event");
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .finish())
+}
@@ -0,0 +1,182 @@
+use actix_web::{web, HttpResponse};
+use chrono::{Duration, Utc};
+use models::{
+    calendar::ics::{IcsCalendar, IcsEvent},
+    projections::{crew_assignment::CrewAssignment, employee::Employee, jobsite::Jobsite},
+    AppState,
+};
+use services::{configuration::ApplicationSettings, signing};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    routes::ApiRoutes,
+    utils::{absolute_url, RouteError},
+    views::{components, TemplateRenderer},
+};
+
+/// How many days of past assignments a feed keeps
+const FEED_HISTORY_DAYS: i64 = 30;
+
+/// What a calendar feed token grants access to
+fn feed_subject(kind: &str, id: &Uuid) -> String {
+    format!("calendar:{}:{}", kind, id)
+}
+
+fn calendar_response(calendar: IcsCalendar) -> HttpResponse {
+    HttpResponse::Ok()
+        .content_type("text/calendar; charset=utf-8")
+        .insert_header(("Cache-Control", "no-cache"))
+        .body(calendar.render())
+}
+
+pub async fn get_employee_calendar_link(
+    db_pool: web::Data<PgPool>,
+    settings: web::Data<ApplicationSettings>,
+    employee_id: web::Path<Uuid>,
+) -> Result<HttpResponse, RouteError> {
+    let employee_id = employee_id.into_inner();
+
+    let mut transaction = db_pool.begin().await?;
+
+    if Employee::get_by_id(&mut transaction, &employee_id)
+        .await?
+        .is_none()
+    {
+        return Err(RouteError::NotFound);
+    }
+
+    transaction.commit().await?;
+
+    let token = signing::sign(
+        &settings.signing_key,
+        &feed_subject("employee", &employee_id),
+    );
+    let url = absolute_url(
+        &settings,
+        &ApiRoutes::get_employee_calendar(employee_id, &token),
+    );
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .body(TemplateRenderer::render(move || {
+            components::calendar::CalendarFeedLink(components::calendar::CalendarFeedLinkProps {
+                url,
+            })
+        })))
+}
+
+pub async fn get_jobsite_calendar_link(
+    db_pool: web::Data<PgPool>,
+    settings: web::Data<ApplicationSettings>,
+    jobsite_id: web::Path<Uuid>,
+) -> Result<HttpResponse, RouteError> {
+    let jobsite_id = jobsite_id.into_inner();
+
+    let mut transaction = db_pool.begin().await?;
+
+    if Jobsite::get_by_id(&mut transaction, &jobsite_id)
+        .await?
+        .is_none()
+    {
+        return Err(RouteError::NotFound);
+    }
+
+    transaction.commit().await?;
+
+    let token = signing::sign(&settings.signing_key, &feed_subject("jobsite", &jobsite_id));
+    let url = absolute_url(
+        &settings,
+        &ApiRoutes::get_jobsite_calendar(jobsite_id, &token),
+    );
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .body(TemplateRenderer::render(move || {
+            components::calendar::CalendarFeedLink(components::calendar::CalendarFeedLinkProps {
+                url,
+            })
+        })))
+}
+
+pub async fn get_employee_calendar(
+    db_pool: web::Data<PgPool>,
+    settings: web::Data<ApplicationSettings>,
+    state: web::Data<AppState>,
+    path: web::Path<(Uuid, String)>,
+) -> Result<HttpResponse, RouteError> {
+    let (employee_id, token) = path.into_inner();
+
+    // A bad token looks the same as a missing employee
+    if !signing::verify(
+        &settings.signing_key,
+        &feed_subject("employee", &employee_id),
+        &token,
+    ) {
+        return Err(RouteError::NotFound);
+    }
+
+    let mut transaction = db_pool.begin().await?;
+
+    let employee = match Employee::get_by_id(&mut transaction, &employee_id).await? {
+        Some(employee) => employee,
+        None => return Err(RouteError::NotFound),
+    };
+
+    let assignments = CrewAssignment::get_list_by_employee_since(
+        &mut transaction,
+        &employee_id,
+        state.today() - Duration::days(FEED_HISTORY_DAYS),
+    )
+    .await?;
+
+    transaction.commit().await?;
+
+    Ok(calendar_response(IcsCalendar::new(
+        format!("{} Schedule", employee.name),
+        assignments
+            .iter()
+            .map(IcsEvent::employee_assignment)
+            .collect(),
+    )))
+}
+
+pub async fn get_jobsite_calendar(
+    db_pool: web::Data<PgPool>,
+    settings: web::Data<ApplicationSettings>,
+    path: web::Path<(Uuid, String)>,
+) -> Result<HttpResponse, RouteError> {
+    let (jobsite_id, token) = path.into_inner();
+
+    if !signing::verify(
+        &settings.signing_key,
+        &feed_subject("jobsite", &jobsite_id),
+        &token,
+    ) {
+        return Err(RouteError::NotFound);
+    }
+
+    let mut transaction = db_pool.begin().await?;
+
+    let jobsite = match Jobsite::get_by_id(&mut transaction, &jobsite_id).await? {
+        Some(jobsite) => jobsite,
+        None => return Err(RouteError::NotFound),
+    };
+
+    let assignments = CrewAssignment::get_list_by_jobsite_since(
+        &mut transaction,
+        &jobsite_id,
+        jobsite.local_date(Utc::now()) - Duration::days(FEED_HISTORY_DAYS),
+    )
+    .await?;
+
+    transaction.commit().await?;
+
+    Ok(calendar_response(IcsCalendar::new(
+        format!("{} Crew", jobsite.name),
+        assignments
+            .iter()
+            .map(IcsEvent::jobsite_assignment)
+            .collect(),
+    )))
+}
@@ -0,0 +1,246 @@
+use actix_web::{web, HttpResponse};
+use chrono::{NaiveDate, Utc};
+use eventstore::EventData;
+use models::{
+    events::{certification::CertificationRecorded, jobsite::JobsiteCertificationsRequired},
+    projections::{
+        certification::{Certification, CertificationType},
+        employee::Employee,
+        jobsite::Jobsite,
+    },
+    AppState,
+};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    utils::{form_choice, ErrorProps, ErrorPropsCollection, RouteError},
+    views::{components, pages, TemplateRenderer},
+};
+
+pub async fn get_certifications_page(
+    db_pool: web::Data<PgPool>,
+    state: web::Data<AppState>,
+) -> Result<HttpResponse, RouteError> {
+    let today = state.today();
+
+    let mut transaction = db_pool.begin().await?;
+
+    let certifications = Certification::get_list(&mut transaction).await?;
+    let alerts = Certification::get_alerts(&mut transaction).await?;
+    let employees = Employee::get_list_active_on(&mut transaction, today).await?;
+
+    transaction.commit().await?;
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .body(TemplateRenderer::render(move || {
+            pages::Certifications(pages::CertificationsProps {
+                certifications,
+                alerts,
+                employees,
+                today,
+            })
+        })))
+}
+
+#[derive(serde::Deserialize)]
+pub struct CertificationData {
+    employee_id: String,
+    certification_type: String,
+    #[serde(default)]
+    certificate_number: String,
+    issued_on: String,
+    #[serde(default)]
+    expires_on: String,
+}
+
+/// Record a certification, recording one the employee already holds renews
+/// it
+pub async fn post_certification(
+    db_pool: web::Data<PgPool>,
+    data: web::Form<CertificationData>,
+    eventstore: web::Data<eventstore::Client>,
+    state: web::Data<AppState>,
+) -> Result<HttpResponse, RouteError> {
+    let mut errors = vec![
+        ErrorProps::new("certification-employee-error".to_string()),
+        ErrorProps::new("certification-date-error".to_string()),
+    ];
+
+    let certification_type = form_choice(
+        CertificationType::ALL,
+        CertificationType::as_str,
+        &data.certification_type,
+        "certification type",
+    )?;
+
+    let Ok(issued_on) = NaiveDate::parse_from_str(&data.issued_on, "%Y-%m-%d") else {
+        errors.set_error("certification-date-error", "An issue date is required")?;
+
+        return Ok(HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(errors.render_errors()?));
+    };
+
+    let expires_on = match data.expires_on.trim() {
+        "" => None,
+        expires_on => match NaiveDate::parse_from_str(expires_on, "%Y-%m-%d") {
+            Ok(expires_on) if expires_on > issued_on => Some(expires_on),
+            _ => {
+                errors.set_error(
+                    "certification-date-error",
+                    "The expiry date has to be after the issue date",
+                )?;
+                None
+            }
+        },
+    };
+
+    if issued_on > state.today() {
+        errors.set_error(
+            "certification-date-error",
+            "Certifications can't be issued in the future",
+        )?;
+    }
+
+    let mut transaction = db_pool.begin().await?;
+
+    let employee = match Uuid::parse_str(&data.employee_id) {
+        Ok(employee_id) => Employee::get_by_id(&mut transaction, &employee_id).await?,
+        Err(_) => None,
+    };
+
+    transaction.commit().await?;
+
+    let Some(employee) = employee else {
+        errors.set_error("certification-employee-error", "Choose an employee")?;
+
+        return Ok(HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(errors.render_errors()?));
+    };
+
+    if errors.has_errors() {
+        return Ok(HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(errors.render_errors()?));
+    }
+
+    let certificate_number = data.certificate_number.trim();
+    let recorded_event = CertificationRecorded {
+        id: Uuid::new_v4(),
+        employee_id: employee.id,
+        certification_type,
+        certificate_number: (!certificate_number.is_empty())
+            .then(|| certificate_number.to_string()),
+        issued_on,
+        expires_on,
+        recorded_at: Utc::now(),
+    };
+
+    let event = EventData::json(CertificationRecorded::event_name(), &recorded_event)
+        .expect("Unable to serialize")
+        .id(Uuid::new_v4());
+
+    eventstore
+        .append_to_stream(
+            Certification::stream_name(&employee.id),
+            &Default::default(),
+            event,
+        )
+        .await
+        .expect("Failed to append event");
+
+    Ok(HttpResponse::Created()
+        .content_type("text/html; charset=utf-8")
+        .body(errors.render_errors()?))
+}
+
+pub async fn get_jobsite_certifications(
+    db_pool: web::Data<PgPool>,
+    jobsite_id: web::Path<Uuid>,
+) -> Result<HttpResponse, RouteError> {
+    let jobsite_id = jobsite_id.into_inner();
+
+    let mut transaction = db_pool.begin().await?;
+
+    if Jobsite::get_by_id(&mut transaction, &jobsite_id)
+        .await?
+        .is_none()
+    {
+        return Err(RouteError::NotFound);
+    }
+
+    let required = Certification::get_required_by_jobsite(&mut transaction, &jobsite_id).await?;
+
+    transaction.commit().await?;
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .body(TemplateRenderer::render(move || {
+            components::certification::JobsiteCertifications(
+                components::certification::JobsiteCertificationsProps {
+                    jobsite_id,
+                    required,
+                },
+            )
+        })))
+}
+
+/// Set the certifications a jobsite requires. The form is read as pairs
+/// since every checked type is sent as its own `certification_type` field.
+pub async fn post_jobsite_certifications(
+    db_pool: web::Data<PgPool>,
+    data: web::Form<Vec<(String, String)>>,
+    eventstore: web::Data<eventstore::Client>,
+    jobsite_id: web::Path<Uuid>,
+) -> Result<HttpResponse, RouteError> {
+    let jobsite_id = jobsite_id.into_inner();
+
+    let certification_types = data
+        .iter()
+        .filter(|(key, _)| key == "certification_type")
+        .map(|(_, value)| {
+            form_choice(
+                CertificationType::ALL,
+                CertificationType::as_str,
+                value,
+                "certification type",
+            )
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut transaction = db_pool.begin().await?;
+
+    if Jobsite::get_by_id(&mut transaction, &jobsite_id)
+        .await?
+        .is_none()
+    {
+        return Err(RouteError::NotFound);
+    }
+
+    transaction.commit().await?;
+
+    let required_event = JobsiteCertificationsRequired {
+        id: jobsite_id,
+        certification_types,
+    };
+
+    let event = EventData::json(JobsiteCertificationsRequired::event_name(), &required_event)
+        .expect("Unable to serialize")
+        .id(Uuid::new_v4());
+
+    eventstore
+        .append_to_stream(
+            format!("jobsite-{}", jobsite_id),
+            &Default::default(),
+            event,
+        )
+        .await
+        .expect("Failed to append event");
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .finish())
+}
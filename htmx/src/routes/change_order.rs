@@ -0,0 +1,333 @@
+use actix_web::{web, HttpResponse};
+use chrono::Utc;
+use eventstore::EventData;
+use leptos::view;
+use models::{
+    events::change_order::{
+        ChangeOrderApproved, ChangeOrderDrafted, ChangeOrderRejected, ChangeOrderSubmitted,
+    },
+    projections::{
+        change_order::{ChangeOrder, ChangeOrderStatus},
+        jobsite::Jobsite,
+    },
+};
+use serde::Serialize;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use super::{jobsite_budget::parse_hours, pay_rate::parse_cents};
+use crate::{
+    utils::{ErrorProps, ErrorPropsCollection, RouteError},
+    views::{components, TemplateRenderer},
+};
+
+pub async fn get_jobsite_change_orders(
+    db_pool: web::Data<PgPool>,
+    jobsite_id: web::Path<Uuid>,
+) -> Result<HttpResponse, RouteError> {
+    let jobsite_id = jobsite_id.into_inner();
+
+    let mut transaction = db_pool.begin().await?;
+
+    if Jobsite::get_by_id(&mut transaction, &jobsite_id)
+        .await?
+        .is_none()
+    {
+        return Err(RouteError::NotFound);
+    }
+
+    let change_orders = ChangeOrder::get_list_by_jobsite(&mut transaction, &jobsite_id).await?;
+
+    transaction.commit().await?;
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .body(TemplateRenderer::render(move || {
+            view! {
+                <components::change_order::JobsiteChangeOrders jobsite_id=jobsite_id change_orders=change_orders />
+                <components::change_order::ChangeOrderCreate jobsite_id=jobsite_id />
+            }
+        })))
+}
+
+/// Parse an amount that may take scope away, e.g. `-1,500`, with `parse`
+fn parse_signed(value: &str, parse: impl Fn(&str) -> Option<i64>) -> Option<i64> {
+    let value = value.trim();
+
+    match value.strip_prefix('-') {
+        Some(value) => parse(value).map(|amount| -amount),
+        None => parse(value),
+    }
+}
+
+async fn append_change_order_event<T: Serialize>(
+    eventstore: &eventstore::Client,
+    change_order_id: &Uuid,
+    event_name: String,
+    event: &T,
+) {
+    let event = EventData::json(event_name, event)
+        .expect("Unable to serialize")
+        .id(Uuid::new_v4());
+
+    eventstore
+        .append_to_stream(
+            ChangeOrder::stream_name(change_order_id),
+            &Default::default(),
+            event,
+        )
+        .await
+        .expect("Failed to append event");
+}
+
+#[derive(serde::Deserialize)]
+pub struct ChangeOrderDraftData {
+    title: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    amount: String,
+    #[serde(default)]
+    hours: String,
+}
+
+pub async fn post_change_order(
+    db_pool: web::Data<PgPool>,
+    jobsite_id: web::Path<Uuid>,
+    data: web::Form<ChangeOrderDraftData>,
+    eventstore: web::Data<eventstore::Client>,
+) -> Result<HttpResponse, RouteError> {
+    let jobsite_id = jobsite_id.into_inner();
+    let title_error = format!("change_order_title_error_{}", jobsite_id);
+    let amount_error = format!("change_order_amount_error_{}", jobsite_id);
+    let hours_error = format!("change_order_hours_error_{}", jobsite_id);
+    let mut errors = vec![
+        ErrorProps::new(title_error.clone()),
+        ErrorProps::new(amount_error.clone()),
+        ErrorProps::new(hours_error.clone()),
+    ];
+
+    let mut transaction = db_pool.begin().await?;
+
+    if Jobsite::get_by_id(&mut transaction, &jobsite_id)
+        .await?
+        .is_none()
+    {
+        return Err(RouteError::NotFound);
+    }
+
+    transaction.commit().await?;
+
+    let title = data.title.trim();
+    if title.is_empty() {
+        errors.set_error(&title_error, "A title is required")?;
+    }
+
+    let amount_cents = match data.amount.trim() {
+        "" => Some(0),
+        amount => {
+            let cents = parse_signed(amount, parse_cents);
+            if cents.is_none() {
+                errors.set_error(&amount_error, "Enter an amount, e.g. 4500 or -1200")?;
+            }
+            cents
+        }
+    };
+
+    let minutes = match data.hours.trim() {
+        "" => Some(0),
+        hours => {
+            let minutes = parse_signed(hours, parse_hours);
+            if minutes.is_none() {
+                errors.set_error(&hours_error, "Enter a number of hours, e.g. 40 or -16")?;
+            }
+            minutes
+        }
+    };
+
+    if amount_cents == Some(0) && minutes == Some(0) && !errors.has_errors() {
+        errors.set_error(
+            &amount_error,
+            "A change order changes the amount, hours, or both",
+        )?;
+    }
+
+    if errors.has_errors() {
+        return Ok(HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(errors.render_errors()?));
+    }
+
+    let (Some(amount_cents), Some(minutes)) = (amount_cents, minutes) else {
+        unreachable!("Amount and hours were validated");
+    };
+
+    let drafted_event = ChangeOrderDrafted {
+        id: Uuid::new_v4(),
+        jobsite_id,
+        title: title.to_string(),
+        description: Some(data.description.trim().to_string())
+            .filter(|description| !description.is_empty()),
+        amount_cents,
+        minutes,
+        drafted_at: Utc::now(),
+    };
+
+    append_change_order_event(
+        &eventstore,
+        &drafted_event.id,
+        ChangeOrderDrafted::event_name(),
+        &drafted_event,
+    )
+    .await;
+
+    Ok(HttpResponse::Created()
+        .content_type("text/html; charset=utf-8")
+        .body(errors.render_errors()?))
+}
+
+/// Load a change order, it has to be at `status` to move on to the next step
+async fn get_change_order_at(
+    db_pool: &PgPool,
+    change_order_id: &Uuid,
+    status: ChangeOrderStatus,
+) -> Result<ChangeOrder, RouteError> {
+    let mut transaction = db_pool.begin().await?;
+
+    let change_order = match ChangeOrder::get_by_id(&mut transaction, change_order_id).await? {
+        Some(change_order) => change_order,
+        None => return Err(RouteError::NotFound),
+    };
+
+    transaction.commit().await?;
+
+    if change_order.status != status {
+        return Err(RouteError::UnexpectedError(anyhow::anyhow!(
+            "This change order is {}, not {}",
+            change_order.status.label().to_lowercase(),
+            status.label().to_lowercase()
+        )));
+    }
+
+    Ok(change_order)
+}
+
+pub async fn post_change_order_submit(
+    db_pool: web::Data<PgPool>,
+    eventstore: web::Data<eventstore::Client>,
+    change_order_id: web::Path<Uuid>,
+) -> Result<HttpResponse, RouteError> {
+    let change_order_id = change_order_id.into_inner();
+
+    get_change_order_at(&db_pool, &change_order_id, ChangeOrderStatus::Drafted).await?;
+
+    let submitted_event = ChangeOrderSubmitted {
+        id: change_order_id,
+        submitted_at: Utc::now(),
+    };
+
+    append_change_order_event(
+        &eventstore,
+        &change_order_id,
+        ChangeOrderSubmitted::event_name(),
+        &submitted_event,
+    )
+    .await;
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .finish())
+}
+
+pub async fn post_change_order_approve(
+    db_pool: web::Data<PgPool>,
+    eventstore: web::Data<eventstore::Client>,
+    change_order_id: web::Path<Uuid>,
+) -> Result<HttpResponse, RouteError> {
+    let change_order_id = change_order_id.into_inner();
+
+    get_change_order_at(&db_pool, &change_order_id, ChangeOrderStatus::Submitted).await?;
+
+    let approved_event = ChangeOrderApproved {
+        id: change_order_id,
+        approved_at: Utc::now(),
+    };
+
+    append_change_order_event(
+        &eventstore,
+        &change_order_id,
+        ChangeOrderApproved::event_name(),
+        &approved_event,
+    )
+    .await;
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .finish())
+}
+
+#[derive(serde::Deserialize)]
+pub struct ChangeOrderRejectData {
+    reason: String,
+}
+
+pub async fn post_change_order_reject(
+    db_pool: web::Data<PgPool>,
+    data: web::Form<ChangeOrderRejectData>,
+    eventstore: web::Data<eventstore::Client>,
+    change_order_id: web::Path<Uuid>,
+) -> Result<HttpResponse, RouteError> {
+    let change_order_id = change_order_id.into_inner();
+
+    let error_id = components::change_order::change_order_action_error_id(&change_order_id);
+    let mut errors = vec![ErrorProps::new(error_id.clone())];
+
+    get_change_order_at(&db_pool, &change_order_id, ChangeOrderStatus::Submitted).await?;
+
+    let reason = data.reason.trim();
+    if reason.is_empty() {
+        errors.set_error(&error_id, "Say why the client turned it down")?;
+
+        return Ok(HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(errors.render_errors()?));
+    }
+
+    let rejected_event = ChangeOrderRejected {
+        id: change_order_id,
+        reason: reason.to_string(),
+        rejected_at: Utc::now(),
+    };
+
+    append_change_order_event(
+        &eventstore,
+        &change_order_id,
+        ChangeOrderRejected::event_name(),
+        &rejected_event,
+    )
+    .await;
+
+    Ok(HttpResponse::Created()
+        .content_type("text/html; charset=utf-8")
+        .body(errors.render_errors()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_cents, parse_hours, parse_signed};
+
+    #[test]
+    fn parses_added_and_removed_scope() {
+        assert_eq!(parse_signed("4,500", parse_cents), Some(450000));
+        assert_eq!(parse_signed(" -$1,200.50", parse_cents), Some(-120050));
+        assert_eq!(parse_signed("-16", parse_hours), Some(-16 * 60));
+        assert_eq!(parse_signed("7.5", parse_hours), Some(450));
+    }
+
+    #[test]
+    fn rejects_doubled_signs() {
+        assert_eq!(parse_signed("--5", parse_cents), None);
+        assert_eq!(parse_signed("--5", parse_hours), None);
+        assert_eq!(parse_signed("-", parse_cents), None);
+    }
+}
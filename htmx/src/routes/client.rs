@@ -0,0 +1,91 @@
+use actix_web::{web, HttpResponse};
+use eventstore::EventData;
+use models::{
+    events::client::ClientCreated,
+    projections::{client::Client, project::Project},
+};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    utils::{ErrorProps, ErrorPropsCollection, RouteError},
+    views::{pages, TemplateRenderer},
+};
+
+pub async fn get_clients_page(db_pool: web::Data<PgPool>) -> Result<HttpResponse, RouteError> {
+    let mut transaction = db_pool.begin().await?;
+
+    let clients = Client::get_list(&mut transaction).await?;
+    let projects = Project::get_list(&mut transaction).await?;
+
+    transaction.commit().await?;
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .body(TemplateRenderer::render(move || {
+            pages::Clients(pages::ClientsProps { clients, projects })
+        })))
+}
+
+#[derive(serde::Deserialize)]
+pub struct ClientCreateData {
+    name: String,
+}
+
+pub async fn post_client(
+    db_pool: web::Data<PgPool>,
+    data: web::Form<ClientCreateData>,
+    eventstore: web::Data<eventstore::Client>,
+) -> Result<HttpResponse, RouteError> {
+    let mut errors = vec![ErrorProps::new("client-name-error".to_string())];
+
+    let name = data.name.trim().to_string();
+
+    if name.is_empty() {
+        errors.set_error("client-name-error", "A name is required")?;
+
+        return Ok(HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(errors.render_errors()?));
+    }
+
+    let mut transaction = db_pool.begin().await?;
+
+    if Client::get_by_name(&mut transaction, &name)
+        .await?
+        .is_some()
+    {
+        errors.set_error(
+            "client-name-error",
+            "A client already exists with this name",
+        )?;
+
+        return Ok(HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(errors.render_errors()?));
+    }
+
+    transaction.commit().await?;
+
+    let create_event = ClientCreated {
+        id: Uuid::new_v4(),
+        name,
+    };
+
+    let event = EventData::json(ClientCreated::event_name(), &create_event)
+        .expect("Unable to serialize")
+        .id(Uuid::new_v4());
+
+    eventstore
+        .append_to_stream(
+            format!("client-{}", create_event.id),
+            &Default::default(),
+            event,
+        )
+        .await
+        .expect("Failed to append event");
+
+    Ok(HttpResponse::Created()
+        .content_type("text/html; charset=utf-8")
+        .body(errors.render_errors()?))
+}
@@ -0,0 +1,307 @@
+use actix_web::{web, HttpResponse};
+use chrono::Utc;
+use eventstore::EventData;
+use leptos::view;
+use models::{
+    events::comment::{CommentDeleted, CommentEdited, CommentMetadata, CommentPosted},
+    projections::{
+        comment::{find_mentions, Comment, MAX_COMMENT_LENGTH},
+        employee::Employee,
+        jobsite::Jobsite,
+    },
+};
+use serde::Serialize;
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::{
+    utils::{ErrorProps, ErrorPropsCollection, RouteError},
+    views::{components, TemplateRenderer},
+};
+
+pub async fn get_jobsite_comments(
+    db_pool: web::Data<PgPool>,
+    jobsite_id: web::Path<Uuid>,
+) -> Result<HttpResponse, RouteError> {
+    let jobsite_id = jobsite_id.into_inner();
+
+    let mut transaction = db_pool.begin().await?;
+
+    let jobsite = match Jobsite::get_by_id(&mut transaction, &jobsite_id).await? {
+        Some(jobsite) => jobsite,
+        None => return Err(RouteError::NotFound),
+    };
+
+    let comments = Comment::get_list_by_jobsite(&mut transaction, &jobsite_id).await?;
+    let employees =
+        Employee::get_list_active_on(&mut transaction, jobsite.local_date(Utc::now())).await?;
+
+    transaction.commit().await?;
+
+    let time_zone = jobsite.tz();
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .body(TemplateRenderer::render(move || {
+            view! {
+                <components::comment::JobsiteComments
+                    jobsite_id=jobsite_id
+                    time_zone=time_zone
+                    comments=comments
+                    employees=employees.clone()
+                />
+                <components::comment::CommentCreate jobsite_id=jobsite_id employees=employees />
+            }
+        })))
+}
+
+async fn append_comment_event<T: Serialize>(
+    eventstore: &eventstore::Client,
+    comment_id: &Uuid,
+    author_id: Uuid,
+    event_name: String,
+    event: &T,
+) {
+    let event = EventData::json(event_name, event)
+        .expect("Unable to serialize")
+        .id(Uuid::new_v4())
+        .metadata_as_json(CommentMetadata { author_id })
+        .expect("Unable to serialize");
+
+    eventstore
+        .append_to_stream(Comment::stream_name(comment_id), &Default::default(), event)
+        .await
+        .expect("Failed to append event");
+}
+
+/// The employee writing, who must still be with the company, along with
+/// everyone who can be mentioned
+async fn comment_author(
+    transaction: &mut Transaction<'_, Postgres>,
+    jobsite: &Jobsite,
+    author_id: &str,
+) -> Result<(Option<Uuid>, Vec<Employee>), RouteError> {
+    let employees =
+        Employee::get_list_active_on(transaction, jobsite.local_date(Utc::now())).await?;
+
+    let author_id = Uuid::parse_str(author_id)
+        .ok()
+        .filter(|author_id| employees.iter().any(|employee| employee.id == *author_id));
+
+    Ok((author_id, employees))
+}
+
+/// The comment's text, if there's something to post and it isn't too long
+fn comment_body(body: &str) -> Result<String, String> {
+    let body = body.trim();
+
+    if body.is_empty() {
+        return Err("Write something to post".to_string());
+    }
+
+    if body.chars().count() > MAX_COMMENT_LENGTH {
+        return Err(format!(
+            "Comments can be at most {} characters",
+            MAX_COMMENT_LENGTH
+        ));
+    }
+
+    Ok(body.to_string())
+}
+
+#[derive(serde::Deserialize)]
+pub struct CommentData {
+    #[serde(default)]
+    author_id: String,
+    #[serde(default)]
+    body: String,
+}
+
+pub async fn post_jobsite_comment(
+    db_pool: web::Data<PgPool>,
+    jobsite_id: web::Path<Uuid>,
+    data: web::Form<CommentData>,
+    eventstore: web::Data<eventstore::Client>,
+) -> Result<HttpResponse, RouteError> {
+    let jobsite_id = jobsite_id.into_inner();
+    let error_id = format!("comment_error_{}", jobsite_id);
+    let mut errors = vec![ErrorProps::new(error_id.clone())];
+
+    let mut transaction = db_pool.begin().await?;
+
+    let jobsite = match Jobsite::get_by_id(&mut transaction, &jobsite_id).await? {
+        Some(jobsite) => jobsite,
+        None => return Err(RouteError::NotFound),
+    };
+
+    let (author_id, employees) =
+        comment_author(&mut transaction, &jobsite, &data.author_id).await?;
+
+    transaction.commit().await?;
+
+    let Some(author_id) = author_id else {
+        errors.set_error(&error_id, "Choose who is posting")?;
+
+        return Ok(HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(errors.render_errors()?));
+    };
+
+    let body = match comment_body(&data.body) {
+        Ok(body) => body,
+        Err(error) => {
+            errors.set_error(&error_id, &error)?;
+
+            return Ok(HttpResponse::Ok()
+                .content_type("text/html; charset=utf-8")
+                .body(errors.render_errors()?));
+        }
+    };
+
+    let posted_event = CommentPosted {
+        id: Uuid::new_v4(),
+        jobsite_id,
+        mentions: find_mentions(&body, &employees),
+        body,
+        posted_at: Utc::now(),
+    };
+
+    append_comment_event(
+        &eventstore,
+        &posted_event.id,
+        author_id,
+        CommentPosted::event_name(),
+        &posted_event,
+    )
+    .await;
+
+    Ok(HttpResponse::Created()
+        .content_type("text/html; charset=utf-8")
+        .body(errors.render_errors()?))
+}
+
+/// Load a comment that hasn't been deleted, along with its jobsite
+async fn get_open_comment(
+    transaction: &mut Transaction<'_, Postgres>,
+    comment_id: &Uuid,
+) -> Result<(Comment, Jobsite), RouteError> {
+    let comment = match Comment::get_by_id(transaction, comment_id).await? {
+        Some(comment) if !comment.is_deleted() => comment,
+        _ => return Err(RouteError::NotFound),
+    };
+
+    let jobsite = match Jobsite::get_by_id(transaction, &comment.jobsite_id).await? {
+        Some(jobsite) => jobsite,
+        None => return Err(RouteError::NotFound),
+    };
+
+    Ok((comment, jobsite))
+}
+
+pub async fn post_comment_edit(
+    db_pool: web::Data<PgPool>,
+    comment_id: web::Path<Uuid>,
+    data: web::Form<CommentData>,
+    eventstore: web::Data<eventstore::Client>,
+) -> Result<HttpResponse, RouteError> {
+    let comment_id = comment_id.into_inner();
+    let error_id = components::comment::comment_action_error_id(&comment_id);
+    let mut errors = vec![ErrorProps::new(error_id.clone())];
+
+    let mut transaction = db_pool.begin().await?;
+
+    let (comment, jobsite) = get_open_comment(&mut transaction, &comment_id).await?;
+    let (author_id, employees) =
+        comment_author(&mut transaction, &jobsite, &data.author_id).await?;
+
+    transaction.commit().await?;
+
+    let Some(author_id) = author_id.filter(|author_id| comment.is_written_by(author_id)) else {
+        errors.set_error(&error_id, "Only the author can edit this comment")?;
+
+        return Ok(HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(errors.render_errors()?));
+    };
+
+    let body = match comment_body(&data.body) {
+        Ok(body) => body,
+        Err(error) => {
+            errors.set_error(&error_id, &error)?;
+
+            return Ok(HttpResponse::Ok()
+                .content_type("text/html; charset=utf-8")
+                .body(errors.render_errors()?));
+        }
+    };
+
+    let edited_event = CommentEdited {
+        id: comment_id,
+        mentions: find_mentions(&body, &employees),
+        body,
+        edited_at: Utc::now(),
+    };
+
+    append_comment_event(
+        &eventstore,
+        &comment_id,
+        author_id,
+        CommentEdited::event_name(),
+        &edited_event,
+    )
+    .await;
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .body(errors.render_errors()?))
+}
+
+#[derive(serde::Deserialize)]
+pub struct CommentDeleteData {
+    #[serde(default)]
+    author_id: String,
+}
+
+pub async fn post_comment_delete(
+    db_pool: web::Data<PgPool>,
+    comment_id: web::Path<Uuid>,
+    data: web::Form<CommentDeleteData>,
+    eventstore: web::Data<eventstore::Client>,
+) -> Result<HttpResponse, RouteError> {
+    let comment_id = comment_id.into_inner();
+    let error_id = components::comment::comment_action_error_id(&comment_id);
+    let mut errors = vec![ErrorProps::new(error_id.clone())];
+
+    let mut transaction = db_pool.begin().await?;
+
+    let (comment, jobsite) = get_open_comment(&mut transaction, &comment_id).await?;
+    let (author_id, _) = comment_author(&mut transaction, &jobsite, &data.author_id).await?;
+
+    transaction.commit().await?;
+
+    let Some(author_id) = author_id.filter(|author_id| comment.is_written_by(author_id)) else {
+        errors.set_error(&error_id, "Only the author can delete this comment")?;
+
+        return Ok(HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(errors.render_errors()?));
+    };
+
+    let deleted_event = CommentDeleted {
+        id: comment_id,
+        deleted_at: Utc::now(),
+    };
+
+    append_comment_event(
+        &eventstore,
+        &comment_id,
+        author_id,
+        CommentDeleted::event_name(),
+        &deleted_event,
+    )
+    .await;
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .body(errors.render_errors()?))
+}
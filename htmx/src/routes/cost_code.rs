@@ -0,0 +1,184 @@
+use actix_web::{web, HttpResponse};
+use chrono::Utc;
+use eventstore::EventData;
+use leptos::view;
+use models::{
+    events::cost_code::{CostCodeAdded, CostCodeRetired},
+    projections::{cost_code::CostCode, jobsite::Jobsite},
+};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    utils::{ErrorProps, ErrorPropsCollection, RouteError},
+    views::{components, TemplateRenderer},
+};
+
+pub async fn get_jobsite_cost_codes(
+    db_pool: web::Data<PgPool>,
+    jobsite_id: web::Path<Uuid>,
+) -> Result<HttpResponse, RouteError> {
+    let jobsite_id = jobsite_id.into_inner();
+
+    let mut transaction = db_pool.begin().await?;
+
+    if Jobsite::get_by_id(&mut transaction, &jobsite_id)
+        .await?
+        .is_none()
+    {
+        return Err(RouteError::NotFound);
+    }
+
+    let cost_codes = CostCode::get_hours_by_jobsite(&mut transaction, &jobsite_id).await?;
+    let uncoded_minutes = CostCode::get_uncoded_minutes(&mut transaction, &jobsite_id).await?;
+    let parents = CostCode::get_active_by_jobsite(&mut transaction, &jobsite_id).await?;
+
+    transaction.commit().await?;
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .body(TemplateRenderer::render(move || {
+            view! {
+                <components::cost_code::JobsiteCostCodes
+                    jobsite_id=jobsite_id
+                    cost_codes=cost_codes
+                    uncoded_minutes=uncoded_minutes
+                />
+                <components::cost_code::CostCodeCreate jobsite_id=jobsite_id parents=parents />
+            }
+        })))
+}
+
+#[derive(serde::Deserialize)]
+pub struct CostCodeAddData {
+    code: String,
+    name: String,
+    parent_id: String,
+}
+
+pub async fn post_cost_code(
+    db_pool: web::Data<PgPool>,
+    jobsite_id: web::Path<Uuid>,
+    data: web::Form<CostCodeAddData>,
+    eventstore: web::Data<eventstore::Client>,
+) -> Result<HttpResponse, RouteError> {
+    let jobsite_id = jobsite_id.into_inner();
+    let code_error = format!("cost_code_code_error_{}", jobsite_id);
+    let name_error = format!("cost_code_name_error_{}", jobsite_id);
+    let mut errors = vec![
+        ErrorProps::new(code_error.clone()),
+        ErrorProps::new(name_error.clone()),
+    ];
+
+    let code = data.code.trim().to_string();
+    let name = data.name.trim().to_string();
+    let parent_id = Uuid::parse_str(&data.parent_id).ok();
+
+    let mut transaction = db_pool.begin().await?;
+
+    if Jobsite::get_by_id(&mut transaction, &jobsite_id)
+        .await?
+        .is_none()
+    {
+        return Err(RouteError::NotFound);
+    }
+
+    if code.is_empty() {
+        errors.set_error(&code_error, "A code is required")?;
+    } else if CostCode::get_by_code(&mut transaction, &jobsite_id, &code)
+        .await?
+        .is_some()
+    {
+        errors.set_error(&code_error, "This jobsite already has this code")?;
+    }
+
+    if name.is_empty() {
+        errors.set_error(&name_error, "A name is required")?;
+    }
+
+    if let Some(parent_id) = parent_id {
+        match CostCode::get_by_id(&mut transaction, &parent_id).await? {
+            Some(parent) if parent.jobsite_id == jobsite_id && !parent.is_retired() => {}
+            _ => errors.set_error(&code_error, "Tasks can only be added under an open code")?,
+        }
+    }
+
+    transaction.commit().await?;
+
+    if errors.has_errors() {
+        return Ok(HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(errors.render_errors()?));
+    }
+
+    let added_event = CostCodeAdded {
+        id: Uuid::new_v4(),
+        jobsite_id,
+        parent_id,
+        code,
+        name,
+        added_at: Utc::now(),
+    };
+
+    let event = EventData::json(CostCodeAdded::event_name(), &added_event)
+        .expect("Unable to serialize")
+        .id(Uuid::new_v4());
+
+    eventstore
+        .append_to_stream(
+            format!("cost_code-{}", added_event.id),
+            &Default::default(),
+            event,
+        )
+        .await
+        .expect("Failed to append event");
+
+    Ok(HttpResponse::Created()
+        .content_type("text/html; charset=utf-8")
+        .body(errors.render_errors()?))
+}
+
+pub async fn post_cost_code_retire(
+    db_pool: web::Data<PgPool>,
+    cost_code_id: web::Path<Uuid>,
+    eventstore: web::Data<eventstore::Client>,
+) -> Result<HttpResponse, RouteError> {
+    let cost_code_id = cost_code_id.into_inner();
+
+    let mut transaction = db_pool.begin().await?;
+
+    let cost_code = match CostCode::get_by_id(&mut transaction, &cost_code_id).await? {
+        Some(cost_code) => cost_code,
+        None => return Err(RouteError::NotFound),
+    };
+
+    transaction.commit().await?;
+
+    if cost_code.is_retired() {
+        return Err(RouteError::UnexpectedError(anyhow::anyhow!(
+            "This cost code has already been retired"
+        )));
+    }
+
+    let retired_event = CostCodeRetired {
+        id: cost_code_id,
+        retired_at: Utc::now(),
+    };
+
+    let event = EventData::json(CostCodeRetired::event_name(), &retired_event)
+        .expect("Unable to serialize")
+        .id(Uuid::new_v4());
+
+    eventstore
+        .append_to_stream(
+            format!("cost_code-{}", cost_code_id),
+            &Default::default(),
+            event,
+        )
+        .await
+        .expect("Failed to append event");
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .finish())
+}
@@ -0,0 +1,371 @@
+use actix_web::{web, HttpResponse};
+use chrono::{NaiveDate, Utc};
+use eventstore::EventData;
+use models::{
+    events::crew_assignment::{CrewMemberAssigned, CrewMemberUnassigned},
+    projections::{
+        certification::{Certification, CertificationType},
+        crew_assignment::{CrewAssignment, CrewWeek},
+        employee::Employee,
+        jobsite::Jobsite,
+        leave::{Leave, LeaveStatus},
+        timesheet::Timesheet,
+    },
+    AppState,
+};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    utils::{ErrorProps, ErrorPropsCollection, RouteError},
+    views::{pages, TemplateRenderer},
+};
+
+/// Longest range a single assignment can cover
+const MAX_ASSIGNMENT_DAYS: i64 = 366;
+
+pub async fn get_schedule_page(
+    db_pool: web::Data<PgPool>,
+    state: web::Data<AppState>,
+) -> Result<HttpResponse, RouteError> {
+    render_schedule_page(db_pool, Timesheet::week_start_of(state.today())).await
+}
+
+pub async fn get_schedule_week_page(
+    db_pool: web::Data<PgPool>,
+    week_start: web::Path<NaiveDate>,
+) -> Result<HttpResponse, RouteError> {
+    render_schedule_page(db_pool, Timesheet::week_start_of(week_start.into_inner())).await
+}
+
+async fn render_schedule_page(
+    db_pool: web::Data<PgPool>,
+    week_start: NaiveDate,
+) -> Result<HttpResponse, RouteError> {
+    let mut transaction = db_pool.begin().await?;
+
+    let employees = Employee::get_list(&mut transaction).await?;
+    let assignments = CrewAssignment::get_week(&mut transaction, week_start).await?;
+    let leave = Leave::get_approved_week(&mut transaction, week_start).await?;
+    let available = Employee::get_list_active_on(&mut transaction, week_start).await?;
+    let jobsites = Jobsite::get_list(&mut transaction).await?;
+
+    transaction.commit().await?;
+
+    let week = CrewWeek::new(week_start, employees, assignments, leave);
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .body(TemplateRenderer::render(move || {
+            pages::Schedule(pages::ScheduleProps {
+                week,
+                employees: available,
+                jobsites,
+            })
+        })))
+}
+
+#[derive(serde::Deserialize)]
+pub struct CrewAssignmentData {
+    employee_id: String,
+    jobsite_id: String,
+    start_date: String,
+    end_date: String,
+}
+
+pub async fn post_crew_assignment(
+    db_pool: web::Data<PgPool>,
+    data: web::Form<CrewAssignmentData>,
+    eventstore: web::Data<eventstore::Client>,
+) -> Result<HttpResponse, RouteError> {
+    let mut errors = vec![
+        ErrorProps::new("crew-assignment-target-error".to_string()),
+        ErrorProps::new("crew-assignment-date-error".to_string()),
+        ErrorProps::new("crew-assignment-certification-warning".to_string()),
+    ];
+
+    let start_date = NaiveDate::parse_from_str(&data.start_date, "%Y-%m-%d").ok();
+    let end_date = NaiveDate::parse_from_str(&data.end_date, "%Y-%m-%d")
+        .ok()
+        .or(start_date);
+
+    let (Some(start_date), Some(end_date)) = (start_date, end_date) else {
+        errors.set_error("crew-assignment-date-error", "A start date is required")?;
+
+        return Ok(HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(errors.render_errors()?));
+    };
+
+    if end_date < start_date {
+        errors.set_error(
+            "crew-assignment-date-error",
+            "The end date can't be before the start date",
+        )?;
+    } else if (end_date - start_date).num_days() >= MAX_ASSIGNMENT_DAYS {
+        errors.set_error(
+            "crew-assignment-date-error",
+            "Assignments can cover at most a year",
+        )?;
+    }
+
+    let mut transaction = db_pool.begin().await?;
+
+    let employee = match Uuid::parse_str(&data.employee_id) {
+        Ok(employee_id) => Employee::get_by_id(&mut transaction, &employee_id).await?,
+        Err(_) => None,
+    };
+    let jobsite = match Uuid::parse_str(&data.jobsite_id) {
+        Ok(jobsite_id) => Jobsite::get_by_id(&mut transaction, &jobsite_id)
+            .await?
+            .filter(|jobsite| jobsite.merged_into_id.is_none() && jobsite.archived_at.is_none()),
+        Err(_) => None,
+    };
+
+    let (Some(employee), Some(jobsite)) = (employee, jobsite) else {
+        errors.set_error(
+            "crew-assignment-target-error",
+            "Choose an employee and a jobsite",
+        )?;
+
+        return Ok(HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(errors.render_errors()?));
+    };
+
+    if let Some(terminated_on) = employee
+        .terminated_on
+        .filter(|terminated_on| *terminated_on <= end_date)
+    {
+        errors.set_error(
+            "crew-assignment-target-error",
+            &format!(
+                "{} was terminated on {}",
+                employee.name,
+                terminated_on.format("%b %-d, %Y")
+            ),
+        )?;
+    }
+
+    if let Some(booked) =
+        CrewAssignment::get_overlapping(&mut transaction, &employee.id, start_date, end_date)
+            .await?
+            .first()
+    {
+        errors.set_error(
+            "crew-assignment-target-error",
+            &format!(
+                "{} is already scheduled at {} from {} to {}",
+                employee.name,
+                booked.jobsite_name,
+                booked.start_date.format("%b %-d"),
+                booked.end_date.format("%b %-d")
+            ),
+        )?;
+    }
+
+    // Approved leave blocks the assignment, pending requests are left for
+    // the supervisor to weigh up when reviewing them
+    if let Some(leave) =
+        Leave::get_overlapping(&mut transaction, &employee.id, start_date, end_date)
+            .await?
+            .iter()
+            .find(|leave| leave.status == LeaveStatus::Approved)
+    {
+        errors.set_error(
+            "crew-assignment-target-error",
+            &format!(
+                "{} is on {} leave from {} to {}",
+                employee.name,
+                leave.leave_type.label().to_lowercase(),
+                leave.start_date.format("%b %-d"),
+                leave.end_date.format("%b %-d")
+            ),
+        )?;
+    }
+
+    let missing = Certification::get_missing_for_assignment(
+        &mut transaction,
+        &employee.id,
+        &jobsite.id,
+        start_date,
+        end_date,
+    )
+    .await?;
+
+    transaction.commit().await?;
+
+    if errors.has_errors() {
+        return Ok(HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(errors.render_errors()?));
+    }
+
+    // Missing certifications don't block the assignment, someone may be
+    // booked on their course already, but the scheduler should know
+    if !missing.is_empty() {
+        errors.set_error(
+            "crew-assignment-certification-warning",
+            &format!(
+                "Assigned, but {} won't hold {} required at {}",
+                employee.name,
+                missing
+                    .iter()
+                    .map(CertificationType::label)
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                jobsite.name
+            ),
+        )?;
+    }
+
+    let assigned_event = CrewMemberAssigned {
+        id: Uuid::new_v4(),
+        employee_id: employee.id,
+        jobsite_id: jobsite.id,
+        start_date,
+        end_date,
+        assigned_at: Utc::now(),
+    };
+
+    let event = EventData::json(CrewMemberAssigned::event_name(), &assigned_event)
+        .expect("Unable to serialize")
+        .id(Uuid::new_v4());
+
+    eventstore
+        .append_to_stream(
+            CrewAssignment::stream_name(&assigned_event.employee_id),
+            &Default::default(),
+            event,
+        )
+        .await
+        .expect("Failed to append event");
+
+    Ok(HttpResponse::Created()
+        .content_type("text/html; charset=utf-8")
+        .body(errors.render_errors()?))
+}
+
+pub async fn post_crew_assignment_unassign(
+    db_pool: web::Data<PgPool>,
+    eventstore: web::Data<eventstore::Client>,
+    assignment_id: web::Path<Uuid>,
+) -> Result<HttpResponse, RouteError> {
+    let mut transaction = db_pool.begin().await?;
+
+    let assignment = match CrewAssignment::get_by_id(&mut transaction, &assignment_id).await? {
+        Some(assignment) => assignment,
+        None => return Err(RouteError::NotFound),
+    };
+
+    transaction.commit().await?;
+
+    if assignment.unassigned_at.is_some() {
+        return Err(RouteError::UnexpectedError(anyhow::anyhow!(
+            "This crew member has already been unassigned"
+        )));
+    }
+
+    let unassigned_event = CrewMemberUnassigned {
+        id: assignment.id,
+        employee_id: assignment.employee_id,
+        unassigned_at: Utc::now(),
+    };
+
+    let event = EventData::json(CrewMemberUnassigned::event_name(), &unassigned_event)
+        .expect("Unable to serialize")
+        .id(Uuid::new_v4());
+
+    eventstore
+        .append_to_stream(
+            CrewAssignment::stream_name(&assignment.employee_id),
+            &Default::default(),
+            event,
+        )
+        .await
+        .expect("Failed to append event");
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .finish())
+}
+
+#[derive(serde::Deserialize)]
+pub struct CrewAssignmentMoveData {
+    jobsite_id: Uuid,
+}
+
+/// Move a crew member to another jobsite for the same days, the old
+/// assignment is closed and the new one opened in a single append
+pub async fn post_crew_assignment_move(
+    db_pool: web::Data<PgPool>,
+    data: web::Form<CrewAssignmentMoveData>,
+    eventstore: web::Data<eventstore::Client>,
+    assignment_id: web::Path<Uuid>,
+) -> Result<HttpResponse, RouteError> {
+    let mut transaction = db_pool.begin().await?;
+
+    let assignment = match CrewAssignment::get_by_id(&mut transaction, &assignment_id).await? {
+        Some(assignment) => assignment,
+        None => return Err(RouteError::NotFound),
+    };
+
+    if Jobsite::get_by_id(&mut transaction, &data.jobsite_id)
+        .await?
+        .filter(|jobsite| jobsite.merged_into_id.is_none() && jobsite.archived_at.is_none())
+        .is_none()
+    {
+        return Err(RouteError::NotFound);
+    }
+
+    transaction.commit().await?;
+
+    if assignment.unassigned_at.is_some() {
+        return Err(RouteError::UnexpectedError(anyhow::anyhow!(
+            "This crew member has already been unassigned"
+        )));
+    }
+
+    if assignment.jobsite_id == data.jobsite_id {
+        return Ok(HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .finish());
+    }
+
+    let moved_at = Utc::now();
+    let unassigned_event = CrewMemberUnassigned {
+        id: assignment.id,
+        employee_id: assignment.employee_id,
+        unassigned_at: moved_at,
+    };
+    let assigned_event = CrewMemberAssigned {
+        id: Uuid::new_v4(),
+        employee_id: assignment.employee_id,
+        jobsite_id: data.jobsite_id,
+        start_date: assignment.start_date,
+        end_date: assignment.end_date,
+        assigned_at: moved_at,
+    };
+
+    let events = vec![
+        EventData::json(CrewMemberUnassigned::event_name(), &unassigned_event)
+            .expect("Unable to serialize")
+            .id(Uuid::new_v4()),
+        EventData::json(CrewMemberAssigned::event_name(), &assigned_event)
+            .expect("Unable to serialize")
+            .id(Uuid::new_v4()),
+    ];
+
+    eventstore
+        .append_to_stream(
+            CrewAssignment::stream_name(&assignment.employee_id),
+            &Default::default(),
+            events,
+        )
+        .await
+        .expect("Failed to append event");
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .finish())
+}
@@ -0,0 +1,304 @@
+use actix_web::{web, HttpResponse};
+use chrono::{NaiveDate, Utc};
+use eventstore::EventData;
+use models::{
+    commands::daily_log::{self, DailyLogLocked},
+    events::daily_log::{DailyLogDelayRecorded, DailyLogSaved, DailyLogSubmitted},
+    projections::{
+        attachment::Attachment,
+        daily_log::{DailyLog, DailyLogDelay, DelayCause, WeatherCondition},
+        jobsite::Jobsite,
+    },
+};
+use serde::Serialize;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    utils::{form_choice, ErrorProps, ErrorPropsCollection, RouteError},
+    views::{pages, TemplateRenderer},
+};
+
+pub async fn get_daily_logs_page(
+    db_pool: web::Data<PgPool>,
+    jobsite_id: web::Path<Uuid>,
+) -> Result<HttpResponse, RouteError> {
+    let jobsite_id = jobsite_id.into_inner();
+
+    let mut transaction = db_pool.begin().await?;
+
+    let jobsite = match Jobsite::get_by_id(&mut transaction, &jobsite_id).await? {
+        Some(jobsite) => jobsite,
+        None => return Err(RouteError::NotFound),
+    };
+
+    let logs = DailyLog::get_list_by_jobsite(&mut transaction, &jobsite_id).await?;
+    let delays = DailyLogDelay::get_list_by_jobsite(&mut transaction, &jobsite_id).await?;
+    let attachments = Attachment::get_list_by_daily_logs(&mut transaction, &jobsite_id).await?;
+
+    transaction.commit().await?;
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .body(TemplateRenderer::render(move || {
+            let today = jobsite.local_date(Utc::now());
+
+            pages::DailyLogs(pages::DailyLogsProps {
+                jobsite,
+                logs,
+                delays,
+                attachments,
+                today,
+            })
+        })))
+}
+
+/// Record an event on the day's log, turned away if the day has been
+/// submitted even when the read models don't show it yet
+async fn append_daily_log_event<T: Serialize>(
+    eventstore: &eventstore::Client,
+    jobsite_id: &Uuid,
+    log_date: &NaiveDate,
+    event_name: String,
+    event: &T,
+) -> Result<Result<(), DailyLogLocked>, RouteError> {
+    let event = EventData::json(event_name, event).expect("Unable to serialize");
+
+    Ok(daily_log::append(eventstore, jobsite_id, log_date, event, Uuid::new_v4()).await?)
+}
+
+#[derive(serde::Deserialize)]
+pub struct DailyLogData {
+    log_date: String,
+    crew_count: String,
+    weather: String,
+    notes: String,
+}
+
+/// Write a day's log, saving a day that's already logged rewrites it
+pub async fn post_daily_log(
+    db_pool: web::Data<PgPool>,
+    data: web::Form<DailyLogData>,
+    eventstore: web::Data<eventstore::Client>,
+    jobsite_id: web::Path<Uuid>,
+) -> Result<HttpResponse, RouteError> {
+    let jobsite_id = jobsite_id.into_inner();
+
+    let mut errors = vec![
+        ErrorProps::new("daily-log-date-error".to_string()),
+        ErrorProps::new("daily-log-crew-error".to_string()),
+    ];
+
+    let Ok(log_date) = NaiveDate::parse_from_str(&data.log_date, "%Y-%m-%d") else {
+        errors.set_error("daily-log-date-error", "A date is required")?;
+
+        return Ok(HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(errors.render_errors()?));
+    };
+
+    let crew_count = match data.crew_count.trim().parse::<i32>() {
+        Ok(crew_count) if crew_count >= 0 => crew_count,
+        _ => {
+            errors.set_error("daily-log-crew-error", "Crew count must be a whole number")?;
+            0
+        }
+    };
+
+    let weather = form_choice(
+        WeatherCondition::ALL,
+        WeatherCondition::as_str,
+        &data.weather,
+        "weather",
+    )?;
+
+    let mut transaction = db_pool.begin().await?;
+
+    let jobsite = match Jobsite::get_by_id(&mut transaction, &jobsite_id).await? {
+        Some(jobsite) => jobsite,
+        None => return Err(RouteError::NotFound),
+    };
+
+    // Days end at midnight where the jobsite is
+    if log_date > jobsite.local_date(Utc::now()) {
+        errors.set_error("daily-log-date-error", "Days can't be logged ahead of time")?;
+    }
+
+    if let Some(daily_log) = DailyLog::get(&mut transaction, &jobsite_id, &log_date).await? {
+        if daily_log.is_locked() {
+            errors.set_error(
+                "daily-log-date-error",
+                "This day's log has been submitted and can no longer be changed",
+            )?;
+        }
+    }
+
+    transaction.commit().await?;
+
+    if errors.has_errors() {
+        return Ok(HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(errors.render_errors()?));
+    }
+
+    let notes = data.notes.trim();
+    let saved_event = DailyLogSaved {
+        jobsite_id,
+        log_date,
+        crew_count,
+        weather,
+        notes: (!notes.is_empty()).then(|| notes.to_string()),
+        saved_at: Utc::now(),
+    };
+
+    if let Err(locked) = append_daily_log_event(
+        &eventstore,
+        &jobsite_id,
+        &log_date,
+        DailyLogSaved::event_name(),
+        &saved_event,
+    )
+    .await?
+    {
+        errors.set_error("daily-log-date-error", &locked.to_string())?;
+
+        return Ok(HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(errors.render_errors()?));
+    }
+
+    Ok(HttpResponse::Created()
+        .content_type("text/html; charset=utf-8")
+        .body(errors.render_errors()?))
+}
+
+/// Load a logged day, it has to exist and still be open for changes
+async fn get_open_daily_log(
+    db_pool: &PgPool,
+    jobsite_id: &Uuid,
+    log_date: &NaiveDate,
+) -> Result<DailyLog, RouteError> {
+    let mut transaction = db_pool.begin().await?;
+
+    let daily_log = match DailyLog::get(&mut transaction, jobsite_id, log_date).await? {
+        Some(daily_log) => daily_log,
+        None => return Err(RouteError::NotFound),
+    };
+
+    transaction.commit().await?;
+
+    if daily_log.is_locked() {
+        return Err(RouteError::UnexpectedError(anyhow::anyhow!(
+            "This day's log has already been submitted"
+        )));
+    }
+
+    Ok(daily_log)
+}
+
+#[derive(serde::Deserialize)]
+pub struct DailyLogDelayData {
+    cause: String,
+    hours: String,
+    reason: String,
+}
+
+pub async fn post_daily_log_delay(
+    db_pool: web::Data<PgPool>,
+    data: web::Form<DailyLogDelayData>,
+    eventstore: web::Data<eventstore::Client>,
+    path: web::Path<(Uuid, NaiveDate)>,
+) -> Result<HttpResponse, RouteError> {
+    let (jobsite_id, log_date) = path.into_inner();
+
+    let error_id = format!("daily_log_delay_error_{}", log_date);
+    let mut errors = vec![ErrorProps::new(error_id.clone())];
+
+    get_open_daily_log(&db_pool, &jobsite_id, &log_date).await?;
+
+    let cause = form_choice(
+        DelayCause::ALL,
+        DelayCause::as_str,
+        &data.cause,
+        "delay cause",
+    )?;
+
+    let minutes_lost = match data.hours.trim().parse::<f64>() {
+        Ok(hours) if hours > 0.0 && hours <= 24.0 => (hours * 60.0).round() as i64,
+        _ => {
+            errors.set_error(&error_id, "Hours lost must be between 0 and 24")?;
+            0
+        }
+    };
+
+    let reason = data.reason.trim();
+    if reason.is_empty() {
+        errors.set_error(&error_id, "A reason is required")?;
+    }
+
+    if errors.has_errors() {
+        return Ok(HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(errors.render_errors()?));
+    }
+
+    let recorded_event = DailyLogDelayRecorded {
+        id: Uuid::new_v4(),
+        jobsite_id,
+        log_date,
+        cause,
+        minutes_lost,
+        reason: reason.to_string(),
+        recorded_at: Utc::now(),
+    };
+
+    if let Err(locked) = append_daily_log_event(
+        &eventstore,
+        &jobsite_id,
+        &log_date,
+        DailyLogDelayRecorded::event_name(),
+        &recorded_event,
+    )
+    .await?
+    {
+        errors.set_error(&error_id, &locked.to_string())?;
+
+        return Ok(HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(errors.render_errors()?));
+    }
+
+    Ok(HttpResponse::Created()
+        .content_type("text/html; charset=utf-8")
+        .body(errors.render_errors()?))
+}
+
+pub async fn post_daily_log_submit(
+    db_pool: web::Data<PgPool>,
+    eventstore: web::Data<eventstore::Client>,
+    path: web::Path<(Uuid, NaiveDate)>,
+) -> Result<HttpResponse, RouteError> {
+    let (jobsite_id, log_date) = path.into_inner();
+
+    get_open_daily_log(&db_pool, &jobsite_id, &log_date).await?;
+
+    let submitted_event = DailyLogSubmitted {
+        jobsite_id,
+        log_date,
+        submitted_at: Utc::now(),
+    };
+
+    append_daily_log_event(
+        &eventstore,
+        &jobsite_id,
+        &log_date,
+        DailyLogSubmitted::event_name(),
+        &submitted_event,
+    )
+    .await?
+    .map_err(anyhow::Error::from)?;
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .finish())
+}
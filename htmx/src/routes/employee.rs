@@ -0,0 +1,231 @@
+use actix_web::{web, HttpResponse};
+use chrono::{NaiveDate, Utc};
+use eventstore::EventData;
+use models::{
+    events::employee::{EmployeeCreated, EmployeePinSet, EmployeeTerminated},
+    projections::{employee::Employee, jobsite::Jobsite, pay_rate::PayRate},
+    AppState,
+};
+use services::configuration::ApplicationSettings;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    routes::kiosk::{hash_pin, is_valid_pin},
+    utils::{ErrorProps, ErrorPropsCollection, RouteError},
+    views::{pages, TemplateRenderer},
+};
+
+#[derive(serde::Deserialize)]
+pub struct EmployeeCreateData {
+    name: String,
+    #[serde(default)]
+    trade: String,
+}
+
+pub async fn post_employee(
+    data: web::Form<EmployeeCreateData>,
+    eventstore: web::Data<eventstore::Client>,
+) -> Result<HttpResponse, RouteError> {
+    let mut errors = vec![ErrorProps {
+        id: "employee-name-error".to_string(),
+        text: None,
+    }];
+
+    let name = data.name.trim().to_string();
+
+    if name.is_empty() {
+        errors.set_error("employee-name-error", "A name is required")?;
+
+        return Ok(HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(errors.render_errors()?));
+    }
+
+    let trade = Some(data.trade.trim().to_string()).filter(|trade| !trade.is_empty());
+
+    let create_event = EmployeeCreated {
+        id: uuid::Uuid::new_v4(),
+        name,
+        trade,
+    };
+
+    let event = EventData::json(EmployeeCreated::event_name(), &create_event)
+        .expect("Unable to serialize")
+        .id(uuid::Uuid::new_v4());
+
+    eventstore
+        .append_to_stream(
+            format!("employee-{}", create_event.id),
+            &Default::default(),
+            event,
+        )
+        .await
+        .expect("Failed to append event");
+
+    // The new row is delivered to every client over the websocket, only clear
+    // any errors left over from a previous submission here
+    Ok(HttpResponse::Created()
+        .content_type("text/html; charset=utf-8")
+        .body(errors.render_errors()?))
+}
+
+pub async fn get_employees_page(
+    db_pool: web::Data<PgPool>,
+    state: web::Data<AppState>,
+) -> Result<HttpResponse, RouteError> {
+    let mut transaction = db_pool.begin().await?;
+
+    let employees = Employee::get_list(&mut transaction).await?;
+    let jobsites = Jobsite::get_list(&mut transaction).await?;
+    let trades = Employee::get_trades(&mut transaction).await?;
+    let pay_rates = PayRate::get_list(&mut transaction).await?;
+
+    transaction.commit().await?;
+
+    let today = state.today();
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .body(TemplateRenderer::render(move || {
+            pages::Employees(pages::EmployeesProps {
+                employees,
+                jobsites,
+                trades,
+                pay_rates,
+                today,
+            })
+        })))
+}
+
+#[derive(serde::Deserialize)]
+pub struct EmployeeTerminateData {
+    terminated_on: String,
+}
+
+pub async fn post_employee_terminate(
+    db_pool: web::Data<PgPool>,
+    data: web::Form<EmployeeTerminateData>,
+    eventstore: web::Data<eventstore::Client>,
+    employee_id: web::Path<Uuid>,
+) -> Result<HttpResponse, RouteError> {
+    let employee_id = employee_id.into_inner();
+    let error_id = format!("employee_terminate_error_{}", employee_id);
+    let mut errors = vec![ErrorProps::new(error_id.clone())];
+
+    let mut transaction = db_pool.begin().await?;
+
+    let employee = match Employee::get_by_id(&mut transaction, &employee_id).await? {
+        Some(employee) => employee,
+        None => return Err(RouteError::NotFound),
+    };
+
+    transaction.commit().await?;
+
+    if employee.terminated_on.is_some() {
+        errors.set_error(&error_id, "This employee has already been terminated")?;
+
+        return Ok(HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(errors.render_errors()?));
+    }
+
+    let Ok(terminated_on) = NaiveDate::parse_from_str(&data.terminated_on, "%Y-%m-%d") else {
+        errors.set_error(&error_id, "A termination date is required")?;
+
+        return Ok(HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(errors.render_errors()?));
+    };
+
+    let terminated_event = EmployeeTerminated {
+        id: employee_id,
+        terminated_on,
+        terminated_at: Utc::now(),
+    };
+
+    let event = EventData::json(EmployeeTerminated::event_name(), &terminated_event)
+        .expect("Unable to serialize")
+        .id(Uuid::new_v4());
+
+    eventstore
+        .append_to_stream(
+            format!("employee-{}", employee_id),
+            &Default::default(),
+            event,
+        )
+        .await
+        .expect("Failed to append event");
+
+    Ok(HttpResponse::Created()
+        .content_type("text/html; charset=utf-8")
+        .body(errors.render_errors()?))
+}
+
+#[derive(serde::Deserialize)]
+pub struct EmployeePinData {
+    pin: String,
+}
+
+/// Set the PIN the employee clocks in with at jobsite kiosks
+pub async fn post_employee_pin(
+    db_pool: web::Data<PgPool>,
+    settings: web::Data<ApplicationSettings>,
+    data: web::Form<EmployeePinData>,
+    eventstore: web::Data<eventstore::Client>,
+    employee_id: web::Path<Uuid>,
+) -> Result<HttpResponse, RouteError> {
+    let employee_id = employee_id.into_inner();
+    let error_id = format!("employee_pin_error_{}", employee_id);
+    let mut errors = vec![ErrorProps::new(error_id.clone())];
+
+    let mut transaction = db_pool.begin().await?;
+
+    let employee = match Employee::get_by_id(&mut transaction, &employee_id).await? {
+        Some(employee) => employee,
+        None => return Err(RouteError::NotFound),
+    };
+
+    transaction.commit().await?;
+
+    if employee.terminated_on.is_some() {
+        errors.set_error(&error_id, "This employee has been terminated")?;
+
+        return Ok(HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(errors.render_errors()?));
+    }
+
+    let pin = data.pin.trim();
+
+    if !is_valid_pin(pin) {
+        errors.set_error(&error_id, "A PIN is 4 to 6 digits")?;
+
+        return Ok(HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(errors.render_errors()?));
+    }
+
+    let pin_event = EmployeePinSet {
+        id: employee_id,
+        pin_hash: hash_pin(&settings.signing_key, &employee_id, pin),
+        set_at: Utc::now(),
+    };
+
+    let event = EventData::json(EmployeePinSet::event_name(), &pin_event)
+        .expect("Unable to serialize")
+        .id(Uuid::new_v4());
+
+    eventstore
+        .append_to_stream(
+            format!("employee-{}", employee_id),
+            &Default::default(),
+            event,
+        )
+        .await
+        .expect("Failed to append event");
+
+    Ok(HttpResponse::Created()
+        .content_type("text/html; charset=utf-8")
+        .body(errors.render_errors()?))
+}
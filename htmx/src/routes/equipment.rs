@@ -0,0 +1,260 @@
+use actix_web::{web, HttpResponse};
+use chrono::Utc;
+use eventstore::EventData;
+use models::{
+    events::equipment::{EquipmentAdded, EquipmentCheckedOut, EquipmentReturned},
+    projections::{employee::Employee, equipment::Equipment, jobsite::Jobsite},
+    AppState,
+};
+use serde::Serialize;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    utils::{ErrorProps, ErrorPropsCollection, RouteError},
+    views::{components, pages, TemplateRenderer},
+};
+
+pub async fn get_equipment_page(
+    db_pool: web::Data<PgPool>,
+    state: web::Data<AppState>,
+) -> Result<HttpResponse, RouteError> {
+    let mut transaction = db_pool.begin().await?;
+
+    let equipment = Equipment::get_list(&mut transaction).await?;
+    let jobsites = Jobsite::get_list(&mut transaction).await?;
+    let employees = Employee::get_list_active_on(&mut transaction, state.today()).await?;
+
+    transaction.commit().await?;
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .body(TemplateRenderer::render(move || {
+            pages::EquipmentCatalog(pages::EquipmentCatalogProps {
+                equipment,
+                jobsites,
+                employees,
+            })
+        })))
+}
+
+pub async fn get_jobsite_equipment(
+    db_pool: web::Data<PgPool>,
+    jobsite_id: web::Path<Uuid>,
+) -> Result<HttpResponse, RouteError> {
+    let jobsite_id = jobsite_id.into_inner();
+
+    let mut transaction = db_pool.begin().await?;
+
+    if Jobsite::get_by_id(&mut transaction, &jobsite_id)
+        .await?
+        .is_none()
+    {
+        return Err(RouteError::NotFound);
+    }
+
+    let equipment = Equipment::get_by_jobsite(&mut transaction, &jobsite_id).await?;
+
+    transaction.commit().await?;
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .body(TemplateRenderer::render(move || {
+            components::equipment::JobsiteEquipment(components::equipment::JobsiteEquipmentProps {
+                jobsite_id,
+                equipment,
+            })
+        })))
+}
+
+async fn append_equipment_event<T: Serialize>(
+    eventstore: &eventstore::Client,
+    equipment_id: &Uuid,
+    event_name: String,
+    event: &T,
+) {
+    let event = EventData::json(event_name, event)
+        .expect("Unable to serialize")
+        .id(Uuid::new_v4());
+
+    eventstore
+        .append_to_stream(
+            Equipment::stream_name(equipment_id),
+            &Default::default(),
+            event,
+        )
+        .await
+        .expect("Failed to append event");
+}
+
+#[derive(serde::Deserialize)]
+pub struct EquipmentData {
+    name: String,
+    #[serde(default)]
+    serial_number: String,
+}
+
+pub async fn post_equipment(
+    data: web::Form<EquipmentData>,
+    eventstore: web::Data<eventstore::Client>,
+) -> Result<HttpResponse, RouteError> {
+    let mut errors = vec![ErrorProps::new("equipment-name-error".to_string())];
+
+    let name = data.name.trim();
+    if name.is_empty() {
+        errors.set_error("equipment-name-error", "A name is required")?;
+
+        return Ok(HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(errors.render_errors()?));
+    }
+
+    let serial_number = data.serial_number.trim();
+    let added_event = EquipmentAdded {
+        id: Uuid::new_v4(),
+        name: name.to_string(),
+        serial_number: (!serial_number.is_empty()).then(|| serial_number.to_string()),
+        added_at: Utc::now(),
+    };
+
+    append_equipment_event(
+        &eventstore,
+        &added_event.id,
+        EquipmentAdded::event_name(),
+        &added_event,
+    )
+    .await;
+
+    Ok(HttpResponse::Created()
+        .content_type("text/html; charset=utf-8")
+        .body(errors.render_errors()?))
+}
+
+async fn get_equipment(db_pool: &PgPool, equipment_id: &Uuid) -> Result<Equipment, RouteError> {
+    let mut transaction = db_pool.begin().await?;
+
+    let equipment = match Equipment::get_by_id(&mut transaction, equipment_id).await? {
+        Some(equipment) => equipment,
+        None => return Err(RouteError::NotFound),
+    };
+
+    transaction.commit().await?;
+
+    Ok(equipment)
+}
+
+#[derive(serde::Deserialize)]
+pub struct EquipmentCheckOutData {
+    jobsite_id: Uuid,
+    #[serde(default)]
+    employee_id: String,
+}
+
+pub async fn post_equipment_check_out(
+    db_pool: web::Data<PgPool>,
+    data: web::Form<EquipmentCheckOutData>,
+    eventstore: web::Data<eventstore::Client>,
+    state: web::Data<AppState>,
+    equipment_id: web::Path<Uuid>,
+) -> Result<HttpResponse, RouteError> {
+    let equipment = get_equipment(&db_pool, &equipment_id).await?;
+
+    let error_id = components::equipment::equipment_action_error_id(&equipment.id);
+    let mut errors = vec![ErrorProps::new(error_id.clone())];
+
+    // Equipment has to come back to the yard before it goes out again
+    if let Some(jobsite_name) = &equipment.jobsite_name {
+        errors.set_error(
+            &error_id,
+            &format!("{} is already out at {}", equipment.name, jobsite_name),
+        )?;
+
+        return Ok(HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(errors.render_errors()?));
+    }
+
+    let employee_id = Uuid::parse_str(&data.employee_id).ok();
+
+    let mut transaction = db_pool.begin().await?;
+
+    if Jobsite::get_by_id(&mut transaction, &data.jobsite_id)
+        .await?
+        .is_none()
+    {
+        return Err(RouteError::NotFound);
+    }
+
+    if let Some(employee_id) = employee_id {
+        match Employee::get_by_id(&mut transaction, &employee_id).await? {
+            None => return Err(RouteError::NotFound),
+            Some(employee) if employee.is_terminated_on(state.today()) => {
+                errors.set_error(&error_id, "This employee is no longer active")?;
+            }
+            Some(_) => {}
+        }
+    }
+
+    transaction.commit().await?;
+
+    if errors.has_errors() {
+        return Ok(HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(errors.render_errors()?));
+    }
+
+    let checked_out_event = EquipmentCheckedOut {
+        id: equipment.id,
+        checkout_id: Uuid::new_v4(),
+        jobsite_id: data.jobsite_id,
+        employee_id,
+        checked_out_at: Utc::now(),
+    };
+
+    append_equipment_event(
+        &eventstore,
+        &equipment.id,
+        EquipmentCheckedOut::event_name(),
+        &checked_out_event,
+    )
+    .await;
+
+    Ok(HttpResponse::Created()
+        .content_type("text/html; charset=utf-8")
+        .body(errors.render_errors()?))
+}
+
+pub async fn post_equipment_return(
+    db_pool: web::Data<PgPool>,
+    eventstore: web::Data<eventstore::Client>,
+    equipment_id: web::Path<Uuid>,
+) -> Result<HttpResponse, RouteError> {
+    let equipment = get_equipment(&db_pool, &equipment_id).await?;
+
+    let (Some(checkout_id), Some(jobsite_id)) = (equipment.checkout_id, equipment.jobsite_id)
+    else {
+        return Err(RouteError::UnexpectedError(anyhow::anyhow!(
+            "{} is already in the yard",
+            equipment.name
+        )));
+    };
+
+    let returned_event = EquipmentReturned {
+        id: equipment.id,
+        checkout_id,
+        jobsite_id,
+        returned_at: Utc::now(),
+    };
+
+    append_equipment_event(
+        &eventstore,
+        &equipment.id,
+        EquipmentReturned::event_name(),
+        &returned_event,
+    )
+    .await;
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .finish())
+}
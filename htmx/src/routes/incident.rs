@@ -0,0 +1,369 @@
+use actix_web::{web, HttpResponse};
+use chrono::{NaiveDate, Utc};
+use eventstore::EventData;
+use models::{
+    events::incident::{
+        IncidentClosed, IncidentCorrectiveActionAssigned, IncidentInvestigated, IncidentReported,
+    },
+    projections::{
+        employee::Employee,
+        incident::{Incident, IncidentEmployee, IncidentSeverity, IncidentStatus},
+        jobsite::Jobsite,
+    },
+    AppState,
+};
+use serde::Serialize;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    utils::{form_choice, ErrorProps, ErrorPropsCollection, RouteError},
+    views::{components, pages, TemplateRenderer},
+};
+
+pub async fn get_incidents_page(
+    db_pool: web::Data<PgPool>,
+    state: web::Data<AppState>,
+) -> Result<HttpResponse, RouteError> {
+    let today = state.today();
+
+    let mut transaction = db_pool.begin().await?;
+
+    let incidents = Incident::get_list(&mut transaction).await?;
+    let involved = IncidentEmployee::get_list(&mut transaction).await?;
+    let safety = Incident::get_jobsite_safety(&mut transaction).await?;
+    let jobsites = Jobsite::get_list(&mut transaction).await?;
+    let employees = Employee::get_list_active_on(&mut transaction, today).await?;
+
+    transaction.commit().await?;
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .body(TemplateRenderer::render(move || {
+            pages::Incidents(pages::IncidentsProps {
+                incidents,
+                involved,
+                safety,
+                jobsites,
+                employees,
+                today,
+            })
+        })))
+}
+
+async fn append_incident_event<T: Serialize>(
+    eventstore: &eventstore::Client,
+    incident_id: &Uuid,
+    event_name: String,
+    event: &T,
+) {
+    let event = EventData::json(event_name, event)
+        .expect("Unable to serialize")
+        .id(Uuid::new_v4());
+
+    eventstore
+        .append_to_stream(
+            Incident::stream_name(incident_id),
+            &Default::default(),
+            event,
+        )
+        .await
+        .expect("Failed to append event");
+}
+
+/// Report an incident. The form is read as pairs since every involved
+/// employee is sent as its own `employee_id` field.
+pub async fn post_incident(
+    db_pool: web::Data<PgPool>,
+    data: web::Form<Vec<(String, String)>>,
+    eventstore: web::Data<eventstore::Client>,
+    state: web::Data<AppState>,
+) -> Result<HttpResponse, RouteError> {
+    let mut errors = vec![
+        ErrorProps::new("incident-jobsite-error".to_string()),
+        ErrorProps::new("incident-date-error".to_string()),
+        ErrorProps::new("incident-description-error".to_string()),
+    ];
+
+    let field = |name: &str| {
+        data.iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.trim())
+            .unwrap_or_default()
+    };
+
+    let severity = form_choice(
+        IncidentSeverity::ALL,
+        IncidentSeverity::as_str,
+        field("severity"),
+        "severity",
+    )?;
+
+    let jobsite_id = Uuid::parse_str(field("jobsite_id")).ok();
+    if jobsite_id.is_none() {
+        errors.set_error("incident-jobsite-error", "A jobsite is required")?;
+    }
+
+    let occurred_on = match NaiveDate::parse_from_str(field("occurred_on"), "%Y-%m-%d") {
+        Ok(occurred_on) if occurred_on > state.today() => {
+            errors.set_error("incident-date-error", "Incidents can't be in the future")?;
+            None
+        }
+        Ok(occurred_on) => Some(occurred_on),
+        Err(_) => {
+            errors.set_error("incident-date-error", "A date is required")?;
+            None
+        }
+    };
+
+    if field("description").is_empty() {
+        errors.set_error("incident-description-error", "Describe what happened")?;
+    }
+
+    let mut employee_ids = data
+        .iter()
+        .filter(|(key, _)| key == "employee_id")
+        .map(|(_, value)| Uuid::parse_str(value))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| RouteError::UnexpectedError(e.into()))?;
+    employee_ids.sort();
+    employee_ids.dedup();
+
+    if errors.has_errors() {
+        return Ok(HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(errors.render_errors()?));
+    }
+
+    let (Some(jobsite_id), Some(occurred_on)) = (jobsite_id, occurred_on) else {
+        unreachable!("Jobsite and date were validated");
+    };
+
+    let mut transaction = db_pool.begin().await?;
+
+    if Jobsite::get_by_id(&mut transaction, &jobsite_id)
+        .await?
+        .is_none()
+    {
+        errors.set_error("incident-jobsite-error", "This jobsite no longer exists")?;
+    }
+
+    for employee_id in &employee_ids {
+        if Employee::get_by_id(&mut transaction, employee_id)
+            .await?
+            .is_none()
+        {
+            return Err(RouteError::UnexpectedError(anyhow::anyhow!(
+                "Unknown employee"
+            )));
+        }
+    }
+
+    transaction.commit().await?;
+
+    if errors.has_errors() {
+        return Ok(HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(errors.render_errors()?));
+    }
+
+    let reported_event = IncidentReported {
+        id: Uuid::new_v4(),
+        jobsite_id,
+        severity,
+        occurred_on,
+        description: field("description").to_string(),
+        employee_ids,
+        reported_at: Utc::now(),
+    };
+
+    append_incident_event(
+        &eventstore,
+        &reported_event.id,
+        IncidentReported::event_name(),
+        &reported_event,
+    )
+    .await;
+
+    Ok(HttpResponse::Created()
+        .content_type("text/html; charset=utf-8")
+        .body(errors.render_errors()?))
+}
+
+/// Load an incident, it has to be at `status` to move on to the next step
+async fn get_incident_at(
+    db_pool: &PgPool,
+    incident_id: &Uuid,
+    status: IncidentStatus,
+) -> Result<Incident, RouteError> {
+    let mut transaction = db_pool.begin().await?;
+
+    let incident = match Incident::get_by_id(&mut transaction, incident_id).await? {
+        Some(incident) => incident,
+        None => return Err(RouteError::NotFound),
+    };
+
+    transaction.commit().await?;
+
+    if incident.status != status {
+        return Err(RouteError::UnexpectedError(anyhow::anyhow!(
+            "This incident is {}, not {}",
+            incident.status.label().to_lowercase(),
+            status.label().to_lowercase()
+        )));
+    }
+
+    Ok(incident)
+}
+
+#[derive(serde::Deserialize)]
+pub struct IncidentInvestigateData {
+    findings: String,
+}
+
+pub async fn post_incident_investigate(
+    db_pool: web::Data<PgPool>,
+    data: web::Form<IncidentInvestigateData>,
+    eventstore: web::Data<eventstore::Client>,
+    incident_id: web::Path<Uuid>,
+) -> Result<HttpResponse, RouteError> {
+    let incident_id = incident_id.into_inner();
+
+    let error_id = components::incident::incident_action_error_id(&incident_id);
+    let mut errors = vec![ErrorProps::new(error_id.clone())];
+
+    get_incident_at(&db_pool, &incident_id, IncidentStatus::Reported).await?;
+
+    let findings = data.findings.trim();
+    if findings.is_empty() {
+        errors.set_error(&error_id, "Findings are required")?;
+
+        return Ok(HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(errors.render_errors()?));
+    }
+
+    let investigated_event = IncidentInvestigated {
+        id: incident_id,
+        findings: findings.to_string(),
+        investigated_at: Utc::now(),
+    };
+
+    append_incident_event(
+        &eventstore,
+        &incident_id,
+        IncidentInvestigated::event_name(),
+        &investigated_event,
+    )
+    .await;
+
+    Ok(HttpResponse::Created()
+        .content_type("text/html; charset=utf-8")
+        .body(errors.render_errors()?))
+}
+
+#[derive(serde::Deserialize)]
+pub struct IncidentCorrectiveActionData {
+    action: String,
+    assignee_id: Uuid,
+    #[serde(default)]
+    due_on: String,
+}
+
+pub async fn post_incident_corrective_action(
+    db_pool: web::Data<PgPool>,
+    data: web::Form<IncidentCorrectiveActionData>,
+    eventstore: web::Data<eventstore::Client>,
+    state: web::Data<AppState>,
+    incident_id: web::Path<Uuid>,
+) -> Result<HttpResponse, RouteError> {
+    let incident_id = incident_id.into_inner();
+
+    let error_id = components::incident::incident_action_error_id(&incident_id);
+    let mut errors = vec![ErrorProps::new(error_id.clone())];
+
+    let incident = get_incident_at(&db_pool, &incident_id, IncidentStatus::Investigated).await?;
+
+    let action = data.action.trim();
+    if action.is_empty() {
+        errors.set_error(&error_id, "A corrective action is required")?;
+    }
+
+    let due_on = match data.due_on.trim() {
+        "" => None,
+        due_on => match NaiveDate::parse_from_str(due_on, "%Y-%m-%d") {
+            Ok(due_on) if due_on >= incident.occurred_on => Some(due_on),
+            _ => {
+                errors.set_error(&error_id, "Due date can't be before the incident")?;
+                None
+            }
+        },
+    };
+
+    let mut transaction = db_pool.begin().await?;
+
+    let assignee = Employee::get_by_id(&mut transaction, &data.assignee_id).await?;
+
+    transaction.commit().await?;
+
+    match assignee {
+        None => return Err(RouteError::NotFound),
+        Some(assignee) if assignee.is_terminated_on(state.today()) => {
+            errors.set_error(&error_id, "This employee is no longer active")?;
+        }
+        Some(_) => {}
+    }
+
+    if errors.has_errors() {
+        return Ok(HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(errors.render_errors()?));
+    }
+
+    let assigned_event = IncidentCorrectiveActionAssigned {
+        id: incident_id,
+        action: action.to_string(),
+        assignee_id: data.assignee_id,
+        due_on,
+        assigned_at: Utc::now(),
+    };
+
+    append_incident_event(
+        &eventstore,
+        &incident_id,
+        IncidentCorrectiveActionAssigned::event_name(),
+        &assigned_event,
+    )
+    .await;
+
+    Ok(HttpResponse::Created()
+        .content_type("text/html; charset=utf-8")
+        .body(errors.render_errors()?))
+}
+
+pub async fn post_incident_close(
+    db_pool: web::Data<PgPool>,
+    eventstore: web::Data<eventstore::Client>,
+    incident_id: web::Path<Uuid>,
+) -> Result<HttpResponse, RouteError> {
+    let incident_id = incident_id.into_inner();
+
+    get_incident_at(&db_pool, &incident_id, IncidentStatus::ActionAssigned).await?;
+
+    let closed_event = IncidentClosed {
+        id: incident_id,
+        closed_at: Utc::now(),
+    };
+
+    append_incident_event(
+        &eventstore,
+        &incident_id,
+        IncidentClosed::event_name(),
+        &closed_event,
+    )
+    .await;
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .finish())
+}
@@ -1,10 +1,21 @@
 use actix_web::{web, HttpResponse};
+use chrono::Utc;
 use eventstore::EventData;
 use models::{
-    events::jobsite::{JobsiteCreated, JobsiteUpdated},
-    projections::jobsite::Jobsite,
+    events::jobsite::{
+        JobsiteArchived, JobsiteAssignedToProject, JobsiteCreated, JobsiteGeofenceSet,
+        JobsiteUpdated, JobsitesMerged, JobsitesUnmerged,
+    },
+    geofence::{Coordinates, Geofence},
+    local_time::parse_time_zone,
+    projections::{
+        client::Client,
+        jobsite::{Jobsite, JobsiteMerge},
+        project::Project,
+    },
 };
 use sqlx::PgPool;
+use uuid::Uuid;
 
 use crate::{
     utils::{ErrorProps, ErrorPropsCollection, RouteError},
@@ -14,6 +25,9 @@ use crate::{
 #[derive(serde::Deserialize)]
 pub struct JobsiteCreateData {
     name: String,
+    #[serde(default)]
+    project_id: String,
+    time_zone: String,
 }
 
 pub async fn post_jobsite(
@@ -21,14 +35,39 @@ pub async fn post_jobsite(
     data: web::Form<JobsiteCreateData>,
     eventstore: web::Data<eventstore::Client>,
 ) -> Result<HttpResponse, RouteError> {
-    let mut errors = vec![ErrorProps {
-        id: "name-error".to_string(),
-        text: None,
-    }];
+    let mut errors = vec![
+        ErrorProps {
+            id: "name-error".to_string(),
+            text: None,
+        },
+        ErrorProps::new("time-zone-error".to_string()),
+    ];
+
+    let Some(time_zone) = parse_time_zone(&data.time_zone) else {
+        errors.set_error("time-zone-error", "Unknown time zone")?;
+
+        return Ok(HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(errors.render_errors()?));
+    };
 
     let mut transaction = db_pool.begin().await.unwrap();
 
-    match Jobsite::get_by_name(&mut transaction, data.name.clone()).await? {
+    let project_id = Uuid::parse_str(&data.project_id).ok();
+    if let Some(project_id) = project_id {
+        if Project::get_by_id(&mut transaction, &project_id)
+            .await?
+            .is_none()
+        {
+            errors.set_error("name-error", "This project does not exist")?;
+
+            return Ok(HttpResponse::Ok()
+                .content_type("text/html; charset=utf-8")
+                .body(errors.render_errors()?));
+        }
+    }
+
+    match Jobsite::get_by_name(&mut transaction, project_id, data.name.clone()).await? {
         Some(_) => {
             errors.set_error("name-error", "A Jobsite already exists with this name")?;
 
@@ -45,6 +84,8 @@ pub async fn post_jobsite(
     let create_event = JobsiteCreated {
         id: jobsite_id,
         name: data.name.clone(),
+        project_id,
+        time_zone: time_zone.name().to_string(),
     };
 
     let event = EventData::json("JobsiteCreated", &create_event)
@@ -73,6 +114,8 @@ pub async fn post_jobsite(
 pub async fn get_jobsites(db_pool: web::Data<PgPool>) -> Result<HttpResponse, RouteError> {
     let mut transaction = db_pool.begin().await.unwrap();
 
+    let clients = Client::get_list(&mut transaction).await?;
+    let projects = Project::get_list(&mut transaction).await?;
     let jobsites = Jobsite::get_list(&mut transaction).await?;
 
     transaction.commit().await?;
@@ -81,8 +124,9 @@ pub async fn get_jobsites(db_pool: web::Data<PgPool>) -> Result<HttpResponse, Ro
         .content_type("text/html; charset=utf-8")
         .body(TemplateRenderer::render(|| {
             components::jobsite::JobsiteList(components::jobsite::JobsiteListProps {
+                clients,
+                projects,
                 jobsites,
-                append: None,
             })
         })))
 }
@@ -95,23 +139,39 @@ pub async fn get_jobsite(
 
     let jobsite = Jobsite::get_by_id(&mut transaction, &jobsite_id.into_inner()).await?;
 
+    // A merged jobsite points at the one its work is recorded at now
+    let merged_into = match jobsite.as_ref().and_then(|jobsite| jobsite.merged_into_id) {
+        Some(merged_into_id) => Jobsite::get_by_id(&mut transaction, &merged_into_id).await?,
+        None => None,
+    };
+
     transaction.commit().await?;
 
-    match jobsite {
-        Some(jobsite) => Ok(HttpResponse::Ok()
+    match (jobsite, merged_into) {
+        (Some(source), Some(target)) => Ok(HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(TemplateRenderer::render(move || {
+                components::jobsite::JobsiteMergedNotice(
+                    components::jobsite::JobsiteMergedNoticeProps {
+                        merge: JobsiteMerge { source, target },
+                    },
+                )
+            }))),
+        (Some(jobsite), None) => Ok(HttpResponse::Ok()
             .content_type("text/html; charset=utf-8")
             .body(TemplateRenderer::render(move || {
                 components::jobsite::JobsiteEdit(components::jobsite::JobsiteEditProps {
                     jobsite: Some(jobsite),
                 })
             }))),
-        None => Err(RouteError::NotFound),
+        (None, _) => Err(RouteError::NotFound),
     }
 }
 
 #[derive(serde::Deserialize)]
 pub struct JobsiteUpdateData {
     name: String,
+    time_zone: String,
 }
 
 pub async fn put_jobsite(
@@ -120,31 +180,43 @@ pub async fn put_jobsite(
     eventstore: web::Data<eventstore::Client>,
     jobsite_id: web::Path<uuid::Uuid>,
 ) -> Result<HttpResponse, RouteError> {
-    let mut errors = vec![ErrorProps {
-        id: "name-error".to_string(),
-        text: None,
-    }];
+    let mut errors = vec![
+        ErrorProps {
+            id: "name-error".to_string(),
+            text: None,
+        },
+        ErrorProps::new("time-zone-error".to_string()),
+    ];
+
+    let Some(time_zone) = parse_time_zone(&data.time_zone) else {
+        errors.set_error("time-zone-error", "Unknown time zone")?;
+
+        return Ok(HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(errors.render_errors()?));
+    };
 
     let mut transaction = db_pool.begin().await.unwrap();
 
     let jobsite_id = jobsite_id.into_inner();
 
-    match Jobsite::get_by_id(&mut transaction, &jobsite_id).await? {
-        Some(_) => {}
+    let jobsite = match Jobsite::get_by_id(&mut transaction, &jobsite_id).await? {
+        Some(jobsite) => jobsite,
         None => {
             return Err(RouteError::NotFound);
         }
     };
 
-    match Jobsite::get_by_name(&mut transaction, data.name.clone()).await? {
-        Some(_) => {
+    match Jobsite::get_by_name(&mut transaction, jobsite.project_id, data.name.clone()).await? {
+        // Keeping the name while changing the time zone is fine
+        Some(existing) if existing.id != jobsite_id => {
             errors.set_error("name-error", "This name is already taken")?;
 
             return Ok(HttpResponse::Ok()
                 .content_type("text/html; charset=utf-8")
                 .body(errors.render_errors()?));
         }
-        None => {}
+        _ => {}
     };
 
     transaction.commit().await?;
@@ -152,6 +224,7 @@ pub async fn put_jobsite(
     let update_event = JobsiteUpdated {
         id: jobsite_id,
         name: data.name.clone(),
+        time_zone: time_zone.name().to_string(),
     };
 
     let event = EventData::json("JobsiteUpdated", &update_event)
@@ -171,3 +244,436 @@ pub async fn put_jobsite(
         .content_type("text/html; charset=utf-8")
         .finish())
 }
+
+pub async fn get_jobsite_project(
+    db_pool: web::Data<PgPool>,
+    jobsite_id: web::Path<Uuid>,
+) -> Result<HttpResponse, RouteError> {
+    let mut transaction = db_pool.begin().await?;
+
+    let jobsite = match Jobsite::get_by_id(&mut transaction, &jobsite_id.into_inner()).await? {
+        Some(jobsite) => jobsite,
+        None => return Err(RouteError::NotFound),
+    };
+    let projects = Project::get_list(&mut transaction).await?;
+
+    transaction.commit().await?;
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .body(TemplateRenderer::render(move || {
+            components::jobsite::JobsiteProjectAssign(
+                components::jobsite::JobsiteProjectAssignProps {
+                    jobsite_id: jobsite.id,
+                    project_id: jobsite.project_id,
+                    projects,
+                },
+            )
+        })))
+}
+
+#[derive(serde::Deserialize)]
+pub struct JobsiteProjectData {
+    project_id: String,
+}
+
+pub async fn post_jobsite_project(
+    db_pool: web::Data<PgPool>,
+    data: web::Form<JobsiteProjectData>,
+    eventstore: web::Data<eventstore::Client>,
+    jobsite_id: web::Path<Uuid>,
+) -> Result<HttpResponse, RouteError> {
+    let jobsite_id = jobsite_id.into_inner();
+    let error_id = format!("jobsite_project_error_{}", jobsite_id);
+    let mut errors = vec![ErrorProps::new(error_id.clone())];
+
+    let mut transaction = db_pool.begin().await?;
+
+    let jobsite = match Jobsite::get_by_id(&mut transaction, &jobsite_id).await? {
+        Some(jobsite) => jobsite,
+        None => return Err(RouteError::NotFound),
+    };
+
+    let project = match Uuid::parse_str(&data.project_id) {
+        Ok(project_id) => Project::get_by_id(&mut transaction, &project_id).await?,
+        Err(_) => None,
+    };
+
+    let Some(project) = project else {
+        errors.set_error(&error_id, "Choose a project to move this jobsite to")?;
+
+        return Ok(HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(errors.render_errors()?));
+    };
+
+    if jobsite.project_id == Some(project.id) {
+        errors.set_error(&error_id, "This jobsite is already in this project")?;
+    } else if Jobsite::get_by_name(&mut transaction, Some(project.id), jobsite.name.clone())
+        .await?
+        .is_some()
+    {
+        errors.set_error(
+            &error_id,
+            "This project already has a jobsite with this name",
+        )?;
+    }
+
+    transaction.commit().await?;
+
+    if errors.has_errors() {
+        return Ok(HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(errors.render_errors()?));
+    }
+
+    let assigned_event = JobsiteAssignedToProject {
+        id: jobsite_id,
+        project_id: project.id,
+    };
+
+    let event = EventData::json(JobsiteAssignedToProject::event_name(), &assigned_event)
+        .expect("Unable to serialize")
+        .id(Uuid::new_v4());
+
+    eventstore
+        .append_to_stream(
+            format!("jobsite-{}", assigned_event.id),
+            &Default::default(),
+            event,
+        )
+        .await
+        .expect("Failed to append event");
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .body(errors.render_errors()?))
+}
+
+pub async fn get_jobsite_geofence(
+    db_pool: web::Data<PgPool>,
+    jobsite_id: web::Path<Uuid>,
+) -> Result<HttpResponse, RouteError> {
+    let mut transaction = db_pool.begin().await?;
+
+    let jobsite = match Jobsite::get_by_id(&mut transaction, &jobsite_id.into_inner()).await? {
+        Some(jobsite) => jobsite,
+        None => return Err(RouteError::NotFound),
+    };
+
+    transaction.commit().await?;
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .body(TemplateRenderer::render(move || {
+            components::jobsite::JobsiteGeofence(components::jobsite::JobsiteGeofenceProps {
+                jobsite_id: jobsite.id,
+                geofence: jobsite.geofence(),
+            })
+        })))
+}
+
+#[derive(serde::Deserialize)]
+pub struct JobsiteGeofenceData {
+    latitude: String,
+    longitude: String,
+    radius_meters: String,
+}
+
+/// The widest geofence a jobsite can have, anything larger wouldn't catch
+/// anyone clocking in from home
+const MAX_GEOFENCE_RADIUS_METERS: i32 = 5_000;
+
+/// Set a jobsite's geofence, or clear it when every field is left blank
+pub async fn post_jobsite_geofence(
+    db_pool: web::Data<PgPool>,
+    data: web::Form<JobsiteGeofenceData>,
+    eventstore: web::Data<eventstore::Client>,
+    jobsite_id: web::Path<Uuid>,
+) -> Result<HttpResponse, RouteError> {
+    let jobsite_id = jobsite_id.into_inner();
+    let error_id = format!("jobsite_geofence_error_{}", jobsite_id);
+    let mut errors = vec![ErrorProps::new(error_id.clone())];
+
+    let fields = [&data.latitude, &data.longitude, &data.radius_meters].map(|field| field.trim());
+
+    let geofence = if fields.iter().all(|field| field.is_empty()) {
+        None
+    } else {
+        let center = match (fields[0].parse(), fields[1].parse()) {
+            (Ok(latitude), Ok(longitude)) => Coordinates::new(latitude, longitude),
+            _ => None,
+        };
+        let radius_meters = fields[2]
+            .parse::<i32>()
+            .ok()
+            .filter(|radius| (1..=MAX_GEOFENCE_RADIUS_METERS).contains(radius));
+
+        match (center, radius_meters) {
+            (Some(center), Some(radius_meters)) => Some(Geofence {
+                center,
+                radius_meters,
+            }),
+            (None, _) => {
+                errors.set_error(&error_id, "Enter the jobsite's latitude and longitude")?;
+                None
+            }
+            (_, None) => {
+                errors.set_error(
+                    &error_id,
+                    &format!(
+                        "The radius must be between 1 and {} meters",
+                        MAX_GEOFENCE_RADIUS_METERS
+                    ),
+                )?;
+                None
+            }
+        }
+    };
+
+    if errors.has_errors() {
+        return Ok(HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(errors.render_errors()?));
+    }
+
+    let mut transaction = db_pool.begin().await?;
+
+    if Jobsite::get_by_id(&mut transaction, &jobsite_id)
+        .await?
+        .is_none()
+    {
+        return Err(RouteError::NotFound);
+    }
+
+    transaction.commit().await?;
+
+    let geofence_event = JobsiteGeofenceSet {
+        id: jobsite_id,
+        geofence,
+    };
+
+    let event = EventData::json(JobsiteGeofenceSet::event_name(), &geofence_event)
+        .expect("Unable to serialize")
+        .id(Uuid::new_v4());
+
+    eventstore
+        .append_to_stream(
+            format!("jobsite-{}", geofence_event.id),
+            &Default::default(),
+            event,
+        )
+        .await
+        .expect("Failed to append event");
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .body(errors.render_errors()?))
+}
+
+pub async fn get_jobsite_merge(
+    db_pool: web::Data<PgPool>,
+    jobsite_id: web::Path<Uuid>,
+) -> Result<HttpResponse, RouteError> {
+    let jobsite_id = jobsite_id.into_inner();
+
+    let mut transaction = db_pool.begin().await?;
+
+    if Jobsite::get_by_id(&mut transaction, &jobsite_id)
+        .await?
+        .is_none()
+    {
+        return Err(RouteError::NotFound);
+    }
+
+    let jobsites = Jobsite::get_list(&mut transaction)
+        .await?
+        .into_iter()
+        .filter(|jobsite| jobsite.id != jobsite_id)
+        .collect::<Vec<_>>();
+
+    transaction.commit().await?;
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .body(TemplateRenderer::render(move || {
+            components::jobsite::JobsiteMergeForm(components::jobsite::JobsiteMergeFormProps {
+                jobsite_id,
+                jobsites,
+            })
+        })))
+}
+
+#[derive(serde::Deserialize)]
+pub struct JobsiteMergeData {
+    target_id: String,
+}
+
+/// Merge a duplicate jobsite into the one its work belongs to
+pub async fn post_jobsite_merge(
+    db_pool: web::Data<PgPool>,
+    data: web::Form<JobsiteMergeData>,
+    eventstore: web::Data<eventstore::Client>,
+    jobsite_id: web::Path<Uuid>,
+) -> Result<HttpResponse, RouteError> {
+    let jobsite_id = jobsite_id.into_inner();
+    let error_id = format!("jobsite_merge_error_{}", jobsite_id);
+    let mut errors = vec![ErrorProps::new(error_id.clone())];
+
+    let mut transaction = db_pool.begin().await?;
+
+    let source = match Jobsite::get_by_id(&mut transaction, &jobsite_id).await? {
+        Some(source) => source,
+        None => return Err(RouteError::NotFound),
+    };
+
+    let target = match Uuid::parse_str(&data.target_id) {
+        Ok(target_id) => Jobsite::get_by_id(&mut transaction, &target_id).await?,
+        Err(_) => None,
+    };
+
+    let merged_into_source = Jobsite::get_merged_into(&mut transaction, &source.id).await?;
+
+    transaction.commit().await?;
+
+    if let Some(target) = target {
+        if source.merged_into_id.is_some() {
+            errors.set_error(&error_id, "This jobsite has already been merged")?;
+        } else if target.id == source.id {
+            errors.set_error(&error_id, "A jobsite can't be merged into itself")?;
+        } else if target.merged_into_id.is_some() {
+            errors.set_error(
+                &error_id,
+                "That jobsite has been merged, choose the jobsite it was merged into",
+            )?;
+        } else if target.archived_at.is_some() {
+            errors.set_error(&error_id, "That jobsite has been archived")?;
+        } else if !merged_into_source.is_empty() {
+            errors.set_error(
+                &error_id,
+                "Other jobsites were merged into this one, unmerge them first",
+            )?;
+        } else {
+            let merged_event = JobsitesMerged {
+                source_id: source.id,
+                target_id: target.id,
+                merged_at: Utc::now(),
+            };
+
+            let event = EventData::json(JobsitesMerged::event_name(), &merged_event)
+                .expect("Unable to serialize")
+                .id(Uuid::new_v4());
+
+            eventstore
+                .append_to_stream(
+                    format!("jobsite-{}", merged_event.source_id),
+                    &Default::default(),
+                    event,
+                )
+                .await
+                .expect("Failed to append event");
+        }
+    } else {
+        errors.set_error(&error_id, "Choose the jobsite to merge this one into")?;
+    }
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .body(errors.render_errors()?))
+}
+
+/// Undo a merge, the jobsite gets back everything that was moved
+pub async fn post_jobsite_unmerge(
+    db_pool: web::Data<PgPool>,
+    eventstore: web::Data<eventstore::Client>,
+    jobsite_id: web::Path<Uuid>,
+) -> Result<HttpResponse, RouteError> {
+    let mut transaction = db_pool.begin().await?;
+
+    let source = match Jobsite::get_by_id(&mut transaction, &jobsite_id.into_inner()).await? {
+        Some(source) => source,
+        None => return Err(RouteError::NotFound),
+    };
+
+    transaction.commit().await?;
+
+    let Some(target_id) = source.merged_into_id else {
+        return Err(RouteError::UnexpectedError(anyhow::anyhow!(
+            "This jobsite has not been merged"
+        )));
+    };
+
+    let unmerged_event = JobsitesUnmerged {
+        source_id: source.id,
+        target_id,
+        unmerged_at: Utc::now(),
+    };
+
+    let event = EventData::json(JobsitesUnmerged::event_name(), &unmerged_event)
+        .expect("Unable to serialize")
+        .id(Uuid::new_v4());
+
+    eventstore
+        .append_to_stream(
+            format!("jobsite-{}", unmerged_event.source_id),
+            &Default::default(),
+            event,
+        )
+        .await
+        .expect("Failed to append event");
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .finish())
+}
+
+/// Archive a jobsite once work there is over, the jobsite archival process
+/// manager clocks out its open shifts and cancels upcoming crew assignments
+pub async fn post_jobsite_archive(
+    db_pool: web::Data<PgPool>,
+    eventstore: web::Data<eventstore::Client>,
+    jobsite_id: web::Path<Uuid>,
+) -> Result<HttpResponse, RouteError> {
+    let mut transaction = db_pool.begin().await?;
+
+    let jobsite = match Jobsite::get_by_id(&mut transaction, &jobsite_id.into_inner()).await? {
+        Some(jobsite) => jobsite,
+        None => return Err(RouteError::NotFound),
+    };
+
+    transaction.commit().await?;
+
+    if jobsite.archived_at.is_some() {
+        return Err(RouteError::UnexpectedError(anyhow::anyhow!(
+            "This jobsite has already been archived"
+        )));
+    }
+
+    if jobsite.merged_into_id.is_some() {
+        return Err(RouteError::UnexpectedError(anyhow::anyhow!(
+            "This jobsite has been merged, archive the jobsite it was merged into"
+        )));
+    }
+
+    let archived_event = JobsiteArchived {
+        id: jobsite.id,
+        archived_at: Utc::now(),
+    };
+
+    let event = EventData::json(JobsiteArchived::event_name(), &archived_event)
+        .expect("Unable to serialize")
+        .id(Uuid::new_v4());
+
+    eventstore
+        .append_to_stream(
+            format!("jobsite-{}", archived_event.id),
+            &Default::default(),
+            event,
+        )
+        .await
+        .expect("Failed to append event");
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .finish())
+}
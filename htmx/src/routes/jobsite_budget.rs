@@ -0,0 +1,166 @@
+use actix_web::{web, HttpResponse};
+use chrono::Utc;
+use eventstore::EventData;
+use leptos::view;
+use models::{
+    events::jobsite_budget::JobsiteBudgetSet,
+    projections::{
+        cost_code::CostCode,
+        jobsite::Jobsite,
+        jobsite_budget::{JobsiteBudget, JobsiteBudgetAlert},
+    },
+};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use super::pay_rate::parse_cents;
+use crate::{
+    utils::{ErrorProps, ErrorPropsCollection, RouteError},
+    views::{components, TemplateRenderer},
+};
+
+pub async fn get_jobsite_budget(
+    db_pool: web::Data<PgPool>,
+    jobsite_id: web::Path<Uuid>,
+) -> Result<HttpResponse, RouteError> {
+    let jobsite_id = jobsite_id.into_inner();
+
+    let mut transaction = db_pool.begin().await?;
+
+    if Jobsite::get_by_id(&mut transaction, &jobsite_id)
+        .await?
+        .is_none()
+    {
+        return Err(RouteError::NotFound);
+    }
+
+    let budgets = JobsiteBudget::get_list_by_jobsite(&mut transaction, &jobsite_id).await?;
+    let alerts = JobsiteBudgetAlert::get_list_by_jobsite(&mut transaction, &jobsite_id).await?;
+    let cost_codes = CostCode::get_active_by_jobsite(&mut transaction, &jobsite_id).await?;
+
+    transaction.commit().await?;
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .body(TemplateRenderer::render(move || {
+            view! {
+                <components::jobsite::JobsiteBudgetPanel jobsite_id=jobsite_id budgets=budgets alerts=alerts />
+                <components::jobsite::JobsiteBudgetCreate jobsite_id=jobsite_id cost_codes=cost_codes />
+            }
+        })))
+}
+
+/// Parse a number of hours such as `120` or `37.5` into minutes
+pub(super) fn parse_hours(value: &str) -> Option<i64> {
+    let hours: f64 = value.trim().parse().ok()?;
+
+    if !hours.is_finite() || hours < 0.0 {
+        return None;
+    }
+
+    Some((hours * 60.0).round() as i64)
+}
+
+#[derive(serde::Deserialize)]
+pub struct JobsiteBudgetSetData {
+    cost_code: String,
+    hours: String,
+    cost: String,
+}
+
+pub async fn post_jobsite_budget(
+    db_pool: web::Data<PgPool>,
+    jobsite_id: web::Path<Uuid>,
+    data: web::Form<JobsiteBudgetSetData>,
+    eventstore: web::Data<eventstore::Client>,
+) -> Result<HttpResponse, RouteError> {
+    let jobsite_id = jobsite_id.into_inner();
+    let code_error = format!("jobsite_budget_code_error_{}", jobsite_id);
+    let hours_error = format!("jobsite_budget_hours_error_{}", jobsite_id);
+    let cost_error = format!("jobsite_budget_cost_error_{}", jobsite_id);
+    let mut errors = vec![
+        ErrorProps::new(code_error.clone()),
+        ErrorProps::new(hours_error.clone()),
+        ErrorProps::new(cost_error.clone()),
+    ];
+
+    let mut transaction = db_pool.begin().await?;
+
+    if Jobsite::get_by_id(&mut transaction, &jobsite_id)
+        .await?
+        .is_none()
+    {
+        return Err(RouteError::NotFound);
+    }
+
+    let cost_code = Some(data.cost_code.trim().to_string()).filter(|code| !code.is_empty());
+
+    if let Some(cost_code) = &cost_code {
+        if CostCode::get_by_code(&mut transaction, &jobsite_id, cost_code)
+            .await?
+            .is_none()
+        {
+            errors.set_error(&code_error, "This jobsite has no such cost code")?;
+        }
+    }
+
+    transaction.commit().await?;
+
+    let budget_minutes = match data.hours.trim() {
+        "" => None,
+        hours => {
+            let minutes = parse_hours(hours);
+            if minutes.is_none() {
+                errors.set_error(&hours_error, "Enter a number of hours, e.g. 120")?;
+            }
+            minutes
+        }
+    };
+
+    let budget_cents = match data.cost.trim() {
+        "" => None,
+        cost => {
+            let cents = parse_cents(cost);
+            if cents.is_none() {
+                errors.set_error(&cost_error, "Enter an amount, e.g. 25000")?;
+            }
+            cents
+        }
+    };
+
+    if budget_minutes.is_none() && budget_cents.is_none() && !errors.has_errors() {
+        errors.set_error(&hours_error, "Budget hours, cost, or both")?;
+    }
+
+    if errors.has_errors() {
+        return Ok(HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(errors.render_errors()?));
+    }
+
+    let set_event = JobsiteBudgetSet {
+        id: Uuid::new_v4(),
+        jobsite_id,
+        cost_code,
+        budget_minutes,
+        budget_cents,
+        set_at: Utc::now(),
+    };
+
+    let event = EventData::json(JobsiteBudgetSet::event_name(), &set_event)
+        .expect("Unable to serialize")
+        .id(Uuid::new_v4());
+
+    eventstore
+        .append_to_stream(
+            format!("jobsite_budget-{}", jobsite_id),
+            &Default::default(),
+            event,
+        )
+        .await
+        .expect("Failed to append event");
+
+    Ok(HttpResponse::Created()
+        .content_type("text/html; charset=utf-8")
+        .body(errors.render_errors()?))
+}
@@ -0,0 +1,467 @@
+use actix_web::{
+    cookie::{Cookie, SameSite},
+    web, HttpRequest, HttpResponse,
+};
+use chrono::Utc;
+use eventstore::EventData;
+use models::{
+    commands::shift::Punch,
+    events::{
+        jobsite::{JobsiteKioskRegistered, JobsiteKioskRevoked},
+        shift::{ClockedIn, ClockedOut, KioskMetadata},
+    },
+    geofence::GeofenceCheck,
+    projections::{
+        employee::Employee,
+        jobsite::Jobsite,
+        kiosk::{accepts_pins, Kiosk, PIN_FAILURE_WINDOW},
+        shift::Shift,
+    },
+};
+use secrecy::Secret;
+use services::{configuration::ApplicationSettings, signing};
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::{
+    routes::{clock_in_error, ApiRoutes},
+    utils::{ErrorProps, ErrorPropsCollection, RouteError},
+    views::{components, pages, TemplateRenderer},
+};
+
+/// Holds the kiosk's device session. It is only sent to the kiosk pages, so
+/// a kiosk tablet can't reach the rest of the app with it.
+const KIOSK_COOKIE: &str = "kiosk_session";
+
+/// Whether `pin` can be used as a kiosk PIN
+pub fn is_valid_pin(pin: &str) -> bool {
+    (4..=6).contains(&pin.len()) && pin.chars().all(|c| c.is_ascii_digit())
+}
+
+/// The signature kept for an employee's PIN. The employee is part of what's
+/// signed, so two employees with the same PIN don't share a hash.
+pub fn hash_pin(key: &Secret<String>, employee_id: &Uuid, pin: &str) -> String {
+    signing::sign(key, &pin_subject(employee_id, pin))
+}
+
+fn pin_subject(employee_id: &Uuid, pin: &str) -> String {
+    format!("pin:{}:{}", employee_id, pin)
+}
+
+/// What a kiosk's device session grants access to
+fn session_subject(kiosk_id: &Uuid, jobsite_id: &Uuid) -> String {
+    format!("kiosk:{}:{}", kiosk_id, jobsite_id)
+}
+
+fn session_cookie(
+    settings: &ApplicationSettings,
+    kiosk_id: &Uuid,
+    jobsite_id: &Uuid,
+) -> Cookie<'static> {
+    let token = signing::sign(
+        &settings.signing_key,
+        &session_subject(kiosk_id, jobsite_id),
+    );
+
+    Cookie::build(KIOSK_COOKIE, format!("{}.{}", kiosk_id, token))
+        .path("/kiosk")
+        .http_only(true)
+        .secure(settings.secure)
+        .same_site(SameSite::Strict)
+        .permanent()
+        .finish()
+}
+
+/// The kiosk the request's device session is for, when it was issued for
+/// this jobsite and the kiosk hasn't been revoked since. A kiosk that was
+/// only just registered may not be in the read model yet, its signed
+/// session is trusted until it is.
+async fn kiosk_session(
+    req: &HttpRequest,
+    settings: &ApplicationSettings,
+    transaction: &mut Transaction<'_, Postgres>,
+    jobsite_id: &Uuid,
+) -> Result<Option<Uuid>, RouteError> {
+    let Some(cookie) = req.cookie(KIOSK_COOKIE) else {
+        return Ok(None);
+    };
+
+    let Some((kiosk_id, token)) = cookie.value().split_once('.') else {
+        return Ok(None);
+    };
+
+    let Ok(kiosk_id) = Uuid::parse_str(kiosk_id) else {
+        return Ok(None);
+    };
+
+    if !signing::verify(
+        &settings.signing_key,
+        &session_subject(&kiosk_id, jobsite_id),
+        token,
+    ) {
+        return Ok(None);
+    }
+
+    match Kiosk::get_by_id(transaction, &kiosk_id).await? {
+        Some(kiosk) if kiosk.is_revoked() || kiosk.jobsite_id != *jobsite_id => Ok(None),
+        _ => Ok(Some(kiosk_id)),
+    }
+}
+
+pub async fn get_jobsite_kiosks(
+    db_pool: web::Data<PgPool>,
+    jobsite_id: web::Path<Uuid>,
+) -> Result<HttpResponse, RouteError> {
+    let jobsite_id = jobsite_id.into_inner();
+
+    let mut transaction = db_pool.begin().await?;
+
+    if Jobsite::get_by_id(&mut transaction, &jobsite_id)
+        .await?
+        .is_none()
+    {
+        return Err(RouteError::NotFound);
+    }
+
+    let kiosks = Kiosk::get_active_by_jobsite(&mut transaction, &jobsite_id).await?;
+
+    transaction.commit().await?;
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .body(TemplateRenderer::render(move || {
+            components::jobsite::JobsiteKiosks(components::jobsite::JobsiteKiosksProps {
+                jobsite_id,
+                kiosks,
+            })
+        })))
+}
+
+#[derive(serde::Deserialize)]
+pub struct KioskRegisterData {
+    name: String,
+}
+
+/// Register the device making the request as a kiosk for the jobsite and
+/// send it to the kiosk page
+pub async fn post_jobsite_kiosk(
+    db_pool: web::Data<PgPool>,
+    settings: web::Data<ApplicationSettings>,
+    data: web::Form<KioskRegisterData>,
+    eventstore: web::Data<eventstore::Client>,
+    jobsite_id: web::Path<Uuid>,
+) -> Result<HttpResponse, RouteError> {
+    let jobsite_id = jobsite_id.into_inner();
+    let error_id = format!("jobsite_kiosk_error_{}", jobsite_id);
+    let mut errors = vec![ErrorProps::new(error_id.clone())];
+
+    let mut transaction = db_pool.begin().await?;
+
+    let jobsite = match Jobsite::get_by_id(&mut transaction, &jobsite_id).await? {
+        Some(jobsite) => jobsite,
+        None => return Err(RouteError::NotFound),
+    };
+
+    transaction.commit().await?;
+
+    if jobsite.merged_into_id.is_some() || jobsite.archived_at.is_some() {
+        errors.set_error(&error_id, "Kiosks can only be set up at open jobsites")?;
+
+        return Ok(HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(errors.render_errors()?));
+    }
+
+    let name = data.name.trim().to_string();
+
+    if name.is_empty() {
+        errors.set_error(&error_id, "Name the kiosk so it can be told apart")?;
+
+        return Ok(HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(errors.render_errors()?));
+    }
+
+    let registered_event = JobsiteKioskRegistered {
+        id: jobsite_id,
+        kiosk_id: Uuid::new_v4(),
+        name,
+        registered_at: Utc::now(),
+    };
+
+    let event = EventData::json(JobsiteKioskRegistered::event_name(), &registered_event)
+        .expect("Unable to serialize")
+        .id(Uuid::new_v4());
+
+    eventstore
+        .append_to_stream(
+            format!("jobsite-{}", jobsite_id),
+            &Default::default(),
+            event,
+        )
+        .await
+        .expect("Failed to append event");
+
+    Ok(HttpResponse::Created()
+        .cookie(session_cookie(
+            &settings,
+            &registered_event.kiosk_id,
+            &jobsite_id,
+        ))
+        .insert_header(("HX-Redirect", ApiRoutes::get_kiosk_page(jobsite_id)))
+        .finish())
+}
+
+pub async fn post_jobsite_kiosk_revoke(
+    db_pool: web::Data<PgPool>,
+    eventstore: web::Data<eventstore::Client>,
+    path: web::Path<(Uuid, Uuid)>,
+) -> Result<HttpResponse, RouteError> {
+    let (jobsite_id, kiosk_id) = path.into_inner();
+
+    let mut transaction = db_pool.begin().await?;
+
+    let kiosk = match Kiosk::get_by_id(&mut transaction, &kiosk_id).await? {
+        Some(kiosk) if kiosk.jobsite_id == jobsite_id => kiosk,
+        _ => return Err(RouteError::NotFound),
+    };
+
+    transaction.commit().await?;
+
+    if kiosk.is_revoked() {
+        return Err(RouteError::UnexpectedError(anyhow::anyhow!(
+            "This kiosk has already been revoked"
+        )));
+    }
+
+    let revoked_event = JobsiteKioskRevoked {
+        id: jobsite_id,
+        kiosk_id,
+        revoked_at: Utc::now(),
+    };
+
+    let event = EventData::json(JobsiteKioskRevoked::event_name(), &revoked_event)
+        .expect("Unable to serialize")
+        .id(Uuid::new_v4());
+
+    eventstore
+        .append_to_stream(
+            format!("jobsite-{}", jobsite_id),
+            &Default::default(),
+            event,
+        )
+        .await
+        .expect("Failed to append event");
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .finish())
+}
+
+pub async fn get_kiosk_page(
+    req: HttpRequest,
+    db_pool: web::Data<PgPool>,
+    settings: web::Data<ApplicationSettings>,
+    jobsite_id: web::Path<Uuid>,
+) -> Result<HttpResponse, RouteError> {
+    let jobsite_id = jobsite_id.into_inner();
+
+    let mut transaction = db_pool.begin().await?;
+
+    let jobsite = match Jobsite::get_by_id(&mut transaction, &jobsite_id).await? {
+        Some(jobsite) => jobsite,
+        None => return Err(RouteError::NotFound),
+    };
+
+    if kiosk_session(&req, &settings, &mut transaction, &jobsite_id)
+        .await?
+        .is_none()
+    {
+        return Ok(HttpResponse::Forbidden()
+            .content_type("text/html; charset=utf-8")
+            .body(TemplateRenderer::render(pages::KioskNotRegistered)));
+    }
+
+    let employees =
+        Employee::get_list_with_pin_active_on(&mut transaction, jobsite.local_date(Utc::now()))
+            .await?;
+
+    transaction.commit().await?;
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .body(TemplateRenderer::render(move || {
+            pages::Kiosk(pages::KioskProps { jobsite, employees })
+        })))
+}
+
+#[derive(serde::Deserialize)]
+pub struct KioskPunchData {
+    employee_id: Uuid,
+    pin: String,
+}
+
+fn punch_response(message: String, success: bool) -> HttpResponse {
+    HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .body(TemplateRenderer::render(move || {
+            components::kiosk::KioskPunchResult(components::kiosk::KioskPunchResultProps {
+                message,
+                success,
+            })
+        }))
+}
+
+/// Clock the employee out if they're on the clock at this jobsite, otherwise
+/// clock them in. Wrong PINs are counted against both the employee and the
+/// kiosk, and once either has had too many the kiosk stops taking PINs for
+/// a while.
+pub async fn post_kiosk_punch(
+    req: HttpRequest,
+    db_pool: web::Data<PgPool>,
+    settings: web::Data<ApplicationSettings>,
+    data: web::Form<KioskPunchData>,
+    eventstore: web::Data<eventstore::Client>,
+    jobsite_id: web::Path<Uuid>,
+) -> Result<HttpResponse, RouteError> {
+    let jobsite_id = jobsite_id.into_inner();
+
+    let mut transaction = db_pool.begin().await?;
+
+    let jobsite = match Jobsite::get_by_id(&mut transaction, &jobsite_id).await? {
+        Some(jobsite) => jobsite,
+        None => return Err(RouteError::NotFound),
+    };
+
+    let Some(kiosk_id) = kiosk_session(&req, &settings, &mut transaction, &jobsite_id).await?
+    else {
+        return Ok(HttpResponse::Forbidden().finish());
+    };
+
+    let now = Utc::now();
+
+    let (employee_failures, kiosk_failures) = Kiosk::count_pin_failures(
+        &mut transaction,
+        &kiosk_id,
+        &data.employee_id,
+        now - PIN_FAILURE_WINDOW,
+    )
+    .await?;
+
+    if !accepts_pins(employee_failures, kiosk_failures) {
+        return Ok(punch_response(
+            "Too many wrong PINs, try again later or ask a supervisor".to_string(),
+            false,
+        ));
+    }
+
+    let pin_hash = Employee::get_pin_hash(&mut transaction, &data.employee_id).await?;
+
+    let pin_matches = pin_hash.is_some_and(|pin_hash| {
+        signing::verify(
+            &settings.signing_key,
+            &pin_subject(&data.employee_id, data.pin.trim()),
+            &pin_hash,
+        )
+    });
+
+    if !pin_matches {
+        Kiosk::record_pin_failure(&mut transaction, &kiosk_id, &data.employee_id, now).await?;
+        transaction.commit().await?;
+
+        return Ok(punch_response("Wrong PIN".to_string(), false));
+    }
+
+    Kiosk::clear_pin_failures(&mut transaction, &kiosk_id, &data.employee_id).await?;
+
+    let employee = match Employee::get_by_id(&mut transaction, &data.employee_id).await? {
+        Some(employee) => employee,
+        None => return Err(RouteError::NotFound),
+    };
+
+    let open_shift = Shift::get_open_by_employee(&mut transaction, &data.employee_id).await?;
+
+    let metadata = KioskMetadata { kiosk_id };
+    let local_time = now.with_timezone(&jobsite.tz()).format("%-I:%M %p");
+
+    match open_shift {
+        Some(shift) if shift.jobsite_id == jobsite_id => {
+            transaction.commit().await?;
+
+            let clocked_out_event = ClockedOut {
+                shift_id: shift.id,
+                employee_id: data.employee_id,
+                clocked_out_at: now,
+            };
+
+            let event = EventData::json(ClockedOut::event_name(), &clocked_out_event)
+                .expect("Unable to serialize")
+                .metadata_as_json(metadata)
+                .expect("Unable to serialize");
+
+            let punch = Punch::Out { shift_id: shift.id };
+
+            if let Err(conflict) = punch
+                .append(&eventstore, &data.employee_id, event, Uuid::new_v4())
+                .await?
+            {
+                return Ok(punch_response(conflict.to_string(), false));
+            }
+
+            Ok(punch_response(
+                format!("{} clocked out at {}", employee.name, local_time),
+                true,
+            ))
+        }
+        Some(_) => {
+            transaction.commit().await?;
+
+            Ok(punch_response(
+                format!(
+                    "{} is on the clock at another jobsite, clock out there first",
+                    employee.name
+                ),
+                false,
+            ))
+        }
+        None => {
+            let error =
+                clock_in_error(&mut transaction, &jobsite, &data.employee_id, false, now).await?;
+
+            transaction.commit().await?;
+
+            if let Some(error) = error {
+                return Ok(punch_response(error.to_string(), false));
+            }
+
+            // The tablet stays at the jobsite, so there is nothing to place
+            let clocked_in_event = ClockedIn {
+                shift_id: Uuid::new_v4(),
+                employee_id: data.employee_id,
+                jobsite_id,
+                cost_code_id: None,
+                clocked_in_at: now,
+                time_zone: jobsite.time_zone.clone(),
+                coordinates: None,
+                geofence: GeofenceCheck::default(),
+            };
+
+            let event = EventData::json(ClockedIn::event_name(), &clocked_in_event)
+                .expect("Unable to serialize")
+                .metadata_as_json(metadata)
+                .expect("Unable to serialize");
+
+            if let Err(conflict) = Punch::In
+                .append(&eventstore, &data.employee_id, event, Uuid::new_v4())
+                .await?
+            {
+                return Ok(punch_response(conflict.to_string(), false));
+            }
+
+            Ok(punch_response(
+                format!("{} clocked in at {}", employee.name, local_time),
+                true,
+            ))
+        }
+    }
+}
@@ -0,0 +1,388 @@
+use actix_web::{web, HttpResponse};
+use chrono::{Datelike, Duration, NaiveDate, Utc};
+use eventstore::EventData;
+use models::{
+    events::leave::{LeaveApproved, LeaveDenied, LeaveRequested},
+    projections::{
+        employee::Employee,
+        leave::{Leave, LeaveBalance, LeaveStatus, LeaveType, LEAVE_DAY_MINUTES},
+        timesheet::Timesheet,
+    },
+    AppState,
+};
+use serde::Serialize;
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::{
+    utils::{form_choice, ErrorProps, ErrorPropsCollection, RouteError},
+    views::{components, format_minutes, pages, TemplateRenderer},
+};
+
+pub async fn get_leave_page(
+    db_pool: web::Data<PgPool>,
+    state: web::Data<AppState>,
+) -> Result<HttpResponse, RouteError> {
+    let today = state.today();
+
+    let mut transaction = db_pool.begin().await?;
+
+    let leave = Leave::get_list(&mut transaction).await?;
+    let balances = LeaveBalance::get_list(&mut transaction, today.year()).await?;
+    let employees = Employee::get_list_active_on(&mut transaction, today).await?;
+
+    transaction.commit().await?;
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .body(TemplateRenderer::render(move || {
+            pages::LeaveRequests(pages::LeaveRequestsProps {
+                leave,
+                balances,
+                employees,
+                today,
+            })
+        })))
+}
+
+async fn append_leave_event<T: Serialize>(
+    eventstore: &eventstore::Client,
+    employee_id: &Uuid,
+    event_name: String,
+    event: &T,
+) {
+    let event = EventData::json(event_name, event)
+        .expect("Unable to serialize")
+        .id(Uuid::new_v4());
+
+    eventstore
+        .append_to_stream(Leave::stream_name(employee_id), &Default::default(), event)
+        .await
+        .expect("Failed to append event");
+}
+
+/// The first week from `start_date` to `end_date` whose timesheet has been
+/// approved, paid leave can't be added to hours that are already signed off
+async fn get_locked_week(
+    transaction: &mut Transaction<'_, Postgres>,
+    employee_id: &Uuid,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+) -> Result<Option<NaiveDate>, sqlx::Error> {
+    let mut week_start = Timesheet::week_start_of(start_date);
+
+    while week_start <= end_date {
+        if Timesheet::is_locked(transaction, employee_id, week_start).await? {
+            return Ok(Some(week_start));
+        }
+        week_start += Duration::days(7);
+    }
+
+    Ok(None)
+}
+
+#[derive(serde::Deserialize)]
+pub struct LeaveData {
+    employee_id: String,
+    leave_type: String,
+    start_date: String,
+    #[serde(default)]
+    end_date: String,
+    #[serde(default)]
+    hours_per_day: String,
+    #[serde(default)]
+    note: String,
+}
+
+pub async fn post_leave(
+    db_pool: web::Data<PgPool>,
+    data: web::Form<LeaveData>,
+    eventstore: web::Data<eventstore::Client>,
+) -> Result<HttpResponse, RouteError> {
+    let mut errors = vec![
+        ErrorProps::new("leave-employee-error".to_string()),
+        ErrorProps::new("leave-date-error".to_string()),
+        ErrorProps::new("leave-hours-error".to_string()),
+    ];
+
+    let leave_type = form_choice(
+        LeaveType::ALL,
+        LeaveType::as_str,
+        &data.leave_type,
+        "leave type",
+    )?;
+
+    let start_date = NaiveDate::parse_from_str(&data.start_date, "%Y-%m-%d").ok();
+    let end_date = NaiveDate::parse_from_str(&data.end_date, "%Y-%m-%d")
+        .ok()
+        .or(start_date);
+
+    let (Some(start_date), Some(end_date)) = (start_date, end_date) else {
+        errors.set_error("leave-date-error", "A start date is required")?;
+
+        return Ok(HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(errors.render_errors()?));
+    };
+
+    // Balances are per calendar year, so a request has to fall in one
+    let leave_dates = Leave::leave_dates(start_date, end_date);
+    if end_date < start_date {
+        errors.set_error(
+            "leave-date-error",
+            "The end date can't be before the start date",
+        )?;
+    } else if end_date.year() != start_date.year() {
+        errors.set_error(
+            "leave-date-error",
+            "Leave over the new year has to be requested for each year",
+        )?;
+    } else if leave_dates.is_empty() {
+        errors.set_error("leave-date-error", "Leave is only taken on weekdays")?;
+    }
+
+    let minutes_per_day = match data.hours_per_day.trim() {
+        "" => Some(LEAVE_DAY_MINUTES),
+        hours => hours
+            .parse::<f64>()
+            .ok()
+            .map(|hours| (hours * 60.0).round() as i64)
+            .filter(|minutes| *minutes > 0 && *minutes <= LEAVE_DAY_MINUTES),
+    };
+    let Some(minutes_per_day) = minutes_per_day else {
+        errors.set_error(
+            "leave-hours-error",
+            &format!(
+                "Hours a day have to be more than 0 and at most {}",
+                LEAVE_DAY_MINUTES / 60
+            ),
+        )?;
+
+        return Ok(HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(errors.render_errors()?));
+    };
+
+    if errors.has_errors() {
+        return Ok(HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(errors.render_errors()?));
+    }
+
+    let mut transaction = db_pool.begin().await?;
+
+    let employee = match Uuid::parse_str(&data.employee_id) {
+        Ok(employee_id) => Employee::get_by_id(&mut transaction, &employee_id).await?,
+        Err(_) => None,
+    };
+
+    let Some(employee) = employee else {
+        errors.set_error("leave-employee-error", "Choose an employee")?;
+
+        return Ok(HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(errors.render_errors()?));
+    };
+
+    if employee.is_terminated_on(end_date) {
+        errors.set_error(
+            "leave-employee-error",
+            &format!("{} won't be employed by then", employee.name),
+        )?;
+    }
+
+    if let Some(leave) =
+        Leave::get_overlapping(&mut transaction, &employee.id, start_date, end_date)
+            .await?
+            .first()
+    {
+        errors.set_error(
+            "leave-date-error",
+            &format!(
+                "{} already has {} leave from {} to {}",
+                employee.name,
+                leave.status.label().to_lowercase(),
+                leave.start_date.format("%b %-d"),
+                leave.end_date.format("%b %-d")
+            ),
+        )?;
+    }
+
+    if let Some(week_start) =
+        get_locked_week(&mut transaction, &employee.id, start_date, end_date).await?
+    {
+        errors.set_error(
+            "leave-date-error",
+            &format!(
+                "The timesheet for the week of {} has already been approved",
+                week_start.format("%b %-d")
+            ),
+        )?;
+    }
+
+    let total_minutes = Leave::total_minutes(start_date, end_date, minutes_per_day);
+    let balance = LeaveBalance::get(
+        &mut transaction,
+        &employee.id,
+        leave_type,
+        start_date.year(),
+    )
+    .await?;
+
+    if total_minutes > balance.available_minutes() {
+        errors.set_error(
+            "leave-hours-error",
+            &format!(
+                "{} only has {} of {} leave left in {}",
+                employee.name,
+                format_minutes(balance.available_minutes().max(0)),
+                leave_type.label().to_lowercase(),
+                start_date.year()
+            ),
+        )?;
+    }
+
+    transaction.commit().await?;
+
+    if errors.has_errors() {
+        return Ok(HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(errors.render_errors()?));
+    }
+
+    let note = data.note.trim();
+    let requested_event = LeaveRequested {
+        id: Uuid::new_v4(),
+        employee_id: employee.id,
+        leave_type,
+        start_date,
+        end_date,
+        minutes_per_day,
+        note: (!note.is_empty()).then(|| note.to_string()),
+        requested_at: Utc::now(),
+    };
+
+    append_leave_event(
+        &eventstore,
+        &employee.id,
+        LeaveRequested::event_name(),
+        &requested_event,
+    )
+    .await;
+
+    Ok(HttpResponse::Created()
+        .content_type("text/html; charset=utf-8")
+        .body(errors.render_errors()?))
+}
+
+/// Load a leave request that's still waiting on a decision
+async fn get_requested_leave(db_pool: &PgPool, leave_id: &Uuid) -> Result<Leave, RouteError> {
+    let mut transaction = db_pool.begin().await?;
+
+    let leave = match Leave::get_by_id(&mut transaction, leave_id).await? {
+        Some(leave) => leave,
+        None => return Err(RouteError::NotFound),
+    };
+
+    transaction.commit().await?;
+
+    if leave.status != LeaveStatus::Requested {
+        return Err(RouteError::UnexpectedError(anyhow::anyhow!(
+            "This leave has already been {}",
+            leave.status.label().to_lowercase()
+        )));
+    }
+
+    Ok(leave)
+}
+
+pub async fn post_leave_approve(
+    db_pool: web::Data<PgPool>,
+    eventstore: web::Data<eventstore::Client>,
+    leave_id: web::Path<Uuid>,
+) -> Result<HttpResponse, RouteError> {
+    let leave = get_requested_leave(&db_pool, &leave_id).await?;
+
+    // The week may have been signed off since the leave was requested
+    let mut transaction = db_pool.begin().await?;
+
+    let locked_week = get_locked_week(
+        &mut transaction,
+        &leave.employee_id,
+        leave.start_date,
+        leave.end_date,
+    )
+    .await?;
+
+    transaction.commit().await?;
+
+    if let Some(week_start) = locked_week {
+        return Err(RouteError::UnexpectedError(anyhow::anyhow!(
+            "The timesheet for the week of {} has already been approved",
+            week_start.format("%b %-d")
+        )));
+    }
+
+    let approved_event = LeaveApproved {
+        id: leave.id,
+        employee_id: leave.employee_id,
+        approved_at: Utc::now(),
+    };
+
+    append_leave_event(
+        &eventstore,
+        &leave.employee_id,
+        LeaveApproved::event_name(),
+        &approved_event,
+    )
+    .await;
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .finish())
+}
+
+#[derive(serde::Deserialize)]
+pub struct LeaveDenyData {
+    reason: String,
+}
+
+pub async fn post_leave_deny(
+    db_pool: web::Data<PgPool>,
+    data: web::Form<LeaveDenyData>,
+    eventstore: web::Data<eventstore::Client>,
+    leave_id: web::Path<Uuid>,
+) -> Result<HttpResponse, RouteError> {
+    let leave = get_requested_leave(&db_pool, &leave_id).await?;
+
+    let error_id = components::leave::leave_action_error_id(&leave.id);
+    let mut errors = vec![ErrorProps::new(error_id.clone())];
+
+    let reason = data.reason.trim();
+    if reason.is_empty() {
+        errors.set_error(&error_id, "A reason is required")?;
+
+        return Ok(HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(errors.render_errors()?));
+    }
+
+    let denied_event = LeaveDenied {
+        id: leave.id,
+        employee_id: leave.employee_id,
+        reason: reason.to_string(),
+        denied_at: Utc::now(),
+    };
+
+    append_leave_event(
+        &eventstore,
+        &leave.employee_id,
+        LeaveDenied::event_name(),
+        &denied_event,
+    )
+    .await;
+
+    Ok(HttpResponse::Created()
+        .content_type("text/html; charset=utf-8")
+        .body(errors.render_errors()?))
+}
@@ -0,0 +1,246 @@
+use actix_web::{web, HttpResponse};
+use chrono::{NaiveDate, Utc};
+use eventstore::EventData;
+use leptos::view;
+use models::{
+    events::material::{MaterialAdded, MaterialConsumed},
+    projections::{
+        jobsite::Jobsite,
+        material::{JobsiteMaterialUsage, Material, MaterialUnit},
+    },
+};
+use serde::Serialize;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use super::pay_rate::parse_cents;
+use crate::{
+    utils::{form_choice, ErrorProps, ErrorPropsCollection, RouteError},
+    views::{components, pages, TemplateRenderer},
+};
+
+pub async fn get_materials_page(db_pool: web::Data<PgPool>) -> Result<HttpResponse, RouteError> {
+    let mut transaction = db_pool.begin().await?;
+
+    let materials = Material::get_list(&mut transaction).await?;
+
+    transaction.commit().await?;
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .body(TemplateRenderer::render(move || {
+            pages::Materials(pages::MaterialsProps { materials })
+        })))
+}
+
+pub async fn get_jobsite_materials(
+    db_pool: web::Data<PgPool>,
+    jobsite_id: web::Path<Uuid>,
+) -> Result<HttpResponse, RouteError> {
+    let jobsite_id = jobsite_id.into_inner();
+
+    let mut transaction = db_pool.begin().await?;
+
+    let Some(jobsite) = Jobsite::get_by_id(&mut transaction, &jobsite_id).await? else {
+        return Err(RouteError::NotFound);
+    };
+
+    let usage = JobsiteMaterialUsage::get_by_jobsite(&mut transaction, &jobsite_id).await?;
+    let materials = Material::get_list(&mut transaction).await?;
+
+    transaction.commit().await?;
+
+    let today = jobsite.local_date(Utc::now());
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .body(TemplateRenderer::render(move || {
+            view! {
+                <components::material::JobsiteMaterials jobsite_id=jobsite_id usage=usage />
+                <components::material::MaterialConsume
+                    jobsite_id=jobsite_id
+                    materials=materials
+                    today=today
+                />
+            }
+        })))
+}
+
+async fn append_material_event<T: Serialize>(
+    eventstore: &eventstore::Client,
+    material_id: &Uuid,
+    event_name: String,
+    event: &T,
+) {
+    let event = EventData::json(event_name, event)
+        .expect("Unable to serialize")
+        .id(Uuid::new_v4());
+
+    eventstore
+        .append_to_stream(
+            Material::stream_name(material_id),
+            &Default::default(),
+            event,
+        )
+        .await
+        .expect("Failed to append event");
+}
+
+#[derive(serde::Deserialize)]
+pub struct MaterialData {
+    name: String,
+    unit: String,
+    #[serde(default)]
+    unit_cost: String,
+}
+
+pub async fn post_material(
+    db_pool: web::Data<PgPool>,
+    data: web::Form<MaterialData>,
+    eventstore: web::Data<eventstore::Client>,
+) -> Result<HttpResponse, RouteError> {
+    let mut errors = vec![
+        ErrorProps::new("material-name-error".to_string()),
+        ErrorProps::new("material-cost-error".to_string()),
+    ];
+
+    let unit = form_choice(MaterialUnit::ALL, MaterialUnit::as_str, &data.unit, "unit")?;
+
+    let name = data.name.trim();
+    if name.is_empty() {
+        errors.set_error("material-name-error", "A name is required")?;
+    } else {
+        let mut transaction = db_pool.begin().await?;
+
+        if let Some(material) = Material::get_by_name(&mut transaction, name).await? {
+            errors.set_error(
+                "material-name-error",
+                &format!("{} is already in the catalog", material.name),
+            )?;
+        }
+
+        transaction.commit().await?;
+    }
+
+    let unit_cost_cents = match data.unit_cost.trim() {
+        "" => None,
+        unit_cost => {
+            let unit_cost_cents = parse_cents(unit_cost);
+            if unit_cost_cents.is_none() {
+                errors.set_error("material-cost-error", "Enter a cost like 12.50")?;
+            }
+            unit_cost_cents
+        }
+    };
+
+    if errors.has_errors() {
+        return Ok(HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(errors.render_errors()?));
+    }
+
+    let added_event = MaterialAdded {
+        id: Uuid::new_v4(),
+        name: name.to_string(),
+        unit,
+        unit_cost_cents,
+        added_at: Utc::now(),
+    };
+
+    append_material_event(
+        &eventstore,
+        &added_event.id,
+        MaterialAdded::event_name(),
+        &added_event,
+    )
+    .await;
+
+    Ok(HttpResponse::Created()
+        .content_type("text/html; charset=utf-8")
+        .body(errors.render_errors()?))
+}
+
+#[derive(serde::Deserialize)]
+pub struct MaterialConsumeData {
+    material_id: String,
+    quantity: String,
+    consumed_on: String,
+}
+
+pub async fn post_jobsite_material(
+    db_pool: web::Data<PgPool>,
+    data: web::Form<MaterialConsumeData>,
+    eventstore: web::Data<eventstore::Client>,
+    jobsite_id: web::Path<Uuid>,
+) -> Result<HttpResponse, RouteError> {
+    let jobsite_id = jobsite_id.into_inner();
+
+    let error_id = components::material::jobsite_material_error_id(&jobsite_id);
+    let mut errors = vec![ErrorProps::new(error_id.clone())];
+
+    let mut transaction = db_pool.begin().await?;
+
+    let Some(jobsite) = Jobsite::get_by_id(&mut transaction, &jobsite_id).await? else {
+        return Err(RouteError::NotFound);
+    };
+
+    let material = match Uuid::parse_str(&data.material_id) {
+        Ok(material_id) => Material::get_by_id(&mut transaction, &material_id).await?,
+        Err(_) => None,
+    };
+
+    transaction.commit().await?;
+
+    let Some(material) = material else {
+        errors.set_error(&error_id, "Choose a material")?;
+
+        return Ok(HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(errors.render_errors()?));
+    };
+
+    let quantity = data
+        .quantity
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|quantity| quantity.is_finite() && *quantity > 0.0);
+    let consumed_on = NaiveDate::parse_from_str(&data.consumed_on, "%Y-%m-%d").ok();
+
+    let (Some(quantity), Some(consumed_on)) = (quantity, consumed_on) else {
+        errors.set_error(&error_id, "A quantity more than 0 and a date are required")?;
+
+        return Ok(HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(errors.render_errors()?));
+    };
+
+    if consumed_on > jobsite.local_date(Utc::now()) {
+        errors.set_error(&error_id, "Materials can't be used in the future")?;
+
+        return Ok(HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(errors.render_errors()?));
+    }
+
+    let consumed_event = MaterialConsumed {
+        id: material.id,
+        consumption_id: Uuid::new_v4(),
+        jobsite_id,
+        quantity,
+        consumed_on,
+        recorded_at: Utc::now(),
+    };
+
+    append_material_event(
+        &eventstore,
+        &material.id,
+        MaterialConsumed::event_name(),
+        &consumed_event,
+    )
+    .await;
+
+    Ok(HttpResponse::Created()
+        .content_type("text/html; charset=utf-8")
+        .body(errors.render_errors()?))
+}
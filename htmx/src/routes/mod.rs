@@ -1,11 +1,60 @@
 use actix_web::HttpResponse;
 
+mod attachment;
+mod calendar;
+mod certification;
+mod change_order;
+mod client;
+mod comment;
+mod cost_code;
+mod crew_assignment;
+mod daily_log;
+mod employee;
+mod equipment;
 mod health_check;
+mod incident;
 mod jobsite;
+mod jobsite_budget;
+mod kiosk;
+mod leave;
+mod material;
+mod pay_rate;
+mod project;
+mod qr_code;
+mod shift;
+mod sync;
+mod timesheet;
 mod websocket;
 
+use actix_web::web;
+use chrono::NaiveDate;
+pub use attachment::*;
+pub use calendar::*;
+pub use certification::*;
+pub use change_order::*;
+pub use client::*;
+pub use comment::*;
+pub use cost_code::*;
+pub use crew_assignment::*;
+pub use daily_log::*;
+pub use employee::*;
+pub use equipment::*;
 pub use health_check::*;
+pub use incident::*;
 pub use jobsite::*;
+pub use jobsite_budget::*;
+pub use kiosk::*;
+pub use leave::*;
+pub use material::*;
+use models::projections::project::Project;
+pub use pay_rate::*;
+pub use project::*;
+pub use qr_code::*;
+use services::configuration::ApplicationSettings;
+pub use shift::*;
+use sqlx::PgPool;
+pub use sync::*;
+pub use timesheet::*;
 use uuid::Uuid;
 pub use websocket::*;
 
@@ -14,11 +63,27 @@ use crate::{
     views::{pages, TemplateRenderer},
 };
 
-#[tracing::instrument(name = "Landing page")]
-pub async fn get_landing_page() -> Result<HttpResponse, RouteError> {
+#[tracing::instrument(name = "Landing page", skip(db_pool, settings))]
+pub async fn get_landing_page(
+    db_pool: web::Data<PgPool>,
+    settings: web::Data<ApplicationSettings>,
+) -> Result<HttpResponse, RouteError> {
+    let mut transaction = db_pool.begin().await?;
+
+    let projects = Project::get_list(&mut transaction).await?;
+
+    transaction.commit().await?;
+
+    let time_zone = settings.time_zone.clone();
+
     Ok(HttpResponse::Ok()
         .content_type("text/html; charset=utf-8")
-        .body(TemplateRenderer::render(pages::Landing)))
+        .body(TemplateRenderer::render(move || {
+            pages::Landing(pages::LandingProps {
+                projects,
+                time_zone,
+            })
+        })))
 }
 
 #[tracing::instrument(name = "Not Found (404) page")]
@@ -54,4 +119,556 @@ impl ApiRoutes {
     pub fn put_jobsite(jobsite_id: Uuid) -> String {
         format!("/jobsite/{jobsite_id}")
     }
+
+    /// Route: `GET /jobsite/:id/project`
+    /// Get the project a jobsite belongs to, and the projects it can move to
+    pub fn get_jobsite_project(jobsite_id: Uuid) -> String {
+        format!("/jobsite/{jobsite_id}/project")
+    }
+
+    /// Route: `POST /jobsite/:id/project`
+    /// Move a jobsite under a project
+    pub fn post_jobsite_project(jobsite_id: Uuid) -> String {
+        format!("/jobsite/{jobsite_id}/project")
+    }
+
+    /// Route: `GET /jobsite/:id/merge`
+    /// Get the form to merge a duplicate jobsite into another
+    pub fn get_jobsite_merge(jobsite_id: Uuid) -> String {
+        format!("/jobsite/{jobsite_id}/merge")
+    }
+
+    /// Route: `POST /jobsite/:id/merge`
+    /// Merge a duplicate jobsite into another
+    pub fn post_jobsite_merge(jobsite_id: Uuid) -> String {
+        format!("/jobsite/{jobsite_id}/merge")
+    }
+
+    /// Route: `POST /jobsite/:id/unmerge`
+    /// Undo a jobsite's merge into another
+    pub fn post_jobsite_unmerge(jobsite_id: Uuid) -> String {
+        format!("/jobsite/{jobsite_id}/unmerge")
+    }
+
+    /// Route: `POST /jobsite/:id/archive`
+    /// Archive a jobsite once work there is over
+    pub fn post_jobsite_archive(jobsite_id: Uuid) -> String {
+        format!("/jobsite/{jobsite_id}/archive")
+    }
+
+    /// Route: `GET /jobsite/:id/daily-logs`
+    /// A jobsite's daily logs, most recent day first
+    pub fn get_daily_logs_page(jobsite_id: Uuid) -> String {
+        format!("/jobsite/{jobsite_id}/daily-logs")
+    }
+
+    /// Route: `POST /jobsite/:id/daily-log`
+    /// Write or rewrite a day's log
+    pub fn post_daily_log(jobsite_id: Uuid) -> String {
+        format!("/jobsite/{jobsite_id}/daily-log")
+    }
+
+    /// Route: `POST /jobsite/:id/daily-log/:log_date/delay`
+    /// Record time lost on a logged day
+    pub fn post_daily_log_delay(jobsite_id: Uuid, log_date: NaiveDate) -> String {
+        format!("/jobsite/{jobsite_id}/daily-log/{log_date}/delay")
+    }
+
+    /// Route: `POST /jobsite/:id/daily-log/:log_date/submit`
+    /// Submit a day's log, locking it
+    pub fn post_daily_log_submit(jobsite_id: Uuid, log_date: NaiveDate) -> String {
+        format!("/jobsite/{jobsite_id}/daily-log/{log_date}/submit")
+    }
+
+    /// Route: `POST /jobsite/:id/daily-log/:log_date/attachment`
+    /// Attach a photo or PDF to a day's log
+    pub fn post_daily_log_attachment(jobsite_id: Uuid, log_date: NaiveDate) -> String {
+        format!("/jobsite/{jobsite_id}/daily-log/{log_date}/attachment")
+    }
+
+    /// Route: `GET /jobsite/:id/attachments`
+    /// Get a jobsite's attachments and the form to add more
+    pub fn get_jobsite_attachments(jobsite_id: Uuid) -> String {
+        format!("/jobsite/{jobsite_id}/attachments")
+    }
+
+    /// Route: `POST /jobsite/:id/attachment`
+    /// Attach a photo or PDF to a jobsite
+    pub fn post_jobsite_attachment(jobsite_id: Uuid) -> String {
+        format!("/jobsite/{jobsite_id}/attachment")
+    }
+
+    /// Route: `GET /attachment/:id`
+    /// Download an attachment's file
+    pub fn get_attachment(attachment_id: Uuid) -> String {
+        format!("/attachment/{attachment_id}")
+    }
+
+    /// Route: `GET /attachment/:id/thumbnail`
+    /// A small PNG preview of an image attachment
+    pub fn get_attachment_thumbnail(attachment_id: Uuid) -> String {
+        format!("/attachment/{attachment_id}/thumbnail")
+    }
+
+    /// Route: `POST /attachment/:id/remove`
+    /// Take an attachment off its jobsite or log
+    pub fn post_attachment_remove(attachment_id: Uuid) -> String {
+        format!("/attachment/{attachment_id}/remove")
+    }
+
+    /// Route: `GET /jobsite/:id/certifications`
+    /// Get the certifications a jobsite requires its crew to hold
+    pub fn get_jobsite_certifications(jobsite_id: Uuid) -> String {
+        format!("/jobsite/{jobsite_id}/certifications")
+    }
+
+    /// Route: `POST /jobsite/:id/certifications`
+    /// Set the certifications a jobsite requires
+    pub fn post_jobsite_certifications(jobsite_id: Uuid) -> String {
+        format!("/jobsite/{jobsite_id}/certifications")
+    }
+
+    /// Route: `GET /jobsite/:id/geofence`
+    /// Get the area around a jobsite clock-ins are checked against
+    pub fn get_jobsite_geofence(jobsite_id: Uuid) -> String {
+        format!("/jobsite/{jobsite_id}/geofence")
+    }
+
+    /// Route: `POST /jobsite/:id/geofence`
+    /// Set or clear a jobsite's geofence
+    pub fn post_jobsite_geofence(jobsite_id: Uuid) -> String {
+        format!("/jobsite/{jobsite_id}/geofence")
+    }
+
+    /// Route: `GET /jobsite/:id/kiosks`
+    /// Get the kiosks registered at a jobsite
+    pub fn get_jobsite_kiosks(jobsite_id: Uuid) -> String {
+        format!("/jobsite/{jobsite_id}/kiosks")
+    }
+
+    /// Route: `POST /jobsite/:id/kiosk`
+    /// Register the requesting device as a kiosk for a jobsite
+    pub fn post_jobsite_kiosk(jobsite_id: Uuid) -> String {
+        format!("/jobsite/{jobsite_id}/kiosk")
+    }
+
+    /// Route: `POST /jobsite/:id/kiosk/:kiosk_id/revoke`
+    /// Stop a kiosk from clocking anyone in or out
+    pub fn post_jobsite_kiosk_revoke(jobsite_id: Uuid, kiosk_id: Uuid) -> String {
+        format!("/jobsite/{jobsite_id}/kiosk/{kiosk_id}/revoke")
+    }
+
+    /// Route: `GET /kiosk/:jobsite_id`
+    /// The kiosk page crews clock in and out on with their PINs
+    pub fn get_kiosk_page(jobsite_id: Uuid) -> String {
+        format!("/kiosk/{jobsite_id}")
+    }
+
+    /// Route: `POST /kiosk/:jobsite_id/punch`
+    /// Clock an employee in or out at a kiosk with their PIN
+    pub fn post_kiosk_punch(jobsite_id: Uuid) -> String {
+        format!("/kiosk/{jobsite_id}/punch")
+    }
+
+    /// Route: `GET /jobsite/:id/qr-code`
+    /// Get the printable clock-in QR code panel for a jobsite
+    pub fn get_jobsite_qr_code(jobsite_id: Uuid) -> String {
+        format!("/jobsite/{jobsite_id}/qr-code")
+    }
+
+    /// Route: `GET /jobsite/:id/qr-code.svg`
+    /// A jobsite's clock-in QR code as an SVG
+    pub fn get_jobsite_qr_code_svg(jobsite_id: Uuid) -> String {
+        format!("/jobsite/{jobsite_id}/qr-code.svg")
+    }
+
+    /// Route: `GET /jobsite/:id/qr-code.png`
+    /// A jobsite's clock-in QR code as a PNG
+    pub fn get_jobsite_qr_code_png(jobsite_id: Uuid) -> String {
+        format!("/jobsite/{jobsite_id}/qr-code.png")
+    }
+
+    /// Route: `GET /clock-in/:jobsite_id?expires=:expires&token=:token`
+    /// The clock-in form a jobsite's QR code opens, behind a signed link
+    pub fn get_jobsite_clock_in_page(jobsite_id: Uuid, expires: i64, token: &str) -> String {
+        format!("/clock-in/{jobsite_id}?expires={expires}&token={token}")
+    }
+
+    /// Route: `POST /clock-in/:jobsite_id`
+    /// Clock in from a jobsite's QR code, the form carries the signed link
+    pub fn post_jobsite_clock_in(jobsite_id: Uuid) -> String {
+        format!("/clock-in/{jobsite_id}")
+    }
+
+    /// Route: `GET /jobsite/:id/calendar-link`
+    /// Get the subscription link for a jobsite's crew calendar
+    pub fn get_jobsite_calendar_link(jobsite_id: Uuid) -> String {
+        format!("/jobsite/{jobsite_id}/calendar-link")
+    }
+
+    /// Route: `GET /calendar/jobsite/:id/:token/crew.ics`
+    /// A jobsite's crew assignments as an iCalendar feed
+    pub fn get_jobsite_calendar(jobsite_id: Uuid, token: &str) -> String {
+        format!("/calendar/jobsite/{jobsite_id}/{token}/crew.ics")
+    }
+
+    /// Route: `GET /jobsite/:id/on-site`
+    /// Get the employees currently on site at a jobsite
+    pub fn get_jobsite_on_site(jobsite_id: Uuid) -> String {
+        format!("/jobsite/{jobsite_id}/on-site")
+    }
+
+    /// Route: `GET /jobsite/:id/labor-cost`
+    /// Get the labor cost summary for a jobsite
+    pub fn get_jobsite_labor_cost(jobsite_id: Uuid) -> String {
+        format!("/jobsite/{jobsite_id}/labor-cost")
+    }
+
+    /// Route: `GET /jobsite/:id/budget`
+    /// Get a jobsite's budgets compared to actuals, and any alerts raised
+    pub fn get_jobsite_budget(jobsite_id: Uuid) -> String {
+        format!("/jobsite/{jobsite_id}/budget")
+    }
+
+    /// Route: `POST /jobsite/:id/budget`
+    /// Set a budget for a jobsite or one of its cost codes
+    pub fn post_jobsite_budget(jobsite_id: Uuid) -> String {
+        format!("/jobsite/{jobsite_id}/budget")
+    }
+
+    /// Route: `GET /jobsite/:id/change-orders`
+    /// Get a jobsite's change orders and the form to draft another
+    pub fn get_jobsite_change_orders(jobsite_id: Uuid) -> String {
+        format!("/jobsite/{jobsite_id}/change-orders")
+    }
+
+    /// Route: `POST /jobsite/:id/change-order`
+    /// Draft a change to a jobsite's scope
+    pub fn post_change_order(jobsite_id: Uuid) -> String {
+        format!("/jobsite/{jobsite_id}/change-order")
+    }
+
+    /// Route: `GET /jobsite/:id/comments`
+    /// The jobsite's comment thread and a form to post to it
+    pub fn get_jobsite_comments(jobsite_id: Uuid) -> String {
+        format!("/jobsite/{jobsite_id}/comments")
+    }
+
+    /// Route: `POST /jobsite/:id/comment`
+    /// Post a comment to the jobsite's thread
+    pub fn post_jobsite_comment(jobsite_id: Uuid) -> String {
+        format!("/jobsite/{jobsite_id}/comment")
+    }
+
+    /// Route: `POST /comment/:id/edit`
+    /// Rewrite a comment, only its author can
+    pub fn post_comment_edit(comment_id: Uuid) -> String {
+        format!("/comment/{comment_id}/edit")
+    }
+
+    /// Route: `POST /comment/:id/delete`
+    /// Take a comment out of its thread, only its author can
+    pub fn post_comment_delete(comment_id: Uuid) -> String {
+        format!("/comment/{comment_id}/delete")
+    }
+
+    /// Route: `POST /change-order/:id/submit`
+    /// Send a drafted change order to the client
+    pub fn post_change_order_submit(change_order_id: Uuid) -> String {
+        format!("/change-order/{change_order_id}/submit")
+    }
+
+    /// Route: `POST /change-order/:id/approve`
+    /// Record the client's approval, applying it to the jobsite's budget
+    pub fn post_change_order_approve(change_order_id: Uuid) -> String {
+        format!("/change-order/{change_order_id}/approve")
+    }
+
+    /// Route: `POST /change-order/:id/reject`
+    /// Record the client turning a change order down
+    pub fn post_change_order_reject(change_order_id: Uuid) -> String {
+        format!("/change-order/{change_order_id}/reject")
+    }
+
+    /// Route: `GET /jobsite/:id/cost-codes`
+    /// Get a jobsite's cost codes and tasks with the hours logged to each
+    pub fn get_jobsite_cost_codes(jobsite_id: Uuid) -> String {
+        format!("/jobsite/{jobsite_id}/cost-codes")
+    }
+
+    /// Route: `POST /jobsite/:id/cost-code`
+    /// Add a cost code, or a task under one, to a jobsite
+    pub fn post_cost_code(jobsite_id: Uuid) -> String {
+        format!("/jobsite/{jobsite_id}/cost-code")
+    }
+
+    /// Route: `POST /cost-code/:id/retire`
+    /// Retire a cost code and its tasks
+    pub fn post_cost_code_retire(cost_code_id: Uuid) -> String {
+        format!("/cost-code/{cost_code_id}/retire")
+    }
+
+    /// Route: `GET /clients`
+    /// Client and project management page
+    pub fn get_clients_page() -> String {
+        String::from("/clients")
+    }
+
+    /// Route: `POST /client`
+    /// Create a new client
+    pub fn post_client() -> String {
+        String::from("/client")
+    }
+
+    /// Route: `POST /project`
+    /// Create a new project for a client
+    pub fn post_project() -> String {
+        String::from("/project")
+    }
+
+    /// Route: `GET /employees`
+    /// Employee management page
+    pub fn get_employees_page() -> String {
+        String::from("/employees")
+    }
+
+    /// Route: `POST /employee`
+    /// Create a new employee
+    pub fn post_employee() -> String {
+        String::from("/employee")
+    }
+
+    /// Route: `POST /employee/:id/terminate`
+    /// Terminate an employee from a given date
+    pub fn post_employee_terminate(employee_id: Uuid) -> String {
+        format!("/employee/{employee_id}/terminate")
+    }
+
+    /// Route: `POST /employee/:id/pin`
+    /// Set the PIN an employee clocks in with at kiosks
+    pub fn post_employee_pin(employee_id: Uuid) -> String {
+        format!("/employee/{employee_id}/pin")
+    }
+
+    /// Route: `GET /employee/:id/calendar-link`
+    /// Get the subscription link for an employee's schedule calendar
+    pub fn get_employee_calendar_link(employee_id: Uuid) -> String {
+        format!("/employee/{employee_id}/calendar-link")
+    }
+
+    /// Route: `GET /calendar/employee/:id/:token/schedule.ics`
+    /// An employee's crew assignments as an iCalendar feed
+    pub fn get_employee_calendar(employee_id: Uuid, token: &str) -> String {
+        format!("/calendar/employee/{employee_id}/{token}/schedule.ics")
+    }
+
+    /// Route: `GET /incidents`
+    /// Safety incidents and days since the last one on each jobsite
+    pub fn get_incidents_page() -> String {
+        String::from("/incidents")
+    }
+
+    /// Route: `POST /incident`
+    /// Report a safety incident on a jobsite
+    pub fn post_incident() -> String {
+        String::from("/incident")
+    }
+
+    /// Route: `POST /incident/:id/investigate`
+    /// Record what an investigation into an incident found
+    pub fn post_incident_investigate(incident_id: Uuid) -> String {
+        format!("/incident/{incident_id}/investigate")
+    }
+
+    /// Route: `POST /incident/:id/corrective-action`
+    /// Assign someone to correct what caused an incident
+    pub fn post_incident_corrective_action(incident_id: Uuid) -> String {
+        format!("/incident/{incident_id}/corrective-action")
+    }
+
+    /// Route: `POST /incident/:id/close`
+    /// Close an incident once its corrective action is done
+    pub fn post_incident_close(incident_id: Uuid) -> String {
+        format!("/incident/{incident_id}/close")
+    }
+
+    /// Route: `GET /certifications`
+    /// Employee certifications and the ones about to expire
+    pub fn get_certifications_page() -> String {
+        String::from("/certifications")
+    }
+
+    /// Route: `POST /certification`
+    /// Record or renew an employee's certification
+    pub fn post_certification() -> String {
+        String::from("/certification")
+    }
+
+    /// Route: `GET /leave`
+    /// Leave requests and what's left of everyone's balances
+    pub fn get_leave_page() -> String {
+        String::from("/leave")
+    }
+
+    /// Route: `POST /leave`
+    /// Request paid time off for an employee
+    pub fn post_leave() -> String {
+        String::from("/leave")
+    }
+
+    /// Route: `POST /leave/:id/approve`
+    /// Approve a leave request
+    pub fn post_leave_approve(leave_id: Uuid) -> String {
+        format!("/leave/{leave_id}/approve")
+    }
+
+    /// Route: `POST /leave/:id/deny`
+    /// Deny a leave request
+    pub fn post_leave_deny(leave_id: Uuid) -> String {
+        format!("/leave/{leave_id}/deny")
+    }
+
+    /// Route: `GET /equipment`
+    /// Equipment catalog and where each piece currently is
+    pub fn get_equipment_page() -> String {
+        String::from("/equipment")
+    }
+
+    /// Route: `POST /equipment`
+    /// Add a piece of equipment to the catalog
+    pub fn post_equipment() -> String {
+        String::from("/equipment")
+    }
+
+    /// Route: `POST /equipment/:id/check-out`
+    /// Check a piece of equipment out of the yard to a jobsite
+    pub fn post_equipment_check_out(equipment_id: Uuid) -> String {
+        format!("/equipment/{equipment_id}/check-out")
+    }
+
+    /// Route: `POST /equipment/:id/return`
+    /// Return a piece of equipment to the yard
+    pub fn post_equipment_return(equipment_id: Uuid) -> String {
+        format!("/equipment/{equipment_id}/return")
+    }
+
+    /// Route: `GET /jobsite/:id/equipment`
+    /// Get the equipment currently out at a jobsite
+    pub fn get_jobsite_equipment(jobsite_id: Uuid) -> String {
+        format!("/jobsite/{jobsite_id}/equipment")
+    }
+
+    /// Route: `GET /materials`
+    /// Material catalog page
+    pub fn get_materials_page() -> String {
+        String::from("/materials")
+    }
+
+    /// Route: `POST /material`
+    /// Add a material to the catalog
+    pub fn post_material() -> String {
+        String::from("/material")
+    }
+
+    /// Route: `GET /jobsite/:id/materials`
+    /// Get what a jobsite has used of each material
+    pub fn get_jobsite_materials(jobsite_id: Uuid) -> String {
+        format!("/jobsite/{jobsite_id}/materials")
+    }
+
+    /// Route: `POST /jobsite/:id/materials`
+    /// Record material used up on a jobsite
+    pub fn post_jobsite_material(jobsite_id: Uuid) -> String {
+        format!("/jobsite/{jobsite_id}/materials")
+    }
+
+    /// Route: `GET /schedule`
+    /// Crew schedule for the current week
+    pub fn get_schedule_page() -> String {
+        String::from("/schedule")
+    }
+
+    /// Route: `GET /schedule/:week_start`
+    /// Crew schedule for the week starting on `week_start`
+    pub fn get_schedule_week_page(week_start: NaiveDate) -> String {
+        format!("/schedule/{week_start}")
+    }
+
+    /// Route: `POST /crew-assignment`
+    /// Assign an employee to a jobsite for a range of days
+    pub fn post_crew_assignment() -> String {
+        String::from("/crew-assignment")
+    }
+
+    /// Route: `POST /crew-assignment/:id/unassign`
+    /// Take an employee off a jobsite they were scheduled on
+    pub fn post_crew_assignment_unassign(assignment_id: Uuid) -> String {
+        format!("/crew-assignment/{assignment_id}/unassign")
+    }
+
+    /// Route: `POST /crew-assignment/:id/move`
+    /// Move a scheduled employee to another jobsite for the same days
+    pub fn post_crew_assignment_move(assignment_id: Uuid) -> String {
+        format!("/crew-assignment/{assignment_id}/move")
+    }
+
+    /// Route: `POST /pay-rate`
+    /// Set a pay rate for an employee or trade
+    pub fn post_pay_rate() -> String {
+        String::from("/pay-rate")
+    }
+
+    /// Route: `POST /shift/clock-in`
+    /// Clock an employee in at a jobsite, opening a new shift
+    pub fn post_clock_in() -> String {
+        String::from("/shift/clock-in")
+    }
+
+    /// Route: `POST /shift/:id/clock-out`
+    /// Clock an employee out, closing their shift
+    pub fn post_clock_out(shift_id: Uuid) -> String {
+        format!("/shift/{shift_id}/clock-out")
+    }
+
+    /// Route: `PUT /shift/:id`
+    /// Correct the times of a closed shift
+    pub fn put_shift(shift_id: Uuid) -> String {
+        format!("/shift/{shift_id}")
+    }
+
+    /// Route: `GET /timesheets`
+    /// Supervisor review queue of submitted timesheets
+    pub fn get_timesheets_page() -> String {
+        String::from("/timesheets")
+    }
+
+    /// Route: `GET /timesheet/:employee_id/:week_start`
+    /// An employee's timesheet for the week starting on `week_start`
+    pub fn get_timesheet_page(employee_id: Uuid, week_start: NaiveDate) -> String {
+        format!("/timesheet/{employee_id}/{week_start}")
+    }
+
+    /// Route: `POST /timesheet/:employee_id/:week_start/submit`
+    /// Submit a week of hours for approval
+    pub fn post_timesheet_submit(employee_id: Uuid, week_start: NaiveDate) -> String {
+        format!("/timesheet/{employee_id}/{week_start}/submit")
+    }
+
+    /// Route: `POST /timesheet/:employee_id/:week_start/approve`
+    /// Approve a submitted timesheet, locking its hours
+    pub fn post_timesheet_approve(employee_id: Uuid, week_start: NaiveDate) -> String {
+        format!("/timesheet/{employee_id}/{week_start}/approve")
+    }
+
+    /// Route: `POST /timesheet/:employee_id/:week_start/reject`
+    /// Reject a submitted timesheet with a reason
+    pub fn post_timesheet_reject(employee_id: Uuid, week_start: NaiveDate) -> String {
+        format!("/timesheet/{employee_id}/{week_start}/reject")
+    }
+
+    /// Route: `POST /timesheet/:employee_id/:week_start/reopen`
+    /// Reopen an approved timesheet, unlocking its hours
+    pub fn post_timesheet_reopen(employee_id: Uuid, week_start: NaiveDate) -> String {
+        format!("/timesheet/{employee_id}/{week_start}/reopen")
+    }
 }
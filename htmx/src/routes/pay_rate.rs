@@ -0,0 +1,211 @@
+use actix_web::{web, HttpResponse};
+use chrono::{Duration, NaiveDate, Utc};
+use eventstore::EventData;
+use models::{
+    events::pay_rate::PayRateSet,
+    projections::{
+        employee::Employee, jobsite::Jobsite, jobsite_labor_cost::JobsiteLaborCost,
+        pay_rate::PayRate,
+    },
+};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    utils::{ErrorProps, ErrorPropsCollection, RouteError},
+    views::{components, TemplateRenderer},
+};
+
+/// How many days back the jobsite cost summary covers
+pub const LABOR_COST_DAYS: i64 = 14;
+
+pub async fn get_jobsite_labor_cost(
+    db_pool: web::Data<PgPool>,
+    jobsite_id: web::Path<Uuid>,
+) -> Result<HttpResponse, RouteError> {
+    let jobsite_id = jobsite_id.into_inner();
+
+    let mut transaction = db_pool.begin().await?;
+
+    let jobsite = match Jobsite::get_by_id(&mut transaction, &jobsite_id).await? {
+        Some(jobsite) => jobsite,
+        None => return Err(RouteError::NotFound),
+    };
+
+    let today = jobsite.local_date(Utc::now());
+    let costs = JobsiteLaborCost::get_list_by_jobsite(
+        &mut transaction,
+        &jobsite_id,
+        today - Duration::days(LABOR_COST_DAYS - 1),
+        today,
+    )
+    .await?;
+
+    transaction.commit().await?;
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .body(TemplateRenderer::render(move || {
+            components::jobsite::JobsiteLaborCostPanel(
+                components::jobsite::JobsiteLaborCostPanelProps {
+                    jobsite_id,
+                    days: LABOR_COST_DAYS,
+                    costs,
+                },
+            )
+        })))
+}
+
+/// Parse a dollar amount such as `32`, `32.5` or `$1,000` into cents. Signs
+/// are rejected, callers that take negative amounts strip the sign first.
+pub(super) fn parse_cents(value: &str) -> Option<i64> {
+    let value = value.trim();
+    let value = value.strip_prefix('$').unwrap_or(value).replace(',', "");
+    let (dollars, cents) = value.split_once('.').unwrap_or((&value, ""));
+
+    let is_digits = |part: &str| part.chars().all(|c| c.is_ascii_digit());
+    if (dollars.is_empty() && cents.is_empty())
+        || cents.len() > 2
+        || !is_digits(dollars)
+        || !is_digits(cents)
+    {
+        return None;
+    }
+
+    let dollars: i64 = if dollars.is_empty() {
+        0
+    } else {
+        dollars.parse().ok()?
+    };
+    let cents: i64 = format!("{:0<2}", cents).parse().ok()?;
+
+    dollars.checked_mul(100)?.checked_add(cents)
+}
+
+#[derive(serde::Deserialize)]
+pub struct PayRateSetData {
+    employee_id: String,
+    trade: String,
+    jobsite_id: String,
+    hourly_rate: String,
+    effective_date: String,
+}
+
+pub async fn post_pay_rate(
+    db_pool: web::Data<PgPool>,
+    data: web::Form<PayRateSetData>,
+    eventstore: web::Data<eventstore::Client>,
+) -> Result<HttpResponse, RouteError> {
+    let mut errors = vec![
+        ErrorProps::new("pay-rate-target-error".to_string()),
+        ErrorProps::new("pay-rate-rate-error".to_string()),
+        ErrorProps::new("pay-rate-date-error".to_string()),
+    ];
+
+    let employee_id = Uuid::parse_str(&data.employee_id).ok();
+    let trade = Some(data.trade.trim().to_string()).filter(|trade| !trade.is_empty());
+    let jobsite_id = Uuid::parse_str(&data.jobsite_id).ok();
+
+    let mut transaction = db_pool.begin().await?;
+
+    match (employee_id, &trade) {
+        (Some(employee_id), None) => {
+            if Employee::get_by_id(&mut transaction, &employee_id)
+                .await?
+                .is_none()
+            {
+                errors.set_error("pay-rate-target-error", "This employee does not exist")?;
+            }
+        }
+        (None, Some(_)) => {}
+        _ => errors.set_error(
+            "pay-rate-target-error",
+            "Choose either an employee or a trade",
+        )?,
+    }
+
+    if let Some(jobsite_id) = jobsite_id {
+        if Jobsite::get_by_id(&mut transaction, &jobsite_id)
+            .await?
+            .is_none()
+        {
+            errors.set_error("pay-rate-target-error", "This jobsite does not exist")?;
+        }
+    }
+
+    transaction.commit().await?;
+
+    let hourly_rate_cents = parse_cents(&data.hourly_rate).filter(|cents| *cents > 0);
+    if hourly_rate_cents.is_none() {
+        errors.set_error("pay-rate-rate-error", "Enter an hourly rate, e.g. 32.50")?;
+    }
+
+    let effective_date = NaiveDate::parse_from_str(&data.effective_date, "%Y-%m-%d").ok();
+    if effective_date.is_none() {
+        errors.set_error("pay-rate-date-error", "An effective date is required")?;
+    }
+
+    let (Some(hourly_rate_cents), Some(effective_date)) = (hourly_rate_cents, effective_date)
+    else {
+        return Ok(HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(errors.render_errors()?));
+    };
+
+    if errors.has_errors() {
+        return Ok(HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(errors.render_errors()?));
+    }
+
+    let set_event = PayRateSet {
+        id: Uuid::new_v4(),
+        employee_id,
+        trade,
+        jobsite_id,
+        hourly_rate_cents,
+        effective_date,
+        set_at: Utc::now(),
+    };
+
+    let event = EventData::json(PayRateSet::event_name(), &set_event)
+        .expect("Unable to serialize")
+        .id(Uuid::new_v4());
+
+    eventstore
+        .append_to_stream(
+            PayRate::stream_name(set_event.employee_id.as_ref(), set_event.trade.as_deref()),
+            &Default::default(),
+            event,
+        )
+        .await
+        .expect("Failed to append event");
+
+    Ok(HttpResponse::Created()
+        .content_type("text/html; charset=utf-8")
+        .body(errors.render_errors()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_cents;
+
+    #[test]
+    fn parses_dollar_amounts() {
+        assert_eq!(parse_cents("32"), Some(3200));
+        assert_eq!(parse_cents("32.5"), Some(3250));
+        assert_eq!(parse_cents(" $1,000 "), Some(100000));
+        assert_eq!(parse_cents(".75"), Some(75));
+    }
+
+    #[test]
+    fn rejects_malformed_and_signed_amounts() {
+        assert_eq!(parse_cents("1.234"), None);
+        assert_eq!(parse_cents("-5.50"), None);
+        assert_eq!(parse_cents("-0.50"), None);
+        assert_eq!(parse_cents("+5"), None);
+        assert_eq!(parse_cents(""), None);
+        assert_eq!(parse_cents("$"), None);
+        assert_eq!(parse_cents("."), None);
+    }
+}
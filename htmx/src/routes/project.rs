@@ -0,0 +1,82 @@
+use actix_web::{web, HttpResponse};
+use eventstore::EventData;
+use models::{
+    events::project::ProjectCreated,
+    projections::{client::Client, project::Project},
+};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::utils::{ErrorProps, ErrorPropsCollection, RouteError};
+
+#[derive(serde::Deserialize)]
+pub struct ProjectCreateData {
+    client_id: Uuid,
+    name: String,
+}
+
+pub async fn post_project(
+    db_pool: web::Data<PgPool>,
+    data: web::Form<ProjectCreateData>,
+    eventstore: web::Data<eventstore::Client>,
+) -> Result<HttpResponse, RouteError> {
+    let mut errors = vec![ErrorProps::new("project-name-error".to_string())];
+
+    let name = data.name.trim().to_string();
+
+    if name.is_empty() {
+        errors.set_error("project-name-error", "A name is required")?;
+
+        return Ok(HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(errors.render_errors()?));
+    }
+
+    let mut transaction = db_pool.begin().await?;
+
+    if Client::get_by_id(&mut transaction, &data.client_id)
+        .await?
+        .is_none()
+    {
+        errors.set_error("project-name-error", "This client does not exist")?;
+    } else if Project::get_by_name(&mut transaction, &data.client_id, &name)
+        .await?
+        .is_some()
+    {
+        errors.set_error(
+            "project-name-error",
+            "This client already has a project with this name",
+        )?;
+    }
+
+    transaction.commit().await?;
+
+    if errors.has_errors() {
+        return Ok(HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(errors.render_errors()?));
+    }
+
+    let create_event = ProjectCreated {
+        id: Uuid::new_v4(),
+        client_id: data.client_id,
+        name,
+    };
+
+    let event = EventData::json(ProjectCreated::event_name(), &create_event)
+        .expect("Unable to serialize")
+        .id(Uuid::new_v4());
+
+    eventstore
+        .append_to_stream(
+            format!("project-{}", create_event.id),
+            &Default::default(),
+            event,
+        )
+        .await
+        .expect("Failed to append event");
+
+    Ok(HttpResponse::Created()
+        .content_type("text/html; charset=utf-8")
+        .body(errors.render_errors()?))
+}
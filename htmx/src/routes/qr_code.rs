@@ -0,0 +1,362 @@
+use actix_web::{web, HttpResponse};
+use chrono::{DateTime, Duration, Utc};
+use models::projections::{cost_code::CostCode, employee::Employee, jobsite::Jobsite};
+use secrecy::Secret;
+use services::{
+    configuration::ApplicationSettings,
+    qr_code::{qr_code_png, qr_code_svg},
+    signing,
+};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use super::shift::{clock_in, ClockInData};
+use crate::{
+    routes::ApiRoutes,
+    utils::{absolute_url, ErrorProps, ErrorPropsCollection, RouteError},
+    views::{components, pages, TemplateRenderer},
+};
+
+/// How long a printed code keeps working before it has to be reprinted
+const QR_CODE_VALID_DAYS: i64 = 180;
+
+/// What a clock-in link grants access to, the expiry is signed with it so
+/// it can't be pushed back
+fn clock_in_subject(jobsite_id: &Uuid, expires: i64) -> String {
+    format!("clock-in:{}:{}", jobsite_id, expires)
+}
+
+/// A link to the jobsite's clock-in form that stops working at `expires_at`
+fn signed_clock_in_path(
+    key: &Secret<String>,
+    jobsite_id: Uuid,
+    expires_at: DateTime<Utc>,
+) -> String {
+    let expires = expires_at.timestamp();
+    let token = signing::sign(key, &clock_in_subject(&jobsite_id, expires));
+
+    ApiRoutes::get_jobsite_clock_in_page(jobsite_id, expires, &token)
+}
+
+/// Whether a scanned link is one the server handed out, expired or not
+fn is_signed_clock_in_link(
+    key: &Secret<String>,
+    jobsite_id: &Uuid,
+    expires: i64,
+    token: &str,
+) -> bool {
+    signing::verify(key, &clock_in_subject(jobsite_id, expires), token)
+}
+
+/// Where a link stands when it's opened or its form submitted
+#[derive(Debug, PartialEq)]
+enum ClockInLink {
+    Valid,
+    Expired,
+    /// Not one the server signed, treated the same as a missing jobsite
+    Tampered,
+}
+
+fn check_clock_in_link(
+    key: &Secret<String>,
+    jobsite_id: &Uuid,
+    expires: i64,
+    token: &str,
+    now: DateTime<Utc>,
+) -> ClockInLink {
+    if !is_signed_clock_in_link(key, jobsite_id, expires, token) {
+        ClockInLink::Tampered
+    } else if expires < now.timestamp() {
+        ClockInLink::Expired
+    } else {
+        ClockInLink::Valid
+    }
+}
+
+/// The URL a jobsite's code encodes, valid from now
+async fn clock_in_url(
+    db_pool: &PgPool,
+    settings: &ApplicationSettings,
+    jobsite_id: Uuid,
+) -> Result<String, RouteError> {
+    let mut transaction = db_pool.begin().await?;
+
+    if Jobsite::get_by_id(&mut transaction, &jobsite_id)
+        .await?
+        .is_none()
+    {
+        return Err(RouteError::NotFound);
+    }
+
+    transaction.commit().await?;
+
+    let expires_at = Utc::now() + Duration::days(QR_CODE_VALID_DAYS);
+
+    Ok(absolute_url(
+        settings,
+        &signed_clock_in_path(&settings.signing_key, jobsite_id, expires_at),
+    ))
+}
+
+pub async fn get_jobsite_qr_code(
+    db_pool: web::Data<PgPool>,
+    jobsite_id: web::Path<Uuid>,
+) -> Result<HttpResponse, RouteError> {
+    let jobsite_id = jobsite_id.into_inner();
+
+    let mut transaction = db_pool.begin().await?;
+
+    let Some(jobsite) = Jobsite::get_by_id(&mut transaction, &jobsite_id).await? else {
+        return Err(RouteError::NotFound);
+    };
+
+    transaction.commit().await?;
+
+    let expires_on = jobsite.local_date(Utc::now() + Duration::days(QR_CODE_VALID_DAYS));
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .body(TemplateRenderer::render(move || {
+            components::jobsite::JobsiteQrCode(components::jobsite::JobsiteQrCodeProps {
+                jobsite_id,
+                expires_on,
+            })
+        })))
+}
+
+pub async fn get_jobsite_qr_code_svg(
+    db_pool: web::Data<PgPool>,
+    settings: web::Data<ApplicationSettings>,
+    jobsite_id: web::Path<Uuid>,
+) -> Result<HttpResponse, RouteError> {
+    let jobsite_id = jobsite_id.into_inner();
+    let url = clock_in_url(&db_pool, &settings, jobsite_id).await?;
+
+    let svg = qr_code_svg(&url).ok_or_else(|| anyhow::anyhow!("Unable to encode QR code"))?;
+
+    Ok(HttpResponse::Ok()
+        .content_type("image/svg+xml")
+        .insert_header(("Cache-Control", "no-store"))
+        .insert_header((
+            "Content-Disposition",
+            format!("inline; filename=\"jobsite-{}.svg\"", jobsite_id),
+        ))
+        .body(svg))
+}
+
+pub async fn get_jobsite_qr_code_png(
+    db_pool: web::Data<PgPool>,
+    settings: web::Data<ApplicationSettings>,
+    jobsite_id: web::Path<Uuid>,
+) -> Result<HttpResponse, RouteError> {
+    let jobsite_id = jobsite_id.into_inner();
+    let url = clock_in_url(&db_pool, &settings, jobsite_id).await?;
+
+    let png = qr_code_png(&url).ok_or_else(|| anyhow::anyhow!("Unable to encode QR code"))?;
+
+    Ok(HttpResponse::Ok()
+        .content_type("image/png")
+        .insert_header(("Cache-Control", "no-store"))
+        .insert_header((
+            "Content-Disposition",
+            format!("inline; filename=\"jobsite-{}.png\"", jobsite_id),
+        ))
+        .body(png))
+}
+
+#[derive(serde::Deserialize)]
+pub struct ClockInLinkQuery {
+    expires: i64,
+    token: String,
+}
+
+/// Where a scanned code lands, the jobsite's clock-in form ready to go
+pub async fn get_jobsite_clock_in_page(
+    db_pool: web::Data<PgPool>,
+    settings: web::Data<ApplicationSettings>,
+    jobsite_id: web::Path<Uuid>,
+    query: web::Query<ClockInLinkQuery>,
+) -> Result<HttpResponse, RouteError> {
+    let jobsite_id = jobsite_id.into_inner();
+
+    match check_clock_in_link(
+        &settings.signing_key,
+        &jobsite_id,
+        query.expires,
+        &query.token,
+        Utc::now(),
+    ) {
+        ClockInLink::Valid => {}
+        ClockInLink::Expired => {
+            return Ok(HttpResponse::Gone()
+                .content_type("text/html; charset=utf-8")
+                .body(TemplateRenderer::render(pages::ClockInLinkExpired)));
+        }
+        ClockInLink::Tampered => return Err(RouteError::NotFound),
+    }
+
+    let mut transaction = db_pool.begin().await?;
+
+    let jobsite = match Jobsite::get_by_id(&mut transaction, &jobsite_id).await? {
+        Some(jobsite) => jobsite,
+        None => return Err(RouteError::NotFound),
+    };
+
+    let available = Employee::get_list_without_open_shift(&mut transaction).await?;
+    let cost_codes = CostCode::get_active_by_jobsite(&mut transaction, &jobsite_id).await?;
+
+    transaction.commit().await?;
+
+    let ClockInLinkQuery { expires, token } = query.into_inner();
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .body(TemplateRenderer::render(move || {
+            pages::JobsiteClockIn(pages::JobsiteClockInProps {
+                jobsite,
+                available,
+                cost_codes,
+                expires,
+                token,
+            })
+        })))
+}
+
+#[derive(serde::Deserialize)]
+pub struct JobsiteClockInData {
+    /// The scanned link's expiry and signature, carried by the form so the
+    /// link is checked again when it's submitted
+    expires: i64,
+    token: String,
+    employee_id: Uuid,
+    cost_code_id: String,
+    #[serde(default)]
+    latitude: String,
+    #[serde(default)]
+    longitude: String,
+}
+
+/// Clock in from the form a scanned code opened
+pub async fn post_jobsite_clock_in(
+    db_pool: web::Data<PgPool>,
+    data: web::Form<JobsiteClockInData>,
+    eventstore: web::Data<eventstore::Client>,
+    settings: web::Data<ApplicationSettings>,
+    jobsite_id: web::Path<Uuid>,
+) -> Result<HttpResponse, RouteError> {
+    let jobsite_id = jobsite_id.into_inner();
+    let data = data.into_inner();
+
+    match check_clock_in_link(
+        &settings.signing_key,
+        &jobsite_id,
+        data.expires,
+        &data.token,
+        Utc::now(),
+    ) {
+        ClockInLink::Valid => {}
+        // The page may have been left open past the link's expiry
+        ClockInLink::Expired => {
+            let mut errors = vec![ErrorProps::new("clock-in-error".to_string())];
+            errors.set_error(
+                "clock-in-error",
+                "This QR code has expired, ask a supervisor to print a new one",
+            )?;
+
+            return Ok(HttpResponse::Ok()
+                .content_type("text/html; charset=utf-8")
+                .body(errors.render_errors()?));
+        }
+        ClockInLink::Tampered => return Err(RouteError::NotFound),
+    }
+
+    let clock_in_data = ClockInData {
+        employee_id: data.employee_id,
+        jobsite_id,
+        cost_code_id: data.cost_code_id,
+        latitude: data.latitude,
+        longitude: data.longitude,
+    };
+
+    clock_in(&db_pool, &eventstore, &clock_in_data).await
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, TimeZone, Utc};
+    use secrecy::Secret;
+    use uuid::Uuid;
+
+    use super::{check_clock_in_link, is_signed_clock_in_link, signed_clock_in_path, ClockInLink};
+
+    fn query_value<'a>(path: &'a str, name: &str) -> &'a str {
+        let query = path.split_once('?').unwrap().1;
+        query
+            .split('&')
+            .find_map(|pair| pair.strip_prefix(&format!("{}=", name)))
+            .unwrap()
+    }
+
+    #[test]
+    fn links_are_signed_for_their_jobsite_and_expiry() {
+        let key = Secret::new("signing-key".to_string());
+        let jobsite_id = Uuid::new_v4();
+        let expires_at = Utc.with_ymd_and_hms(2025, 8, 1, 0, 0, 0).unwrap();
+
+        let path = signed_clock_in_path(&key, jobsite_id, expires_at);
+        let expires = query_value(&path, "expires").parse::<i64>().unwrap();
+        let token = query_value(&path, "token");
+
+        assert_eq!(expires, expires_at.timestamp());
+        assert!(is_signed_clock_in_link(&key, &jobsite_id, expires, token));
+
+        // Pushing the expiry back or pointing it at another jobsite breaks it
+        assert!(!is_signed_clock_in_link(
+            &key,
+            &jobsite_id,
+            expires + 86_400,
+            token
+        ));
+        assert!(!is_signed_clock_in_link(
+            &key,
+            &Uuid::new_v4(),
+            expires,
+            token
+        ));
+        assert!(!is_signed_clock_in_link(
+            &Secret::new("another-key".to_string()),
+            &jobsite_id,
+            expires,
+            token
+        ));
+    }
+
+    #[test]
+    fn submitted_links_are_checked_like_opened_ones() {
+        let key = Secret::new("signing-key".to_string());
+        let jobsite_id = Uuid::new_v4();
+        let expires_at = Utc.with_ymd_and_hms(2025, 8, 1, 0, 0, 0).unwrap();
+
+        let path = signed_clock_in_path(&key, jobsite_id, expires_at);
+        let expires = query_value(&path, "expires").parse::<i64>().unwrap();
+        let token = query_value(&path, "token");
+
+        let before = expires_at - Duration::minutes(1);
+        let after = expires_at + Duration::minutes(1);
+
+        assert_eq!(
+            check_clock_in_link(&key, &jobsite_id, expires, token, before),
+            ClockInLink::Valid
+        );
+        // A form left open on a phone past the expiry can't be submitted
+        assert_eq!(
+            check_clock_in_link(&key, &jobsite_id, expires, token, after),
+            ClockInLink::Expired
+        );
+        // Nor can hidden fields edited to push the expiry back
+        assert_eq!(
+            check_clock_in_link(&key, &jobsite_id, expires + 86_400, token, after),
+            ClockInLink::Tampered
+        );
+    }
+}
@@ -0,0 +1,266 @@
+use actix_web::{web, HttpResponse};
+use chrono::{DateTime, Utc};
+use eventstore::EventData;
+use models::{
+    commands::shift::Punch,
+    events::shift::{ClockedIn, ClockedOut},
+    geofence::{check_clock_in, Coordinates},
+    projections::{
+        cost_code::CostCode, employee::Employee, jobsite::Jobsite, shift::Shift,
+        timesheet::Timesheet,
+    },
+};
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::{
+    utils::{ErrorProps, ErrorPropsCollection, RouteError},
+    views::{components, TemplateRenderer},
+};
+
+pub async fn get_jobsite_on_site(
+    db_pool: web::Data<PgPool>,
+    jobsite_id: web::Path<Uuid>,
+) -> Result<HttpResponse, RouteError> {
+    let jobsite_id = jobsite_id.into_inner();
+
+    let mut transaction = db_pool.begin().await?;
+
+    if Jobsite::get_by_id(&mut transaction, &jobsite_id)
+        .await?
+        .is_none()
+    {
+        return Err(RouteError::NotFound);
+    }
+
+    let on_site = Shift::get_on_site(&mut transaction, &jobsite_id).await?;
+    let available = Employee::get_list_without_open_shift(&mut transaction).await?;
+    let cost_codes = CostCode::get_active_by_jobsite(&mut transaction, &jobsite_id).await?;
+
+    transaction.commit().await?;
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .body(TemplateRenderer::render(move || {
+            components::shift::JobsiteOnSite(components::shift::JobsiteOnSiteProps {
+                jobsite_id,
+                on_site,
+                available,
+                cost_codes,
+            })
+        })))
+}
+
+#[derive(serde::Deserialize)]
+pub struct ClockInData {
+    pub(super) employee_id: Uuid,
+    pub(super) jobsite_id: Uuid,
+    pub(super) cost_code_id: String,
+    /// Filled in by the browser when the device shares its location
+    #[serde(default)]
+    pub(super) latitude: String,
+    #[serde(default)]
+    pub(super) longitude: String,
+}
+
+/// Why the employee can't clock in at the jobsite at `clocked_in_at`, if
+/// anything stops them. Shared by the jobsite panel, the kiosks and device
+/// sync, each of which knows in its own way whether the employee is
+/// already `on_the_clock`.
+pub(crate) async fn clock_in_error(
+    transaction: &mut Transaction<'_, Postgres>,
+    jobsite: &Jobsite,
+    employee_id: &Uuid,
+    on_the_clock: bool,
+    clocked_in_at: DateTime<Utc>,
+) -> Result<Option<&'static str>, RouteError> {
+    if jobsite.merged_into_id.is_some() {
+        return Ok(Some(
+            "This jobsite was merged into another, clock in there instead",
+        ));
+    }
+
+    if jobsite.archived_at.is_some() {
+        return Ok(Some("This jobsite has been archived"));
+    }
+
+    let work_date = jobsite.local_date(clocked_in_at);
+
+    match Employee::get_by_id(transaction, employee_id).await? {
+        Some(employee) if employee.is_terminated_on(work_date) => {
+            return Ok(Some("This employee has been terminated"));
+        }
+        Some(_) => {}
+        None => return Ok(Some("This employee does not exist")),
+    }
+
+    if on_the_clock {
+        return Ok(Some(
+            "This employee is already clocked in, they must clock out first",
+        ));
+    }
+
+    if Timesheet::is_locked(transaction, employee_id, work_date).await? {
+        return Ok(Some(
+            "This week's timesheet has been approved, it must be reopened first",
+        ));
+    }
+
+    Ok(None)
+}
+
+pub async fn post_clock_in(
+    db_pool: web::Data<PgPool>,
+    data: web::Form<ClockInData>,
+    eventstore: web::Data<eventstore::Client>,
+) -> Result<HttpResponse, RouteError> {
+    clock_in(&db_pool, &eventstore, &data).await
+}
+
+/// Open a shift for the employee, shared by every form that clocks in
+pub(super) async fn clock_in(
+    db_pool: &PgPool,
+    eventstore: &eventstore::Client,
+    data: &ClockInData,
+) -> Result<HttpResponse, RouteError> {
+    let mut errors = vec![ErrorProps {
+        id: "clock-in-error".to_string(),
+        text: None,
+    }];
+
+    let mut transaction = db_pool.begin().await?;
+
+    let jobsite = match Jobsite::get_by_id(&mut transaction, &data.jobsite_id).await? {
+        Some(jobsite) => jobsite,
+        None => return Err(RouteError::NotFound),
+    };
+
+    let clocked_in_at = Utc::now();
+    let on_the_clock = Shift::get_open_by_employee(&mut transaction, &data.employee_id)
+        .await?
+        .is_some();
+
+    if let Some(error) = clock_in_error(
+        &mut transaction,
+        &jobsite,
+        &data.employee_id,
+        on_the_clock,
+        clocked_in_at,
+    )
+    .await?
+    {
+        errors.set_error("clock-in-error", error)?;
+
+        return Ok(HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(errors.render_errors()?));
+    }
+
+    // Time can only be logged against an active code on the same jobsite
+    let cost_code_id = Uuid::parse_str(&data.cost_code_id).ok();
+    if let Some(cost_code_id) = cost_code_id {
+        match CostCode::get_by_id(&mut transaction, &cost_code_id).await? {
+            Some(cost_code)
+                if cost_code.jobsite_id == data.jobsite_id && !cost_code.is_retired() => {}
+            _ => {
+                errors.set_error(
+                    "clock-in-error",
+                    "This cost code is not open on this jobsite",
+                )?;
+
+                return Ok(HttpResponse::Ok()
+                    .content_type("text/html; charset=utf-8")
+                    .body(errors.render_errors()?));
+            }
+        }
+    }
+
+    transaction.commit().await?;
+
+    let coordinates = match (data.latitude.trim().parse(), data.longitude.trim().parse()) {
+        (Ok(latitude), Ok(longitude)) => Coordinates::new(latitude, longitude),
+        _ => None,
+    };
+
+    let clocked_in_event = ClockedIn {
+        shift_id: Uuid::new_v4(),
+        employee_id: data.employee_id,
+        jobsite_id: data.jobsite_id,
+        cost_code_id,
+        clocked_in_at,
+        time_zone: jobsite.time_zone.clone(),
+        coordinates,
+        geofence: check_clock_in(jobsite.geofence(), coordinates),
+    };
+
+    let event =
+        EventData::json(ClockedIn::event_name(), &clocked_in_event).expect("Unable to serialize");
+
+    // Checked against the employee's own stream as well, another clock-in
+    // may have gone through since the read models were
+    if let Err(conflict) = Punch::In
+        .append(eventstore, &data.employee_id, event, Uuid::new_v4())
+        .await?
+    {
+        errors.set_error("clock-in-error", &conflict.to_string())?;
+
+        return Ok(HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(errors.render_errors()?));
+    }
+
+    Ok(HttpResponse::Created()
+        .content_type("text/html; charset=utf-8")
+        .body(errors.render_errors()?))
+}
+
+pub async fn post_clock_out(
+    db_pool: web::Data<PgPool>,
+    eventstore: web::Data<eventstore::Client>,
+    shift_id: web::Path<Uuid>,
+) -> Result<HttpResponse, RouteError> {
+    let shift_id = shift_id.into_inner();
+    let mut errors = vec![ErrorProps {
+        id: "clock-out-error".to_string(),
+        text: None,
+    }];
+
+    let mut transaction = db_pool.begin().await?;
+
+    let shift = match Shift::get_by_id(&mut transaction, &shift_id).await? {
+        Some(shift) => shift,
+        None => return Err(RouteError::NotFound),
+    };
+
+    transaction.commit().await?;
+
+    if !shift.is_open() {
+        errors.set_error("clock-out-error", "This shift has already been clocked out")?;
+
+        return Ok(HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(errors.render_errors()?));
+    }
+
+    let clocked_out_event = ClockedOut {
+        shift_id,
+        employee_id: shift.employee_id,
+        clocked_out_at: Utc::now(),
+    };
+
+    let event =
+        EventData::json(ClockedOut::event_name(), &clocked_out_event).expect("Unable to serialize");
+
+    let punch = Punch::Out { shift_id };
+
+    if let Err(conflict) = punch
+        .append(&eventstore, &shift.employee_id, event, Uuid::new_v4())
+        .await?
+    {
+        errors.set_error("clock-out-error", &conflict.to_string())?;
+    }
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .body(errors.render_errors()?))
+}
@@ -0,0 +1,285 @@
+use actix_web::{web, HttpResponse};
+use chrono::Utc;
+use models::{
+    commands::{daily_log::DailyLogLocked, shift::ShiftConflict, Command, CommandMetadata},
+    geofence::{check_clock_in, Coordinates},
+    projections::{
+        cost_code::CostCode, daily_log::DailyLog, jobsite::Jobsite, shift::Shift,
+        synced_command::SyncedCommand,
+    },
+    sync::{
+        PendingShifts, QueuedAction, QueuedCommand, SyncRequest, SyncResponse, SyncResult,
+        MAX_CLOCK_SKEW, MAX_SYNC_BATCH,
+    },
+};
+use sqlx::{PgPool, Postgres, Transaction};
+
+use crate::{routes::clock_in_error, utils::RouteError};
+
+/// Apply a field device's offline queue in the order it was sent, one
+/// result per command. Each command is checked against the server's records
+/// as they stand after the ones before it.
+pub async fn post_sync(
+    db_pool: web::Data<PgPool>,
+    eventstore: web::Data<eventstore::Client>,
+    request: web::Json<SyncRequest>,
+) -> Result<HttpResponse, RouteError> {
+    let request = request.into_inner();
+
+    if request.commands.len() > MAX_SYNC_BATCH {
+        return Ok(HttpResponse::PayloadTooLarge().json(format!(
+            "At most {} commands can be synced at once",
+            MAX_SYNC_BATCH
+        )));
+    }
+
+    let mut pending = PendingShifts::default();
+    let mut results = Vec::with_capacity(request.commands.len());
+
+    for queued in request.commands {
+        results.push(sync_command(&db_pool, &eventstore, &mut pending, queued).await?);
+    }
+
+    Ok(HttpResponse::Ok().json(SyncResponse { results }))
+}
+
+async fn sync_command(
+    db_pool: &PgPool,
+    eventstore: &eventstore::Client,
+    pending: &mut PendingShifts,
+    queued: QueuedCommand,
+) -> Result<SyncResult, RouteError> {
+    let key = queued.idempotency_key;
+
+    let mut transaction = db_pool.begin().await?;
+
+    if SyncedCommand::get(&mut transaction, &key).await?.is_some() {
+        transaction.commit().await?;
+
+        return Ok(SyncResult::duplicate(key));
+    }
+
+    // Device clocks drift, but nothing queued offline happened in the future
+    if queued.client_timestamp > Utc::now() + MAX_CLOCK_SKEW {
+        return Ok(SyncResult::rejected(
+            key,
+            "The device's clock is ahead of the server's",
+        ));
+    }
+
+    let command = match queued_to_command(&mut transaction, pending, &queued).await? {
+        Ok(command) => command,
+        Err(result) => return Ok(result),
+    };
+
+    // The key doubles as the event id, so the event store drops the event if
+    // a sync dies between appending it and recording the command
+    if let Err(e) = command
+        .execute(
+            eventstore,
+            &mut transaction,
+            &CommandMetadata::caused_by(key, None),
+            key,
+        )
+        .await
+    {
+        // Clocked in elsewhere or the day submitted since the read models
+        // were checked
+        if let Some(conflict) = e.downcast_ref::<ShiftConflict>() {
+            return Ok(SyncResult::conflict(key, &conflict.to_string()));
+        }
+        if let Some(locked) = e.downcast_ref::<DailyLogLocked>() {
+            return Ok(SyncResult::conflict(key, &locked.to_string()));
+        }
+
+        return Err(e.into());
+    }
+
+    SyncedCommand::record(
+        &mut transaction,
+        &key,
+        queued.command.as_str(),
+        queued.client_timestamp,
+        Utc::now(),
+    )
+    .await?;
+
+    transaction.commit().await?;
+
+    pending.apply(&command);
+
+    Ok(SyncResult::applied(key))
+}
+
+/// The command a queued one asks for, or why it can't be applied
+async fn queued_to_command(
+    transaction: &mut Transaction<'_, Postgres>,
+    pending: &PendingShifts,
+    queued: &QueuedCommand,
+) -> Result<Result<Command, SyncResult>, RouteError> {
+    let key = queued.idempotency_key;
+    let at = queued.client_timestamp;
+
+    match &queued.command {
+        QueuedAction::ClockIn {
+            shift_id,
+            employee_id,
+            jobsite_id,
+            cost_code_id,
+            coordinates,
+        } => {
+            let Some(jobsite) = Jobsite::get_by_id(transaction, jobsite_id).await? else {
+                return Ok(Err(SyncResult::rejected(
+                    key,
+                    "This jobsite does not exist",
+                )));
+            };
+
+            if pending.get(shift_id).is_some()
+                || Shift::get_by_id(transaction, shift_id).await?.is_some()
+            {
+                return Ok(Err(SyncResult::rejected(
+                    key,
+                    "This shift has already been clocked in",
+                )));
+            }
+
+            let open_shift_id = Shift::get_open_by_employee(transaction, employee_id)
+                .await?
+                .map(|shift| shift.id);
+            let on_the_clock = pending.is_on_the_clock(employee_id, open_shift_id);
+
+            if let Some(error) =
+                clock_in_error(transaction, &jobsite, employee_id, on_the_clock, at).await?
+            {
+                return Ok(Err(SyncResult::conflict(key, error)));
+            }
+
+            // Time can only be logged against an active code on the same jobsite
+            if let Some(cost_code_id) = cost_code_id {
+                match CostCode::get_by_id(transaction, cost_code_id).await? {
+                    Some(cost_code)
+                        if cost_code.jobsite_id == *jobsite_id && !cost_code.is_retired() => {}
+                    _ => {
+                        return Ok(Err(SyncResult::conflict(
+                            key,
+                            "This cost code is not open on this jobsite",
+                        )));
+                    }
+                }
+            }
+
+            let coordinates = coordinates.and_then(|coordinates| {
+                Coordinates::new(coordinates.latitude, coordinates.longitude)
+            });
+
+            Ok(Ok(Command::ClockIn {
+                shift_id: *shift_id,
+                employee_id: *employee_id,
+                jobsite_id: *jobsite_id,
+                cost_code_id: *cost_code_id,
+                clocked_in_at: at,
+                time_zone: jobsite.time_zone.clone(),
+                coordinates,
+                geofence: check_clock_in(jobsite.geofence(), coordinates),
+            }))
+        }
+        QueuedAction::ClockOut { shift_id } => {
+            let (employee_id, clocked_in_at, clocked_out) = match pending.get(shift_id) {
+                Some(shift) => (
+                    shift.employee_id,
+                    shift.clocked_in_at,
+                    pending.is_clocked_out(shift_id),
+                ),
+                None => match Shift::get_by_id(transaction, shift_id).await? {
+                    Some(shift) => (
+                        shift.employee_id,
+                        shift.clocked_in_at,
+                        !shift.is_open() || pending.is_clocked_out(shift_id),
+                    ),
+                    None => {
+                        return Ok(Err(SyncResult::rejected(key, "This shift does not exist")));
+                    }
+                },
+            };
+
+            if clocked_out {
+                return Ok(Err(SyncResult::conflict(
+                    key,
+                    "This shift has already been clocked out",
+                )));
+            }
+
+            if at < clocked_in_at {
+                return Ok(Err(SyncResult::rejected(
+                    key,
+                    "A shift can't be clocked out before it was clocked in",
+                )));
+            }
+
+            Ok(Ok(Command::ClockOut {
+                shift_id: *shift_id,
+                employee_id,
+                clocked_out_at: at,
+            }))
+        }
+        QueuedAction::SaveDailyLog {
+            jobsite_id,
+            log_date,
+            crew_count,
+            weather,
+            notes,
+        } => {
+            if *crew_count < 0 {
+                return Ok(Err(SyncResult::rejected(
+                    key,
+                    "Crew count must be a whole number",
+                )));
+            }
+
+            let Some(jobsite) = Jobsite::get_by_id(transaction, jobsite_id).await? else {
+                return Ok(Err(SyncResult::rejected(
+                    key,
+                    "This jobsite does not exist",
+                )));
+            };
+
+            // Days end at midnight where the jobsite is
+            if *log_date > jobsite.local_date(at) {
+                return Ok(Err(SyncResult::rejected(
+                    key,
+                    "Days can't be logged ahead of time",
+                )));
+            }
+
+            if let Some(daily_log) = DailyLog::get(transaction, jobsite_id, log_date).await? {
+                if daily_log.is_locked() {
+                    return Ok(Err(SyncResult::conflict(
+                        key,
+                        "This day's log has been submitted and can no longer be changed",
+                    )));
+                }
+
+                // Someone else saved the day after this edit was made offline,
+                // theirs isn't overwritten with an older version
+                if daily_log.saved_at > at {
+                    return Ok(Err(SyncResult::conflict(
+                        key,
+                        "This day's log was changed after this edit was made",
+                    )));
+                }
+            }
+
+            let notes = notes.as_deref().map(str::trim).unwrap_or_default();
+
+            Ok(Ok(Command::SaveDailyLog {
+                jobsite_id: *jobsite_id,
+                log_date: *log_date,
+                crew_count: *crew_count,
+                weather: *weather,
+                notes: (!notes.is_empty()).then(|| notes.to_string()),
+                saved_at: at,
+            }))
+        }
+    }
+}
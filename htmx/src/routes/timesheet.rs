@@ -0,0 +1,426 @@
+use actix_web::{web, HttpResponse};
+use chrono::{NaiveDate, NaiveDateTime, Utc};
+use eventstore::EventData;
+use models::{
+    events::{
+        shift::ShiftCorrected,
+        timesheet::{TimesheetApproved, TimesheetRejected, TimesheetReopened, TimesheetSubmitted},
+    },
+    local_time::{from_local, local_date},
+    projections::{
+        employee::Employee,
+        labor_classification::LaborClassification,
+        leave::Leave,
+        shift::Shift,
+        timesheet::{Timesheet, TimesheetStatus},
+    },
+};
+use serde::Serialize;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    utils::{ErrorProps, ErrorPropsCollection, RouteError},
+    views::{pages, TemplateRenderer},
+};
+
+pub async fn get_timesheet_page(
+    db_pool: web::Data<PgPool>,
+    path: web::Path<(Uuid, NaiveDate)>,
+) -> Result<HttpResponse, RouteError> {
+    let (employee_id, week_start) = path.into_inner();
+    let week_start = Timesheet::week_start_of(week_start);
+
+    let mut transaction = db_pool.begin().await?;
+
+    let employee = match Employee::get_by_id(&mut transaction, &employee_id).await? {
+        Some(employee) => employee,
+        None => return Err(RouteError::NotFound),
+    };
+
+    let timesheet = Timesheet::get(&mut transaction, &employee_id, &week_start).await?;
+    let shifts = Shift::get_list_by_employee(
+        &mut transaction,
+        &employee_id,
+        week_start,
+        Timesheet::week_end_of(week_start),
+    )
+    .await?;
+    let totals = LaborClassification::get_totals_by_employee(
+        &mut transaction,
+        &employee_id,
+        week_start,
+        Timesheet::week_end_of(week_start),
+    )
+    .await?;
+    let leave_minutes = Leave::get_minutes_by_employee(
+        &mut transaction,
+        &employee_id,
+        week_start,
+        Timesheet::week_end_of(week_start),
+    )
+    .await?;
+
+    transaction.commit().await?;
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .body(TemplateRenderer::render(move || {
+            pages::EmployeeTimesheet(pages::EmployeeTimesheetProps {
+                employee,
+                week_start,
+                timesheet,
+                shifts,
+                totals,
+                leave_minutes,
+            })
+        })))
+}
+
+pub async fn get_timesheets_page(db_pool: web::Data<PgPool>) -> Result<HttpResponse, RouteError> {
+    let mut transaction = db_pool.begin().await?;
+
+    let reviews = Timesheet::get_review_queue(&mut transaction).await?;
+
+    transaction.commit().await?;
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .body(TemplateRenderer::render(move || {
+            pages::Timesheets(pages::TimesheetsProps { reviews })
+        })))
+}
+
+/// Load the current status of a timesheet, ensuring the employee exists
+async fn get_timesheet_status(
+    db_pool: &PgPool,
+    employee_id: &Uuid,
+    week_start: &NaiveDate,
+) -> Result<TimesheetStatus, RouteError> {
+    let mut transaction = db_pool.begin().await?;
+
+    if Employee::get_by_id(&mut transaction, employee_id)
+        .await?
+        .is_none()
+    {
+        return Err(RouteError::NotFound);
+    }
+
+    let status = Timesheet::get_status(&mut transaction, employee_id, week_start).await?;
+
+    transaction.commit().await?;
+
+    Ok(status)
+}
+
+async fn append_timesheet_event<T: Serialize>(
+    eventstore: &eventstore::Client,
+    employee_id: &Uuid,
+    week_start: &NaiveDate,
+    event_name: String,
+    event: &T,
+) {
+    let event = EventData::json(event_name, event)
+        .expect("Unable to serialize")
+        .id(Uuid::new_v4());
+
+    eventstore
+        .append_to_stream(
+            Timesheet::stream_name(employee_id, week_start),
+            &Default::default(),
+            event,
+        )
+        .await
+        .expect("Failed to append event");
+}
+
+pub async fn post_timesheet_submit(
+    db_pool: web::Data<PgPool>,
+    eventstore: web::Data<eventstore::Client>,
+    path: web::Path<(Uuid, NaiveDate)>,
+) -> Result<HttpResponse, RouteError> {
+    let (employee_id, week_start) = path.into_inner();
+    let week_start = Timesheet::week_start_of(week_start);
+
+    let status = get_timesheet_status(&db_pool, &employee_id, &week_start).await?;
+
+    if !status.can_submit() {
+        return Err(RouteError::UnexpectedError(anyhow::anyhow!(
+            "A {} timesheet cannot be submitted",
+            status.as_str()
+        )));
+    }
+
+    let mut transaction = db_pool.begin().await?;
+
+    // Hours still being worked can't be signed off on
+    if let Some(shift) = Shift::get_open_by_employee(&mut transaction, &employee_id).await? {
+        if Timesheet::week_start_of(shift.work_date()) == week_start {
+            return Err(RouteError::UnexpectedError(anyhow::anyhow!(
+                "This employee must clock out before the week can be submitted"
+            )));
+        }
+    }
+
+    transaction.commit().await?;
+
+    let submitted_event = TimesheetSubmitted {
+        employee_id,
+        week_start,
+        submitted_at: Utc::now(),
+    };
+
+    append_timesheet_event(
+        &eventstore,
+        &employee_id,
+        &week_start,
+        TimesheetSubmitted::event_name(),
+        &submitted_event,
+    )
+    .await;
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .finish())
+}
+
+pub async fn post_timesheet_approve(
+    db_pool: web::Data<PgPool>,
+    eventstore: web::Data<eventstore::Client>,
+    path: web::Path<(Uuid, NaiveDate)>,
+) -> Result<HttpResponse, RouteError> {
+    let (employee_id, week_start) = path.into_inner();
+    let week_start = Timesheet::week_start_of(week_start);
+
+    let status = get_timesheet_status(&db_pool, &employee_id, &week_start).await?;
+
+    if status != TimesheetStatus::Submitted {
+        return Err(RouteError::UnexpectedError(anyhow::anyhow!(
+            "Only submitted timesheets can be approved"
+        )));
+    }
+
+    let approved_event = TimesheetApproved {
+        employee_id,
+        week_start,
+        approved_at: Utc::now(),
+    };
+
+    append_timesheet_event(
+        &eventstore,
+        &employee_id,
+        &week_start,
+        TimesheetApproved::event_name(),
+        &approved_event,
+    )
+    .await;
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .finish())
+}
+
+#[derive(serde::Deserialize)]
+pub struct TimesheetRejectData {
+    reason: String,
+}
+
+pub async fn post_timesheet_reject(
+    db_pool: web::Data<PgPool>,
+    eventstore: web::Data<eventstore::Client>,
+    path: web::Path<(Uuid, NaiveDate)>,
+    data: web::Form<TimesheetRejectData>,
+) -> Result<HttpResponse, RouteError> {
+    let (employee_id, week_start) = path.into_inner();
+    let week_start = Timesheet::week_start_of(week_start);
+
+    let error_id = format!("timesheet_reject_error_{}_{}", employee_id, week_start);
+    let mut errors = vec![ErrorProps::new(error_id.clone())];
+
+    let status = get_timesheet_status(&db_pool, &employee_id, &week_start).await?;
+
+    if status != TimesheetStatus::Submitted {
+        return Err(RouteError::UnexpectedError(anyhow::anyhow!(
+            "Only submitted timesheets can be rejected"
+        )));
+    }
+
+    let reason = data.reason.trim().to_string();
+
+    if reason.is_empty() {
+        errors.set_error(&error_id, "A reason is required to reject a timesheet")?;
+
+        return Ok(HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(errors.render_errors()?));
+    }
+
+    let rejected_event = TimesheetRejected {
+        employee_id,
+        week_start,
+        reason,
+        rejected_at: Utc::now(),
+    };
+
+    append_timesheet_event(
+        &eventstore,
+        &employee_id,
+        &week_start,
+        TimesheetRejected::event_name(),
+        &rejected_event,
+    )
+    .await;
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .body(errors.render_errors()?))
+}
+
+pub async fn post_timesheet_reopen(
+    db_pool: web::Data<PgPool>,
+    eventstore: web::Data<eventstore::Client>,
+    path: web::Path<(Uuid, NaiveDate)>,
+) -> Result<HttpResponse, RouteError> {
+    let (employee_id, week_start) = path.into_inner();
+    let week_start = Timesheet::week_start_of(week_start);
+
+    let status = get_timesheet_status(&db_pool, &employee_id, &week_start).await?;
+
+    if status != TimesheetStatus::Approved {
+        return Err(RouteError::UnexpectedError(anyhow::anyhow!(
+            "Only approved timesheets can be reopened"
+        )));
+    }
+
+    let reopened_event = TimesheetReopened {
+        employee_id,
+        week_start,
+        reopened_at: Utc::now(),
+    };
+
+    append_timesheet_event(
+        &eventstore,
+        &employee_id,
+        &week_start,
+        TimesheetReopened::event_name(),
+        &reopened_event,
+    )
+    .await;
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .finish())
+}
+
+#[derive(serde::Deserialize)]
+pub struct ShiftCorrectionData {
+    clocked_in_at: String,
+    clocked_out_at: String,
+}
+
+pub async fn put_shift(
+    db_pool: web::Data<PgPool>,
+    eventstore: web::Data<eventstore::Client>,
+    shift_id: web::Path<Uuid>,
+    data: web::Form<ShiftCorrectionData>,
+) -> Result<HttpResponse, RouteError> {
+    let shift_id = shift_id.into_inner();
+
+    let error_id = format!("shift_correction_error_{}", shift_id);
+    let mut errors = vec![ErrorProps::new(error_id.clone())];
+
+    // datetime-local inputs have no zone, times are entered in the zone the
+    // shift was worked in
+    let parse = |value: &str| NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M");
+
+    let (clocked_in_at, clocked_out_at) =
+        match (parse(&data.clocked_in_at), parse(&data.clocked_out_at)) {
+            (Ok(clocked_in_at), Ok(clocked_out_at)) => (clocked_in_at, clocked_out_at),
+            _ => {
+                errors.set_error(&error_id, "Both times are required")?;
+
+                return Ok(HttpResponse::Ok()
+                    .content_type("text/html; charset=utf-8")
+                    .body(errors.render_errors()?));
+            }
+        };
+
+    let mut transaction = db_pool.begin().await?;
+
+    let shift = match Shift::get_by_id(&mut transaction, &shift_id).await? {
+        Some(shift) => shift,
+        None => return Err(RouteError::NotFound),
+    };
+
+    let time_zone = shift.tz();
+    let (clocked_in_at, clocked_out_at) = match (
+        from_local(clocked_in_at, &time_zone),
+        from_local(clocked_out_at, &time_zone),
+    ) {
+        (Some(clocked_in_at), Some(clocked_out_at)) => (clocked_in_at, clocked_out_at),
+        _ => {
+            errors.set_error(&error_id, "The clocks skip that time at this jobsite")?;
+
+            return Ok(HttpResponse::Ok()
+                .content_type("text/html; charset=utf-8")
+                .body(errors.render_errors()?));
+        }
+    };
+
+    if clocked_out_at <= clocked_in_at {
+        errors.set_error(&error_id, "Clock out must be after clock in")?;
+
+        return Ok(HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(errors.render_errors()?));
+    }
+
+    if shift.is_open() {
+        errors.set_error(&error_id, "An open shift cannot be corrected")?;
+
+        return Ok(HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(errors.render_errors()?));
+    }
+
+    // Neither the week the hours are moving out of nor the one they're moving
+    // into may be approved
+    if Timesheet::is_locked(&mut transaction, &shift.employee_id, shift.work_date()).await?
+        || Timesheet::is_locked(
+            &mut transaction,
+            &shift.employee_id,
+            local_date(clocked_in_at, &time_zone),
+        )
+        .await?
+    {
+        errors.set_error(
+            &error_id,
+            "These hours are on an approved timesheet, reopen it to make corrections",
+        )?;
+
+        return Ok(HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(errors.render_errors()?));
+    }
+
+    transaction.commit().await?;
+
+    let corrected_event = ShiftCorrected {
+        shift_id,
+        clocked_in_at,
+        clocked_out_at,
+    };
+
+    let event = EventData::json(ShiftCorrected::event_name(), &corrected_event)
+        .expect("Unable to serialize")
+        .id(Uuid::new_v4());
+
+    eventstore
+        .append_to_stream(format!("shift-{}", shift_id), &Default::default(), event)
+        .await
+        .expect("Failed to append event");
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .body(errors.render_errors()?))
+}
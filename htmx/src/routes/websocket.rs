@@ -2,15 +2,48 @@ use std::collections::HashSet;
 
 use actix_web::{rt, web, HttpRequest, HttpResponse};
 use actix_ws::{Message, MessageStream, Session};
+use chrono::{Datelike, Duration, NaiveDate, Utc};
 use leptos::view;
 use log::error;
-use models::{projections::jobsite::Jobsite, AppState, JobsiteBroadcast};
+use models::{
+    projections::{
+        attachment::Attachment,
+        certification::Certification,
+        change_order::ChangeOrder,
+        client::Client,
+        comment::Comment,
+        cost_code::CostCode,
+        crew_assignment::{CrewAssignment, CrewWeek},
+        daily_log::{DailyLog, DailyLogDelay},
+        employee::Employee,
+        equipment::Equipment,
+        incident::{Incident, IncidentEmployee},
+        jobsite::{Jobsite, JobsiteMerge},
+        jobsite_budget::{JobsiteBudget, JobsiteBudgetAlert},
+        jobsite_labor_cost::JobsiteLaborCost,
+        kiosk::Kiosk,
+        leave::{Leave, LeaveBalance},
+        material::{JobsiteMaterialUsage, Material},
+        pay_rate::PayRate,
+        project::Project,
+        shift::Shift,
+        timesheet::{Timesheet, TimesheetStatus},
+    },
+    AppState, AttachmentBroadcast, CertificationBroadcast, ChangeOrderBroadcast, ClientBroadcast,
+    CommentBroadcast, CostCodeBroadcast, CrewAssignmentBroadcast, DailyLogBroadcast,
+    EmployeeBroadcast, EquipmentBroadcast, IncidentBroadcast, JobsiteBroadcast,
+    JobsiteBudgetBroadcast, LeaveBroadcast, MaterialBroadcast, PayRateBroadcast, ProjectBroadcast,
+    ShiftBroadcast, TimesheetBroadcast,
+};
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 use tokio_stream::StreamExt;
 use uuid::Uuid;
 
-use crate::views::{components, TemplateRenderer};
+use crate::{
+    routes::LABOR_COST_DAYS,
+    views::{components, TemplateRenderer},
+};
 
 pub async fn websocket(
     req: HttpRequest,
@@ -31,10 +64,34 @@ async fn ws_handler(
     state: web::Data<AppState>,
     db_pool: web::Data<PgPool>,
 ) {
-    // Subscribe to jobsite updates
+    // Subscribe to read model updates
     let mut jobsite_rx = state.jobsite_tx.subscribe();
+    let mut client_rx = state.client_tx.subscribe();
+    let mut project_rx = state.project_tx.subscribe();
+    let mut employee_rx = state.employee_tx.subscribe();
+    let mut crew_assignment_rx = state.crew_assignment_tx.subscribe();
+    let mut shift_rx = state.shift_tx.subscribe();
+    let mut timesheet_rx = state.timesheet_tx.subscribe();
+    let mut pay_rate_rx = state.pay_rate_tx.subscribe();
+    let mut jobsite_budget_rx = state.jobsite_budget_tx.subscribe();
+    let mut cost_code_rx = state.cost_code_tx.subscribe();
+    let mut daily_log_rx = state.daily_log_tx.subscribe();
+    let mut attachment_rx = state.attachment_tx.subscribe();
+    let mut incident_rx = state.incident_tx.subscribe();
+    let mut certification_rx = state.certification_tx.subscribe();
+    let mut leave_rx = state.leave_tx.subscribe();
+    let mut equipment_rx = state.equipment_tx.subscribe();
+    let mut material_rx = state.material_tx.subscribe();
+    let mut change_order_rx = state.change_order_tx.subscribe();
+    let mut comment_rx = state.comment_tx.subscribe();
 
     let mut subscribed_jobsites = HashSet::<Uuid>::new();
+    // The jobsite currently open in this client's detail panel
+    let mut watched_jobsite: Option<Uuid> = None;
+    // The week of the crew schedule this client has open
+    let mut watched_schedule: Option<NaiveDate> = None;
+    // The jobsite whose daily logs this client has open
+    let mut watched_daily_logs: Option<Uuid> = None;
 
     loop {
         tokio::select! {
@@ -42,7 +99,7 @@ async fn ws_handler(
             Some(Ok(msg)) = msg_stream.next() => {
                 match msg {
                     Message::Text(text) => {
-                        handle_client_message(&text, &mut session, &mut subscribed_jobsites, &db_pool).await;
+                        handle_client_message(&text, &mut session, &mut subscribed_jobsites, &mut watched_jobsite, &mut watched_schedule, &mut watched_daily_logs, &db_pool).await;
                     },
                     Message::Close(_) => {
                         break;
@@ -59,6 +116,279 @@ async fn ws_handler(
                     JobsiteBroadcast::JobsiteUpdated(jobsite) => {
                         send_jobsite_updated_update(&mut session, jobsite).await;
                     }
+                    JobsiteBroadcast::JobsiteAssignedToProject(jobsite) => {
+                        send_jobsite_assigned_update(&mut session, jobsite, &db_pool).await;
+                    }
+                    JobsiteBroadcast::JobsiteCertificationsRequired(jobsite) => {
+                        if watched_jobsite == Some(jobsite.id) {
+                            send_jobsite_certifications_update(&mut session, jobsite.id, &db_pool).await;
+                        }
+                    }
+                    JobsiteBroadcast::JobsiteGeofenceSet(jobsite) => {
+                        if watched_jobsite == Some(jobsite.id) {
+                            send_jobsite_geofence_update(&mut session, jobsite).await;
+                        }
+                    }
+                    JobsiteBroadcast::JobsiteKioskRegistered(kiosk)
+                    | JobsiteBroadcast::JobsiteKioskRevoked(kiosk) => {
+                        if watched_jobsite == Some(kiosk.jobsite_id) {
+                            send_jobsite_kiosks_update(&mut session, kiosk.jobsite_id, &db_pool).await;
+                        }
+                    }
+                    // Hours, assignments and attachments move to the target, a
+                    // freshly unmerged jobsite loads its own panels when reopened
+                    JobsiteBroadcast::JobsitesMerged(merge) => {
+                        let target_id = merge.target.id;
+                        let watched = watched_jobsite == Some(merge.source.id);
+                        send_jobsite_merged_update(&mut session, merge, watched).await;
+                        if watched_jobsite == Some(target_id) {
+                            send_jobsite_moved_work_update(&mut session, target_id, &db_pool).await;
+                        }
+                    }
+                    JobsiteBroadcast::JobsitesUnmerged(merge) => {
+                        let target_id = merge.target.id;
+                        let watched = watched_jobsite == Some(merge.source.id);
+                        send_jobsite_unmerged_update(&mut session, merge.source, watched).await;
+                        if watched_jobsite == Some(target_id) {
+                            send_jobsite_moved_work_update(&mut session, target_id, &db_pool).await;
+                        }
+                    }
+                    JobsiteBroadcast::JobsiteArchived(jobsite) => {
+                        let watched = watched_jobsite == Some(jobsite.id);
+                        send_jobsite_archived_update(&mut session, jobsite, watched).await;
+                    }
+                }
+            },
+            Ok(client_update) = client_rx.recv() => {
+                match client_update {
+                    ClientBroadcast::ClientCreated(client) => {
+                        send_client_created_update(&mut session, client).await;
+                    }
+                }
+            },
+            Ok(project_update) = project_rx.recv() => {
+                match project_update {
+                    ProjectBroadcast::ProjectCreated(project) => {
+                        send_project_created_update(&mut session, project).await;
+                    }
+                }
+            },
+            Ok(employee_update) = employee_rx.recv() => {
+                match employee_update {
+                    EmployeeBroadcast::EmployeeCreated(employee) => {
+                        send_employee_created_update(&mut session, employee, state.today()).await;
+                    }
+                    // Terminating someone can put their future schedule in conflict
+                    EmployeeBroadcast::EmployeeTerminated(employee) => {
+                        send_employee_terminated_update(&mut session, employee, state.today()).await;
+                        if let Some(week_start) = watched_schedule {
+                            send_crew_calendar_update(&mut session, week_start, &db_pool).await;
+                        }
+                    }
+                }
+            },
+            Ok(crew_assignment_update) = crew_assignment_rx.recv() => {
+                let assignment = match crew_assignment_update {
+                    CrewAssignmentBroadcast::CrewMemberAssigned(assignment)
+                    | CrewAssignmentBroadcast::CrewMemberUnassigned(assignment) => assignment,
+                };
+                if let Some(week_start) = watched_schedule.filter(|week_start| {
+                    assignment.overlaps(*week_start, Timesheet::week_end_of(*week_start))
+                }) {
+                    send_crew_calendar_update(&mut session, week_start, &db_pool).await;
+                }
+            },
+            Ok(shift_update) = shift_rx.recv() => {
+                match shift_update {
+                    // Any shift changes who is available to clock in, so the
+                    // watched jobsite is refreshed regardless of where it happened
+                    ShiftBroadcast::ClockedIn(_) => {
+                        if let Some(jobsite_id) = watched_jobsite {
+                            send_jobsite_on_site_update(&mut session, jobsite_id, &db_pool).await;
+                        }
+                    }
+                    // Closed hours are what get priced, so these also change labor costs
+                    ShiftBroadcast::ClockedOut(_) => {
+                        if let Some(jobsite_id) = watched_jobsite {
+                            send_jobsite_on_site_update(&mut session, jobsite_id, &db_pool).await;
+                            send_jobsite_labor_cost_update(&mut session, jobsite_id, &db_pool).await;
+                            send_jobsite_budget_update(&mut session, jobsite_id, &db_pool).await;
+                            send_jobsite_cost_codes_update(&mut session, jobsite_id, &db_pool).await;
+                        }
+                    }
+                    ShiftBroadcast::ShiftCorrected(shift) => {
+                        send_timesheet_shift_update(&mut session, shift).await;
+                        if let Some(jobsite_id) = watched_jobsite {
+                            send_jobsite_labor_cost_update(&mut session, jobsite_id, &db_pool).await;
+                            send_jobsite_budget_update(&mut session, jobsite_id, &db_pool).await;
+                            send_jobsite_cost_codes_update(&mut session, jobsite_id, &db_pool).await;
+                        }
+                    }
+                }
+            },
+            Ok(pay_rate_update) = pay_rate_rx.recv() => {
+                match pay_rate_update {
+                    PayRateBroadcast::PayRateSet(pay_rate) => {
+                        send_pay_rate_set_update(&mut session, pay_rate).await;
+                        if let Some(jobsite_id) = watched_jobsite {
+                            send_jobsite_labor_cost_update(&mut session, jobsite_id, &db_pool).await;
+                            send_jobsite_budget_update(&mut session, jobsite_id, &db_pool).await;
+                        }
+                    }
+                }
+            },
+            Ok(jobsite_budget_update) = jobsite_budget_rx.recv() => {
+                let jobsite_id = match jobsite_budget_update {
+                    JobsiteBudgetBroadcast::JobsiteBudgetSet(budget) => budget.jobsite_id,
+                    JobsiteBudgetBroadcast::JobsiteBudgetThresholdReached(alert) => alert.jobsite_id,
+                };
+                if watched_jobsite == Some(jobsite_id) {
+                    send_jobsite_budget_update(&mut session, jobsite_id, &db_pool).await;
+                }
+            },
+            // New and retired codes change what time can be logged against
+            Ok(cost_code_update) = cost_code_rx.recv() => {
+                let cost_code = match cost_code_update {
+                    CostCodeBroadcast::CostCodeAdded(cost_code)
+                    | CostCodeBroadcast::CostCodeRetired(cost_code) => cost_code,
+                };
+                if watched_jobsite == Some(cost_code.jobsite_id) {
+                    send_jobsite_cost_codes_update(&mut session, cost_code.jobsite_id, &db_pool).await;
+                    send_cost_code_create_update(&mut session, cost_code.jobsite_id, &db_pool).await;
+                    send_jobsite_on_site_update(&mut session, cost_code.jobsite_id, &db_pool).await;
+                }
+            },
+            Ok(daily_log_update) = daily_log_rx.recv() => {
+                let daily_log = match daily_log_update {
+                    DailyLogBroadcast::DailyLogSaved(daily_log)
+                    | DailyLogBroadcast::DailyLogDelayRecorded(daily_log)
+                    | DailyLogBroadcast::DailyLogSubmitted(daily_log) => daily_log,
+                };
+                if watched_daily_logs == Some(daily_log.jobsite_id) {
+                    send_daily_log_list_update(&mut session, daily_log.jobsite_id, &db_pool).await;
+                }
+            },
+            Ok(attachment_update) = attachment_rx.recv() => {
+                let attachment = match attachment_update {
+                    AttachmentBroadcast::AttachmentAdded(attachment)
+                    | AttachmentBroadcast::AttachmentRemoved(attachment) => attachment,
+                };
+                match attachment.log_date {
+                    Some(_) if watched_daily_logs == Some(attachment.jobsite_id) => {
+                        send_daily_log_list_update(&mut session, attachment.jobsite_id, &db_pool).await;
+                    }
+                    None if watched_jobsite == Some(attachment.jobsite_id) => {
+                        send_jobsite_attachments_update(&mut session, attachment.jobsite_id, &db_pool).await;
+                    }
+                    _ => {}
+                }
+            },
+            Ok(incident_update) = incident_rx.recv() => {
+                match incident_update {
+                    IncidentBroadcast::IncidentReported(incident) => {
+                        send_incident_update(&mut session, incident, true, state.today(), &db_pool).await;
+                    }
+                    IncidentBroadcast::IncidentInvestigated(incident)
+                    | IncidentBroadcast::IncidentCorrectiveActionAssigned(incident)
+                    | IncidentBroadcast::IncidentClosed(incident) => {
+                        send_incident_update(&mut session, incident, false, state.today(), &db_pool).await;
+                    }
+                }
+            },
+            Ok(certification_update) = certification_rx.recv() => {
+                match certification_update {
+                    CertificationBroadcast::CertificationRecorded(_)
+                    | CertificationBroadcast::CertificationExpiryWarned(_)
+                    | CertificationBroadcast::CertificationExpired(_) => {
+                        send_certifications_update(&mut session, state.today(), &db_pool).await;
+                    }
+                }
+            },
+            Ok(leave_update) = leave_rx.recv() => {
+                match leave_update {
+                    LeaveBroadcast::LeaveRequested(_) | LeaveBroadcast::LeaveDenied(_) => {
+                        send_leave_update(&mut session, state.today(), &db_pool).await;
+                    }
+                    // Approved leave shows up on the schedule
+                    LeaveBroadcast::LeaveApproved(leave) => {
+                        send_leave_update(&mut session, state.today(), &db_pool).await;
+                        if let Some(week_start) = watched_schedule.filter(|week_start| {
+                            leave.start_date <= Timesheet::week_end_of(*week_start)
+                                && *week_start <= leave.end_date
+                        }) {
+                            send_crew_calendar_update(&mut session, week_start, &db_pool).await;
+                        }
+                    }
+                }
+            },
+            Ok(equipment_update) = equipment_rx.recv() => {
+                match equipment_update {
+                    EquipmentBroadcast::EquipmentAdded(_)
+                    | EquipmentBroadcast::EquipmentCheckedOut(_)
+                    | EquipmentBroadcast::EquipmentReturned(_) => {
+                        send_equipment_list_update(&mut session, state.today(), &db_pool).await;
+                        // Returned equipment no longer says which jobsite it left
+                        if let Some(jobsite_id) = watched_jobsite {
+                            send_jobsite_equipment_update(&mut session, jobsite_id, &db_pool).await;
+                        }
+                    }
+                }
+            },
+            Ok(material_update) = material_rx.recv() => {
+                match material_update {
+                    // New materials can be picked on any open jobsite
+                    MaterialBroadcast::MaterialAdded(_) => {
+                        send_material_list_update(&mut session, &db_pool).await;
+                        if let Some(jobsite_id) = watched_jobsite {
+                            send_jobsite_materials_update(&mut session, jobsite_id, &db_pool).await;
+                        }
+                    }
+                    MaterialBroadcast::MaterialConsumed(consumption) => {
+                        if watched_jobsite == Some(consumption.jobsite_id) {
+                            send_jobsite_materials_update(&mut session, consumption.jobsite_id, &db_pool).await;
+                        }
+                    }
+                }
+            },
+            Ok(change_order_update) = change_order_rx.recv() => {
+                match change_order_update {
+                    ChangeOrderBroadcast::ChangeOrderDrafted(change_order)
+                    | ChangeOrderBroadcast::ChangeOrderSubmitted(change_order)
+                    | ChangeOrderBroadcast::ChangeOrderRejected(change_order) => {
+                        if watched_jobsite == Some(change_order.jobsite_id) {
+                            send_jobsite_change_orders_update(&mut session, change_order.jobsite_id, &db_pool).await;
+                        }
+                    }
+                    // Approved change orders revise the jobsite's budget
+                    ChangeOrderBroadcast::ChangeOrderApproved(change_order) => {
+                        if watched_jobsite == Some(change_order.jobsite_id) {
+                            send_jobsite_change_orders_update(&mut session, change_order.jobsite_id, &db_pool).await;
+                            send_jobsite_budget_update(&mut session, change_order.jobsite_id, &db_pool).await;
+                        }
+                    }
+                }
+            },
+            Ok(comment_update) = comment_rx.recv() => {
+                match comment_update {
+                    CommentBroadcast::CommentPosted(comment)
+                    | CommentBroadcast::CommentEdited(comment)
+                    | CommentBroadcast::CommentDeleted(comment) => {
+                        if watched_jobsite == Some(comment.jobsite_id) {
+                            send_jobsite_comments_update(&mut session, comment.jobsite_id, &db_pool).await;
+                        }
+                    }
+                }
+            },
+            Ok(timesheet_update) = timesheet_rx.recv() => {
+                match timesheet_update {
+                    TimesheetBroadcast::TimesheetSubmitted(timesheet) => {
+                        send_timesheet_submitted_update(&mut session, timesheet, &db_pool).await;
+                    }
+                    TimesheetBroadcast::TimesheetApproved(timesheet)
+                    | TimesheetBroadcast::TimesheetRejected(timesheet)
+                    | TimesheetBroadcast::TimesheetReopened(timesheet) => {
+                        send_timesheet_status_update(&mut session, timesheet).await;
+                    }
                 }
             },
             else => break,
@@ -70,6 +400,9 @@ async fn handle_client_message(
     text: &str,
     session: &mut Session,
     subscribed_jobsites: &mut HashSet<Uuid>,
+    watched_jobsite: &mut Option<Uuid>,
+    watched_schedule: &mut Option<NaiveDate>,
+    watched_daily_logs: &mut Option<Uuid>,
     db_pool: &web::Data<PgPool>,
 ) {
     if let Ok(message) = serde_json::from_str::<JobsiteClientMessage>(text) {
@@ -86,6 +419,15 @@ async fn handle_client_message(
             JobsiteClientMessage::JobsiteRegister { jobsite_id } => {
                 subscribed_jobsites.insert(jobsite_id);
             }
+            JobsiteClientMessage::WatchJobsite { jobsite_id } => {
+                *watched_jobsite = Some(jobsite_id);
+            }
+            JobsiteClientMessage::WatchSchedule { week_start } => {
+                *watched_schedule = Some(week_start);
+            }
+            JobsiteClientMessage::WatchDailyLogs { jobsite_id } => {
+                *watched_daily_logs = Some(jobsite_id);
+            }
         }
     } else {
         error!("Failed to parse client message: {}", text);
@@ -98,7 +440,128 @@ async fn send_jobsite_update(session: &mut Session, jobsite: Jobsite) {
     let html = TemplateRenderer::render(move || {
         view! {
             <components::jobsite::JobsiteRow jobsite=jobsite.clone() jobsite_id=jobsite_id />
-            <components::jobsite::JobsiteList jobsites=vec![jobsite.clone()] append=jobsite_id />
+            <components::jobsite::ProjectJobsiteList project_id=jobsite.project_id jobsites=vec![jobsite.clone()] append=jobsite_id />
+        }
+    });
+
+    let _ = session.text(html).await;
+}
+
+/// Move the jobsite's row into its new project's group, and refresh the
+/// project picker if the jobsite is open
+async fn send_jobsite_assigned_update(
+    session: &mut Session,
+    jobsite: Jobsite,
+    db_pool: &web::Data<PgPool>,
+) {
+    let mut transaction = match db_pool.begin().await {
+        Ok(transaction) => transaction,
+        Err(e) => {
+            error!("Failed to start transaction: {}", e);
+            return;
+        }
+    };
+
+    let projects = match Project::get_list(&mut transaction).await {
+        Ok(projects) => projects,
+        Err(e) => {
+            error!("Failed to get projects: {}", e);
+            return;
+        }
+    };
+
+    let _ = transaction.commit().await;
+
+    let jobsite_id = jobsite.id;
+
+    let html = TemplateRenderer::render(move || {
+        view! {
+            <div id=format!("jobsite_row_{}", jobsite_id) hx-swap-oob="delete"></div>
+            <components::jobsite::ProjectJobsiteList project_id=jobsite.project_id jobsites=vec![jobsite.clone()] append=jobsite_id />
+            <components::jobsite::JobsiteProjectAssign jobsite_id=jobsite_id project_id=jobsite.project_id projects=projects />
+        }
+    });
+
+    let _ = session.text(html).await;
+}
+
+/// Turn the merged jobsite's row into a link to the jobsite it was merged
+/// into, and replace the jobsite itself if it is open
+async fn send_jobsite_merged_update(session: &mut Session, merge: JobsiteMerge, watched: bool) {
+    let html = TemplateRenderer::render(move || {
+        view! {
+            <components::jobsite::JobsiteMergedRow merge=merge.clone() />
+            {watched.then(|| view! { <components::jobsite::JobsiteMergedNotice merge=merge /> })}
+        }
+    });
+
+    let _ = session.text(html).await;
+}
+
+/// Archived jobsites leave the list, and show as archived if open
+async fn send_jobsite_archived_update(session: &mut Session, jobsite: Jobsite, watched: bool) {
+    let jobsite_id = jobsite.id;
+
+    let html = TemplateRenderer::render(move || {
+        view! {
+            <div id=format!("jobsite_row_{}", jobsite_id) hx-swap-oob="delete"></div>
+            {watched.then(|| view! { <components::jobsite::JobsiteEdit jobsite=Some(jobsite) /> })}
+        }
+    });
+
+    let _ = session.text(html).await;
+}
+
+/// Refresh everything on a jobsite that a merge or unmerge moves
+async fn send_jobsite_moved_work_update(
+    session: &mut Session,
+    jobsite_id: Uuid,
+    db_pool: &web::Data<PgPool>,
+) {
+    send_jobsite_on_site_update(session, jobsite_id, db_pool).await;
+    send_jobsite_labor_cost_update(session, jobsite_id, db_pool).await;
+    send_jobsite_budget_update(session, jobsite_id, db_pool).await;
+    send_jobsite_attachments_update(session, jobsite_id, db_pool).await;
+}
+
+/// Bring back the unmerged jobsite's row, and the jobsite itself if it is
+/// open
+async fn send_jobsite_unmerged_update(session: &mut Session, jobsite: Jobsite, watched: bool) {
+    if watched {
+        let edited = jobsite.clone();
+        let html = TemplateRenderer::render(move || {
+            view! {
+                <components::jobsite::JobsiteEdit jobsite=Some(edited) />
+            }
+        });
+
+        let _ = session.text(html).await;
+    }
+
+    send_jobsite_update(session, jobsite).await;
+}
+
+async fn send_client_created_update(session: &mut Session, client: Client) {
+    let client_id = client.id;
+
+    let html = TemplateRenderer::render(move || {
+        view! {
+            <components::jobsite::JobsiteClientList clients=vec![client.clone()] projects=vec![] jobsites=vec![] append=client_id />
+            <components::client::ClientList clients=vec![client] projects=vec![] append=client_id />
+        }
+    });
+
+    let _ = session.text(html).await;
+}
+
+async fn send_project_created_update(session: &mut Session, project: Project) {
+    let project_id = project.id;
+    let client_id = project.client_id;
+
+    let html = TemplateRenderer::render(move || {
+        view! {
+            <components::jobsite::ClientProjectList client_id=client_id projects=vec![project.clone()] jobsites=vec![] append=project_id />
+            <components::project::ProjectList client_id=client_id projects=vec![project] append=project_id />
         }
     });
 
@@ -118,9 +581,879 @@ async fn send_jobsite_updated_update(session: &mut Session, jobsite: Jobsite) {
     let _ = session.text(html).await;
 }
 
+async fn send_employee_created_update(session: &mut Session, employee: Employee, today: NaiveDate) {
+    let employee_id = employee.id;
+
+    let html = TemplateRenderer::render(move || {
+        view! {
+            <components::employee::EmployeeList employees=vec![employee] today=today append=employee_id />
+        }
+    });
+
+    let _ = session.text(html).await;
+}
+
+async fn send_employee_terminated_update(
+    session: &mut Session,
+    employee: Employee,
+    today: NaiveDate,
+) {
+    let html = TemplateRenderer::render(move || {
+        view! {
+            <components::employee::EmployeeRow employee=employee today=today />
+        }
+    });
+
+    let _ = session.text(html).await;
+}
+
+async fn send_crew_calendar_update(
+    session: &mut Session,
+    week_start: NaiveDate,
+    db_pool: &web::Data<PgPool>,
+) {
+    let mut transaction = match db_pool.begin().await {
+        Ok(transaction) => transaction,
+        Err(e) => {
+            error!("Failed to start transaction: {}", e);
+            return;
+        }
+    };
+
+    let employees = Employee::get_list(&mut transaction).await;
+    let assignments = CrewAssignment::get_week(&mut transaction, week_start).await;
+    let leave = Leave::get_approved_week(&mut transaction, week_start).await;
+    let jobsites = Jobsite::get_list(&mut transaction).await;
+
+    let (employees, assignments, leave, jobsites) = match (employees, assignments, leave, jobsites)
+    {
+        (Ok(employees), Ok(assignments), Ok(leave), Ok(jobsites)) => {
+            (employees, assignments, leave, jobsites)
+        }
+        (Err(e), _, _, _) | (_, Err(e), _, _) | (_, _, Err(e), _) | (_, _, _, Err(e)) => {
+            error!("Failed to get crew schedule: {}", e);
+            return;
+        }
+    };
+
+    let _ = transaction.commit().await;
+
+    let week = CrewWeek::new(week_start, employees, assignments, leave);
+
+    let html = TemplateRenderer::render(move || {
+        view! {
+            <components::crew_assignment::CrewCalendar week=week jobsites=jobsites />
+        }
+    });
+
+    let _ = session.text(html).await;
+}
+
+async fn send_daily_log_list_update(
+    session: &mut Session,
+    jobsite_id: Uuid,
+    db_pool: &web::Data<PgPool>,
+) {
+    let mut transaction = match db_pool.begin().await {
+        Ok(transaction) => transaction,
+        Err(e) => {
+            error!("Failed to start transaction: {}", e);
+            return;
+        }
+    };
+
+    let logs = DailyLog::get_list_by_jobsite(&mut transaction, &jobsite_id).await;
+    let delays = DailyLogDelay::get_list_by_jobsite(&mut transaction, &jobsite_id).await;
+    let attachments = Attachment::get_list_by_daily_logs(&mut transaction, &jobsite_id).await;
+
+    let (logs, delays, attachments) = match (logs, delays, attachments) {
+        (Ok(logs), Ok(delays), Ok(attachments)) => (logs, delays, attachments),
+        (Err(e), _, _) | (_, Err(e), _) | (_, _, Err(e)) => {
+            error!("Failed to get daily logs: {}", e);
+            return;
+        }
+    };
+
+    let _ = transaction.commit().await;
+
+    let html = TemplateRenderer::render(move || {
+        view! {
+            <components::daily_log::DailyLogList
+                jobsite_id=jobsite_id
+                logs=logs
+                delays=delays
+                attachments=attachments
+            />
+        }
+    });
+
+    let _ = session.text(html).await;
+}
+
+async fn send_jobsite_attachments_update(
+    session: &mut Session,
+    jobsite_id: Uuid,
+    db_pool: &web::Data<PgPool>,
+) {
+    let mut transaction = match db_pool.begin().await {
+        Ok(transaction) => transaction,
+        Err(e) => {
+            error!("Failed to start transaction: {}", e);
+            return;
+        }
+    };
+
+    let attachments = match Attachment::get_list_by_jobsite(&mut transaction, &jobsite_id).await {
+        Ok(attachments) => attachments,
+        Err(e) => {
+            error!("Failed to get jobsite attachments: {}", e);
+            return;
+        }
+    };
+
+    let _ = transaction.commit().await;
+
+    let html = TemplateRenderer::render(move || {
+        view! {
+            <components::attachment::JobsiteAttachments jobsite_id=jobsite_id attachments=attachments />
+        }
+    });
+
+    let _ = session.text(html).await;
+}
+
+/// Add or refresh an incident's row, along with the days since the last
+/// incident on every jobsite
+async fn send_incident_update(
+    session: &mut Session,
+    incident: Incident,
+    reported: bool,
+    today: NaiveDate,
+    db_pool: &web::Data<PgPool>,
+) {
+    let mut transaction = match db_pool.begin().await {
+        Ok(transaction) => transaction,
+        Err(e) => {
+            error!("Failed to start transaction: {}", e);
+            return;
+        }
+    };
+
+    let involved = IncidentEmployee::get_list_by_incident(&mut transaction, &incident.id).await;
+    let employees = Employee::get_list_active_on(&mut transaction, today).await;
+    let safety = Incident::get_jobsite_safety(&mut transaction).await;
+
+    let (involved, employees, safety) = match (involved, employees, safety) {
+        (Ok(involved), Ok(employees), Ok(safety)) => (involved, employees, safety),
+        (Err(e), _, _) | (_, Err(e), _) | (_, _, Err(e)) => {
+            error!("Failed to get incident: {}", e);
+            return;
+        }
+    };
+
+    let _ = transaction.commit().await;
+
+    let incident_id = incident.id;
+
+    let html = TemplateRenderer::render(move || {
+        view! {
+            {if reported {
+                view! {
+                    <components::incident::IncidentList
+                        incidents=vec![incident.clone()]
+                        involved=involved.clone()
+                        employees=employees.clone()
+                        append=incident_id
+                    />
+                }
+            } else {
+                view! {
+                    <components::incident::IncidentRow
+                        incident=incident.clone()
+                        involved=involved.clone()
+                        employees=employees.clone()
+                    />
+                }
+            }}
+            <components::incident::SafetyBoard safety=safety.clone() today=today />
+        }
+    });
+
+    let _ = session.text(html).await;
+}
+
+/// Refresh the certification list and the ones about to expire
+async fn send_certifications_update(
+    session: &mut Session,
+    today: NaiveDate,
+    db_pool: &web::Data<PgPool>,
+) {
+    let mut transaction = match db_pool.begin().await {
+        Ok(transaction) => transaction,
+        Err(e) => {
+            error!("Failed to start transaction: {}", e);
+            return;
+        }
+    };
+
+    let certifications = Certification::get_list(&mut transaction).await;
+    let alerts = Certification::get_alerts(&mut transaction).await;
+
+    let (certifications, alerts) = match (certifications, alerts) {
+        (Ok(certifications), Ok(alerts)) => (certifications, alerts),
+        (Err(e), _) | (_, Err(e)) => {
+            error!("Failed to get certifications: {}", e);
+            return;
+        }
+    };
+
+    let _ = transaction.commit().await;
+
+    let html = TemplateRenderer::render(move || {
+        view! {
+            <components::certification::CertificationList certifications=certifications today=today />
+            <components::certification::CertificationAlerts alerts=alerts today=today />
+        }
+    });
+
+    let _ = session.text(html).await;
+}
+
+async fn send_leave_update(session: &mut Session, today: NaiveDate, db_pool: &web::Data<PgPool>) {
+    let mut transaction = match db_pool.begin().await {
+        Ok(transaction) => transaction,
+        Err(e) => {
+            error!("Failed to start transaction: {}", e);
+            return;
+        }
+    };
+
+    let leave = Leave::get_list(&mut transaction).await;
+    let balances = LeaveBalance::get_list(&mut transaction, today.year()).await;
+    let employees = Employee::get_list_active_on(&mut transaction, today).await;
+
+    let (leave, balances, employees) = match (leave, balances, employees) {
+        (Ok(leave), Ok(balances), Ok(employees)) => (leave, balances, employees),
+        (Err(e), _, _) | (_, Err(e), _) | (_, _, Err(e)) => {
+            error!("Failed to get leave: {}", e);
+            return;
+        }
+    };
+
+    let _ = transaction.commit().await;
+
+    let html = TemplateRenderer::render(move || {
+        view! {
+            <components::leave::LeaveList leave=leave />
+            <components::leave::LeaveBalances employees=employees balances=balances year=today.year() />
+        }
+    });
+
+    let _ = session.text(html).await;
+}
+
+/// Refresh the equipment catalog and where each piece is
+async fn send_equipment_list_update(
+    session: &mut Session,
+    today: NaiveDate,
+    db_pool: &web::Data<PgPool>,
+) {
+    let mut transaction = match db_pool.begin().await {
+        Ok(transaction) => transaction,
+        Err(e) => {
+            error!("Failed to start transaction: {}", e);
+            return;
+        }
+    };
+
+    let equipment = Equipment::get_list(&mut transaction).await;
+    let jobsites = Jobsite::get_list(&mut transaction).await;
+    let employees = Employee::get_list_active_on(&mut transaction, today).await;
+
+    let (equipment, jobsites, employees) = match (equipment, jobsites, employees) {
+        (Ok(equipment), Ok(jobsites), Ok(employees)) => (equipment, jobsites, employees),
+        (Err(e), _, _) | (_, Err(e), _) | (_, _, Err(e)) => {
+            error!("Failed to get equipment: {}", e);
+            return;
+        }
+    };
+
+    let _ = transaction.commit().await;
+
+    let html = TemplateRenderer::render(move || {
+        view! {
+            <components::equipment::EquipmentList
+                equipment=equipment
+                jobsites=jobsites
+                employees=employees
+            />
+        }
+    });
+
+    let _ = session.text(html).await;
+}
+
+async fn send_jobsite_equipment_update(
+    session: &mut Session,
+    jobsite_id: Uuid,
+    db_pool: &web::Data<PgPool>,
+) {
+    let mut transaction = match db_pool.begin().await {
+        Ok(transaction) => transaction,
+        Err(e) => {
+            error!("Failed to start transaction: {}", e);
+            return;
+        }
+    };
+
+    let equipment = match Equipment::get_by_jobsite(&mut transaction, &jobsite_id).await {
+        Ok(equipment) => equipment,
+        Err(e) => {
+            error!("Failed to get jobsite equipment: {}", e);
+            return;
+        }
+    };
+
+    let _ = transaction.commit().await;
+
+    let html = TemplateRenderer::render(move || {
+        view! {
+            <components::equipment::JobsiteEquipment jobsite_id=jobsite_id equipment=equipment />
+        }
+    });
+
+    let _ = session.text(html).await;
+}
+
+async fn send_material_list_update(session: &mut Session, db_pool: &web::Data<PgPool>) {
+    let mut transaction = match db_pool.begin().await {
+        Ok(transaction) => transaction,
+        Err(e) => {
+            error!("Failed to start transaction: {}", e);
+            return;
+        }
+    };
+
+    let materials = match Material::get_list(&mut transaction).await {
+        Ok(materials) => materials,
+        Err(e) => {
+            error!("Failed to get materials: {}", e);
+            return;
+        }
+    };
+
+    let _ = transaction.commit().await;
+
+    let html = TemplateRenderer::render(move || {
+        view! { <components::material::MaterialList materials=materials /> }
+    });
+
+    let _ = session.text(html).await;
+}
+
+/// Refresh a jobsite's material usage along with the materials its form
+/// can record
+async fn send_jobsite_materials_update(
+    session: &mut Session,
+    jobsite_id: Uuid,
+    db_pool: &web::Data<PgPool>,
+) {
+    let mut transaction = match db_pool.begin().await {
+        Ok(transaction) => transaction,
+        Err(e) => {
+            error!("Failed to start transaction: {}", e);
+            return;
+        }
+    };
+
+    let jobsite = Jobsite::get_by_id(&mut transaction, &jobsite_id).await;
+    let usage = JobsiteMaterialUsage::get_by_jobsite(&mut transaction, &jobsite_id).await;
+    let materials = Material::get_list(&mut transaction).await;
+
+    let (jobsite, usage, materials) = match (jobsite, usage, materials) {
+        (Ok(Some(jobsite)), Ok(usage), Ok(materials)) => (jobsite, usage, materials),
+        (Ok(None), _, _) => return,
+        (Err(e), _, _) | (_, Err(e), _) | (_, _, Err(e)) => {
+            error!("Failed to get jobsite materials: {}", e);
+            return;
+        }
+    };
+
+    let _ = transaction.commit().await;
+
+    let today = jobsite.local_date(Utc::now());
+
+    let html = TemplateRenderer::render(move || {
+        view! {
+            <components::material::JobsiteMaterials jobsite_id=jobsite_id usage=usage />
+            <components::material::MaterialConsume
+                jobsite_id=jobsite_id
+                materials=materials
+                today=today
+            />
+        }
+    });
+
+    let _ = session.text(html).await;
+}
+
+async fn send_jobsite_certifications_update(
+    session: &mut Session,
+    jobsite_id: Uuid,
+    db_pool: &web::Data<PgPool>,
+) {
+    let mut transaction = match db_pool.begin().await {
+        Ok(transaction) => transaction,
+        Err(e) => {
+            error!("Failed to start transaction: {}", e);
+            return;
+        }
+    };
+
+    let required = match Certification::get_required_by_jobsite(&mut transaction, &jobsite_id).await
+    {
+        Ok(required) => required,
+        Err(e) => {
+            error!("Failed to get jobsite certifications: {}", e);
+            return;
+        }
+    };
+
+    let _ = transaction.commit().await;
+
+    let html = TemplateRenderer::render(move || {
+        view! {
+            <components::certification::JobsiteCertifications jobsite_id=jobsite_id required=required />
+        }
+    });
+
+    let _ = session.text(html).await;
+}
+
+async fn send_jobsite_geofence_update(session: &mut Session, jobsite: Jobsite) {
+    let jobsite_id = jobsite.id;
+    let geofence = jobsite.geofence();
+
+    let html = TemplateRenderer::render(move || {
+        view! {
+            <components::jobsite::JobsiteGeofence jobsite_id=jobsite_id geofence=geofence />
+        }
+    });
+
+    let _ = session.text(html).await;
+}
+
+async fn send_jobsite_kiosks_update(
+    session: &mut Session,
+    jobsite_id: Uuid,
+    db_pool: &web::Data<PgPool>,
+) {
+    let mut transaction = match db_pool.begin().await {
+        Ok(transaction) => transaction,
+        Err(e) => {
+            error!("Failed to start transaction: {}", e);
+            return;
+        }
+    };
+
+    let kiosks = match Kiosk::get_active_by_jobsite(&mut transaction, &jobsite_id).await {
+        Ok(kiosks) => kiosks,
+        Err(e) => {
+            error!("Failed to get jobsite kiosks: {}", e);
+            return;
+        }
+    };
+
+    let _ = transaction.commit().await;
+
+    let html = TemplateRenderer::render(move || {
+        view! {
+            <components::jobsite::JobsiteKiosks jobsite_id=jobsite_id kiosks=kiosks />
+        }
+    });
+
+    let _ = session.text(html).await;
+}
+
+async fn send_jobsite_on_site_update(
+    session: &mut Session,
+    jobsite_id: Uuid,
+    db_pool: &web::Data<PgPool>,
+) {
+    let mut transaction = match db_pool.begin().await {
+        Ok(transaction) => transaction,
+        Err(e) => {
+            error!("Failed to start transaction: {}", e);
+            return;
+        }
+    };
+
+    let on_site = Shift::get_on_site(&mut transaction, &jobsite_id).await;
+    let available = Employee::get_list_without_open_shift(&mut transaction).await;
+    let cost_codes = CostCode::get_active_by_jobsite(&mut transaction, &jobsite_id).await;
+
+    let (on_site, available, cost_codes) = match (on_site, available, cost_codes) {
+        (Ok(on_site), Ok(available), Ok(cost_codes)) => (on_site, available, cost_codes),
+        (Err(e), _, _) | (_, Err(e), _) | (_, _, Err(e)) => {
+            error!("Failed to get on site employees: {}", e);
+            return;
+        }
+    };
+
+    let _ = transaction.commit().await;
+
+    let html = TemplateRenderer::render(move || {
+        view! {
+            <components::shift::JobsiteOnSite jobsite_id=jobsite_id on_site=on_site available=available cost_codes=cost_codes />
+        }
+    });
+
+    let _ = session.text(html).await;
+}
+
+async fn send_jobsite_labor_cost_update(
+    session: &mut Session,
+    jobsite_id: Uuid,
+    db_pool: &web::Data<PgPool>,
+) {
+    let mut transaction = match db_pool.begin().await {
+        Ok(transaction) => transaction,
+        Err(e) => {
+            error!("Failed to start transaction: {}", e);
+            return;
+        }
+    };
+
+    let jobsite = match Jobsite::get_by_id(&mut transaction, &jobsite_id).await {
+        Ok(Some(jobsite)) => jobsite,
+        Ok(None) => return,
+        Err(e) => {
+            error!("Failed to get jobsite: {}", e);
+            return;
+        }
+    };
+
+    let days = LABOR_COST_DAYS;
+    let today = jobsite.local_date(Utc::now());
+    let costs = match JobsiteLaborCost::get_list_by_jobsite(
+        &mut transaction,
+        &jobsite_id,
+        today - Duration::days(days - 1),
+        today,
+    )
+    .await
+    {
+        Ok(costs) => costs,
+        Err(e) => {
+            error!("Failed to get jobsite labor costs: {}", e);
+            return;
+        }
+    };
+
+    let _ = transaction.commit().await;
+
+    let html = TemplateRenderer::render(move || {
+        view! {
+            <components::jobsite::JobsiteLaborCostPanel jobsite_id=jobsite_id days=days costs=costs />
+        }
+    });
+
+    let _ = session.text(html).await;
+}
+
+async fn send_jobsite_budget_update(
+    session: &mut Session,
+    jobsite_id: Uuid,
+    db_pool: &web::Data<PgPool>,
+) {
+    let mut transaction = match db_pool.begin().await {
+        Ok(transaction) => transaction,
+        Err(e) => {
+            error!("Failed to start transaction: {}", e);
+            return;
+        }
+    };
+
+    let budgets = JobsiteBudget::get_list_by_jobsite(&mut transaction, &jobsite_id).await;
+    let alerts = JobsiteBudgetAlert::get_list_by_jobsite(&mut transaction, &jobsite_id).await;
+
+    let (budgets, alerts) = match (budgets, alerts) {
+        (Ok(budgets), Ok(alerts)) => (budgets, alerts),
+        (Err(e), _) | (_, Err(e)) => {
+            error!("Failed to get jobsite budgets: {}", e);
+            return;
+        }
+    };
+
+    let _ = transaction.commit().await;
+
+    let html = TemplateRenderer::render(move || {
+        view! {
+            <components::jobsite::JobsiteBudgetPanel jobsite_id=jobsite_id budgets=budgets alerts=alerts />
+        }
+    });
+
+    let _ = session.text(html).await;
+}
+
+async fn send_jobsite_change_orders_update(
+    session: &mut Session,
+    jobsite_id: Uuid,
+    db_pool: &web::Data<PgPool>,
+) {
+    let mut transaction = match db_pool.begin().await {
+        Ok(transaction) => transaction,
+        Err(e) => {
+            error!("Failed to start transaction: {}", e);
+            return;
+        }
+    };
+
+    let change_orders = match ChangeOrder::get_list_by_jobsite(&mut transaction, &jobsite_id).await
+    {
+        Ok(change_orders) => change_orders,
+        Err(e) => {
+            error!("Failed to get jobsite change orders: {}", e);
+            return;
+        }
+    };
+
+    let _ = transaction.commit().await;
+
+    let html = TemplateRenderer::render(move || {
+        view! {
+            <components::change_order::JobsiteChangeOrders jobsite_id=jobsite_id change_orders=change_orders />
+        }
+    });
+
+    let _ = session.text(html).await;
+}
+
+/// The thread only, so anything being typed into the post form is kept
+async fn send_jobsite_comments_update(
+    session: &mut Session,
+    jobsite_id: Uuid,
+    db_pool: &web::Data<PgPool>,
+) {
+    let mut transaction = match db_pool.begin().await {
+        Ok(transaction) => transaction,
+        Err(e) => {
+            error!("Failed to start transaction: {}", e);
+            return;
+        }
+    };
+
+    let jobsite = match Jobsite::get_by_id(&mut transaction, &jobsite_id).await {
+        Ok(Some(jobsite)) => jobsite,
+        Ok(None) => return,
+        Err(e) => {
+            error!("Failed to get jobsite: {}", e);
+            return;
+        }
+    };
+
+    let comments = match Comment::get_list_by_jobsite(&mut transaction, &jobsite_id).await {
+        Ok(comments) => comments,
+        Err(e) => {
+            error!("Failed to get jobsite comments: {}", e);
+            return;
+        }
+    };
+
+    let employees =
+        match Employee::get_list_active_on(&mut transaction, jobsite.local_date(Utc::now())).await
+        {
+            Ok(employees) => employees,
+            Err(e) => {
+                error!("Failed to get employees: {}", e);
+                return;
+            }
+        };
+
+    let _ = transaction.commit().await;
+
+    let time_zone = jobsite.tz();
+
+    let html = TemplateRenderer::render(move || {
+        view! {
+            <components::comment::JobsiteComments
+                jobsite_id=jobsite_id
+                time_zone=time_zone
+                comments=comments
+                employees=employees
+            />
+        }
+    });
+
+    let _ = session.text(html).await;
+}
+
+async fn send_jobsite_cost_codes_update(
+    session: &mut Session,
+    jobsite_id: Uuid,
+    db_pool: &web::Data<PgPool>,
+) {
+    let mut transaction = match db_pool.begin().await {
+        Ok(transaction) => transaction,
+        Err(e) => {
+            error!("Failed to start transaction: {}", e);
+            return;
+        }
+    };
+
+    let cost_codes = CostCode::get_hours_by_jobsite(&mut transaction, &jobsite_id).await;
+    let uncoded_minutes = CostCode::get_uncoded_minutes(&mut transaction, &jobsite_id).await;
+
+    let (cost_codes, uncoded_minutes) = match (cost_codes, uncoded_minutes) {
+        (Ok(cost_codes), Ok(uncoded_minutes)) => (cost_codes, uncoded_minutes),
+        (Err(e), _) | (_, Err(e)) => {
+            error!("Failed to get cost code hours: {}", e);
+            return;
+        }
+    };
+
+    let _ = transaction.commit().await;
+
+    let html = TemplateRenderer::render(move || {
+        view! {
+            <components::cost_code::JobsiteCostCodes
+                jobsite_id=jobsite_id
+                cost_codes=cost_codes
+                uncoded_minutes=uncoded_minutes
+            />
+        }
+    });
+
+    let _ = session.text(html).await;
+}
+
+async fn send_cost_code_create_update(
+    session: &mut Session,
+    jobsite_id: Uuid,
+    db_pool: &web::Data<PgPool>,
+) {
+    let mut transaction = match db_pool.begin().await {
+        Ok(transaction) => transaction,
+        Err(e) => {
+            error!("Failed to start transaction: {}", e);
+            return;
+        }
+    };
+
+    let parents = match CostCode::get_active_by_jobsite(&mut transaction, &jobsite_id).await {
+        Ok(parents) => parents,
+        Err(e) => {
+            error!("Failed to get cost codes: {}", e);
+            return;
+        }
+    };
+
+    let _ = transaction.commit().await;
+
+    let html = TemplateRenderer::render(move || {
+        view! {
+            <components::cost_code::CostCodeCreate jobsite_id=jobsite_id parents=parents />
+        }
+    });
+
+    let _ = session.text(html).await;
+}
+
+async fn send_pay_rate_set_update(session: &mut Session, pay_rate: PayRate) {
+    let pay_rate_id = pay_rate.id;
+
+    let html = TemplateRenderer::render(move || {
+        view! {
+            <components::pay_rate::PayRateList pay_rates=vec![pay_rate] append=pay_rate_id />
+        }
+    });
+
+    let _ = session.text(html).await;
+}
+
+async fn send_timesheet_shift_update(session: &mut Session, shift: Shift) {
+    // Corrections are only accepted on weeks that aren't locked
+    let html = TemplateRenderer::render(move || {
+        view! {
+            <components::timesheet::TimesheetShiftRow shift=shift locked=false />
+        }
+    });
+
+    let _ = session.text(html).await;
+}
+
+async fn send_timesheet_submitted_update(
+    session: &mut Session,
+    timesheet: Timesheet,
+    db_pool: &web::Data<PgPool>,
+) {
+    let mut transaction = match db_pool.begin().await {
+        Ok(transaction) => transaction,
+        Err(e) => {
+            error!("Failed to start transaction: {}", e);
+            return;
+        }
+    };
+
+    let review = match Timesheet::get_review(
+        &mut transaction,
+        &timesheet.employee_id,
+        &timesheet.week_start,
+    )
+    .await
+    {
+        Ok(Some(review)) => review,
+        Ok(None) => return,
+        Err(e) => {
+            error!("Failed to get timesheet review: {}", e);
+            return;
+        }
+    };
+
+    let _ = transaction.commit().await;
+
+    let append = components::timesheet::review_row_id(&review.employee_id, &review.week_start);
+
+    let html = TemplateRenderer::render(move || {
+        view! {
+            <components::timesheet::TimesheetStatusPanel
+                employee_id=timesheet.employee_id
+                week_start=timesheet.week_start
+                timesheet=Some(timesheet.clone())
+            />
+            <components::timesheet::TimesheetReviewQueue reviews=vec![review] append=append />
+        }
+    });
+
+    let _ = session.text(html).await;
+}
+
+async fn send_timesheet_status_update(session: &mut Session, timesheet: Timesheet) {
+    let review_row_id =
+        components::timesheet::review_row_id(&timesheet.employee_id, &timesheet.week_start);
+    let reviewed = timesheet.status != TimesheetStatus::Submitted;
+
+    let html = TemplateRenderer::render(move || {
+        view! {
+            <components::timesheet::TimesheetStatusPanel
+                employee_id=timesheet.employee_id
+                week_start=timesheet.week_start
+                timesheet=Some(timesheet.clone())
+            />
+            // Reviewed timesheets leave the queue
+            {reviewed.then(|| view! { <div id=review_row_id hx-swap-oob="delete"></div> })}
+        }
+    });
+
+    let _ = session.text(html).await;
+}
+
 #[derive(Serialize, Deserialize)]
 #[serde(tag = "type")]
 pub enum JobsiteClientMessage {
     JobsiteLoading { jobsite_id: Uuid },
     JobsiteRegister { jobsite_id: Uuid },
+    WatchJobsite { jobsite_id: Uuid },
+    WatchSchedule { week_start: NaiveDate },
+    WatchDailyLogs { jobsite_id: Uuid },
 }
@@ -3,6 +3,7 @@ use std::{fmt, rc::Rc};
 use actix_web::{HttpResponse, ResponseError};
 use anyhow::bail;
 use leptos::*;
+use services::configuration::ApplicationSettings;
 
 use crate::views::{FormError, TemplateRenderer};
 
@@ -40,6 +41,26 @@ impl ResponseError for RouteError {
     }
 }
 
+/// The option whose `as_str` is `value`. Forms only offer the known options,
+/// so any other value came from a tampered form and is an unexpected error
+/// rather than something to show the user.
+pub fn form_choice<T: Copy>(
+    options: impl IntoIterator<Item = T>,
+    as_str: impl Fn(&T) -> &'static str,
+    value: &str,
+    label: &str,
+) -> Result<T, RouteError> {
+    options
+        .into_iter()
+        .find(|option| as_str(option) == value)
+        .ok_or_else(|| RouteError::UnexpectedError(anyhow::anyhow!("Unknown {}", label)))
+}
+
+/// Absolute URL for `path` on the app's configured public address
+pub fn absolute_url(settings: &ApplicationSettings, path: &str) -> String {
+    format!("{}{}", settings.base_url.trim_end_matches('/'), path)
+}
+
 fn error_chain_fmt(
     e: &impl std::error::Error,
     f: &mut std::fmt::Formatter<'_>,
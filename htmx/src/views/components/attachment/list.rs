@@ -0,0 +1,66 @@
+use leptos::*;
+use models::projections::attachment::Attachment;
+use uuid::Uuid;
+
+use crate::{
+    routes::ApiRoutes,
+    views::components::attachment::{jobsite_attachment_error_id, AttachmentUpload},
+};
+
+/// Attachments shown as thumbnails, or by file name when they aren't images
+#[component]
+pub fn AttachmentList(attachments: Vec<Attachment>, locked: bool) -> impl IntoView {
+    view! {
+        <div class="flex flex-row flex-wrap gap-2 mt-1">
+            {attachments.into_iter().map(|attachment| {
+                let preview = match attachment.thumbnail_hash {
+                    Some(_) => view! {
+                        <img
+                            src=ApiRoutes::get_attachment_thumbnail(attachment.id)
+                            alt=attachment.file_name.clone()
+                            class="h-16 w-16 object-cover rounded-md"
+                        />
+                    }.into_view(),
+                    None => view! {
+                        <span class="h-16 w-16 flex items-center justify-center bg-gray-200 rounded-md text-xs text-center break-all p-1">
+                            {attachment.file_name.clone()}
+                        </span>
+                    }.into_view(),
+                };
+
+                view! {
+                    <div id=format!("attachment_{}", attachment.id) class="flex flex-col items-center text-xs">
+                        <a href=ApiRoutes::get_attachment(attachment.id) target="_blank" title=attachment.file_name>
+                            {preview}
+                        </a>
+                        {(!locked).then(|| view! {
+                            <button
+                                hx-post=ApiRoutes::post_attachment_remove(attachment.id)
+                                hx-swap="none"
+                                hx-confirm="Remove this attachment?"
+                                class="text-red-700 hover:text-red-900"
+                            >
+                                Remove
+                            </button>
+                        })}
+                    </div>
+                }
+            }).collect::<Vec<_>>().into_view()}
+        </div>
+    }
+}
+
+/// A jobsite's own attachments along with the form to add more
+#[component]
+pub fn JobsiteAttachments(jobsite_id: Uuid, attachments: Vec<Attachment>) -> impl IntoView {
+    view! {
+        <div id=format!("jobsite_attachments_{}", jobsite_id) class="mt-4 w-full text-white">
+            <span class="font-bold">Attachments</span>
+            <AttachmentUpload
+                action=ApiRoutes::post_jobsite_attachment(jobsite_id)
+                error_id=jobsite_attachment_error_id(&jobsite_id)
+            />
+            <AttachmentList attachments=attachments locked=false />
+        </div>
+    }
+}
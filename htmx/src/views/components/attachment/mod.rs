@@ -0,0 +1,5 @@
+mod list;
+mod upload;
+
+pub use list::*;
+pub use upload::*;
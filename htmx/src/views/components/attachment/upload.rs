@@ -0,0 +1,33 @@
+use chrono::NaiveDate;
+use leptos::*;
+use uuid::Uuid;
+
+use crate::views::FormError;
+
+/// File types the upload picker offers, the server checks the content itself
+const ACCEPTED_FILES: &str = ".jpg,.jpeg,.png,.gif,.webp,.pdf";
+
+pub fn jobsite_attachment_error_id(jobsite_id: &Uuid) -> String {
+    format!("jobsite_attachment_error_{}", jobsite_id)
+}
+
+pub fn daily_log_attachment_error_id(log_date: &NaiveDate) -> String {
+    format!("daily_log_attachment_error_{}", log_date)
+}
+
+#[component]
+pub fn AttachmentUpload(action: String, error_id: String) -> impl IntoView {
+    view! {
+        <form
+          hx-post=action
+          hx-swap="none"
+          hx-encoding="multipart/form-data"
+          hx-on:htmx:after-request="if (event.detail.successful) this.reset()"
+          class="mt-1 flex flex-row items-center gap-2 text-sm"
+        >
+          <input type="file" name="file" accept=ACCEPTED_FILES class="flex-grow text-black" />
+          <button class="text-orange-900 hover:text-orange-700">Attach</button>
+        </form>
+        <FormError id=error_id />
+    }
+}
@@ -0,0 +1,23 @@
+use leptos::*;
+
+/// Subscription link for a calendar feed, along with the URL itself for
+/// calendar apps that need it pasted in
+#[component]
+pub fn CalendarFeedLink(url: String) -> impl IntoView {
+    let webcal_url = match url.split_once("://") {
+        Some((_, rest)) => format!("webcal://{}", rest),
+        None => url.clone(),
+    };
+
+    view! {
+        <div class="mt-4 w-full text-sm text-white">
+            <a class="hover:text-orange-500 underline" href=webcal_url>Subscribe to calendar</a>
+            <input
+                readonly
+                value=url
+                class="mt-1 p-1 w-full border rounded-md text-black text-xs"
+                onclick="this.select()"
+            />
+        </div>
+    }
+}
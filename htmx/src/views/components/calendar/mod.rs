@@ -0,0 +1,3 @@
+mod feed_link;
+
+pub use feed_link::*;
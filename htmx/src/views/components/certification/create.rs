@@ -0,0 +1,61 @@
+use chrono::NaiveDate;
+use leptos::*;
+use models::projections::{certification::CertificationType, employee::Employee};
+
+use crate::{routes::ApiRoutes, views::FormError};
+
+/// Form for recording a certification, recording one an employee already
+/// holds renews it
+#[component]
+pub fn CertificationCreate(employees: Vec<Employee>, today: NaiveDate) -> impl IntoView {
+    view! {
+        <form
+          hx-post=ApiRoutes::post_certification()
+          hx-swap="none"
+          hx-disabled-elt="#certification-submit"
+          hx-on:htmx:after-request="if (event.detail.successful) this.reset()"
+          class="w-full flex flex-row flex-wrap items-end gap-2"
+        >
+          <div class="flex-grow">
+            <label class="block text-sm font-medium text-white">Employee</label>
+            <select name="employee_id" class="mt-1 p-2 w-full border rounded-md text-black">
+              {employees.into_iter().map(|employee| view! {
+                  <option value=employee.id.to_string()>{employee.name}</option>
+              }).collect::<Vec<_>>().into_view()}
+            </select>
+          </div>
+          <div>
+            <label class="block text-sm font-medium text-white">Certification</label>
+            <select name="certification_type" class="mt-1 p-2 border rounded-md text-black">
+              {CertificationType::ALL.into_iter().map(|certification_type| view! {
+                  <option value=certification_type.as_str()>{certification_type.label()}</option>
+              }).collect::<Vec<_>>().into_view()}
+            </select>
+          </div>
+          <div>
+            <label class="block text-sm font-medium text-white">Number</label>
+            <input name="certificate_number" class="mt-1 p-2 w-32 border rounded-md text-black" />
+          </div>
+          <div>
+            <label class="block text-sm font-medium text-white">Issued</label>
+            <input
+                type="date"
+                name="issued_on"
+                max=today.to_string()
+                class="mt-1 p-2 border rounded-md text-black"
+            />
+          </div>
+          <div>
+            <label class="block text-sm font-medium text-white">Expires</label>
+            <input type="date" name="expires_on" class="mt-1 p-2 border rounded-md text-black" />
+          </div>
+          <button id="certification-submit" class="bg-orange-600 disabled:bg-orange-300 text-white p-2 rounded-md hover:bg-orange-700">
+            Record
+          </button>
+          <div class="w-full">
+            <FormError id="certification-employee-error".to_string() />
+            <FormError id="certification-date-error".to_string() />
+          </div>
+        </form>
+    }
+}
@@ -0,0 +1,39 @@
+use leptos::*;
+use models::projections::certification::CertificationType;
+use uuid::Uuid;
+
+use crate::routes::ApiRoutes;
+
+/// The certifications a jobsite's crew have to hold
+#[component]
+pub fn JobsiteCertifications(jobsite_id: Uuid, required: Vec<CertificationType>) -> impl IntoView {
+    let submit_id = format!("jobsite-certifications-submit-{}", jobsite_id);
+
+    view! {
+        <form
+          id=format!("jobsite_certifications_{}", jobsite_id)
+          hx-post=ApiRoutes::post_jobsite_certifications(jobsite_id)
+          hx-swap="none"
+          hx-disabled-elt=format!("#{}", submit_id)
+          class="mt-4 w-full"
+        >
+          <label class="block text-sm font-medium text-white">Required Certifications</label>
+          <div class="mt-1 flex flex-row flex-wrap gap-x-4 text-white text-sm">
+            {CertificationType::ALL.into_iter().map(|certification_type| view! {
+                <label class="flex items-center gap-1">
+                    <input
+                        type="checkbox"
+                        name="certification_type"
+                        value=certification_type.as_str()
+                        checked=required.contains(&certification_type)
+                    />
+                    {certification_type.label()}
+                </label>
+            }).collect::<Vec<_>>().into_view()}
+          </div>
+          <button id=submit_id class="mt-2 w-full bg-orange-600 disabled:bg-orange-300 text-white p-2 rounded-md hover:bg-orange-700">
+            Save Requirements
+          </button>
+        </form>
+    }
+}
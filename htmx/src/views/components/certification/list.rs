@@ -0,0 +1,68 @@
+use chrono::NaiveDate;
+use leptos::*;
+use models::projections::certification::Certification;
+
+/// Every certification on record
+#[component]
+pub fn CertificationList(certifications: Vec<Certification>, today: NaiveDate) -> impl IntoView {
+    view! {
+        <div class="w-11/12 mx-auto rounded-md p-4" id="certification-list">
+            {certifications.into_iter().map(|certification| view! {
+                <CertificationRow certification=certification today=today />
+            }).collect::<Vec<_>>().into_view()}
+        </div>
+    }
+}
+
+/// Certifications that are about to lapse or have lapsed without being
+/// renewed
+#[component]
+pub fn CertificationAlerts(alerts: Vec<Certification>, today: NaiveDate) -> impl IntoView {
+    view! {
+        <div class="w-11/12 mx-auto rounded-md p-4" id="certification-alerts">
+            {if alerts.is_empty() {
+                view! { <span class="text-white">Nothing is about to expire</span> }.into_view()
+            } else {
+                alerts.into_iter().map(|certification| view! {
+                    <CertificationRow certification=certification today=today />
+                }).collect::<Vec<_>>().into_view()
+            }}
+        </div>
+    }
+}
+
+#[component]
+pub fn CertificationRow(certification: Certification, today: NaiveDate) -> impl IntoView {
+    let (status, status_class) = if certification.is_expired_on(today) {
+        ("Expired", "text-red-700")
+    } else if certification.is_expiring_on(today) {
+        ("Expiring soon", "text-orange-800")
+    } else {
+        ("Valid", "text-gray-700")
+    };
+
+    view! {
+        <div class="flex items-center justify-between p-2 my-2 bg-gray-400 rounded-md">
+            <div class="flex flex-col">
+                <span class="text-lg">
+                    {format!("{}, {}", certification.employee_name, certification.certification_type.label())}
+                </span>
+                <span class="text-sm text-gray-700">
+                    {format!(
+                        "Issued {}{}{}",
+                        certification.issued_on.format("%b %-d, %Y"),
+                        certification
+                            .expires_on
+                            .map(|expires_on| format!(", expires {}", expires_on.format("%b %-d, %Y")))
+                            .unwrap_or_default(),
+                        certification
+                            .certificate_number
+                            .map(|number| format!(", #{}", number))
+                            .unwrap_or_default()
+                    )}
+                </span>
+            </div>
+            <span class=format!("text-sm {}", status_class)>{status}</span>
+        </div>
+    }
+}
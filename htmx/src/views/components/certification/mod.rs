@@ -0,0 +1,7 @@
+mod create;
+mod jobsite;
+mod list;
+
+pub use create::*;
+pub use jobsite::*;
+pub use list::*;
@@ -0,0 +1,45 @@
+use leptos::*;
+use uuid::Uuid;
+
+use crate::{routes::ApiRoutes, views::FormError};
+
+/// Form for drafting a change to a jobsite's scope
+#[component]
+pub fn ChangeOrderCreate(jobsite_id: Uuid) -> impl IntoView {
+    let submit_id = format!("change-order-submit-{}", jobsite_id);
+
+    view! {
+        <form
+          hx-post=ApiRoutes::post_change_order(jobsite_id)
+          hx-swap="none"
+          hx-disabled-elt=format!("#{}", submit_id)
+          hx-on:htmx:after-request="if (event.detail.successful) this.reset()"
+          class="mt-2 w-full"
+        >
+          <div class="mb-4">
+            <label class="block text-sm font-medium text-white">Title</label>
+            <input name="title" placeholder="Add second floor washroom" class="mt-1 p-2 w-full border rounded-md text-black" />
+            <FormError id=format!("change_order_title_error_{}", jobsite_id) />
+          </div>
+          <div class="mb-4">
+            <label class="block text-sm font-medium text-white">Description</label>
+            <textarea name="description" rows="2" class="mt-1 p-2 w-full border rounded-md text-black"></textarea>
+          </div>
+          <div class="mb-4 flex flex-row gap-2">
+            <div class="w-1/2">
+              <label class="block text-sm font-medium text-white">Amount ($)</label>
+              <input name="amount" placeholder="4500 or -1200" class="mt-1 p-2 w-full border rounded-md text-black" />
+              <FormError id=format!("change_order_amount_error_{}", jobsite_id) />
+            </div>
+            <div class="w-1/2">
+              <label class="block text-sm font-medium text-white">Hours</label>
+              <input name="hours" placeholder="40 or -16" class="mt-1 p-2 w-full border rounded-md text-black" />
+              <FormError id=format!("change_order_hours_error_{}", jobsite_id) />
+            </div>
+          </div>
+          <button id=submit_id class="w-full bg-orange-600 disabled:bg-orange-300 text-white p-2 rounded-md hover:bg-orange-700">
+            Draft Change Order
+          </button>
+        </form>
+    }
+}
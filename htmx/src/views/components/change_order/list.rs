@@ -0,0 +1,137 @@
+use chrono::{DateTime, Utc};
+use leptos::*;
+use models::projections::change_order::{ChangeOrder, ChangeOrderStatus};
+use uuid::Uuid;
+
+use crate::{
+    routes::ApiRoutes,
+    views::{format_signed_cents, format_signed_minutes, FormError},
+};
+
+/// The element id of the errors shown under a change order's actions
+pub fn change_order_action_error_id(change_order_id: &Uuid) -> String {
+    format!("change_order_action_error_{}", change_order_id)
+}
+
+/// A jobsite's change orders in the order they were drafted, with what the
+/// approved ones have added to the contract
+#[component]
+pub fn JobsiteChangeOrders(jobsite_id: Uuid, change_orders: Vec<ChangeOrder>) -> impl IntoView {
+    let approved = change_orders
+        .iter()
+        .filter(|change_order| change_order.status == ChangeOrderStatus::Approved)
+        .collect::<Vec<_>>();
+    let approved_cents = approved
+        .iter()
+        .map(|change_order| change_order.amount_cents)
+        .sum::<i64>();
+    let approved_minutes = approved
+        .iter()
+        .map(|change_order| change_order.minutes)
+        .sum::<i64>();
+    let any_approved = !approved.is_empty();
+
+    view! {
+        <div id=format!("jobsite_change_orders_{}", jobsite_id) class="mt-6 w-full">
+            <div class="text-center mb-2">
+                <span class="text-orange-700 text-xl font-bold" style="font-family: 'Roboto Slab', serif;">Change Orders</span>
+            </div>
+            {if change_orders.is_empty() {
+                view! {
+                    <div class="text-center text-white italic">No change orders</div>
+                }.into_view()
+            } else {
+                change_orders.into_iter().map(|change_order| view! {
+                    <ChangeOrderRow change_order=change_order />
+                }).collect::<Vec<_>>().into_view()
+            }}
+            {any_approved.then(|| view! {
+                <div class="flex items-center justify-between p-2 text-white">
+                    <span class="italic">Approved</span>
+                    <span>
+                        {format!(
+                            "{}, {}",
+                            format_signed_cents(approved_cents),
+                            format_signed_minutes(approved_minutes)
+                        )}
+                    </span>
+                </div>
+            })}
+        </div>
+    }
+}
+
+#[component]
+pub fn ChangeOrderRow(change_order: ChangeOrder) -> impl IntoView {
+    let change_order_id = change_order.id;
+
+    // Each step the change order has been through, oldest first
+    let timeline = [
+        Some(("Drafted", change_order.drafted_at)),
+        change_order.submitted_at.map(|at| ("Submitted", at)),
+        change_order.approved_at.map(|at| ("Approved", at)),
+        change_order.rejected_at.map(|at| ("Rejected", at)),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<(&str, DateTime<Utc>)>>();
+
+    view! {
+        <div id=format!("change_order_row_{}", change_order_id) class="p-2 my-2 bg-gray-400 rounded-md">
+            <div class="flex items-center justify-between">
+                <span class="text-lg">{change_order.title}</span>
+                <span class="text-sm">{change_order.status.label()}</span>
+            </div>
+            <span class="text-sm text-gray-700">
+                {format!(
+                    "{}, {}",
+                    format_signed_cents(change_order.amount_cents),
+                    format_signed_minutes(change_order.minutes)
+                )}
+            </span>
+            {change_order.description.map(|description| view! {
+                <p class="text-sm whitespace-pre-line">{description}</p>
+            })}
+            <ol class="mt-1 text-sm text-gray-700">
+                {timeline.into_iter().map(|(step, at)| view! {
+                    <li>{format!("{} {}", step, at.format("%b %-d, %Y"))}</li>
+                }).collect::<Vec<_>>().into_view()}
+            </ol>
+            {change_order.rejection_reason.map(|reason| view! {
+                <p class="text-sm whitespace-pre-line">{format!("Rejected: {}", reason)}</p>
+            })}
+            {match change_order.status {
+                ChangeOrderStatus::Drafted => view! {
+                    <button
+                        hx-post=ApiRoutes::post_change_order_submit(change_order_id)
+                        hx-swap="none"
+                        class="mt-1 text-sm bg-orange-600 text-white p-1 rounded-md hover:bg-orange-700"
+                    >
+                        Submit to Client
+                    </button>
+                }.into_view(),
+                ChangeOrderStatus::Submitted => view! {
+                    <div class="mt-1 flex flex-row items-center gap-2 text-sm">
+                        <button
+                            hx-post=ApiRoutes::post_change_order_approve(change_order_id)
+                            hx-swap="none"
+                            class="bg-orange-600 text-white p-1 rounded-md hover:bg-orange-700"
+                        >
+                            Approve
+                        </button>
+                        <form
+                          hx-post=ApiRoutes::post_change_order_reject(change_order_id)
+                          hx-swap="none"
+                          class="flex flex-grow flex-row items-center gap-2"
+                        >
+                          <input name="reason" placeholder="Reason" class="p-1 flex-grow border rounded-md text-black" />
+                          <button class="text-orange-900 hover:text-orange-700">Reject</button>
+                        </form>
+                    </div>
+                    <FormError id=change_order_action_error_id(&change_order_id) />
+                }.into_view(),
+                ChangeOrderStatus::Approved | ChangeOrderStatus::Rejected => ().into_view(),
+            }}
+        </div>
+    }
+}
@@ -0,0 +1,25 @@
+use leptos::*;
+
+use crate::{routes::ApiRoutes, views::FormError};
+
+#[component]
+pub fn ClientCreate() -> impl IntoView {
+    view! {
+        <form
+          hx-post=ApiRoutes::post_client()
+          hx-swap="none"
+          hx-disabled-elt="#client-submit"
+          hx-on:htmx:after-request="if (event.detail.successful) this.reset()"
+          class="w-full"
+        >
+          <div class="mb-4">
+            <label class="block text-sm font-medium text-white">Client Name</label>
+            <input name="name" class="mt-1 p-2 w-full border rounded-md text-black" />
+            <FormError id="client-name-error".to_string() />
+          </div>
+          <button id="client-submit" class="w-full bg-orange-600 disabled:bg-orange-300 text-white p-2 rounded-md hover:bg-orange-700">
+            Add Client
+          </button>
+        </form>
+    }
+}
@@ -0,0 +1,48 @@
+use leptos::*;
+use models::projections::{client::Client, project::Project};
+use uuid::Uuid;
+
+use crate::views::components::project::ProjectList;
+
+#[component]
+pub fn ClientList(
+    clients: Vec<Client>,
+    projects: Vec<Project>,
+    #[prop(optional)] append: Option<Uuid>,
+) -> impl IntoView {
+    let hx_swap_oob = if append.is_some() { "afterbegin" } else { "" };
+    let data_append = if let Some(append) = append {
+        format!("client_row_{}", append)
+    } else {
+        "".to_string()
+    };
+
+    view! {
+        <div
+            class="w-11/12 mx-auto rounded-md p-4"
+            id="client-list"
+            hx-swap-oob=hx_swap_oob
+            data-append=data_append
+        >
+            {clients.into_iter().map(|client| {
+                let client_projects = projects
+                    .iter()
+                    .filter(|project| project.client_id == client.id)
+                    .cloned()
+                    .collect::<Vec<_>>();
+
+                view! { <ClientRow client=client projects=client_projects /> }
+            }).collect::<Vec<_>>().into_view()}
+        </div>
+    }
+}
+
+#[component]
+pub fn ClientRow(client: Client, projects: Vec<Project>) -> impl IntoView {
+    view! {
+        <div id=format!("client_row_{}", client.id) class="p-2 my-2 bg-gray-400 rounded-md">
+            <span class="text-lg">{client.name}</span>
+            <ProjectList client_id=client.id projects=projects />
+        </div>
+    }
+}
@@ -0,0 +1,41 @@
+use leptos::*;
+use models::projections::employee::Employee;
+use uuid::Uuid;
+
+use crate::{routes::ApiRoutes, views::FormError};
+
+/// Form for adding to a jobsite's thread. There are no logins, so whoever
+/// is posting picks themselves.
+#[component]
+pub fn CommentCreate(jobsite_id: Uuid, employees: Vec<Employee>) -> impl IntoView {
+    let submit_id = format!("comment-submit-{}", jobsite_id);
+
+    view! {
+        <form
+          hx-post=ApiRoutes::post_jobsite_comment(jobsite_id)
+          hx-swap="none"
+          hx-disabled-elt=format!("#{}", submit_id)
+          hx-on:htmx:after-request="if (event.detail.xhr.status === 201) this.body.value = ''"
+          class="mt-2 w-full"
+        >
+          <textarea
+              name="body"
+              rows="2"
+              placeholder="Leave a note, @Name to mention someone"
+              class="p-2 w-full border rounded-md text-black"
+          ></textarea>
+          <div class="mt-1 flex flex-row gap-2">
+            <select name="author_id" class="p-2 flex-grow border rounded-md text-black">
+              <option value="">Posting as...</option>
+              {employees.into_iter().map(|employee| view! {
+                  <option value=employee.id.to_string()>{employee.name}</option>
+              }).collect_view()}
+            </select>
+            <button id=submit_id class="bg-orange-600 disabled:bg-orange-300 text-white p-2 rounded-md hover:bg-orange-700">
+              Post
+            </button>
+          </div>
+          <FormError id=format!("comment_error_{}", jobsite_id) />
+        </form>
+    }
+}
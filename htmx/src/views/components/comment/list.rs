@@ -0,0 +1,101 @@
+use chrono_tz::Tz;
+use leptos::*;
+use models::projections::{comment::Comment, employee::Employee};
+use uuid::Uuid;
+
+use crate::{routes::ApiRoutes, views::FormError};
+
+/// The element id of the errors shown under a comment's edit and delete forms
+pub fn comment_action_error_id(comment_id: &Uuid) -> String {
+    format!("comment_action_error_{}", comment_id)
+}
+
+/// A jobsite's comment thread, oldest first, with times where the jobsite is
+#[component]
+pub fn JobsiteComments(
+    jobsite_id: Uuid,
+    time_zone: Tz,
+    comments: Vec<Comment>,
+    employees: Vec<Employee>,
+) -> impl IntoView {
+    view! {
+        <div id=format!("jobsite_comments_{}", jobsite_id) class="mt-6 w-full">
+            <div class="text-center mb-2">
+                <span class="text-orange-700 text-xl font-bold" style="font-family: 'Roboto Slab', serif;">Comments</span>
+            </div>
+            {if comments.is_empty() {
+                view! {
+                    <div class="text-center text-white italic">No comments</div>
+                }.into_view()
+            } else {
+                comments.into_iter().map(|comment| view! {
+                    <CommentRow comment=comment time_zone=time_zone employees=employees.clone() />
+                }).collect::<Vec<_>>().into_view()
+            }}
+        </div>
+    }
+}
+
+#[component]
+pub fn CommentRow(comment: Comment, time_zone: Tz, employees: Vec<Employee>) -> impl IntoView {
+    let comment_id = comment.id;
+    let posted_at = comment
+        .posted_at
+        .with_timezone(&time_zone)
+        .format("%b %-d, %-I:%M %p");
+
+    // Editing and deleting are only let through for the author
+    let author_options = move || {
+        view! {
+            <option value="">Who are you?</option>
+            {employees.iter().map(|employee| view! {
+                <option value=employee.id.to_string()>{employee.name.clone()}</option>
+            }).collect_view()}
+        }
+    };
+
+    view! {
+        <div id=format!("comment_row_{}", comment_id) class="p-2 my-2 bg-gray-400 rounded-md">
+            <div class="flex items-center justify-between text-sm">
+                <span class="font-bold">{comment.author_name.unwrap_or_else(|| "Unknown".to_string())}</span>
+                <span class="text-gray-700">
+                    {format!("{}{}", posted_at, if comment.edited_at.is_some() { " (edited)" } else { "" })}
+                </span>
+            </div>
+            <p class="text-sm whitespace-pre-line">{comment.body.clone()}</p>
+            {(!comment.mentions.is_empty()).then(|| view! {
+                <p class="text-xs text-gray-700">
+                    {format!("Mentioned: {}", comment.mentions.join(", "))}
+                </p>
+            })}
+            <details class="mt-1 text-sm">
+                <summary class="cursor-pointer text-orange-900 hover:text-orange-700">Edit</summary>
+                <form
+                  hx-post=ApiRoutes::post_comment_edit(comment_id)
+                  hx-swap="none"
+                  class="mt-1 flex flex-col gap-1"
+                >
+                  <textarea name="body" rows="2" class="p-1 border rounded-md text-black">{comment.body}</textarea>
+                  <div class="flex flex-row gap-2">
+                    <select name="author_id" class="p-1 flex-grow border rounded-md text-black">
+                      {author_options.clone()}
+                    </select>
+                    <button class="bg-orange-600 text-white p-1 rounded-md hover:bg-orange-700">Save</button>
+                  </div>
+                </form>
+                <form
+                  hx-post=ApiRoutes::post_comment_delete(comment_id)
+                  hx-swap="none"
+                  hx-confirm="Delete this comment?"
+                  class="mt-1 flex flex-row gap-2"
+                >
+                  <select name="author_id" class="p-1 flex-grow border rounded-md text-black">
+                    {author_options}
+                  </select>
+                  <button class="text-red-700 hover:text-red-900">Delete</button>
+                </form>
+            </details>
+            <FormError id=comment_action_error_id(&comment_id) />
+        </div>
+    }
+}
@@ -0,0 +1,50 @@
+use leptos::*;
+use models::projections::cost_code::CostCode;
+use uuid::Uuid;
+
+use crate::{routes::ApiRoutes, views::FormError};
+
+#[component]
+pub fn CostCodeCreate(
+    jobsite_id: Uuid,
+    /// Active codes a new task can be added under
+    parents: Vec<CostCode>,
+) -> impl IntoView {
+    let submit_id = format!("cost-code-submit-{}", jobsite_id);
+
+    view! {
+        <form
+          id=format!("cost_code_create_{}", jobsite_id)
+          hx-post=ApiRoutes::post_cost_code(jobsite_id)
+          hx-swap="none"
+          hx-disabled-elt=format!("#{}", submit_id)
+          hx-on:htmx:after-request="if (event.detail.successful) this.reset()"
+          class="mt-2 w-full"
+        >
+          <div class="mb-4 flex flex-row gap-2">
+            <div class="w-1/3">
+              <label class="block text-sm font-medium text-white">Code</label>
+              <input name="code" placeholder="03-300" class="mt-1 p-2 w-full border rounded-md text-black" />
+              <FormError id=format!("cost_code_code_error_{}", jobsite_id) />
+            </div>
+            <div class="w-2/3">
+              <label class="block text-sm font-medium text-white">Name</label>
+              <input name="name" placeholder="Cast-in-place concrete" class="mt-1 p-2 w-full border rounded-md text-black" />
+              <FormError id=format!("cost_code_name_error_{}", jobsite_id) />
+            </div>
+          </div>
+          <div class="mb-4">
+            <label class="block text-sm font-medium text-white">Task Of</label>
+            <select name="parent_id" class="mt-1 p-2 w-full border rounded-md text-black">
+              <option value="">Nothing, a top level code</option>
+              {parents.into_iter().map(|parent| view! {
+                  <option value=parent.id.to_string()>{format!("{} {}", parent.code, parent.name)}</option>
+              }).collect::<Vec<_>>().into_view()}
+            </select>
+          </div>
+          <button id=submit_id class="w-full bg-orange-600 disabled:bg-orange-300 text-white p-2 rounded-md hover:bg-orange-700">
+            Add Cost Code
+          </button>
+        </form>
+    }
+}
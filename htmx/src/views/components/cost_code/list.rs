@@ -0,0 +1,89 @@
+use leptos::*;
+use models::projections::cost_code::CostCodeHours;
+use uuid::Uuid;
+
+use crate::{routes::ApiRoutes, views::format_minutes};
+
+#[component]
+pub fn JobsiteCostCodes(
+    jobsite_id: Uuid,
+    cost_codes: Vec<CostCodeHours>,
+    /// Hours on the jobsite not logged against any code
+    uncoded_minutes: i64,
+) -> impl IntoView {
+    view! {
+        <div id=format!("jobsite_cost_codes_{}", jobsite_id) class="mt-6 w-full">
+            <div class="text-center mb-2">
+                <span class="text-orange-700 text-xl font-bold" style="font-family: 'Roboto Slab', serif;">Cost Codes</span>
+            </div>
+            {if cost_codes.is_empty() {
+                view! {
+                    <div class="text-center text-white italic">No cost codes</div>
+                }.into_view()
+            } else {
+                CostCodeHours::tree(cost_codes)
+                    .into_iter()
+                    .map(|(depth, cost_code)| view! { <CostCodeRow cost_code=cost_code depth=depth /> })
+                    .collect::<Vec<_>>()
+                    .into_view()
+            }}
+            {(uncoded_minutes > 0).then(|| view! {
+                <div class="flex items-center justify-between p-2 text-white">
+                    <span class="italic">No cost code</span>
+                    <span>{format_minutes(uncoded_minutes)}</span>
+                </div>
+            })}
+        </div>
+    }
+}
+
+#[component]
+pub fn CostCodeRow(
+    cost_code: CostCodeHours,
+    /// How far beneath a top level code this task is
+    depth: usize,
+) -> impl IntoView {
+    let retired = cost_code.retired_at.is_some();
+    let class = if retired {
+        "flex items-center justify-between p-2 my-2 bg-gray-600 rounded-md"
+    } else {
+        "flex items-center justify-between p-2 my-2 bg-gray-400 rounded-md"
+    };
+
+    view! {
+        <div
+            class=class
+            style=format!("margin-left: {}rem", depth * 2)
+            id=format!("cost_code_row_{}", cost_code.id)
+        >
+            <div class="flex flex-col">
+                <span class="text-lg">{format!("{} {}", cost_code.code, cost_code.name)}</span>
+                <span class="text-sm text-gray-700">
+                    {if cost_code.total_minutes == cost_code.minutes {
+                        format_minutes(cost_code.minutes)
+                    } else {
+                        format!(
+                            "{} total, {} directly",
+                            format_minutes(cost_code.total_minutes),
+                            format_minutes(cost_code.minutes)
+                        )
+                    }}
+                </span>
+            </div>
+            {if retired {
+                view! { <span class="text-sm italic">Retired</span> }.into_view()
+            } else {
+                view! {
+                    <button
+                        hx-post=ApiRoutes::post_cost_code_retire(cost_code.id)
+                        hx-swap="none"
+                        hx-confirm="Retire this cost code and its tasks?"
+                        class="bg-orange-600 disabled:bg-orange-300 text-white p-2 rounded-md hover:bg-orange-700"
+                    >
+                        Retire
+                    </button>
+                }.into_view()
+            }}
+        </div>
+    }
+}
@@ -0,0 +1,109 @@
+use chrono::Duration;
+use leptos::*;
+use models::projections::{
+    crew_assignment::{CrewAssignment, CrewDay, CrewWeek},
+    jobsite::Jobsite,
+};
+
+use crate::routes::ApiRoutes;
+
+/// The weekly schedule grid, one row per employee and one column per day
+#[component]
+pub fn CrewCalendar(week: CrewWeek, jobsites: Vec<Jobsite>) -> impl IntoView {
+    let dates = (0..7)
+        .map(|offset| week.week_start + Duration::days(offset))
+        .collect::<Vec<_>>();
+
+    view! {
+        <div id=format!("crew_calendar_{}", week.week_start) class="w-full overflow-auto">
+            <table class="w-full table-fixed text-sm text-white">
+                <thead>
+                    <tr>
+                        <th class="w-32 text-left p-1">Employee</th>
+                        {dates.into_iter().map(|date| view! {
+                            <th class="p-1">{date.format("%a %-d").to_string()}</th>
+                        }).collect::<Vec<_>>().into_view()}
+                    </tr>
+                </thead>
+                <tbody>
+                    {if week.rows.is_empty() {
+                        view! {
+                            <tr>
+                                <td colspan="8" class="text-center italic p-2">No employees to schedule</td>
+                            </tr>
+                        }.into_view()
+                    } else {
+                        week.rows.into_iter().map(|row| view! {
+                            <tr class="border-t border-gray-600">
+                                <td class="p-1 align-top">{row.employee.name}</td>
+                                {row.days.into_iter().map(|day| view! {
+                                    <CrewCalendarCell day=day jobsites=jobsites.clone() />
+                                }).collect::<Vec<_>>().into_view()}
+                            </tr>
+                        }).collect::<Vec<_>>().into_view()
+                    }}
+                </tbody>
+            </table>
+        </div>
+    }
+}
+
+#[component]
+fn CrewCalendarCell(day: CrewDay, jobsites: Vec<Jobsite>) -> impl IntoView {
+    let class = if day.conflict.is_some() {
+        "p-1 align-top bg-red-900/60"
+    } else {
+        "p-1 align-top"
+    };
+
+    view! {
+        <td class=class>
+            {day.conflict.map(|conflict| view! {
+                <span class="block text-xs text-red-300 font-bold">{conflict.as_str()}</span>
+            })}
+            {day.leave.map(|leave_type| view! {
+                <span class="block text-xs text-orange-300 italic">{format!("{} leave", leave_type.label())}</span>
+            })}
+            {day.assignments.into_iter().map(|assignment| view! {
+                <CrewAssignmentChip assignment=assignment jobsites=jobsites.clone() />
+            }).collect::<Vec<_>>().into_view()}
+        </td>
+    }
+}
+
+/// A scheduled jobsite in a calendar cell, the dispatcher moves the crew
+/// member by picking another jobsite
+#[component]
+fn CrewAssignmentChip(assignment: CrewAssignment, jobsites: Vec<Jobsite>) -> impl IntoView {
+    let title = format!(
+        "{} to {}",
+        assignment.start_date.format("%b %-d"),
+        assignment.end_date.format("%b %-d")
+    );
+
+    view! {
+        <div class="my-1 flex flex-row items-center gap-1 rounded-md bg-gray-400 p-1 text-black" title=title>
+            <select
+                name="jobsite_id"
+                class="w-full truncate bg-transparent"
+                hx-post=ApiRoutes::post_crew_assignment_move(assignment.id)
+                hx-trigger="change"
+                hx-swap="none"
+            >
+                {jobsites.into_iter().map(|jobsite| view! {
+                    <option value=jobsite.id.to_string() selected=jobsite.id == assignment.jobsite_id>
+                        {jobsite.name}
+                    </option>
+                }).collect::<Vec<_>>().into_view()}
+            </select>
+            <button
+                class="hover:text-red-700"
+                hx-post=ApiRoutes::post_crew_assignment_unassign(assignment.id)
+                hx-swap="none"
+                hx-confirm=format!("Unassign {} from {}?", assignment.employee_name, assignment.jobsite_name)
+            >
+                "×"
+            </button>
+        </div>
+    }
+}
@@ -0,0 +1,49 @@
+use leptos::*;
+use models::projections::{employee::Employee, jobsite::Jobsite};
+
+use crate::{routes::ApiRoutes, views::FormError};
+
+#[component]
+pub fn CrewAssignmentCreate(employees: Vec<Employee>, jobsites: Vec<Jobsite>) -> impl IntoView {
+    view! {
+        <form
+          hx-post=ApiRoutes::post_crew_assignment()
+          hx-swap="none"
+          hx-disabled-elt="#crew-assignment-submit"
+          class="w-full flex flex-row flex-wrap items-end gap-2"
+        >
+          <div class="flex-grow">
+            <label class="block text-sm font-medium text-white">Employee</label>
+            <select name="employee_id" class="mt-1 p-2 w-full border rounded-md text-black">
+              {employees.into_iter().map(|employee| view! {
+                  <option value=employee.id.to_string()>{employee.name}</option>
+              }).collect::<Vec<_>>().into_view()}
+            </select>
+          </div>
+          <div class="flex-grow">
+            <label class="block text-sm font-medium text-white">Jobsite</label>
+            <select name="jobsite_id" class="mt-1 p-2 w-full border rounded-md text-black">
+              {jobsites.into_iter().map(|jobsite| view! {
+                  <option value=jobsite.id.to_string()>{jobsite.name}</option>
+              }).collect::<Vec<_>>().into_view()}
+            </select>
+          </div>
+          <div>
+            <label class="block text-sm font-medium text-white">From</label>
+            <input type="date" name="start_date" class="mt-1 p-2 border rounded-md text-black" />
+          </div>
+          <div>
+            <label class="block text-sm font-medium text-white">To</label>
+            <input type="date" name="end_date" class="mt-1 p-2 border rounded-md text-black" />
+          </div>
+          <button id="crew-assignment-submit" class="bg-orange-600 disabled:bg-orange-300 text-white p-2 rounded-md hover:bg-orange-700">
+            Assign
+          </button>
+          <div class="w-full">
+            <FormError id="crew-assignment-target-error".to_string() />
+            <FormError id="crew-assignment-date-error".to_string() />
+            <FormError id="crew-assignment-certification-warning".to_string() />
+          </div>
+        </form>
+    }
+}
@@ -0,0 +1,5 @@
+mod calendar;
+mod create;
+
+pub use calendar::*;
+pub use create::*;
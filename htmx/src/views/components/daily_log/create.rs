@@ -0,0 +1,58 @@
+use chrono::NaiveDate;
+use leptos::*;
+use models::projections::daily_log::WeatherCondition;
+use uuid::Uuid;
+
+use crate::{routes::ApiRoutes, views::FormError};
+
+/// Form for logging a day, saving a day that's already logged rewrites it
+#[component]
+pub fn DailyLogCreate(jobsite_id: Uuid, today: NaiveDate) -> impl IntoView {
+    view! {
+        <form
+          hx-post=ApiRoutes::post_daily_log(jobsite_id)
+          hx-swap="none"
+          hx-disabled-elt="#daily-log-submit"
+          class="w-full flex flex-row flex-wrap items-end gap-2"
+        >
+          <div>
+            <label class="block text-sm font-medium text-white">Date</label>
+            <input
+                type="date"
+                name="log_date"
+                value=today.to_string()
+                max=today.to_string()
+                class="mt-1 p-2 border rounded-md text-black"
+            />
+          </div>
+          <div>
+            <label class="block text-sm font-medium text-white">Crew</label>
+            <input
+                type="number"
+                name="crew_count"
+                min="0"
+                class="mt-1 p-2 w-24 border rounded-md text-black"
+            />
+          </div>
+          <div>
+            <label class="block text-sm font-medium text-white">Weather</label>
+            <select name="weather" class="mt-1 p-2 border rounded-md text-black">
+              {WeatherCondition::ALL.into_iter().map(|weather| view! {
+                  <option value=weather.as_str()>{weather.as_str()}</option>
+              }).collect::<Vec<_>>().into_view()}
+            </select>
+          </div>
+          <div class="flex-grow">
+            <label class="block text-sm font-medium text-white">Notes</label>
+            <textarea name="notes" rows="1" class="mt-1 p-2 w-full border rounded-md text-black"></textarea>
+          </div>
+          <button id="daily-log-submit" class="bg-orange-600 disabled:bg-orange-300 text-white p-2 rounded-md hover:bg-orange-700">
+            Save
+          </button>
+          <div class="w-full">
+            <FormError id="daily-log-date-error".to_string() />
+            <FormError id="daily-log-crew-error".to_string() />
+          </div>
+        </form>
+    }
+}
@@ -0,0 +1,141 @@
+use chrono::NaiveDate;
+use leptos::*;
+use models::projections::{
+    attachment::Attachment,
+    daily_log::{DailyLog, DailyLogDelay, DelayCause},
+};
+use uuid::Uuid;
+
+use crate::{
+    routes::ApiRoutes,
+    views::{
+        components::attachment::{daily_log_attachment_error_id, AttachmentList, AttachmentUpload},
+        format_minutes, FormError,
+    },
+};
+
+/// A jobsite's logs by date, most recent first
+#[component]
+pub fn DailyLogList(
+    jobsite_id: Uuid,
+    logs: Vec<DailyLog>,
+    delays: Vec<DailyLogDelay>,
+    attachments: Vec<Attachment>,
+) -> impl IntoView {
+    view! {
+        <div id=format!("daily_log_list_{}", jobsite_id)>
+            {logs.into_iter().map(|daily_log| {
+                let log_delays = delays
+                    .iter()
+                    .filter(|delay| delay.log_date == daily_log.log_date)
+                    .cloned()
+                    .collect::<Vec<_>>();
+                let log_attachments = attachments
+                    .iter()
+                    .filter(|attachment| attachment.log_date == Some(daily_log.log_date))
+                    .cloned()
+                    .collect::<Vec<_>>();
+
+                view! {
+                    <DailyLogRow daily_log=daily_log delays=log_delays attachments=log_attachments />
+                }
+            }).collect::<Vec<_>>().into_view()}
+        </div>
+    }
+}
+
+#[component]
+pub fn DailyLogRow(
+    daily_log: DailyLog,
+    delays: Vec<DailyLogDelay>,
+    attachments: Vec<Attachment>,
+) -> impl IntoView {
+    let jobsite_id = daily_log.jobsite_id;
+    let log_date = daily_log.log_date;
+    let locked = daily_log.is_locked();
+    let minutes_lost = delays.iter().map(|delay| delay.minutes_lost).sum::<i64>();
+
+    view! {
+        <div
+            id=format!("daily_log_{}_{}", jobsite_id, log_date)
+            class="p-2 my-2 bg-gray-400 rounded-md"
+        >
+            <div class="flex items-center justify-between">
+                <span class="text-lg">{log_date.format("%a %b %-d, %Y").to_string()}</span>
+                {match daily_log.submitted_at {
+                    Some(submitted_at) => view! {
+                        <span class="text-sm text-gray-700">
+                            {format!("Submitted {}", submitted_at.format("%b %-d, %Y"))}
+                        </span>
+                    }.into_view(),
+                    None => view! {
+                        <button
+                            hx-post=ApiRoutes::post_daily_log_submit(jobsite_id, log_date)
+                            hx-swap="none"
+                            hx-confirm="Submit this day's log? It can't be changed afterwards."
+                            class="bg-orange-600 text-white p-1 rounded-md hover:bg-orange-700 text-sm"
+                        >
+                            Submit
+                        </button>
+                    }.into_view(),
+                }}
+            </div>
+            <div class="flex flex-row gap-4 text-sm">
+                <span>{format!("Crew {}", daily_log.crew_count)}</span>
+                <span>{format!("Weather {}", daily_log.weather.as_str())}</span>
+                {(minutes_lost > 0).then(|| view! {
+                    <span class="text-red-700">{format!("Delayed {}", format_minutes(minutes_lost))}</span>
+                })}
+            </div>
+            {daily_log.notes.map(|notes| view! { <p class="text-sm whitespace-pre-line">{notes}</p> })}
+            <ul class="text-sm list-disc pl-5">
+                {delays.into_iter().map(|delay| view! {
+                    <li>
+                        {format!(
+                            "{}, {}: {}",
+                            delay.cause.as_str(),
+                            format_minutes(delay.minutes_lost),
+                            delay.reason
+                        )}
+                    </li>
+                }).collect::<Vec<_>>().into_view()}
+            </ul>
+            <AttachmentList attachments=attachments locked=locked />
+            {(!locked).then(|| view! {
+                <DailyLogDelayCreate jobsite_id=jobsite_id log_date=log_date />
+                <AttachmentUpload
+                    action=ApiRoutes::post_daily_log_attachment(jobsite_id, log_date)
+                    error_id=daily_log_attachment_error_id(&log_date)
+                />
+            })}
+        </div>
+    }
+}
+
+#[component]
+pub fn DailyLogDelayCreate(jobsite_id: Uuid, log_date: NaiveDate) -> impl IntoView {
+    view! {
+        <form
+          hx-post=ApiRoutes::post_daily_log_delay(jobsite_id, log_date)
+          hx-swap="none"
+          class="mt-1 flex flex-row items-center gap-2 text-sm"
+        >
+          <select name="cause" class="p-1 border rounded-md text-black">
+            {DelayCause::ALL.into_iter().map(|cause| view! {
+                <option value=cause.as_str()>{cause.as_str()}</option>
+            }).collect::<Vec<_>>().into_view()}
+          </select>
+          <input
+              type="number"
+              name="hours"
+              step="0.25"
+              min="0"
+              placeholder="Hours"
+              class="p-1 w-20 border rounded-md text-black"
+          />
+          <input name="reason" placeholder="Reason" class="p-1 flex-grow border rounded-md text-black" />
+          <button class="text-orange-900 hover:text-orange-700">Add Delay</button>
+        </form>
+        <FormError id=format!("daily_log_delay_error_{}", log_date) />
+    }
+}
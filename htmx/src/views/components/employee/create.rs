@@ -0,0 +1,37 @@
+use leptos::*;
+
+use crate::{routes::ApiRoutes, views::FormError};
+
+#[component]
+pub fn EmployeeCreate() -> impl IntoView {
+    view! {
+        <form
+          hx-post=ApiRoutes::post_employee()
+          hx-swap="none"
+          hx-disabled-elt="#employee-submit"
+          hx-on:htmx:after-request="if (event.detail.successful) this.reset()"
+          class="w-full"
+        >
+          <div class="mb-4">
+            <label class="block text-sm font-medium text-white">Name</label>
+            <input
+              name="name"
+              class="mt-1 p-2 w-full border rounded-md text-black"
+              autofocus
+            />
+            <FormError id="employee-name-error".to_string() />
+          </div>
+          <div class="mb-4">
+            <label class="block text-sm font-medium text-white">Trade</label>
+            <input
+              name="trade"
+              placeholder="Optional, e.g. Carpenter"
+              class="mt-1 p-2 w-full border rounded-md text-black"
+            />
+          </div>
+          <button id="employee-submit" class="w-full bg-orange-600 disabled:bg-orange-300 text-white p-2 rounded-md hover:bg-orange-700">
+            Add Employee
+          </button>
+        </form>
+    }
+}
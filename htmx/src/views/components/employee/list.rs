@@ -0,0 +1,31 @@
+use chrono::NaiveDate;
+use leptos::*;
+use models::projections::employee::Employee;
+use uuid::Uuid;
+
+use crate::views::components::employee::EmployeeRow;
+
+#[component]
+pub fn EmployeeList(
+    employees: Vec<Employee>,
+    today: NaiveDate,
+    #[prop(optional)] append: Option<Uuid>,
+) -> impl IntoView {
+    let hx_swap_oob = if append.is_some() { "afterbegin" } else { "" };
+    let data_append = if let Some(append) = append {
+        format!("employee_row_{}", append)
+    } else {
+        "".to_string()
+    };
+
+    view! {
+        <div
+            class="w-11/12 mx-auto rounded-md p-4"
+            id="employee-list"
+            hx-swap-oob=hx_swap_oob
+            data-append=data_append
+        >
+            {employees.into_iter().map(|employee| view! { <EmployeeRow employee=employee today=today /> }).collect::<Vec<_>>().into_view()}
+        </div>
+    }
+}
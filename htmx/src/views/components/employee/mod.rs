@@ -0,0 +1,7 @@
+mod create;
+mod list;
+mod row;
+
+pub use create::*;
+pub use list::*;
+pub use row::*;
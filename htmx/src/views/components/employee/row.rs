@@ -0,0 +1,66 @@
+use chrono::NaiveDate;
+use leptos::*;
+use models::projections::{employee::Employee, timesheet::Timesheet};
+
+use crate::{routes::ApiRoutes, views::FormError};
+
+#[component]
+pub fn EmployeeRow(employee: Employee, today: NaiveDate) -> impl IntoView {
+    let employee_id = employee.id;
+
+    view! {
+        <div
+            class="employee-row p-2 my-2 bg-gray-400 rounded-md"
+            id=format!("employee_row_{}", employee.id)
+        >
+            <div class="flex items-center justify-between">
+                <div class="flex flex-col">
+                    <span class="text-lg">{employee.name}</span>
+                    {employee.trade.map(|trade| view! { <span class="text-sm text-gray-700">{trade}</span> })}
+                </div>
+                <a
+                    class="text-sm hover:text-orange-700"
+                    href=ApiRoutes::get_timesheet_page(employee.id, Timesheet::week_start_of(today))
+                >
+                    Timesheet
+                </a>
+            </div>
+            {match employee.terminated_on {
+                Some(terminated_on) => view! {
+                    <span class="text-sm text-red-700">
+                        {format!("Terminated {}", terminated_on.format("%b %-d, %Y"))}
+                    </span>
+                }.into_view(),
+                None => view! {
+                    <form
+                      hx-post=ApiRoutes::post_employee_terminate(employee_id)
+                      hx-swap="none"
+                      hx-confirm="Terminate this employee? They won't be able to be scheduled or clock in from this date."
+                      class="mt-1 flex flex-row items-center gap-2 text-sm"
+                    >
+                      <input type="date" name="terminated_on" class="p-1 border rounded-md text-black" />
+                      <button class="text-red-700 hover:text-red-900">Terminate</button>
+                    </form>
+                    <FormError id=format!("employee_terminate_error_{}", employee_id) />
+                    <form
+                      hx-post=ApiRoutes::post_employee_pin(employee_id)
+                      hx-swap="none"
+                      hx-on:htmx:after-request="if (event.detail.successful) this.reset()"
+                      class="mt-1 flex flex-row items-center gap-2 text-sm"
+                    >
+                      <input
+                          type="password"
+                          name="pin"
+                          inputmode="numeric"
+                          autocomplete="off"
+                          placeholder="Kiosk PIN"
+                          class="p-1 w-24 border rounded-md text-black"
+                      />
+                      <button class="hover:text-orange-700">Set PIN</button>
+                    </form>
+                    <FormError id=format!("employee_pin_error_{}", employee_id) />
+                }.into_view(),
+            }}
+        </div>
+    }
+}
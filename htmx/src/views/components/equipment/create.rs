@@ -0,0 +1,32 @@
+use leptos::*;
+
+use crate::{routes::ApiRoutes, views::FormError};
+
+/// Form for adding a piece of equipment to the catalog, it starts in the yard
+#[component]
+pub fn EquipmentCreate() -> impl IntoView {
+    view! {
+        <form
+          hx-post=ApiRoutes::post_equipment()
+          hx-swap="none"
+          hx-disabled-elt="#equipment-submit"
+          hx-on:htmx:after-request="if (event.detail.successful) this.reset()"
+          class="w-full flex flex-row flex-wrap items-end gap-2"
+        >
+          <div class="flex-grow">
+            <label class="block text-sm font-medium text-white">Name</label>
+            <input name="name" placeholder="Mini excavator" class="mt-1 p-2 w-full border rounded-md text-black" />
+          </div>
+          <div>
+            <label class="block text-sm font-medium text-white">Serial Number</label>
+            <input name="serial_number" class="mt-1 p-2 border rounded-md text-black" />
+          </div>
+          <button id="equipment-submit" class="bg-orange-600 disabled:bg-orange-300 text-white p-2 rounded-md hover:bg-orange-700">
+            Add
+          </button>
+          <div class="w-full">
+            <FormError id="equipment-name-error".to_string() />
+          </div>
+        </form>
+    }
+}
@@ -0,0 +1,136 @@
+use leptos::*;
+use models::projections::{employee::Employee, equipment::Equipment, jobsite::Jobsite};
+use uuid::Uuid;
+
+use crate::{routes::ApiRoutes, views::FormError};
+
+/// The element id of the errors shown under a piece of equipment's checkout
+pub fn equipment_action_error_id(equipment_id: &Uuid) -> String {
+    format!("equipment_action_error_{}", equipment_id)
+}
+
+/// A row per piece of equipment saying where it is, with a checkout form for
+/// pieces in the yard and a return button for the rest
+#[component]
+pub fn EquipmentList(
+    equipment: Vec<Equipment>,
+    /// Jobsites equipment in the yard can be checked out to
+    jobsites: Vec<Jobsite>,
+    employees: Vec<Employee>,
+) -> impl IntoView {
+    view! {
+        <div class="w-11/12 mx-auto rounded-md p-4" id="equipment-list">
+            {equipment.into_iter().map(|equipment| view! {
+                <EquipmentRow
+                    equipment=equipment
+                    jobsites=jobsites.clone()
+                    employees=employees.clone()
+                />
+            }).collect::<Vec<_>>().into_view()}
+        </div>
+    }
+}
+
+#[component]
+pub fn EquipmentRow(
+    equipment: Equipment,
+    jobsites: Vec<Jobsite>,
+    employees: Vec<Employee>,
+) -> impl IntoView {
+    let equipment_id = equipment.id;
+    let location = match (&equipment.jobsite_name, &equipment.employee_name) {
+        (Some(jobsite_name), Some(employee_name)) => {
+            format!("At {} with {}", jobsite_name, employee_name)
+        }
+        (Some(jobsite_name), None) => format!("At {}", jobsite_name),
+        _ => String::from("In the yard"),
+    };
+
+    view! {
+        <div id=format!("equipment_row_{}", equipment_id) class="p-2 my-2 bg-gray-400 rounded-md">
+            <div class="flex items-center justify-between">
+                <span class="text-lg">{equipment.name}</span>
+                <span class="text-sm">{location}</span>
+            </div>
+            {equipment.serial_number.map(|serial_number| view! {
+                <span class="text-sm text-gray-700">{format!("S/N {}", serial_number)}</span>
+            })}
+            {if let Some(checked_out_at) = equipment.checked_out_at {
+                view! {
+                    <div class="mt-1 flex flex-row items-center justify-between text-sm">
+                        <span class="text-gray-700">
+                            {format!("Out since {}", checked_out_at.format("%b %-d, %Y"))}
+                        </span>
+                        <button
+                            hx-post=ApiRoutes::post_equipment_return(equipment_id)
+                            hx-swap="none"
+                            class="bg-orange-600 text-white p-1 rounded-md hover:bg-orange-700"
+                        >
+                            Return
+                        </button>
+                    </div>
+                }.into_view()
+            } else {
+                view! {
+                    <form
+                      hx-post=ApiRoutes::post_equipment_check_out(equipment_id)
+                      hx-swap="none"
+                      class="mt-1 flex flex-row items-center gap-2 text-sm"
+                    >
+                      <select name="jobsite_id" class="p-1 flex-grow border rounded-md text-black">
+                        {jobsites.into_iter().map(|jobsite| view! {
+                            <option value=jobsite.id.to_string()>{jobsite.name}</option>
+                        }).collect::<Vec<_>>().into_view()}
+                      </select>
+                      <select name="employee_id" class="p-1 flex-grow border rounded-md text-black">
+                        <option value="">Nobody in particular</option>
+                        {employees.into_iter().map(|employee| view! {
+                            <option value=employee.id.to_string()>{employee.name}</option>
+                        }).collect::<Vec<_>>().into_view()}
+                      </select>
+                      <button class="bg-orange-600 text-white p-1 rounded-md hover:bg-orange-700">
+                        Check Out
+                      </button>
+                    </form>
+                }.into_view()
+            }}
+            <FormError id=equipment_action_error_id(&equipment_id) />
+        </div>
+    }
+}
+
+/// The equipment currently out at a jobsite
+#[component]
+pub fn JobsiteEquipment(jobsite_id: Uuid, equipment: Vec<Equipment>) -> impl IntoView {
+    view! {
+        <div id=format!("jobsite_equipment_{}", jobsite_id) class="mt-6 w-full">
+            <div class="text-center mb-2">
+                <span class="text-orange-700 text-xl font-bold" style="font-family: 'Roboto Slab', serif;">Equipment</span>
+            </div>
+            {if equipment.is_empty() {
+                view! {
+                    <div class="text-center text-white italic">No equipment on site</div>
+                }.into_view()
+            } else {
+                equipment.into_iter().map(|equipment| view! {
+                    <div class="flex items-center justify-between p-2 my-2 bg-gray-400 rounded-md">
+                        <span class="text-lg">{equipment.name}</span>
+                        <span class="text-sm text-gray-700">
+                            {match (equipment.employee_name, equipment.checked_out_at) {
+                                (Some(employee_name), Some(checked_out_at)) => format!(
+                                    "{} since {}",
+                                    employee_name,
+                                    checked_out_at.format("%b %-d")
+                                ),
+                                (None, Some(checked_out_at)) => {
+                                    format!("Since {}", checked_out_at.format("%b %-d"))
+                                }
+                                _ => String::new(),
+                            }}
+                        </span>
+                    </div>
+                }).collect::<Vec<_>>().into_view()
+            }}
+        </div>
+    }
+}
@@ -0,0 +1,41 @@
+use chrono::NaiveDate;
+use leptos::*;
+use models::projections::incident::JobsiteSafety;
+
+/// Days since the last incident on every jobsite
+#[component]
+pub fn SafetyBoard(safety: Vec<JobsiteSafety>, today: NaiveDate) -> impl IntoView {
+    view! {
+        <div id="safety-board" class="w-11/12 mx-auto rounded-md p-4">
+            {safety.into_iter().map(|jobsite| {
+                let days_since_incident = jobsite.days_since_incident(today);
+
+                view! {
+                    <div class="flex items-center justify-between p-2 my-2 bg-gray-400 rounded-md">
+                        <div class="flex flex-col">
+                            <span class="text-lg">{jobsite.jobsite_name}</span>
+                            {(jobsite.open_incidents > 0).then(|| view! {
+                                <span class="text-sm text-red-700">
+                                    {format!("{} open", jobsite.open_incidents)}
+                                </span>
+                            })}
+                        </div>
+                        {match days_since_incident {
+                            Some(days) => view! {
+                                <span class="flex flex-col items-end">
+                                    <span class="text-2xl font-bold">{days}</span>
+                                    <span class="text-sm text-gray-700">
+                                        {if days == 1 { "day since an incident" } else { "days since an incident" }}
+                                    </span>
+                                </span>
+                            }.into_view(),
+                            None => view! {
+                                <span class="text-sm text-gray-700">No incidents</span>
+                            }.into_view(),
+                        }}
+                    </div>
+                }
+            }).collect::<Vec<_>>().into_view()}
+        </div>
+    }
+}
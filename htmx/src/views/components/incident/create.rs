@@ -0,0 +1,72 @@
+use chrono::NaiveDate;
+use leptos::*;
+use models::projections::{employee::Employee, incident::IncidentSeverity, jobsite::Jobsite};
+
+use crate::{routes::ApiRoutes, views::FormError};
+
+#[component]
+pub fn IncidentCreate(
+    jobsites: Vec<Jobsite>,
+    employees: Vec<Employee>,
+    today: NaiveDate,
+) -> impl IntoView {
+    view! {
+        <form
+          hx-post=ApiRoutes::post_incident()
+          hx-swap="none"
+          hx-disabled-elt="#incident-submit"
+          hx-on:htmx:after-request="if (event.detail.successful) this.reset()"
+          class="w-full flex flex-row flex-wrap items-end gap-2"
+        >
+          <div class="flex-grow">
+            <label class="block text-sm font-medium text-white">Jobsite</label>
+            <select name="jobsite_id" class="mt-1 p-2 w-full border rounded-md text-black">
+              {jobsites.into_iter().map(|jobsite| view! {
+                  <option value=jobsite.id.to_string()>{jobsite.name}</option>
+              }).collect::<Vec<_>>().into_view()}
+            </select>
+          </div>
+          <div>
+            <label class="block text-sm font-medium text-white">Severity</label>
+            <select name="severity" class="mt-1 p-2 border rounded-md text-black">
+              {IncidentSeverity::ALL.into_iter().map(|severity| view! {
+                  <option value=severity.as_str()>{severity.label()}</option>
+              }).collect::<Vec<_>>().into_view()}
+            </select>
+          </div>
+          <div>
+            <label class="block text-sm font-medium text-white">Date</label>
+            <input
+                type="date"
+                name="occurred_on"
+                value=today.to_string()
+                max=today.to_string()
+                class="mt-1 p-2 border rounded-md text-black"
+            />
+          </div>
+          <div class="w-full">
+            <label class="block text-sm font-medium text-white">What happened</label>
+            <textarea name="description" rows="2" class="mt-1 p-2 w-full border rounded-md text-black"></textarea>
+          </div>
+          <fieldset class="w-full">
+            <legend class="block text-sm font-medium text-white">Employees involved</legend>
+            <div class="mt-1 p-2 max-h-24 overflow-auto flex flex-row flex-wrap gap-x-4 bg-white rounded-md text-black text-sm">
+              {employees.into_iter().map(|employee| view! {
+                  <label class="flex items-center gap-1">
+                      <input type="checkbox" name="employee_id" value=employee.id.to_string() />
+                      {employee.name}
+                  </label>
+              }).collect::<Vec<_>>().into_view()}
+            </div>
+          </fieldset>
+          <button id="incident-submit" class="bg-orange-600 disabled:bg-orange-300 text-white p-2 rounded-md hover:bg-orange-700">
+            Report
+          </button>
+          <div class="w-full">
+            <FormError id="incident-jobsite-error".to_string() />
+            <FormError id="incident-date-error".to_string() />
+            <FormError id="incident-description-error".to_string() />
+          </div>
+        </form>
+    }
+}
@@ -0,0 +1,152 @@
+use leptos::*;
+use models::projections::{
+    employee::Employee,
+    incident::{Incident, IncidentEmployee, IncidentStatus},
+};
+use uuid::Uuid;
+
+use crate::{routes::ApiRoutes, views::FormError};
+
+/// The element id of the errors shown under an incident's next step
+pub fn incident_action_error_id(incident_id: &Uuid) -> String {
+    format!("incident_action_error_{}", incident_id)
+}
+
+#[component]
+pub fn IncidentList(
+    incidents: Vec<Incident>,
+    /// Everyone involved in any of `incidents`
+    involved: Vec<IncidentEmployee>,
+    /// Employees a corrective action can be assigned to
+    employees: Vec<Employee>,
+    #[prop(optional)] append: Option<Uuid>,
+) -> impl IntoView {
+    let hx_swap_oob = if append.is_some() { "afterbegin" } else { "" };
+    let data_append = if let Some(append) = append {
+        format!("incident_row_{}", append)
+    } else {
+        "".to_string()
+    };
+
+    view! {
+        <div
+            class="w-11/12 mx-auto rounded-md p-4"
+            id="incident-list"
+            hx-swap-oob=hx_swap_oob
+            data-append=data_append
+        >
+            {incidents.into_iter().map(|incident| {
+                let incident_involved = involved
+                    .iter()
+                    .filter(|employee| employee.incident_id == incident.id)
+                    .cloned()
+                    .collect::<Vec<_>>();
+
+                view! {
+                    <IncidentRow
+                        incident=incident
+                        involved=incident_involved
+                        employees=employees.clone()
+                    />
+                }
+            }).collect::<Vec<_>>().into_view()}
+        </div>
+    }
+}
+
+#[component]
+pub fn IncidentRow(
+    incident: Incident,
+    involved: Vec<IncidentEmployee>,
+    employees: Vec<Employee>,
+) -> impl IntoView {
+    let incident_id = incident.id;
+    let involved = involved
+        .into_iter()
+        .map(|employee| employee.employee_name)
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    view! {
+        <div
+            id=format!("incident_row_{}", incident_id)
+            class="p-2 my-2 bg-gray-400 rounded-md"
+        >
+            <div class="flex items-center justify-between">
+                <span class="text-lg">
+                    {format!("{}, {}", incident.jobsite_name, incident.occurred_on.format("%b %-d, %Y"))}
+                </span>
+                <span class="text-sm">
+                    {format!("{} · {}", incident.severity.label(), incident.status.label())}
+                </span>
+            </div>
+            <p class="text-sm whitespace-pre-line">{incident.description}</p>
+            {(!involved.is_empty()).then(|| view! {
+                <p class="text-sm text-gray-700">{format!("Involved: {}", involved)}</p>
+            })}
+            {incident.findings.map(|findings| view! {
+                <p class="text-sm whitespace-pre-line">{format!("Findings: {}", findings)}</p>
+            })}
+            {incident.corrective_action.map(|action| view! {
+                <p class="text-sm whitespace-pre-line">
+                    {format!(
+                        "Corrective action: {} ({}{})",
+                        action,
+                        incident.action_assignee_name.unwrap_or_default(),
+                        incident
+                            .action_due_on
+                            .map(|due_on| format!(", due {}", due_on.format("%b %-d")))
+                            .unwrap_or_default()
+                    )}
+                </p>
+            })}
+            {match incident.status {
+                IncidentStatus::Reported => view! {
+                    <form
+                      hx-post=ApiRoutes::post_incident_investigate(incident_id)
+                      hx-swap="none"
+                      class="mt-1 flex flex-row items-center gap-2 text-sm"
+                    >
+                      <input name="findings" placeholder="Findings" class="p-1 flex-grow border rounded-md text-black" />
+                      <button class="text-orange-900 hover:text-orange-700">Record Investigation</button>
+                    </form>
+                }.into_view(),
+                IncidentStatus::Investigated => view! {
+                    <form
+                      hx-post=ApiRoutes::post_incident_corrective_action(incident_id)
+                      hx-swap="none"
+                      class="mt-1 flex flex-row items-center gap-2 text-sm"
+                    >
+                      <input name="action" placeholder="Corrective action" class="p-1 flex-grow border rounded-md text-black" />
+                      <select name="assignee_id" class="p-1 border rounded-md text-black">
+                        {employees.into_iter().map(|employee| view! {
+                            <option value=employee.id.to_string()>{employee.name}</option>
+                        }).collect::<Vec<_>>().into_view()}
+                      </select>
+                      <input type="date" name="due_on" class="p-1 border rounded-md text-black" />
+                      <button class="text-orange-900 hover:text-orange-700">Assign</button>
+                    </form>
+                }.into_view(),
+                IncidentStatus::ActionAssigned => view! {
+                    <button
+                        hx-post=ApiRoutes::post_incident_close(incident_id)
+                        hx-swap="none"
+                        hx-confirm="Close this incident? The corrective action should be done."
+                        class="mt-1 bg-orange-600 text-white p-1 rounded-md hover:bg-orange-700 text-sm"
+                    >
+                        Close
+                    </button>
+                }.into_view(),
+                IncidentStatus::Closed => view! {
+                    <span class="text-sm text-gray-700">
+                        {incident
+                            .closed_at
+                            .map(|closed_at| format!("Closed {}", closed_at.format("%b %-d, %Y")))
+                            .unwrap_or_default()}
+                    </span>
+                }.into_view(),
+            }}
+            <FormError id=incident_action_error_id(&incident_id) />
+        </div>
+    }
+}
@@ -0,0 +1,7 @@
+mod board;
+mod create;
+mod list;
+
+pub use board::*;
+pub use create::*;
+pub use list::*;
@@ -0,0 +1,154 @@
+use leptos::*;
+use models::projections::{
+    cost_code::CostCode,
+    jobsite_budget::{BudgetMeasure, JobsiteBudget, JobsiteBudgetAlert},
+};
+use uuid::Uuid;
+
+use crate::{
+    routes::ApiRoutes,
+    views::{format_cents, format_minutes, format_signed_cents, format_signed_minutes, FormError},
+};
+
+#[component]
+pub fn JobsiteBudgetPanel(
+    jobsite_id: Uuid,
+    budgets: Vec<JobsiteBudget>,
+    alerts: Vec<JobsiteBudgetAlert>,
+) -> impl IntoView {
+    view! {
+        <div id=format!("jobsite_budget_{}", jobsite_id) class="mt-6 w-full">
+            <div class="text-center mb-2">
+                <span class="text-orange-700 text-xl font-bold" style="font-family: 'Roboto Slab', serif;">Budget</span>
+            </div>
+            {alerts.into_iter().map(|alert| view! { <JobsiteBudgetAlertRow alert=alert /> }).collect::<Vec<_>>().into_view()}
+            {if budgets.is_empty() {
+                view! {
+                    <div class="text-center text-white italic">No budget set</div>
+                }.into_view()
+            } else {
+                budgets.into_iter().map(|budget| view! { <JobsiteBudgetRow budget=budget /> }).collect::<Vec<_>>().into_view()
+            }}
+        </div>
+    }
+}
+
+#[component]
+pub fn JobsiteBudgetRow(budget: JobsiteBudget) -> impl IntoView {
+    let hours_used = budget.percent_used(BudgetMeasure::Hours);
+    let cost_used = budget.percent_used(BudgetMeasure::Cost);
+
+    view! {
+        <div class="p-2 my-2 bg-gray-400 rounded-md">
+            <span class="text-lg">{budget.cost_code.clone().unwrap_or_else(|| "Whole jobsite".to_string())}</span>
+            {budget.revised_minutes().map(|budget_minutes| view! {
+                <BudgetUsage
+                    label=format!("{} of {}", format_minutes(budget.actual_minutes), format_minutes(budget_minutes))
+                    percent_used=hours_used.unwrap_or_default()
+                />
+            })}
+            {budget.revised_cents().map(|budget_cents| view! {
+                <BudgetUsage
+                    label=format!("{} of {}", format_cents(budget.actual_cents), format_cents(budget_cents))
+                    percent_used=cost_used.unwrap_or_default()
+                />
+            })}
+            {(budget.change_order_minutes != 0 || budget.change_order_cents != 0).then(|| view! {
+                <span class="text-sm text-gray-700 italic">
+                    {format!(
+                        "Includes change orders of {} and {}",
+                        format_signed_minutes(budget.change_order_minutes),
+                        format_signed_cents(budget.change_order_cents)
+                    )}
+                </span>
+            })}
+        </div>
+    }
+}
+
+#[component]
+fn BudgetUsage(label: String, percent_used: i64) -> impl IntoView {
+    let bar_class = if percent_used >= 100 {
+        "h-2 rounded-md bg-red-600"
+    } else if percent_used >= 80 {
+        "h-2 rounded-md bg-orange-500"
+    } else {
+        "h-2 rounded-md bg-green-600"
+    };
+
+    view! {
+        <div class="mt-1">
+            <div class="flex items-center justify-between text-sm text-gray-700">
+                <span>{label}</span>
+                <span>{format!("{}%", percent_used)}</span>
+            </div>
+            <div class="h-2 w-full rounded-md bg-gray-200">
+                <div class=bar_class style=format!("width: {}%", percent_used.min(100))></div>
+            </div>
+        </div>
+    }
+}
+
+#[component]
+pub fn JobsiteBudgetAlertRow(alert: JobsiteBudgetAlert) -> impl IntoView {
+    let class = if alert.threshold >= 100 {
+        "p-2 my-2 bg-red-100 text-red-700 rounded-md"
+    } else {
+        "p-2 my-2 bg-orange-100 text-orange-700 rounded-md"
+    };
+
+    view! {
+        <div class=class role="alert">
+            {format!(
+                "{} {} budget reached {}% ({}% used) on {}",
+                alert.cost_code.unwrap_or_else(|| "Jobsite".to_string()),
+                alert.measure.as_str(),
+                alert.threshold,
+                alert.percent_used,
+                alert.reached_at.format("%b %-d")
+            )}
+        </div>
+    }
+}
+
+#[component]
+pub fn JobsiteBudgetCreate(jobsite_id: Uuid, cost_codes: Vec<CostCode>) -> impl IntoView {
+    let submit_id = format!("jobsite-budget-submit-{}", jobsite_id);
+    let codes_list_id = format!("jobsite-budget-codes-{}", jobsite_id);
+
+    view! {
+        <form
+          hx-post=ApiRoutes::post_jobsite_budget(jobsite_id)
+          hx-swap="none"
+          hx-disabled-elt=format!("#{}", submit_id)
+          hx-on:htmx:after-request="if (event.detail.successful) this.reset()"
+          class="mt-2 w-full"
+        >
+          <div class="mb-4">
+            <label class="block text-sm font-medium text-white">Cost Code</label>
+            <input name="cost_code" list=codes_list_id.clone() placeholder="Whole jobsite" class="mt-1 p-2 w-full border rounded-md text-black" />
+            <datalist id=codes_list_id>
+              {cost_codes.into_iter().map(|cost_code| view! {
+                  <option value=cost_code.code>{cost_code.name}</option>
+              }).collect::<Vec<_>>().into_view()}
+            </datalist>
+            <FormError id=format!("jobsite_budget_code_error_{}", jobsite_id) />
+          </div>
+          <div class="mb-4 flex flex-row gap-2">
+            <div class="w-1/2">
+              <label class="block text-sm font-medium text-white">Hours</label>
+              <input name="hours" placeholder="120" class="mt-1 p-2 w-full border rounded-md text-black" />
+              <FormError id=format!("jobsite_budget_hours_error_{}", jobsite_id) />
+            </div>
+            <div class="w-1/2">
+              <label class="block text-sm font-medium text-white">Cost ($)</label>
+              <input name="cost" placeholder="25000" class="mt-1 p-2 w-full border rounded-md text-black" />
+              <FormError id=format!("jobsite_budget_cost_error_{}", jobsite_id) />
+            </div>
+          </div>
+          <button id=submit_id class="w-full bg-orange-600 disabled:bg-orange-300 text-white p-2 rounded-md hover:bg-orange-700">
+            Set Budget
+          </button>
+        </form>
+    }
+}
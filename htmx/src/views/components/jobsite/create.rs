@@ -1,9 +1,13 @@
 use leptos::*;
+use models::projections::project::Project;
 
-use crate::{routes::ApiRoutes, views::FormError};
+use crate::{
+    routes::ApiRoutes,
+    views::{components::jobsite::JobsiteTimeZoneInput, FormError},
+};
 
 #[component]
-pub fn JobsiteCreate() -> impl IntoView {
+pub fn JobsiteCreate(projects: Vec<Project>, time_zone: String) -> impl IntoView {
     view! {
         <form
           hx-post=ApiRoutes::post_jobsite()
@@ -20,6 +24,17 @@ pub fn JobsiteCreate() -> impl IntoView {
             />
             <FormError id="name-error".to_string() />
           </div>
+          <div class="mb-4">
+            <label class="block text-sm font-medium text-white">Project</label>
+            <select name="project_id" class="mt-1 p-2 w-full border rounded-md text-black">
+              <option value="">No project</option>
+              {projects.into_iter().map(|project| view! {
+                  <option value=project.id.to_string()>{format!("{} / {}", project.client_name, project.name)}</option>
+              }).collect::<Vec<_>>().into_view()}
+            </select>
+            <FormError id="project-error".to_string() />
+          </div>
+          <JobsiteTimeZoneInput time_zone list_id="jobsite-create-time-zones".to_string() />
           <button id="jobsite-submit" class="w-full bg-orange-600 disabled:bg-orange-300 text-white p-2 rounded-md hover:bg-orange-700">
             Submit
           </button>
@@ -1,7 +1,10 @@
 use leptos::*;
 use models::projections::jobsite::Jobsite;
 
-use crate::{routes::ApiRoutes, views::FormError};
+use crate::{
+    routes::{ApiRoutes, JobsiteClientMessage},
+    views::{components::jobsite::JobsiteTimeZoneInput, FormError},
+};
 
 #[component]
 pub fn JobsiteEdit(jobsite: Option<Jobsite>) -> impl IntoView {
@@ -14,6 +17,11 @@ pub fn JobsiteEdit(jobsite: Option<Jobsite>) -> impl IntoView {
             </div>
         },
         Some(jobsite) => {
+            let ws_vals = serde_json::to_string(&JobsiteClientMessage::WatchJobsite {
+                jobsite_id: jobsite.id,
+            })
+            .unwrap();
+
             view! {
                 <div id=format!("jobsite_edit_{}", jobsite.id) class="h-full w-full mx-4">
                     <div class="text-center mb-4">
@@ -35,10 +43,118 @@ pub fn JobsiteEdit(jobsite: Option<Jobsite>) -> impl IntoView {
                         />
                         <FormError id="name-error".to_string() />
                       </div>
+                      <JobsiteTimeZoneInput
+                          time_zone=jobsite.time_zone
+                          list_id="jobsite-edit-time-zones".to_string()
+                      />
                       <button id="jobsite-edit-submit" class="w-full bg-orange-600 disabled:bg-orange-300 text-white p-2 rounded-md hover:bg-orange-700">
                         Update
                       </button>
                     </form>
+                    <a
+                        class="block mt-2 text-center text-white hover:text-orange-500"
+                        href=ApiRoutes::get_daily_logs_page(jobsite.id)
+                    >
+                        Daily Logs
+                    </a>
+                    <div ws-send hx-vals=ws_vals hx-trigger="load"></div>
+                    <div
+                        hx-get=ApiRoutes::get_jobsite_project(jobsite.id)
+                        hx-trigger="load"
+                        hx-swap="outerHTML"
+                    ></div>
+                    <div
+                        hx-get=ApiRoutes::get_jobsite_certifications(jobsite.id)
+                        hx-trigger="load"
+                        hx-swap="outerHTML"
+                    ></div>
+                    <div
+                        hx-get=ApiRoutes::get_jobsite_geofence(jobsite.id)
+                        hx-trigger="load"
+                        hx-swap="outerHTML"
+                    ></div>
+                    <div
+                        hx-get=ApiRoutes::get_jobsite_kiosks(jobsite.id)
+                        hx-trigger="load"
+                        hx-swap="outerHTML"
+                    ></div>
+                    <div
+                        hx-get=ApiRoutes::get_jobsite_qr_code(jobsite.id)
+                        hx-trigger="load"
+                        hx-swap="outerHTML"
+                    ></div>
+                    <div
+                        hx-get=ApiRoutes::get_jobsite_on_site(jobsite.id)
+                        hx-trigger="load"
+                        hx-swap="outerHTML"
+                    ></div>
+                    <div
+                        hx-get=ApiRoutes::get_jobsite_labor_cost(jobsite.id)
+                        hx-trigger="load"
+                        hx-swap="outerHTML"
+                    ></div>
+                    <div
+                        hx-get=ApiRoutes::get_jobsite_budget(jobsite.id)
+                        hx-trigger="load"
+                        hx-swap="outerHTML"
+                    ></div>
+                    <div
+                        hx-get=ApiRoutes::get_jobsite_change_orders(jobsite.id)
+                        hx-trigger="load"
+                        hx-swap="outerHTML"
+                    ></div>
+                    <div
+                        hx-get=ApiRoutes::get_jobsite_comments(jobsite.id)
+                        hx-trigger="load"
+                        hx-swap="outerHTML"
+                    ></div>
+                    <div
+                        hx-get=ApiRoutes::get_jobsite_cost_codes(jobsite.id)
+                        hx-trigger="load"
+                        hx-swap="outerHTML"
+                    ></div>
+                    <div
+                        hx-get=ApiRoutes::get_jobsite_equipment(jobsite.id)
+                        hx-trigger="load"
+                        hx-swap="outerHTML"
+                    ></div>
+                    <div
+                        hx-get=ApiRoutes::get_jobsite_materials(jobsite.id)
+                        hx-trigger="load"
+                        hx-swap="outerHTML"
+                    ></div>
+                    <div
+                        hx-get=ApiRoutes::get_jobsite_attachments(jobsite.id)
+                        hx-trigger="load"
+                        hx-swap="outerHTML"
+                    ></div>
+                    <div
+                        hx-get=ApiRoutes::get_jobsite_calendar_link(jobsite.id)
+                        hx-trigger="load"
+                        hx-swap="outerHTML"
+                    ></div>
+                    <div
+                        hx-get=ApiRoutes::get_jobsite_merge(jobsite.id)
+                        hx-trigger="load"
+                        hx-swap="outerHTML"
+                    ></div>
+                    {match jobsite.archived_at {
+                        Some(archived_at) => view! {
+                            <p class="mt-4 text-center text-white italic">
+                                {format!("Archived on {}", archived_at.format("%b %-d, %Y"))}
+                            </p>
+                        }.into_view(),
+                        None => view! {
+                            <button
+                                hx-post=ApiRoutes::post_jobsite_archive(jobsite.id)
+                                hx-swap="none"
+                                hx-confirm="Archive this jobsite? Anyone still clocked in there is clocked out and upcoming crew assignments are cancelled."
+                                class="mt-4 w-full bg-gray-600 text-white p-2 rounded-md hover:bg-gray-700"
+                            >
+                                Archive Jobsite
+                            </button>
+                        }.into_view(),
+                    }}
                 </div>
             }
         }
@@ -0,0 +1,49 @@
+use leptos::*;
+use models::geofence::Geofence;
+use uuid::Uuid;
+
+use crate::{routes::ApiRoutes, views::FormError};
+
+/// The jobsite's location and the radius clock-ins are expected within,
+/// leaving the fields blank clears it
+#[component]
+pub fn JobsiteGeofence(jobsite_id: Uuid, geofence: Option<Geofence>) -> impl IntoView {
+    let submit_id = format!("jobsite-geofence-submit-{}", jobsite_id);
+    let field = |value: Option<String>| value.unwrap_or_default();
+
+    view! {
+        <form
+          id=format!("jobsite_geofence_{}", jobsite_id)
+          hx-post=ApiRoutes::post_jobsite_geofence(jobsite_id)
+          hx-swap="none"
+          hx-disabled-elt=format!("#{}", submit_id)
+          class="mt-4 w-full"
+        >
+          <label class="block text-sm font-medium text-white">Geofence</label>
+          <div class="mt-1 flex flex-row gap-2">
+            <input
+              name="latitude"
+              placeholder="Latitude"
+              value=field(geofence.map(|geofence| geofence.center.latitude.to_string()))
+              class="p-2 w-1/3 border rounded-md text-black"
+            />
+            <input
+              name="longitude"
+              placeholder="Longitude"
+              value=field(geofence.map(|geofence| geofence.center.longitude.to_string()))
+              class="p-2 w-1/3 border rounded-md text-black"
+            />
+            <input
+              name="radius_meters"
+              placeholder="Radius (m)"
+              value=field(geofence.map(|geofence| geofence.radius_meters.to_string()))
+              class="p-2 w-1/3 border rounded-md text-black"
+            />
+          </div>
+          <FormError id=format!("jobsite_geofence_error_{}", jobsite_id) />
+          <button id=submit_id class="mt-2 w-full bg-orange-600 disabled:bg-orange-300 text-white p-2 rounded-md hover:bg-orange-700">
+            Save Geofence
+          </button>
+        </form>
+    }
+}
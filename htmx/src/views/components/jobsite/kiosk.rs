@@ -0,0 +1,55 @@
+use leptos::*;
+use models::projections::kiosk::Kiosk;
+use uuid::Uuid;
+
+use crate::{routes::ApiRoutes, views::FormError};
+
+/// The tablets clocking the jobsite's crew in and out. Registering sets up
+/// the device doing it as a kiosk, so it's done from the tablet itself.
+#[component]
+pub fn JobsiteKiosks(jobsite_id: Uuid, kiosks: Vec<Kiosk>) -> impl IntoView {
+    let submit_id = format!("jobsite-kiosk-submit-{}", jobsite_id);
+
+    view! {
+        <div id=format!("jobsite_kiosks_{}", jobsite_id) class="mt-4 w-full">
+          <label class="block text-sm font-medium text-white">Kiosks</label>
+          <ul class="mt-1 text-white text-sm">
+            {kiosks
+                .into_iter()
+                .map(|kiosk| view! {
+                    <li class="flex flex-row justify-between py-1">
+                      <span>
+                        {format!("{} - since {}", kiosk.name, kiosk.registered_at.format("%b %-d, %Y"))}
+                      </span>
+                      <button
+                          hx-post=ApiRoutes::post_jobsite_kiosk_revoke(jobsite_id, kiosk.id)
+                          hx-swap="none"
+                          hx-confirm="Revoke this kiosk? It will stop accepting PINs straight away."
+                          class="text-red-400 hover:text-red-600"
+                      >
+                        Revoke
+                      </button>
+                    </li>
+                })
+                .collect_view()}
+          </ul>
+          <form
+            hx-post=ApiRoutes::post_jobsite_kiosk(jobsite_id)
+            hx-swap="none"
+            hx-disabled-elt=format!("#{}", submit_id)
+            hx-confirm="Set up this device as a kiosk for the jobsite?"
+            class="mt-1 flex flex-row gap-2"
+          >
+            <input
+                name="name"
+                placeholder="Kiosk name, e.g. Trailer tablet"
+                class="p-2 flex-grow border rounded-md text-black"
+            />
+            <button id=submit_id class="bg-orange-600 disabled:bg-orange-300 text-white p-2 rounded-md hover:bg-orange-700">
+              Use This Device
+            </button>
+          </form>
+          <FormError id=format!("jobsite_kiosk_error_{}", jobsite_id) />
+        </div>
+    }
+}
@@ -0,0 +1,60 @@
+use leptos::*;
+use models::projections::jobsite_labor_cost::JobsiteLaborCost;
+use uuid::Uuid;
+
+use crate::views::{format_cents, format_minutes};
+
+#[component]
+pub fn JobsiteLaborCostPanel(
+    jobsite_id: Uuid,
+    /// How many days back `costs` covers
+    days: i64,
+    costs: Vec<JobsiteLaborCost>,
+) -> impl IntoView {
+    let total_cents: i64 = costs.iter().map(|cost| cost.cost_cents).sum();
+    let unrated_minutes: i64 = costs.iter().map(|cost| cost.unrated_minutes).sum();
+
+    view! {
+        <div id=format!("jobsite_labor_cost_{}", jobsite_id) class="mt-6 w-full">
+            <div class="text-center mb-2">
+                <span class="text-orange-700 text-xl font-bold" style="font-family: 'Roboto Slab', serif;">Labor Cost</span>
+            </div>
+            <div class="flex items-center justify-between p-2 text-white">
+                <span>{format!("Last {} days", days)}</span>
+                <span class="text-lg font-bold">{format_cents(total_cents)}</span>
+            </div>
+            {(unrated_minutes > 0).then(|| view! {
+                <div class="p-2 text-sm text-red-300">
+                    {format!("{} worked without a pay rate is not included", format_minutes(unrated_minutes))}
+                </div>
+            })}
+            {if costs.is_empty() {
+                view! {
+                    <div class="text-center text-white italic">No hours worked</div>
+                }.into_view()
+            } else {
+                costs.into_iter().map(|cost| view! { <JobsiteLaborCostRow cost=cost /> }).collect::<Vec<_>>().into_view()
+            }}
+        </div>
+    }
+}
+
+#[component]
+pub fn JobsiteLaborCostRow(cost: JobsiteLaborCost) -> impl IntoView {
+    view! {
+        <div class="flex items-center justify-between p-2 my-2 bg-gray-400 rounded-md">
+            <div class="flex flex-col">
+                <span class="text-lg">{cost.work_date.format("%a %b %-d").to_string()}</span>
+                <span class="text-sm text-gray-700">
+                    {format!(
+                        "{} regular, {} overtime, {} double time",
+                        format_minutes(cost.regular_minutes),
+                        format_minutes(cost.overtime_minutes),
+                        format_minutes(cost.double_time_minutes)
+                    )}
+                </span>
+            </div>
+            <span class="text-lg">{format_cents(cost.cost_cents)}</span>
+        </div>
+    }
+}
@@ -1,28 +1,126 @@
 use leptos::*;
-use models::projections::jobsite::Jobsite;
+use models::projections::{client::Client, jobsite::Jobsite, project::Project};
 use uuid::Uuid;
 
 use crate::views::components::jobsite::JobsiteRow;
 
+/// Jobsites grouped by client and project, jobsites not yet assigned to a
+/// project are listed last
 #[component]
 pub fn JobsiteList(
+    clients: Vec<Client>,
+    projects: Vec<Project>,
+    jobsites: Vec<Jobsite>,
+) -> impl IntoView {
+    let unassigned = jobsites
+        .iter()
+        .filter(|jobsite| jobsite.project_id.is_none())
+        .cloned()
+        .collect::<Vec<_>>();
+
+    view! {
+        <div class="w-11/12 mx-auto rounded-md p-4" id="jobsite-list">
+            <JobsiteClientList clients=clients projects=projects jobsites=jobsites />
+            <div class="mt-4">
+                <span class="text-white font-bold">No project</span>
+                <ProjectJobsiteList project_id=None jobsites=unassigned />
+            </div>
+        </div>
+    }
+}
+
+#[component]
+pub fn JobsiteClientList(
+    clients: Vec<Client>,
+    projects: Vec<Project>,
     jobsites: Vec<Jobsite>,
     #[prop(optional)] append: Option<Uuid>,
 ) -> impl IntoView {
     let hx_swap_oob = if append.is_some() { "afterbegin" } else { "" };
     let data_append = if let Some(append) = append {
-        format!("jobsite_row_{}", append)
+        format!("client_jobsites_{}", append)
+    } else {
+        "".to_string()
+    };
+
+    view! {
+        <div id="jobsite-client-list" hx-swap-oob=hx_swap_oob data-append=data_append>
+            {clients.into_iter().map(|client| {
+                let client_projects = projects
+                    .iter()
+                    .filter(|project| project.client_id == client.id)
+                    .cloned()
+                    .collect::<Vec<_>>();
+
+                view! {
+                    <div id=format!("client_jobsites_{}", client.id) class="mb-4">
+                        <span class="text-orange-500 text-xl font-bold">{client.name}</span>
+                        <ClientProjectList client_id=client.id projects=client_projects jobsites=jobsites.clone() />
+                    </div>
+                }
+            }).collect::<Vec<_>>().into_view()}
+        </div>
+    }
+}
+
+#[component]
+pub fn ClientProjectList(
+    client_id: Uuid,
+    projects: Vec<Project>,
+    jobsites: Vec<Jobsite>,
+    #[prop(optional)] append: Option<Uuid>,
+) -> impl IntoView {
+    let hx_swap_oob = if append.is_some() { "afterbegin" } else { "" };
+    let data_append = if let Some(append) = append {
+        format!("project_group_{}", append)
     } else {
         "".to_string()
     };
 
     view! {
         <div
-            class="w-11/12 mx-auto rounded-md p-4"
-            id="jobsite-list"
+            id=format!("client_projects_{}", client_id)
+            class="pl-2"
             hx-swap-oob=hx_swap_oob
             data-append=data_append
         >
+            {projects.into_iter().map(|project| {
+                let project_jobsites = jobsites
+                    .iter()
+                    .filter(|jobsite| jobsite.project_id == Some(project.id))
+                    .cloned()
+                    .collect::<Vec<_>>();
+
+                view! {
+                    <div id=format!("project_group_{}", project.id) class="mt-2">
+                        <span class="text-white font-bold">{project.name}</span>
+                        <ProjectJobsiteList project_id=Some(project.id) jobsites=project_jobsites />
+                    </div>
+                }
+            }).collect::<Vec<_>>().into_view()}
+        </div>
+    }
+}
+
+#[component]
+pub fn ProjectJobsiteList(
+    project_id: Option<Uuid>,
+    jobsites: Vec<Jobsite>,
+    #[prop(optional)] append: Option<Uuid>,
+) -> impl IntoView {
+    let hx_swap_oob = if append.is_some() { "afterbegin" } else { "" };
+    let data_append = if let Some(append) = append {
+        format!("jobsite_row_{}", append)
+    } else {
+        "".to_string()
+    };
+    let id = match project_id {
+        Some(project_id) => format!("project_jobsites_{}", project_id),
+        None => "project_jobsites_none".to_string(),
+    };
+
+    view! {
+        <div id=id class="pl-2" hx-swap-oob=hx_swap_oob data-append=data_append>
             {jobsites.into_iter().map(|jobsite| view! { <JobsiteRow jobsite=jobsite.clone() jobsite_id=jobsite.id /> }).collect::<Vec<_>>().into_view()}
         </div>
     }
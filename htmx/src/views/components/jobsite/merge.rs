@@ -0,0 +1,86 @@
+use leptos::*;
+use models::projections::jobsite::{Jobsite, JobsiteMerge};
+use uuid::Uuid;
+
+use crate::{routes::ApiRoutes, views::FormError};
+
+#[component]
+pub fn JobsiteMergeForm(jobsite_id: Uuid, jobsites: Vec<Jobsite>) -> impl IntoView {
+    let submit_id = format!("jobsite-merge-submit-{}", jobsite_id);
+
+    view! {
+        <form
+          id=format!("jobsite_merge_{}", jobsite_id)
+          hx-post=ApiRoutes::post_jobsite_merge(jobsite_id)
+          hx-swap="none"
+          hx-disabled-elt=format!("#{}", submit_id)
+          hx-confirm="Merge this jobsite? Its hours, assignments and attachments move to the jobsite you chose."
+          class="mt-4 w-full"
+        >
+          <div class="mb-4">
+            <label class="block text-sm font-medium text-white">Duplicate of</label>
+            <select name="target_id" class="mt-1 p-2 w-full border rounded-md text-black">
+              <option value="">Choose a jobsite</option>
+              {jobsites.into_iter().map(|jobsite| view! {
+                  <option value=jobsite.id.to_string()>{jobsite.name}</option>
+              }).collect::<Vec<_>>().into_view()}
+            </select>
+            <FormError id=format!("jobsite_merge_error_{}", jobsite_id) />
+          </div>
+          <button id=submit_id class="w-full bg-orange-600 disabled:bg-orange-300 text-white p-2 rounded-md hover:bg-orange-700">
+            Merge Into Jobsite
+          </button>
+        </form>
+    }
+}
+
+/// Takes the place of a merged jobsite's row, opening the jobsite it was
+/// merged into instead
+#[component]
+pub fn JobsiteMergedRow(merge: JobsiteMerge) -> impl IntoView {
+    view! {
+        <div
+            class="jobsite-row cursor-pointer flex items-center justify-between p-2 my-2 bg-gray-600 rounded-md"
+            id=format!("jobsite_row_{}", merge.source.id)
+            hx-get=ApiRoutes::get_jobsite(merge.target.id)
+            hx-swap="innerHTML"
+            hx-target="#jobsite-edit"
+            hx-trigger="click"
+        >
+            <span class="text-lg line-through">{merge.source.name}</span>
+            <span class="text-sm italic">{format!("Merged into {}", merge.target.name)}</span>
+        </div>
+    }
+}
+
+/// Shown in place of a merged jobsite, with a way to undo the merge
+#[component]
+pub fn JobsiteMergedNotice(merge: JobsiteMerge) -> impl IntoView {
+    view! {
+        <div id=format!("jobsite_edit_{}", merge.source.id) class="h-full w-full mx-4 text-center">
+            <div class="mb-4">
+                <span class="text-orange-700 text-3xl font-bold" style="font-family: 'Roboto Slab', serif;">{merge.source.name}</span>
+            </div>
+            <p class="text-white">
+                "This jobsite was merged into "
+                <a
+                    class="cursor-pointer underline hover:text-orange-500"
+                    hx-get=ApiRoutes::get_jobsite(merge.target.id)
+                    hx-swap="innerHTML"
+                    hx-target="#jobsite-edit"
+                >
+                    {merge.target.name}
+                </a>
+                ", its hours, assignments and attachments are recorded there."
+            </p>
+            <button
+                hx-post=ApiRoutes::post_jobsite_unmerge(merge.source.id)
+                hx-swap="none"
+                hx-confirm="Undo this merge? Everything that was moved goes back to this jobsite."
+                class="mt-4 bg-orange-600 text-white p-2 rounded-md hover:bg-orange-700"
+            >
+                Unmerge
+            </button>
+        </div>
+    }
+}
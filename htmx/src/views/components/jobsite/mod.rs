@@ -1,9 +1,25 @@
+mod budget;
 mod create;
 mod edit;
+mod geofence;
+mod kiosk;
+mod labor_cost;
 mod list;
+mod merge;
+mod project;
+mod qr_code;
 mod row;
+mod time_zone;
 
+pub use budget::*;
 pub use create::*;
 pub use edit::*;
+pub use geofence::*;
+pub use kiosk::*;
+pub use labor_cost::*;
 pub use list::*;
+pub use merge::*;
+pub use project::*;
+pub use qr_code::*;
 pub use row::*;
+pub use time_zone::*;
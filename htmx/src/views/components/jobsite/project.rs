@@ -0,0 +1,40 @@
+use leptos::*;
+use models::projections::project::Project;
+use uuid::Uuid;
+
+use crate::{routes::ApiRoutes, views::FormError};
+
+#[component]
+pub fn JobsiteProjectAssign(
+    jobsite_id: Uuid,
+    project_id: Option<Uuid>,
+    projects: Vec<Project>,
+) -> impl IntoView {
+    let submit_id = format!("jobsite-project-submit-{}", jobsite_id);
+
+    view! {
+        <form
+          id=format!("jobsite_project_{}", jobsite_id)
+          hx-post=ApiRoutes::post_jobsite_project(jobsite_id)
+          hx-swap="none"
+          hx-disabled-elt=format!("#{}", submit_id)
+          class="mt-4 w-full"
+        >
+          <div class="mb-4">
+            <label class="block text-sm font-medium text-white">Project</label>
+            <select name="project_id" class="mt-1 p-2 w-full border rounded-md text-black">
+              <option value="" selected=project_id.is_none()>No project</option>
+              {projects.into_iter().map(|project| view! {
+                  <option value=project.id.to_string() selected=project_id == Some(project.id)>
+                    {format!("{} / {}", project.client_name, project.name)}
+                  </option>
+              }).collect::<Vec<_>>().into_view()}
+            </select>
+            <FormError id=format!("jobsite_project_error_{}", jobsite_id) />
+          </div>
+          <button id=submit_id class="w-full bg-orange-600 disabled:bg-orange-300 text-white p-2 rounded-md hover:bg-orange-700">
+            Move To Project
+          </button>
+        </form>
+    }
+}
@@ -0,0 +1,30 @@
+use chrono::NaiveDate;
+use leptos::*;
+use uuid::Uuid;
+
+use crate::routes::ApiRoutes;
+
+/// The code to print for the site gate, scanning it opens the jobsite's
+/// clock-in form
+#[component]
+pub fn JobsiteQrCode(jobsite_id: Uuid, expires_on: NaiveDate) -> impl IntoView {
+    view! {
+        <div id=format!("jobsite_qr_code_{}", jobsite_id) class="mt-4 w-full">
+          <label class="block text-sm font-medium text-white">Clock-In QR Code</label>
+          <div class="mt-1 flex flex-row items-center gap-4">
+            <img
+                src=ApiRoutes::get_jobsite_qr_code_svg(jobsite_id)
+                alt="Clock-in QR code"
+                class="h-24 w-24 bg-white rounded-md"
+            />
+            <div class="flex flex-col text-sm text-white">
+              <span>{format!("Codes printed today work until {}", expires_on.format("%b %-d, %Y"))}</span>
+              <div class="flex flex-row gap-4">
+                <a class="hover:text-orange-500" href=ApiRoutes::get_jobsite_qr_code_svg(jobsite_id) target="_blank">SVG</a>
+                <a class="hover:text-orange-500" href=ApiRoutes::get_jobsite_qr_code_png(jobsite_id) target="_blank">PNG</a>
+              </div>
+            </div>
+          </div>
+        </div>
+    }
+}
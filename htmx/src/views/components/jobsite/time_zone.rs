@@ -0,0 +1,26 @@
+use chrono_tz::TZ_VARIANTS;
+use leptos::*;
+
+use crate::views::FormError;
+
+/// Time zone field for the jobsite forms, suggesting every IANA zone name
+#[component]
+pub fn JobsiteTimeZoneInput(time_zone: String, list_id: String) -> impl IntoView {
+    view! {
+        <div class="mb-4">
+          <label class="block text-sm font-medium text-white">Time Zone</label>
+          <input
+            name="time_zone"
+            value=time_zone
+            list=list_id.clone()
+            class="mt-1 p-2 w-full border rounded-md text-black"
+          />
+          <datalist id=list_id>
+            {TZ_VARIANTS.iter().map(|time_zone| view! {
+                <option value=time_zone.name()></option>
+            }).collect::<Vec<_>>().into_view()}
+          </datalist>
+          <FormError id="time-zone-error".to_string() />
+        </div>
+    }
+}
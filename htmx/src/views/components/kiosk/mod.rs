@@ -0,0 +1,3 @@
+mod punch;
+
+pub use punch::*;
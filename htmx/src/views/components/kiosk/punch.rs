@@ -0,0 +1,51 @@
+use leptos::*;
+use models::projections::employee::Employee;
+use uuid::Uuid;
+
+use crate::routes::ApiRoutes;
+
+/// Pick your name and enter your PIN, the kiosk works out whether that
+/// clocks you in or out
+#[component]
+pub fn KioskPunch(jobsite_id: Uuid, employees: Vec<Employee>) -> impl IntoView {
+    view! {
+        <form
+          hx-post=ApiRoutes::post_kiosk_punch(jobsite_id)
+          hx-target="#kiosk-result"
+          hx-swap="innerHTML"
+          hx-disabled-elt="#kiosk-punch-submit"
+          hx-on:htmx:after-request="if (event.detail.successful) this.reset()"
+          class="w-full"
+        >
+          <select name="employee_id" class="p-4 w-full border rounded-md text-black text-xl">
+            {employees
+                .into_iter()
+                .map(|employee| view! { <option value=employee.id.to_string()>{employee.name}</option> })
+                .collect_view()}
+          </select>
+          <input
+              type="password"
+              name="pin"
+              inputmode="numeric"
+              autocomplete="off"
+              placeholder="PIN"
+              class="mt-4 p-4 w-full border rounded-md text-black text-xl text-center"
+          />
+          <button id="kiosk-punch-submit" class="mt-4 w-full bg-orange-600 disabled:bg-orange-300 text-white p-4 rounded-md text-xl hover:bg-orange-700">
+            Clock In / Out
+          </button>
+        </form>
+        <div id="kiosk-result" class="mt-4"></div>
+    }
+}
+
+#[component]
+pub fn KioskPunchResult(message: String, success: bool) -> impl IntoView {
+    let class = if success {
+        "p-4 bg-green-100 text-green-800 rounded-md text-xl text-center"
+    } else {
+        "p-4 bg-red-100 text-red-700 rounded-md text-xl text-center"
+    };
+
+    view! { <div class=class role="alert">{message}</div> }
+}
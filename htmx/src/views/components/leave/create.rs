@@ -0,0 +1,76 @@
+use chrono::NaiveDate;
+use leptos::*;
+use models::projections::{
+    employee::Employee,
+    leave::{LeaveType, LEAVE_DAY_MINUTES},
+};
+
+use crate::{routes::ApiRoutes, views::FormError};
+
+/// Form for requesting paid time off, leave only counts on weekdays
+#[component]
+pub fn LeaveCreate(employees: Vec<Employee>, today: NaiveDate) -> impl IntoView {
+    view! {
+        <form
+          hx-post=ApiRoutes::post_leave()
+          hx-swap="none"
+          hx-disabled-elt="#leave-submit"
+          hx-on:htmx:after-request="if (event.detail.successful) this.reset()"
+          class="w-full flex flex-row flex-wrap items-end gap-2"
+        >
+          <div class="flex-grow">
+            <label class="block text-sm font-medium text-white">Employee</label>
+            <select name="employee_id" class="mt-1 p-2 w-full border rounded-md text-black">
+              {employees.into_iter().map(|employee| view! {
+                  <option value=employee.id.to_string()>{employee.name}</option>
+              }).collect::<Vec<_>>().into_view()}
+            </select>
+          </div>
+          <div>
+            <label class="block text-sm font-medium text-white">Type</label>
+            <select name="leave_type" class="mt-1 p-2 border rounded-md text-black">
+              {LeaveType::ALL.into_iter().map(|leave_type| view! {
+                  <option value=leave_type.as_str()>{leave_type.label()}</option>
+              }).collect::<Vec<_>>().into_view()}
+            </select>
+          </div>
+          <div>
+            <label class="block text-sm font-medium text-white">From</label>
+            <input
+                type="date"
+                name="start_date"
+                value=today.to_string()
+                class="mt-1 p-2 border rounded-md text-black"
+            />
+          </div>
+          <div>
+            <label class="block text-sm font-medium text-white">To</label>
+            <input type="date" name="end_date" class="mt-1 p-2 border rounded-md text-black" />
+          </div>
+          <div>
+            <label class="block text-sm font-medium text-white">Hours a day</label>
+            <input
+                type="number"
+                name="hours_per_day"
+                min="0.25"
+                max=(LEAVE_DAY_MINUTES / 60).to_string()
+                step="0.25"
+                placeholder=(LEAVE_DAY_MINUTES / 60).to_string()
+                class="mt-1 p-2 w-24 border rounded-md text-black"
+            />
+          </div>
+          <div class="w-full">
+            <label class="block text-sm font-medium text-white">Note</label>
+            <input name="note" class="mt-1 p-2 w-full border rounded-md text-black" />
+          </div>
+          <button id="leave-submit" class="bg-orange-600 disabled:bg-orange-300 text-white p-2 rounded-md hover:bg-orange-700">
+            Request
+          </button>
+          <div class="w-full">
+            <FormError id="leave-employee-error".to_string() />
+            <FormError id="leave-date-error".to_string() />
+            <FormError id="leave-hours-error".to_string() />
+          </div>
+        </form>
+    }
+}
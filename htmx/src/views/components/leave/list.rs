@@ -0,0 +1,122 @@
+use leptos::*;
+use models::projections::{
+    employee::Employee,
+    leave::{Leave, LeaveBalance, LeaveStatus, LeaveType},
+};
+use uuid::Uuid;
+
+use crate::{
+    routes::ApiRoutes,
+    views::{format_minutes, FormError},
+};
+
+/// The element id of the errors shown under a leave request's decision
+pub fn leave_action_error_id(leave_id: &Uuid) -> String {
+    format!("leave_action_error_{}", leave_id)
+}
+
+/// Every leave request, most recent first
+#[component]
+pub fn LeaveList(leave: Vec<Leave>) -> impl IntoView {
+    view! {
+        <div class="w-11/12 mx-auto rounded-md p-4" id="leave-list">
+            {leave.into_iter().map(|leave| view! {
+                <LeaveRow leave=leave />
+            }).collect::<Vec<_>>().into_view()}
+        </div>
+    }
+}
+
+#[component]
+pub fn LeaveRow(leave: Leave) -> impl IntoView {
+    let leave_id = leave.id;
+
+    view! {
+        <div id=format!("leave_row_{}", leave_id) class="p-2 my-2 bg-gray-400 rounded-md">
+            <div class="flex items-center justify-between">
+                <span class="text-lg">
+                    {format!("{}, {} leave", leave.employee_name, leave.leave_type.label())}
+                </span>
+                <span class="text-sm">{leave.status.label()}</span>
+            </div>
+            <span class="text-sm text-gray-700">
+                {format!(
+                    "{} to {}, {}",
+                    leave.start_date.format("%b %-d, %Y"),
+                    leave.end_date.format("%b %-d, %Y"),
+                    format_minutes(leave.total_minutes)
+                )}
+            </span>
+            {leave.note.map(|note| view! {
+                <p class="text-sm whitespace-pre-line">{note}</p>
+            })}
+            {leave.denial_reason.map(|reason| view! {
+                <p class="text-sm whitespace-pre-line">{format!("Denied: {}", reason)}</p>
+            })}
+            {(leave.status == LeaveStatus::Requested).then(|| view! {
+                <div class="mt-1 flex flex-row items-center gap-2 text-sm">
+                    <button
+                        hx-post=ApiRoutes::post_leave_approve(leave_id)
+                        hx-swap="none"
+                        class="bg-orange-600 text-white p-1 rounded-md hover:bg-orange-700"
+                    >
+                        Approve
+                    </button>
+                    <form
+                      hx-post=ApiRoutes::post_leave_deny(leave_id)
+                      hx-swap="none"
+                      class="flex flex-grow flex-row items-center gap-2"
+                    >
+                      <input name="reason" placeholder="Reason" class="p-1 flex-grow border rounded-md text-black" />
+                      <button class="text-orange-900 hover:text-orange-700">Deny</button>
+                    </form>
+                </div>
+                <FormError id=leave_action_error_id(&leave_id) />
+            })}
+        </div>
+    }
+}
+
+/// What each active employee has left of every leave type this year
+#[component]
+pub fn LeaveBalances(
+    employees: Vec<Employee>,
+    balances: Vec<LeaveBalance>,
+    year: i32,
+) -> impl IntoView {
+    view! {
+        <div class="w-11/12 mx-auto rounded-md p-4 text-white" id="leave-balances">
+            <table class="w-full text-sm">
+                <thead>
+                    <tr>
+                        <th class="text-left p-1">{year}</th>
+                        {LeaveType::ALL.into_iter().map(|leave_type| view! {
+                            <th class="text-right p-1">{leave_type.label()}</th>
+                        }).collect::<Vec<_>>().into_view()}
+                    </tr>
+                </thead>
+                <tbody>
+                    {employees.into_iter().map(|employee| view! {
+                        <tr class="border-t border-gray-600">
+                            <td class="p-1">{employee.name}</td>
+                            {LeaveType::ALL.into_iter().map(|leave_type| {
+                                let balance = LeaveBalance::find(&balances, employee.id, leave_type, year);
+                                let title = format!(
+                                    "{} used, {} pending",
+                                    format_minutes(balance.approved_minutes),
+                                    format_minutes(balance.pending_minutes)
+                                );
+
+                                view! {
+                                    <td class="text-right p-1" title=title>
+                                        {format_minutes(balance.remaining_minutes())}
+                                    </td>
+                                }
+                            }).collect::<Vec<_>>().into_view()}
+                        </tr>
+                    }).collect::<Vec<_>>().into_view()}
+                </tbody>
+            </table>
+        </div>
+    }
+}
@@ -0,0 +1,5 @@
+mod create;
+mod list;
+
+pub use create::*;
+pub use list::*;
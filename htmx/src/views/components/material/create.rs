@@ -0,0 +1,107 @@
+use chrono::NaiveDate;
+use leptos::*;
+use models::projections::material::{Material, MaterialUnit};
+use uuid::Uuid;
+
+use crate::{routes::ApiRoutes, views::FormError};
+
+/// The element id of the errors shown under a jobsite's material form
+pub fn jobsite_material_error_id(jobsite_id: &Uuid) -> String {
+    format!("jobsite_material_error_{}", jobsite_id)
+}
+
+/// Form for adding a material to the catalog
+#[component]
+pub fn MaterialCreate() -> impl IntoView {
+    view! {
+        <form
+          hx-post=ApiRoutes::post_material()
+          hx-swap="none"
+          hx-disabled-elt="#material-submit"
+          hx-on:htmx:after-request="if (event.detail.successful) this.reset()"
+          class="w-full flex flex-row flex-wrap items-end gap-2"
+        >
+          <div class="flex-grow">
+            <label class="block text-sm font-medium text-white">Name</label>
+            <input name="name" placeholder="Ready-mix concrete" class="mt-1 p-2 w-full border rounded-md text-black" />
+          </div>
+          <div>
+            <label class="block text-sm font-medium text-white">Unit</label>
+            <select name="unit" class="mt-1 p-2 border rounded-md text-black">
+              {MaterialUnit::ALL.into_iter().map(|unit| view! {
+                  <option value=unit.as_str()>{unit.label()}</option>
+              }).collect::<Vec<_>>().into_view()}
+            </select>
+          </div>
+          <div>
+            <label class="block text-sm font-medium text-white">Cost per Unit</label>
+            <input name="unit_cost" placeholder="0.00" class="mt-1 p-2 w-28 border rounded-md text-black" />
+          </div>
+          <button id="material-submit" class="bg-orange-600 disabled:bg-orange-300 text-white p-2 rounded-md hover:bg-orange-700">
+            Add
+          </button>
+          <div class="w-full">
+            <FormError id="material-name-error".to_string() />
+            <FormError id="material-cost-error".to_string() />
+          </div>
+        </form>
+    }
+}
+
+/// Form for recording material used up on a jobsite
+#[component]
+pub fn MaterialConsume(
+    jobsite_id: Uuid,
+    materials: Vec<Material>,
+    today: NaiveDate,
+) -> impl IntoView {
+    let submit_id = format!("material-consume-submit-{}", jobsite_id);
+
+    view! {
+        <form
+          id=format!("material_consume_{}", jobsite_id)
+          hx-post=ApiRoutes::post_jobsite_material(jobsite_id)
+          hx-swap="none"
+          hx-disabled-elt=format!("#{}", submit_id)
+          hx-on:htmx:after-request="if (event.detail.successful) this.reset()"
+          class="mt-2 w-full"
+        >
+          <div class="mb-4 flex flex-row gap-2">
+            <div class="w-1/2">
+              <label class="block text-sm font-medium text-white">Material</label>
+              <select name="material_id" class="mt-1 p-2 w-full border rounded-md text-black">
+                {materials.into_iter().map(|material| view! {
+                    <option value=material.id.to_string()>
+                      {format!("{} ({})", material.name, material.unit.abbreviation())}
+                    </option>
+                }).collect::<Vec<_>>().into_view()}
+              </select>
+            </div>
+            <div class="w-1/4">
+              <label class="block text-sm font-medium text-white">Quantity</label>
+              <input
+                  type="number"
+                  name="quantity"
+                  min="0"
+                  step="any"
+                  class="mt-1 p-2 w-full border rounded-md text-black"
+              />
+            </div>
+            <div class="w-1/4">
+              <label class="block text-sm font-medium text-white">Used On</label>
+              <input
+                  type="date"
+                  name="consumed_on"
+                  value=today.to_string()
+                  max=today.to_string()
+                  class="mt-1 p-2 w-full border rounded-md text-black"
+              />
+            </div>
+          </div>
+          <FormError id=jobsite_material_error_id(&jobsite_id) />
+          <button id=submit_id class="w-full bg-orange-600 disabled:bg-orange-300 text-white p-2 rounded-md hover:bg-orange-700">
+            Record Usage
+          </button>
+        </form>
+    }
+}
@@ -0,0 +1,73 @@
+use leptos::*;
+use models::projections::material::{JobsiteMaterialUsage, Material};
+use uuid::Uuid;
+
+use crate::views::format_cents;
+
+/// The material catalog by name
+#[component]
+pub fn MaterialList(materials: Vec<Material>) -> impl IntoView {
+    view! {
+        <div class="w-11/12 mx-auto rounded-md p-4" id="material-list">
+            {materials.into_iter().map(|material| view! {
+                <div
+                    id=format!("material_row_{}", material.id)
+                    class="flex items-center justify-between p-2 my-2 bg-gray-400 rounded-md"
+                >
+                    <span class="text-lg">{material.name}</span>
+                    <span class="text-sm text-gray-700">
+                        {match material.unit_cost_cents {
+                            Some(cents) => format!("{} / {}", format_cents(cents), material.unit.abbreviation()),
+                            None => material.unit.label().to_string(),
+                        }}
+                    </span>
+                </div>
+            }).collect::<Vec<_>>().into_view()}
+        </div>
+    }
+}
+
+/// What a jobsite has used of each material and what it cost
+#[component]
+pub fn JobsiteMaterials(jobsite_id: Uuid, usage: Vec<JobsiteMaterialUsage>) -> impl IntoView {
+    let total_cents = usage
+        .iter()
+        .filter_map(|usage| usage.cost_cents)
+        .sum::<i64>();
+
+    view! {
+        <div id=format!("jobsite_materials_{}", jobsite_id) class="mt-6 w-full">
+            <div class="text-center mb-2">
+                <span class="text-orange-700 text-xl font-bold" style="font-family: 'Roboto Slab', serif;">Materials</span>
+            </div>
+            {if usage.is_empty() {
+                view! {
+                    <div class="text-center text-white italic">No materials used</div>
+                }.into_view()
+            } else {
+                usage.into_iter().map(|usage| view! {
+                    <div class="flex items-center justify-between p-2 my-2 bg-gray-400 rounded-md">
+                        <div class="flex flex-col">
+                            <span class="text-lg">{usage.material_name}</span>
+                            <span class="text-sm text-gray-700">
+                                {format!("Last used {}", usage.last_consumed_on.format("%b %-d, %Y"))}
+                            </span>
+                        </div>
+                        <div class="flex flex-col text-right">
+                            <span>{format!("{} {}", usage.quantity, usage.unit.abbreviation())}</span>
+                            {usage.cost_cents.map(|cents| view! {
+                                <span class="text-sm text-gray-700">{format_cents(cents)}</span>
+                            })}
+                        </div>
+                    </div>
+                }).collect::<Vec<_>>().into_view()
+            }}
+            {(total_cents > 0).then(|| view! {
+                <div class="flex items-center justify-between p-2 text-white">
+                    <span class="italic">Total</span>
+                    <span>{format_cents(total_cents)}</span>
+                </div>
+            })}
+        </div>
+    }
+}
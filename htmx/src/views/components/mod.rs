@@ -1 +1,20 @@
+pub mod attachment;
+pub mod calendar;
+pub mod certification;
+pub mod change_order;
+pub mod client;
+pub mod comment;
+pub mod cost_code;
+pub mod crew_assignment;
+pub mod daily_log;
+pub mod employee;
+pub mod equipment;
+pub mod incident;
 pub mod jobsite;
+pub mod kiosk;
+pub mod leave;
+pub mod material;
+pub mod pay_rate;
+pub mod project;
+pub mod shift;
+pub mod timesheet;
@@ -0,0 +1,65 @@
+use leptos::*;
+use models::projections::{employee::Employee, jobsite::Jobsite};
+
+use crate::{routes::ApiRoutes, views::FormError};
+
+#[component]
+pub fn PayRateCreate(
+    employees: Vec<Employee>,
+    jobsites: Vec<Jobsite>,
+    trades: Vec<String>,
+) -> impl IntoView {
+    view! {
+        <form
+          hx-post=ApiRoutes::post_pay_rate()
+          hx-swap="none"
+          hx-disabled-elt="#pay-rate-submit"
+          hx-on:htmx:after-request="if (event.detail.successful) this.reset()"
+          class="w-full"
+        >
+          <div class="mb-4 flex flex-row gap-2">
+            <div class="w-1/2">
+              <label class="block text-sm font-medium text-white">Employee</label>
+              <select name="employee_id" class="mt-1 p-2 w-full border rounded-md text-black">
+                <option value="">None</option>
+                {employees.into_iter().map(|employee| view! {
+                    <option value=employee.id.to_string()>{employee.name}</option>
+                }).collect::<Vec<_>>().into_view()}
+              </select>
+            </div>
+            <div class="w-1/2">
+              <label class="block text-sm font-medium text-white">Or Trade</label>
+              <input name="trade" list="pay-rate-trades" class="mt-1 p-2 w-full border rounded-md text-black" />
+              <datalist id="pay-rate-trades">
+                {trades.into_iter().map(|trade| view! { <option value=trade></option> }).collect::<Vec<_>>().into_view()}
+              </datalist>
+            </div>
+          </div>
+          <FormError id="pay-rate-target-error".to_string() />
+          <div class="mb-4">
+            <label class="block text-sm font-medium text-white">Jobsite</label>
+            <select name="jobsite_id" class="mt-1 p-2 w-full border rounded-md text-black">
+              <option value="">All jobsites</option>
+              {jobsites.into_iter().map(|jobsite| view! {
+                  <option value=jobsite.id.to_string()>{jobsite.name}</option>
+              }).collect::<Vec<_>>().into_view()}
+            </select>
+          </div>
+          <div class="mb-4 flex flex-row gap-2">
+            <div class="w-1/2">
+              <label class="block text-sm font-medium text-white">Hourly Rate ($)</label>
+              <input name="hourly_rate" placeholder="32.50" class="mt-1 p-2 w-full border rounded-md text-black" />
+              <FormError id="pay-rate-rate-error".to_string() />
+            </div>
+            <div class="w-1/2">
+              <label class="block text-sm font-medium text-white">Effective</label>
+              <input type="date" name="effective_date" class="mt-1 p-2 w-full border rounded-md text-black" />
+              <FormError id="pay-rate-date-error".to_string() />
+            </div>
+          </div>
+          <button id="pay-rate-submit" class="w-full bg-orange-600 disabled:bg-orange-300 text-white p-2 rounded-md hover:bg-orange-700">
+            Set Pay Rate
+          </button>
+        </form>
+    }
+}
@@ -0,0 +1,52 @@
+use leptos::*;
+use models::projections::pay_rate::PayRate;
+use uuid::Uuid;
+
+use crate::views::format_cents;
+
+#[component]
+pub fn PayRateList(pay_rates: Vec<PayRate>, #[prop(optional)] append: Option<Uuid>) -> impl IntoView {
+    let hx_swap_oob = if append.is_some() { "afterbegin" } else { "" };
+    let data_append = if let Some(append) = append {
+        format!("pay_rate_row_{}", append)
+    } else {
+        "".to_string()
+    };
+
+    view! {
+        <div
+            class="w-11/12 mx-auto rounded-md p-4"
+            id="pay-rate-list"
+            hx-swap-oob=hx_swap_oob
+            data-append=data_append
+        >
+            {pay_rates.into_iter().map(|pay_rate| view! { <PayRateRow pay_rate=pay_rate /> }).collect::<Vec<_>>().into_view()}
+        </div>
+    }
+}
+
+#[component]
+pub fn PayRateRow(pay_rate: PayRate) -> impl IntoView {
+    let applies_to = pay_rate
+        .employee_name
+        .or(pay_rate.trade.map(|trade| format!("All {}", trade)))
+        .unwrap_or_default();
+    let jobsite = pay_rate
+        .jobsite_name
+        .unwrap_or_else(|| "All jobsites".to_string());
+
+    view! {
+        <div
+            class="flex items-center justify-between p-2 my-2 bg-gray-400 rounded-md"
+            id=format!("pay_rate_row_{}", pay_rate.id)
+        >
+            <div class="flex flex-col">
+                <span class="text-lg">{applies_to}</span>
+                <span class="text-sm text-gray-700">
+                    {format!("{}, from {}", jobsite, pay_rate.effective_date.format("%b %-d, %Y"))}
+                </span>
+            </div>
+            <span class="text-lg">{format!("{}/hr", format_cents(pay_rate.hourly_rate_cents))}</span>
+        </div>
+    }
+}
@@ -0,0 +1,34 @@
+use leptos::*;
+use models::projections::client::Client;
+
+use crate::{routes::ApiRoutes, views::FormError};
+
+#[component]
+pub fn ProjectCreate(clients: Vec<Client>) -> impl IntoView {
+    view! {
+        <form
+          hx-post=ApiRoutes::post_project()
+          hx-swap="none"
+          hx-disabled-elt="#project-submit"
+          hx-on:htmx:after-request="if (event.detail.successful) this.reset()"
+          class="w-full"
+        >
+          <div class="mb-4">
+            <label class="block text-sm font-medium text-white">Client</label>
+            <select name="client_id" class="mt-1 p-2 w-full border rounded-md text-black">
+              {clients.into_iter().map(|client| view! {
+                  <option value=client.id.to_string()>{client.name}</option>
+              }).collect::<Vec<_>>().into_view()}
+            </select>
+          </div>
+          <div class="mb-4">
+            <label class="block text-sm font-medium text-white">Project Name</label>
+            <input name="name" class="mt-1 p-2 w-full border rounded-md text-black" />
+            <FormError id="project-name-error".to_string() />
+          </div>
+          <button id="project-submit" class="w-full bg-orange-600 disabled:bg-orange-300 text-white p-2 rounded-md hover:bg-orange-700">
+            Add Project
+          </button>
+        </form>
+    }
+}
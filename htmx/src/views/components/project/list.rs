@@ -0,0 +1,31 @@
+use leptos::*;
+use models::projections::project::Project;
+use uuid::Uuid;
+
+/// A client's projects on the clients page
+#[component]
+pub fn ProjectList(
+    client_id: Uuid,
+    projects: Vec<Project>,
+    #[prop(optional)] append: Option<Uuid>,
+) -> impl IntoView {
+    let hx_swap_oob = if append.is_some() { "afterbegin" } else { "" };
+    let data_append = if let Some(append) = append {
+        format!("project_row_{}", append)
+    } else {
+        "".to_string()
+    };
+
+    view! {
+        <ul
+            id=format!("client_row_projects_{}", client_id)
+            class="pl-4 text-sm text-gray-700"
+            hx-swap-oob=hx_swap_oob
+            data-append=data_append
+        >
+            {projects.into_iter().map(|project| view! {
+                <li id=format!("project_row_{}", project.id)>{project.name}</li>
+            }).collect::<Vec<_>>().into_view()}
+        </ul>
+    }
+}
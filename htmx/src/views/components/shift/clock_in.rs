@@ -0,0 +1,59 @@
+use leptos::*;
+use models::projections::{cost_code::CostCode, employee::Employee, jobsite::Jobsite};
+
+use crate::{
+    routes::ApiRoutes,
+    views::{components::shift::LOCATE_DEVICE, FormError},
+};
+
+/// Shown once the clock-in went through, the form stays up for the next
+/// person scanning on the same phone
+const SHOW_CLOCKED_IN: &str = "if (event.detail.xhr.status === 201) { \
+    this.reset(); \
+    document.getElementById('clock-in-done').classList.remove('hidden'); \
+}";
+
+/// The clock-in form a jobsite's QR code opens, sized for a phone, it posts
+/// back the signed link it was opened with
+#[component]
+pub fn JobsiteClockInForm(
+    jobsite: Jobsite,
+    available: Vec<Employee>,
+    cost_codes: Vec<CostCode>,
+    expires: i64,
+    token: String,
+) -> impl IntoView {
+    view! {
+        <form
+          hx-post=ApiRoutes::post_jobsite_clock_in(jobsite.id)
+          hx-swap="none"
+          hx-disabled-elt="#clock-in-submit"
+          hx-on:focusin=LOCATE_DEVICE
+          hx-on:htmx:after-request=SHOW_CLOCKED_IN
+          class="w-full flex flex-col gap-4"
+        >
+          <input type="hidden" name="expires" value=expires.to_string() />
+          <input type="hidden" name="token" value=token />
+          <input type="hidden" name="latitude" />
+          <input type="hidden" name="longitude" />
+          <select name="employee_id" class="p-4 w-full border rounded-md text-black text-lg">
+            {available.into_iter().map(|employee| view! {
+                <option value=employee.id.to_string()>{employee.name}</option>
+            }).collect_view()}
+          </select>
+          <select name="cost_code_id" class="p-4 w-full border rounded-md text-black text-lg">
+            <option value="">No cost code</option>
+            {cost_codes.into_iter().map(|cost_code| view! {
+                <option value=cost_code.id.to_string()>{format!("{} {}", cost_code.code, cost_code.name)}</option>
+            }).collect_view()}
+          </select>
+          <button id="clock-in-submit" class="w-full bg-orange-600 disabled:bg-orange-300 text-white p-4 rounded-md text-lg hover:bg-orange-700">
+            Clock In
+          </button>
+        </form>
+        <FormError id="clock-in-error".to_string() />
+        <div id="clock-in-done" class="hidden mt-4 p-4 bg-green-100 text-green-800 rounded-md text-center" role="status">
+          Clocked in
+        </div>
+    }
+}
@@ -0,0 +1,5 @@
+mod clock_in;
+mod on_site;
+
+pub use clock_in::*;
+pub use on_site::*;
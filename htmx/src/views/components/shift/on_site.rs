@@ -0,0 +1,97 @@
+use leptos::*;
+use models::{
+    local_time::time_zone,
+    projections::{cost_code::CostCode, employee::Employee, shift::OnSiteEmployee},
+};
+use uuid::Uuid;
+
+use crate::{routes::ApiRoutes, views::FormError};
+
+/// Ask the device where it is once the clock-in form is first used, the
+/// clock-in goes through without coordinates if it won't say
+pub const LOCATE_DEVICE: &str = "if (!this.dataset.located && navigator.geolocation) { \
+    this.dataset.located = true; \
+    navigator.geolocation.getCurrentPosition(position => { \
+        this.latitude.value = position.coords.latitude; \
+        this.longitude.value = position.coords.longitude; \
+    }); \
+}";
+
+#[component]
+pub fn JobsiteOnSite(
+    jobsite_id: Uuid,
+    on_site: Vec<OnSiteEmployee>,
+    available: Vec<Employee>,
+    cost_codes: Vec<CostCode>,
+) -> impl IntoView {
+    view! {
+        <div id=format!("jobsite_on_site_{}", jobsite_id) class="mt-6 w-full">
+            <div class="text-center mb-2">
+                <span class="text-orange-700 text-xl font-bold" style="font-family: 'Roboto Slab', serif;">Currently On Site</span>
+            </div>
+            <form
+              hx-post=ApiRoutes::post_clock_in()
+              hx-swap="none"
+              hx-disabled-elt="#clock-in-submit"
+              hx-on:focusin=LOCATE_DEVICE
+              class="w-full flex flex-row gap-2"
+            >
+              <input type="hidden" name="jobsite_id" value=jobsite_id.to_string() />
+              <input type="hidden" name="latitude" />
+              <input type="hidden" name="longitude" />
+              <select name="employee_id" class="p-2 flex-grow border rounded-md text-black">
+                {available.into_iter().map(|employee| view! {
+                    <option value=employee.id.to_string()>{employee.name}</option>
+                }).collect::<Vec<_>>().into_view()}
+              </select>
+              <select name="cost_code_id" class="p-2 border rounded-md text-black">
+                <option value="">No cost code</option>
+                {cost_codes.into_iter().map(|cost_code| view! {
+                    <option value=cost_code.id.to_string()>{format!("{} {}", cost_code.code, cost_code.name)}</option>
+                }).collect::<Vec<_>>().into_view()}
+              </select>
+              <button id="clock-in-submit" class="bg-orange-600 disabled:bg-orange-300 text-white p-2 rounded-md hover:bg-orange-700">
+                Clock In
+              </button>
+            </form>
+            <FormError id="clock-in-error".to_string() />
+            <div class="mt-2">
+                {if on_site.is_empty() {
+                    view! {
+                        <div class="text-center text-white italic">Nobody is on site</div>
+                    }.into_view()
+                } else {
+                    on_site.into_iter().map(|on_site| view! { <OnSiteRow on_site=on_site /> }).collect::<Vec<_>>().into_view()
+                }}
+            </div>
+            <FormError id="clock-out-error".to_string() />
+        </div>
+    }
+}
+
+#[component]
+pub fn OnSiteRow(on_site: OnSiteEmployee) -> impl IntoView {
+    view! {
+        <div
+            class="flex items-center justify-between p-2 my-2 bg-gray-400 rounded-md"
+            id=format!("on_site_row_{}", on_site.shift_id)
+        >
+            <div class="flex flex-col">
+                <span class="text-lg">{on_site.employee_name}</span>
+                <span class="text-sm text-gray-700">
+                    {format!(
+                        "Since {}",
+                        on_site.clocked_in_at.with_timezone(&time_zone(&on_site.time_zone)).format("%H:%M %Z")
+                    )}
+                </span>
+            </div>
+            <button
+                hx-post=ApiRoutes::post_clock_out(on_site.shift_id)
+                hx-swap="none"
+                class="bg-orange-600 disabled:bg-orange-300 text-white p-2 rounded-md hover:bg-orange-700"
+            >
+                Clock Out
+            </button>
+        </div>
+    }
+}
@@ -0,0 +1,7 @@
+mod review;
+mod shift_row;
+mod status;
+
+pub use review::*;
+pub use shift_row::*;
+pub use status::*;
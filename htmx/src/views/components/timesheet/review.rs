@@ -0,0 +1,74 @@
+use chrono::NaiveDate;
+use leptos::*;
+use models::projections::timesheet::TimesheetReview;
+use uuid::Uuid;
+
+use crate::{
+    routes::ApiRoutes,
+    views::{components::timesheet::TimesheetReviewActions, format_minutes},
+};
+
+/// The element id of a timesheet's row in the review queue
+pub fn review_row_id(employee_id: &Uuid, week_start: &NaiveDate) -> String {
+    format!("timesheet_review_{}_{}", employee_id, week_start)
+}
+
+#[component]
+pub fn TimesheetReviewQueue(
+    reviews: Vec<TimesheetReview>,
+    /// The element id of a review row being appended over the websocket
+    #[prop(optional)]
+    append: Option<String>,
+) -> impl IntoView {
+    let hx_swap_oob = if append.is_some() { "afterbegin" } else { "" };
+    let data_append = append.unwrap_or_default();
+
+    view! {
+        <div
+            class="w-11/12 mx-auto rounded-md p-4"
+            id="timesheet-review-queue"
+            hx-swap-oob=hx_swap_oob
+            data-append=data_append
+        >
+            {reviews.into_iter().map(|review| view! { <TimesheetReviewRow review=review /> }).collect::<Vec<_>>().into_view()}
+        </div>
+    }
+}
+
+#[component]
+pub fn TimesheetReviewRow(review: TimesheetReview) -> impl IntoView {
+    view! {
+        <div
+            class="flex items-center justify-between p-2 my-2 bg-gray-400 rounded-md"
+            id=review_row_id(&review.employee_id, &review.week_start)
+        >
+            <a
+                class="flex flex-col hover:text-orange-700"
+                href=ApiRoutes::get_timesheet_page(review.employee_id, review.week_start)
+            >
+                <span class="text-lg">{review.employee_name}</span>
+                <span class="text-sm text-gray-700">
+                    {format!(
+                        "Week of {}, {}{}",
+                        review.week_start.format("%b %-d"),
+                        format_minutes(review.minutes_worked),
+                        if review.leave_minutes > 0 {
+                            format!(" + {} leave", format_minutes(review.leave_minutes))
+                        } else {
+                            "".to_string()
+                        }
+                    )}
+                </span>
+                {(review.flagged_punches > 0).then(|| view! {
+                    <span class="text-sm text-red-700 font-bold">
+                        {match review.flagged_punches {
+                            1 => "1 clock-in outside the jobsite geofence".to_string(),
+                            flagged => format!("{} clock-ins outside the jobsite geofence", flagged),
+                        }}
+                    </span>
+                })}
+            </a>
+            <TimesheetReviewActions employee_id=review.employee_id week_start=review.week_start />
+        </div>
+    }
+}
@@ -0,0 +1,89 @@
+use leptos::*;
+use models::{geofence::GeofenceStatus, projections::shift::Shift};
+
+use crate::{
+    routes::ApiRoutes,
+    views::{format_distance, format_minutes, FormError},
+};
+
+/// A shift on an employee's weekly timesheet, closed shifts in a week that
+/// isn't locked can have their times corrected
+#[component]
+pub fn TimesheetShiftRow(shift: Shift, locked: bool) -> impl IntoView {
+    let worked = shift
+        .clocked_out_at
+        .map(|clocked_out_at| format_minutes((clocked_out_at - shift.clocked_in_at).num_minutes()))
+        .unwrap_or_else(|| "On shift".to_string());
+
+    // Times are shown and corrected in the zone the shift was worked in
+    let time_zone = shift.tz();
+    let clocked_in_at = shift.clocked_in_at.with_timezone(&time_zone);
+
+    // Where the clock-in came from, punches outside the geofence are
+    // called out for the reviewer
+    let location = match (shift.geofence_status, shift.geofence_distance_meters) {
+        (GeofenceStatus::Outside, Some(distance)) => view! {
+            <span class="text-sm text-red-700 font-bold">
+                {format!("Clocked in {} from the jobsite, outside its geofence", format_distance(distance))}
+            </span>
+        }
+        .into_view(),
+        (GeofenceStatus::Inside, Some(distance)) => view! {
+            <span class="text-sm text-gray-700">
+                {format!("Clocked in {} from the jobsite", format_distance(distance))}
+            </span>
+        }
+        .into_view(),
+        _ => view! {
+            <span class="text-sm text-gray-700 italic">Clock-in location unknown</span>
+        }
+        .into_view(),
+    };
+
+    let correction = match shift.clocked_out_at {
+        Some(clocked_out_at) if !locked => {
+            let error_id = format!("shift_correction_error_{}", shift.id);
+
+            view! {
+                <form
+                    hx-put=ApiRoutes::put_shift(shift.id)
+                    hx-swap="none"
+                    class="flex flex-row items-center gap-2"
+                >
+                    <input
+                        type="datetime-local"
+                        name="clocked_in_at"
+                        value=clocked_in_at.format("%Y-%m-%dT%H:%M").to_string()
+                        class="p-1 border rounded-md text-black"
+                    />
+                    <input
+                        type="datetime-local"
+                        name="clocked_out_at"
+                        value=clocked_out_at.with_timezone(&time_zone).format("%Y-%m-%dT%H:%M").to_string()
+                        class="p-1 border rounded-md text-black"
+                    />
+                    <button class="bg-orange-600 text-white p-1 rounded-md hover:bg-orange-700">
+                        Correct
+                    </button>
+                </form>
+                <FormError id=error_id />
+            }
+            .into_view()
+        }
+        _ => view! {}.into_view(),
+    };
+
+    view! {
+        <div
+            class="flex flex-col p-2 my-2 bg-gray-400 rounded-md"
+            id=format!("timesheet_shift_row_{}", shift.id)
+        >
+            <div class="flex items-center justify-between">
+                <span class="text-lg">{clocked_in_at.format("%a %b %-d, %H:%M %Z").to_string()}</span>
+                <span>{worked}</span>
+            </div>
+            {location}
+            {correction}
+        </div>
+    }
+}
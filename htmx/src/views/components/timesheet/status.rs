@@ -0,0 +1,93 @@
+use chrono::NaiveDate;
+use leptos::*;
+use models::projections::timesheet::{Timesheet, TimesheetStatus};
+use uuid::Uuid;
+
+use crate::{routes::ApiRoutes, views::FormError};
+
+#[component]
+pub fn TimesheetStatusPanel(
+    employee_id: Uuid,
+    week_start: NaiveDate,
+    timesheet: Option<Timesheet>,
+) -> impl IntoView {
+    let status = timesheet
+        .as_ref()
+        .map(|timesheet| timesheet.status.clone())
+        .unwrap_or(TimesheetStatus::Open);
+    let rejection_reason = timesheet.and_then(|timesheet| timesheet.rejection_reason);
+
+    let actions = match status {
+        TimesheetStatus::Open | TimesheetStatus::Rejected => view! {
+            <button
+                hx-post=ApiRoutes::post_timesheet_submit(employee_id, week_start)
+                hx-swap="none"
+                class="bg-orange-600 text-white p-2 rounded-md hover:bg-orange-700"
+            >
+                Submit For Approval
+            </button>
+        }
+        .into_view(),
+        TimesheetStatus::Submitted => view! {
+            <TimesheetReviewActions employee_id=employee_id week_start=week_start />
+        }
+        .into_view(),
+        TimesheetStatus::Approved => view! {
+            <button
+                hx-post=ApiRoutes::post_timesheet_reopen(employee_id, week_start)
+                hx-swap="none"
+                hx-confirm="Reopen this week? Its hours will be unlocked for corrections."
+                class="bg-gray-600 text-white p-2 rounded-md hover:bg-gray-700"
+            >
+                Reopen
+            </button>
+        }
+        .into_view(),
+    };
+
+    view! {
+        <div
+            id=format!("timesheet_status_{}_{}", employee_id, week_start)
+            class="flex flex-col gap-2 p-2 my-2 bg-gray-400 rounded-md"
+        >
+            <div class="flex items-center justify-between">
+                <span class="text-lg">{format!("Status: {}", status.as_str())}</span>
+                {actions}
+            </div>
+            {rejection_reason.map(|reason| view! {
+                <span class="text-sm text-red-700">{format!("Rejected: {}", reason)}</span>
+            })}
+        </div>
+    }
+}
+
+/// Approve and reject controls for a submitted timesheet
+#[component]
+pub fn TimesheetReviewActions(employee_id: Uuid, week_start: NaiveDate) -> impl IntoView {
+    let error_id = format!("timesheet_reject_error_{}_{}", employee_id, week_start);
+
+    view! {
+        <div class="flex flex-col">
+            <div class="flex flex-row gap-2">
+                <button
+                    hx-post=ApiRoutes::post_timesheet_approve(employee_id, week_start)
+                    hx-swap="none"
+                    class="bg-orange-600 text-white p-2 rounded-md hover:bg-orange-700"
+                >
+                    Approve
+                </button>
+                <form
+                    hx-post=ApiRoutes::post_timesheet_reject(employee_id, week_start)
+                    hx-swap="none"
+                    class="flex flex-row gap-2"
+                >
+                    <input name="reason" placeholder="Reason" class="p-2 border rounded-md text-black" />
+                    <button class="bg-gray-600 text-white p-2 rounded-md hover:bg-gray-700">
+                        Reject
+                    </button>
+                </form>
+            </div>
+            <FormError id=error_id />
+        </div>
+    }
+}
@@ -1,5 +1,7 @@
 use leptos::*;
 
+use crate::routes::ApiRoutes;
+
 #[component]
 pub fn MainLayout(children: Children, title: String) -> impl IntoView {
     view! {
@@ -22,11 +24,63 @@ pub fn GradientBody(children: Children) -> impl IntoView {
             hx-ext="ws"
             ws-connect="/websocket"
         >
+            <Nav />
+            <WsStatusIndicator />
+            <div id="flash-error" class="fixed top-12 left-1/2 -translate-x-1/2 text-red-500"></div>
             {children()}
         </body>
     }
 }
 
+/// The body of a kiosk page, without the navigation or the websocket the
+/// rest of the app uses since a kiosk can only clock crews in and out
+#[component]
+pub fn KioskBody(children: Children) -> impl IntoView {
+    view! {
+        <body
+            id="body"
+            class="bg-gradient-to-br from-gray-900 to-gray-700 h-screen flex justify-center items-center"
+            style="font-family: 'Roboto'"
+        >
+            {children()}
+        </body>
+    }
+}
+
+#[component]
+pub fn Nav() -> impl IntoView {
+    view! {
+        <nav class="fixed top-4 left-4 flex flex-row gap-4 text-white">
+            <a class="hover:text-orange-500" href="/">Jobsites</a>
+            <a class="hover:text-orange-500" href=ApiRoutes::get_clients_page()>Clients</a>
+            <a class="hover:text-orange-500" href=ApiRoutes::get_employees_page()>Employees</a>
+            <a class="hover:text-orange-500" href=ApiRoutes::get_certifications_page()>Certifications</a>
+            <a class="hover:text-orange-500" href=ApiRoutes::get_schedule_page()>Schedule</a>
+            <a class="hover:text-orange-500" href=ApiRoutes::get_leave_page()>Time Off</a>
+            <a class="hover:text-orange-500" href=ApiRoutes::get_timesheets_page()>Timesheets</a>
+            <a class="hover:text-orange-500" href=ApiRoutes::get_incidents_page()>Safety</a>
+            <a class="hover:text-orange-500" href=ApiRoutes::get_equipment_page()>Equipment</a>
+            <a class="hover:text-orange-500" href=ApiRoutes::get_materials_page()>Materials</a>
+        </nav>
+    }
+}
+
+#[component]
+pub fn WsStatusIndicator() -> impl IntoView {
+    view! {
+        <div id="ws-status-indicator" class="fixed top-4 right-4 h-3 w-3">
+            <div
+                id="ws-status-ping"
+                class="animate-ping absolute inline-flex h-full w-full rounded-full bg-red-500 opacity-75"
+            ></div>
+            <div
+                id="ws-status-circle"
+                class="rounded-full relative inline-flex h-3 w-3 bg-red-500"
+            ></div>
+        </div>
+    }
+}
+
 #[component]
 pub fn Header(title: String) -> impl IntoView {
     view! {
@@ -20,6 +20,39 @@ impl TemplateRenderer {
     }
 }
 
+/// Format a number of minutes as hours and minutes, e.g. `7h 30m`
+pub fn format_minutes(minutes: i64) -> String {
+    format!("{}h {:02}m", minutes / 60, minutes % 60)
+}
+
+/// Format an amount in cents as dollars, e.g. `$1234.50`
+pub fn format_cents(cents: i64) -> String {
+    let sign = if cents < 0 { "-" } else { "" };
+    format!("{}${}.{:02}", sign, cents.abs() / 100, cents.abs() % 100)
+}
+
+/// Format a change in minutes with its sign, e.g. `+7h 30m`
+pub fn format_signed_minutes(minutes: i64) -> String {
+    let sign = if minutes < 0 { "-" } else { "+" };
+    format!("{}{}", sign, format_minutes(minutes.abs()))
+}
+
+/// Format a change in cents with its sign, e.g. `-$1234.50`
+pub fn format_signed_cents(cents: i64) -> String {
+    let sign = if cents < 0 { "-" } else { "+" };
+    format!("{}{}", sign, format_cents(cents.abs()))
+}
+
+/// Format a distance in meters, switching to kilometers past one, e.g.
+/// `350 m` or `1.2 km`
+pub fn format_distance(meters: f64) -> String {
+    if meters < 1_000.0 {
+        format!("{:.0} m", meters)
+    } else {
+        format!("{:.1} km", meters / 1_000.0)
+    }
+}
+
 #[component]
 pub fn FormError(id: String, #[prop(optional)] children: Option<Children>) -> impl IntoView {
     view! {
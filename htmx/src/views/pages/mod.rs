@@ -1,28 +1,54 @@
+use chrono::{Datelike, Duration, NaiveDate};
 use leptos::*;
+use models::projections::{
+    attachment::Attachment,
+    certification::Certification,
+    client::Client,
+    cost_code::CostCode,
+    crew_assignment::CrewWeek,
+    daily_log::{DailyLog, DailyLogDelay},
+    employee::Employee,
+    equipment::Equipment,
+    incident::{Incident, IncidentEmployee, JobsiteSafety},
+    jobsite::Jobsite,
+    labor_classification::LaborTotals,
+    leave::{Leave, LeaveBalance},
+    material::Material,
+    pay_rate::PayRate,
+    project::Project,
+    shift::Shift,
+    timesheet::{Timesheet, TimesheetReview},
+};
 
 use crate::{
-    routes::ApiRoutes,
+    routes::{ApiRoutes, JobsiteClientMessage},
     views::{
-        components::jobsite::{JobsiteCreate, JobsiteEdit},
-        layouts,
+        components::{
+            certification::{CertificationAlerts, CertificationCreate, CertificationList},
+            client::{ClientCreate, ClientList},
+            crew_assignment::{CrewAssignmentCreate, CrewCalendar},
+            daily_log::{DailyLogCreate, DailyLogList},
+            employee::{EmployeeCreate, EmployeeList},
+            equipment::{EquipmentCreate, EquipmentList},
+            incident::{IncidentCreate, IncidentList, SafetyBoard},
+            jobsite::{JobsiteCreate, JobsiteEdit},
+            kiosk::KioskPunch,
+            leave::{LeaveBalances, LeaveCreate, LeaveList},
+            material::{MaterialCreate, MaterialList},
+            pay_rate::{PayRateCreate, PayRateList},
+            project::ProjectCreate,
+            shift::JobsiteClockInForm,
+            timesheet::{TimesheetReviewQueue, TimesheetShiftRow, TimesheetStatusPanel},
+        },
+        format_minutes, layouts,
     },
 };
 
 #[component]
-pub fn Landing() -> impl IntoView {
+pub fn Landing(projects: Vec<Project>, time_zone: String) -> impl IntoView {
     view! {
         <layouts::MainLayout title=String::from("ESRS")>
             <layouts::GradientBody>
-                <div id="ws-status-indicator" class="fixed top-4 right-4 h-3 w-3">
-                    <div
-                        id="ws-status-ping"
-                        class="animate-ping absolute inline-flex h-full w-full rounded-full bg-red-500 opacity-75"
-                    ></div>
-                    <div
-                        id="ws-status-circle"
-                        class="rounded-full relative inline-flex h-3 w-3 bg-red-500"
-                    ></div>
-                </div>
                 <div class="relative flex flex-row divide-x divide-orange-500 justify-around border-gray-800 backdrop-blur bg-slate-700/70 p-8 rounded-lg shadow-lg w-3/4 h-3/4">
                     <div class="w-1/2 mx-4 flex flex-col">
                         <div class="text-center mb-4">
@@ -33,7 +59,7 @@ pub fn Landing() -> impl IntoView {
                                 Jobsite
                             </span>
                         </div>
-                        <JobsiteCreate />
+                        <JobsiteCreate projects=projects time_zone />
                         <div
                             class="flex-grow overflow-auto"
                             hx-get=ApiRoutes::get_jobsite_list()
@@ -49,6 +75,560 @@ pub fn Landing() -> impl IntoView {
     }
 }
 
+#[component]
+pub fn Clients(clients: Vec<Client>, projects: Vec<Project>) -> impl IntoView {
+    view! {
+        <layouts::MainLayout title=String::from("ESRS - Clients")>
+            <layouts::GradientBody>
+                <div class="relative flex flex-row divide-x divide-orange-500 justify-around border-gray-800 backdrop-blur bg-slate-700/70 p-8 rounded-lg shadow-lg w-3/4 h-3/4">
+                    <div class="w-1/2 mx-4 flex flex-col">
+                        <div class="text-center mb-4">
+                            <span
+                                class="text-orange-700 text-3xl font-bold"
+                                style="font-family: 'Roboto Slab', serif;"
+                            >
+                                Clients
+                            </span>
+                        </div>
+                        <ClientCreate />
+                        <div class="flex-grow overflow-auto">
+                            <ClientList clients=clients.clone() projects=projects />
+                        </div>
+                    </div>
+                    <div class="w-1/2 px-4 flex flex-col">
+                        <div class="text-center mb-4">
+                            <span
+                                class="text-orange-700 text-3xl font-bold"
+                                style="font-family: 'Roboto Slab', serif;"
+                            >
+                                Projects
+                            </span>
+                        </div>
+                        <ProjectCreate clients=clients />
+                    </div>
+                </div>
+            </layouts::GradientBody>
+        </layouts::MainLayout>
+    }
+}
+
+#[component]
+pub fn Schedule(week: CrewWeek, employees: Vec<Employee>, jobsites: Vec<Jobsite>) -> impl IntoView {
+    let week_start = week.week_start;
+    let ws_vals =
+        serde_json::to_string(&JobsiteClientMessage::WatchSchedule { week_start }).unwrap();
+
+    view! {
+        <layouts::MainLayout title=String::from("ESRS - Schedule")>
+            <layouts::GradientBody>
+                <div class="relative flex flex-col border-gray-800 backdrop-blur bg-slate-700/70 p-8 rounded-lg shadow-lg w-11/12 h-3/4">
+                    <div class="text-center mb-4">
+                        <span
+                            class="text-orange-700 text-3xl font-bold"
+                            style="font-family: 'Roboto Slab', serif;"
+                        >
+                            Schedule
+                        </span>
+                    </div>
+                    <div class="flex flex-row justify-between text-white mb-4">
+                        <a
+                            class="hover:text-orange-500"
+                            href=ApiRoutes::get_schedule_week_page(week_start - Duration::days(7))
+                        >
+                            Previous
+                        </a>
+                        <span>
+                            {format!(
+                                "{} - {}",
+                                week_start.format("%b %-d"),
+                                Timesheet::week_end_of(week_start).format("%b %-d, %Y")
+                            )}
+                        </span>
+                        <a
+                            class="hover:text-orange-500"
+                            href=ApiRoutes::get_schedule_week_page(week_start + Duration::days(7))
+                        >
+                            Next
+                        </a>
+                    </div>
+                    <CrewAssignmentCreate employees=employees jobsites=jobsites.clone() />
+                    <div ws-send hx-vals=ws_vals hx-trigger="load"></div>
+                    <div class="flex-grow overflow-auto mt-4">
+                        <CrewCalendar week=week jobsites=jobsites />
+                    </div>
+                </div>
+            </layouts::GradientBody>
+        </layouts::MainLayout>
+    }
+}
+
+#[component]
+pub fn DailyLogs(
+    jobsite: Jobsite,
+    logs: Vec<DailyLog>,
+    delays: Vec<DailyLogDelay>,
+    attachments: Vec<Attachment>,
+    today: NaiveDate,
+) -> impl IntoView {
+    let jobsite_id = jobsite.id;
+    let ws_vals =
+        serde_json::to_string(&JobsiteClientMessage::WatchDailyLogs { jobsite_id }).unwrap();
+
+    view! {
+        <layouts::MainLayout title=String::from("ESRS - Daily Logs")>
+            <layouts::GradientBody>
+                <div class="relative flex flex-col border-gray-800 backdrop-blur bg-slate-700/70 p-8 rounded-lg shadow-lg w-3/4 h-3/4">
+                    <div class="text-center mb-4">
+                        <span
+                            class="text-orange-700 text-3xl font-bold"
+                            style="font-family: 'Roboto Slab', serif;"
+                        >
+                            {format!("{} Daily Logs", jobsite.name)}
+                        </span>
+                    </div>
+                    <DailyLogCreate jobsite_id=jobsite_id today=today />
+                    <div ws-send hx-vals=ws_vals hx-trigger="load"></div>
+                    <div class="flex-grow overflow-auto mt-4">
+                        <DailyLogList
+                            jobsite_id=jobsite_id
+                            logs=logs
+                            delays=delays
+                            attachments=attachments
+                        />
+                    </div>
+                </div>
+            </layouts::GradientBody>
+        </layouts::MainLayout>
+    }
+}
+
+#[component]
+pub fn Employees(
+    employees: Vec<Employee>,
+    jobsites: Vec<Jobsite>,
+    trades: Vec<String>,
+    pay_rates: Vec<PayRate>,
+    today: NaiveDate,
+) -> impl IntoView {
+    view! {
+        <layouts::MainLayout title=String::from("ESRS - Employees")>
+            <layouts::GradientBody>
+                <div class="relative flex flex-row divide-x divide-orange-500 justify-around border-gray-800 backdrop-blur bg-slate-700/70 p-8 rounded-lg shadow-lg w-3/4 h-3/4">
+                    <div class="w-1/2 mx-4 flex flex-col">
+                        <div class="text-center mb-4">
+                            <span
+                                class="text-orange-700 text-3xl font-bold"
+                                style="font-family: 'Roboto Slab', serif;"
+                            >
+                                Employees
+                            </span>
+                        </div>
+                        <EmployeeCreate />
+                        <div class="flex-grow overflow-auto">
+                            <EmployeeList employees=employees.clone() today=today />
+                        </div>
+                    </div>
+                    <div class="w-1/2 px-4 flex flex-col">
+                        <div class="text-center mb-4">
+                            <span
+                                class="text-orange-700 text-3xl font-bold"
+                                style="font-family: 'Roboto Slab', serif;"
+                            >
+                                Pay Rates
+                            </span>
+                        </div>
+                        <PayRateCreate employees=employees jobsites=jobsites trades=trades />
+                        <div class="flex-grow overflow-auto">
+                            <PayRateList pay_rates=pay_rates />
+                        </div>
+                    </div>
+                </div>
+            </layouts::GradientBody>
+        </layouts::MainLayout>
+    }
+}
+
+#[component]
+pub fn Certifications(
+    certifications: Vec<Certification>,
+    alerts: Vec<Certification>,
+    employees: Vec<Employee>,
+    today: NaiveDate,
+) -> impl IntoView {
+    view! {
+        <layouts::MainLayout title=String::from("ESRS - Certifications")>
+            <layouts::GradientBody>
+                <div class="relative flex flex-row divide-x divide-orange-500 justify-around border-gray-800 backdrop-blur bg-slate-700/70 p-8 rounded-lg shadow-lg w-3/4 h-3/4">
+                    <div class="w-2/3 mx-4 flex flex-col">
+                        <div class="text-center mb-4">
+                            <span
+                                class="text-orange-700 text-3xl font-bold"
+                                style="font-family: 'Roboto Slab', serif;"
+                            >
+                                Certifications
+                            </span>
+                        </div>
+                        <CertificationCreate employees=employees today=today />
+                        <div class="flex-grow overflow-auto">
+                            <CertificationList certifications=certifications today=today />
+                        </div>
+                    </div>
+                    <div class="w-1/3 px-4 flex flex-col">
+                        <div class="text-center mb-4">
+                            <span
+                                class="text-orange-700 text-3xl font-bold"
+                                style="font-family: 'Roboto Slab', serif;"
+                            >
+                                Expiring
+                            </span>
+                        </div>
+                        <div class="flex-grow overflow-auto">
+                            <CertificationAlerts alerts=alerts today=today />
+                        </div>
+                    </div>
+                </div>
+            </layouts::GradientBody>
+        </layouts::MainLayout>
+    }
+}
+
+#[component]
+pub fn EquipmentCatalog(
+    equipment: Vec<Equipment>,
+    jobsites: Vec<Jobsite>,
+    employees: Vec<Employee>,
+) -> impl IntoView {
+    view! {
+        <layouts::MainLayout title=String::from("ESRS - Equipment")>
+            <layouts::GradientBody>
+                <div class="relative flex flex-row divide-x divide-orange-500 justify-around border-gray-800 backdrop-blur bg-slate-700/70 p-8 rounded-lg shadow-lg w-3/4 h-3/4">
+                    <div class="w-full mx-4 flex flex-col">
+                        <div class="text-center mb-4">
+                            <span
+                                class="text-orange-700 text-3xl font-bold"
+                                style="font-family: 'Roboto Slab', serif;"
+                            >
+                                Equipment
+                            </span>
+                        </div>
+                        <EquipmentCreate />
+                        <div class="flex-grow overflow-auto">
+                            <EquipmentList equipment=equipment jobsites=jobsites employees=employees />
+                        </div>
+                    </div>
+                </div>
+            </layouts::GradientBody>
+        </layouts::MainLayout>
+    }
+}
+
+#[component]
+pub fn Materials(materials: Vec<Material>) -> impl IntoView {
+    view! {
+        <layouts::MainLayout title=String::from("ESRS - Materials")>
+            <layouts::GradientBody>
+                <div class="relative flex flex-row divide-x divide-orange-500 justify-around border-gray-800 backdrop-blur bg-slate-700/70 p-8 rounded-lg shadow-lg w-3/4 h-3/4">
+                    <div class="w-full mx-4 flex flex-col">
+                        <div class="text-center mb-4">
+                            <span
+                                class="text-orange-700 text-3xl font-bold"
+                                style="font-family: 'Roboto Slab', serif;"
+                            >
+                                Materials
+                            </span>
+                        </div>
+                        <MaterialCreate />
+                        <div class="flex-grow overflow-auto">
+                            <MaterialList materials=materials />
+                        </div>
+                    </div>
+                </div>
+            </layouts::GradientBody>
+        </layouts::MainLayout>
+    }
+}
+
+#[component]
+pub fn LeaveRequests(
+    leave: Vec<Leave>,
+    balances: Vec<LeaveBalance>,
+    employees: Vec<Employee>,
+    today: NaiveDate,
+) -> impl IntoView {
+    view! {
+        <layouts::MainLayout title=String::from("ESRS - Time Off")>
+            <layouts::GradientBody>
+                <div class="relative flex flex-row divide-x divide-orange-500 justify-around border-gray-800 backdrop-blur bg-slate-700/70 p-8 rounded-lg shadow-lg w-3/4 h-3/4">
+                    <div class="w-2/3 mx-4 flex flex-col">
+                        <div class="text-center mb-4">
+                            <span
+                                class="text-orange-700 text-3xl font-bold"
+                                style="font-family: 'Roboto Slab', serif;"
+                            >
+                                Time Off
+                            </span>
+                        </div>
+                        <LeaveCreate employees=employees.clone() today=today />
+                        <div class="flex-grow overflow-auto">
+                            <LeaveList leave=leave />
+                        </div>
+                    </div>
+                    <div class="w-1/3 px-4 flex flex-col">
+                        <div class="text-center mb-4">
+                            <span
+                                class="text-orange-700 text-3xl font-bold"
+                                style="font-family: 'Roboto Slab', serif;"
+                            >
+                                Balances
+                            </span>
+                        </div>
+                        <div class="flex-grow overflow-auto">
+                            <LeaveBalances employees=employees balances=balances year=today.year() />
+                        </div>
+                    </div>
+                </div>
+            </layouts::GradientBody>
+        </layouts::MainLayout>
+    }
+}
+
+#[component]
+pub fn EmployeeTimesheet(
+    employee: Employee,
+    week_start: NaiveDate,
+    timesheet: Option<Timesheet>,
+    shifts: Vec<Shift>,
+    totals: LaborTotals,
+    /// Approved paid leave taken during the week
+    leave_minutes: i64,
+) -> impl IntoView {
+    let locked = timesheet
+        .as_ref()
+        .map(|timesheet| timesheet.status.is_locked())
+        .unwrap_or(false);
+    let minutes_worked: i64 = shifts
+        .iter()
+        .filter_map(|shift| {
+            shift
+                .clocked_out_at
+                .map(|clocked_out_at| (clocked_out_at - shift.clocked_in_at).num_minutes())
+        })
+        .sum();
+
+    view! {
+        <layouts::MainLayout title=format!("ESRS - {} Timesheet", employee.name)>
+            <layouts::GradientBody>
+                <div class="relative flex flex-col border-gray-800 backdrop-blur bg-slate-700/70 p-8 rounded-lg shadow-lg w-1/2 h-3/4">
+                    <div class="text-center mb-4">
+                        <span
+                            class="text-orange-700 text-3xl font-bold"
+                            style="font-family: 'Roboto Slab', serif;"
+                        >
+                            {employee.name}
+                        </span>
+                    </div>
+                    <div class="flex flex-row justify-between text-white">
+                        <a
+                            class="hover:text-orange-500"
+                            href=ApiRoutes::get_timesheet_page(employee.id, week_start - Duration::days(7))
+                        >
+                            Previous
+                        </a>
+                        <span>
+                            {format!(
+                                "{} - {}, {}",
+                                week_start.format("%b %-d"),
+                                Timesheet::week_end_of(week_start).format("%b %-d"),
+                                format_minutes(minutes_worked)
+                            )}
+                        </span>
+                        <a
+                            class="hover:text-orange-500"
+                            href=ApiRoutes::get_timesheet_page(employee.id, week_start + Duration::days(7))
+                        >
+                            Next
+                        </a>
+                    </div>
+                    <div class="flex flex-row justify-around text-white text-sm">
+                        <span>{format!("Regular {}", format_minutes(totals.regular_minutes))}</span>
+                        <span>{format!("Overtime {}", format_minutes(totals.overtime_minutes))}</span>
+                        <span>{format!("Double Time {}", format_minutes(totals.double_time_minutes))}</span>
+                        <span>{format!("Paid Leave {}", format_minutes(leave_minutes))}</span>
+                    </div>
+                    <TimesheetStatusPanel employee_id=employee.id week_start=week_start timesheet=timesheet />
+                    <div
+                        hx-get=ApiRoutes::get_employee_calendar_link(employee.id)
+                        hx-trigger="load"
+                        hx-swap="outerHTML"
+                    ></div>
+                    <div class="flex-grow overflow-auto">
+                        {shifts.into_iter().map(|shift| view! { <TimesheetShiftRow shift=shift locked=locked /> }).collect::<Vec<_>>().into_view()}
+                    </div>
+                </div>
+            </layouts::GradientBody>
+        </layouts::MainLayout>
+    }
+}
+
+#[component]
+pub fn Timesheets(reviews: Vec<TimesheetReview>) -> impl IntoView {
+    view! {
+        <layouts::MainLayout title=String::from("ESRS - Timesheet Review")>
+            <layouts::GradientBody>
+                <div class="relative flex flex-col border-gray-800 backdrop-blur bg-slate-700/70 p-8 rounded-lg shadow-lg w-1/2 h-3/4">
+                    <div class="text-center mb-4">
+                        <span
+                            class="text-orange-700 text-3xl font-bold"
+                            style="font-family: 'Roboto Slab', serif;"
+                        >
+                            Timesheet Review
+                        </span>
+                    </div>
+                    <div class="flex-grow overflow-auto">
+                        <TimesheetReviewQueue reviews=reviews />
+                    </div>
+                </div>
+            </layouts::GradientBody>
+        </layouts::MainLayout>
+    }
+}
+
+#[component]
+pub fn Incidents(
+    incidents: Vec<Incident>,
+    involved: Vec<IncidentEmployee>,
+    safety: Vec<JobsiteSafety>,
+    jobsites: Vec<Jobsite>,
+    employees: Vec<Employee>,
+    today: NaiveDate,
+) -> impl IntoView {
+    view! {
+        <layouts::MainLayout title=String::from("ESRS - Safety")>
+            <layouts::GradientBody>
+                <div class="relative flex flex-row divide-x divide-orange-500 justify-around border-gray-800 backdrop-blur bg-slate-700/70 p-8 rounded-lg shadow-lg w-3/4 h-3/4">
+                    <div class="w-2/3 mx-4 flex flex-col">
+                        <div class="text-center mb-4">
+                            <span
+                                class="text-orange-700 text-3xl font-bold"
+                                style="font-family: 'Roboto Slab', serif;"
+                            >
+                                Incidents
+                            </span>
+                        </div>
+                        <IncidentCreate jobsites=jobsites employees=employees.clone() today=today />
+                        <div class="flex-grow overflow-auto">
+                            <IncidentList incidents=incidents involved=involved employees=employees />
+                        </div>
+                    </div>
+                    <div class="w-1/3 px-4 flex flex-col">
+                        <div class="text-center mb-4">
+                            <span
+                                class="text-orange-700 text-3xl font-bold"
+                                style="font-family: 'Roboto Slab', serif;"
+                            >
+                                Days Since
+                            </span>
+                        </div>
+                        <div class="flex-grow overflow-auto">
+                            <SafetyBoard safety=safety today=today />
+                        </div>
+                    </div>
+                </div>
+            </layouts::GradientBody>
+        </layouts::MainLayout>
+    }
+}
+
+#[component]
+pub fn JobsiteClockIn(
+    jobsite: Jobsite,
+    available: Vec<Employee>,
+    cost_codes: Vec<CostCode>,
+    expires: i64,
+    token: String,
+) -> impl IntoView {
+    view! {
+        <layouts::MainLayout title=format!("ESRS - Clock In at {}", jobsite.name)>
+            <layouts::GradientBody>
+                <div class="relative border-gray-800 backdrop-blur bg-slate-700/70 p-8 rounded-lg shadow-lg w-full sm:w-1/2">
+                    <div class="text-center mb-4">
+                        <span
+                            class="text-orange-700 text-3xl font-bold"
+                            style="font-family: 'Roboto Slab', serif;"
+                        >
+                            {jobsite.name.clone()}
+                        </span>
+                    </div>
+                    <JobsiteClockInForm jobsite available cost_codes expires token />
+                </div>
+            </layouts::GradientBody>
+        </layouts::MainLayout>
+    }
+}
+
+#[component]
+pub fn ClockInLinkExpired() -> impl IntoView {
+    view! {
+        <layouts::MainLayout title=String::from("ESRS - Clock In")>
+            <layouts::GradientBody>
+                <div class="relative bg-white p-8 rounded-lg shadow-lg w-96">
+                    <div class="text-center mb-4">
+                        <span
+                            class="text-orange-700 text-3xl font-bold"
+                            style="font-family: 'Roboto Slab', serif;"
+                        >
+                            ESRS
+                        </span>
+                    </div>
+                    <p>This QR code has expired. Ask a supervisor to print a new one for the site.</p>
+                </div>
+            </layouts::GradientBody>
+        </layouts::MainLayout>
+    }
+}
+
+#[component]
+pub fn Kiosk(jobsite: Jobsite, employees: Vec<Employee>) -> impl IntoView {
+    view! {
+        <layouts::MainLayout title=format!("ESRS - {} Kiosk", jobsite.name)>
+            <layouts::KioskBody>
+                <div class="relative border-gray-800 backdrop-blur bg-slate-700/70 p-8 rounded-lg shadow-lg w-1/2">
+                    <div class="text-center mb-4">
+                        <span
+                            class="text-orange-700 text-3xl font-bold"
+                            style="font-family: 'Roboto Slab', serif;"
+                        >
+                            {jobsite.name}
+                        </span>
+                    </div>
+                    <KioskPunch jobsite_id=jobsite.id employees=employees />
+                </div>
+            </layouts::KioskBody>
+        </layouts::MainLayout>
+    }
+}
+
+#[component]
+pub fn KioskNotRegistered() -> impl IntoView {
+    view! {
+        <layouts::MainLayout title=String::from("ESRS - Kiosk")>
+            <layouts::KioskBody>
+                <div class="relative bg-white p-8 rounded-lg shadow-lg w-96">
+                    <div class="text-center mb-4">
+                        <span
+                            class="text-orange-700 text-3xl font-bold"
+                            style="font-family: 'Roboto Slab', serif;"
+                        >
+                            ESRS
+                        </span>
+                    </div>
+                    <p>This device is not a kiosk for this jobsite. A supervisor can set it up from the jobsite page.</p>
+                </div>
+            </layouts::KioskBody>
+        </layouts::MainLayout>
+    }
+}
+
 #[component]
 pub fn NotFound() -> impl IntoView {
     view! {
@@ -12,14 +12,14 @@ pub async fn create_jobsite(data: JobsiteCreateData) -> Result<Uuid, ServerFnErr
     use actix_web::web::Data;
     use leptos_actix::extract;
     use sqlx::PgPool;
-    use models::{events::jobsite::JobsiteCreated, projections::jobsite::Jobsite};
+    use models::{events::jobsite::JobsiteCreated, local_time::default_time_zone, projections::jobsite::Jobsite};
     use eventstore::EventData;
     
     let (db_pool, eventstore): (Data<PgPool>, Data<eventstore::Client>) = extract().await?;
 
     let mut transaction = db_pool.begin().await.unwrap();
 
-    match Jobsite::get_by_name(&mut transaction, data.name.clone()).await? {
+    match Jobsite::get_by_name(&mut transaction, None, data.name.clone()).await? {
         Some(_) => {
             return Err(ServerFnError::new("A Jobsite already exists with this name"));
         }
@@ -32,6 +32,8 @@ pub async fn create_jobsite(data: JobsiteCreateData) -> Result<Uuid, ServerFnErr
     let create_event = JobsiteCreated {
         id: jobsite_id,
         name: data.name.clone(),
+        project_id: None,
+        time_zone: default_time_zone(),
     };
 
     let event = EventData::json("JobsiteCreated", &create_event)
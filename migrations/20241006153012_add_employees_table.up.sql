@@ -0,0 +1,5 @@
+-- Add up migration script here
+CREATE TABLE employees (
+  id UUID PRIMARY KEY,
+  name VARCHAR(255) NOT NULL
+);
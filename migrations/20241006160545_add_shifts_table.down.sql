@@ -0,0 +1,6 @@
+-- Add down migration script here
+DROP TABLE IF EXISTS shifts;
+
+DROP TABLE IF EXISTS employee_hours;
+ALTER TABLE employee_hours_legacy RENAME TO employee_hours;
+ALTER INDEX employee_hours_legacy_pkey RENAME TO employee_hours_pkey;
@@ -0,0 +1,26 @@
+-- Add up migration script here
+CREATE TABLE shifts (
+  id UUID PRIMARY KEY,
+  employee_id UUID NOT NULL,
+  jobsite_id UUID NOT NULL,
+  clocked_in_at TIMESTAMPTZ NOT NULL,
+  clocked_out_at TIMESTAMPTZ
+);
+
+-- The write model keeps employees to one open shift, the read model only
+-- records what happened
+CREATE INDEX shifts_open_employee_id_idx ON shifts (employee_id) WHERE clocked_out_at IS NULL;
+CREATE INDEX shifts_jobsite_id_idx ON shifts (jobsite_id);
+
+-- Worked hours are now derived from closed shifts. The hours recorded
+-- before shifts existed are kept under a new name.
+ALTER TABLE employee_hours RENAME TO employee_hours_legacy;
+ALTER INDEX employee_hours_pkey RENAME TO employee_hours_legacy_pkey;
+
+CREATE TABLE employee_hours (
+  shift_id UUID PRIMARY KEY,
+  employee_id UUID NOT NULL,
+  jobsite_id UUID NOT NULL,
+  work_date DATE NOT NULL,
+  minutes_worked BIGINT NOT NULL
+);
@@ -0,0 +1,12 @@
+-- Add up migration script here
+CREATE TABLE timesheets (
+  employee_id UUID NOT NULL,
+  week_start DATE NOT NULL,
+  status VARCHAR(255) NOT NULL,
+  rejection_reason TEXT,
+  submitted_at TIMESTAMPTZ,
+  reviewed_at TIMESTAMPTZ,
+  PRIMARY KEY (employee_id, week_start)
+);
+
+CREATE INDEX timesheets_status_idx ON timesheets (status);
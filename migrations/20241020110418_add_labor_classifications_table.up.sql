@@ -0,0 +1,13 @@
+-- Add up migration script here
+CREATE TABLE labor_classifications (
+  shift_id UUID PRIMARY KEY,
+  employee_id UUID NOT NULL,
+  jobsite_id UUID NOT NULL,
+  work_date DATE NOT NULL,
+  regular_minutes BIGINT NOT NULL,
+  overtime_minutes BIGINT NOT NULL,
+  double_time_minutes BIGINT NOT NULL
+);
+
+CREATE INDEX labor_classifications_employee_id_work_date_idx ON labor_classifications (employee_id, work_date);
+CREATE INDEX labor_classifications_jobsite_id_idx ON labor_classifications (jobsite_id);
@@ -0,0 +1,4 @@
+-- Add down migration script here
+DROP TABLE IF EXISTS jobsite_labor_costs;
+DROP TABLE IF EXISTS pay_rates;
+ALTER TABLE employees DROP COLUMN IF EXISTS trade;
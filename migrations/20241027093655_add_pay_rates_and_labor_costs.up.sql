@@ -0,0 +1,30 @@
+-- Add up migration script here
+ALTER TABLE employees ADD COLUMN trade VARCHAR(255);
+
+-- A rate applies to either a single employee or everyone in a trade, and
+-- optionally only at a single jobsite
+CREATE TABLE pay_rates (
+  id UUID PRIMARY KEY,
+  employee_id UUID,
+  trade VARCHAR(255),
+  jobsite_id UUID,
+  hourly_rate_cents BIGINT NOT NULL,
+  effective_date DATE NOT NULL,
+  set_at TIMESTAMPTZ NOT NULL,
+  CHECK (employee_id IS NOT NULL OR trade IS NOT NULL)
+);
+
+CREATE INDEX pay_rates_employee_id_idx ON pay_rates (employee_id);
+CREATE INDEX pay_rates_trade_idx ON pay_rates (trade);
+
+CREATE TABLE jobsite_labor_costs (
+  jobsite_id UUID NOT NULL,
+  work_date DATE NOT NULL,
+  regular_minutes BIGINT NOT NULL,
+  overtime_minutes BIGINT NOT NULL,
+  double_time_minutes BIGINT NOT NULL,
+  -- Minutes worked by someone without a pay rate, not included in the cost
+  unrated_minutes BIGINT NOT NULL,
+  cost_cents BIGINT NOT NULL,
+  PRIMARY KEY (jobsite_id, work_date)
+);
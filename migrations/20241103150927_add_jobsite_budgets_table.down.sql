@@ -0,0 +1,3 @@
+-- Add down migration script here
+DROP TABLE IF EXISTS jobsite_budget_alerts;
+DROP TABLE IF EXISTS jobsite_budgets;
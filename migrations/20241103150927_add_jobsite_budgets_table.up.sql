@@ -0,0 +1,31 @@
+-- Add up migration script here
+CREATE TABLE jobsite_budgets (
+  -- The id of the latest JobsiteBudgetSet event, resetting a budget re-arms its alerts
+  id UUID PRIMARY KEY,
+  jobsite_id UUID NOT NULL,
+  cost_code VARCHAR(255),
+  budget_minutes BIGINT,
+  budget_cents BIGINT,
+  actual_minutes BIGINT NOT NULL DEFAULT 0,
+  actual_cents BIGINT NOT NULL DEFAULT 0,
+  set_at TIMESTAMPTZ NOT NULL
+);
+
+-- One budget per jobsite, and per cost code within it
+CREATE UNIQUE INDEX jobsite_budgets_jobsite_id_cost_code_idx ON jobsite_budgets (jobsite_id, COALESCE(cost_code, ''));
+
+CREATE TABLE jobsite_budget_alerts (
+  budget_id UUID NOT NULL,
+  jobsite_id UUID NOT NULL,
+  cost_code VARCHAR(255),
+  measure VARCHAR(255) NOT NULL,
+  -- What was budgeted for the measure when the threshold was reached, a
+  -- change order that moves it re-arms the measure's alerts
+  budgeted BIGINT NOT NULL,
+  threshold INT NOT NULL,
+  percent_used BIGINT NOT NULL,
+  reached_at TIMESTAMPTZ NOT NULL,
+  PRIMARY KEY (budget_id, measure, budgeted, threshold)
+);
+
+CREATE INDEX jobsite_budget_alerts_jobsite_id_idx ON jobsite_budget_alerts (jobsite_id);
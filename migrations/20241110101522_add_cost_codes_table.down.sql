@@ -0,0 +1,5 @@
+-- Add down migration script here
+ALTER TABLE labor_classifications DROP COLUMN IF EXISTS cost_cents;
+DROP INDEX IF EXISTS shifts_cost_code_id_idx;
+ALTER TABLE shifts DROP COLUMN IF EXISTS cost_code_id;
+DROP TABLE IF EXISTS cost_codes;
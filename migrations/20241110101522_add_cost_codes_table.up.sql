@@ -0,0 +1,21 @@
+-- Add up migration script here
+CREATE TABLE cost_codes (
+  id UUID PRIMARY KEY,
+  jobsite_id UUID NOT NULL,
+  -- Tasks are cost codes nested under another code on the same jobsite
+  parent_id UUID REFERENCES cost_codes (id),
+  code VARCHAR(50) NOT NULL,
+  name VARCHAR(255) NOT NULL,
+  added_at TIMESTAMPTZ NOT NULL,
+  retired_at TIMESTAMPTZ
+);
+
+CREATE UNIQUE INDEX cost_codes_jobsite_id_code_idx ON cost_codes (jobsite_id, code);
+CREATE INDEX cost_codes_parent_id_idx ON cost_codes (parent_id);
+
+ALTER TABLE shifts ADD COLUMN cost_code_id UUID;
+CREATE INDEX shifts_cost_code_id_idx ON shifts (cost_code_id);
+
+-- Each shift is priced individually so costs can be rolled up by cost code,
+-- NULL when no pay rate applies
+ALTER TABLE labor_classifications ADD COLUMN cost_cents BIGINT;
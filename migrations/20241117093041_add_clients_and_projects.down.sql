@@ -0,0 +1,6 @@
+-- Add down migration script here
+DROP INDEX IF EXISTS jobsites_project_id_name_idx;
+ALTER TABLE jobsites ADD CONSTRAINT jobsites_name_key UNIQUE (name);
+ALTER TABLE jobsites DROP COLUMN IF EXISTS project_id;
+DROP TABLE IF EXISTS projects;
+DROP TABLE IF EXISTS clients;
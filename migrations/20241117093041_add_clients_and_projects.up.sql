@@ -0,0 +1,19 @@
+-- Add up migration script here
+CREATE TABLE clients (
+  id UUID PRIMARY KEY,
+  name VARCHAR(255) UNIQUE NOT NULL
+);
+
+CREATE TABLE projects (
+  id UUID PRIMARY KEY,
+  client_id UUID NOT NULL REFERENCES clients (id),
+  name VARCHAR(255) NOT NULL,
+  UNIQUE (client_id, name)
+);
+
+ALTER TABLE jobsites ADD COLUMN project_id UUID REFERENCES projects (id);
+
+-- Jobsite names only need to be unique within their project, jobsites not
+-- yet assigned to a project share a single namespace
+ALTER TABLE jobsites DROP CONSTRAINT jobsites_name_key;
+CREATE UNIQUE INDEX jobsites_project_id_name_idx ON jobsites (COALESCE(project_id, '00000000-0000-0000-0000-000000000000'), name);
@@ -0,0 +1,4 @@
+-- Add down migration script here
+DROP TABLE IF EXISTS crew_assignments;
+
+ALTER TABLE employees DROP COLUMN IF EXISTS terminated_on;
@@ -0,0 +1,16 @@
+-- Add up migration script here
+ALTER TABLE employees ADD COLUMN terminated_on DATE;
+
+CREATE TABLE crew_assignments (
+  id UUID PRIMARY KEY,
+  employee_id UUID NOT NULL,
+  jobsite_id UUID NOT NULL,
+  -- Both dates are inclusive
+  start_date DATE NOT NULL,
+  end_date DATE NOT NULL,
+  assigned_at TIMESTAMPTZ NOT NULL,
+  unassigned_at TIMESTAMPTZ
+);
+
+CREATE INDEX crew_assignments_employee_id_idx ON crew_assignments (employee_id, start_date, end_date);
+CREATE INDEX crew_assignments_jobsite_id_idx ON crew_assignments (jobsite_id);
@@ -0,0 +1,3 @@
+-- Add down migration script here
+DROP TABLE IF EXISTS daily_log_delays;
+DROP TABLE IF EXISTS daily_logs;
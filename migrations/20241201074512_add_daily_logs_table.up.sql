@@ -0,0 +1,25 @@
+-- Add up migration script here
+CREATE TABLE daily_logs (
+  jobsite_id UUID NOT NULL,
+  log_date DATE NOT NULL,
+  crew_count INT NOT NULL,
+  weather VARCHAR(255) NOT NULL,
+  notes TEXT,
+  saved_at TIMESTAMPTZ NOT NULL,
+  -- Submitted logs are locked against further changes
+  submitted_at TIMESTAMPTZ,
+  PRIMARY KEY (jobsite_id, log_date)
+);
+
+CREATE TABLE daily_log_delays (
+  id UUID PRIMARY KEY,
+  jobsite_id UUID NOT NULL,
+  log_date DATE NOT NULL,
+  cause VARCHAR(255) NOT NULL,
+  minutes_lost BIGINT NOT NULL,
+  reason TEXT NOT NULL,
+  recorded_at TIMESTAMPTZ NOT NULL,
+  FOREIGN KEY (jobsite_id, log_date) REFERENCES daily_logs (jobsite_id, log_date)
+);
+
+CREATE INDEX daily_log_delays_jobsite_id_log_date_idx ON daily_log_delays (jobsite_id, log_date);
@@ -0,0 +1,16 @@
+-- Add up migration script here
+CREATE TABLE attachments (
+  id UUID PRIMARY KEY,
+  jobsite_id UUID NOT NULL,
+  -- Set when the attachment belongs to a daily log rather than the jobsite
+  log_date DATE,
+  file_name VARCHAR(255) NOT NULL,
+  content_type VARCHAR(255) NOT NULL,
+  size_bytes BIGINT NOT NULL,
+  content_hash CHAR(64) NOT NULL,
+  thumbnail_hash CHAR(64),
+  added_at TIMESTAMPTZ NOT NULL,
+  removed_at TIMESTAMPTZ
+);
+
+CREATE INDEX attachments_jobsite_id_log_date_idx ON attachments (jobsite_id, log_date);
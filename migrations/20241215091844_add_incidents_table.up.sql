@@ -0,0 +1,25 @@
+-- Add up migration script here
+CREATE TABLE incidents (
+  id UUID PRIMARY KEY,
+  jobsite_id UUID NOT NULL,
+  severity VARCHAR(255) NOT NULL,
+  status VARCHAR(255) NOT NULL,
+  occurred_on DATE NOT NULL,
+  description TEXT NOT NULL,
+  findings TEXT,
+  corrective_action TEXT,
+  action_assignee_id UUID,
+  action_due_on DATE,
+  reported_at TIMESTAMPTZ NOT NULL,
+  investigated_at TIMESTAMPTZ,
+  action_assigned_at TIMESTAMPTZ,
+  closed_at TIMESTAMPTZ
+);
+
+CREATE INDEX incidents_jobsite_id_occurred_on_idx ON incidents (jobsite_id, occurred_on);
+
+CREATE TABLE incident_employees (
+  incident_id UUID NOT NULL REFERENCES incidents (id),
+  employee_id UUID NOT NULL,
+  PRIMARY KEY (incident_id, employee_id)
+);
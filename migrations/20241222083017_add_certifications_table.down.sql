@@ -0,0 +1,3 @@
+-- Add down migration script here
+DROP TABLE IF EXISTS jobsite_certifications;
+DROP TABLE IF EXISTS certifications;
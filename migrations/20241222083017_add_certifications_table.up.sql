@@ -0,0 +1,20 @@
+-- Add up migration script here
+CREATE TABLE certifications (
+  id UUID PRIMARY KEY,
+  employee_id UUID NOT NULL,
+  certification_type VARCHAR(255) NOT NULL,
+  certificate_number VARCHAR(255),
+  issued_on DATE NOT NULL,
+  expires_on DATE,
+  recorded_at TIMESTAMPTZ NOT NULL,
+  warned_at TIMESTAMPTZ,
+  expired_at TIMESTAMPTZ
+);
+
+CREATE INDEX certifications_employee_id_certification_type_idx ON certifications (employee_id, certification_type);
+
+CREATE TABLE jobsite_certifications (
+  jobsite_id UUID NOT NULL,
+  certification_type VARCHAR(255) NOT NULL,
+  PRIMARY KEY (jobsite_id, certification_type)
+);
@@ -0,0 +1,4 @@
+-- Add down migration script here
+DROP TABLE IF EXISTS leave_balances;
+DROP TABLE IF EXISTS leave_days;
+DROP TABLE IF EXISTS leave_requests;
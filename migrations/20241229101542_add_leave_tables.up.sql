@@ -0,0 +1,36 @@
+-- Add up migration script here
+CREATE TABLE leave_requests (
+  id UUID PRIMARY KEY,
+  employee_id UUID NOT NULL,
+  leave_type VARCHAR(255) NOT NULL,
+  start_date DATE NOT NULL,
+  end_date DATE NOT NULL,
+  minutes_per_day BIGINT NOT NULL,
+  total_minutes BIGINT NOT NULL,
+  note TEXT,
+  status VARCHAR(255) NOT NULL,
+  denial_reason TEXT,
+  requested_at TIMESTAMPTZ NOT NULL,
+  reviewed_at TIMESTAMPTZ
+);
+
+CREATE INDEX leave_requests_employee_id_idx ON leave_requests (employee_id);
+
+CREATE TABLE leave_days (
+  leave_id UUID NOT NULL,
+  employee_id UUID NOT NULL,
+  leave_date DATE NOT NULL,
+  minutes BIGINT NOT NULL,
+  PRIMARY KEY (leave_id, leave_date)
+);
+
+CREATE INDEX leave_days_employee_id_leave_date_idx ON leave_days (employee_id, leave_date);
+
+CREATE TABLE leave_balances (
+  employee_id UUID NOT NULL,
+  leave_type VARCHAR(255) NOT NULL,
+  year INTEGER NOT NULL,
+  pending_minutes BIGINT NOT NULL DEFAULT 0,
+  approved_minutes BIGINT NOT NULL DEFAULT 0,
+  PRIMARY KEY (employee_id, leave_type, year)
+);
@@ -0,0 +1,5 @@
+-- Add down migration script here
+DROP TABLE IF EXISTS material_consumptions;
+DROP TABLE IF EXISTS materials;
+DROP TABLE IF EXISTS equipment_checkouts;
+DROP TABLE IF EXISTS equipment;
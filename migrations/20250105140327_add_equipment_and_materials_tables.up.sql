@@ -0,0 +1,39 @@
+-- Add up migration script here
+CREATE TABLE equipment (
+  id UUID PRIMARY KEY,
+  name VARCHAR(255) NOT NULL,
+  serial_number VARCHAR(255),
+  added_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE TABLE equipment_checkouts (
+  id UUID PRIMARY KEY,
+  equipment_id UUID NOT NULL,
+  jobsite_id UUID NOT NULL,
+  employee_id UUID,
+  checked_out_at TIMESTAMPTZ NOT NULL,
+  returned_at TIMESTAMPTZ
+);
+
+-- A piece of equipment can only be out at one jobsite at a time
+CREATE UNIQUE INDEX equipment_checkouts_open_idx ON equipment_checkouts (equipment_id) WHERE returned_at IS NULL;
+CREATE INDEX equipment_checkouts_jobsite_id_idx ON equipment_checkouts (jobsite_id);
+
+CREATE TABLE materials (
+  id UUID PRIMARY KEY,
+  name VARCHAR(255) NOT NULL,
+  unit VARCHAR(255) NOT NULL,
+  unit_cost_cents BIGINT,
+  added_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE TABLE material_consumptions (
+  id UUID PRIMARY KEY,
+  material_id UUID NOT NULL,
+  jobsite_id UUID NOT NULL,
+  quantity DOUBLE PRECISION NOT NULL,
+  consumed_on DATE NOT NULL,
+  recorded_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX material_consumptions_jobsite_id_idx ON material_consumptions (jobsite_id);
@@ -0,0 +1,5 @@
+-- Add down migration script here
+ALTER TABLE jobsite_budgets
+  DROP COLUMN IF EXISTS change_order_cents,
+  DROP COLUMN IF EXISTS change_order_minutes;
+DROP TABLE IF EXISTS change_orders;
@@ -0,0 +1,22 @@
+-- Add up migration script here
+CREATE TABLE change_orders (
+  id UUID PRIMARY KEY,
+  jobsite_id UUID NOT NULL,
+  title VARCHAR(255) NOT NULL,
+  description TEXT,
+  amount_cents BIGINT NOT NULL,
+  minutes BIGINT NOT NULL,
+  status VARCHAR(255) NOT NULL,
+  rejection_reason TEXT,
+  drafted_at TIMESTAMPTZ NOT NULL,
+  submitted_at TIMESTAMPTZ,
+  approved_at TIMESTAMPTZ,
+  rejected_at TIMESTAMPTZ
+);
+
+CREATE INDEX change_orders_jobsite_id_idx ON change_orders (jobsite_id);
+
+-- What approved change orders add to a jobsite wide budget
+ALTER TABLE jobsite_budgets
+  ADD COLUMN change_order_minutes BIGINT NOT NULL DEFAULT 0,
+  ADD COLUMN change_order_cents BIGINT NOT NULL DEFAULT 0;
@@ -0,0 +1,7 @@
+-- Add down migration script here
+ALTER TABLE attachments DROP COLUMN IF EXISTS merged_from_id;
+ALTER TABLE crew_assignments DROP COLUMN IF EXISTS merged_from_id;
+ALTER TABLE labor_classifications DROP COLUMN IF EXISTS merged_from_id;
+ALTER TABLE employee_hours DROP COLUMN IF EXISTS merged_from_id;
+ALTER TABLE shifts DROP COLUMN IF EXISTS merged_from_id;
+ALTER TABLE jobsites DROP COLUMN IF EXISTS merged_into_id;
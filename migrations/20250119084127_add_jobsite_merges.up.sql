@@ -0,0 +1,11 @@
+-- Add up migration script here
+-- Set while a jobsite has been merged into another
+ALTER TABLE jobsites ADD COLUMN merged_into_id UUID;
+
+-- The jobsite a row was recorded against before a merge re-pointed it, so
+-- unmerging can send it back
+ALTER TABLE shifts ADD COLUMN merged_from_id UUID;
+ALTER TABLE employee_hours ADD COLUMN merged_from_id UUID;
+ALTER TABLE labor_classifications ADD COLUMN merged_from_id UUID;
+ALTER TABLE crew_assignments ADD COLUMN merged_from_id UUID;
+ALTER TABLE attachments ADD COLUMN merged_from_id UUID;
@@ -0,0 +1,3 @@
+-- Add down migration script here
+DROP TABLE IF EXISTS process_manager_states;
+ALTER TABLE jobsites DROP COLUMN IF EXISTS archived_at;
@@ -0,0 +1,9 @@
+-- Add up migration script here
+ALTER TABLE jobsites ADD COLUMN archived_at TIMESTAMPTZ;
+
+-- What each process manager has folded from the events it has seen so far,
+-- saved together with its snapshot position
+CREATE TABLE process_manager_states (
+  key VARCHAR(255) PRIMARY KEY,
+  state JSONB NOT NULL
+);
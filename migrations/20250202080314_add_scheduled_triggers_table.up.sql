@@ -0,0 +1,13 @@
+-- Add up migration script here
+-- Time triggers waiting to come due. Fired triggers are kept so that
+-- scheduling the same trigger again, say while replaying events, is a no-op.
+CREATE TABLE scheduled_triggers (
+  id UUID PRIMARY KEY,
+  key VARCHAR(255) NOT NULL UNIQUE,
+  trigger JSONB NOT NULL,
+  due_at TIMESTAMPTZ NOT NULL,
+  correlation_id UUID NOT NULL,
+  fired_at TIMESTAMPTZ
+);
+
+CREATE INDEX scheduled_triggers_due_at_idx ON scheduled_triggers (due_at) WHERE fired_at IS NULL;
@@ -0,0 +1,3 @@
+-- Add down migration script here
+ALTER TABLE shifts DROP COLUMN IF EXISTS time_zone;
+ALTER TABLE jobsites DROP COLUMN IF EXISTS time_zone;
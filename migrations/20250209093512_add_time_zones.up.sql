@@ -0,0 +1,4 @@
+-- Add up migration script here
+-- Days used to be counted in UTC, existing jobsites and shifts keep doing so
+ALTER TABLE jobsites ADD COLUMN time_zone VARCHAR(64) NOT NULL DEFAULT 'UTC';
+ALTER TABLE shifts ADD COLUMN time_zone VARCHAR(64) NOT NULL DEFAULT 'UTC';
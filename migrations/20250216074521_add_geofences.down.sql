@@ -0,0 +1,11 @@
+-- Add down migration script here
+DROP INDEX IF EXISTS idx_shifts_geofence_outside;
+
+ALTER TABLE shifts DROP COLUMN IF EXISTS geofence_distance_meters;
+ALTER TABLE shifts DROP COLUMN IF EXISTS geofence_status;
+ALTER TABLE shifts DROP COLUMN IF EXISTS longitude;
+ALTER TABLE shifts DROP COLUMN IF EXISTS latitude;
+
+ALTER TABLE jobsites DROP COLUMN IF EXISTS geofence_radius_meters;
+ALTER TABLE jobsites DROP COLUMN IF EXISTS longitude;
+ALTER TABLE jobsites DROP COLUMN IF EXISTS latitude;
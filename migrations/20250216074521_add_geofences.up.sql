@@ -0,0 +1,13 @@
+-- Add up migration script here
+-- Jobsites without a geofence can't place clock-ins, those stay unknown
+ALTER TABLE jobsites ADD COLUMN latitude DOUBLE PRECISION;
+ALTER TABLE jobsites ADD COLUMN longitude DOUBLE PRECISION;
+ALTER TABLE jobsites ADD COLUMN geofence_radius_meters INTEGER;
+
+ALTER TABLE shifts ADD COLUMN latitude DOUBLE PRECISION;
+ALTER TABLE shifts ADD COLUMN longitude DOUBLE PRECISION;
+ALTER TABLE shifts ADD COLUMN geofence_status VARCHAR(16) NOT NULL DEFAULT 'unknown';
+ALTER TABLE shifts ADD COLUMN geofence_distance_meters DOUBLE PRECISION;
+
+CREATE INDEX idx_shifts_geofence_outside ON shifts (employee_id, clocked_in_at)
+WHERE geofence_status = 'outside';
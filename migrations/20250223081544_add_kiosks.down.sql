@@ -0,0 +1,4 @@
+-- Add down migration script here
+DROP TABLE IF EXISTS kiosk_pin_failures;
+DROP TABLE IF EXISTS kiosks;
+DROP TABLE IF EXISTS employee_pins;
@@ -0,0 +1,29 @@
+-- Add up migration script here
+-- Only the PIN's signature is kept, never the PIN itself
+CREATE TABLE employee_pins (
+    employee_id UUID PRIMARY KEY REFERENCES employees(id),
+    pin_hash VARCHAR(64) NOT NULL,
+    set_at TIMESTAMPTZ NOT NULL
+);
+
+-- Shared tablets registered to clock crews in and out at a jobsite
+CREATE TABLE kiosks (
+    id UUID PRIMARY KEY,
+    jobsite_id UUID NOT NULL REFERENCES jobsites(id),
+    name VARCHAR(255) NOT NULL,
+    registered_at TIMESTAMPTZ NOT NULL,
+    revoked_at TIMESTAMPTZ
+);
+
+CREATE INDEX idx_kiosks_jobsite_id ON kiosks (jobsite_id);
+
+-- Wrong PINs entered at a kiosk, only the recent ones are counted. A kiosk
+-- can take PINs before its registration reaches the read model.
+CREATE TABLE kiosk_pin_failures (
+    id UUID PRIMARY KEY,
+    kiosk_id UUID NOT NULL,
+    employee_id UUID NOT NULL REFERENCES employees(id),
+    failed_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX idx_kiosk_pin_failures_kiosk_id ON kiosk_pin_failures (kiosk_id, failed_at);
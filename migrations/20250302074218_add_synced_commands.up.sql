@@ -0,0 +1,9 @@
+-- Add up migration script here
+-- Commands field devices queued offline that have been applied, so a batch
+-- sent again after a dropped connection isn't applied twice
+CREATE TABLE synced_commands (
+    idempotency_key UUID PRIMARY KEY,
+    command_type VARCHAR(32) NOT NULL,
+    client_timestamp TIMESTAMPTZ NOT NULL,
+    synced_at TIMESTAMPTZ NOT NULL
+);
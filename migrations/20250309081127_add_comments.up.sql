@@ -0,0 +1,21 @@
+-- Add up migration script here
+-- Jobsite comment threads, the author comes from the posting event's
+-- metadata. Deleted comments are kept but left out of the thread.
+CREATE TABLE comments (
+    id UUID PRIMARY KEY,
+    jobsite_id UUID NOT NULL REFERENCES jobsites(id),
+    author_id UUID REFERENCES employees(id),
+    body TEXT NOT NULL,
+    posted_at TIMESTAMPTZ NOT NULL,
+    edited_at TIMESTAMPTZ,
+    deleted_at TIMESTAMPTZ
+);
+
+CREATE INDEX idx_comments_jobsite_id ON comments (jobsite_id, posted_at);
+
+-- Employees named with @ in a comment, replaced whenever it's edited
+CREATE TABLE comment_mentions (
+    comment_id UUID NOT NULL REFERENCES comments(id),
+    employee_id UUID NOT NULL REFERENCES employees(id),
+    PRIMARY KEY (comment_id, employee_id)
+);
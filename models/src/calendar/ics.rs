@@ -0,0 +1,205 @@
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+
+use crate::projections::crew_assignment::CrewAssignment;
+
+/// Domain part of every UID, UIDs are derived from aggregate ids so a
+/// subscribed calendar replaces an entry instead of adding a duplicate
+const UID_DOMAIN: &str = "esrs";
+
+/// Longest a content line can be before it has to be folded, in octets
+const MAX_LINE_LENGTH: usize = 75;
+
+/// An all day event in an iCalendar feed
+#[derive(Debug, Clone)]
+pub struct IcsEvent {
+    pub uid: String,
+    pub stamp: DateTime<Utc>,
+    /// First day of the event
+    pub start_date: NaiveDate,
+    /// Last day of the event, inclusive
+    pub end_date: NaiveDate,
+    pub summary: String,
+    pub location: Option<String>,
+}
+
+impl IcsEvent {
+    /// A crew assignment as it appears in the assigned employee's calendar
+    pub fn employee_assignment(assignment: &CrewAssignment) -> Self {
+        Self {
+            uid: format!("crew-assignment-{}@{}", assignment.id, UID_DOMAIN),
+            stamp: assignment.assigned_at,
+            start_date: assignment.start_date,
+            end_date: assignment.end_date,
+            summary: assignment.jobsite_name.clone(),
+            location: Some(assignment.jobsite_name.clone()),
+        }
+    }
+
+    /// A crew assignment as it appears in the jobsite's calendar
+    pub fn jobsite_assignment(assignment: &CrewAssignment) -> Self {
+        Self {
+            uid: format!("jobsite-crew-assignment-{}@{}", assignment.id, UID_DOMAIN),
+            stamp: assignment.assigned_at,
+            start_date: assignment.start_date,
+            end_date: assignment.end_date,
+            summary: assignment.employee_name.clone(),
+            location: Some(assignment.jobsite_name.clone()),
+        }
+    }
+}
+
+/// An iCalendar (RFC 5545) document that calendar apps can subscribe to
+#[derive(Debug, Clone)]
+pub struct IcsCalendar {
+    pub name: String,
+    pub events: Vec<IcsEvent>,
+}
+
+impl IcsCalendar {
+    pub fn new(name: String, events: Vec<IcsEvent>) -> Self {
+        Self { name, events }
+    }
+
+    pub fn render(&self) -> String {
+        let mut lines = vec![
+            "BEGIN:VCALENDAR".to_string(),
+            "VERSION:2.0".to_string(),
+            "PRODID:-//ESRS//Crew Schedule//EN".to_string(),
+            "CALSCALE:GREGORIAN".to_string(),
+            "METHOD:PUBLISH".to_string(),
+            format!("X-WR-CALNAME:{}", escape_text(&self.name)),
+        ];
+
+        for event in &self.events {
+            lines.push("BEGIN:VEVENT".to_string());
+            lines.push(format!("UID:{}", event.uid));
+            lines.push(format!("DTSTAMP:{}", event.stamp.format("%Y%m%dT%H%M%SZ")));
+            lines.push(format!(
+                "DTSTART;VALUE=DATE:{}",
+                event.start_date.format("%Y%m%d")
+            ));
+            // All day events end on the day after their last day
+            lines.push(format!(
+                "DTEND;VALUE=DATE:{}",
+                (event.end_date + Duration::days(1)).format("%Y%m%d")
+            ));
+            lines.push(format!("SUMMARY:{}", escape_text(&event.summary)));
+            if let Some(location) = &event.location {
+                lines.push(format!("LOCATION:{}", escape_text(location)));
+            }
+            lines.push("TRANSP:TRANSPARENT".to_string());
+            lines.push("END:VEVENT".to_string());
+        }
+
+        lines.push("END:VCALENDAR".to_string());
+
+        lines
+            .iter()
+            .map(|line| fold_line(line))
+            .collect::<Vec<_>>()
+            .join("")
+    }
+}
+
+/// Escape the characters TEXT values can't contain as-is
+fn escape_text(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+/// Split a content line into CRLF terminated lines of at most 75 octets,
+/// continuation lines start with a space, multi-byte characters are never
+/// split
+fn fold_line(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len() + 8);
+    let mut length = 0;
+
+    for c in line.chars() {
+        if length + c.len_utf8() > MAX_LINE_LENGTH {
+            folded.push_str("\r\n ");
+            length = 1;
+        }
+        folded.push(c);
+        length += c.len_utf8();
+    }
+
+    folded.push_str("\r\n");
+    folded
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{NaiveDate, TimeZone, Utc};
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn assignment(jobsite_name: &str) -> CrewAssignment {
+        CrewAssignment {
+            id: Uuid::parse_str("6f1c2a4e-8d3b-4c5a-9e7f-0a1b2c3d4e5f").unwrap(),
+            employee_id: Uuid::new_v4(),
+            employee_name: "Sam Lee".to_string(),
+            jobsite_id: Uuid::new_v4(),
+            jobsite_name: jobsite_name.to_string(),
+            start_date: NaiveDate::from_ymd_opt(2024, 11, 4).unwrap(),
+            end_date: NaiveDate::from_ymd_opt(2024, 11, 8).unwrap(),
+            assigned_at: Utc.with_ymd_and_hms(2024, 11, 1, 15, 30, 0).unwrap(),
+            unassigned_at: None,
+        }
+    }
+
+    #[test]
+    fn uids_come_from_the_assignment_id() {
+        let assignment = assignment("Main St");
+        let mut renamed = assignment.clone();
+        renamed.jobsite_name = "Main Street".to_string();
+        renamed.assigned_at = Utc::now();
+
+        let uid = IcsEvent::employee_assignment(&assignment).uid;
+        assert_eq!(
+            uid,
+            "crew-assignment-6f1c2a4e-8d3b-4c5a-9e7f-0a1b2c3d4e5f@esrs"
+        );
+        assert_eq!(IcsEvent::employee_assignment(&renamed).uid, uid);
+
+        // The jobsite's calendar is a separate feed, with its own UIDs
+        assert_eq!(
+            IcsEvent::jobsite_assignment(&assignment).uid,
+            "jobsite-crew-assignment-6f1c2a4e-8d3b-4c5a-9e7f-0a1b2c3d4e5f@esrs"
+        );
+    }
+
+    #[test]
+    fn text_values_are_escaped() {
+        assert_eq!(
+            escape_text("Smith, Jones; \\ Co\r\nUnit 2\nBack"),
+            r"Smith\, Jones\; \\ Co\nUnit 2\nBack"
+        );
+
+        let calendar = IcsCalendar::new(
+            "Crew, East".to_string(),
+            vec![IcsEvent::employee_assignment(&assignment("Lot 4; Phase 2"))],
+        );
+        let rendered = calendar.render();
+
+        assert!(rendered.contains("X-WR-CALNAME:Crew\\, East\r\n"));
+        assert!(rendered.contains("SUMMARY:Lot 4\\; Phase 2\r\n"));
+        assert!(rendered.contains("DTSTART;VALUE=DATE:20241104\r\n"));
+        assert!(rendered.contains("DTEND;VALUE=DATE:20241109\r\n"));
+    }
+
+    #[test]
+    fn long_lines_are_folded_between_characters() {
+        let line = format!("SUMMARY:{}", "é".repeat(40));
+        let folded = fold_line(&line);
+
+        assert!(folded
+            .split("\r\n")
+            .all(|part| part.len() <= MAX_LINE_LENGTH));
+        assert_eq!(folded.replace("\r\n ", "").trim_end(), line);
+    }
+}
@@ -0,0 +1,150 @@
+use uuid::Uuid;
+
+use crate::events::crew_assignment::CrewAssignmentEvent;
+
+/// How many times an unassignment is checked again after losing a race
+/// with another write to the employee's schedule before giving up
+#[cfg(feature = "connect")]
+const APPEND_ATTEMPTS: usize = 5;
+
+/// Why an employee's crew schedule turned an unassignment away
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq)]
+#[error("This crew assignment has already been cancelled")]
+pub struct AssignmentCancelled;
+
+/// Whether the assignment can still be cancelled after the events already
+/// on the employee's schedule, it can't once it has been unassigned
+pub fn check(
+    recorded: &[CrewAssignmentEvent],
+    assignment_id: &Uuid,
+) -> Result<(), AssignmentCancelled> {
+    let cancelled = |event: &CrewAssignmentEvent| match event {
+        CrewAssignmentEvent::CrewMemberUnassigned(unassigned) => unassigned.id == *assignment_id,
+        _ => false,
+    };
+
+    if recorded.iter().any(cancelled) {
+        return Err(AssignmentCancelled);
+    }
+
+    Ok(())
+}
+
+/**
+ * Append the unassignment `event` as `event_id` to the employee's crew
+ * schedule, expecting the stream to be where it was when the assignment
+ * was checked, so a cancellation isn't recorded twice with different
+ * times. An event already recorded under the same id is taken as this one
+ * being retried.
+ */
+#[cfg(feature = "connect")]
+pub async fn unassign(
+    eventstore: &eventstore::Client,
+    employee_id: &Uuid,
+    assignment_id: &Uuid,
+    event: eventstore::EventData,
+    event_id: Uuid,
+) -> anyhow::Result<Result<(), AssignmentCancelled>> {
+    use crate::projections::crew_assignment::CrewAssignment;
+
+    let stream_name = CrewAssignment::stream_name(employee_id);
+    let event = event.id(event_id);
+
+    for _ in 0..APPEND_ATTEMPTS {
+        let mut stream = eventstore
+            .read_stream(stream_name.clone(), &Default::default())
+            .await?;
+
+        let mut expected_revision = eventstore::ExpectedRevision::NoStream;
+        let mut recorded = vec![];
+
+        loop {
+            let resolved = match stream.next().await {
+                Ok(Some(resolved)) => resolved,
+                Ok(None) | Err(eventstore::Error::ResourceNotFound) => break,
+                Err(e) => return Err(e.into()),
+            };
+
+            let original = resolved.get_original_event();
+            if original.id == event_id {
+                return Ok(Ok(()));
+            }
+
+            expected_revision = eventstore::ExpectedRevision::Exact(original.revision);
+            recorded.push(CrewAssignmentEvent::try_from(resolved)?);
+        }
+
+        if let Err(cancelled) = check(&recorded, assignment_id) {
+            return Ok(Err(cancelled));
+        }
+
+        let options =
+            eventstore::AppendToStreamOptions::default().expected_revision(expected_revision);
+
+        match eventstore
+            .append_to_stream(stream_name.clone(), &options, event.clone())
+            .await
+        {
+            Ok(_) => return Ok(Ok(())),
+            Err(eventstore::Error::WrongExpectedVersion { .. }) => continue,
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    anyhow::bail!("{} kept changing while it was being written", stream_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{NaiveDate, Utc};
+    use uuid::Uuid;
+
+    use super::{check, AssignmentCancelled};
+    use crate::events::crew_assignment::{
+        CrewAssignmentEvent, CrewMemberAssigned, CrewMemberUnassigned,
+    };
+
+    #[test]
+    fn an_assignment_is_only_cancelled_once() {
+        let (assignment_id, employee_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let day = NaiveDate::from_ymd_opt(2025, 3, 4).unwrap();
+        let assigned = |id| {
+            CrewAssignmentEvent::CrewMemberAssigned(CrewMemberAssigned {
+                id,
+                employee_id,
+                jobsite_id: Uuid::new_v4(),
+                start_date: day,
+                end_date: day,
+                assigned_at: Utc::now(),
+            })
+        };
+        let unassigned = |id| {
+            CrewAssignmentEvent::CrewMemberUnassigned(CrewMemberUnassigned {
+                id,
+                employee_id,
+                unassigned_at: Utc::now(),
+            })
+        };
+        let other_id = Uuid::new_v4();
+
+        assert_eq!(check(&[assigned(assignment_id)], &assignment_id), Ok(()));
+        assert_eq!(
+            check(
+                &[
+                    assigned(assignment_id),
+                    assigned(other_id),
+                    unassigned(other_id)
+                ],
+                &assignment_id
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            check(
+                &[assigned(assignment_id), unassigned(assignment_id)],
+                &assignment_id
+            ),
+            Err(AssignmentCancelled)
+        );
+    }
+}
@@ -0,0 +1,127 @@
+#[cfg(feature = "connect")]
+use uuid::Uuid;
+
+use crate::events::daily_log::DailyLogEvent;
+
+/// How many times an append is checked again after losing a race with
+/// another write to the same day before giving up
+#[cfg(feature = "connect")]
+const APPEND_ATTEMPTS: usize = 5;
+
+/// Why a day's log stream turned a write away
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq)]
+#[error("This day's log has been submitted and can no longer be changed")]
+pub struct DailyLogLocked;
+
+/// Whether anything more can be recorded on a day after the events already
+/// on its stream, nothing can once the day has been submitted
+pub fn check(recorded: &[DailyLogEvent]) -> Result<(), DailyLogLocked> {
+    if recorded
+        .iter()
+        .any(|event| matches!(event, DailyLogEvent::DailyLogSubmitted(_)))
+    {
+        return Err(DailyLogLocked);
+    }
+
+    Ok(())
+}
+
+/**
+ * Append `event` as `event_id` to the day's log stream, expecting the
+ * stream to be where it was when the day was checked. A write that lands
+ * in between is checked again, so nothing is recorded after a submit the
+ * read models haven't caught up with. An event already recorded under the
+ * same id is taken as this one being retried.
+ */
+#[cfg(feature = "connect")]
+pub async fn append(
+    eventstore: &eventstore::Client,
+    jobsite_id: &Uuid,
+    log_date: &chrono::NaiveDate,
+    event: eventstore::EventData,
+    event_id: Uuid,
+) -> anyhow::Result<Result<(), DailyLogLocked>> {
+    use crate::projections::daily_log::DailyLog;
+
+    let stream_name = DailyLog::stream_name(jobsite_id, log_date);
+    let event = event.id(event_id);
+
+    for _ in 0..APPEND_ATTEMPTS {
+        let mut stream = eventstore
+            .read_stream(stream_name.clone(), &Default::default())
+            .await?;
+
+        let mut expected_revision = eventstore::ExpectedRevision::NoStream;
+        let mut recorded = vec![];
+
+        loop {
+            let resolved = match stream.next().await {
+                Ok(Some(resolved)) => resolved,
+                Ok(None) | Err(eventstore::Error::ResourceNotFound) => break,
+                Err(e) => return Err(e.into()),
+            };
+
+            let original = resolved.get_original_event();
+            if original.id == event_id {
+                return Ok(Ok(()));
+            }
+
+            expected_revision = eventstore::ExpectedRevision::Exact(original.revision);
+            recorded.push(DailyLogEvent::try_from(resolved)?);
+        }
+
+        if let Err(locked) = check(&recorded) {
+            return Ok(Err(locked));
+        }
+
+        let options =
+            eventstore::AppendToStreamOptions::default().expected_revision(expected_revision);
+
+        match eventstore
+            .append_to_stream(stream_name.clone(), &options, event.clone())
+            .await
+        {
+            Ok(_) => return Ok(Ok(())),
+            Err(eventstore::Error::WrongExpectedVersion { .. }) => continue,
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    anyhow::bail!("{} kept changing while it was being written", stream_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{NaiveDate, Utc};
+    use uuid::Uuid;
+
+    use super::{check, DailyLogLocked};
+    use crate::{
+        events::daily_log::{DailyLogEvent, DailyLogSaved, DailyLogSubmitted},
+        projections::daily_log::WeatherCondition,
+    };
+
+    #[test]
+    fn nothing_is_recorded_on_a_submitted_day() {
+        let (jobsite_id, log_date) = (Uuid::new_v4(), NaiveDate::from_ymd_opt(2025, 3, 4).unwrap());
+        let saved = || {
+            DailyLogEvent::DailyLogSaved(DailyLogSaved {
+                jobsite_id,
+                log_date,
+                crew_count: 6,
+                weather: WeatherCondition::Clear,
+                notes: None,
+                saved_at: Utc::now(),
+            })
+        };
+        let submitted = DailyLogEvent::DailyLogSubmitted(DailyLogSubmitted {
+            jobsite_id,
+            log_date,
+            submitted_at: Utc::now(),
+        });
+
+        assert_eq!(check(&[]), Ok(()));
+        assert_eq!(check(&[saved(), saved()]), Ok(()));
+        assert_eq!(check(&[saved(), submitted]), Err(DailyLogLocked));
+    }
+}
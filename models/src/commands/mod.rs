@@ -0,0 +1,348 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    geofence::{Coordinates, GeofenceCheck},
+    projections::daily_log::WeatherCondition,
+    scheduler::Trigger,
+};
+
+pub mod crew_assignment;
+pub mod daily_log;
+pub mod shift;
+
+/// Ties the events a command records back to what caused them, stored in
+/// the event metadata under the names EventStoreDB uses for its own links
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct CommandMetadata {
+    /// The event that led to the command
+    #[serde(rename = "$causationId")]
+    pub causation_id: Uuid,
+    /// The event that started the whole chain
+    #[serde(rename = "$correlationId")]
+    pub correlation_id: Uuid,
+}
+
+impl CommandMetadata {
+    /// Metadata for a command caused by an event, carrying the event's own
+    /// correlation forward when it has one
+    pub fn caused_by(event_id: Uuid, metadata: Option<CommandMetadata>) -> Self {
+        Self {
+            causation_id: event_id,
+            correlation_id: metadata.map_or(event_id, |metadata| metadata.correlation_id),
+        }
+    }
+
+    /// The id of the event recorded by the `index`th command `issuer` gave
+    /// in reaction to the causing event. Handling that event again, as a
+    /// restart does, gives the same ids, so the event store takes the
+    /// repeated appends as already done.
+    pub fn command_event_id(&self, issuer: &str, index: usize) -> Uuid {
+        Uuid::new_v5(
+            &self.causation_id,
+            format!("{}-{}", issuer, index).as_bytes(),
+        )
+    }
+}
+
+/// What process managers ask the domain to do in reaction to events, and
+/// what field devices queued up while offline
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    ClockIn {
+        shift_id: Uuid,
+        employee_id: Uuid,
+        jobsite_id: Uuid,
+        cost_code_id: Option<Uuid>,
+        clocked_in_at: DateTime<Utc>,
+        time_zone: String,
+        coordinates: Option<Coordinates>,
+        geofence: GeofenceCheck,
+    },
+    ClockOut {
+        shift_id: Uuid,
+        employee_id: Uuid,
+        clocked_out_at: DateTime<Utc>,
+    },
+    UnassignCrewMember {
+        assignment_id: Uuid,
+        employee_id: Uuid,
+        unassigned_at: DateTime<Utc>,
+    },
+    SubmitTimesheet {
+        employee_id: Uuid,
+        week_start: NaiveDate,
+        submitted_at: DateTime<Utc>,
+    },
+    WarnCertificationExpiry {
+        certification_id: Uuid,
+        employee_id: Uuid,
+        expires_on: NaiveDate,
+        warned_at: DateTime<Utc>,
+    },
+    ExpireCertification {
+        certification_id: Uuid,
+        employee_id: Uuid,
+        expired_on: NaiveDate,
+        expired_at: DateTime<Utc>,
+    },
+    SaveDailyLog {
+        jobsite_id: Uuid,
+        log_date: NaiveDate,
+        crew_count: i32,
+        weather: WeatherCondition,
+        notes: Option<String>,
+        saved_at: DateTime<Utc>,
+    },
+    /// Have the scheduler issue the trigger's command once `due_at` passes
+    Schedule {
+        trigger: Trigger,
+        due_at: DateTime<Utc>,
+    },
+    /// Drop a trigger that is no longer needed, if it hasn't fired yet
+    Cancel { trigger: Trigger },
+}
+
+#[cfg(feature = "connect")]
+impl Command {
+    /// Record the command's event as `event_id`, tagged with what caused it.
+    /// Clock-ins are turned away with a [`shift::ShiftConflict`] when the
+    /// employee is already on the clock, daily logs with a
+    /// [`daily_log::DailyLogLocked`] once the day is submitted and
+    /// unassignments with a [`crew_assignment::AssignmentCancelled`] once the
+    /// assignment is cancelled. Scheduling commands are kept in the
+    /// transaction instead.
+    pub async fn execute(
+        &self,
+        eventstore: &eventstore::Client,
+        transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        metadata: &CommandMetadata,
+        event_id: Uuid,
+    ) -> anyhow::Result<()> {
+        use eventstore::EventData;
+
+        use self::shift::{Punch, ShiftConflict};
+        use crate::{
+            events::{
+                certification::{CertificationExpired, CertificationExpiryWarned},
+                crew_assignment::CrewMemberUnassigned,
+                daily_log::DailyLogSaved,
+                shift::{ClockedIn, ClockedOut},
+                timesheet::TimesheetSubmitted,
+            },
+            projections::{
+                certification::Certification, scheduled_trigger::ScheduledTrigger,
+                timesheet::Timesheet,
+            },
+        };
+
+        let (stream_name, event) = match self {
+            Command::ClockIn {
+                shift_id,
+                employee_id,
+                jobsite_id,
+                cost_code_id,
+                clocked_in_at,
+                time_zone,
+                coordinates,
+                geofence,
+            } => {
+                let event = EventData::json(
+                    ClockedIn::event_name(),
+                    &ClockedIn {
+                        shift_id: *shift_id,
+                        employee_id: *employee_id,
+                        jobsite_id: *jobsite_id,
+                        cost_code_id: *cost_code_id,
+                        clocked_in_at: *clocked_in_at,
+                        time_zone: time_zone.clone(),
+                        coordinates: *coordinates,
+                        geofence: *geofence,
+                    },
+                )?
+                .metadata_as_json(metadata)?;
+
+                Punch::In
+                    .append(eventstore, employee_id, event, event_id)
+                    .await??;
+
+                return Ok(());
+            }
+            Command::ClockOut {
+                shift_id,
+                employee_id,
+                clocked_out_at,
+            } => {
+                let event = EventData::json(
+                    ClockedOut::event_name(),
+                    &ClockedOut {
+                        shift_id: *shift_id,
+                        employee_id: *employee_id,
+                        clocked_out_at: *clocked_out_at,
+                    },
+                )?
+                .metadata_as_json(metadata)?;
+
+                let punch = Punch::Out {
+                    shift_id: *shift_id,
+                };
+
+                // A shift someone else clocked out first needs nothing more
+                match punch
+                    .append(eventstore, employee_id, event, event_id)
+                    .await?
+                {
+                    Ok(()) | Err(ShiftConflict::NotClockedIn) => return Ok(()),
+                    Err(conflict) => return Err(conflict.into()),
+                }
+            }
+            Command::UnassignCrewMember {
+                assignment_id,
+                employee_id,
+                unassigned_at,
+            } => {
+                let event = EventData::json(
+                    CrewMemberUnassigned::event_name(),
+                    &CrewMemberUnassigned {
+                        id: *assignment_id,
+                        employee_id: *employee_id,
+                        unassigned_at: *unassigned_at,
+                    },
+                )?
+                .metadata_as_json(metadata)?;
+
+                crew_assignment::unassign(eventstore, employee_id, assignment_id, event, event_id)
+                    .await??;
+
+                return Ok(());
+            }
+            Command::SubmitTimesheet {
+                employee_id,
+                week_start,
+                submitted_at,
+            } => (
+                Timesheet::stream_name(employee_id, week_start),
+                EventData::json(
+                    TimesheetSubmitted::event_name(),
+                    &TimesheetSubmitted {
+                        employee_id: *employee_id,
+                        week_start: *week_start,
+                        submitted_at: *submitted_at,
+                    },
+                )?,
+            ),
+            Command::WarnCertificationExpiry {
+                certification_id,
+                employee_id,
+                expires_on,
+                warned_at,
+            } => (
+                Certification::stream_name(employee_id),
+                EventData::json(
+                    CertificationExpiryWarned::event_name(),
+                    &CertificationExpiryWarned {
+                        id: *certification_id,
+                        employee_id: *employee_id,
+                        expires_on: *expires_on,
+                        warned_at: *warned_at,
+                    },
+                )?,
+            ),
+            Command::ExpireCertification {
+                certification_id,
+                employee_id,
+                expired_on,
+                expired_at,
+            } => (
+                Certification::stream_name(employee_id),
+                EventData::json(
+                    CertificationExpired::event_name(),
+                    &CertificationExpired {
+                        id: *certification_id,
+                        employee_id: *employee_id,
+                        expired_on: *expired_on,
+                        expired_at: *expired_at,
+                    },
+                )?,
+            ),
+            Command::SaveDailyLog {
+                jobsite_id,
+                log_date,
+                crew_count,
+                weather,
+                notes,
+                saved_at,
+            } => {
+                let event = EventData::json(
+                    DailyLogSaved::event_name(),
+                    &DailyLogSaved {
+                        jobsite_id: *jobsite_id,
+                        log_date: *log_date,
+                        crew_count: *crew_count,
+                        weather: *weather,
+                        notes: notes.clone(),
+                        saved_at: *saved_at,
+                    },
+                )?
+                .metadata_as_json(metadata)?;
+
+                daily_log::append(eventstore, jobsite_id, log_date, event, event_id).await??;
+
+                return Ok(());
+            }
+            Command::Schedule { trigger, due_at } => {
+                return ScheduledTrigger::schedule(
+                    transaction,
+                    trigger,
+                    *due_at,
+                    metadata.correlation_id,
+                )
+                .await;
+            }
+            Command::Cancel { trigger } => {
+                ScheduledTrigger::cancel(transaction, trigger).await?;
+                return Ok(());
+            }
+        };
+
+        let event = event.metadata_as_json(metadata)?.id(event_id);
+
+        eventstore
+            .append_to_stream(stream_name, &Default::default(), event)
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::CommandMetadata;
+
+    #[test]
+    fn command_event_ids_repeat_for_the_same_cause() {
+        let metadata = CommandMetadata::caused_by(Uuid::new_v4(), None);
+
+        assert_eq!(
+            metadata.command_event_id("deadlines", 0),
+            metadata.command_event_id("deadlines", 0)
+        );
+        assert_ne!(
+            metadata.command_event_id("deadlines", 0),
+            metadata.command_event_id("deadlines", 1)
+        );
+        assert_ne!(
+            metadata.command_event_id("deadlines", 0),
+            metadata.command_event_id("jobsite_archival", 0)
+        );
+
+        let other = CommandMetadata::caused_by(Uuid::new_v4(), None);
+        assert_ne!(
+            metadata.command_event_id("deadlines", 0),
+            other.command_event_id("deadlines", 0)
+        );
+    }
+}
@@ -0,0 +1,175 @@
+use uuid::Uuid;
+
+use crate::events::shift::ShiftEvent;
+
+/// Clock-ins and clock-outs go on one stream per employee. Each is appended
+/// at the revision it was checked against, so two clock-ins racing each
+/// other can't both open a shift.
+pub fn stream_name(employee_id: &Uuid) -> String {
+    format!("shift-employee-{}", employee_id)
+}
+
+/// Why the employee's shift stream turned a punch away
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq)]
+pub enum ShiftConflict {
+    #[error("This employee is already clocked in, they must clock out first")]
+    AlreadyClockedIn,
+    #[error("This shift has already been clocked out")]
+    NotClockedIn,
+}
+
+/// What is being recorded on the employee's shift stream
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Punch {
+    In,
+    Out { shift_id: Uuid },
+}
+
+impl Punch {
+    /// Whether the punch can follow the last event on the employee's stream
+    pub fn check(&self, last: Option<&ShiftEvent>) -> Result<(), ShiftConflict> {
+        let open_shift_id = match last {
+            Some(ShiftEvent::ClockedIn(clocked_in)) => Some(clocked_in.shift_id),
+            _ => None,
+        };
+
+        match (self, open_shift_id) {
+            (Punch::In, None) => Ok(()),
+            (Punch::In, Some(_)) => Err(ShiftConflict::AlreadyClockedIn),
+            (Punch::Out { shift_id }, Some(open_shift_id)) if *shift_id == open_shift_id => Ok(()),
+            (Punch::Out { .. }, _) => Err(ShiftConflict::NotClockedIn),
+        }
+    }
+
+    /// Why the punch lost a race with another one for the same employee
+    #[cfg(feature = "connect")]
+    fn conflict(&self) -> ShiftConflict {
+        match self {
+            Punch::In => ShiftConflict::AlreadyClockedIn,
+            Punch::Out { .. } => ShiftConflict::NotClockedIn,
+        }
+    }
+
+    /**
+     * Append the punch's event as `event_id` to the employee's shift stream,
+     * expecting the stream to be where it was when the punch was checked.
+     * An event already recorded under the same id is taken as this one
+     * being retried.
+     */
+    #[cfg(feature = "connect")]
+    pub async fn append(
+        &self,
+        eventstore: &eventstore::Client,
+        employee_id: &Uuid,
+        event: eventstore::EventData,
+        event_id: Uuid,
+    ) -> anyhow::Result<Result<(), ShiftConflict>> {
+        let stream_name = stream_name(employee_id);
+
+        let options = eventstore::ReadStreamOptions::default()
+            .position(eventstore::StreamPosition::End)
+            .max_count(1);
+
+        let last = match eventstore
+            .read_stream(stream_name.clone(), &options)
+            .await?
+            .next()
+            .await
+        {
+            Ok(last) => last,
+            Err(eventstore::Error::ResourceNotFound) => None,
+            Err(e) => return Err(e.into()),
+        };
+
+        let (expected_revision, last) = match last {
+            Some(resolved) => {
+                let recorded = resolved.get_original_event();
+
+                if recorded.id == event_id {
+                    return Ok(Ok(()));
+                }
+
+                let revision = eventstore::ExpectedRevision::Exact(recorded.revision);
+                (revision, Some(ShiftEvent::try_from(resolved)?))
+            }
+            None => (eventstore::ExpectedRevision::NoStream, None),
+        };
+
+        if let Err(conflict) = self.check(last.as_ref()) {
+            return Ok(Err(conflict));
+        }
+
+        let options =
+            eventstore::AppendToStreamOptions::default().expected_revision(expected_revision);
+
+        match eventstore
+            .append_to_stream(stream_name, &options, event.id(event_id))
+            .await
+        {
+            Ok(_) => Ok(Ok(())),
+            Err(eventstore::Error::WrongExpectedVersion { .. }) => Ok(Err(self.conflict())),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    use super::{Punch, ShiftConflict};
+    use crate::{
+        events::shift::{ClockedIn, ClockedOut, ShiftEvent},
+        geofence::GeofenceCheck,
+    };
+
+    fn clocked_in(shift_id: Uuid, employee_id: Uuid) -> ShiftEvent {
+        ShiftEvent::ClockedIn(ClockedIn {
+            shift_id,
+            employee_id,
+            jobsite_id: Uuid::new_v4(),
+            cost_code_id: None,
+            clocked_in_at: Utc::now(),
+            time_zone: "UTC".to_string(),
+            coordinates: None,
+            geofence: GeofenceCheck::default(),
+        })
+    }
+
+    #[test]
+    fn an_employee_has_at_most_one_open_shift() {
+        let employee_id = Uuid::new_v4();
+        let shift_id = Uuid::new_v4();
+        let open = clocked_in(shift_id, employee_id);
+        let closed = ShiftEvent::ClockedOut(ClockedOut {
+            shift_id,
+            employee_id,
+            clocked_out_at: Utc::now(),
+        });
+
+        assert_eq!(Punch::In.check(None), Ok(()));
+        assert_eq!(Punch::In.check(Some(&closed)), Ok(()));
+        assert_eq!(
+            Punch::In.check(Some(&open)),
+            Err(ShiftConflict::AlreadyClockedIn)
+        );
+
+        assert_eq!(Punch::Out { shift_id }.check(Some(&open)), Ok(()));
+        assert_eq!(
+            Punch::Out { shift_id }.check(Some(&closed)),
+            Err(ShiftConflict::NotClockedIn)
+        );
+        assert_eq!(
+            Punch::Out {
+                shift_id: Uuid::new_v4()
+            }
+            .check(Some(&open)),
+            Err(ShiftConflict::NotClockedIn)
+        );
+        assert_eq!(
+            Punch::Out { shift_id }.check(None),
+            Err(ShiftConflict::NotClockedIn)
+        );
+    }
+}
@@ -0,0 +1,281 @@
+#[cfg(feature = "connect")]
+use {
+    log::error,
+    serde_json::Value,
+    crate::AppState,
+    super::EventParseError
+};
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A file attached to a jobsite, or to its daily log for `log_date`. The
+/// content is referenced by hash and kept in attachment storage.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct AttachmentAdded {
+    pub id: Uuid,
+    pub jobsite_id: Uuid,
+    pub log_date: Option<NaiveDate>,
+    pub file_name: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub content_hash: String,
+    pub thumbnail_hash: Option<String>,
+    pub added_at: DateTime<Utc>,
+}
+
+impl AttachmentAdded {
+    pub fn event_name() -> String {
+        String::from("AttachmentAdded")
+    }
+
+    #[cfg(feature = "connect")]
+    pub async fn handle_read_model(
+        &self,
+        handler: AttachmentReadModelHandler,
+    ) -> anyhow::Result<()> {
+        use crate::{projections::attachment::Attachment, AttachmentBroadcast};
+
+        let mut transaction = handler
+            .db_pool
+            .begin()
+            .await
+            .expect("Failed to start transaction");
+
+        match Attachment::add(&mut transaction, self).await {
+            Ok(attachment) => match transaction.commit().await {
+                Ok(_) => {
+                    if let Err(e) = handler
+                        .app_state
+                        .attachment_tx
+                        .send(AttachmentBroadcast::AttachmentAdded(attachment))
+                    {
+                        error!("Failed to send attachment to channel: {}", e);
+                    }
+                }
+                Err(e) => error!("Failed to commit transaction: {}", e),
+            },
+            Err(e) => {
+                error!("Failed to add attachment in read model: {}", e);
+                if let Err(e) = transaction.rollback().await {
+                    error!("Failed to rollback transaction: {}", e);
+                }
+            }
+        };
+
+        Ok(())
+    }
+}
+
+/// An attachment taken off its jobsite or log. The content stays in storage
+/// since other attachments may share it.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct AttachmentRemoved {
+    pub id: Uuid,
+    pub removed_at: DateTime<Utc>,
+}
+
+impl AttachmentRemoved {
+    pub fn event_name() -> String {
+        String::from("AttachmentRemoved")
+    }
+
+    #[cfg(feature = "connect")]
+    pub async fn handle_read_model(
+        &self,
+        handler: AttachmentReadModelHandler,
+    ) -> anyhow::Result<()> {
+        use crate::{projections::attachment::Attachment, AttachmentBroadcast};
+
+        let mut transaction = handler
+            .db_pool
+            .begin()
+            .await
+            .expect("Failed to start transaction");
+
+        match Attachment::remove(&mut transaction, self).await {
+            Ok(attachment) => match transaction.commit().await {
+                Ok(_) => {
+                    if let Err(e) = handler
+                        .app_state
+                        .attachment_tx
+                        .send(AttachmentBroadcast::AttachmentRemoved(attachment))
+                    {
+                        error!("Failed to send attachment to channel: {}", e);
+                    }
+                }
+                Err(e) => error!("Failed to commit transaction: {}", e),
+            },
+            Err(e) => {
+                error!("Failed to remove attachment in read model: {}", e);
+                if let Err(e) = transaction.rollback().await {
+                    error!("Failed to rollback transaction: {}", e);
+                }
+            }
+        };
+
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "type")]
+pub enum AttachmentEvent {
+    AttachmentAdded(AttachmentAdded),
+    AttachmentRemoved(AttachmentRemoved),
+}
+
+#[cfg(feature = "connect")]
+impl TryFrom<eventstore::ResolvedEvent> for AttachmentEvent {
+    type Error = EventParseError;
+
+    fn try_from(value: eventstore::ResolvedEvent) -> Result<Self, Self::Error> {
+        let event_data = value.event.ok_or(EventParseError::MissingEventData)?;
+        let event_json: Value = serde_json::from_slice(&event_data.data)
+            .map_err(EventParseError::DeserializationError)?;
+
+        match event_data.event_type {
+            s if s == AttachmentAdded::event_name() => Ok(AttachmentEvent::AttachmentAdded(
+                serde_json::from_value(event_json)
+                    .map_err(EventParseError::DeserializationError)?,
+            )),
+            s if s == AttachmentRemoved::event_name() => Ok(AttachmentEvent::AttachmentRemoved(
+                serde_json::from_value(event_json)
+                    .map_err(EventParseError::DeserializationError)?,
+            )),
+            _ => Err(EventParseError::UnknownEventType(event_data.event_type)),
+        }
+    }
+}
+
+impl AttachmentEvent {
+    #[cfg(feature = "connect")]
+    pub fn subscription_filter() -> eventstore::SubscriptionFilter {
+        eventstore::SubscriptionFilter::on_stream_name().add_prefix("attachment-")
+    }
+
+    #[cfg(feature = "connect")]
+    pub async fn handle_read_model(
+        &self,
+        handler: AttachmentReadModelHandler,
+    ) -> anyhow::Result<()> {
+        match self {
+            AttachmentEvent::AttachmentAdded(event) => event.handle_read_model(handler).await,
+            AttachmentEvent::AttachmentRemoved(event) => event.handle_read_model(handler).await,
+        }
+    }
+}
+
+/**
+ * Attachment read model handler
+ * Holds all necessary service connections and state to handle attachment events
+ */
+#[derive(Clone)]
+#[cfg(feature = "connect")]
+pub struct AttachmentReadModelHandler {
+    eventstore: std::sync::Arc<eventstore::Client>,
+    db_pool: std::sync::Arc<sqlx::PgPool>,
+    app_state: AppState,
+}
+
+#[cfg(feature = "connect")]
+impl AttachmentReadModelHandler {
+    pub fn new(
+        eventstore: std::sync::Arc<eventstore::Client>,
+        db_pool: std::sync::Arc<sqlx::PgPool>,
+        app_state: AppState,
+    ) -> Self {
+        Self {
+            eventstore,
+            db_pool,
+            app_state,
+        }
+    }
+
+    /**
+     * Subscribe to all attachment events and handle them in the read model
+     */
+    pub async fn subscribe(&self) {
+        let snapshot_position = match self.get_snapshot_position().await {
+            Ok(position) => position,
+            Err(e) => {
+                error!("Failed to get snapshot position: {}", e);
+                return;
+            }
+        };
+
+        let mut attachment_subscription = self
+            .eventstore
+            .subscribe_to_all(
+                &eventstore::SubscribeToAllOptions::default()
+                    .position(eventstore::StreamPosition::Position(eventstore::Position {
+                        commit: snapshot_position as u64,
+                        prepare: snapshot_position as u64,
+                    }))
+                    .filter(AttachmentEvent::subscription_filter()),
+            )
+            .await;
+
+        while let Ok(resolved_event) = attachment_subscription.next().await {
+            let position = resolved_event
+                .event
+                .as_ref()
+                .map(|event| event.position.commit);
+
+            let event: AttachmentEvent = match resolved_event.try_into() {
+                Ok(event) => event,
+                Err(e) => {
+                    error!("Failed to parse event: {}", e);
+                    continue;
+                }
+            };
+
+            if let Err(e) = event.handle_read_model(self.clone()).await {
+                error!("Failed to handle event: {}", e);
+                continue;
+            }
+
+            if let Err(e) = self.set_snapshot_position(position).await {
+                error!("Failed to set snapshot position: {}", e);
+                continue;
+            }
+        }
+    }
+
+    /**
+     * Get the current snapshot position for attachments
+     */
+    pub async fn get_snapshot_position(&self) -> anyhow::Result<i64> {
+        use crate::projections::snapshot_position::{SnapshotPosition, SnapshotPositionKey};
+
+        let mut transaction = self.db_pool.begin().await?;
+
+        let snapshot_position =
+            SnapshotPosition::get_by_key(&mut transaction, SnapshotPositionKey::Attachment).await?;
+
+        Ok(snapshot_position.map(|position| position.value).unwrap_or(0))
+    }
+
+    /**
+     * Set the current snapshot position for attachments
+     */
+    pub async fn set_snapshot_position(&self, position: Option<u64>) -> anyhow::Result<()> {
+        use crate::projections::snapshot_position::{SnapshotPosition, SnapshotPositionKey};
+
+        let mut transaction = self.db_pool.begin().await?;
+
+        if let Some(position) = position {
+            let snapshot_position = SnapshotPosition {
+                key: SnapshotPositionKey::Attachment,
+                value: position as i64,
+            };
+
+            snapshot_position.insert(&mut transaction).await?;
+
+            transaction.commit().await?;
+        }
+
+        Ok(())
+    }
+}
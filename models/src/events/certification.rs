@@ -0,0 +1,353 @@
+#[cfg(feature = "connect")]
+use {
+    log::error,
+    serde_json::Value,
+    crate::AppState,
+    super::EventParseError
+};
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::projections::certification::CertificationType;
+
+/// A certification an employee earned or renewed
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CertificationRecorded {
+    pub id: Uuid,
+    pub employee_id: Uuid,
+    pub certification_type: CertificationType,
+    pub certificate_number: Option<String>,
+    pub issued_on: NaiveDate,
+    pub expires_on: Option<NaiveDate>,
+    pub recorded_at: DateTime<Utc>,
+}
+
+impl CertificationRecorded {
+    pub fn event_name() -> String {
+        String::from("CertificationRecorded")
+    }
+
+    #[cfg(feature = "connect")]
+    pub async fn handle_read_model(
+        &self,
+        handler: CertificationReadModelHandler,
+    ) -> anyhow::Result<()> {
+        use crate::{projections::certification::Certification, CertificationBroadcast};
+
+        let mut transaction = handler
+            .db_pool
+            .begin()
+            .await
+            .expect("Failed to start transaction");
+
+        match Certification::record(&mut transaction, self).await {
+            Ok(certification) => match transaction.commit().await {
+                Ok(_) => {
+                    if let Err(e) = handler
+                        .app_state
+                        .certification_tx
+                        .send(CertificationBroadcast::CertificationRecorded(certification))
+                    {
+                        error!("Failed to send certification to channel: {}", e);
+                    }
+                }
+                Err(e) => error!("Failed to commit transaction: {}", e),
+            },
+            Err(e) => {
+                error!("Failed to record certification in read model: {}", e);
+                if let Err(e) = transaction.rollback().await {
+                    error!("Failed to rollback transaction: {}", e);
+                }
+            }
+        };
+
+        Ok(())
+    }
+}
+
+/// A certification is about to lapse and hasn't been renewed
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CertificationExpiryWarned {
+    pub id: Uuid,
+    pub employee_id: Uuid,
+    pub expires_on: NaiveDate,
+    pub warned_at: DateTime<Utc>,
+}
+
+impl CertificationExpiryWarned {
+    pub fn event_name() -> String {
+        String::from("CertificationExpiryWarned")
+    }
+
+    #[cfg(feature = "connect")]
+    pub async fn handle_read_model(
+        &self,
+        handler: CertificationReadModelHandler,
+    ) -> anyhow::Result<()> {
+        use crate::{projections::certification::Certification, CertificationBroadcast};
+
+        let mut transaction = handler
+            .db_pool
+            .begin()
+            .await
+            .expect("Failed to start transaction");
+
+        match Certification::warn_expiry(&mut transaction, self).await {
+            Ok(certification) => match transaction.commit().await {
+                Ok(_) => {
+                    if let Err(e) = handler.app_state.certification_tx.send(
+                        CertificationBroadcast::CertificationExpiryWarned(certification),
+                    ) {
+                        error!("Failed to send certification to channel: {}", e);
+                    }
+                }
+                Err(e) => error!("Failed to commit transaction: {}", e),
+            },
+            Err(e) => {
+                error!(
+                    "Failed to warn of certification expiry in read model: {}",
+                    e
+                );
+                if let Err(e) = transaction.rollback().await {
+                    error!("Failed to rollback transaction: {}", e);
+                }
+            }
+        };
+
+        Ok(())
+    }
+}
+
+/// A certification lapsed, the employee no longer holds it
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CertificationExpired {
+    pub id: Uuid,
+    pub employee_id: Uuid,
+    pub expired_on: NaiveDate,
+    pub expired_at: DateTime<Utc>,
+}
+
+impl CertificationExpired {
+    pub fn event_name() -> String {
+        String::from("CertificationExpired")
+    }
+
+    #[cfg(feature = "connect")]
+    pub async fn handle_read_model(
+        &self,
+        handler: CertificationReadModelHandler,
+    ) -> anyhow::Result<()> {
+        use crate::{projections::certification::Certification, CertificationBroadcast};
+
+        let mut transaction = handler
+            .db_pool
+            .begin()
+            .await
+            .expect("Failed to start transaction");
+
+        match Certification::expire(&mut transaction, self).await {
+            Ok(certification) => match transaction.commit().await {
+                Ok(_) => {
+                    if let Err(e) = handler
+                        .app_state
+                        .certification_tx
+                        .send(CertificationBroadcast::CertificationExpired(certification))
+                    {
+                        error!("Failed to send certification to channel: {}", e);
+                    }
+                }
+                Err(e) => error!("Failed to commit transaction: {}", e),
+            },
+            Err(e) => {
+                error!("Failed to expire certification in read model: {}", e);
+                if let Err(e) = transaction.rollback().await {
+                    error!("Failed to rollback transaction: {}", e);
+                }
+            }
+        };
+
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "type")]
+pub enum CertificationEvent {
+    CertificationRecorded(CertificationRecorded),
+    CertificationExpiryWarned(CertificationExpiryWarned),
+    CertificationExpired(CertificationExpired),
+}
+
+#[cfg(feature = "connect")]
+impl TryFrom<eventstore::ResolvedEvent> for CertificationEvent {
+    type Error = EventParseError;
+
+    fn try_from(value: eventstore::ResolvedEvent) -> Result<Self, Self::Error> {
+        let event_data = value.event.ok_or(EventParseError::MissingEventData)?;
+        let event_json: Value = serde_json::from_slice(&event_data.data)
+            .map_err(EventParseError::DeserializationError)?;
+
+        match event_data.event_type {
+            s if s == CertificationRecorded::event_name() => {
+                Ok(CertificationEvent::CertificationRecorded(
+                    serde_json::from_value(event_json)
+                        .map_err(EventParseError::DeserializationError)?,
+                ))
+            }
+            s if s == CertificationExpiryWarned::event_name() => {
+                Ok(CertificationEvent::CertificationExpiryWarned(
+                    serde_json::from_value(event_json)
+                        .map_err(EventParseError::DeserializationError)?,
+                ))
+            }
+            s if s == CertificationExpired::event_name() => {
+                Ok(CertificationEvent::CertificationExpired(
+                    serde_json::from_value(event_json)
+                        .map_err(EventParseError::DeserializationError)?,
+                ))
+            }
+            _ => Err(EventParseError::UnknownEventType(event_data.event_type)),
+        }
+    }
+}
+
+impl CertificationEvent {
+    #[cfg(feature = "connect")]
+    pub fn subscription_filter() -> eventstore::SubscriptionFilter {
+        eventstore::SubscriptionFilter::on_stream_name().add_prefix("certification-")
+    }
+
+    #[cfg(feature = "connect")]
+    pub async fn handle_read_model(
+        &self,
+        handler: CertificationReadModelHandler,
+    ) -> anyhow::Result<()> {
+        match self {
+            CertificationEvent::CertificationRecorded(event) => {
+                event.handle_read_model(handler).await
+            }
+            CertificationEvent::CertificationExpiryWarned(event) => {
+                event.handle_read_model(handler).await
+            }
+            CertificationEvent::CertificationExpired(event) => {
+                event.handle_read_model(handler).await
+            }
+        }
+    }
+}
+
+/**
+ * Certification read model handler
+ * Holds all necessary service connections and state to handle certification events
+ */
+#[derive(Clone)]
+#[cfg(feature = "connect")]
+pub struct CertificationReadModelHandler {
+    eventstore: std::sync::Arc<eventstore::Client>,
+    db_pool: std::sync::Arc<sqlx::PgPool>,
+    app_state: AppState,
+}
+
+#[cfg(feature = "connect")]
+impl CertificationReadModelHandler {
+    pub fn new(
+        eventstore: std::sync::Arc<eventstore::Client>,
+        db_pool: std::sync::Arc<sqlx::PgPool>,
+        app_state: AppState,
+    ) -> Self {
+        Self {
+            eventstore,
+            db_pool,
+            app_state,
+        }
+    }
+
+    /**
+     * Subscribe to all certification events and handle them in the read model
+     */
+    pub async fn subscribe(&self) {
+        let snapshot_position = match self.get_snapshot_position().await {
+            Ok(position) => position,
+            Err(e) => {
+                error!("Failed to get snapshot position: {}", e);
+                return;
+            }
+        };
+
+        let mut certification_subscription = self
+            .eventstore
+            .subscribe_to_all(
+                &eventstore::SubscribeToAllOptions::default()
+                    .position(eventstore::StreamPosition::Position(eventstore::Position {
+                        commit: snapshot_position as u64,
+                        prepare: snapshot_position as u64,
+                    }))
+                    .filter(CertificationEvent::subscription_filter()),
+            )
+            .await;
+
+        while let Ok(resolved_event) = certification_subscription.next().await {
+            let position = resolved_event
+                .event
+                .as_ref()
+                .map(|event| event.position.commit);
+
+            let event: CertificationEvent = match resolved_event.try_into() {
+                Ok(event) => event,
+                Err(e) => {
+                    error!("Failed to parse event: {}", e);
+                    continue;
+                }
+            };
+
+            if let Err(e) = event.handle_read_model(self.clone()).await {
+                error!("Failed to handle event: {}", e);
+                continue;
+            }
+
+            if let Err(e) = self.set_snapshot_position(position).await {
+                error!("Failed to set snapshot position: {}", e);
+                continue;
+            }
+        }
+    }
+
+    /**
+     * Get the current snapshot position for certifications
+     */
+    pub async fn get_snapshot_position(&self) -> anyhow::Result<i64> {
+        use crate::projections::snapshot_position::{SnapshotPosition, SnapshotPositionKey};
+
+        let mut transaction = self.db_pool.begin().await?;
+
+        let snapshot_position =
+            SnapshotPosition::get_by_key(&mut transaction, SnapshotPositionKey::Certification)
+                .await?;
+
+        Ok(snapshot_position.map(|position| position.value).unwrap_or(0))
+    }
+
+    /**
+     * Set the current snapshot position for certifications
+     */
+    pub async fn set_snapshot_position(&self, position: Option<u64>) -> anyhow::Result<()> {
+        use crate::projections::snapshot_position::{SnapshotPosition, SnapshotPositionKey};
+
+        let mut transaction = self.db_pool.begin().await?;
+
+        if let Some(position) = position {
+            let snapshot_position = SnapshotPosition {
+                key: SnapshotPositionKey::Certification,
+                value: position as i64,
+            };
+
+            snapshot_position.insert(&mut transaction).await?;
+
+            transaction.commit().await?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,427 @@
+#[cfg(feature = "connect")]
+use {
+    log::error,
+    serde_json::Value,
+    crate::AppState,
+    super::EventParseError
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A change to a jobsite's scope, written up before it goes to the client
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ChangeOrderDrafted {
+    pub id: Uuid,
+    pub jobsite_id: Uuid,
+    pub title: String,
+    pub description: Option<String>,
+    /// What the change adds to the contract, negative when it takes away
+    pub amount_cents: i64,
+    /// Labor the change adds, negative when it takes away
+    pub minutes: i64,
+    pub drafted_at: DateTime<Utc>,
+}
+
+impl ChangeOrderDrafted {
+    pub fn event_name() -> String {
+        String::from("ChangeOrderDrafted")
+    }
+
+    #[cfg(feature = "connect")]
+    pub async fn handle_read_model(
+        &self,
+        handler: ChangeOrderReadModelHandler,
+    ) -> anyhow::Result<()> {
+        use crate::{projections::change_order::ChangeOrder, ChangeOrderBroadcast};
+
+        let mut transaction = handler
+            .db_pool
+            .begin()
+            .await
+            .expect("Failed to start transaction");
+
+        match ChangeOrder::draft(&mut transaction, self).await {
+            Ok(change_order) => match transaction.commit().await {
+                Ok(_) => {
+                    if let Err(e) = handler
+                        .app_state
+                        .change_order_tx
+                        .send(ChangeOrderBroadcast::ChangeOrderDrafted(change_order))
+                    {
+                        error!("Failed to send change order to channel: {}", e);
+                    }
+                }
+                Err(e) => error!("Failed to commit transaction: {}", e),
+            },
+            Err(e) => {
+                error!("Failed to draft change order in read model: {}", e);
+                if let Err(e) = transaction.rollback().await {
+                    error!("Failed to rollback transaction: {}", e);
+                }
+            }
+        };
+
+        Ok(())
+    }
+}
+
+/// A drafted change order was sent to the client for sign off
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ChangeOrderSubmitted {
+    pub id: Uuid,
+    pub submitted_at: DateTime<Utc>,
+}
+
+impl ChangeOrderSubmitted {
+    pub fn event_name() -> String {
+        String::from("ChangeOrderSubmitted")
+    }
+
+    #[cfg(feature = "connect")]
+    pub async fn handle_read_model(
+        &self,
+        handler: ChangeOrderReadModelHandler,
+    ) -> anyhow::Result<()> {
+        use crate::{projections::change_order::ChangeOrder, ChangeOrderBroadcast};
+
+        let mut transaction = handler
+            .db_pool
+            .begin()
+            .await
+            .expect("Failed to start transaction");
+
+        match ChangeOrder::submit(&mut transaction, self).await {
+            Ok(change_order) => match transaction.commit().await {
+                Ok(_) => {
+                    if let Err(e) = handler
+                        .app_state
+                        .change_order_tx
+                        .send(ChangeOrderBroadcast::ChangeOrderSubmitted(change_order))
+                    {
+                        error!("Failed to send change order to channel: {}", e);
+                    }
+                }
+                Err(e) => error!("Failed to commit transaction: {}", e),
+            },
+            Err(e) => {
+                error!("Failed to submit change order in read model: {}", e);
+                if let Err(e) = transaction.rollback().await {
+                    error!("Failed to rollback transaction: {}", e);
+                }
+            }
+        };
+
+        Ok(())
+    }
+}
+
+/// The client signed off on a change order, its amount and hours now count
+/// towards the jobsite's budget
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ChangeOrderApproved {
+    pub id: Uuid,
+    pub approved_at: DateTime<Utc>,
+}
+
+impl ChangeOrderApproved {
+    pub fn event_name() -> String {
+        String::from("ChangeOrderApproved")
+    }
+
+    #[cfg(feature = "connect")]
+    pub async fn handle_read_model(
+        &self,
+        handler: ChangeOrderReadModelHandler,
+    ) -> anyhow::Result<()> {
+        use crate::{
+            events::jobsite_budget::check_budget_thresholds,
+            projections::{change_order::ChangeOrder, jobsite_budget::JobsiteBudget},
+            ChangeOrderBroadcast,
+        };
+
+        let mut transaction = handler
+            .db_pool
+            .begin()
+            .await
+            .expect("Failed to start transaction");
+
+        let change_order = match ChangeOrder::approve(&mut transaction, self).await {
+            Ok(change_order) => change_order,
+            Err(e) => {
+                error!("Failed to approve change order in read model: {}", e);
+                if let Err(e) = transaction.rollback().await {
+                    error!("Failed to rollback transaction: {}", e);
+                }
+                return Ok(());
+            }
+        };
+
+        if let Err(e) = JobsiteBudget::refresh_change_orders(&mut transaction, &change_order.jobsite_id).await {
+            error!("Failed to update budgets in read model: {}", e);
+            if let Err(e) = transaction.rollback().await {
+                error!("Failed to rollback transaction: {}", e);
+            }
+            return Ok(());
+        }
+
+        match transaction.commit().await {
+            Ok(_) => {
+                // A change order that takes scope away can push a budget
+                // past a threshold
+                if let Err(e) = check_budget_thresholds(
+                    &handler.eventstore,
+                    &handler.db_pool,
+                    &[change_order.jobsite_id],
+                )
+                .await
+                {
+                    error!("Failed to check budget thresholds: {}", e);
+                }
+
+                if let Err(e) = handler
+                    .app_state
+                    .change_order_tx
+                    .send(ChangeOrderBroadcast::ChangeOrderApproved(change_order))
+                {
+                    error!("Failed to send change order to channel: {}", e);
+                }
+            }
+            Err(e) => error!("Failed to commit transaction: {}", e),
+        }
+
+        Ok(())
+    }
+}
+
+/// The client turned a change order down
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ChangeOrderRejected {
+    pub id: Uuid,
+    pub reason: String,
+    pub rejected_at: DateTime<Utc>,
+}
+
+impl ChangeOrderRejected {
+    pub fn event_name() -> String {
+        String::from("ChangeOrderRejected")
+    }
+
+    #[cfg(feature = "connect")]
+    pub async fn handle_read_model(
+        &self,
+        handler: ChangeOrderReadModelHandler,
+    ) -> anyhow::Result<()> {
+        use crate::{projections::change_order::ChangeOrder, ChangeOrderBroadcast};
+
+        let mut transaction = handler
+            .db_pool
+            .begin()
+            .await
+            .expect("Failed to start transaction");
+
+        match ChangeOrder::reject(&mut transaction, self).await {
+            Ok(change_order) => match transaction.commit().await {
+                Ok(_) => {
+                    if let Err(e) = handler
+                        .app_state
+                        .change_order_tx
+                        .send(ChangeOrderBroadcast::ChangeOrderRejected(change_order))
+                    {
+                        error!("Failed to send change order to channel: {}", e);
+                    }
+                }
+                Err(e) => error!("Failed to commit transaction: {}", e),
+            },
+            Err(e) => {
+                error!("Failed to reject change order in read model: {}", e);
+                if let Err(e) = transaction.rollback().await {
+                    error!("Failed to rollback transaction: {}", e);
+                }
+            }
+        };
+
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "type")]
+pub enum ChangeOrderEvent {
+    ChangeOrderDrafted(ChangeOrderDrafted),
+    ChangeOrderSubmitted(ChangeOrderSubmitted),
+    ChangeOrderApproved(ChangeOrderApproved),
+    ChangeOrderRejected(ChangeOrderRejected),
+}
+
+#[cfg(feature = "connect")]
+impl TryFrom<eventstore::ResolvedEvent> for ChangeOrderEvent {
+    type Error = EventParseError;
+
+    fn try_from(value: eventstore::ResolvedEvent) -> Result<Self, Self::Error> {
+        let event_data = value.event.ok_or(EventParseError::MissingEventData)?;
+        let event_json: Value = serde_json::from_slice(&event_data.data)
+            .map_err(EventParseError::DeserializationError)?;
+
+        match event_data.event_type {
+            s if s == ChangeOrderDrafted::event_name() => Ok(ChangeOrderEvent::ChangeOrderDrafted(
+                serde_json::from_value(event_json)
+                    .map_err(EventParseError::DeserializationError)?,
+            )),
+            s if s == ChangeOrderSubmitted::event_name() => {
+                Ok(ChangeOrderEvent::ChangeOrderSubmitted(
+                    serde_json::from_value(event_json)
+                        .map_err(EventParseError::DeserializationError)?,
+                ))
+            }
+            s if s == ChangeOrderApproved::event_name() => {
+                Ok(ChangeOrderEvent::ChangeOrderApproved(
+                    serde_json::from_value(event_json)
+                        .map_err(EventParseError::DeserializationError)?,
+                ))
+            }
+            s if s == ChangeOrderRejected::event_name() => {
+                Ok(ChangeOrderEvent::ChangeOrderRejected(
+                    serde_json::from_value(event_json)
+                        .map_err(EventParseError::DeserializationError)?,
+                ))
+            }
+            _ => Err(EventParseError::UnknownEventType(event_data.event_type)),
+        }
+    }
+}
+
+impl ChangeOrderEvent {
+    #[cfg(feature = "connect")]
+    pub fn subscription_filter() -> eventstore::SubscriptionFilter {
+        eventstore::SubscriptionFilter::on_stream_name().add_prefix("change_order-")
+    }
+
+    #[cfg(feature = "connect")]
+    pub async fn handle_read_model(
+        &self,
+        handler: ChangeOrderReadModelHandler,
+    ) -> anyhow::Result<()> {
+        match self {
+            ChangeOrderEvent::ChangeOrderDrafted(event) => event.handle_read_model(handler).await,
+            ChangeOrderEvent::ChangeOrderSubmitted(event) => event.handle_read_model(handler).await,
+            ChangeOrderEvent::ChangeOrderApproved(event) => event.handle_read_model(handler).await,
+            ChangeOrderEvent::ChangeOrderRejected(event) => event.handle_read_model(handler).await,
+        }
+    }
+}
+
+/**
+ * Change order read model handler
+ * Holds all necessary service connections and state to handle change order events
+ */
+#[derive(Clone)]
+#[cfg(feature = "connect")]
+pub struct ChangeOrderReadModelHandler {
+    eventstore: std::sync::Arc<eventstore::Client>,
+    db_pool: std::sync::Arc<sqlx::PgPool>,
+    app_state: AppState,
+}
+
+#[cfg(feature = "connect")]
+impl ChangeOrderReadModelHandler {
+    pub fn new(
+        eventstore: std::sync::Arc<eventstore::Client>,
+        db_pool: std::sync::Arc<sqlx::PgPool>,
+        app_state: AppState,
+    ) -> Self {
+        Self {
+            eventstore,
+            db_pool,
+            app_state,
+        }
+    }
+
+    /**
+     * Subscribe to all change order events and handle them in the read model
+     */
+    pub async fn subscribe(&self) {
+        let snapshot_position = match self.get_snapshot_position().await {
+            Ok(position) => position,
+            Err(e) => {
+                error!("Failed to get snapshot position: {}", e);
+                return;
+            }
+        };
+
+        let mut change_order_subscription = self
+            .eventstore
+            .subscribe_to_all(
+                &eventstore::SubscribeToAllOptions::default()
+                    .position(eventstore::StreamPosition::Position(eventstore::Position {
+                        commit: snapshot_position as u64,
+                        prepare: snapshot_position as u64,
+                    }))
+                    .filter(ChangeOrderEvent::subscription_filter()),
+            )
+            .await;
+
+        while let Ok(resolved_event) = change_order_subscription.next().await {
+            let position = resolved_event
+                .event
+                .as_ref()
+                .map(|event| event.position.commit);
+
+            let event: ChangeOrderEvent = match resolved_event.try_into() {
+                Ok(event) => event,
+                Err(e) => {
+                    error!("Failed to parse event: {}", e);
+                    continue;
+                }
+            };
+
+            if let Err(e) = event.handle_read_model(self.clone()).await {
+                error!("Failed to handle event: {}", e);
+                continue;
+            }
+
+            if let Err(e) = self.set_snapshot_position(position).await {
+                error!("Failed to set snapshot position: {}", e);
+                continue;
+            }
+        }
+    }
+
+    /**
+     * Get the current snapshot position for change orders
+     */
+    pub async fn get_snapshot_position(&self) -> anyhow::Result<i64> {
+        use crate::projections::snapshot_position::{SnapshotPosition, SnapshotPositionKey};
+
+        let mut transaction = self.db_pool.begin().await?;
+
+        let snapshot_position =
+            SnapshotPosition::get_by_key(&mut transaction, SnapshotPositionKey::ChangeOrder)
+                .await?;
+
+        Ok(snapshot_position.map(|position| position.value).unwrap_or(0))
+    }
+
+    /**
+     * Set the current snapshot position for change orders
+     */
+    pub async fn set_snapshot_position(&self, position: Option<u64>) -> anyhow::Result<()> {
+        use crate::projections::snapshot_position::{SnapshotPosition, SnapshotPositionKey};
+
+        let mut transaction = self.db_pool.begin().await?;
+
+        if let Some(position) = position {
+            let snapshot_position = SnapshotPosition {
+                key: SnapshotPositionKey::ChangeOrder,
+                value: position as i64,
+            };
+
+            snapshot_position.insert(&mut transaction).await?;
+
+            transaction.commit().await?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,208 @@
+#[cfg(feature = "connect")]
+use {
+    log::error,
+    serde_json::Value,
+    crate::AppState,
+    super::EventParseError
+};
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ClientCreated {
+    pub id: Uuid,
+    pub name: String,
+}
+
+impl ClientCreated {
+    pub fn event_name() -> String {
+        String::from("ClientCreated")
+    }
+
+    #[cfg(feature = "connect")]
+    pub async fn handle_read_model(&self, handler: ClientReadModelHandler) -> anyhow::Result<()> {
+        use crate::{projections::client::Client, ClientBroadcast};
+
+        let mut transaction = handler
+            .db_pool
+            .begin()
+            .await
+            .expect("Failed to start transaction");
+
+        match Client::create(&mut transaction, self).await {
+            Ok(client) => match transaction.commit().await {
+                Ok(_) => {
+                    if let Err(e) = handler
+                        .app_state
+                        .client_tx
+                        .send(ClientBroadcast::ClientCreated(client))
+                    {
+                        error!("Failed to send client to channel: {}", e);
+                    }
+                }
+                Err(e) => error!("Failed to commit transaction: {}", e),
+            },
+            Err(e) => {
+                error!("Failed to create client in read model: {}", e);
+                if let Err(e) = transaction.rollback().await {
+                    error!("Failed to rollback transaction: {}", e);
+                }
+            }
+        };
+
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "type")]
+pub enum ClientEvent {
+    ClientCreated(ClientCreated),
+}
+
+#[cfg(feature = "connect")]
+impl TryFrom<eventstore::ResolvedEvent> for ClientEvent {
+    type Error = EventParseError;
+
+    fn try_from(value: eventstore::ResolvedEvent) -> Result<Self, Self::Error> {
+        let event_data = value.event.ok_or(EventParseError::MissingEventData)?;
+        let event_json: Value = serde_json::from_slice(&event_data.data)
+            .map_err(EventParseError::DeserializationError)?;
+
+        match event_data.event_type {
+            s if s == ClientCreated::event_name() => Ok(ClientEvent::ClientCreated(
+                serde_json::from_value(event_json)
+                    .map_err(EventParseError::DeserializationError)?,
+            )),
+            _ => Err(EventParseError::UnknownEventType(event_data.event_type)),
+        }
+    }
+}
+
+impl ClientEvent {
+    #[cfg(feature = "connect")]
+    pub fn subscription_filter() -> eventstore::SubscriptionFilter {
+        eventstore::SubscriptionFilter::on_stream_name().add_prefix("client-")
+    }
+
+    #[cfg(feature = "connect")]
+    pub async fn handle_read_model(&self, handler: ClientReadModelHandler) -> anyhow::Result<()> {
+        match self {
+            ClientEvent::ClientCreated(event) => event.handle_read_model(handler).await,
+        }
+    }
+}
+
+/**
+ * Client read model handler
+ * Holds all necessary service connections and state to handle client events
+ */
+#[derive(Clone)]
+#[cfg(feature = "connect")]
+pub struct ClientReadModelHandler {
+    eventstore: std::sync::Arc<eventstore::Client>,
+    db_pool: std::sync::Arc<sqlx::PgPool>,
+    app_state: AppState,
+}
+
+#[cfg(feature = "connect")]
+impl ClientReadModelHandler {
+    pub fn new(
+        eventstore: std::sync::Arc<eventstore::Client>,
+        db_pool: std::sync::Arc<sqlx::PgPool>,
+        app_state: AppState,
+    ) -> Self {
+        Self {
+            eventstore,
+            db_pool,
+            app_state,
+        }
+    }
+
+    /**
+     * Subscribe to all client events and handle them in the read model
+     */
+    pub async fn subscribe(&self) {
+        let snapshot_position = match self.get_snapshot_position().await {
+            Ok(position) => position,
+            Err(e) => {
+                error!("Failed to get snapshot position: {}", e);
+                return;
+            }
+        };
+
+        let mut client_subscription = self
+            .eventstore
+            .subscribe_to_all(
+                &eventstore::SubscribeToAllOptions::default()
+                    .position(eventstore::StreamPosition::Position(eventstore::Position {
+                        commit: snapshot_position as u64,
+                        prepare: snapshot_position as u64,
+                    }))
+                    .filter(ClientEvent::subscription_filter()),
+            )
+            .await;
+
+        while let Ok(resolved_event) = client_subscription.next().await {
+            let position = resolved_event
+                .event
+                .as_ref()
+                .map(|event| event.position.commit);
+
+            let event: ClientEvent = match resolved_event.try_into() {
+                Ok(event) => event,
+                Err(e) => {
+                    error!("Failed to parse event: {}", e);
+                    continue;
+                }
+            };
+
+            if let Err(e) = event.handle_read_model(self.clone()).await {
+                error!("Failed to handle event: {}", e);
+                continue;
+            }
+
+            if let Err(e) = self.set_snapshot_position(position).await {
+                error!("Failed to set snapshot position: {}", e);
+                continue;
+            }
+        }
+    }
+
+    /**
+     * Get the current snapshot position for clients
+     */
+    pub async fn get_snapshot_position(&self) -> anyhow::Result<i64> {
+        use crate::projections::snapshot_position::{SnapshotPosition, SnapshotPositionKey};
+
+        let mut transaction = self.db_pool.begin().await?;
+
+        let snapshot_position =
+            SnapshotPosition::get_by_key(&mut transaction, SnapshotPositionKey::Client).await?;
+
+        Ok(snapshot_position.map(|position| position.value).unwrap_or(0))
+    }
+
+    /**
+     * Set the current snapshot position for clients
+     */
+    pub async fn set_snapshot_position(&self, position: Option<u64>) -> anyhow::Result<()> {
+        use crate::projections::snapshot_position::{SnapshotPosition, SnapshotPositionKey};
+
+        let mut transaction = self.db_pool.begin().await?;
+
+        if let Some(position) = position {
+            let snapshot_position = SnapshotPosition {
+                key: SnapshotPositionKey::Client,
+                value: position as i64,
+            };
+
+            snapshot_position.insert(&mut transaction).await?;
+
+            transaction.commit().await?;
+        }
+
+        Ok(())
+    }
+}
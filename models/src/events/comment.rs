@@ -0,0 +1,347 @@
+#[cfg(feature = "connect")]
+use {
+    log::error,
+    serde_json::Value,
+    crate::AppState,
+    super::EventParseError
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Recorded in the metadata of every comment event, the employee who posted,
+/// edited or deleted the comment
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct CommentMetadata {
+    #[serde(rename = "authorId")]
+    pub author_id: Uuid,
+}
+
+/// A comment added to a jobsite's thread
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CommentPosted {
+    pub id: Uuid,
+    pub jobsite_id: Uuid,
+    pub body: String,
+    /// Employees named with `@` in the body
+    #[serde(default)]
+    pub mentions: Vec<Uuid>,
+    pub posted_at: DateTime<Utc>,
+}
+
+impl CommentPosted {
+    pub fn event_name() -> String {
+        String::from("CommentPosted")
+    }
+
+    #[cfg(feature = "connect")]
+    pub async fn handle_read_model(
+        &self,
+        handler: CommentReadModelHandler,
+        metadata: Option<CommentMetadata>,
+    ) -> anyhow::Result<()> {
+        use crate::{projections::comment::Comment, CommentBroadcast};
+
+        let mut transaction = handler
+            .db_pool
+            .begin()
+            .await
+            .expect("Failed to start transaction");
+
+        let author_id = metadata.map(|metadata| metadata.author_id);
+
+        match Comment::post(&mut transaction, self, author_id).await {
+            Ok(comment) => match transaction.commit().await {
+                Ok(_) => {
+                    if let Err(e) = handler
+                        .app_state
+                        .comment_tx
+                        .send(CommentBroadcast::CommentPosted(comment))
+                    {
+                        error!("Failed to send comment to channel: {}", e);
+                    }
+                }
+                Err(e) => error!("Failed to commit transaction: {}", e),
+            },
+            Err(e) => {
+                error!("Failed to post comment in read model: {}", e);
+                if let Err(e) = transaction.rollback().await {
+                    error!("Failed to rollback transaction: {}", e);
+                }
+            }
+        };
+
+        Ok(())
+    }
+}
+
+/// The author rewrote their comment
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CommentEdited {
+    pub id: Uuid,
+    pub body: String,
+    #[serde(default)]
+    pub mentions: Vec<Uuid>,
+    pub edited_at: DateTime<Utc>,
+}
+
+impl CommentEdited {
+    pub fn event_name() -> String {
+        String::from("CommentEdited")
+    }
+
+    #[cfg(feature = "connect")]
+    pub async fn handle_read_model(&self, handler: CommentReadModelHandler) -> anyhow::Result<()> {
+        use crate::{projections::comment::Comment, CommentBroadcast};
+
+        let mut transaction = handler
+            .db_pool
+            .begin()
+            .await
+            .expect("Failed to start transaction");
+
+        match Comment::edit(&mut transaction, self).await {
+            Ok(comment) => match transaction.commit().await {
+                Ok(_) => {
+                    if let Err(e) = handler
+                        .app_state
+                        .comment_tx
+                        .send(CommentBroadcast::CommentEdited(comment))
+                    {
+                        error!("Failed to send comment to channel: {}", e);
+                    }
+                }
+                Err(e) => error!("Failed to commit transaction: {}", e),
+            },
+            Err(e) => {
+                error!("Failed to edit comment in read model: {}", e);
+                if let Err(e) = transaction.rollback().await {
+                    error!("Failed to rollback transaction: {}", e);
+                }
+            }
+        };
+
+        Ok(())
+    }
+}
+
+/// The author took their comment down, it drops out of the thread
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CommentDeleted {
+    pub id: Uuid,
+    pub deleted_at: DateTime<Utc>,
+}
+
+impl CommentDeleted {
+    pub fn event_name() -> String {
+        String::from("CommentDeleted")
+    }
+
+    #[cfg(feature = "connect")]
+    pub async fn handle_read_model(&self, handler: CommentReadModelHandler) -> anyhow::Result<()> {
+        use crate::{projections::comment::Comment, CommentBroadcast};
+
+        let mut transaction = handler
+            .db_pool
+            .begin()
+            .await
+            .expect("Failed to start transaction");
+
+        match Comment::delete(&mut transaction, self).await {
+            Ok(comment) => match transaction.commit().await {
+                Ok(_) => {
+                    if let Err(e) = handler
+                        .app_state
+                        .comment_tx
+                        .send(CommentBroadcast::CommentDeleted(comment))
+                    {
+                        error!("Failed to send comment to channel: {}", e);
+                    }
+                }
+                Err(e) => error!("Failed to commit transaction: {}", e),
+            },
+            Err(e) => {
+                error!("Failed to delete comment in read model: {}", e);
+                if let Err(e) = transaction.rollback().await {
+                    error!("Failed to rollback transaction: {}", e);
+                }
+            }
+        };
+
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "type")]
+pub enum CommentEvent {
+    CommentPosted(CommentPosted),
+    CommentEdited(CommentEdited),
+    CommentDeleted(CommentDeleted),
+}
+
+#[cfg(feature = "connect")]
+impl TryFrom<eventstore::ResolvedEvent> for CommentEvent {
+    type Error = EventParseError;
+
+    fn try_from(value: eventstore::ResolvedEvent) -> Result<Self, Self::Error> {
+        let event_data = value.event.ok_or(EventParseError::MissingEventData)?;
+        let event_json: Value = serde_json::from_slice(&event_data.data)
+            .map_err(EventParseError::DeserializationError)?;
+
+        match event_data.event_type {
+            s if s == CommentPosted::event_name() => Ok(CommentEvent::CommentPosted(
+                serde_json::from_value(event_json)
+                    .map_err(EventParseError::DeserializationError)?,
+            )),
+            s if s == CommentEdited::event_name() => Ok(CommentEvent::CommentEdited(
+                serde_json::from_value(event_json)
+                    .map_err(EventParseError::DeserializationError)?,
+            )),
+            s if s == CommentDeleted::event_name() => Ok(CommentEvent::CommentDeleted(
+                serde_json::from_value(event_json)
+                    .map_err(EventParseError::DeserializationError)?,
+            )),
+            _ => Err(EventParseError::UnknownEventType(event_data.event_type)),
+        }
+    }
+}
+
+impl CommentEvent {
+    #[cfg(feature = "connect")]
+    pub fn subscription_filter() -> eventstore::SubscriptionFilter {
+        eventstore::SubscriptionFilter::on_stream_name().add_prefix("comment-")
+    }
+
+    /// Only a new comment takes its author from the metadata, edits and
+    /// deletes are checked against that author before they're recorded
+    #[cfg(feature = "connect")]
+    pub async fn handle_read_model(
+        &self,
+        handler: CommentReadModelHandler,
+        metadata: Option<CommentMetadata>,
+    ) -> anyhow::Result<()> {
+        match self {
+            CommentEvent::CommentPosted(event) => event.handle_read_model(handler, metadata).await,
+            CommentEvent::CommentEdited(event) => event.handle_read_model(handler).await,
+            CommentEvent::CommentDeleted(event) => event.handle_read_model(handler).await,
+        }
+    }
+}
+
+/**
+ * Comment read model handler
+ * Holds all necessary service connections and state to handle comment events
+ */
+#[derive(Clone)]
+#[cfg(feature = "connect")]
+pub struct CommentReadModelHandler {
+    eventstore: std::sync::Arc<eventstore::Client>,
+    db_pool: std::sync::Arc<sqlx::PgPool>,
+    app_state: AppState,
+}
+
+#[cfg(feature = "connect")]
+impl CommentReadModelHandler {
+    pub fn new(
+        eventstore: std::sync::Arc<eventstore::Client>,
+        db_pool: std::sync::Arc<sqlx::PgPool>,
+        app_state: AppState,
+    ) -> Self {
+        Self {
+            eventstore,
+            db_pool,
+            app_state,
+        }
+    }
+
+    /**
+     * Subscribe to all comment events and handle them in the read model
+     */
+    pub async fn subscribe(&self) {
+        let snapshot_position = match self.get_snapshot_position().await {
+            Ok(position) => position,
+            Err(e) => {
+                error!("Failed to get snapshot position: {}", e);
+                return;
+            }
+        };
+
+        let mut comment_subscription = self
+            .eventstore
+            .subscribe_to_all(
+                &eventstore::SubscribeToAllOptions::default()
+                    .position(eventstore::StreamPosition::Position(eventstore::Position {
+                        commit: snapshot_position as u64,
+                        prepare: snapshot_position as u64,
+                    }))
+                    .filter(CommentEvent::subscription_filter()),
+            )
+            .await;
+
+        while let Ok(resolved_event) = comment_subscription.next().await {
+            let position = resolved_event
+                .event
+                .as_ref()
+                .map(|event| event.position.commit);
+            let metadata = resolved_event.event.as_ref().and_then(|event| {
+                serde_json::from_slice::<CommentMetadata>(&event.custom_metadata).ok()
+            });
+
+            let event: CommentEvent = match resolved_event.try_into() {
+                Ok(event) => event,
+                Err(e) => {
+                    error!("Failed to parse event: {}", e);
+                    continue;
+                }
+            };
+
+            if let Err(e) = event.handle_read_model(self.clone(), metadata).await {
+                error!("Failed to handle event: {}", e);
+                continue;
+            }
+
+            if let Err(e) = self.set_snapshot_position(position).await {
+                error!("Failed to set snapshot position: {}", e);
+                continue;
+            }
+        }
+    }
+
+    /**
+     * Get the current snapshot position for comments
+     */
+    pub async fn get_snapshot_position(&self) -> anyhow::Result<i64> {
+        use crate::projections::snapshot_position::{SnapshotPosition, SnapshotPositionKey};
+
+        let mut transaction = self.db_pool.begin().await?;
+
+        let snapshot_position =
+            SnapshotPosition::get_by_key(&mut transaction, SnapshotPositionKey::Comment).await?;
+
+        Ok(snapshot_position.map(|position| position.value).unwrap_or(0))
+    }
+
+    /**
+     * Set the current snapshot position for comments
+     */
+    pub async fn set_snapshot_position(&self, position: Option<u64>) -> anyhow::Result<()> {
+        use crate::projections::snapshot_position::{SnapshotPosition, SnapshotPositionKey};
+
+        let mut transaction = self.db_pool.begin().await?;
+
+        if let Some(position) = position {
+            let snapshot_position = SnapshotPosition {
+                key: SnapshotPositionKey::Comment,
+                value: position as i64,
+            };
+
+            snapshot_position.insert(&mut transaction).await?;
+
+            transaction.commit().await?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,268 @@
+#[cfg(feature = "connect")]
+use {
+    log::error,
+    serde_json::Value,
+    crate::AppState,
+    super::EventParseError
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A cost code added to a jobsite, or a task when it has a parent code
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CostCodeAdded {
+    pub id: Uuid,
+    pub jobsite_id: Uuid,
+    pub parent_id: Option<Uuid>,
+    pub code: String,
+    pub name: String,
+    pub added_at: DateTime<Utc>,
+}
+
+impl CostCodeAdded {
+    pub fn event_name() -> String {
+        String::from("CostCodeAdded")
+    }
+
+    #[cfg(feature = "connect")]
+    pub async fn handle_read_model(&self, handler: CostCodeReadModelHandler) -> anyhow::Result<()> {
+        use crate::{projections::cost_code::CostCode, CostCodeBroadcast};
+
+        let mut transaction = handler
+            .db_pool
+            .begin()
+            .await
+            .expect("Failed to start transaction");
+
+        match CostCode::add(&mut transaction, self).await {
+            Ok(cost_code) => match transaction.commit().await {
+                Ok(_) => {
+                    if let Err(e) = handler
+                        .app_state
+                        .cost_code_tx
+                        .send(CostCodeBroadcast::CostCodeAdded(cost_code))
+                    {
+                        error!("Failed to send cost code to channel: {}", e);
+                    }
+                }
+                Err(e) => error!("Failed to commit transaction: {}", e),
+            },
+            Err(e) => {
+                error!("Failed to add cost code in read model: {}", e);
+                if let Err(e) = transaction.rollback().await {
+                    error!("Failed to rollback transaction: {}", e);
+                }
+            }
+        };
+
+        Ok(())
+    }
+}
+
+/// A cost code no longer accepting time, along with any tasks under it.
+/// Time already logged against it is kept.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CostCodeRetired {
+    pub id: Uuid,
+    pub retired_at: DateTime<Utc>,
+}
+
+impl CostCodeRetired {
+    pub fn event_name() -> String {
+        String::from("CostCodeRetired")
+    }
+
+    #[cfg(feature = "connect")]
+    pub async fn handle_read_model(&self, handler: CostCodeReadModelHandler) -> anyhow::Result<()> {
+        use crate::{projections::cost_code::CostCode, CostCodeBroadcast};
+
+        let mut transaction = handler
+            .db_pool
+            .begin()
+            .await
+            .expect("Failed to start transaction");
+
+        match CostCode::retire(&mut transaction, self).await {
+            Ok(cost_code) => match transaction.commit().await {
+                Ok(_) => {
+                    if let Err(e) = handler
+                        .app_state
+                        .cost_code_tx
+                        .send(CostCodeBroadcast::CostCodeRetired(cost_code))
+                    {
+                        error!("Failed to send cost code to channel: {}", e);
+                    }
+                }
+                Err(e) => error!("Failed to commit transaction: {}", e),
+            },
+            Err(e) => {
+                error!("Failed to retire cost code in read model: {}", e);
+                if let Err(e) = transaction.rollback().await {
+                    error!("Failed to rollback transaction: {}", e);
+                }
+            }
+        };
+
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "type")]
+pub enum CostCodeEvent {
+    CostCodeAdded(CostCodeAdded),
+    CostCodeRetired(CostCodeRetired),
+}
+
+#[cfg(feature = "connect")]
+impl TryFrom<eventstore::ResolvedEvent> for CostCodeEvent {
+    type Error = EventParseError;
+
+    fn try_from(value: eventstore::ResolvedEvent) -> Result<Self, Self::Error> {
+        let event_data = value.event.ok_or(EventParseError::MissingEventData)?;
+        let event_json: Value = serde_json::from_slice(&event_data.data)
+            .map_err(EventParseError::DeserializationError)?;
+
+        match event_data.event_type {
+            s if s == CostCodeAdded::event_name() => Ok(CostCodeEvent::CostCodeAdded(
+                serde_json::from_value(event_json)
+                    .map_err(EventParseError::DeserializationError)?,
+            )),
+            s if s == CostCodeRetired::event_name() => Ok(CostCodeEvent::CostCodeRetired(
+                serde_json::from_value(event_json)
+                    .map_err(EventParseError::DeserializationError)?,
+            )),
+            _ => Err(EventParseError::UnknownEventType(event_data.event_type)),
+        }
+    }
+}
+
+impl CostCodeEvent {
+    #[cfg(feature = "connect")]
+    pub fn subscription_filter() -> eventstore::SubscriptionFilter {
+        eventstore::SubscriptionFilter::on_stream_name().add_prefix("cost_code-")
+    }
+
+    #[cfg(feature = "connect")]
+    pub async fn handle_read_model(&self, handler: CostCodeReadModelHandler) -> anyhow::Result<()> {
+        match self {
+            CostCodeEvent::CostCodeAdded(event) => event.handle_read_model(handler).await,
+            CostCodeEvent::CostCodeRetired(event) => event.handle_read_model(handler).await,
+        }
+    }
+}
+
+/**
+ * Cost code read model handler
+ * Holds all necessary service connections and state to handle cost code events
+ */
+#[derive(Clone)]
+#[cfg(feature = "connect")]
+pub struct CostCodeReadModelHandler {
+    eventstore: std::sync::Arc<eventstore::Client>,
+    db_pool: std::sync::Arc<sqlx::PgPool>,
+    app_state: AppState,
+}
+
+#[cfg(feature = "connect")]
+impl CostCodeReadModelHandler {
+    pub fn new(
+        eventstore: std::sync::Arc<eventstore::Client>,
+        db_pool: std::sync::Arc<sqlx::PgPool>,
+        app_state: AppState,
+    ) -> Self {
+        Self {
+            eventstore,
+            db_pool,
+            app_state,
+        }
+    }
+
+    /**
+     * Subscribe to all cost code events and handle them in the read model
+     */
+    pub async fn subscribe(&self) {
+        let snapshot_position = match self.get_snapshot_position().await {
+            Ok(position) => position,
+            Err(e) => {
+                error!("Failed to get snapshot position: {}", e);
+                return;
+            }
+        };
+
+        let mut cost_code_subscription = self
+            .eventstore
+            .subscribe_to_all(
+                &eventstore::SubscribeToAllOptions::default()
+                    .position(eventstore::StreamPosition::Position(eventstore::Position {
+                        commit: snapshot_position as u64,
+                        prepare: snapshot_position as u64,
+                    }))
+                    .filter(CostCodeEvent::subscription_filter()),
+            )
+            .await;
+
+        while let Ok(resolved_event) = cost_code_subscription.next().await {
+            let position = resolved_event
+                .event
+                .as_ref()
+                .map(|event| event.position.commit);
+
+            let event: CostCodeEvent = match resolved_event.try_into() {
+                Ok(event) => event,
+                Err(e) => {
+                    error!("Failed to parse event: {}", e);
+                    continue;
+                }
+            };
+
+            if let Err(e) = event.handle_read_model(self.clone()).await {
+                error!("Failed to handle event: {}", e);
+                continue;
+            }
+
+            if let Err(e) = self.set_snapshot_position(position).await {
+                error!("Failed to set snapshot position: {}", e);
+                continue;
+            }
+        }
+    }
+
+    /**
+     * Get the current snapshot position for cost codes
+     */
+    pub async fn get_snapshot_position(&self) -> anyhow::Result<i64> {
+        use crate::projections::snapshot_position::{SnapshotPosition, SnapshotPositionKey};
+
+        let mut transaction = self.db_pool.begin().await?;
+
+        let snapshot_position =
+            SnapshotPosition::get_by_key(&mut transaction, SnapshotPositionKey::CostCode).await?;
+
+        Ok(snapshot_position.map(|position| position.value).unwrap_or(0))
+    }
+
+    /**
+     * Set the current snapshot position for cost codes
+     */
+    pub async fn set_snapshot_position(&self, position: Option<u64>) -> anyhow::Result<()> {
+        use crate::projections::snapshot_position::{SnapshotPosition, SnapshotPositionKey};
+
+        let mut transaction = self.db_pool.begin().await?;
+
+        if let Some(position) = position {
+            let snapshot_position = SnapshotPosition {
+                key: SnapshotPositionKey::CostCode,
+                value: position as i64,
+            };
+
+            snapshot_position.insert(&mut transaction).await?;
+
+            transaction.commit().await?;
+        }
+
+        Ok(())
+    }
+}
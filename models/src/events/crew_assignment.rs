@@ -0,0 +1,283 @@
+#[cfg(feature = "connect")]
+use {
+    log::error,
+    serde_json::Value,
+    crate::AppState,
+    super::EventParseError
+};
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// An employee scheduled to work on a jobsite for a range of days, both
+/// dates inclusive
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CrewMemberAssigned {
+    pub id: Uuid,
+    pub employee_id: Uuid,
+    pub jobsite_id: Uuid,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub assigned_at: DateTime<Utc>,
+}
+
+impl CrewMemberAssigned {
+    pub fn event_name() -> String {
+        String::from("CrewMemberAssigned")
+    }
+
+    #[cfg(feature = "connect")]
+    pub async fn handle_read_model(
+        &self,
+        handler: CrewAssignmentReadModelHandler,
+    ) -> anyhow::Result<()> {
+        use crate::{projections::crew_assignment::CrewAssignment, CrewAssignmentBroadcast};
+
+        let mut transaction = handler
+            .db_pool
+            .begin()
+            .await
+            .expect("Failed to start transaction");
+
+        match CrewAssignment::assign(&mut transaction, self).await {
+            Ok(assignment) => match transaction.commit().await {
+                Ok(_) => {
+                    if let Err(e) = handler
+                        .app_state
+                        .crew_assignment_tx
+                        .send(CrewAssignmentBroadcast::CrewMemberAssigned(assignment))
+                    {
+                        error!("Failed to send crew assignment to channel: {}", e);
+                    }
+                }
+                Err(e) => error!("Failed to commit transaction: {}", e),
+            },
+            Err(e) => {
+                error!("Failed to assign crew member in read model: {}", e);
+                if let Err(e) = transaction.rollback().await {
+                    error!("Failed to rollback transaction: {}", e);
+                }
+            }
+        };
+
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CrewMemberUnassigned {
+    pub id: Uuid,
+    pub employee_id: Uuid,
+    pub unassigned_at: DateTime<Utc>,
+}
+
+impl CrewMemberUnassigned {
+    pub fn event_name() -> String {
+        String::from("CrewMemberUnassigned")
+    }
+
+    #[cfg(feature = "connect")]
+    pub async fn handle_read_model(
+        &self,
+        handler: CrewAssignmentReadModelHandler,
+    ) -> anyhow::Result<()> {
+        use crate::{projections::crew_assignment::CrewAssignment, CrewAssignmentBroadcast};
+
+        let mut transaction = handler
+            .db_pool
+            .begin()
+            .await
+            .expect("Failed to start transaction");
+
+        match CrewAssignment::unassign(&mut transaction, self).await {
+            Ok(assignment) => match transaction.commit().await {
+                Ok(_) => {
+                    if let Err(e) = handler
+                        .app_state
+                        .crew_assignment_tx
+                        .send(CrewAssignmentBroadcast::CrewMemberUnassigned(assignment))
+                    {
+                        error!("Failed to send crew assignment to channel: {}", e);
+                    }
+                }
+                Err(e) => error!("Failed to commit transaction: {}", e),
+            },
+            Err(e) => {
+                error!("Failed to unassign crew member in read model: {}", e);
+                if let Err(e) = transaction.rollback().await {
+                    error!("Failed to rollback transaction: {}", e);
+                }
+            }
+        };
+
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "type")]
+pub enum CrewAssignmentEvent {
+    CrewMemberAssigned(CrewMemberAssigned),
+    CrewMemberUnassigned(CrewMemberUnassigned),
+}
+
+#[cfg(feature = "connect")]
+impl TryFrom<eventstore::ResolvedEvent> for CrewAssignmentEvent {
+    type Error = EventParseError;
+
+    fn try_from(value: eventstore::ResolvedEvent) -> Result<Self, Self::Error> {
+        let event_data = value.event.ok_or(EventParseError::MissingEventData)?;
+        let event_json: Value = serde_json::from_slice(&event_data.data)
+            .map_err(EventParseError::DeserializationError)?;
+
+        match event_data.event_type {
+            s if s == CrewMemberAssigned::event_name() => {
+                Ok(CrewAssignmentEvent::CrewMemberAssigned(
+                    serde_json::from_value(event_json)
+                        .map_err(EventParseError::DeserializationError)?,
+                ))
+            }
+            s if s == CrewMemberUnassigned::event_name() => {
+                Ok(CrewAssignmentEvent::CrewMemberUnassigned(
+                    serde_json::from_value(event_json)
+                        .map_err(EventParseError::DeserializationError)?,
+                ))
+            }
+            _ => Err(EventParseError::UnknownEventType(event_data.event_type)),
+        }
+    }
+}
+
+impl CrewAssignmentEvent {
+    #[cfg(feature = "connect")]
+    pub fn subscription_filter() -> eventstore::SubscriptionFilter {
+        eventstore::SubscriptionFilter::on_stream_name().add_prefix("crew_schedule-")
+    }
+
+    #[cfg(feature = "connect")]
+    pub async fn handle_read_model(
+        &self,
+        handler: CrewAssignmentReadModelHandler,
+    ) -> anyhow::Result<()> {
+        match self {
+            CrewAssignmentEvent::CrewMemberAssigned(event) => event.handle_read_model(handler).await,
+            CrewAssignmentEvent::CrewMemberUnassigned(event) => {
+                event.handle_read_model(handler).await
+            }
+        }
+    }
+}
+
+/**
+ * Crew assignment read model handler
+ * Holds all necessary service connections and state to handle crew assignment events
+ */
+#[derive(Clone)]
+#[cfg(feature = "connect")]
+pub struct CrewAssignmentReadModelHandler {
+    eventstore: std::sync::Arc<eventstore::Client>,
+    db_pool: std::sync::Arc<sqlx::PgPool>,
+    app_state: AppState,
+}
+
+#[cfg(feature = "connect")]
+impl CrewAssignmentReadModelHandler {
+    pub fn new(
+        eventstore: std::sync::Arc<eventstore::Client>,
+        db_pool: std::sync::Arc<sqlx::PgPool>,
+        app_state: AppState,
+    ) -> Self {
+        Self {
+            eventstore,
+            db_pool,
+            app_state,
+        }
+    }
+
+    /**
+     * Subscribe to all crew assignment events and handle them in the read model
+     */
+    pub async fn subscribe(&self) {
+        let snapshot_position = match self.get_snapshot_position().await {
+            Ok(position) => position,
+            Err(e) => {
+                error!("Failed to get snapshot position: {}", e);
+                return;
+            }
+        };
+
+        let mut crew_assignment_subscription = self
+            .eventstore
+            .subscribe_to_all(
+                &eventstore::SubscribeToAllOptions::default()
+                    .position(eventstore::StreamPosition::Position(eventstore::Position {
+                        commit: snapshot_position as u64,
+                        prepare: snapshot_position as u64,
+                    }))
+                    .filter(CrewAssignmentEvent::subscription_filter()),
+            )
+            .await;
+
+        while let Ok(resolved_event) = crew_assignment_subscription.next().await {
+            let position = resolved_event
+                .event
+                .as_ref()
+                .map(|event| event.position.commit);
+
+            let event: CrewAssignmentEvent = match resolved_event.try_into() {
+                Ok(event) => event,
+                Err(e) => {
+                    error!("Failed to parse event: {}", e);
+                    continue;
+                }
+            };
+
+            if let Err(e) = event.handle_read_model(self.clone()).await {
+                error!("Failed to handle event: {}", e);
+                continue;
+            }
+
+            if let Err(e) = self.set_snapshot_position(position).await {
+                error!("Failed to set snapshot position: {}", e);
+                continue;
+            }
+        }
+    }
+
+    /**
+     * Get the current snapshot position for crew assignments
+     */
+    pub async fn get_snapshot_position(&self) -> anyhow::Result<i64> {
+        use crate::projections::snapshot_position::{SnapshotPosition, SnapshotPositionKey};
+
+        let mut transaction = self.db_pool.begin().await?;
+
+        let snapshot_position =
+            SnapshotPosition::get_by_key(&mut transaction, SnapshotPositionKey::CrewAssignment).await?;
+
+        Ok(snapshot_position.map(|position| position.value).unwrap_or(0))
+    }
+
+    /**
+     * Set the current snapshot position for crew assignments
+     */
+    pub async fn set_snapshot_position(&self, position: Option<u64>) -> anyhow::Result<()> {
+        use crate::projections::snapshot_position::{SnapshotPosition, SnapshotPositionKey};
+
+        let mut transaction = self.db_pool.begin().await?;
+
+        if let Some(position) = position {
+            let snapshot_position = SnapshotPosition {
+                key: SnapshotPositionKey::CrewAssignment,
+                value: position as i64,
+            };
+
+            snapshot_position.insert(&mut transaction).await?;
+
+            transaction.commit().await?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,332 @@
+#[cfg(feature = "connect")]
+use {
+    log::error,
+    serde_json::Value,
+    crate::AppState,
+    super::EventParseError
+};
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::projections::daily_log::{DelayCause, WeatherCondition};
+
+/// A day's log written or rewritten by the foreman, until it's submitted
+#[derive(Serialize, Deserialize, Debug)]
+pub struct DailyLogSaved {
+    pub jobsite_id: Uuid,
+    pub log_date: NaiveDate,
+    pub crew_count: i32,
+    pub weather: WeatherCondition,
+    pub notes: Option<String>,
+    pub saved_at: DateTime<Utc>,
+}
+
+impl DailyLogSaved {
+    pub fn event_name() -> String {
+        String::from("DailyLogSaved")
+    }
+
+    #[cfg(feature = "connect")]
+    pub async fn handle_read_model(&self, handler: DailyLogReadModelHandler) -> anyhow::Result<()> {
+        use crate::{projections::daily_log::DailyLog, DailyLogBroadcast};
+
+        let mut transaction = handler
+            .db_pool
+            .begin()
+            .await
+            .expect("Failed to start transaction");
+
+        match DailyLog::save(&mut transaction, self).await {
+            // A save recorded after the day was submitted changes nothing
+            Ok(None) => {}
+            Ok(Some(daily_log)) => match transaction.commit().await {
+                Ok(_) => {
+                    if let Err(e) = handler
+                        .app_state
+                        .daily_log_tx
+                        .send(DailyLogBroadcast::DailyLogSaved(daily_log))
+                    {
+                        error!("Failed to send daily log to channel: {}", e);
+                    }
+                }
+                Err(e) => error!("Failed to commit transaction: {}", e),
+            },
+            Err(e) => {
+                error!("Failed to save daily log in read model: {}", e);
+                if let Err(e) = transaction.rollback().await {
+                    error!("Failed to rollback transaction: {}", e);
+                }
+            }
+        };
+
+        Ok(())
+    }
+}
+
+/// Time lost on a logged day and why
+#[derive(Serialize, Deserialize, Debug)]
+pub struct DailyLogDelayRecorded {
+    pub id: Uuid,
+    pub jobsite_id: Uuid,
+    pub log_date: NaiveDate,
+    pub cause: DelayCause,
+    pub minutes_lost: i64,
+    pub reason: String,
+    pub recorded_at: DateTime<Utc>,
+}
+
+impl DailyLogDelayRecorded {
+    pub fn event_name() -> String {
+        String::from("DailyLogDelayRecorded")
+    }
+
+    #[cfg(feature = "connect")]
+    pub async fn handle_read_model(&self, handler: DailyLogReadModelHandler) -> anyhow::Result<()> {
+        use crate::{projections::daily_log::DailyLog, DailyLogBroadcast};
+
+        let mut transaction = handler
+            .db_pool
+            .begin()
+            .await
+            .expect("Failed to start transaction");
+
+        match DailyLog::record_delay(&mut transaction, self).await {
+            Ok(daily_log) => match transaction.commit().await {
+                Ok(_) => {
+                    if let Err(e) = handler
+                        .app_state
+                        .daily_log_tx
+                        .send(DailyLogBroadcast::DailyLogDelayRecorded(daily_log))
+                    {
+                        error!("Failed to send daily log to channel: {}", e);
+                    }
+                }
+                Err(e) => error!("Failed to commit transaction: {}", e),
+            },
+            Err(e) => {
+                error!("Failed to record daily log delay in read model: {}", e);
+                if let Err(e) = transaction.rollback().await {
+                    error!("Failed to rollback transaction: {}", e);
+                }
+            }
+        };
+
+        Ok(())
+    }
+}
+
+/// A day's log handed in, it can no longer be changed
+#[derive(Serialize, Deserialize, Debug)]
+pub struct DailyLogSubmitted {
+    pub jobsite_id: Uuid,
+    pub log_date: NaiveDate,
+    pub submitted_at: DateTime<Utc>,
+}
+
+impl DailyLogSubmitted {
+    pub fn event_name() -> String {
+        String::from("DailyLogSubmitted")
+    }
+
+    #[cfg(feature = "connect")]
+    pub async fn handle_read_model(&self, handler: DailyLogReadModelHandler) -> anyhow::Result<()> {
+        use crate::{projections::daily_log::DailyLog, DailyLogBroadcast};
+
+        let mut transaction = handler
+            .db_pool
+            .begin()
+            .await
+            .expect("Failed to start transaction");
+
+        match DailyLog::submit(&mut transaction, self).await {
+            Ok(daily_log) => match transaction.commit().await {
+                Ok(_) => {
+                    if let Err(e) = handler
+                        .app_state
+                        .daily_log_tx
+                        .send(DailyLogBroadcast::DailyLogSubmitted(daily_log))
+                    {
+                        error!("Failed to send daily log to channel: {}", e);
+                    }
+                }
+                Err(e) => error!("Failed to commit transaction: {}", e),
+            },
+            Err(e) => {
+                error!("Failed to submit daily log in read model: {}", e);
+                if let Err(e) = transaction.rollback().await {
+                    error!("Failed to rollback transaction: {}", e);
+                }
+            }
+        };
+
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "type")]
+pub enum DailyLogEvent {
+    DailyLogSaved(DailyLogSaved),
+    DailyLogDelayRecorded(DailyLogDelayRecorded),
+    DailyLogSubmitted(DailyLogSubmitted),
+}
+
+#[cfg(feature = "connect")]
+impl TryFrom<eventstore::ResolvedEvent> for DailyLogEvent {
+    type Error = EventParseError;
+
+    fn try_from(value: eventstore::ResolvedEvent) -> Result<Self, Self::Error> {
+        let event_data = value.event.ok_or(EventParseError::MissingEventData)?;
+        let event_json: Value = serde_json::from_slice(&event_data.data)
+            .map_err(EventParseError::DeserializationError)?;
+
+        match event_data.event_type {
+            s if s == DailyLogSaved::event_name() => Ok(DailyLogEvent::DailyLogSaved(
+                serde_json::from_value(event_json)
+                    .map_err(EventParseError::DeserializationError)?,
+            )),
+            s if s == DailyLogDelayRecorded::event_name() => {
+                Ok(DailyLogEvent::DailyLogDelayRecorded(
+                    serde_json::from_value(event_json)
+                        .map_err(EventParseError::DeserializationError)?,
+                ))
+            }
+            s if s == DailyLogSubmitted::event_name() => Ok(DailyLogEvent::DailyLogSubmitted(
+                serde_json::from_value(event_json)
+                    .map_err(EventParseError::DeserializationError)?,
+            )),
+            _ => Err(EventParseError::UnknownEventType(event_data.event_type)),
+        }
+    }
+}
+
+impl DailyLogEvent {
+    #[cfg(feature = "connect")]
+    pub fn subscription_filter() -> eventstore::SubscriptionFilter {
+        eventstore::SubscriptionFilter::on_stream_name().add_prefix("daily_log-")
+    }
+
+    #[cfg(feature = "connect")]
+    pub async fn handle_read_model(&self, handler: DailyLogReadModelHandler) -> anyhow::Result<()> {
+        match self {
+            DailyLogEvent::DailyLogSaved(event) => event.handle_read_model(handler).await,
+            DailyLogEvent::DailyLogDelayRecorded(event) => event.handle_read_model(handler).await,
+            DailyLogEvent::DailyLogSubmitted(event) => event.handle_read_model(handler).await,
+        }
+    }
+}
+
+/**
+ * Daily log read model handler
+ * Holds all necessary service connections and state to handle daily log events
+ */
+#[derive(Clone)]
+#[cfg(feature = "connect")]
+pub struct DailyLogReadModelHandler {
+    eventstore: std::sync::Arc<eventstore::Client>,
+    db_pool: std::sync::Arc<sqlx::PgPool>,
+    app_state: AppState,
+}
+
+#[cfg(feature = "connect")]
+impl DailyLogReadModelHandler {
+    pub fn new(
+        eventstore: std::sync::Arc<eventstore::Client>,
+        db_pool: std::sync::Arc<sqlx::PgPool>,
+        app_state: AppState,
+    ) -> Self {
+        Self {
+            eventstore,
+            db_pool,
+            app_state,
+        }
+    }
+
+    /**
+     * Subscribe to all daily log events and handle them in the read model
+     */
+    pub async fn subscribe(&self) {
+        let snapshot_position = match self.get_snapshot_position().await {
+            Ok(position) => position,
+            Err(e) => {
+                error!("Failed to get snapshot position: {}", e);
+                return;
+            }
+        };
+
+        let mut daily_log_subscription = self
+            .eventstore
+            .subscribe_to_all(
+                &eventstore::SubscribeToAllOptions::default()
+                    .position(eventstore::StreamPosition::Position(eventstore::Position {
+                        commit: snapshot_position as u64,
+                        prepare: snapshot_position as u64,
+                    }))
+                    .filter(DailyLogEvent::subscription_filter()),
+            )
+            .await;
+
+        while let Ok(resolved_event) = daily_log_subscription.next().await {
+            let position = resolved_event
+                .event
+                .as_ref()
+                .map(|event| event.position.commit);
+
+            let event: DailyLogEvent = match resolved_event.try_into() {
+                Ok(event) => event,
+                Err(e) => {
+                    error!("Failed to parse event: {}", e);
+                    continue;
+                }
+            };
+
+            if let Err(e) = event.handle_read_model(self.clone()).await {
+                error!("Failed to handle event: {}", e);
+                continue;
+            }
+
+            if let Err(e) = self.set_snapshot_position(position).await {
+                error!("Failed to set snapshot position: {}", e);
+                continue;
+            }
+        }
+    }
+
+    /**
+     * Get the current snapshot position for daily logs
+     */
+    pub async fn get_snapshot_position(&self) -> anyhow::Result<i64> {
+        use crate::projections::snapshot_position::{SnapshotPosition, SnapshotPositionKey};
+
+        let mut transaction = self.db_pool.begin().await?;
+
+        let snapshot_position =
+            SnapshotPosition::get_by_key(&mut transaction, SnapshotPositionKey::DailyLog).await?;
+
+        Ok(snapshot_position.map(|position| position.value).unwrap_or(0))
+    }
+
+    /**
+     * Set the current snapshot position for daily logs
+     */
+    pub async fn set_snapshot_position(&self, position: Option<u64>) -> anyhow::Result<()> {
+        use crate::projections::snapshot_position::{SnapshotPosition, SnapshotPositionKey};
+
+        let mut transaction = self.db_pool.begin().await?;
+
+        if let Some(position) = position {
+            let snapshot_position = SnapshotPosition {
+                key: SnapshotPositionKey::DailyLog,
+                value: position as i64,
+            };
+
+            snapshot_position.insert(&mut transaction).await?;
+
+            transaction.commit().await?;
+        }
+
+        Ok(())
+    }
+}
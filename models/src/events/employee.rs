@@ -0,0 +1,314 @@
+#[cfg(feature = "connect")]
+use {
+    log::error,
+    serde_json::Value,
+    crate::AppState,
+    super::EventParseError
+};
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct EmployeeCreated {
+    pub id: Uuid,
+    pub name: String,
+    #[serde(default)]
+    pub trade: Option<String>,
+}
+
+impl EmployeeCreated {
+    pub fn event_name() -> String {
+        String::from("EmployeeCreated")
+    }
+
+    #[cfg(feature = "connect")]
+    pub async fn handle_read_model(&self, handler: EmployeeReadModelHandler) -> anyhow::Result<()> {
+        use crate::{projections::employee::Employee, EmployeeBroadcast};
+
+        let mut transaction = handler
+            .db_pool
+            .begin()
+            .await
+            .expect("Failed to start transaction");
+
+        match Employee::create(&mut transaction, self).await {
+            Ok(employee) => match transaction.commit().await {
+                Ok(_) => {
+                    if let Err(e) = handler
+                        .app_state
+                        .employee_tx
+                        .send(EmployeeBroadcast::EmployeeCreated(employee))
+                    {
+                        error!("Failed to send employee to channel: {}", e);
+                    }
+                }
+                Err(e) => error!("Failed to commit transaction: {}", e),
+            },
+            Err(e) => {
+                error!("Failed to create employee in read model: {}", e);
+                if let Err(e) = transaction.rollback().await {
+                    error!("Failed to rollback transaction: {}", e);
+                }
+            }
+        };
+
+        Ok(())
+    }
+}
+
+/// An employee leaving the company, they can't be scheduled or clock in from
+/// `terminated_on` onwards
+#[derive(Serialize, Deserialize, Debug)]
+pub struct EmployeeTerminated {
+    pub id: Uuid,
+    pub terminated_on: NaiveDate,
+    pub terminated_at: DateTime<Utc>,
+}
+
+impl EmployeeTerminated {
+    pub fn event_name() -> String {
+        String::from("EmployeeTerminated")
+    }
+
+    #[cfg(feature = "connect")]
+    pub async fn handle_read_model(&self, handler: EmployeeReadModelHandler) -> anyhow::Result<()> {
+        use crate::{projections::employee::Employee, EmployeeBroadcast};
+
+        let mut transaction = handler
+            .db_pool
+            .begin()
+            .await
+            .expect("Failed to start transaction");
+
+        match Employee::terminate(&mut transaction, self).await {
+            Ok(employee) => match transaction.commit().await {
+                Ok(_) => {
+                    if let Err(e) = handler
+                        .app_state
+                        .employee_tx
+                        .send(EmployeeBroadcast::EmployeeTerminated(employee))
+                    {
+                        error!("Failed to send employee to channel: {}", e);
+                    }
+                }
+                Err(e) => error!("Failed to commit transaction: {}", e),
+            },
+            Err(e) => {
+                error!("Failed to terminate employee in read model: {}", e);
+                if let Err(e) = transaction.rollback().await {
+                    error!("Failed to rollback transaction: {}", e);
+                }
+            }
+        };
+
+        Ok(())
+    }
+}
+
+/// An employee's kiosk PIN, only its signature is recorded so the PIN can be
+/// checked without being kept
+#[derive(Serialize, Deserialize, Debug)]
+pub struct EmployeePinSet {
+    pub id: Uuid,
+    pub pin_hash: String,
+    pub set_at: DateTime<Utc>,
+}
+
+impl EmployeePinSet {
+    pub fn event_name() -> String {
+        String::from("EmployeePinSet")
+    }
+
+    #[cfg(feature = "connect")]
+    pub async fn handle_read_model(&self, handler: EmployeeReadModelHandler) -> anyhow::Result<()> {
+        use crate::projections::employee::Employee;
+
+        let mut transaction = handler
+            .db_pool
+            .begin()
+            .await
+            .expect("Failed to start transaction");
+
+        match Employee::set_pin(&mut transaction, self).await {
+            Ok(_) => {
+                if let Err(e) = transaction.commit().await {
+                    error!("Failed to commit transaction: {}", e);
+                }
+            }
+            Err(e) => {
+                error!("Failed to set employee pin in read model: {}", e);
+                if let Err(e) = transaction.rollback().await {
+                    error!("Failed to rollback transaction: {}", e);
+                }
+            }
+        };
+
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "type")]
+pub enum EmployeeEvent {
+    EmployeeCreated(EmployeeCreated),
+    EmployeeTerminated(EmployeeTerminated),
+    EmployeePinSet(EmployeePinSet),
+}
+
+#[cfg(feature = "connect")]
+impl TryFrom<eventstore::ResolvedEvent> for EmployeeEvent {
+    type Error = EventParseError;
+
+    fn try_from(value: eventstore::ResolvedEvent) -> Result<Self, Self::Error> {
+        let event_data = value.event.ok_or(EventParseError::MissingEventData)?;
+        let event_json: Value = serde_json::from_slice(&event_data.data)
+            .map_err(EventParseError::DeserializationError)?;
+
+        match event_data.event_type {
+            s if s == EmployeeCreated::event_name() => Ok(EmployeeEvent::EmployeeCreated(
+                serde_json::from_value(event_json)
+                    .map_err(EventParseError::DeserializationError)?,
+            )),
+            s if s == EmployeeTerminated::event_name() => Ok(EmployeeEvent::EmployeeTerminated(
+                serde_json::from_value(event_json)
+                    .map_err(EventParseError::DeserializationError)?,
+            )),
+            s if s == EmployeePinSet::event_name() => Ok(EmployeeEvent::EmployeePinSet(
+                serde_json::from_value(event_json)
+                    .map_err(EventParseError::DeserializationError)?,
+            )),
+            _ => Err(EventParseError::UnknownEventType(event_data.event_type)),
+        }
+    }
+}
+
+impl EmployeeEvent {
+    #[cfg(feature = "connect")]
+    pub fn subscription_filter() -> eventstore::SubscriptionFilter {
+        eventstore::SubscriptionFilter::on_stream_name().add_prefix("employee-")
+    }
+
+    #[cfg(feature = "connect")]
+    pub async fn handle_read_model(&self, handler: EmployeeReadModelHandler) -> anyhow::Result<()> {
+        match self {
+            EmployeeEvent::EmployeeCreated(event) => event.handle_read_model(handler).await,
+            EmployeeEvent::EmployeeTerminated(event) => event.handle_read_model(handler).await,
+            EmployeeEvent::EmployeePinSet(event) => event.handle_read_model(handler).await,
+        }
+    }
+}
+
+/**
+ * Employee read model handler
+ * Holds all necessary service connections and state to handle employee events
+ */
+#[derive(Clone)]
+#[cfg(feature = "connect")]
+pub struct EmployeeReadModelHandler {
+    eventstore: std::sync::Arc<eventstore::Client>,
+    db_pool: std::sync::Arc<sqlx::PgPool>,
+    app_state: AppState,
+}
+
+#[cfg(feature = "connect")]
+impl EmployeeReadModelHandler {
+    pub fn new(
+        eventstore: std::sync::Arc<eventstore::Client>,
+        db_pool: std::sync::Arc<sqlx::PgPool>,
+        app_state: AppState,
+    ) -> Self {
+        Self {
+            eventstore,
+            db_pool,
+            app_state,
+        }
+    }
+
+    /**
+     * Subscribe to all employee events and handle them in the read model
+     */
+    pub async fn subscribe(&self) {
+        let snapshot_position = match self.get_snapshot_position().await {
+            Ok(position) => position,
+            Err(e) => {
+                error!("Failed to get snapshot position: {}", e);
+                return;
+            }
+        };
+
+        let mut employee_subscription = self
+            .eventstore
+            .subscribe_to_all(
+                &eventstore::SubscribeToAllOptions::default()
+                    .position(eventstore::StreamPosition::Position(eventstore::Position {
+                        commit: snapshot_position as u64,
+                        prepare: snapshot_position as u64,
+                    }))
+                    .filter(EmployeeEvent::subscription_filter()),
+            )
+            .await;
+
+        while let Ok(resolved_event) = employee_subscription.next().await {
+            let position = resolved_event
+                .event
+                .as_ref()
+                .map(|event| event.position.commit);
+
+            let event: EmployeeEvent = match resolved_event.try_into() {
+                Ok(event) => event,
+                Err(e) => {
+                    error!("Failed to parse event: {}", e);
+                    continue;
+                }
+            };
+
+            if let Err(e) = event.handle_read_model(self.clone()).await {
+                error!("Failed to handle event: {}", e);
+                continue;
+            }
+
+            if let Err(e) = self.set_snapshot_position(position).await {
+                error!("Failed to set snapshot position: {}", e);
+                continue;
+            }
+        }
+    }
+
+    /**
+     * Get the current snapshot position for employees
+     */
+    pub async fn get_snapshot_position(&self) -> anyhow::Result<i64> {
+        use crate::projections::snapshot_position::{SnapshotPosition, SnapshotPositionKey};
+
+        let mut transaction = self.db_pool.begin().await?;
+
+        let snapshot_position =
+            SnapshotPosition::get_by_key(&mut transaction, SnapshotPositionKey::Employee).await?;
+
+        Ok(snapshot_position.map(|position| position.value).unwrap_or(0))
+    }
+
+    /**
+     * Set the current snapshot position for employees
+     */
+    pub async fn set_snapshot_position(&self, position: Option<u64>) -> anyhow::Result<()> {
+        use crate::projections::snapshot_position::{SnapshotPosition, SnapshotPositionKey};
+
+        let mut transaction = self.db_pool.begin().await?;
+
+        if let Some(position) = position {
+            let snapshot_position = SnapshotPosition {
+                key: SnapshotPositionKey::Employee,
+                value: position as i64,
+            };
+
+            snapshot_position.insert(&mut transaction).await?;
+
+            transaction.commit().await?;
+        }
+
+        Ok(())
+    }
+}
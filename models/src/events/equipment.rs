@@ -0,0 +1,336 @@
+#[cfg(feature = "connect")]
+use {
+    log::error,
+    serde_json::Value,
+    crate::AppState,
+    super::EventParseError
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A piece of equipment added to the catalog, it starts out in the yard
+#[derive(Serialize, Deserialize, Debug)]
+pub struct EquipmentAdded {
+    pub id: Uuid,
+    pub name: String,
+    pub serial_number: Option<String>,
+    pub added_at: DateTime<Utc>,
+}
+
+impl EquipmentAdded {
+    pub fn event_name() -> String {
+        String::from("EquipmentAdded")
+    }
+
+    #[cfg(feature = "connect")]
+    pub async fn handle_read_model(
+        &self,
+        handler: EquipmentReadModelHandler,
+    ) -> anyhow::Result<()> {
+        use crate::{projections::equipment::Equipment, EquipmentBroadcast};
+
+        let mut transaction = handler
+            .db_pool
+            .begin()
+            .await
+            .expect("Failed to start transaction");
+
+        match Equipment::add(&mut transaction, self).await {
+            Ok(equipment) => match transaction.commit().await {
+                Ok(_) => {
+                    if let Err(e) = handler
+                        .app_state
+                        .equipment_tx
+                        .send(EquipmentBroadcast::EquipmentAdded(equipment))
+                    {
+                        error!("Failed to send equipment to channel: {}", e);
+                    }
+                }
+                Err(e) => error!("Failed to commit transaction: {}", e),
+            },
+            Err(e) => {
+                error!("Failed to add equipment in read model: {}", e);
+                if let Err(e) = transaction.rollback().await {
+                    error!("Failed to rollback transaction: {}", e);
+                }
+            }
+        };
+
+        Ok(())
+    }
+}
+
+/// Equipment taken out of the yard to a jobsite
+#[derive(Serialize, Deserialize, Debug)]
+pub struct EquipmentCheckedOut {
+    pub id: Uuid,
+    pub checkout_id: Uuid,
+    pub jobsite_id: Uuid,
+    /// Who took it, when someone signed for it
+    pub employee_id: Option<Uuid>,
+    pub checked_out_at: DateTime<Utc>,
+}
+
+impl EquipmentCheckedOut {
+    pub fn event_name() -> String {
+        String::from("EquipmentCheckedOut")
+    }
+
+    #[cfg(feature = "connect")]
+    pub async fn handle_read_model(
+        &self,
+        handler: EquipmentReadModelHandler,
+    ) -> anyhow::Result<()> {
+        use crate::{projections::equipment::Equipment, EquipmentBroadcast};
+
+        let mut transaction = handler
+            .db_pool
+            .begin()
+            .await
+            .expect("Failed to start transaction");
+
+        match Equipment::check_out(&mut transaction, self).await {
+            Ok(equipment) => match transaction.commit().await {
+                Ok(_) => {
+                    if let Err(e) = handler
+                        .app_state
+                        .equipment_tx
+                        .send(EquipmentBroadcast::EquipmentCheckedOut(equipment))
+                    {
+                        error!("Failed to send equipment to channel: {}", e);
+                    }
+                }
+                Err(e) => error!("Failed to commit transaction: {}", e),
+            },
+            Err(e) => {
+                error!("Failed to check out equipment in read model: {}", e);
+                if let Err(e) = transaction.rollback().await {
+                    error!("Failed to rollback transaction: {}", e);
+                }
+            }
+        };
+
+        Ok(())
+    }
+}
+
+/// Equipment brought back to the yard from a jobsite
+#[derive(Serialize, Deserialize, Debug)]
+pub struct EquipmentReturned {
+    pub id: Uuid,
+    pub checkout_id: Uuid,
+    pub jobsite_id: Uuid,
+    pub returned_at: DateTime<Utc>,
+}
+
+impl EquipmentReturned {
+    pub fn event_name() -> String {
+        String::from("EquipmentReturned")
+    }
+
+    #[cfg(feature = "connect")]
+    pub async fn handle_read_model(
+        &self,
+        handler: EquipmentReadModelHandler,
+    ) -> anyhow::Result<()> {
+        use crate::{projections::equipment::Equipment, EquipmentBroadcast};
+
+        let mut transaction = handler
+            .db_pool
+            .begin()
+            .await
+            .expect("Failed to start transaction");
+
+        match Equipment::check_in(&mut transaction, self).await {
+            Ok(equipment) => match transaction.commit().await {
+                Ok(_) => {
+                    if let Err(e) = handler
+                        .app_state
+                        .equipment_tx
+                        .send(EquipmentBroadcast::EquipmentReturned(equipment))
+                    {
+                        error!("Failed to send equipment to channel: {}", e);
+                    }
+                }
+                Err(e) => error!("Failed to commit transaction: {}", e),
+            },
+            Err(e) => {
+                error!("Failed to return equipment in read model: {}", e);
+                if let Err(e) = transaction.rollback().await {
+                    error!("Failed to rollback transaction: {}", e);
+                }
+            }
+        };
+
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "type")]
+pub enum EquipmentEvent {
+    EquipmentAdded(EquipmentAdded),
+    EquipmentCheckedOut(EquipmentCheckedOut),
+    EquipmentReturned(EquipmentReturned),
+}
+
+#[cfg(feature = "connect")]
+impl TryFrom<eventstore::ResolvedEvent> for EquipmentEvent {
+    type Error = EventParseError;
+
+    fn try_from(value: eventstore::ResolvedEvent) -> Result<Self, Self::Error> {
+        let event_data = value.event.ok_or(EventParseError::MissingEventData)?;
+        let event_json: Value = serde_json::from_slice(&event_data.data)
+            .map_err(EventParseError::DeserializationError)?;
+
+        match event_data.event_type {
+            s if s == EquipmentAdded::event_name() => Ok(EquipmentEvent::EquipmentAdded(
+                serde_json::from_value(event_json)
+                    .map_err(EventParseError::DeserializationError)?,
+            )),
+            s if s == EquipmentCheckedOut::event_name() => Ok(EquipmentEvent::EquipmentCheckedOut(
+                serde_json::from_value(event_json)
+                    .map_err(EventParseError::DeserializationError)?,
+            )),
+            s if s == EquipmentReturned::event_name() => Ok(EquipmentEvent::EquipmentReturned(
+                serde_json::from_value(event_json)
+                    .map_err(EventParseError::DeserializationError)?,
+            )),
+            _ => Err(EventParseError::UnknownEventType(event_data.event_type)),
+        }
+    }
+}
+
+impl EquipmentEvent {
+    #[cfg(feature = "connect")]
+    pub fn subscription_filter() -> eventstore::SubscriptionFilter {
+        eventstore::SubscriptionFilter::on_stream_name().add_prefix("equipment-")
+    }
+
+    #[cfg(feature = "connect")]
+    pub async fn handle_read_model(
+        &self,
+        handler: EquipmentReadModelHandler,
+    ) -> anyhow::Result<()> {
+        match self {
+            EquipmentEvent::EquipmentAdded(event) => event.handle_read_model(handler).await,
+            EquipmentEvent::EquipmentCheckedOut(event) => event.handle_read_model(handler).await,
+            EquipmentEvent::EquipmentReturned(event) => event.handle_read_model(handler).await,
+        }
+    }
+}
+
+/**
+ * Equipment read model handler
+ * Holds all necessary service connections and state to handle equipment events
+ */
+#[derive(Clone)]
+#[cfg(feature = "connect")]
+pub struct EquipmentReadModelHandler {
+    eventstore: std::sync::Arc<eventstore::Client>,
+    db_pool: std::sync::Arc<sqlx::PgPool>,
+    app_state: AppState,
+}
+
+#[cfg(feature = "connect")]
+impl EquipmentReadModelHandler {
+    pub fn new(
+        eventstore: std::sync::Arc<eventstore::Client>,
+        db_pool: std::sync::Arc<sqlx::PgPool>,
+        app_state: AppState,
+    ) -> Self {
+        Self {
+            eventstore,
+            db_pool,
+            app_state,
+        }
+    }
+
+    /**
+     * Subscribe to all equipment events and handle them in the read model
+     */
+    pub async fn subscribe(&self) {
+        let snapshot_position = match self.get_snapshot_position().await {
+            Ok(position) => position,
+            Err(e) => {
+                error!("Failed to get snapshot position: {}", e);
+                return;
+            }
+        };
+
+        let mut equipment_subscription = self
+            .eventstore
+            .subscribe_to_all(
+                &eventstore::SubscribeToAllOptions::default()
+                    .position(eventstore::StreamPosition::Position(eventstore::Position {
+                        commit: snapshot_position as u64,
+                        prepare: snapshot_position as u64,
+                    }))
+                    .filter(EquipmentEvent::subscription_filter()),
+            )
+            .await;
+
+        while let Ok(resolved_event) = equipment_subscription.next().await {
+            let position = resolved_event
+                .event
+                .as_ref()
+                .map(|event| event.position.commit);
+
+            let event: EquipmentEvent = match resolved_event.try_into() {
+                Ok(event) => event,
+                Err(e) => {
+                    error!("Failed to parse event: {}", e);
+                    continue;
+                }
+            };
+
+            if let Err(e) = event.handle_read_model(self.clone()).await {
+                error!("Failed to handle event: {}", e);
+                continue;
+            }
+
+            if let Err(e) = self.set_snapshot_position(position).await {
+                error!("Failed to set snapshot position: {}", e);
+                continue;
+            }
+        }
+    }
+
+    /**
+     * Get the current snapshot position for equipment
+     */
+    pub async fn get_snapshot_position(&self) -> anyhow::Result<i64> {
+        use crate::projections::snapshot_position::{SnapshotPosition, SnapshotPositionKey};
+
+        let mut transaction = self.db_pool.begin().await?;
+
+        let snapshot_position =
+            SnapshotPosition::get_by_key(&mut transaction, SnapshotPositionKey::Equipment).await?;
+
+        Ok(snapshot_position.map(|position| position.value).unwrap_or(0))
+    }
+
+    /**
+     * Set the current snapshot position for equipment
+     */
+    pub async fn set_snapshot_position(&self, position: Option<u64>) -> anyhow::Result<()> {
+        use crate::projections::snapshot_position::{SnapshotPosition, SnapshotPositionKey};
+
+        let mut transaction = self.db_pool.begin().await?;
+
+        if let Some(position) = position {
+            let snapshot_position = SnapshotPosition {
+                key: SnapshotPositionKey::Equipment,
+                value: position as i64,
+            };
+
+            snapshot_position.insert(&mut transaction).await?;
+
+            transaction.commit().await?;
+        }
+
+        Ok(())
+    }
+}
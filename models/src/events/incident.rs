@@ -0,0 +1,388 @@
+#[cfg(feature = "connect")]
+use {
+    log::error,
+    serde_json::Value,
+    crate::AppState,
+    super::EventParseError
+};
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::projections::incident::IncidentSeverity;
+
+/// A safety incident on a jobsite and the employees involved in it
+#[derive(Serialize, Deserialize, Debug)]
+pub struct IncidentReported {
+    pub id: Uuid,
+    pub jobsite_id: Uuid,
+    pub severity: IncidentSeverity,
+    pub occurred_on: NaiveDate,
+    pub description: String,
+    pub employee_ids: Vec<Uuid>,
+    pub reported_at: DateTime<Utc>,
+}
+
+impl IncidentReported {
+    pub fn event_name() -> String {
+        String::from("IncidentReported")
+    }
+
+    #[cfg(feature = "connect")]
+    pub async fn handle_read_model(&self, handler: IncidentReadModelHandler) -> anyhow::Result<()> {
+        use crate::{projections::incident::Incident, IncidentBroadcast};
+
+        let mut transaction = handler
+            .db_pool
+            .begin()
+            .await
+            .expect("Failed to start transaction");
+
+        match Incident::report(&mut transaction, self).await {
+            Ok(incident) => match transaction.commit().await {
+                Ok(_) => {
+                    if let Err(e) = handler
+                        .app_state
+                        .incident_tx
+                        .send(IncidentBroadcast::IncidentReported(incident))
+                    {
+                        error!("Failed to send incident to channel: {}", e);
+                    }
+                }
+                Err(e) => error!("Failed to commit transaction: {}", e),
+            },
+            Err(e) => {
+                error!("Failed to report incident in read model: {}", e);
+                if let Err(e) = transaction.rollback().await {
+                    error!("Failed to rollback transaction: {}", e);
+                }
+            }
+        };
+
+        Ok(())
+    }
+}
+
+/// What an investigation found caused the incident
+#[derive(Serialize, Deserialize, Debug)]
+pub struct IncidentInvestigated {
+    pub id: Uuid,
+    pub findings: String,
+    pub investigated_at: DateTime<Utc>,
+}
+
+impl IncidentInvestigated {
+    pub fn event_name() -> String {
+        String::from("IncidentInvestigated")
+    }
+
+    #[cfg(feature = "connect")]
+    pub async fn handle_read_model(&self, handler: IncidentReadModelHandler) -> anyhow::Result<()> {
+        use crate::{projections::incident::Incident, IncidentBroadcast};
+
+        let mut transaction = handler
+            .db_pool
+            .begin()
+            .await
+            .expect("Failed to start transaction");
+
+        match Incident::investigate(&mut transaction, self).await {
+            Ok(incident) => match transaction.commit().await {
+                Ok(_) => {
+                    if let Err(e) = handler
+                        .app_state
+                        .incident_tx
+                        .send(IncidentBroadcast::IncidentInvestigated(incident))
+                    {
+                        error!("Failed to send incident to channel: {}", e);
+                    }
+                }
+                Err(e) => error!("Failed to commit transaction: {}", e),
+            },
+            Err(e) => {
+                error!("Failed to investigate incident in read model: {}", e);
+                if let Err(e) = transaction.rollback().await {
+                    error!("Failed to rollback transaction: {}", e);
+                }
+            }
+        };
+
+        Ok(())
+    }
+}
+
+/// What will be done to keep the incident from happening again, and who is
+/// responsible for doing it
+#[derive(Serialize, Deserialize, Debug)]
+pub struct IncidentCorrectiveActionAssigned {
+    pub id: Uuid,
+    pub action: String,
+    pub assignee_id: Uuid,
+    pub due_on: Option<NaiveDate>,
+    pub assigned_at: DateTime<Utc>,
+}
+
+impl IncidentCorrectiveActionAssigned {
+    pub fn event_name() -> String {
+        String::from("IncidentCorrectiveActionAssigned")
+    }
+
+    #[cfg(feature = "connect")]
+    pub async fn handle_read_model(&self, handler: IncidentReadModelHandler) -> anyhow::Result<()> {
+        use crate::{projections::incident::Incident, IncidentBroadcast};
+
+        let mut transaction = handler
+            .db_pool
+            .begin()
+            .await
+            .expect("Failed to start transaction");
+
+        match Incident::assign_corrective_action(&mut transaction, self).await {
+            Ok(incident) => match transaction.commit().await {
+                Ok(_) => {
+                    if let Err(e) = handler.app_state.incident_tx.send(
+                        IncidentBroadcast::IncidentCorrectiveActionAssigned(incident),
+                    ) {
+                        error!("Failed to send incident to channel: {}", e);
+                    }
+                }
+                Err(e) => error!("Failed to commit transaction: {}", e),
+            },
+            Err(e) => {
+                error!(
+                    "Failed to assign incident corrective action in read model: {}",
+                    e
+                );
+                if let Err(e) = transaction.rollback().await {
+                    error!("Failed to rollback transaction: {}", e);
+                }
+            }
+        };
+
+        Ok(())
+    }
+}
+
+/// The corrective action is done and the incident needs no more follow up
+#[derive(Serialize, Deserialize, Debug)]
+pub struct IncidentClosed {
+    pub id: Uuid,
+    pub closed_at: DateTime<Utc>,
+}
+
+impl IncidentClosed {
+    pub fn event_name() -> String {
+        String::from("IncidentClosed")
+    }
+
+    #[cfg(feature = "connect")]
+    pub async fn handle_read_model(&self, handler: IncidentReadModelHandler) -> anyhow::Result<()> {
+        use crate::{projections::incident::Incident, IncidentBroadcast};
+
+        let mut transaction = handler
+            .db_pool
+            .begin()
+            .await
+            .expect("Failed to start transaction");
+
+        match Incident::close(&mut transaction, self).await {
+            Ok(incident) => match transaction.commit().await {
+                Ok(_) => {
+                    if let Err(e) = handler
+                        .app_state
+                        .incident_tx
+                        .send(IncidentBroadcast::IncidentClosed(incident))
+                    {
+                        error!("Failed to send incident to channel: {}", e);
+                    }
+                }
+                Err(e) => error!("Failed to commit transaction: {}", e),
+            },
+            Err(e) => {
+                error!("Failed to close incident in read model: {}", e);
+                if let Err(e) = transaction.rollback().await {
+                    error!("Failed to rollback transaction: {}", e);
+                }
+            }
+        };
+
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "type")]
+pub enum IncidentEvent {
+    IncidentReported(IncidentReported),
+    IncidentInvestigated(IncidentInvestigated),
+    IncidentCorrectiveActionAssigned(IncidentCorrectiveActionAssigned),
+    IncidentClosed(IncidentClosed),
+}
+
+#[cfg(feature = "connect")]
+impl TryFrom<eventstore::ResolvedEvent> for IncidentEvent {
+    type Error = EventParseError;
+
+    fn try_from(value: eventstore::ResolvedEvent) -> Result<Self, Self::Error> {
+        let event_data = value.event.ok_or(EventParseError::MissingEventData)?;
+        let event_json: Value = serde_json::from_slice(&event_data.data)
+            .map_err(EventParseError::DeserializationError)?;
+
+        match event_data.event_type {
+            s if s == IncidentReported::event_name() => Ok(IncidentEvent::IncidentReported(
+                serde_json::from_value(event_json)
+                    .map_err(EventParseError::DeserializationError)?,
+            )),
+            s if s == IncidentInvestigated::event_name() => {
+                Ok(IncidentEvent::IncidentInvestigated(
+                    serde_json::from_value(event_json)
+                        .map_err(EventParseError::DeserializationError)?,
+                ))
+            }
+            s if s == IncidentCorrectiveActionAssigned::event_name() => {
+                Ok(IncidentEvent::IncidentCorrectiveActionAssigned(
+                    serde_json::from_value(event_json)
+                        .map_err(EventParseError::DeserializationError)?,
+                ))
+            }
+            s if s == IncidentClosed::event_name() => Ok(IncidentEvent::IncidentClosed(
+                serde_json::from_value(event_json)
+                    .map_err(EventParseError::DeserializationError)?,
+            )),
+            _ => Err(EventParseError::UnknownEventType(event_data.event_type)),
+        }
+    }
+}
+
+impl IncidentEvent {
+    #[cfg(feature = "connect")]
+    pub fn subscription_filter() -> eventstore::SubscriptionFilter {
+        eventstore::SubscriptionFilter::on_stream_name().add_prefix("incident-")
+    }
+
+    #[cfg(feature = "connect")]
+    pub async fn handle_read_model(&self, handler: IncidentReadModelHandler) -> anyhow::Result<()> {
+        match self {
+            IncidentEvent::IncidentReported(event) => event.handle_read_model(handler).await,
+            IncidentEvent::IncidentInvestigated(event) => event.handle_read_model(handler).await,
+            IncidentEvent::IncidentCorrectiveActionAssigned(event) => {
+                event.handle_read_model(handler).await
+            }
+            IncidentEvent::IncidentClosed(event) => event.handle_read_model(handler).await,
+        }
+    }
+}
+
+/**
+ * Incident read model handler
+ * Holds all necessary service connections and state to handle incident events
+ */
+#[derive(Clone)]
+#[cfg(feature = "connect")]
+pub struct IncidentReadModelHandler {
+    eventstore: std::sync::Arc<eventstore::Client>,
+    db_pool: std::sync::Arc<sqlx::PgPool>,
+    app_state: AppState,
+}
+
+#[cfg(feature = "connect")]
+impl IncidentReadModelHandler {
+    pub fn new(
+        eventstore: std::sync::Arc<eventstore::Client>,
+        db_pool: std::sync::Arc<sqlx::PgPool>,
+        app_state: AppState,
+    ) -> Self {
+        Self {
+            eventstore,
+            db_pool,
+            app_state,
+        }
+    }
+
+    /**
+     * Subscribe to all incident events and handle them in the read model
+     */
+    pub async fn subscribe(&self) {
+        let snapshot_position = match self.get_snapshot_position().await {
+            Ok(position) => position,
+            Err(e) => {
+                error!("Failed to get snapshot position: {}", e);
+                return;
+            }
+        };
+
+        let mut incident_subscription = self
+            .eventstore
+            .subscribe_to_all(
+                &eventstore::SubscribeToAllOptions::default()
+                    .position(eventstore::StreamPosition::Position(eventstore::Position {
+                        commit: snapshot_position as u64,
+                        prepare: snapshot_position as u64,
+                    }))
+                    .filter(IncidentEvent::subscription_filter()),
+            )
+            .await;
+
+        while let Ok(resolved_event) = incident_subscription.next().await {
+            let position = resolved_event
+                .event
+                .as_ref()
+                .map(|event| event.position.commit);
+
+            let event: IncidentEvent = match resolved_event.try_into() {
+                Ok(event) => event,
+                Err(e) => {
+                    error!("Failed to parse event: {}", e);
+                    continue;
+                }
+            };
+
+            if let Err(e) = event.handle_read_model(self.clone()).await {
+                error!("Failed to handle event: {}", e);
+                continue;
+            }
+
+            if let Err(e) = self.set_snapshot_position(position).await {
+                error!("Failed to set snapshot position: {}", e);
+                continue;
+            }
+        }
+    }
+
+    /**
+     * Get the current snapshot position for incidents
+     */
+    pub async fn get_snapshot_position(&self) -> anyhow::Result<i64> {
+        use crate::projections::snapshot_position::{SnapshotPosition, SnapshotPositionKey};
+
+        let mut transaction = self.db_pool.begin().await?;
+
+        let snapshot_position =
+            SnapshotPosition::get_by_key(&mut transaction, SnapshotPositionKey::Incident).await?;
+
+        Ok(snapshot_position.map(|position| position.value).unwrap_or(0))
+    }
+
+    /**
+     * Set the current snapshot position for incidents
+     */
+    pub async fn set_snapshot_position(&self, position: Option<u64>) -> anyhow::Result<()> {
+        use crate::projections::snapshot_position::{SnapshotPosition, SnapshotPositionKey};
+
+        let mut transaction = self.db_pool.begin().await?;
+
+        if let Some(position) = position {
+            let snapshot_position = SnapshotPosition {
+                key: SnapshotPositionKey::Incident,
+                value: position as i64,
+            };
+
+            snapshot_position.insert(&mut transaction).await?;
+
+            transaction.commit().await?;
+        }
+
+        Ok(())
+    }
+}
@@ -6,13 +6,24 @@ use {
     super::EventParseError
 };
 
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::{
+    geofence::Geofence, local_time::default_time_zone,
+    projections::certification::CertificationType,
+};
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct JobsiteCreated {
     pub id: Uuid,
     pub name: String,
+    #[serde(default)]
+    pub project_id: Option<Uuid>,
+    /// IANA name of the zone the jobsite's days are counted in
+    #[serde(default = "default_time_zone")]
+    pub time_zone: String,
 }
 
 impl JobsiteCreated {
@@ -59,6 +70,8 @@ impl JobsiteCreated {
 pub struct JobsiteUpdated {
     pub id: Uuid,
     pub name: String,
+    #[serde(default = "default_time_zone")]
+    pub time_zone: String,
 }
 
 impl JobsiteUpdated {
@@ -103,11 +116,512 @@ impl JobsiteUpdated {
     }
 }
 
+/// A jobsite moved under a project, jobsites created before projects existed
+/// start out without one
+#[derive(Serialize, Deserialize, Debug)]
+pub struct JobsiteAssignedToProject {
+    pub id: Uuid,
+    pub project_id: Uuid,
+}
+
+impl JobsiteAssignedToProject {
+    pub fn event_name() -> String {
+        String::from("JobsiteAssignedToProject")
+    }
+
+    #[cfg(feature = "connect")]
+    pub async fn handle_read_model(&self, handler: JobsiteReadModelHandler) -> anyhow::Result<()> {
+        use crate::{projections::jobsite::Jobsite, JobsiteBroadcast};
+
+        let mut transaction = handler
+            .db_pool
+            .begin()
+            .await
+            .expect("Failed to start transaction");
+
+        match Jobsite::assign_to_project(&mut transaction, self).await {
+            Ok(jobsite) => match transaction.commit().await {
+                Ok(_) => {
+                    if let Err(e) = handler
+                        .app_state
+                        .jobsite_tx
+                        .send(JobsiteBroadcast::JobsiteAssignedToProject(jobsite))
+                    {
+                        error!("Failed to send jobsite to channel: {}", e);
+                    }
+                }
+                Err(e) => error!("Failed to commit transaction: {}", e),
+            },
+            Err(e) => {
+                error!("Failed to assign jobsite to project in read model: {}", e);
+                if let Err(e) = transaction.rollback().await {
+                    error!("Failed to rollback transaction: {}", e);
+                }
+            }
+        };
+
+        Ok(())
+    }
+}
+
+/// The full set of certifications the jobsite's crew have to hold, replacing
+/// whatever was required before
+#[derive(Serialize, Deserialize, Debug)]
+pub struct JobsiteCertificationsRequired {
+    pub id: Uuid,
+    pub certification_types: Vec<CertificationType>,
+}
+
+impl JobsiteCertificationsRequired {
+    pub fn event_name() -> String {
+        String::from("JobsiteCertificationsRequired")
+    }
+
+    #[cfg(feature = "connect")]
+    pub async fn handle_read_model(&self, handler: JobsiteReadModelHandler) -> anyhow::Result<()> {
+        use crate::{projections::jobsite::Jobsite, JobsiteBroadcast};
+
+        let mut transaction = handler
+            .db_pool
+            .begin()
+            .await
+            .expect("Failed to start transaction");
+
+        match Jobsite::require_certifications(&mut transaction, self).await {
+            Ok(jobsite) => match transaction.commit().await {
+                Ok(_) => {
+                    if let Err(e) = handler
+                        .app_state
+                        .jobsite_tx
+                        .send(JobsiteBroadcast::JobsiteCertificationsRequired(jobsite))
+                    {
+                        error!("Failed to send jobsite to channel: {}", e);
+                    }
+                }
+                Err(e) => error!("Failed to commit transaction: {}", e),
+            },
+            Err(e) => {
+                error!(
+                    "Failed to set jobsite certification requirements in read model: {}",
+                    e
+                );
+                if let Err(e) = transaction.rollback().await {
+                    error!("Failed to rollback transaction: {}", e);
+                }
+            }
+        };
+
+        Ok(())
+    }
+}
+
+/// Where the jobsite is and how far from it clock-ins are still inside,
+/// clearing it leaves clock-ins there unchecked
+#[derive(Serialize, Deserialize, Debug)]
+pub struct JobsiteGeofenceSet {
+    pub id: Uuid,
+    pub geofence: Option<Geofence>,
+}
+
+impl JobsiteGeofenceSet {
+    pub fn event_name() -> String {
+        String::from("JobsiteGeofenceSet")
+    }
+
+    #[cfg(feature = "connect")]
+    pub async fn handle_read_model(&self, handler: JobsiteReadModelHandler) -> anyhow::Result<()> {
+        use crate::{projections::jobsite::Jobsite, JobsiteBroadcast};
+
+        let mut transaction = handler
+            .db_pool
+            .begin()
+            .await
+            .expect("Failed to start transaction");
+
+        match Jobsite::set_geofence(&mut transaction, self).await {
+            Ok(jobsite) => match transaction.commit().await {
+                Ok(_) => {
+                    if let Err(e) = handler
+                        .app_state
+                        .jobsite_tx
+                        .send(JobsiteBroadcast::JobsiteGeofenceSet(jobsite))
+                    {
+                        error!("Failed to send jobsite to channel: {}", e);
+                    }
+                }
+                Err(e) => error!("Failed to commit transaction: {}", e),
+            },
+            Err(e) => {
+                error!("Failed to set jobsite geofence in read model: {}", e);
+                if let Err(e) = transaction.rollback().await {
+                    error!("Failed to rollback transaction: {}", e);
+                }
+            }
+        };
+
+        Ok(())
+    }
+}
+
+/// A shared tablet set up to clock the jobsite's crew in and out with their
+/// PINs
+#[derive(Serialize, Deserialize, Debug)]
+pub struct JobsiteKioskRegistered {
+    pub id: Uuid,
+    pub kiosk_id: Uuid,
+    pub name: String,
+    pub registered_at: DateTime<Utc>,
+}
+
+impl JobsiteKioskRegistered {
+    pub fn event_name() -> String {
+        String::from("JobsiteKioskRegistered")
+    }
+
+    #[cfg(feature = "connect")]
+    pub async fn handle_read_model(&self, handler: JobsiteReadModelHandler) -> anyhow::Result<()> {
+        use crate::{projections::kiosk::Kiosk, JobsiteBroadcast};
+
+        let mut transaction = handler
+            .db_pool
+            .begin()
+            .await
+            .expect("Failed to start transaction");
+
+        match Kiosk::register(&mut transaction, self).await {
+            Ok(kiosk) => match transaction.commit().await {
+                Ok(_) => {
+                    if let Err(e) = handler
+                        .app_state
+                        .jobsite_tx
+                        .send(JobsiteBroadcast::JobsiteKioskRegistered(kiosk))
+                    {
+                        error!("Failed to send kiosk to channel: {}", e);
+                    }
+                }
+                Err(e) => error!("Failed to commit transaction: {}", e),
+            },
+            Err(e) => {
+                error!("Failed to register kiosk in read model: {}", e);
+                if let Err(e) = transaction.rollback().await {
+                    error!("Failed to rollback transaction: {}", e);
+                }
+            }
+        };
+
+        Ok(())
+    }
+}
+
+/// A kiosk that should no longer be trusted, such as a lost tablet. Its
+/// device session stops working straight away.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct JobsiteKioskRevoked {
+    pub id: Uuid,
+    pub kiosk_id: Uuid,
+    pub revoked_at: DateTime<Utc>,
+}
+
+impl JobsiteKioskRevoked {
+    pub fn event_name() -> String {
+        String::from("JobsiteKioskRevoked")
+    }
+
+    #[cfg(feature = "connect")]
+    pub async fn handle_read_model(&self, handler: JobsiteReadModelHandler) -> anyhow::Result<()> {
+        use crate::{projections::kiosk::Kiosk, JobsiteBroadcast};
+
+        let mut transaction = handler
+            .db_pool
+            .begin()
+            .await
+            .expect("Failed to start transaction");
+
+        match Kiosk::revoke(&mut transaction, self).await {
+            Ok(kiosk) => match transaction.commit().await {
+                Ok(_) => {
+                    if let Err(e) = handler
+                        .app_state
+                        .jobsite_tx
+                        .send(JobsiteBroadcast::JobsiteKioskRevoked(kiosk))
+                    {
+                        error!("Failed to send kiosk to channel: {}", e);
+                    }
+                }
+                Err(e) => error!("Failed to commit transaction: {}", e),
+            },
+            Err(e) => {
+                error!("Failed to revoke kiosk in read model: {}", e);
+                if let Err(e) = transaction.rollback().await {
+                    error!("Failed to rollback transaction: {}", e);
+                }
+            }
+        };
+
+        Ok(())
+    }
+}
+
+/// A duplicate jobsite folded into the one its work should have been
+/// recorded against
+#[derive(Serialize, Deserialize, Debug)]
+pub struct JobsitesMerged {
+    pub source_id: Uuid,
+    pub target_id: Uuid,
+    pub merged_at: DateTime<Utc>,
+}
+
+impl JobsitesMerged {
+    pub fn event_name() -> String {
+        String::from("JobsitesMerged")
+    }
+
+    #[cfg(feature = "connect")]
+    pub async fn handle_read_model(&self, handler: JobsiteReadModelHandler) -> anyhow::Result<()> {
+        use crate::{
+            events::jobsite_budget::check_budget_thresholds,
+            projections::jobsite::{Jobsite, JobsiteMerge},
+            JobsiteBroadcast,
+        };
+
+        let mut transaction = handler
+            .db_pool
+            .begin()
+            .await
+            .expect("Failed to start transaction");
+
+        let jobsite_ids = [self.source_id, self.target_id];
+
+        let (source, moved_from) = match Jobsite::merge(&mut transaction, self).await {
+            Ok(moved) => moved,
+            Err(e) => {
+                error!("Failed to merge jobsites in read model: {}", e);
+                if let Err(e) = transaction.rollback().await {
+                    error!("Failed to rollback transaction: {}", e);
+                }
+                return Ok(());
+            }
+        };
+
+        if let Err(e) = refresh_merged_costs(&mut transaction, moved_from, &jobsite_ids).await {
+            error!("Failed to recompute labor costs in read model: {}", e);
+            if let Err(e) = transaction.rollback().await {
+                error!("Failed to rollback transaction: {}", e);
+            }
+            return Ok(());
+        }
+
+        let target = match Jobsite::get_by_id(&mut transaction, &self.target_id)
+            .await
+            .and_then(|target| target.ok_or(sqlx::Error::RowNotFound))
+        {
+            Ok(target) => target,
+            Err(e) => {
+                error!("Failed to get jobsite from read model: {}", e);
+                if let Err(e) = transaction.rollback().await {
+                    error!("Failed to rollback transaction: {}", e);
+                }
+                return Ok(());
+            }
+        };
+
+        let merge = JobsiteMerge { source, target };
+
+        match transaction.commit().await {
+            Ok(_) => {
+                if let Err(e) =
+                    check_budget_thresholds(&handler.eventstore, &handler.db_pool, &jobsite_ids)
+                        .await
+                {
+                    error!("Failed to check budget thresholds: {}", e);
+                }
+
+                if let Err(e) = handler
+                    .app_state
+                    .jobsite_tx
+                    .send(JobsiteBroadcast::JobsitesMerged(merge))
+                {
+                    error!("Failed to send jobsite to channel: {}", e);
+                }
+            }
+            Err(e) => error!("Failed to commit transaction: {}", e),
+        }
+
+        Ok(())
+    }
+}
+
+/// Undoes a merge, the duplicate gets back everything that was re-pointed
+#[derive(Serialize, Deserialize, Debug)]
+pub struct JobsitesUnmerged {
+    pub source_id: Uuid,
+    pub target_id: Uuid,
+    pub unmerged_at: DateTime<Utc>,
+}
+
+impl JobsitesUnmerged {
+    pub fn event_name() -> String {
+        String::from("JobsitesUnmerged")
+    }
+
+    #[cfg(feature = "connect")]
+    pub async fn handle_read_model(&self, handler: JobsiteReadModelHandler) -> anyhow::Result<()> {
+        use crate::{
+            events::jobsite_budget::check_budget_thresholds,
+            projections::jobsite::{Jobsite, JobsiteMerge},
+            JobsiteBroadcast,
+        };
+
+        let mut transaction = handler
+            .db_pool
+            .begin()
+            .await
+            .expect("Failed to start transaction");
+
+        let jobsite_ids = [self.source_id, self.target_id];
+
+        let (source, moved_from) = match Jobsite::unmerge(&mut transaction, self).await {
+            Ok(moved) => moved,
+            Err(e) => {
+                error!("Failed to unmerge jobsites in read model: {}", e);
+                if let Err(e) = transaction.rollback().await {
+                    error!("Failed to rollback transaction: {}", e);
+                }
+                return Ok(());
+            }
+        };
+
+        if let Err(e) = refresh_merged_costs(&mut transaction, moved_from, &jobsite_ids).await {
+            error!("Failed to recompute labor costs in read model: {}", e);
+            if let Err(e) = transaction.rollback().await {
+                error!("Failed to rollback transaction: {}", e);
+            }
+            return Ok(());
+        }
+
+        let target = match Jobsite::get_by_id(&mut transaction, &self.target_id)
+            .await
+            .and_then(|target| target.ok_or(sqlx::Error::RowNotFound))
+        {
+            Ok(target) => target,
+            Err(e) => {
+                error!("Failed to get jobsite from read model: {}", e);
+                if let Err(e) = transaction.rollback().await {
+                    error!("Failed to rollback transaction: {}", e);
+                }
+                return Ok(());
+            }
+        };
+
+        let merge = JobsiteMerge { source, target };
+
+        match transaction.commit().await {
+            Ok(_) => {
+                if let Err(e) =
+                    check_budget_thresholds(&handler.eventstore, &handler.db_pool, &jobsite_ids)
+                        .await
+                {
+                    error!("Failed to check budget thresholds: {}", e);
+                }
+
+                if let Err(e) = handler
+                    .app_state
+                    .jobsite_tx
+                    .send(JobsiteBroadcast::JobsitesUnmerged(merge))
+                {
+                    error!("Failed to send jobsite to channel: {}", e);
+                }
+            }
+            Err(e) => error!("Failed to commit transaction: {}", e),
+        }
+
+        Ok(())
+    }
+}
+
+/// Hours moved between jobsites, so reprice and total the days they were
+/// worked on and bring both jobsites' budget actuals up to date
+#[cfg(feature = "connect")]
+async fn refresh_merged_costs(
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    moved_from: Option<chrono::NaiveDate>,
+    jobsite_ids: &[Uuid],
+) -> Result<(), sqlx::Error> {
+    use crate::projections::{jobsite_budget::JobsiteBudget, jobsite_labor_cost::JobsiteLaborCost};
+
+    if let Some(moved_from) = moved_from {
+        JobsiteLaborCost::recompute(transaction, moved_from, None).await?;
+    }
+
+    for jobsite_id in jobsite_ids {
+        JobsiteBudget::refresh_actuals(transaction, jobsite_id).await?;
+    }
+
+    Ok(())
+}
+
+/// Work at the jobsite is over. Open shifts there are clocked out and
+/// upcoming crew assignments cancelled by the jobsite archival process
+/// manager.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct JobsiteArchived {
+    pub id: Uuid,
+    pub archived_at: DateTime<Utc>,
+}
+
+impl JobsiteArchived {
+    pub fn event_name() -> String {
+        String::from("JobsiteArchived")
+    }
+
+    #[cfg(feature = "connect")]
+    pub async fn handle_read_model(&self, handler: JobsiteReadModelHandler) -> anyhow::Result<()> {
+        use crate::{projections::jobsite::Jobsite, JobsiteBroadcast};
+
+        let mut transaction = handler
+            .db_pool
+            .begin()
+            .await
+            .expect("Failed to start transaction");
+
+        match Jobsite::archive(&mut transaction, self).await {
+            Ok(jobsite) => match transaction.commit().await {
+                Ok(_) => {
+                    if let Err(e) = handler
+                        .app_state
+                        .jobsite_tx
+                        .send(JobsiteBroadcast::JobsiteArchived(jobsite))
+                    {
+                        error!("Failed to send jobsite to channel: {}", e);
+                    }
+                }
+                Err(e) => error!("Failed to commit transaction: {}", e),
+            },
+            Err(e) => {
+                error!("Failed to archive jobsite in read model: {}", e);
+                if let Err(e) = transaction.rollback().await {
+                    error!("Failed to rollback transaction: {}", e);
+                }
+            }
+        };
+
+        Ok(())
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(tag = "type")]
 pub enum JobsiteEvent {
     JobsiteCreated(JobsiteCreated),
     JobsiteUpdated(JobsiteUpdated),
+    JobsiteAssignedToProject(JobsiteAssignedToProject),
+    JobsiteCertificationsRequired(JobsiteCertificationsRequired),
+    JobsiteGeofenceSet(JobsiteGeofenceSet),
+    JobsiteKioskRegistered(JobsiteKioskRegistered),
+    JobsiteKioskRevoked(JobsiteKioskRevoked),
+    JobsitesMerged(JobsitesMerged),
+    JobsitesUnmerged(JobsitesUnmerged),
+    JobsiteArchived(JobsiteArchived),
 }
 
 #[cfg(feature = "connect")]
@@ -128,6 +642,44 @@ impl TryFrom<eventstore::ResolvedEvent> for JobsiteEvent {
                 serde_json::from_value(event_json)
                     .map_err(EventParseError::DeserializationError)?,
             )),
+            s if s == JobsiteAssignedToProject::event_name() => {
+                Ok(JobsiteEvent::JobsiteAssignedToProject(
+                    serde_json::from_value(event_json)
+                        .map_err(EventParseError::DeserializationError)?,
+                ))
+            }
+            s if s == JobsiteCertificationsRequired::event_name() => {
+                Ok(JobsiteEvent::JobsiteCertificationsRequired(
+                    serde_json::from_value(event_json)
+                        .map_err(EventParseError::DeserializationError)?,
+                ))
+            }
+            s if s == JobsiteGeofenceSet::event_name() => Ok(JobsiteEvent::JobsiteGeofenceSet(
+                serde_json::from_value(event_json)
+                    .map_err(EventParseError::DeserializationError)?,
+            )),
+            s if s == JobsiteKioskRegistered::event_name() => {
+                Ok(JobsiteEvent::JobsiteKioskRegistered(
+                    serde_json::from_value(event_json)
+                        .map_err(EventParseError::DeserializationError)?,
+                ))
+            }
+            s if s == JobsiteKioskRevoked::event_name() => Ok(JobsiteEvent::JobsiteKioskRevoked(
+                serde_json::from_value(event_json)
+                    .map_err(EventParseError::DeserializationError)?,
+            )),
+            s if s == JobsitesMerged::event_name() => Ok(JobsiteEvent::JobsitesMerged(
+                serde_json::from_value(event_json)
+                    .map_err(EventParseError::DeserializationError)?,
+            )),
+            s if s == JobsitesUnmerged::event_name() => Ok(JobsiteEvent::JobsitesUnmerged(
+                serde_json::from_value(event_json)
+                    .map_err(EventParseError::DeserializationError)?,
+            )),
+            s if s == JobsiteArchived::event_name() => Ok(JobsiteEvent::JobsiteArchived(
+                serde_json::from_value(event_json)
+                    .map_err(EventParseError::DeserializationError)?,
+            )),
             _ => Err(EventParseError::UnknownEventType(event_data.event_type)),
         }
     }
@@ -144,6 +696,16 @@ impl JobsiteEvent {
         match self {
             JobsiteEvent::JobsiteCreated(event) => event.handle_read_model(handler).await,
             JobsiteEvent::JobsiteUpdated(event) => event.handle_read_model(handler).await,
+            JobsiteEvent::JobsiteAssignedToProject(event) => event.handle_read_model(handler).await,
+            JobsiteEvent::JobsiteCertificationsRequired(event) => {
+                event.handle_read_model(handler).await
+            }
+            JobsiteEvent::JobsiteGeofenceSet(event) => event.handle_read_model(handler).await,
+            JobsiteEvent::JobsiteKioskRegistered(event) => event.handle_read_model(handler).await,
+            JobsiteEvent::JobsiteKioskRevoked(event) => event.handle_read_model(handler).await,
+            JobsiteEvent::JobsitesMerged(event) => event.handle_read_model(handler).await,
+            JobsiteEvent::JobsitesUnmerged(event) => event.handle_read_model(handler).await,
+            JobsiteEvent::JobsiteArchived(event) => event.handle_read_model(handler).await,
         }
     }
 }
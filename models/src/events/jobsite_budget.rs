@@ -0,0 +1,390 @@
+#[cfg(feature = "connect")]
+use {
+    log::error,
+    serde_json::Value,
+    crate::AppState,
+    super::EventParseError
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::projections::jobsite_budget::{BudgetMeasure, JobsiteBudget, ReachedThreshold};
+
+/// A labor budget for a jobsite, or one of its cost codes, replacing any
+/// budget set before it
+#[derive(Serialize, Deserialize, Debug)]
+pub struct JobsiteBudgetSet {
+    pub id: Uuid,
+    pub jobsite_id: Uuid,
+    pub cost_code: Option<String>,
+    pub budget_minutes: Option<i64>,
+    pub budget_cents: Option<i64>,
+    pub set_at: DateTime<Utc>,
+}
+
+impl JobsiteBudgetSet {
+    pub fn event_name() -> String {
+        String::from("JobsiteBudgetSet")
+    }
+
+    #[cfg(feature = "connect")]
+    pub async fn handle_read_model(
+        &self,
+        handler: JobsiteBudgetReadModelHandler,
+    ) -> anyhow::Result<()> {
+        use crate::{projections::jobsite_budget::JobsiteBudget, JobsiteBudgetBroadcast};
+
+        let mut transaction = handler
+            .db_pool
+            .begin()
+            .await
+            .expect("Failed to start transaction");
+
+        match JobsiteBudget::set(&mut transaction, self).await {
+            Ok(budget) => match transaction.commit().await {
+                Ok(_) => {
+                    // The new budget may already be past a threshold
+                    if let Err(e) = check_budget_thresholds(
+                        &handler.eventstore,
+                        &handler.db_pool,
+                        &[self.jobsite_id],
+                    )
+                    .await
+                    {
+                        error!("Failed to check budget thresholds: {}", e);
+                    }
+
+                    if let Err(e) = handler
+                        .app_state
+                        .jobsite_budget_tx
+                        .send(JobsiteBudgetBroadcast::JobsiteBudgetSet(budget))
+                    {
+                        error!("Failed to send jobsite budget to channel: {}", e);
+                    }
+                }
+                Err(e) => error!("Failed to commit transaction: {}", e),
+            },
+            Err(e) => {
+                error!("Failed to set jobsite budget in read model: {}", e);
+                if let Err(e) = transaction.rollback().await {
+                    error!("Failed to rollback transaction: {}", e);
+                }
+            }
+        };
+
+        Ok(())
+    }
+}
+
+/// A budget reached one of its alert thresholds
+#[derive(Serialize, Deserialize, Debug)]
+pub struct JobsiteBudgetThresholdReached {
+    pub budget_id: Uuid,
+    pub jobsite_id: Uuid,
+    pub cost_code: Option<String>,
+    pub measure: BudgetMeasure,
+    /// The revised budget the threshold was reached against
+    pub budgeted: i64,
+    pub threshold: i32,
+    pub percent_used: i64,
+    pub reached_at: DateTime<Utc>,
+}
+
+impl JobsiteBudgetThresholdReached {
+    pub fn new(budget: &JobsiteBudget, reached: ReachedThreshold, reached_at: DateTime<Utc>) -> Self {
+        Self {
+            budget_id: budget.id,
+            jobsite_id: budget.jobsite_id,
+            cost_code: budget.cost_code.clone(),
+            measure: reached.measure,
+            budgeted: reached.budgeted,
+            threshold: reached.threshold,
+            percent_used: reached.percent_used,
+            reached_at,
+        }
+    }
+
+    pub fn event_name() -> String {
+        String::from("JobsiteBudgetThresholdReached")
+    }
+
+    /// Each threshold of a budget revision is reached at most once, so gets
+    /// a stream of its own that can only ever be created once. Checking the
+    /// same revision again, as a read model rebuild does, adds nothing.
+    pub fn stream_name(&self) -> String {
+        format!(
+            "jobsite_budget_alert-{}_{}_{}_{}",
+            self.budget_id,
+            self.measure.as_str(),
+            self.budgeted,
+            self.threshold
+        )
+    }
+
+    #[cfg(feature = "connect")]
+    pub async fn handle_read_model(
+        &self,
+        handler: JobsiteBudgetReadModelHandler,
+    ) -> anyhow::Result<()> {
+        use crate::{projections::jobsite_budget::JobsiteBudgetAlert, JobsiteBudgetBroadcast};
+
+        let mut transaction = handler
+            .db_pool
+            .begin()
+            .await
+            .expect("Failed to start transaction");
+
+        match JobsiteBudgetAlert::create(&mut transaction, self).await {
+            Ok(alert) => match transaction.commit().await {
+                Ok(_) => {
+                    if let Err(e) = handler
+                        .app_state
+                        .jobsite_budget_tx
+                        .send(JobsiteBudgetBroadcast::JobsiteBudgetThresholdReached(alert))
+                    {
+                        error!("Failed to send jobsite budget alert to channel: {}", e);
+                    }
+                }
+                Err(e) => error!("Failed to commit transaction: {}", e),
+            },
+            Err(e) => {
+                error!("Failed to create jobsite budget alert in read model: {}", e);
+                if let Err(e) = transaction.rollback().await {
+                    error!("Failed to rollback transaction: {}", e);
+                }
+            }
+        };
+
+        Ok(())
+    }
+}
+
+/// Refresh the jobsites' budget actuals and emit an alert event for every
+/// threshold that has been reached but not yet alerted on. Called after
+/// anything that changes logged hours or labor costs, with the jobsites it
+/// touched.
+#[cfg(feature = "connect")]
+pub async fn check_budget_thresholds(
+    eventstore: &eventstore::Client,
+    db_pool: &sqlx::PgPool,
+    jobsite_ids: &[Uuid],
+) -> anyhow::Result<()> {
+    use crate::projections::jobsite_budget::{JobsiteBudget, JobsiteBudgetAlert};
+
+    let mut transaction = db_pool.begin().await?;
+
+    let mut reached_events = vec![];
+    for jobsite_id in jobsite_ids {
+        JobsiteBudget::refresh_actuals(&mut transaction, jobsite_id).await?;
+
+        for budget in JobsiteBudget::get_list_by_jobsite(&mut transaction, jobsite_id).await? {
+            for reached in budget.reached_thresholds() {
+                if JobsiteBudgetAlert::exists(&mut transaction, &budget.id, &reached).await? {
+                    continue;
+                }
+
+                reached_events.push(JobsiteBudgetThresholdReached::new(
+                    &budget,
+                    reached,
+                    Utc::now(),
+                ));
+            }
+        }
+    }
+
+    transaction.commit().await?;
+
+    for reached_event in reached_events {
+        let event = eventstore::EventData::json(
+            JobsiteBudgetThresholdReached::event_name(),
+            &reached_event,
+        )?
+        .id(Uuid::new_v4());
+
+        // Another check may have raced us to this alert, which is fine
+        match eventstore
+            .append_to_stream(
+                reached_event.stream_name(),
+                &eventstore::AppendToStreamOptions::default()
+                    .expected_revision(eventstore::ExpectedRevision::NoStream),
+                event,
+            )
+            .await
+        {
+            Ok(_) | Err(eventstore::Error::WrongExpectedVersion { .. }) => {}
+            Err(e) => error!("Failed to append budget alert: {}", e),
+        }
+    }
+
+    Ok(())
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "type")]
+pub enum JobsiteBudgetEvent {
+    JobsiteBudgetSet(JobsiteBudgetSet),
+    JobsiteBudgetThresholdReached(JobsiteBudgetThresholdReached),
+}
+
+#[cfg(feature = "connect")]
+impl TryFrom<eventstore::ResolvedEvent> for JobsiteBudgetEvent {
+    type Error = EventParseError;
+
+    fn try_from(value: eventstore::ResolvedEvent) -> Result<Self, Self::Error> {
+        let event_data = value.event.ok_or(EventParseError::MissingEventData)?;
+        let event_json: Value = serde_json::from_slice(&event_data.data)
+            .map_err(EventParseError::DeserializationError)?;
+
+        match event_data.event_type {
+            s if s == JobsiteBudgetSet::event_name() => Ok(JobsiteBudgetEvent::JobsiteBudgetSet(
+                serde_json::from_value(event_json)
+                    .map_err(EventParseError::DeserializationError)?,
+            )),
+            s if s == JobsiteBudgetThresholdReached::event_name() => {
+                Ok(JobsiteBudgetEvent::JobsiteBudgetThresholdReached(
+                    serde_json::from_value(event_json)
+                        .map_err(EventParseError::DeserializationError)?,
+                ))
+            }
+            _ => Err(EventParseError::UnknownEventType(event_data.event_type)),
+        }
+    }
+}
+
+impl JobsiteBudgetEvent {
+    #[cfg(feature = "connect")]
+    pub fn subscription_filter() -> eventstore::SubscriptionFilter {
+        eventstore::SubscriptionFilter::on_stream_name()
+            .add_prefix("jobsite_budget-")
+            .add_prefix("jobsite_budget_alert-")
+    }
+
+    #[cfg(feature = "connect")]
+    pub async fn handle_read_model(
+        &self,
+        handler: JobsiteBudgetReadModelHandler,
+    ) -> anyhow::Result<()> {
+        match self {
+            JobsiteBudgetEvent::JobsiteBudgetSet(event) => event.handle_read_model(handler).await,
+            JobsiteBudgetEvent::JobsiteBudgetThresholdReached(event) => {
+                event.handle_read_model(handler).await
+            }
+        }
+    }
+}
+
+/**
+ * Jobsite budget read model handler
+ * Holds all necessary service connections and state to handle jobsite budget events
+ */
+#[derive(Clone)]
+#[cfg(feature = "connect")]
+pub struct JobsiteBudgetReadModelHandler {
+    eventstore: std::sync::Arc<eventstore::Client>,
+    db_pool: std::sync::Arc<sqlx::PgPool>,
+    app_state: AppState,
+}
+
+#[cfg(feature = "connect")]
+impl JobsiteBudgetReadModelHandler {
+    pub fn new(
+        eventstore: std::sync::Arc<eventstore::Client>,
+        db_pool: std::sync::Arc<sqlx::PgPool>,
+        app_state: AppState,
+    ) -> Self {
+        Self {
+            eventstore,
+            db_pool,
+            app_state,
+        }
+    }
+
+    /**
+     * Subscribe to all jobsite budget events and handle them in the read model
+     */
+    pub async fn subscribe(&self) {
+        let snapshot_position = match self.get_snapshot_position().await {
+            Ok(position) => position,
+            Err(e) => {
+                error!("Failed to get snapshot position: {}", e);
+                return;
+            }
+        };
+
+        let mut jobsite_budget_subscription = self
+            .eventstore
+            .subscribe_to_all(
+                &eventstore::SubscribeToAllOptions::default()
+                    .position(eventstore::StreamPosition::Position(eventstore::Position {
+                        commit: snapshot_position as u64,
+                        prepare: snapshot_position as u64,
+                    }))
+                    .filter(JobsiteBudgetEvent::subscription_filter()),
+            )
+            .await;
+
+        while let Ok(resolved_event) = jobsite_budget_subscription.next().await {
+            let position = resolved_event
+                .event
+                .as_ref()
+                .map(|event| event.position.commit);
+
+            let event: JobsiteBudgetEvent = match resolved_event.try_into() {
+                Ok(event) => event,
+                Err(e) => {
+                    error!("Failed to parse event: {}", e);
+                    continue;
+                }
+            };
+
+            if let Err(e) = event.handle_read_model(self.clone()).await {
+                error!("Failed to handle event: {}", e);
+                continue;
+            }
+
+            if let Err(e) = self.set_snapshot_position(position).await {
+                error!("Failed to set snapshot position: {}", e);
+                continue;
+            }
+        }
+    }
+
+    /**
+     * Get the current snapshot position for jobsite budgets
+     */
+    pub async fn get_snapshot_position(&self) -> anyhow::Result<i64> {
+        use crate::projections::snapshot_position::{SnapshotPosition, SnapshotPositionKey};
+
+        let mut transaction = self.db_pool.begin().await?;
+
+        let snapshot_position =
+            SnapshotPosition::get_by_key(&mut transaction, SnapshotPositionKey::JobsiteBudget)
+                .await?;
+
+        Ok(snapshot_position.map(|position| position.value).unwrap_or(0))
+    }
+
+    /**
+     * Set the current snapshot position for jobsite budgets
+     */
+    pub async fn set_snapshot_position(&self, position: Option<u64>) -> anyhow::Result<()> {
+        use crate::projections::snapshot_position::{SnapshotPosition, SnapshotPositionKey};
+
+        let mut transaction = self.db_pool.begin().await?;
+
+        if let Some(position) = position {
+            let snapshot_position = SnapshotPosition {
+                key: SnapshotPositionKey::JobsiteBudget,
+                value: position as i64,
+            };
+
+            snapshot_position.insert(&mut transaction).await?;
+
+            transaction.commit().await?;
+        }
+
+        Ok(())
+    }
+}
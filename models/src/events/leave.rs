@@ -0,0 +1,328 @@
+#[cfg(feature = "connect")]
+use {
+    log::error,
+    serde_json::Value,
+    crate::AppState,
+    super::EventParseError
+};
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::projections::leave::LeaveType;
+
+/// An employee asked for paid time off, `minutes_per_day` is taken on each
+/// weekday from `start_date` to `end_date`
+#[derive(Serialize, Deserialize, Debug)]
+pub struct LeaveRequested {
+    pub id: Uuid,
+    pub employee_id: Uuid,
+    pub leave_type: LeaveType,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub minutes_per_day: i64,
+    pub note: Option<String>,
+    pub requested_at: DateTime<Utc>,
+}
+
+impl LeaveRequested {
+    pub fn event_name() -> String {
+        String::from("LeaveRequested")
+    }
+
+    #[cfg(feature = "connect")]
+    pub async fn handle_read_model(&self, handler: LeaveReadModelHandler) -> anyhow::Result<()> {
+        use crate::{projections::leave::Leave, LeaveBroadcast};
+
+        let mut transaction = handler
+            .db_pool
+            .begin()
+            .await
+            .expect("Failed to start transaction");
+
+        match Leave::request(&mut transaction, self).await {
+            Ok(leave) => match transaction.commit().await {
+                Ok(_) => {
+                    if let Err(e) = handler
+                        .app_state
+                        .leave_tx
+                        .send(LeaveBroadcast::LeaveRequested(leave))
+                    {
+                        error!("Failed to send leave to channel: {}", e);
+                    }
+                }
+                Err(e) => error!("Failed to commit transaction: {}", e),
+            },
+            Err(e) => {
+                error!("Failed to request leave in read model: {}", e);
+                if let Err(e) = transaction.rollback().await {
+                    error!("Failed to rollback transaction: {}", e);
+                }
+            }
+        };
+
+        Ok(())
+    }
+}
+
+/// A supervisor signed off on a leave request
+#[derive(Serialize, Deserialize, Debug)]
+pub struct LeaveApproved {
+    pub id: Uuid,
+    pub employee_id: Uuid,
+    pub approved_at: DateTime<Utc>,
+}
+
+impl LeaveApproved {
+    pub fn event_name() -> String {
+        String::from("LeaveApproved")
+    }
+
+    #[cfg(feature = "connect")]
+    pub async fn handle_read_model(&self, handler: LeaveReadModelHandler) -> anyhow::Result<()> {
+        use crate::{projections::leave::Leave, LeaveBroadcast};
+
+        let mut transaction = handler
+            .db_pool
+            .begin()
+            .await
+            .expect("Failed to start transaction");
+
+        match Leave::approve(&mut transaction, self).await {
+            Ok(leave) => match transaction.commit().await {
+                Ok(_) => {
+                    if let Err(e) = handler
+                        .app_state
+                        .leave_tx
+                        .send(LeaveBroadcast::LeaveApproved(leave))
+                    {
+                        error!("Failed to send leave to channel: {}", e);
+                    }
+                }
+                Err(e) => error!("Failed to commit transaction: {}", e),
+            },
+            Err(e) => {
+                error!("Failed to approve leave in read model: {}", e);
+                if let Err(e) = transaction.rollback().await {
+                    error!("Failed to rollback transaction: {}", e);
+                }
+            }
+        };
+
+        Ok(())
+    }
+}
+
+/// A supervisor turned a leave request down
+#[derive(Serialize, Deserialize, Debug)]
+pub struct LeaveDenied {
+    pub id: Uuid,
+    pub employee_id: Uuid,
+    pub reason: String,
+    pub denied_at: DateTime<Utc>,
+}
+
+impl LeaveDenied {
+    pub fn event_name() -> String {
+        String::from("LeaveDenied")
+    }
+
+    #[cfg(feature = "connect")]
+    pub async fn handle_read_model(&self, handler: LeaveReadModelHandler) -> anyhow::Result<()> {
+        use crate::{projections::leave::Leave, LeaveBroadcast};
+
+        let mut transaction = handler
+            .db_pool
+            .begin()
+            .await
+            .expect("Failed to start transaction");
+
+        match Leave::deny(&mut transaction, self).await {
+            Ok(leave) => match transaction.commit().await {
+                Ok(_) => {
+                    if let Err(e) = handler
+                        .app_state
+                        .leave_tx
+                        .send(LeaveBroadcast::LeaveDenied(leave))
+                    {
+                        error!("Failed to send leave to channel: {}", e);
+                    }
+                }
+                Err(e) => error!("Failed to commit transaction: {}", e),
+            },
+            Err(e) => {
+                error!("Failed to deny leave in read model: {}", e);
+                if let Err(e) = transaction.rollback().await {
+                    error!("Failed to rollback transaction: {}", e);
+                }
+            }
+        };
+
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "type")]
+pub enum LeaveEvent {
+    LeaveRequested(LeaveRequested),
+    LeaveApproved(LeaveApproved),
+    LeaveDenied(LeaveDenied),
+}
+
+#[cfg(feature = "connect")]
+impl TryFrom<eventstore::ResolvedEvent> for LeaveEvent {
+    type Error = EventParseError;
+
+    fn try_from(value: eventstore::ResolvedEvent) -> Result<Self, Self::Error> {
+        let event_data = value.event.ok_or(EventParseError::MissingEventData)?;
+        let event_json: Value = serde_json::from_slice(&event_data.data)
+            .map_err(EventParseError::DeserializationError)?;
+
+        match event_data.event_type {
+            s if s == LeaveRequested::event_name() => Ok(LeaveEvent::LeaveRequested(
+                serde_json::from_value(event_json)
+                    .map_err(EventParseError::DeserializationError)?,
+            )),
+            s if s == LeaveApproved::event_name() => Ok(LeaveEvent::LeaveApproved(
+                serde_json::from_value(event_json)
+                    .map_err(EventParseError::DeserializationError)?,
+            )),
+            s if s == LeaveDenied::event_name() => Ok(LeaveEvent::LeaveDenied(
+                serde_json::from_value(event_json)
+                    .map_err(EventParseError::DeserializationError)?,
+            )),
+            _ => Err(EventParseError::UnknownEventType(event_data.event_type)),
+        }
+    }
+}
+
+impl LeaveEvent {
+    #[cfg(feature = "connect")]
+    pub fn subscription_filter() -> eventstore::SubscriptionFilter {
+        eventstore::SubscriptionFilter::on_stream_name().add_prefix("leave-")
+    }
+
+    #[cfg(feature = "connect")]
+    pub async fn handle_read_model(&self, handler: LeaveReadModelHandler) -> anyhow::Result<()> {
+        match self {
+            LeaveEvent::LeaveRequested(event) => event.handle_read_model(handler).await,
+            LeaveEvent::LeaveApproved(event) => event.handle_read_model(handler).await,
+            LeaveEvent::LeaveDenied(event) => event.handle_read_model(handler).await,
+        }
+    }
+}
+
+/**
+ * Leave read model handler
+ * Holds all necessary service connections and state to handle leave events
+ */
+#[derive(Clone)]
+#[cfg(feature = "connect")]
+pub struct LeaveReadModelHandler {
+    eventstore: std::sync::Arc<eventstore::Client>,
+    db_pool: std::sync::Arc<sqlx::PgPool>,
+    app_state: AppState,
+}
+
+#[cfg(feature = "connect")]
+impl LeaveReadModelHandler {
+    pub fn new(
+        eventstore: std::sync::Arc<eventstore::Client>,
+        db_pool: std::sync::Arc<sqlx::PgPool>,
+        app_state: AppState,
+    ) -> Self {
+        Self {
+            eventstore,
+            db_pool,
+            app_state,
+        }
+    }
+
+    /**
+     * Subscribe to all leave events and handle them in the read model
+     */
+    pub async fn subscribe(&self) {
+        let snapshot_position = match self.get_snapshot_position().await {
+            Ok(position) => position,
+            Err(e) => {
+                error!("Failed to get snapshot position: {}", e);
+                return;
+            }
+        };
+
+        let mut leave_subscription = self
+            .eventstore
+            .subscribe_to_all(
+                &eventstore::SubscribeToAllOptions::default()
+                    .position(eventstore::StreamPosition::Position(eventstore::Position {
+                        commit: snapshot_position as u64,
+                        prepare: snapshot_position as u64,
+                    }))
+                    .filter(LeaveEvent::subscription_filter()),
+            )
+            .await;
+
+        while let Ok(resolved_event) = leave_subscription.next().await {
+            let position = resolved_event
+                .event
+                .as_ref()
+                .map(|event| event.position.commit);
+
+            let event: LeaveEvent = match resolved_event.try_into() {
+                Ok(event) => event,
+                Err(e) => {
+                    error!("Failed to parse event: {}", e);
+                    continue;
+                }
+            };
+
+            if let Err(e) = event.handle_read_model(self.clone()).await {
+                error!("Failed to handle event: {}", e);
+                continue;
+            }
+
+            if let Err(e) = self.set_snapshot_position(position).await {
+                error!("Failed to set snapshot position: {}", e);
+                continue;
+            }
+        }
+    }
+
+    /**
+     * Get the current snapshot position for leaves
+     */
+    pub async fn get_snapshot_position(&self) -> anyhow::Result<i64> {
+        use crate::projections::snapshot_position::{SnapshotPosition, SnapshotPositionKey};
+
+        let mut transaction = self.db_pool.begin().await?;
+
+        let snapshot_position =
+            SnapshotPosition::get_by_key(&mut transaction, SnapshotPositionKey::Leave).await?;
+
+        Ok(snapshot_position.map(|position| position.value).unwrap_or(0))
+    }
+
+    /**
+     * Set the current snapshot position for leaves
+     */
+    pub async fn set_snapshot_position(&self, position: Option<u64>) -> anyhow::Result<()> {
+        use crate::projections::snapshot_position::{SnapshotPosition, SnapshotPositionKey};
+
+        let mut transaction = self.db_pool.begin().await?;
+
+        if let Some(position) = position {
+            let snapshot_position = SnapshotPosition {
+                key: SnapshotPositionKey::Leave,
+                value: position as i64,
+            };
+
+            snapshot_position.insert(&mut transaction).await?;
+
+            transaction.commit().await?;
+        }
+
+        Ok(())
+    }
+}
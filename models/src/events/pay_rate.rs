@@ -0,0 +1,270 @@
+#[cfg(feature = "connect")]
+use {
+    log::error,
+    serde_json::Value,
+    crate::AppState,
+    super::EventParseError
+};
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// An hourly rate taking effect for an employee or a trade, see
+/// [`crate::projections::pay_rate::PayRate`] for how rates are resolved
+#[derive(Serialize, Deserialize, Debug)]
+pub struct PayRateSet {
+    pub id: Uuid,
+    pub employee_id: Option<Uuid>,
+    pub trade: Option<String>,
+    pub jobsite_id: Option<Uuid>,
+    pub hourly_rate_cents: i64,
+    pub effective_date: NaiveDate,
+    pub set_at: DateTime<Utc>,
+}
+
+impl PayRateSet {
+    pub fn event_name() -> String {
+        String::from("PayRateSet")
+    }
+
+    #[cfg(feature = "connect")]
+    pub async fn handle_read_model(&self, handler: PayRateReadModelHandler) -> anyhow::Result<()> {
+        use crate::{
+            events::jobsite_budget::check_budget_thresholds,
+            projections::{
+                employee_hours::EmployeeHours, jobsite_labor_cost::JobsiteLaborCost,
+                pay_rate::PayRate,
+            },
+            PayRateBroadcast,
+        };
+
+        let mut transaction = handler
+            .db_pool
+            .begin()
+            .await
+            .expect("Failed to start transaction");
+
+        let pay_rate = match PayRate::create(&mut transaction, self).await {
+            Ok(pay_rate) => pay_rate,
+            Err(e) => {
+                error!("Failed to create pay rate in read model: {}", e);
+                if let Err(e) = transaction.rollback().await {
+                    error!("Failed to rollback transaction: {}", e);
+                }
+                return Ok(());
+            }
+        };
+
+        // A rate can be back dated, so everything from its effective date on
+        // needs repricing
+        if let Err(e) =
+            JobsiteLaborCost::recompute(&mut transaction, self.effective_date, None).await
+        {
+            error!("Failed to recompute labor costs in read model: {}", e);
+            if let Err(e) = transaction.rollback().await {
+                error!("Failed to rollback transaction: {}", e);
+            }
+            return Ok(());
+        }
+
+        // Only jobsites where the rate was worked under can have moved
+        let jobsite_ids = match self.jobsite_id {
+            Some(jobsite_id) => Ok(vec![jobsite_id]),
+            None => {
+                EmployeeHours::get_jobsite_ids(
+                    &mut transaction,
+                    self.employee_id.as_ref(),
+                    self.trade.as_deref(),
+                    self.effective_date,
+                    None,
+                )
+                .await
+            }
+        };
+        let jobsite_ids = match jobsite_ids {
+            Ok(jobsite_ids) => jobsite_ids,
+            Err(e) => {
+                error!("Failed to get repriced jobsites from read model: {}", e);
+                if let Err(e) = transaction.rollback().await {
+                    error!("Failed to rollback transaction: {}", e);
+                }
+                return Ok(());
+            }
+        };
+
+        match transaction.commit().await {
+            Ok(_) => {
+                if let Err(e) =
+                    check_budget_thresholds(&handler.eventstore, &handler.db_pool, &jobsite_ids)
+                        .await
+                {
+                    error!("Failed to check budget thresholds: {}", e);
+                }
+
+                if let Err(e) = handler
+                    .app_state
+                    .pay_rate_tx
+                    .send(PayRateBroadcast::PayRateSet(pay_rate))
+                {
+                    error!("Failed to send pay rate to channel: {}", e);
+                }
+            }
+            Err(e) => error!("Failed to commit transaction: {}", e),
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "type")]
+pub enum PayRateEvent {
+    PayRateSet(PayRateSet),
+}
+
+#[cfg(feature = "connect")]
+impl TryFrom<eventstore::ResolvedEvent> for PayRateEvent {
+    type Error = EventParseError;
+
+    fn try_from(value: eventstore::ResolvedEvent) -> Result<Self, Self::Error> {
+        let event_data = value.event.ok_or(EventParseError::MissingEventData)?;
+        let event_json: Value = serde_json::from_slice(&event_data.data)
+            .map_err(EventParseError::DeserializationError)?;
+
+        match event_data.event_type {
+            s if s == PayRateSet::event_name() => Ok(PayRateEvent::PayRateSet(
+                serde_json::from_value(event_json)
+                    .map_err(EventParseError::DeserializationError)?,
+            )),
+            _ => Err(EventParseError::UnknownEventType(event_data.event_type)),
+        }
+    }
+}
+
+impl PayRateEvent {
+    #[cfg(feature = "connect")]
+    pub fn subscription_filter() -> eventstore::SubscriptionFilter {
+        eventstore::SubscriptionFilter::on_stream_name().add_prefix("pay_rate-")
+    }
+
+    #[cfg(feature = "connect")]
+    pub async fn handle_read_model(&self, handler: PayRateReadModelHandler) -> anyhow::Result<()> {
+        match self {
+            PayRateEvent::PayRateSet(event) => event.handle_read_model(handler).await,
+        }
+    }
+}
+
+/**
+ * Pay rate read model handler
+ * Holds all necessary service connections and state to handle pay rate events
+ */
+#[derive(Clone)]
+#[cfg(feature = "connect")]
+pub struct PayRateReadModelHandler {
+    eventstore: std::sync::Arc<eventstore::Client>,
+    db_pool: std::sync::Arc<sqlx::PgPool>,
+    app_state: AppState,
+}
+
+#[cfg(feature = "connect")]
+impl PayRateReadModelHandler {
+    pub fn new(
+        eventstore: std::sync::Arc<eventstore::Client>,
+        db_pool: std::sync::Arc<sqlx::PgPool>,
+        app_state: AppState,
+    ) -> Self {
+        Self {
+            eventstore,
+            db_pool,
+            app_state,
+        }
+    }
+
+    /**
+     * Subscribe to all pay rate events and handle them in the read model
+     */
+    pub async fn subscribe(&self) {
+        let snapshot_position = match self.get_snapshot_position().await {
+            Ok(position) => position,
+            Err(e) => {
+                error!("Failed to get snapshot position: {}", e);
+                return;
+            }
+        };
+
+        let mut pay_rate_subscription = self
+            .eventstore
+            .subscribe_to_all(
+                &eventstore::SubscribeToAllOptions::default()
+                    .position(eventstore::StreamPosition::Position(eventstore::Position {
+                        commit: snapshot_position as u64,
+                        prepare: snapshot_position as u64,
+                    }))
+                    .filter(PayRateEvent::subscription_filter()),
+            )
+            .await;
+
+        while let Ok(resolved_event) = pay_rate_subscription.next().await {
+            let position = resolved_event
+                .event
+                .as_ref()
+                .map(|event| event.position.commit);
+
+            let event: PayRateEvent = match resolved_event.try_into() {
+                Ok(event) => event,
+                Err(e) => {
+                    error!("Failed to parse event: {}", e);
+                    continue;
+                }
+            };
+
+            if let Err(e) = event.handle_read_model(self.clone()).await {
+                error!("Failed to handle event: {}", e);
+                continue;
+            }
+
+            if let Err(e) = self.set_snapshot_position(position).await {
+                error!("Failed to set snapshot position: {}", e);
+                continue;
+            }
+        }
+    }
+
+    /**
+     * Get the current snapshot position for pay rates
+     */
+    pub async fn get_snapshot_position(&self) -> anyhow::Result<i64> {
+        use crate::projections::snapshot_position::{SnapshotPosition, SnapshotPositionKey};
+
+        let mut transaction = self.db_pool.begin().await?;
+
+        let snapshot_position =
+            SnapshotPosition::get_by_key(&mut transaction, SnapshotPositionKey::PayRate).await?;
+
+        Ok(snapshot_position.map(|position| position.value).unwrap_or(0))
+    }
+
+    /**
+     * Set the current snapshot position for pay rates
+     */
+    pub async fn set_snapshot_position(&self, position: Option<u64>) -> anyhow::Result<()> {
+        use crate::projections::snapshot_position::{SnapshotPosition, SnapshotPositionKey};
+
+        let mut transaction = self.db_pool.begin().await?;
+
+        if let Some(position) = position {
+            let snapshot_position = SnapshotPosition {
+                key: SnapshotPositionKey::PayRate,
+                value: position as i64,
+            };
+
+            snapshot_position.insert(&mut transaction).await?;
+
+            transaction.commit().await?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,515 @@
+#[cfg(feature = "connect")]
+use {
+    log::error,
+    serde_json::Value,
+    crate::AppState,
+    super::EventParseError
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    geofence::{Coordinates, GeofenceCheck},
+    local_time::default_time_zone,
+};
+
+/// Recorded in the metadata of clock-ins and clock-outs made at a kiosk, so
+/// punches can be traced back to the tablet they came from
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct KioskMetadata {
+    #[serde(rename = "kioskId")]
+    pub kiosk_id: Uuid,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ClockedIn {
+    pub shift_id: Uuid,
+    pub employee_id: Uuid,
+    pub jobsite_id: Uuid,
+    /// The cost code the time is logged against, if any
+    #[serde(default)]
+    pub cost_code_id: Option<Uuid>,
+    pub clocked_in_at: DateTime<Utc>,
+    /// The jobsite's time zone at clock-in, the shift's hours fall on days
+    /// in this zone
+    #[serde(default = "default_time_zone")]
+    pub time_zone: String,
+    /// Where the device was, when it shared its location
+    #[serde(default)]
+    pub coordinates: Option<Coordinates>,
+    /// The coordinates checked against the jobsite's geofence at clock-in
+    #[serde(default)]
+    pub geofence: GeofenceCheck,
+}
+
+impl ClockedIn {
+    pub fn event_name() -> String {
+        String::from("ClockedIn")
+    }
+
+    #[cfg(feature = "connect")]
+    pub async fn handle_read_model(&self, handler: ShiftReadModelHandler) -> anyhow::Result<()> {
+        use crate::{projections::shift::Shift, ShiftBroadcast};
+
+        let mut transaction = handler
+            .db_pool
+            .begin()
+            .await
+            .expect("Failed to start transaction");
+
+        match Shift::clock_in(&mut transaction, self).await {
+            Ok(shift) => match transaction.commit().await {
+                Ok(_) => {
+                    if let Err(e) = handler
+                        .app_state
+                        .shift_tx
+                        .send(ShiftBroadcast::ClockedIn(shift))
+                    {
+                        error!("Failed to send shift to channel: {}", e);
+                    }
+                }
+                Err(e) => error!("Failed to commit transaction: {}", e),
+            },
+            Err(e) => {
+                error!("Failed to clock in shift in read model: {}", e);
+                if let Err(e) = transaction.rollback().await {
+                    error!("Failed to rollback transaction: {}", e);
+                }
+            }
+        };
+
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ClockedOut {
+    pub shift_id: Uuid,
+    pub employee_id: Uuid,
+    pub clocked_out_at: DateTime<Utc>,
+}
+
+impl ClockedOut {
+    pub fn event_name() -> String {
+        String::from("ClockedOut")
+    }
+
+    #[cfg(feature = "connect")]
+    pub async fn handle_read_model(&self, handler: ShiftReadModelHandler) -> anyhow::Result<()> {
+        use crate::{
+            events::jobsite_budget::check_budget_thresholds,
+            projections::{
+                employee_hours::EmployeeHours, jobsite_labor_cost::JobsiteLaborCost,
+                labor_classification::LaborClassification, shift::Shift, timesheet::Timesheet,
+            },
+            ShiftBroadcast,
+        };
+
+        let mut transaction = handler
+            .db_pool
+            .begin()
+            .await
+            .expect("Failed to start transaction");
+
+        let shift = match Shift::clock_out(&mut transaction, self).await {
+            Ok(shift) => shift,
+            Err(e) => {
+                error!("Failed to clock out shift in read model: {}", e);
+                if let Err(e) = transaction.rollback().await {
+                    error!("Failed to rollback transaction: {}", e);
+                }
+                return Ok(());
+            }
+        };
+
+        if let Err(e) = EmployeeHours::record(&mut transaction, &shift).await {
+            error!("Failed to record employee hours in read model: {}", e);
+            if let Err(e) = transaction.rollback().await {
+                error!("Failed to rollback transaction: {}", e);
+            }
+            return Ok(());
+        }
+
+        let week_start = Timesheet::week_start_of(shift.work_date());
+
+        if let Err(e) = LaborClassification::reclassify_week(
+            &mut transaction,
+            &handler.app_state.pay_rules,
+            &shift.employee_id,
+            week_start,
+        )
+        .await
+        {
+            error!("Failed to classify labor in read model: {}", e);
+            if let Err(e) = transaction.rollback().await {
+                error!("Failed to rollback transaction: {}", e);
+            }
+            return Ok(());
+        }
+
+        if let Err(e) = JobsiteLaborCost::recompute(
+            &mut transaction,
+            week_start,
+            Some(Timesheet::week_end_of(week_start)),
+        )
+        .await
+        {
+            error!("Failed to recompute labor costs in read model: {}", e);
+            if let Err(e) = transaction.rollback().await {
+                error!("Failed to rollback transaction: {}", e);
+            }
+            return Ok(());
+        }
+
+        // Reclassifying the week can move costs on any jobsite worked in it
+        let jobsite_ids = match EmployeeHours::get_jobsite_ids(
+            &mut transaction,
+            Some(&shift.employee_id),
+            None,
+            week_start,
+            Some(Timesheet::week_end_of(week_start)),
+        )
+        .await
+        {
+            Ok(jobsite_ids) => jobsite_ids,
+            Err(e) => {
+                error!("Failed to get worked jobsites from read model: {}", e);
+                if let Err(e) = transaction.rollback().await {
+                    error!("Failed to rollback transaction: {}", e);
+                }
+                return Ok(());
+            }
+        };
+
+        match transaction.commit().await {
+            Ok(_) => {
+                if let Err(e) =
+                    check_budget_thresholds(&handler.eventstore, &handler.db_pool, &jobsite_ids)
+                        .await
+                {
+                    error!("Failed to check budget thresholds: {}", e);
+                }
+
+                if let Err(e) = handler
+                    .app_state
+                    .shift_tx
+                    .send(ShiftBroadcast::ClockedOut(shift))
+                {
+                    error!("Failed to send shift to channel: {}", e);
+                }
+            }
+            Err(e) => error!("Failed to commit transaction: {}", e),
+        }
+
+        Ok(())
+    }
+}
+
+/// A supervisor correction of a closed shift's times
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ShiftCorrected {
+    pub shift_id: Uuid,
+    pub clocked_in_at: DateTime<Utc>,
+    pub clocked_out_at: DateTime<Utc>,
+}
+
+impl ShiftCorrected {
+    pub fn event_name() -> String {
+        String::from("ShiftCorrected")
+    }
+
+    #[cfg(feature = "connect")]
+    pub async fn handle_read_model(&self, handler: ShiftReadModelHandler) -> anyhow::Result<()> {
+        use crate::{
+            events::jobsite_budget::check_budget_thresholds,
+            projections::{
+                employee_hours::EmployeeHours, jobsite_labor_cost::JobsiteLaborCost,
+                labor_classification::LaborClassification, shift::Shift, timesheet::Timesheet,
+            },
+            ShiftBroadcast,
+        };
+
+        let mut transaction = handler
+            .db_pool
+            .begin()
+            .await
+            .expect("Failed to start transaction");
+
+        // The week the shift is moving out of needs reclassifying as well
+        let previous_week_start = match Shift::get_by_id(&mut transaction, &self.shift_id).await {
+            Ok(Some(shift)) => Some(Timesheet::week_start_of(shift.work_date())),
+            Ok(None) => None,
+            Err(e) => {
+                error!("Failed to get shift in read model: {}", e);
+                None
+            }
+        };
+
+        let shift = match Shift::correct(&mut transaction, self).await {
+            Ok(shift) => shift,
+            Err(e) => {
+                error!("Failed to correct shift in read model: {}", e);
+                if let Err(e) = transaction.rollback().await {
+                    error!("Failed to rollback transaction: {}", e);
+                }
+                return Ok(());
+            }
+        };
+
+        if let Err(e) = EmployeeHours::record(&mut transaction, &shift).await {
+            error!("Failed to record employee hours in read model: {}", e);
+            if let Err(e) = transaction.rollback().await {
+                error!("Failed to rollback transaction: {}", e);
+            }
+            return Ok(());
+        }
+
+        let week_start = Timesheet::week_start_of(shift.work_date());
+        let mut week_starts = vec![week_start];
+        if let Some(previous_week_start) = previous_week_start.filter(|w| *w != week_start) {
+            week_starts.insert(0, previous_week_start);
+        }
+
+        let mut jobsite_ids = vec![];
+        for week_start in week_starts {
+            if let Err(e) = LaborClassification::reclassify_week(
+                &mut transaction,
+                &handler.app_state.pay_rules,
+                &shift.employee_id,
+                week_start,
+            )
+            .await
+            {
+                error!("Failed to classify labor in read model: {}", e);
+                if let Err(e) = transaction.rollback().await {
+                    error!("Failed to rollback transaction: {}", e);
+                }
+                return Ok(());
+            }
+
+            if let Err(e) = JobsiteLaborCost::recompute(
+                &mut transaction,
+                week_start,
+                Some(Timesheet::week_end_of(week_start)),
+            )
+            .await
+            {
+                error!("Failed to recompute labor costs in read model: {}", e);
+                if let Err(e) = transaction.rollback().await {
+                    error!("Failed to rollback transaction: {}", e);
+                }
+                return Ok(());
+            }
+
+            match EmployeeHours::get_jobsite_ids(
+                &mut transaction,
+                Some(&shift.employee_id),
+                None,
+                week_start,
+                Some(Timesheet::week_end_of(week_start)),
+            )
+            .await
+            {
+                Ok(worked) => jobsite_ids.extend(worked),
+                Err(e) => {
+                    error!("Failed to get worked jobsites from read model: {}", e);
+                    if let Err(e) = transaction.rollback().await {
+                        error!("Failed to rollback transaction: {}", e);
+                    }
+                    return Ok(());
+                }
+            }
+        }
+        // Both weeks can have been worked at the same jobsites
+        jobsite_ids.sort();
+        jobsite_ids.dedup();
+
+        match transaction.commit().await {
+            Ok(_) => {
+                if let Err(e) =
+                    check_budget_thresholds(&handler.eventstore, &handler.db_pool, &jobsite_ids)
+                        .await
+                {
+                    error!("Failed to check budget thresholds: {}", e);
+                }
+
+                if let Err(e) = handler
+                    .app_state
+                    .shift_tx
+                    .send(ShiftBroadcast::ShiftCorrected(shift))
+                {
+                    error!("Failed to send shift to channel: {}", e);
+                }
+            }
+            Err(e) => error!("Failed to commit transaction: {}", e),
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "type")]
+pub enum ShiftEvent {
+    ClockedIn(ClockedIn),
+    ClockedOut(ClockedOut),
+    ShiftCorrected(ShiftCorrected),
+}
+
+#[cfg(feature = "connect")]
+impl TryFrom<eventstore::ResolvedEvent> for ShiftEvent {
+    type Error = EventParseError;
+
+    fn try_from(value: eventstore::ResolvedEvent) -> Result<Self, Self::Error> {
+        let event_data = value.event.ok_or(EventParseError::MissingEventData)?;
+        let event_json: Value = serde_json::from_slice(&event_data.data)
+            .map_err(EventParseError::DeserializationError)?;
+
+        match event_data.event_type {
+            s if s == ClockedIn::event_name() => Ok(ShiftEvent::ClockedIn(
+                serde_json::from_value(event_json)
+                    .map_err(EventParseError::DeserializationError)?,
+            )),
+            s if s == ClockedOut::event_name() => Ok(ShiftEvent::ClockedOut(
+                serde_json::from_value(event_json)
+                    .map_err(EventParseError::DeserializationError)?,
+            )),
+            s if s == ShiftCorrected::event_name() => Ok(ShiftEvent::ShiftCorrected(
+                serde_json::from_value(event_json)
+                    .map_err(EventParseError::DeserializationError)?,
+            )),
+            _ => Err(EventParseError::UnknownEventType(event_data.event_type)),
+        }
+    }
+}
+
+impl ShiftEvent {
+    #[cfg(feature = "connect")]
+    pub fn subscription_filter() -> eventstore::SubscriptionFilter {
+        eventstore::SubscriptionFilter::on_stream_name().add_prefix("shift-")
+    }
+
+    #[cfg(feature = "connect")]
+    pub async fn handle_read_model(&self, handler: ShiftReadModelHandler) -> anyhow::Result<()> {
+        match self {
+            ShiftEvent::ClockedIn(event) => event.handle_read_model(handler).await,
+            ShiftEvent::ClockedOut(event) => event.handle_read_model(handler).await,
+            ShiftEvent::ShiftCorrected(event) => event.handle_read_model(handler).await,
+        }
+    }
+}
+
+/**
+ * Shift read model handler
+ * Holds all necessary service connections and state to handle shift events
+ */
+#[derive(Clone)]
+#[cfg(feature = "connect")]
+pub struct ShiftReadModelHandler {
+    eventstore: std::sync::Arc<eventstore::Client>,
+    db_pool: std::sync::Arc<sqlx::PgPool>,
+    app_state: AppState,
+}
+
+#[cfg(feature = "connect")]
+impl ShiftReadModelHandler {
+    pub fn new(
+        eventstore: std::sync::Arc<eventstore::Client>,
+        db_pool: std::sync::Arc<sqlx::PgPool>,
+        app_state: AppState,
+    ) -> Self {
+        Self {
+            eventstore,
+            db_pool,
+            app_state,
+        }
+    }
+
+    /**
+     * Subscribe to all shift events and handle them in the read model
+     */
+    pub async fn subscribe(&self) {
+        let snapshot_position = match self.get_snapshot_position().await {
+            Ok(position) => position,
+            Err(e) => {
+                error!("Failed to get snapshot position: {}", e);
+                return;
+            }
+        };
+
+        let mut shift_subscription = self
+            .eventstore
+            .subscribe_to_all(
+                &eventstore::SubscribeToAllOptions::default()
+                    .position(eventstore::StreamPosition::Position(eventstore::Position {
+                        commit: snapshot_position as u64,
+                        prepare: snapshot_position as u64,
+                    }))
+                    .filter(ShiftEvent::subscription_filter()),
+            )
+            .await;
+
+        while let Ok(resolved_event) = shift_subscription.next().await {
+            let position = resolved_event
+                .event
+                .as_ref()
+                .map(|event| event.position.commit);
+
+            let event: ShiftEvent = match resolved_event.try_into() {
+                Ok(event) => event,
+                Err(e) => {
+                    error!("Failed to parse event: {}", e);
+                    continue;
+                }
+            };
+
+            if let Err(e) = event.handle_read_model(self.clone()).await {
+                error!("Failed to handle event: {}", e);
+                continue;
+            }
+
+            if let Err(e) = self.set_snapshot_position(position).await {
+                error!("Failed to set snapshot position: {}", e);
+                continue;
+            }
+        }
+    }
+
+    /**
+     * Get the current snapshot position for shifts
+     */
+    pub async fn get_snapshot_position(&self) -> anyhow::Result<i64> {
+        use crate::projections::snapshot_position::{SnapshotPosition, SnapshotPositionKey};
+
+        let mut transaction = self.db_pool.begin().await?;
+
+        let snapshot_position =
+            SnapshotPosition::get_by_key(&mut transaction, SnapshotPositionKey::Shift).await?;
+
+        Ok(snapshot_position.map(|position| position.value).unwrap_or(0))
+    }
+
+    /**
+     * Set the current snapshot position for shifts
+     */
+    pub async fn set_snapshot_position(&self, position: Option<u64>) -> anyhow::Result<()> {
+        use crate::projections::snapshot_position::{SnapshotPosition, SnapshotPositionKey};
+
+        let mut transaction = self.db_pool.begin().await?;
+
+        if let Some(position) = position {
+            let snapshot_position = SnapshotPosition {
+                key: SnapshotPositionKey::Shift,
+                value: position as i64,
+            };
+
+            snapshot_position.insert(&mut transaction).await?;
+
+            transaction.commit().await?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,370 @@
+#[cfg(feature = "connect")]
+use {
+    log::error,
+    serde_json::Value,
+    crate::AppState,
+    super::EventParseError
+};
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TimesheetSubmitted {
+    pub employee_id: Uuid,
+    pub week_start: NaiveDate,
+    pub submitted_at: DateTime<Utc>,
+}
+
+impl TimesheetSubmitted {
+    pub fn event_name() -> String {
+        String::from("TimesheetSubmitted")
+    }
+
+    #[cfg(feature = "connect")]
+    pub async fn handle_read_model(&self, handler: TimesheetReadModelHandler) -> anyhow::Result<()> {
+        use crate::{projections::timesheet::Timesheet, TimesheetBroadcast};
+
+        let mut transaction = handler
+            .db_pool
+            .begin()
+            .await
+            .expect("Failed to start transaction");
+
+        match Timesheet::submit(&mut transaction, self).await {
+            Ok(timesheet) => match transaction.commit().await {
+                Ok(_) => {
+                    if let Err(e) = handler
+                        .app_state
+                        .timesheet_tx
+                        .send(TimesheetBroadcast::TimesheetSubmitted(timesheet))
+                    {
+                        error!("Failed to send timesheet to channel: {}", e);
+                    }
+                }
+                Err(e) => error!("Failed to commit transaction: {}", e),
+            },
+            Err(e) => {
+                error!("Failed to submit timesheet in read model: {}", e);
+                if let Err(e) = transaction.rollback().await {
+                    error!("Failed to rollback transaction: {}", e);
+                }
+            }
+        };
+
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TimesheetApproved {
+    pub employee_id: Uuid,
+    pub week_start: NaiveDate,
+    pub approved_at: DateTime<Utc>,
+}
+
+impl TimesheetApproved {
+    pub fn event_name() -> String {
+        String::from("TimesheetApproved")
+    }
+
+    #[cfg(feature = "connect")]
+    pub async fn handle_read_model(&self, handler: TimesheetReadModelHandler) -> anyhow::Result<()> {
+        use crate::{projections::timesheet::Timesheet, TimesheetBroadcast};
+
+        let mut transaction = handler
+            .db_pool
+            .begin()
+            .await
+            .expect("Failed to start transaction");
+
+        match Timesheet::approve(&mut transaction, self).await {
+            Ok(timesheet) => match transaction.commit().await {
+                Ok(_) => {
+                    if let Err(e) = handler
+                        .app_state
+                        .timesheet_tx
+                        .send(TimesheetBroadcast::TimesheetApproved(timesheet))
+                    {
+                        error!("Failed to send timesheet to channel: {}", e);
+                    }
+                }
+                Err(e) => error!("Failed to commit transaction: {}", e),
+            },
+            Err(e) => {
+                error!("Failed to approve timesheet in read model: {}", e);
+                if let Err(e) = transaction.rollback().await {
+                    error!("Failed to rollback transaction: {}", e);
+                }
+            }
+        };
+
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TimesheetRejected {
+    pub employee_id: Uuid,
+    pub week_start: NaiveDate,
+    pub reason: String,
+    pub rejected_at: DateTime<Utc>,
+}
+
+impl TimesheetRejected {
+    pub fn event_name() -> String {
+        String::from("TimesheetRejected")
+    }
+
+    #[cfg(feature = "connect")]
+    pub async fn handle_read_model(&self, handler: TimesheetReadModelHandler) -> anyhow::Result<()> {
+        use crate::{projections::timesheet::Timesheet, TimesheetBroadcast};
+
+        let mut transaction = handler
+            .db_pool
+            .begin()
+            .await
+            .expect("Failed to start transaction");
+
+        match Timesheet::reject(&mut transaction, self).await {
+            Ok(timesheet) => match transaction.commit().await {
+                Ok(_) => {
+                    if let Err(e) = handler
+                        .app_state
+                        .timesheet_tx
+                        .send(TimesheetBroadcast::TimesheetRejected(timesheet))
+                    {
+                        error!("Failed to send timesheet to channel: {}", e);
+                    }
+                }
+                Err(e) => error!("Failed to commit transaction: {}", e),
+            },
+            Err(e) => {
+                error!("Failed to reject timesheet in read model: {}", e);
+                if let Err(e) = transaction.rollback().await {
+                    error!("Failed to rollback transaction: {}", e);
+                }
+            }
+        };
+
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TimesheetReopened {
+    pub employee_id: Uuid,
+    pub week_start: NaiveDate,
+    pub reopened_at: DateTime<Utc>,
+}
+
+impl TimesheetReopened {
+    pub fn event_name() -> String {
+        String::from("TimesheetReopened")
+    }
+
+    #[cfg(feature = "connect")]
+    pub async fn handle_read_model(&self, handler: TimesheetReadModelHandler) -> anyhow::Result<()> {
+        use crate::{projections::timesheet::Timesheet, TimesheetBroadcast};
+
+        let mut transaction = handler
+            .db_pool
+            .begin()
+            .await
+            .expect("Failed to start transaction");
+
+        match Timesheet::reopen(&mut transaction, self).await {
+            Ok(timesheet) => match transaction.commit().await {
+                Ok(_) => {
+                    if let Err(e) = handler
+                        .app_state
+                        .timesheet_tx
+                        .send(TimesheetBroadcast::TimesheetReopened(timesheet))
+                    {
+                        error!("Failed to send timesheet to channel: {}", e);
+                    }
+                }
+                Err(e) => error!("Failed to commit transaction: {}", e),
+            },
+            Err(e) => {
+                error!("Failed to reopen timesheet in read model: {}", e);
+                if let Err(e) = transaction.rollback().await {
+                    error!("Failed to rollback transaction: {}", e);
+                }
+            }
+        };
+
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "type")]
+pub enum TimesheetEvent {
+    TimesheetSubmitted(TimesheetSubmitted),
+    TimesheetApproved(TimesheetApproved),
+    TimesheetRejected(TimesheetRejected),
+    TimesheetReopened(TimesheetReopened),
+}
+
+#[cfg(feature = "connect")]
+impl TryFrom<eventstore::ResolvedEvent> for TimesheetEvent {
+    type Error = EventParseError;
+
+    fn try_from(value: eventstore::ResolvedEvent) -> Result<Self, Self::Error> {
+        let event_data = value.event.ok_or(EventParseError::MissingEventData)?;
+        let event_json: Value = serde_json::from_slice(&event_data.data)
+            .map_err(EventParseError::DeserializationError)?;
+
+        match event_data.event_type {
+            s if s == TimesheetSubmitted::event_name() => Ok(TimesheetEvent::TimesheetSubmitted(
+                serde_json::from_value(event_json)
+                    .map_err(EventParseError::DeserializationError)?,
+            )),
+            s if s == TimesheetApproved::event_name() => Ok(TimesheetEvent::TimesheetApproved(
+                serde_json::from_value(event_json)
+                    .map_err(EventParseError::DeserializationError)?,
+            )),
+            s if s == TimesheetRejected::event_name() => Ok(TimesheetEvent::TimesheetRejected(
+                serde_json::from_value(event_json)
+                    .map_err(EventParseError::DeserializationError)?,
+            )),
+            s if s == TimesheetReopened::event_name() => Ok(TimesheetEvent::TimesheetReopened(
+                serde_json::from_value(event_json)
+                    .map_err(EventParseError::DeserializationError)?,
+            )),
+            _ => Err(EventParseError::UnknownEventType(event_data.event_type)),
+        }
+    }
+}
+
+impl TimesheetEvent {
+    #[cfg(feature = "connect")]
+    pub fn subscription_filter() -> eventstore::SubscriptionFilter {
+        eventstore::SubscriptionFilter::on_stream_name().add_prefix("timesheet-")
+    }
+
+    #[cfg(feature = "connect")]
+    pub async fn handle_read_model(&self, handler: TimesheetReadModelHandler) -> anyhow::Result<()> {
+        match self {
+            TimesheetEvent::TimesheetSubmitted(event) => event.handle_read_model(handler).await,
+            TimesheetEvent::TimesheetApproved(event) => event.handle_read_model(handler).await,
+            TimesheetEvent::TimesheetRejected(event) => event.handle_read_model(handler).await,
+            TimesheetEvent::TimesheetReopened(event) => event.handle_read_model(handler).await,
+        }
+    }
+}
+
+/**
+ * Timesheet read model handler
+ * Holds all necessary service connections and state to handle timesheet events
+ */
+#[derive(Clone)]
+#[cfg(feature = "connect")]
+pub struct TimesheetReadModelHandler {
+    eventstore: std::sync::Arc<eventstore::Client>,
+    db_pool: std::sync::Arc<sqlx::PgPool>,
+    app_state: AppState,
+}
+
+#[cfg(feature = "connect")]
+impl TimesheetReadModelHandler {
+    pub fn new(
+        eventstore: std::sync::Arc<eventstore::Client>,
+        db_pool: std::sync::Arc<sqlx::PgPool>,
+        app_state: AppState,
+    ) -> Self {
+        Self {
+            eventstore,
+            db_pool,
+            app_state,
+        }
+    }
+
+    /**
+     * Subscribe to all timesheet events and handle them in the read model
+     */
+    pub async fn subscribe(&self) {
+        let snapshot_position = match self.get_snapshot_position().await {
+            Ok(position) => position,
+            Err(e) => {
+                error!("Failed to get snapshot position: {}", e);
+                return;
+            }
+        };
+
+        let mut timesheet_subscription = self
+            .eventstore
+            .subscribe_to_all(
+                &eventstore::SubscribeToAllOptions::default()
+                    .position(eventstore::StreamPosition::Position(eventstore::Position {
+                        commit: snapshot_position as u64,
+                        prepare: snapshot_position as u64,
+                    }))
+                    .filter(TimesheetEvent::subscription_filter()),
+            )
+            .await;
+
+        while let Ok(resolved_event) = timesheet_subscription.next().await {
+            let position = resolved_event
+                .event
+                .as_ref()
+                .map(|event| event.position.commit);
+
+            let event: TimesheetEvent = match resolved_event.try_into() {
+                Ok(event) => event,
+                Err(e) => {
+                    error!("Failed to parse event: {}", e);
+                    continue;
+                }
+            };
+
+            if let Err(e) = event.handle_read_model(self.clone()).await {
+                error!("Failed to handle event: {}", e);
+                continue;
+            }
+
+            if let Err(e) = self.set_snapshot_position(position).await {
+                error!("Failed to set snapshot position: {}", e);
+                continue;
+            }
+        }
+    }
+
+    /**
+     * Get the current snapshot position for timesheets
+     */
+    pub async fn get_snapshot_position(&self) -> anyhow::Result<i64> {
+        use crate::projections::snapshot_position::{SnapshotPosition, SnapshotPositionKey};
+
+        let mut transaction = self.db_pool.begin().await?;
+
+        let snapshot_position =
+            SnapshotPosition::get_by_key(&mut transaction, SnapshotPositionKey::Timesheet).await?;
+
+        Ok(snapshot_position.map(|position| position.value).unwrap_or(0))
+    }
+
+    /**
+     * Set the current snapshot position for timesheets
+     */
+    pub async fn set_snapshot_position(&self, position: Option<u64>) -> anyhow::Result<()> {
+        use crate::projections::snapshot_position::{SnapshotPosition, SnapshotPositionKey};
+
+        let mut transaction = self.db_pool.begin().await?;
+
+        if let Some(position) = position {
+            let snapshot_position = SnapshotPosition {
+                key: SnapshotPositionKey::Timesheet,
+                value: position as i64,
+            };
+
+            snapshot_position.insert(&mut transaction).await?;
+
+            transaction.commit().await?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,172 @@
+use serde::{Deserialize, Serialize};
+
+/// Mean radius of the earth, close enough for jobsite-sized distances
+const EARTH_RADIUS_METERS: f64 = 6_371_000.0;
+
+/// A point reported by a device or set on a jobsite, in degrees
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct Coordinates {
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+impl Coordinates {
+    /// Coordinates that are actually on the globe, anything else is dropped
+    pub fn new(latitude: f64, longitude: f64) -> Option<Self> {
+        let on_globe = (-90.0..=90.0).contains(&latitude) && (-180.0..=180.0).contains(&longitude);
+
+        on_globe.then_some(Self {
+            latitude,
+            longitude,
+        })
+    }
+
+    /// Great-circle distance to another point
+    pub fn distance_meters(&self, other: &Coordinates) -> f64 {
+        let (latitude, other_latitude) = (self.latitude.to_radians(), other.latitude.to_radians());
+        let half_latitude = (other_latitude - latitude) / 2.0;
+        let half_longitude = (other.longitude - self.longitude).to_radians() / 2.0;
+
+        let a = half_latitude.sin().powi(2)
+            + latitude.cos() * other_latitude.cos() * half_longitude.sin().powi(2);
+
+        2.0 * EARTH_RADIUS_METERS * a.sqrt().asin()
+    }
+}
+
+/// The area around a jobsite clock-ins are expected from
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct Geofence {
+    pub center: Coordinates,
+    pub radius_meters: i32,
+}
+
+impl Geofence {
+    /// Check where a clock-in came from, clock-ins without coordinates can't
+    /// be placed
+    pub fn check(&self, at: Option<Coordinates>) -> GeofenceCheck {
+        let Some(at) = at else {
+            return GeofenceCheck::default();
+        };
+
+        let distance_meters = self.center.distance_meters(&at);
+        let status = if distance_meters <= self.radius_meters as f64 {
+            GeofenceStatus::Inside
+        } else {
+            GeofenceStatus::Outside
+        };
+
+        GeofenceCheck {
+            status,
+            distance_meters: Some(distance_meters),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub enum GeofenceStatus {
+    Inside,
+    /// Flagged for a supervisor to look at when reviewing the timesheet
+    Outside,
+    /// No coordinates were sent, or the jobsite has no geofence
+    #[default]
+    Unknown,
+}
+
+impl GeofenceStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GeofenceStatus::Inside => "inside",
+            GeofenceStatus::Outside => "outside",
+            GeofenceStatus::Unknown => "unknown",
+        }
+    }
+}
+
+impl From<String> for GeofenceStatus {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "inside" => GeofenceStatus::Inside,
+            "outside" => GeofenceStatus::Outside,
+            "unknown" => GeofenceStatus::Unknown,
+            _ => panic!("Invalid GeofenceStatus"),
+        }
+    }
+}
+
+/// Where a clock-in was made relative to the jobsite's geofence
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub struct GeofenceCheck {
+    pub status: GeofenceStatus,
+    /// From the center of the jobsite, known whenever the device sent its
+    /// coordinates and the jobsite has a geofence
+    pub distance_meters: Option<f64>,
+}
+
+/// Check a clock-in against a jobsite that may not have a geofence set
+pub fn check_clock_in(geofence: Option<Geofence>, at: Option<Coordinates>) -> GeofenceCheck {
+    geofence
+        .map(|geofence| geofence.check(at))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check_clock_in, Coordinates, Geofence, GeofenceCheck, GeofenceStatus};
+
+    fn jobsite() -> Geofence {
+        Geofence {
+            center: Coordinates::new(39.7392, -104.9903).unwrap(),
+            radius_meters: 200,
+        }
+    }
+
+    #[test]
+    fn distances_follow_the_curve_of_the_earth() {
+        let denver = Coordinates::new(39.7392, -104.9903).unwrap();
+        let boulder = Coordinates::new(40.0150, -105.2705).unwrap();
+
+        let distance = denver.distance_meters(&boulder);
+        assert!((distance - 38_890.0).abs() < 100.0, "{}", distance);
+        assert_eq!(denver.distance_meters(&denver), 0.0);
+    }
+
+    #[test]
+    fn clock_ins_within_the_radius_are_inside() {
+        // About 110 meters north of the center
+        let check = jobsite().check(Coordinates::new(39.7402, -104.9903));
+
+        assert_eq!(check.status, GeofenceStatus::Inside);
+        assert!((check.distance_meters.unwrap() - 111.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn clock_ins_past_the_radius_are_outside() {
+        // About a kilometer east of the center
+        let check = jobsite().check(Coordinates::new(39.7392, -104.9786));
+
+        assert_eq!(check.status, GeofenceStatus::Outside);
+        assert!(check.distance_meters.unwrap() > 900.0);
+    }
+
+    #[test]
+    fn clock_ins_that_cant_be_placed_are_unknown() {
+        let unknown = GeofenceCheck {
+            status: GeofenceStatus::Unknown,
+            distance_meters: None,
+        };
+
+        assert_eq!(jobsite().check(None), unknown);
+        assert_eq!(
+            check_clock_in(None, Coordinates::new(39.7392, -104.9903)),
+            unknown
+        );
+    }
+
+    #[test]
+    fn coordinates_off_the_globe_are_dropped() {
+        assert_eq!(Coordinates::new(91.0, 0.0), None);
+        assert_eq!(Coordinates::new(0.0, -180.5), None);
+        assert!(Coordinates::new(-90.0, 180.0).is_some());
+    }
+}
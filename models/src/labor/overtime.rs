@@ -0,0 +1,439 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::{Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum PayClass {
+    Regular,
+    Overtime,
+    DoubleTime,
+}
+
+impl PayClass {
+    /// The multiple of the base rate time in this class is paid at
+    pub fn multiplier(&self) -> f64 {
+        match self {
+            PayClass::Regular => 1.0,
+            PayClass::Overtime => 1.5,
+            PayClass::DoubleTime => 2.0,
+        }
+    }
+}
+
+/// A closed shift to be classified, entries are evaluated in the order given
+/// so a week's entries must be passed in chronologically
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimeEntry {
+    pub shift_id: Uuid,
+    pub jobsite_id: Uuid,
+    pub work_date: NaiveDate,
+    pub minutes: i64,
+}
+
+/// How the minutes of a single time entry break down by pay class
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Classification {
+    pub shift_id: Uuid,
+    pub jobsite_id: Uuid,
+    pub work_date: NaiveDate,
+    pub regular_minutes: i64,
+    pub overtime_minutes: i64,
+    pub double_time_minutes: i64,
+}
+
+impl Classification {
+    fn new(entry: &TimeEntry) -> Self {
+        Self {
+            shift_id: entry.shift_id,
+            jobsite_id: entry.jobsite_id,
+            work_date: entry.work_date,
+            regular_minutes: 0,
+            overtime_minutes: 0,
+            double_time_minutes: 0,
+        }
+    }
+
+    fn add(&mut self, class: PayClass, minutes: i64) {
+        match class {
+            PayClass::Regular => self.regular_minutes += minutes,
+            PayClass::Overtime => self.overtime_minutes += minutes,
+            PayClass::DoubleTime => self.double_time_minutes += minutes,
+        }
+    }
+}
+
+/// The thresholds used to classify a week of time, all thresholds are in
+/// minutes and a `None` threshold is never reached
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PayRules {
+    /// Time worked in a day beyond this is overtime
+    pub daily_overtime_after: Option<i64>,
+    /// Time worked in a day beyond this is double time
+    pub daily_double_time_after: Option<i64>,
+    /// Regular time worked in a week beyond this is overtime
+    pub weekly_overtime_after: Option<i64>,
+    /// When set, all time on the seventh consecutive day worked is overtime
+    /// up to this threshold and double time beyond it
+    pub seventh_day_double_time_after: Option<i64>,
+    /// Every minute worked on a holiday is paid as `holiday_class`, and
+    /// doesn't count towards the weekly threshold
+    pub holidays: Vec<NaiveDate>,
+    pub holiday_class: PayClass,
+}
+
+/// Thresholds that replace a preset's, in minutes. A `None` threshold keeps
+/// the preset's, so rule sets without one of the preset's thresholds start
+/// from [`PayRules::none`].
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+pub struct PayThresholds {
+    pub daily_overtime_after: Option<i64>,
+    pub daily_double_time_after: Option<i64>,
+    pub weekly_overtime_after: Option<i64>,
+    pub seventh_day_double_time_after: Option<i64>,
+}
+
+impl PayRules {
+    /// No premium time at all, everything is regular
+    pub fn none() -> Self {
+        Self {
+            daily_overtime_after: None,
+            daily_double_time_after: None,
+            weekly_overtime_after: None,
+            seventh_day_double_time_after: None,
+            holidays: vec![],
+            holiday_class: PayClass::Regular,
+        }
+    }
+
+    /// Overtime after 40 hours in a week
+    pub fn federal() -> Self {
+        Self {
+            weekly_overtime_after: Some(40 * 60),
+            holiday_class: PayClass::Overtime,
+            ..Self::none()
+        }
+    }
+
+    /// Overtime after 8 hours a day or 40 a week, double time after 12 hours
+    /// a day, and the seventh consecutive day is overtime with double time
+    /// after 8 hours
+    pub fn california() -> Self {
+        Self {
+            daily_overtime_after: Some(8 * 60),
+            daily_double_time_after: Some(12 * 60),
+            weekly_overtime_after: Some(40 * 60),
+            seventh_day_double_time_after: Some(8 * 60),
+            holidays: vec![],
+            holiday_class: PayClass::Overtime,
+        }
+    }
+
+    /// Look up a preset rule set by the name used in configuration
+    pub fn preset(name: &str) -> Option<Self> {
+        match name {
+            "none" => Some(Self::none()),
+            "federal" => Some(Self::federal()),
+            "california" => Some(Self::california()),
+            _ => None,
+        }
+    }
+
+    pub fn with_holidays(self, holidays: Vec<NaiveDate>) -> Self {
+        Self { holidays, ..self }
+    }
+
+    pub fn with_thresholds(self, thresholds: PayThresholds) -> Self {
+        Self {
+            daily_overtime_after: thresholds
+                .daily_overtime_after
+                .or(self.daily_overtime_after),
+            daily_double_time_after: thresholds
+                .daily_double_time_after
+                .or(self.daily_double_time_after),
+            weekly_overtime_after: thresholds
+                .weekly_overtime_after
+                .or(self.weekly_overtime_after),
+            seventh_day_double_time_after: thresholds
+                .seventh_day_double_time_after
+                .or(self.seventh_day_double_time_after),
+            ..self
+        }
+    }
+
+    /// Classify a single workweek of time entries. Daily thresholds are
+    /// applied first, only the time left regular counts towards the weekly
+    /// threshold.
+    ///
+    /// Paid leave isn't time worked and is never passed in as an entry, so
+    /// it doesn't count towards any threshold and a day off on leave breaks
+    /// a run of consecutive days worked.
+    pub fn classify_week(&self, entries: &[TimeEntry]) -> Vec<Classification> {
+        let worked_dates: HashSet<NaiveDate> = entries
+            .iter()
+            .filter(|entry| entry.minutes > 0)
+            .map(|entry| entry.work_date)
+            .collect();
+
+        let mut daily_minutes = HashMap::<NaiveDate, i64>::new();
+        let mut weekly_regular_minutes = 0;
+
+        entries
+            .iter()
+            .map(|entry| {
+                let mut classification = Classification::new(entry);
+
+                if self.holidays.contains(&entry.work_date) {
+                    classification.add(self.holiday_class, entry.minutes);
+                    return classification;
+                }
+
+                let worked_today = daily_minutes.entry(entry.work_date).or_insert(0);
+                let start = *worked_today;
+                let end = start + entry.minutes;
+                *worked_today = end;
+
+                let (overtime_after, double_time_after) = match self.seventh_day_double_time_after {
+                    Some(double_time_after) if is_seventh_day(entry.work_date, &worked_dates) => {
+                        (Some(0), Some(double_time_after))
+                    }
+                    _ => (self.daily_overtime_after, self.daily_double_time_after),
+                };
+
+                let double_time_from = double_time_after.unwrap_or(i64::MAX);
+                let overtime_from = overtime_after.unwrap_or(i64::MAX).min(double_time_from);
+
+                let mut regular = overlap(start, end, 0, overtime_from);
+                let mut overtime = overlap(start, end, overtime_from, double_time_from);
+                let double_time = overlap(start, end, double_time_from, i64::MAX);
+
+                if let Some(weekly_overtime_after) = self.weekly_overtime_after {
+                    let remaining = (weekly_overtime_after - weekly_regular_minutes).max(0);
+                    let over = (regular - remaining).max(0);
+                    regular -= over;
+                    overtime += over;
+                }
+
+                weekly_regular_minutes += regular;
+
+                classification.add(PayClass::Regular, regular);
+                classification.add(PayClass::Overtime, overtime);
+                classification.add(PayClass::DoubleTime, double_time);
+
+                classification
+            })
+            .collect()
+    }
+}
+
+/// Whether `date` is the seventh day in a row with time worked
+fn is_seventh_day(date: NaiveDate, worked_dates: &HashSet<NaiveDate>) -> bool {
+    (1..=6).all(|days| worked_dates.contains(&(date - Duration::days(days))))
+}
+
+/// Minutes of `[start, end)` that fall within `[from, to)`
+fn overlap(start: i64, end: i64, from: i64, to: i64) -> i64 {
+    (end.min(to) - start.max(from)).max(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const H: i64 = 60;
+
+    /// `(name, rules, (day offset from Monday, hours) worked, expected totals)`
+    type Case = (&'static str, PayRules, Vec<(i64, i64)>, (i64, i64, i64));
+
+    fn monday() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 10, 7).unwrap()
+    }
+
+    /// Build one entry per `(day offset from Monday, hours)` pair
+    fn entries(days: &[(i64, i64)]) -> Vec<TimeEntry> {
+        let jobsite_id = Uuid::new_v4();
+
+        days.iter()
+            .map(|(offset, hours)| TimeEntry {
+                shift_id: Uuid::new_v4(),
+                jobsite_id,
+                work_date: monday() + Duration::days(*offset),
+                minutes: hours * H,
+            })
+            .collect()
+    }
+
+    /// Sum classifications into `(regular, overtime, double time)` hours
+    fn totals(classifications: &[Classification]) -> (i64, i64, i64) {
+        classifications.iter().fold((0, 0, 0), |(r, o, d), c| {
+            (
+                r + c.regular_minutes / H,
+                o + c.overtime_minutes / H,
+                d + c.double_time_minutes / H,
+            )
+        })
+    }
+
+    #[test]
+    fn classifies_common_rule_sets() {
+        let cases: Vec<Case> = vec![
+            (
+                "none, long days",
+                PayRules::none(),
+                vec![(0, 14), (1, 14)],
+                (28, 0, 0),
+            ),
+            (
+                "federal, under 40",
+                PayRules::federal(),
+                vec![(0, 8), (1, 8), (2, 8), (3, 8)],
+                (32, 0, 0),
+            ),
+            (
+                "federal, long days under 40",
+                PayRules::federal(),
+                vec![(0, 12), (1, 12), (2, 12)],
+                (36, 0, 0),
+            ),
+            (
+                "federal, over 40",
+                PayRules::federal(),
+                vec![(0, 10), (1, 10), (2, 10), (3, 10), (4, 5)],
+                (40, 5, 0),
+            ),
+            (
+                "california, 10 hour day",
+                PayRules::california(),
+                vec![(0, 10)],
+                (8, 2, 0),
+            ),
+            (
+                "california, 14 hour day",
+                PayRules::california(),
+                vec![(0, 14)],
+                (8, 4, 2),
+            ),
+            (
+                "california, daily overtime doesn't count towards weekly",
+                PayRules::california(),
+                vec![(0, 10), (1, 10), (2, 10), (3, 10), (4, 10)],
+                (40, 10, 0),
+            ),
+            (
+                "california, sixth day pushes past 40",
+                PayRules::california(),
+                vec![(0, 8), (1, 8), (2, 8), (3, 8), (4, 8), (5, 8)],
+                (40, 8, 0),
+            ),
+            (
+                "california, seventh consecutive day",
+                PayRules::california(),
+                vec![(0, 4), (1, 4), (2, 4), (3, 4), (4, 4), (5, 4), (6, 10)],
+                (24, 8, 2),
+            ),
+            (
+                "california, seven days with a gap isn't consecutive",
+                PayRules::california(),
+                vec![(0, 4), (1, 4), (2, 4), (4, 4), (5, 4), (6, 10)],
+                (28, 2, 0),
+            ),
+        ];
+
+        for (name, rules, days, expected) in cases {
+            let classifications = rules.classify_week(&entries(&days));
+            assert_eq!(totals(&classifications), expected, "{}", name);
+        }
+    }
+
+    #[test]
+    fn classifies_holidays() {
+        let holiday = monday() + Duration::days(2);
+
+        let cases: Vec<Case> = vec![
+            (
+                "federal, holiday is overtime",
+                PayRules::federal().with_holidays(vec![holiday]),
+                vec![(1, 8), (2, 8)],
+                (8, 8, 0),
+            ),
+            (
+                "federal, holiday doesn't count towards weekly",
+                PayRules::federal().with_holidays(vec![holiday]),
+                vec![(0, 10), (1, 10), (2, 10), (3, 10), (4, 10)],
+                (40, 10, 0),
+            ),
+            (
+                "california, double time holidays",
+                PayRules {
+                    holiday_class: PayClass::DoubleTime,
+                    ..PayRules::california().with_holidays(vec![holiday])
+                },
+                vec![(2, 10)],
+                (0, 0, 10),
+            ),
+        ];
+
+        for (name, rules, days, expected) in cases {
+            let classifications = rules.classify_week(&entries(&days));
+            assert_eq!(totals(&classifications), expected, "{}", name);
+        }
+    }
+
+    #[test]
+    fn splits_thresholds_across_shifts_in_a_day() {
+        // Two shifts on the same day, the second crosses the daily threshold
+        let classifications = PayRules::california().classify_week(&entries(&[(0, 6), (0, 4)]));
+
+        assert_eq!(
+            classifications
+                .iter()
+                .map(|c| (c.regular_minutes / H, c.overtime_minutes / H))
+                .collect::<Vec<_>>(),
+            vec![(6, 0), (2, 2)]
+        );
+    }
+
+    #[test]
+    fn presets_are_found_by_name() {
+        let cases = vec![
+            ("none", Some(PayRules::none())),
+            ("federal", Some(PayRules::federal())),
+            ("california", Some(PayRules::california())),
+            ("unknown", None),
+        ];
+
+        for (name, expected) in cases {
+            assert_eq!(PayRules::preset(name), expected, "{}", name);
+        }
+    }
+
+    #[test]
+    fn thresholds_replace_only_what_they_set() {
+        let rules = PayRules::federal().with_thresholds(PayThresholds {
+            daily_overtime_after: Some(10 * H),
+            ..Default::default()
+        });
+
+        assert_eq!(rules.daily_overtime_after, Some(10 * H));
+        assert_eq!(rules.weekly_overtime_after, Some(40 * H));
+        assert_eq!(rules.daily_double_time_after, None);
+        assert_eq!(
+            totals(&rules.classify_week(&entries(&[(0, 12), (1, 8)]))),
+            (18, 2, 0)
+        );
+    }
+
+    #[test]
+    fn rule_sets_can_be_built_from_none() {
+        let rules = PayRules::none().with_thresholds(PayThresholds {
+            weekly_overtime_after: Some(45 * H),
+            ..Default::default()
+        });
+
+        assert_eq!(
+            totals(&rules.classify_week(&entries(&[(0, 12), (1, 12), (2, 12), (3, 12)]))),
+            (45, 3, 0)
+        );
+    }
+}
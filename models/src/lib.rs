@@ -1,18 +1,220 @@
 #[cfg(feature = "connect")]
-use projections::jobsite::Jobsite;
+use projections::{
+    attachment::Attachment,
+    certification::Certification,
+    change_order::ChangeOrder,
+    client::Client,
+    comment::Comment,
+    cost_code::CostCode,
+    crew_assignment::CrewAssignment,
+    daily_log::DailyLog,
+    employee::Employee,
+    equipment::Equipment,
+    incident::Incident,
+    jobsite::{Jobsite, JobsiteMerge},
+    jobsite_budget::{JobsiteBudget, JobsiteBudgetAlert},
+    kiosk::Kiosk,
+    leave::Leave,
+    material::{Material, MaterialConsumption},
+    pay_rate::PayRate,
+    project::Project,
+    shift::Shift,
+    timesheet::Timesheet,
+};
 
+pub mod calendar;
+pub mod commands;
 pub mod events;
+pub mod geofence;
+pub mod labor;
+pub mod local_time;
+pub mod process_managers;
 pub mod projections;
+pub mod scheduler;
+pub mod sync;
 
 #[derive(Clone)]
 #[cfg(feature = "connect")]
 pub enum JobsiteBroadcast {
     JobsiteCreated(Jobsite),
     JobsiteUpdated(Jobsite),
+    JobsiteAssignedToProject(Jobsite),
+    JobsiteCertificationsRequired(Jobsite),
+    JobsiteGeofenceSet(Jobsite),
+    JobsiteKioskRegistered(Kiosk),
+    JobsiteKioskRevoked(Kiosk),
+    JobsitesMerged(JobsiteMerge),
+    JobsitesUnmerged(JobsiteMerge),
+    JobsiteArchived(Jobsite),
+}
+
+#[derive(Clone)]
+#[cfg(feature = "connect")]
+pub enum ClientBroadcast {
+    ClientCreated(Client),
+}
+
+#[derive(Clone)]
+#[cfg(feature = "connect")]
+pub enum ProjectBroadcast {
+    ProjectCreated(Project),
+}
+
+#[derive(Clone)]
+#[cfg(feature = "connect")]
+pub enum EmployeeBroadcast {
+    EmployeeCreated(Employee),
+    EmployeeTerminated(Employee),
+}
+
+#[derive(Clone)]
+#[cfg(feature = "connect")]
+pub enum CrewAssignmentBroadcast {
+    CrewMemberAssigned(CrewAssignment),
+    CrewMemberUnassigned(CrewAssignment),
+}
+
+#[derive(Clone)]
+#[cfg(feature = "connect")]
+pub enum ShiftBroadcast {
+    ClockedIn(Shift),
+    ClockedOut(Shift),
+    ShiftCorrected(Shift),
+}
+
+#[derive(Clone)]
+#[cfg(feature = "connect")]
+pub enum TimesheetBroadcast {
+    TimesheetSubmitted(Timesheet),
+    TimesheetApproved(Timesheet),
+    TimesheetRejected(Timesheet),
+    TimesheetReopened(Timesheet),
+}
+
+#[derive(Clone)]
+#[cfg(feature = "connect")]
+pub enum PayRateBroadcast {
+    PayRateSet(PayRate),
+}
+
+#[derive(Clone)]
+#[cfg(feature = "connect")]
+pub enum JobsiteBudgetBroadcast {
+    JobsiteBudgetSet(JobsiteBudget),
+    JobsiteBudgetThresholdReached(JobsiteBudgetAlert),
+}
+
+#[derive(Clone)]
+#[cfg(feature = "connect")]
+pub enum CostCodeBroadcast {
+    CostCodeAdded(CostCode),
+    CostCodeRetired(CostCode),
+}
+
+#[derive(Clone)]
+#[cfg(feature = "connect")]
+pub enum DailyLogBroadcast {
+    DailyLogSaved(DailyLog),
+    DailyLogDelayRecorded(DailyLog),
+    DailyLogSubmitted(DailyLog),
+}
+
+#[derive(Clone)]
+#[cfg(feature = "connect")]
+pub enum AttachmentBroadcast {
+    AttachmentAdded(Attachment),
+    AttachmentRemoved(Attachment),
+}
+
+#[derive(Clone)]
+#[cfg(feature = "connect")]
+pub enum IncidentBroadcast {
+    IncidentReported(Incident),
+    IncidentInvestigated(Incident),
+    IncidentCorrectiveActionAssigned(Incident),
+    IncidentClosed(Incident),
+}
+
+#[derive(Clone)]
+#[cfg(feature = "connect")]
+pub enum CertificationBroadcast {
+    CertificationRecorded(Certification),
+    CertificationExpiryWarned(Certification),
+    CertificationExpired(Certification),
+}
+
+#[derive(Clone)]
+#[cfg(feature = "connect")]
+pub enum LeaveBroadcast {
+    LeaveRequested(Leave),
+    LeaveApproved(Leave),
+    LeaveDenied(Leave),
+}
+
+#[derive(Clone)]
+#[cfg(feature = "connect")]
+pub enum EquipmentBroadcast {
+    EquipmentAdded(Equipment),
+    EquipmentCheckedOut(Equipment),
+    EquipmentReturned(Equipment),
+}
+
+#[derive(Clone)]
+#[cfg(feature = "connect")]
+pub enum MaterialBroadcast {
+    MaterialAdded(Material),
+    MaterialConsumed(MaterialConsumption),
+}
+
+#[derive(Clone)]
+#[cfg(feature = "connect")]
+pub enum ChangeOrderBroadcast {
+    ChangeOrderDrafted(ChangeOrder),
+    ChangeOrderSubmitted(ChangeOrder),
+    ChangeOrderApproved(ChangeOrder),
+    ChangeOrderRejected(ChangeOrder),
+}
+
+#[derive(Clone)]
+#[cfg(feature = "connect")]
+pub enum CommentBroadcast {
+    CommentPosted(Comment),
+    CommentEdited(Comment),
+    CommentDeleted(Comment),
 }
 
 #[derive(Clone)]
 #[cfg(feature = "connect")]
 pub struct AppState {
     pub jobsite_tx: tokio::sync::broadcast::Sender<JobsiteBroadcast>,
+    pub client_tx: tokio::sync::broadcast::Sender<ClientBroadcast>,
+    pub project_tx: tokio::sync::broadcast::Sender<ProjectBroadcast>,
+    pub employee_tx: tokio::sync::broadcast::Sender<EmployeeBroadcast>,
+    pub crew_assignment_tx: tokio::sync::broadcast::Sender<CrewAssignmentBroadcast>,
+    pub shift_tx: tokio::sync::broadcast::Sender<ShiftBroadcast>,
+    pub timesheet_tx: tokio::sync::broadcast::Sender<TimesheetBroadcast>,
+    pub pay_rate_tx: tokio::sync::broadcast::Sender<PayRateBroadcast>,
+    pub jobsite_budget_tx: tokio::sync::broadcast::Sender<JobsiteBudgetBroadcast>,
+    pub cost_code_tx: tokio::sync::broadcast::Sender<CostCodeBroadcast>,
+    pub daily_log_tx: tokio::sync::broadcast::Sender<DailyLogBroadcast>,
+    pub attachment_tx: tokio::sync::broadcast::Sender<AttachmentBroadcast>,
+    pub incident_tx: tokio::sync::broadcast::Sender<IncidentBroadcast>,
+    pub certification_tx: tokio::sync::broadcast::Sender<CertificationBroadcast>,
+    pub leave_tx: tokio::sync::broadcast::Sender<LeaveBroadcast>,
+    pub equipment_tx: tokio::sync::broadcast::Sender<EquipmentBroadcast>,
+    pub material_tx: tokio::sync::broadcast::Sender<MaterialBroadcast>,
+    pub change_order_tx: tokio::sync::broadcast::Sender<ChangeOrderBroadcast>,
+    pub comment_tx: tokio::sync::broadcast::Sender<CommentBroadcast>,
+    pub pay_rules: labor::overtime::PayRules,
+    /// The company's zone, see `ApplicationSettings::time_zone`
+    pub time_zone: chrono_tz::Tz,
+}
+
+#[cfg(feature = "connect")]
+impl AppState {
+    /// Today where the company is, for views that aren't about one jobsite,
+    /// jobsite views use `Jobsite::local_date` instead
+    pub fn today(&self) -> chrono::NaiveDate {
+        local_time::local_date(chrono::Utc::now(), &self.time_zone)
+    }
 }
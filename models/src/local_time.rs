@@ -0,0 +1,142 @@
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, Offset, TimeZone, Utc};
+use chrono_tz::Tz;
+
+/// The zone of jobsites and shifts recorded before jobsites had one, their
+/// days were counted in UTC
+pub const DEFAULT_TIME_ZONE: &str = "UTC";
+
+pub fn default_time_zone() -> String {
+    DEFAULT_TIME_ZONE.to_string()
+}
+
+/// A time zone by its IANA name, such as `America/Denver`
+pub fn parse_time_zone(name: &str) -> Option<Tz> {
+    name.trim().parse().ok()
+}
+
+/// A stored zone name, these were checked when recorded so an unknown one
+/// falls back to UTC rather than failing
+pub fn time_zone(name: &str) -> Tz {
+    parse_time_zone(name).unwrap_or(Tz::UTC)
+}
+
+/// The day it is in the time zone at `at`
+pub fn local_date(at: DateTime<Utc>, time_zone: &Tz) -> NaiveDate {
+    at.with_timezone(time_zone).date_naive()
+}
+
+/// A wall clock time in the time zone as UTC. Times skipped when clocks
+/// spring forward don't exist, times repeated when they fall back are taken
+/// the first time round.
+pub fn from_local(time: NaiveDateTime, time_zone: &Tz) -> Option<DateTime<Utc>> {
+    time_zone
+        .from_local_datetime(&time)
+        .earliest()
+        .map(|time| time.with_timezone(&Utc))
+}
+
+/// The first instant of `date` in the time zone. Where clocks spring forward
+/// at midnight the day starts when they do.
+pub fn start_of_day(date: NaiveDate, time_zone: &Tz) -> DateTime<Utc> {
+    let midnight = date.and_hms_opt(0, 0, 0).unwrap();
+
+    from_local(midnight, time_zone).unwrap_or_else(|| {
+        // Midnight was skipped, it falls at the instant the gap ends
+        let offset_before = time_zone
+            .offset_from_utc_datetime(&(midnight - Duration::days(1)))
+            .fix();
+        (midnight - offset_before).and_utc()
+    })
+}
+
+/// The first midnight in the time zone after `at`
+pub fn next_midnight(at: DateTime<Utc>, time_zone: &Tz) -> DateTime<Utc> {
+    start_of_day(local_date(at, time_zone) + Duration::days(1), time_zone)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, NaiveDate, TimeZone, Utc};
+    use chrono_tz::{
+        America::{Los_Angeles, New_York, Sao_Paulo},
+        Tz,
+    };
+
+    use super::{from_local, local_date, next_midnight, parse_time_zone, start_of_day};
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn evening_shifts_fall_on_the_local_day() {
+        // 22:00 in Los Angeles is already the next day in UTC
+        let clocked_in_at = Utc.with_ymd_and_hms(2025, 2, 4, 6, 0, 0).unwrap();
+
+        assert_eq!(local_date(clocked_in_at, &Los_Angeles), date(2025, 2, 3));
+        assert_eq!(local_date(clocked_in_at, &Tz::UTC), date(2025, 2, 4));
+    }
+
+    #[test]
+    fn days_are_shorter_and_longer_when_clocks_change() {
+        let spring_forward = date(2025, 3, 9);
+        let fall_back = date(2025, 11, 2);
+        let day_length = |date: NaiveDate| {
+            start_of_day(date + Duration::days(1), &New_York) - start_of_day(date, &New_York)
+        };
+
+        assert_eq!(day_length(spring_forward), Duration::hours(23));
+        assert_eq!(day_length(fall_back), Duration::hours(25));
+        assert_eq!(day_length(date(2025, 3, 10)), Duration::hours(24));
+    }
+
+    #[test]
+    fn midnight_follows_the_clock_change() {
+        // 20:00 the night before clocks spring forward, still EST
+        let evening = Utc.with_ymd_and_hms(2025, 3, 9, 1, 0, 0).unwrap();
+        assert_eq!(
+            next_midnight(evening, &New_York),
+            Utc.with_ymd_and_hms(2025, 3, 9, 5, 0, 0).unwrap()
+        );
+
+        // 20:00 that evening, now EDT
+        let evening = Utc.with_ymd_and_hms(2025, 3, 10, 0, 0, 0).unwrap();
+        assert_eq!(
+            next_midnight(evening, &New_York),
+            Utc.with_ymd_and_hms(2025, 3, 10, 4, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn skipped_midnights_start_the_day_when_clocks_spring_forward() {
+        // Clocks in São Paulo went from 00:00 straight to 01:00 on this day
+        assert_eq!(
+            start_of_day(date(2018, 11, 4), &Sao_Paulo),
+            Utc.with_ymd_and_hms(2018, 11, 4, 3, 0, 0).unwrap()
+        );
+        assert_eq!(
+            local_date(start_of_day(date(2018, 11, 4), &Sao_Paulo), &Sao_Paulo),
+            date(2018, 11, 4)
+        );
+    }
+
+    #[test]
+    fn skipped_times_dont_exist_and_repeated_ones_are_taken_first() {
+        let skipped = date(2025, 3, 9).and_hms_opt(2, 30, 0).unwrap();
+        assert_eq!(from_local(skipped, &New_York), None);
+
+        // 01:30 happens twice, first in EDT
+        let repeated = date(2025, 11, 2).and_hms_opt(1, 30, 0).unwrap();
+        assert_eq!(
+            from_local(repeated, &New_York),
+            Some(Utc.with_ymd_and_hms(2025, 11, 2, 5, 30, 0).unwrap())
+        );
+    }
+
+    #[test]
+    fn time_zones_are_found_by_name() {
+        assert_eq!(parse_time_zone("America/New_York"), Some(New_York));
+        assert_eq!(parse_time_zone(" UTC "), Some(Tz::UTC));
+        assert_eq!(parse_time_zone("Mars/Olympus_Mons"), None);
+    }
+}
@@ -0,0 +1,498 @@
+use std::collections::BTreeSet;
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[cfg(feature = "connect")]
+use crate::events::EventParseError;
+use crate::{
+    commands::Command,
+    events::{certification::CertificationEvent, shift::ShiftEvent, timesheet::TimesheetEvent},
+    local_time::{local_date, next_midnight, start_of_day, time_zone},
+    process_managers::ProcessManager,
+    projections::{
+        certification::CERTIFICATION_WARNING_DAYS, snapshot_position::SnapshotPositionKey,
+        timesheet::Timesheet,
+    },
+    scheduler::Trigger,
+};
+
+/// How long after the week ends timesheets are submitted, leaving time for
+/// shifts still open at midnight to be clocked out first
+pub const TIMESHEET_GRACE: Duration = Duration::hours(1);
+
+/// How far behind UTC the furthest time zone runs, a week has closed
+/// everywhere this long after it closes in UTC
+const LATEST_ZONE_OFFSET: Duration = Duration::hours(12);
+
+/// Schedules the time triggers shifts, timesheets and certifications call
+/// for: shifts left open are clocked out at midnight, weeks left unsubmitted
+/// are submitted Sunday night and certifications are warned about and
+/// expired on time. Shift deadlines follow the jobsite's time zone.
+pub struct Deadlines {
+    /// The zone for deadlines that aren't tied to a jobsite
+    pub time_zone: Tz,
+    /// When the subscription started. Weeks that had already closed by then
+    /// are history being replayed, they were submitted or not at the time.
+    pub started_at: DateTime<Utc>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct DeadlinesState {
+    /// Weeks that have been submitted once, reopened or rejected weeks are
+    /// left for the employee to submit again. Only weeks that are still
+    /// open somewhere are kept.
+    pub submitted_weeks: BTreeSet<(Uuid, NaiveDate)>,
+}
+
+impl DeadlinesState {
+    /// Forget submitted weeks that have closed in every time zone, their
+    /// auto-submit has come and gone so there is nothing left to hold back
+    fn forget_closed_weeks(&mut self, now: DateTime<Utc>) {
+        self.submitted_weeks.retain(|(_, week_start)| {
+            week_closes_at(*week_start, &Tz::UTC) + LATEST_ZONE_OFFSET > now
+        });
+    }
+}
+
+#[derive(Debug)]
+pub enum DeadlinesEvent {
+    Shift(ShiftEvent),
+    Timesheet(TimesheetEvent),
+    Certification(CertificationEvent),
+}
+
+#[cfg(feature = "connect")]
+impl TryFrom<eventstore::ResolvedEvent> for DeadlinesEvent {
+    type Error = EventParseError;
+
+    fn try_from(value: eventstore::ResolvedEvent) -> Result<Self, Self::Error> {
+        let stream_id = match &value.event {
+            Some(event) => event.stream_id.clone(),
+            None => return Err(EventParseError::MissingEventData),
+        };
+
+        match stream_id {
+            s if s.starts_with("shift-") => Ok(DeadlinesEvent::Shift(value.try_into()?)),
+            s if s.starts_with("timesheet-") => Ok(DeadlinesEvent::Timesheet(value.try_into()?)),
+            _ => Ok(DeadlinesEvent::Certification(value.try_into()?)),
+        }
+    }
+}
+
+impl ProcessManager for Deadlines {
+    type State = DeadlinesState;
+    type Event = DeadlinesEvent;
+
+    const KEY: SnapshotPositionKey = SnapshotPositionKey::Deadlines;
+
+    #[cfg(feature = "connect")]
+    fn subscription_filter() -> eventstore::SubscriptionFilter {
+        eventstore::SubscriptionFilter::on_stream_name()
+            .add_prefix("shift-")
+            .add_prefix("timesheet-")
+            .add_prefix("certification-")
+    }
+
+    fn handle(
+        &self,
+        state: &mut Self::State,
+        event: &Self::Event,
+        recorded_at: DateTime<Utc>,
+    ) -> Vec<Command> {
+        state.forget_closed_weeks(recorded_at);
+
+        match event {
+            DeadlinesEvent::Shift(ShiftEvent::ClockedIn(clocked_in)) => {
+                let shift_time_zone = time_zone(&clocked_in.time_zone);
+                let week_start = Timesheet::week_start_of(local_date(
+                    clocked_in.clocked_in_at,
+                    &shift_time_zone,
+                ));
+
+                let mut commands = vec![Command::Schedule {
+                    trigger: Trigger::AutoClockOut {
+                        shift_id: clocked_in.shift_id,
+                        employee_id: clocked_in.employee_id,
+                    },
+                    due_at: next_midnight(clocked_in.clocked_in_at, &shift_time_zone),
+                }];
+
+                let closes_at = week_closes_at(week_start, &shift_time_zone);
+                if closes_at > self.started_at
+                    && !state
+                        .submitted_weeks
+                        .contains(&(clocked_in.employee_id, week_start))
+                {
+                    commands.push(Command::Schedule {
+                        trigger: Trigger::AutoSubmitTimesheet {
+                            employee_id: clocked_in.employee_id,
+                            week_start,
+                        },
+                        due_at: closes_at,
+                    });
+                }
+
+                commands
+            }
+            DeadlinesEvent::Shift(ShiftEvent::ClockedOut(clocked_out)) => {
+                vec![Command::Cancel {
+                    trigger: Trigger::AutoClockOut {
+                        shift_id: clocked_out.shift_id,
+                        employee_id: clocked_out.employee_id,
+                    },
+                }]
+            }
+            DeadlinesEvent::Timesheet(TimesheetEvent::TimesheetSubmitted(submitted)) => {
+                state
+                    .submitted_weeks
+                    .insert((submitted.employee_id, submitted.week_start));
+
+                vec![Command::Cancel {
+                    trigger: Trigger::AutoSubmitTimesheet {
+                        employee_id: submitted.employee_id,
+                        week_start: submitted.week_start,
+                    },
+                }]
+            }
+            DeadlinesEvent::Certification(CertificationEvent::CertificationRecorded(recorded)) => {
+                let Some(expires_on) = recorded.expires_on else {
+                    return vec![];
+                };
+
+                vec![
+                    Command::Schedule {
+                        trigger: Trigger::WarnCertificationExpiry {
+                            certification_id: recorded.id,
+                            employee_id: recorded.employee_id,
+                            expires_on,
+                        },
+                        due_at: start_of_day(
+                            expires_on - Duration::days(CERTIFICATION_WARNING_DAYS),
+                            &self.time_zone,
+                        ),
+                    },
+                    // Certifications are good through the day they expire on
+                    Command::Schedule {
+                        trigger: Trigger::ExpireCertification {
+                            certification_id: recorded.id,
+                            employee_id: recorded.employee_id,
+                            expires_on,
+                        },
+                        due_at: start_of_day(expires_on + Duration::days(1), &self.time_zone),
+                    },
+                ]
+            }
+            _ => vec![],
+        }
+    }
+}
+
+/// When a week that starts on `week_start` is submitted if nobody has
+pub fn week_closes_at(week_start: NaiveDate, time_zone: &Tz) -> DateTime<Utc> {
+    let next_week_start = Timesheet::week_end_of(week_start) + Duration::days(1);
+    start_of_day(next_week_start, time_zone) + TIMESHEET_GRACE
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Duration, NaiveDate, TimeZone, Utc};
+    use chrono_tz::America::New_York;
+    use uuid::Uuid;
+
+    use super::{Deadlines, DeadlinesEvent, DeadlinesState};
+    use crate::{
+        commands::Command,
+        events::{
+            certification::{CertificationEvent, CertificationRecorded},
+            shift::{ClockedIn, ClockedOut, ShiftEvent},
+            timesheet::{TimesheetEvent, TimesheetSubmitted},
+        },
+        process_managers::ProcessManager,
+        projections::certification::CertificationType,
+        scheduler::{Clock, FakeClock, Trigger},
+    };
+
+    fn deadlines() -> Deadlines {
+        Deadlines {
+            time_zone: New_York,
+            started_at: clock().now(),
+        }
+    }
+
+    /// Monday afternoon, 09:00 in the jobsite's time zone
+    fn clock() -> FakeClock {
+        FakeClock::new(Utc.with_ymd_and_hms(2025, 2, 3, 14, 0, 0).unwrap())
+    }
+
+    fn week_start() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 2, 3).unwrap()
+    }
+
+    fn clock_in(
+        state: &mut DeadlinesState,
+        employee_id: Uuid,
+        at: DateTime<Utc>,
+    ) -> (Uuid, Vec<Command>) {
+        let shift_id = Uuid::new_v4();
+        let event = DeadlinesEvent::Shift(ShiftEvent::ClockedIn(ClockedIn {
+            shift_id,
+            employee_id,
+            jobsite_id: Uuid::new_v4(),
+            cost_code_id: None,
+            clocked_in_at: at,
+            time_zone: New_York.name().to_string(),
+            coordinates: None,
+            geofence: Default::default(),
+        }));
+
+        (shift_id, deadlines().handle(state, &event, at))
+    }
+
+    /// The triggers the commands schedule that are due by the clock, fired
+    fn fire_due(commands: &[Command], clock: &FakeClock) -> Vec<Command> {
+        commands
+            .iter()
+            .filter_map(|command| match command {
+                Command::Schedule { trigger, due_at } if *due_at <= clock.now() => {
+                    Some(trigger.command(*due_at))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn forgotten_shifts_are_clocked_out_at_midnight_in_the_time_zone() {
+        let clock = clock();
+        let mut state = DeadlinesState::default();
+
+        let employee_id = Uuid::new_v4();
+        let (shift_id, commands) = clock_in(&mut state, employee_id, clock.now());
+        let midnight = Utc.with_ymd_and_hms(2025, 2, 4, 5, 0, 0).unwrap();
+
+        clock.advance(Duration::hours(14));
+        assert!(fire_due(&commands, &clock).is_empty());
+
+        clock.advance(Duration::hours(1));
+        assert_eq!(
+            fire_due(&commands, &clock),
+            vec![Command::ClockOut {
+                shift_id,
+                employee_id,
+                clocked_out_at: midnight,
+            }]
+        );
+
+        let clocked_out = DeadlinesEvent::Shift(ShiftEvent::ClockedOut(ClockedOut {
+            shift_id,
+            employee_id,
+            clocked_out_at: clock.now(),
+        }));
+        assert_eq!(
+            deadlines().handle(&mut state, &clocked_out, clock.now()),
+            vec![Command::Cancel {
+                trigger: Trigger::AutoClockOut {
+                    shift_id,
+                    employee_id,
+                },
+            }]
+        );
+    }
+
+    #[test]
+    fn weeks_are_submitted_sunday_night_unless_submitted_already() {
+        let clock = clock();
+        let employee_id = Uuid::new_v4();
+        let mut state = DeadlinesState::default();
+
+        let (_, commands) = clock_in(&mut state, employee_id, clock.now());
+        let auto_submit = Trigger::AutoSubmitTimesheet {
+            employee_id,
+            week_start: week_start(),
+        };
+        assert!(commands.contains(&Command::Schedule {
+            trigger: auto_submit.clone(),
+            due_at: Utc.with_ymd_and_hms(2025, 2, 10, 6, 0, 0).unwrap(),
+        }));
+
+        // 23:00 Sunday in the time zone, the week is still open
+        clock.advance(Duration::days(6) + Duration::hours(14));
+        assert!(!fire_due(&commands, &clock)
+            .iter()
+            .any(|command| matches!(command, Command::SubmitTimesheet { .. })));
+
+        let submitted =
+            DeadlinesEvent::Timesheet(TimesheetEvent::TimesheetSubmitted(TimesheetSubmitted {
+                employee_id,
+                week_start: week_start(),
+                submitted_at: clock.now(),
+            }));
+        assert_eq!(
+            deadlines().handle(&mut state, &submitted, clock.now()),
+            vec![Command::Cancel {
+                trigger: auto_submit,
+            }]
+        );
+
+        // Clocking in again that week doesn't submit it a second time
+        let friday = Utc.with_ymd_and_hms(2025, 2, 7, 14, 0, 0).unwrap();
+        let (_, commands) = clock_in(&mut state, employee_id, friday);
+        assert!(!commands.iter().any(|command| matches!(
+            command,
+            Command::Schedule {
+                trigger: Trigger::AutoSubmitTimesheet { .. },
+                ..
+            }
+        )));
+    }
+
+    #[test]
+    fn submitted_weeks_are_forgotten_once_closed_everywhere() {
+        let clock = clock();
+        let employee_id = Uuid::new_v4();
+        let mut state = DeadlinesState::default();
+
+        let submitted =
+            DeadlinesEvent::Timesheet(TimesheetEvent::TimesheetSubmitted(TimesheetSubmitted {
+                employee_id,
+                week_start: week_start(),
+                submitted_at: clock.now(),
+            }));
+        deadlines().handle(&mut state, &submitted, clock.now());
+        assert!(state.submitted_weeks.contains(&(employee_id, week_start())));
+
+        // 23:00 Sunday at UTC-12, the week hasn't closed there yet
+        clock.advance(Duration::days(6) + Duration::hours(21));
+        clock_in(&mut state, Uuid::new_v4(), clock.now());
+        assert!(state.submitted_weeks.contains(&(employee_id, week_start())));
+
+        clock.advance(Duration::hours(3));
+        clock_in(&mut state, Uuid::new_v4(), clock.now());
+        assert!(state.submitted_weeks.is_empty());
+    }
+
+    #[test]
+    fn sunday_evening_shifts_count_toward_the_local_week() {
+        let employee_id = Uuid::new_v4();
+        let mut state = DeadlinesState::default();
+
+        // 21:00 Sunday in the jobsite's time zone, Monday in UTC
+        let sunday_evening = Utc.with_ymd_and_hms(2025, 2, 10, 2, 0, 0).unwrap();
+        let (shift_id, commands) = clock_in(&mut state, employee_id, sunday_evening);
+
+        assert_eq!(
+            commands,
+            vec![
+                Command::Schedule {
+                    trigger: Trigger::AutoClockOut {
+                        shift_id,
+                        employee_id,
+                    },
+                    due_at: Utc.with_ymd_and_hms(2025, 2, 10, 5, 0, 0).unwrap(),
+                },
+                Command::Schedule {
+                    trigger: Trigger::AutoSubmitTimesheet {
+                        employee_id,
+                        week_start: week_start(),
+                    },
+                    due_at: Utc.with_ymd_and_hms(2025, 2, 10, 6, 0, 0).unwrap(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn weeks_closed_before_the_subscription_started_are_not_submitted() {
+        let employee_id = Uuid::new_v4();
+        let mut state = DeadlinesState::default();
+
+        // Replaying a clock-in from the week before
+        let last_week = Utc.with_ymd_and_hms(2025, 1, 28, 14, 0, 0).unwrap();
+        let (shift_id, commands) = clock_in(&mut state, employee_id, last_week);
+
+        assert_eq!(
+            commands,
+            vec![Command::Schedule {
+                trigger: Trigger::AutoClockOut {
+                    shift_id,
+                    employee_id,
+                },
+                due_at: Utc.with_ymd_and_hms(2025, 1, 29, 5, 0, 0).unwrap(),
+            }]
+        );
+    }
+
+    #[test]
+    fn certifications_are_warned_about_and_expired_on_time() {
+        let clock = clock();
+        let (id, employee_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let expires_on = NaiveDate::from_ymd_opt(2025, 3, 31).unwrap();
+        let mut state = DeadlinesState::default();
+
+        let recorded = DeadlinesEvent::Certification(CertificationEvent::CertificationRecorded(
+            CertificationRecorded {
+                id,
+                employee_id,
+                certification_type: CertificationType::FirstAid,
+                certificate_number: None,
+                issued_on: NaiveDate::from_ymd_opt(2022, 3, 31).unwrap(),
+                expires_on: Some(expires_on),
+                recorded_at: clock.now(),
+            },
+        ));
+        let commands = deadlines().handle(&mut state, &recorded, clock.now());
+
+        let warned_at = Utc.with_ymd_and_hms(2025, 3, 1, 5, 0, 0).unwrap();
+        clock.advance(warned_at - clock.now());
+        assert_eq!(
+            fire_due(&commands, &clock),
+            vec![Command::WarnCertificationExpiry {
+                certification_id: id,
+                employee_id,
+                expires_on,
+                warned_at,
+            }]
+        );
+
+        // Still good on the day it expires on, midnight after it is in EDT
+        let expired_at = Utc.with_ymd_and_hms(2025, 4, 1, 4, 0, 0).unwrap();
+        clock.advance(expired_at - clock.now() - Duration::minutes(1));
+        assert_eq!(fire_due(&commands, &clock).len(), 1);
+
+        clock.advance(Duration::minutes(1));
+        assert_eq!(
+            fire_due(&commands, &clock)[1],
+            Command::ExpireCertification {
+                certification_id: id,
+                employee_id,
+                expired_on: expires_on,
+                expired_at,
+            }
+        );
+    }
+
+    #[test]
+    fn certifications_that_never_expire_need_no_triggers() {
+        let clock = clock();
+        let mut state = DeadlinesState::default();
+
+        let recorded = DeadlinesEvent::Certification(CertificationEvent::CertificationRecorded(
+            CertificationRecorded {
+                id: Uuid::new_v4(),
+                employee_id: Uuid::new_v4(),
+                certification_type: CertificationType::FirstAid,
+                certificate_number: None,
+                issued_on: clock.now().date_naive(),
+                expires_on: None,
+                recorded_at: clock.now(),
+            },
+        ));
+
+        assert!(deadlines()
+            .handle(&mut state, &recorded, clock.now())
+            .is_empty());
+    }
+}
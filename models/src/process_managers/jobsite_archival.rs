@@ -0,0 +1,482 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[cfg(feature = "connect")]
+use crate::events::EventParseError;
+use crate::{
+    commands::Command,
+    events::{crew_assignment::CrewAssignmentEvent, jobsite::JobsiteEvent, shift::ShiftEvent},
+    local_time::{local_date, time_zone, DEFAULT_TIME_ZONE},
+    process_managers::ProcessManager,
+    projections::snapshot_position::SnapshotPositionKey,
+};
+
+/// Archiving a jobsite clocks out whoever is still on the clock there and
+/// cancels crew assignments that have not started yet. Shifts and
+/// assignments that reach an archived jobsite afterwards are undone too.
+pub struct JobsiteArchival;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ScheduledAssignment {
+    pub employee_id: Uuid,
+    pub jobsite_id: Uuid,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct OpenShift {
+    pub employee_id: Uuid,
+    pub jobsite_id: Uuid,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct JobsiteArchivalState {
+    /// Open shifts, who is on each and the jobsite it was clocked into
+    pub open_shifts: BTreeMap<Uuid, OpenShift>,
+    /// Crew assignments that have not ended yet
+    pub assignments: BTreeMap<Uuid, ScheduledAssignment>,
+    /// Merged jobsites and the jobsite their work is recorded at now
+    pub merged_into: BTreeMap<Uuid, Uuid>,
+    /// Archived jobsites and the day each was archived on
+    pub archived: BTreeMap<Uuid, NaiveDate>,
+    /// Jobsites' time zones, days at a jobsite are counted in its zone
+    #[serde(default)]
+    pub time_zones: BTreeMap<Uuid, String>,
+}
+
+impl JobsiteArchivalState {
+    /// Where work recorded against a jobsite ends up, following a merge
+    fn current_jobsite(&self, jobsite_id: Uuid) -> Uuid {
+        self.merged_into
+            .get(&jobsite_id)
+            .copied()
+            .unwrap_or(jobsite_id)
+    }
+
+    /// The day it is at `at` where work at the jobsite is recorded
+    fn local_date(&self, jobsite_id: Uuid, at: DateTime<Utc>) -> NaiveDate {
+        let time_zone_name = self
+            .time_zones
+            .get(&self.current_jobsite(jobsite_id))
+            .map_or(DEFAULT_TIME_ZONE, String::as_str);
+
+        local_date(at, &time_zone(time_zone_name))
+    }
+
+    fn is_archived(&self, jobsite_id: Uuid) -> bool {
+        self.archived
+            .contains_key(&self.current_jobsite(jobsite_id))
+    }
+}
+
+#[derive(Debug)]
+pub enum JobsiteArchivalEvent {
+    Jobsite(JobsiteEvent),
+    Shift(ShiftEvent),
+    CrewAssignment(CrewAssignmentEvent),
+}
+
+#[cfg(feature = "connect")]
+impl TryFrom<eventstore::ResolvedEvent> for JobsiteArchivalEvent {
+    type Error = EventParseError;
+
+    fn try_from(value: eventstore::ResolvedEvent) -> Result<Self, Self::Error> {
+        let stream_id = match &value.event {
+            Some(event) => event.stream_id.clone(),
+            None => return Err(EventParseError::MissingEventData),
+        };
+
+        match stream_id {
+            s if s.starts_with("jobsite-") => Ok(JobsiteArchivalEvent::Jobsite(value.try_into()?)),
+            s if s.starts_with("shift-") => Ok(JobsiteArchivalEvent::Shift(value.try_into()?)),
+            _ => Ok(JobsiteArchivalEvent::CrewAssignment(value.try_into()?)),
+        }
+    }
+}
+
+impl ProcessManager for JobsiteArchival {
+    type State = JobsiteArchivalState;
+    type Event = JobsiteArchivalEvent;
+
+    const KEY: SnapshotPositionKey = SnapshotPositionKey::JobsiteArchival;
+
+    #[cfg(feature = "connect")]
+    fn subscription_filter() -> eventstore::SubscriptionFilter {
+        eventstore::SubscriptionFilter::on_stream_name()
+            .add_prefix("jobsite-")
+            .add_prefix("shift-")
+            .add_prefix("crew_schedule-")
+    }
+
+    fn handle(
+        &self,
+        state: &mut Self::State,
+        event: &Self::Event,
+        recorded_at: DateTime<Utc>,
+    ) -> Vec<Command> {
+        // Assignments that have ended can't be cancelled any more
+        let assignments = std::mem::take(&mut state.assignments);
+        state.assignments = assignments
+            .into_iter()
+            .filter(|(_, assignment)| {
+                assignment.end_date >= state.local_date(assignment.jobsite_id, recorded_at)
+            })
+            .collect();
+
+        match event {
+            JobsiteArchivalEvent::Jobsite(JobsiteEvent::JobsiteCreated(created)) => {
+                state
+                    .time_zones
+                    .insert(created.id, created.time_zone.clone());
+                vec![]
+            }
+            JobsiteArchivalEvent::Jobsite(JobsiteEvent::JobsiteUpdated(updated)) => {
+                state
+                    .time_zones
+                    .insert(updated.id, updated.time_zone.clone());
+                vec![]
+            }
+            JobsiteArchivalEvent::Jobsite(JobsiteEvent::JobsiteArchived(archived)) => {
+                let archived_on = state.local_date(archived.id, archived.archived_at);
+                state.archived.insert(archived.id, archived_on);
+
+                let clock_outs = state
+                    .open_shifts
+                    .iter()
+                    .filter(|(_, shift)| state.current_jobsite(shift.jobsite_id) == archived.id)
+                    .map(|(shift_id, shift)| Command::ClockOut {
+                        shift_id: *shift_id,
+                        employee_id: shift.employee_id,
+                        clocked_out_at: archived.archived_at,
+                    });
+
+                let cancellations = state
+                    .assignments
+                    .iter()
+                    .filter(|(_, assignment)| {
+                        state.current_jobsite(assignment.jobsite_id) == archived.id
+                            && assignment.start_date > archived_on
+                    })
+                    .map(|(assignment_id, assignment)| Command::UnassignCrewMember {
+                        assignment_id: *assignment_id,
+                        employee_id: assignment.employee_id,
+                        unassigned_at: archived.archived_at,
+                    });
+
+                clock_outs.chain(cancellations).collect()
+            }
+            JobsiteArchivalEvent::Jobsite(JobsiteEvent::JobsitesMerged(merged)) => {
+                state.merged_into.insert(merged.source_id, merged.target_id);
+                vec![]
+            }
+            JobsiteArchivalEvent::Jobsite(JobsiteEvent::JobsitesUnmerged(unmerged)) => {
+                state.merged_into.remove(&unmerged.source_id);
+                vec![]
+            }
+            JobsiteArchivalEvent::Shift(ShiftEvent::ClockedIn(clocked_in)) => {
+                state.open_shifts.insert(
+                    clocked_in.shift_id,
+                    OpenShift {
+                        employee_id: clocked_in.employee_id,
+                        jobsite_id: clocked_in.jobsite_id,
+                    },
+                );
+
+                // Clocked in as the jobsite was archived, the shift gets no time
+                if state.is_archived(clocked_in.jobsite_id) {
+                    vec![Command::ClockOut {
+                        shift_id: clocked_in.shift_id,
+                        employee_id: clocked_in.employee_id,
+                        clocked_out_at: clocked_in.clocked_in_at,
+                    }]
+                } else {
+                    vec![]
+                }
+            }
+            JobsiteArchivalEvent::Shift(ShiftEvent::ClockedOut(clocked_out)) => {
+                state.open_shifts.remove(&clocked_out.shift_id);
+                vec![]
+            }
+            JobsiteArchivalEvent::CrewAssignment(CrewAssignmentEvent::CrewMemberAssigned(
+                assigned,
+            )) => {
+                if assigned.end_date >= state.local_date(assigned.jobsite_id, recorded_at) {
+                    state.assignments.insert(
+                        assigned.id,
+                        ScheduledAssignment {
+                            employee_id: assigned.employee_id,
+                            jobsite_id: assigned.jobsite_id,
+                            start_date: assigned.start_date,
+                            end_date: assigned.end_date,
+                        },
+                    );
+                }
+
+                if state.is_archived(assigned.jobsite_id) {
+                    vec![Command::UnassignCrewMember {
+                        assignment_id: assigned.id,
+                        employee_id: assigned.employee_id,
+                        unassigned_at: recorded_at,
+                    }]
+                } else {
+                    vec![]
+                }
+            }
+            JobsiteArchivalEvent::CrewAssignment(CrewAssignmentEvent::CrewMemberUnassigned(
+                unassigned,
+            )) => {
+                state.assignments.remove(&unassigned.id);
+                vec![]
+            }
+            _ => vec![],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Duration, NaiveDate, TimeZone, Utc};
+    use uuid::Uuid;
+
+    use super::{JobsiteArchival, JobsiteArchivalEvent, JobsiteArchivalState};
+    use crate::{
+        commands::Command,
+        events::{
+            crew_assignment::{CrewAssignmentEvent, CrewMemberAssigned, CrewMemberUnassigned},
+            jobsite::{JobsiteArchived, JobsiteCreated, JobsiteEvent, JobsitesMerged},
+            shift::{ClockedIn, ClockedOut, ShiftEvent},
+        },
+        process_managers::ProcessManager,
+    };
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 1, 27, 15, 0, 0).unwrap()
+    }
+
+    fn day(offset: i64) -> NaiveDate {
+        now().date_naive() + Duration::days(offset)
+    }
+
+    fn clock_in(state: &mut JobsiteArchivalState, jobsite_id: Uuid) -> (Uuid, Uuid, Vec<Command>) {
+        let (shift_id, employee_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let event = JobsiteArchivalEvent::Shift(ShiftEvent::ClockedIn(ClockedIn {
+            shift_id,
+            employee_id,
+            jobsite_id,
+            cost_code_id: None,
+            clocked_in_at: now(),
+            time_zone: "UTC".to_string(),
+            coordinates: None,
+            geofence: Default::default(),
+        }));
+
+        (
+            shift_id,
+            employee_id,
+            JobsiteArchival.handle(state, &event, now()),
+        )
+    }
+
+    fn assign(
+        state: &mut JobsiteArchivalState,
+        jobsite_id: Uuid,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> (Uuid, Uuid, Vec<Command>) {
+        let (id, employee_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let event = JobsiteArchivalEvent::CrewAssignment(CrewAssignmentEvent::CrewMemberAssigned(
+            CrewMemberAssigned {
+                id,
+                employee_id,
+                jobsite_id,
+                start_date,
+                end_date,
+                assigned_at: now(),
+            },
+        ));
+
+        (
+            id,
+            employee_id,
+            JobsiteArchival.handle(state, &event, now()),
+        )
+    }
+
+    fn archive(state: &mut JobsiteArchivalState, jobsite_id: Uuid) -> Vec<Command> {
+        let event = JobsiteArchivalEvent::Jobsite(JobsiteEvent::JobsiteArchived(JobsiteArchived {
+            id: jobsite_id,
+            archived_at: now(),
+        }));
+
+        JobsiteArchival.handle(state, &event, now())
+    }
+
+    #[test]
+    fn archiving_clocks_out_the_jobsite_and_cancels_upcoming_assignments() {
+        let (jobsite_id, other_jobsite_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let mut state = JobsiteArchivalState::default();
+
+        let (shift_id, clocked_in_id, _) = clock_in(&mut state, jobsite_id);
+        clock_in(&mut state, other_jobsite_id);
+        let (upcoming_id, employee_id, _) = assign(&mut state, jobsite_id, day(1), day(5));
+        assign(&mut state, jobsite_id, day(-2), day(2));
+        assign(&mut state, other_jobsite_id, day(1), day(5));
+
+        let commands = archive(&mut state, jobsite_id);
+
+        assert_eq!(
+            commands,
+            vec![
+                Command::ClockOut {
+                    shift_id,
+                    employee_id: clocked_in_id,
+                    clocked_out_at: now(),
+                },
+                Command::UnassignCrewMember {
+                    assignment_id: upcoming_id,
+                    employee_id,
+                    unassigned_at: now(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn finished_work_is_left_alone() {
+        let jobsite_id = Uuid::new_v4();
+        let mut state = JobsiteArchivalState::default();
+
+        let (shift_id, employee_id, _) = clock_in(&mut state, jobsite_id);
+        let clocked_out = JobsiteArchivalEvent::Shift(ShiftEvent::ClockedOut(ClockedOut {
+            shift_id,
+            employee_id,
+            clocked_out_at: now(),
+        }));
+        JobsiteArchival.handle(&mut state, &clocked_out, now());
+
+        let (assignment_id, employee_id, _) = assign(&mut state, jobsite_id, day(1), day(5));
+        let unassigned = JobsiteArchivalEvent::CrewAssignment(
+            CrewAssignmentEvent::CrewMemberUnassigned(CrewMemberUnassigned {
+                id: assignment_id,
+                employee_id,
+                unassigned_at: now(),
+            }),
+        );
+        JobsiteArchival.handle(&mut state, &unassigned, now());
+
+        assign(&mut state, jobsite_id, day(-5), day(-1));
+
+        assert!(archive(&mut state, jobsite_id).is_empty());
+        assert!(state.assignments.is_empty());
+    }
+
+    #[test]
+    fn work_arriving_after_archival_is_undone() {
+        let jobsite_id = Uuid::new_v4();
+        let mut state = JobsiteArchivalState::default();
+
+        archive(&mut state, jobsite_id);
+
+        let (shift_id, employee_id, commands) = clock_in(&mut state, jobsite_id);
+        assert_eq!(
+            commands,
+            vec![Command::ClockOut {
+                shift_id,
+                employee_id,
+                clocked_out_at: now(),
+            }]
+        );
+
+        let (assignment_id, employee_id, commands) = assign(&mut state, jobsite_id, day(3), day(4));
+        assert_eq!(
+            commands,
+            vec![Command::UnassignCrewMember {
+                assignment_id,
+                employee_id,
+                unassigned_at: now(),
+            }]
+        );
+    }
+
+    #[test]
+    fn merged_jobsites_are_archived_with_their_target() {
+        let (source_id, target_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let mut state = JobsiteArchivalState::default();
+
+        let (shift_id, employee_id, _) = clock_in(&mut state, source_id);
+        let merged = JobsiteArchivalEvent::Jobsite(JobsiteEvent::JobsitesMerged(JobsitesMerged {
+            source_id,
+            target_id,
+            merged_at: now(),
+        }));
+        JobsiteArchival.handle(&mut state, &merged, now());
+
+        assert_eq!(
+            archive(&mut state, target_id),
+            vec![Command::ClockOut {
+                shift_id,
+                employee_id,
+                clocked_out_at: now(),
+            }]
+        );
+    }
+
+    #[test]
+    fn upcoming_is_judged_by_the_day_at_the_jobsite() {
+        let jobsite_id = Uuid::new_v4();
+        let mut state = JobsiteArchivalState::default();
+
+        let created = JobsiteArchivalEvent::Jobsite(JobsiteEvent::JobsiteCreated(JobsiteCreated {
+            id: jobsite_id,
+            name: "Main St".to_string(),
+            project_id: None,
+            time_zone: "America/Denver".to_string(),
+        }));
+        JobsiteArchival.handle(&mut state, &created, now());
+
+        // Archived at 8pm in Denver, already the next day in UTC
+        let archived_at = Utc.with_ymd_and_hms(2025, 1, 28, 3, 0, 0).unwrap();
+        let tomorrow = NaiveDate::from_ymd_opt(2025, 1, 28).unwrap();
+        let (assignment_id, employee_id, _) = assign(&mut state, jobsite_id, tomorrow, tomorrow);
+
+        let archived =
+            JobsiteArchivalEvent::Jobsite(JobsiteEvent::JobsiteArchived(JobsiteArchived {
+                id: jobsite_id,
+                archived_at,
+            }));
+        let commands = JobsiteArchival.handle(&mut state, &archived, archived_at);
+
+        assert_eq!(
+            state.archived.get(&jobsite_id),
+            Some(&NaiveDate::from_ymd_opt(2025, 1, 27).unwrap())
+        );
+        assert_eq!(
+            commands,
+            vec![Command::UnassignCrewMember {
+                assignment_id,
+                employee_id,
+                unassigned_at: archived_at,
+            }]
+        );
+    }
+
+    #[test]
+    fn state_survives_being_saved_as_json() {
+        let jobsite_id = Uuid::new_v4();
+        let mut state = JobsiteArchivalState::default();
+
+        clock_in(&mut state, jobsite_id);
+        assign(&mut state, jobsite_id, day(1), day(5));
+        archive(&mut state, jobsite_id);
+
+        let saved = serde_json::to_string(&state).unwrap();
+        assert_eq!(
+            serde_json::from_str::<JobsiteArchivalState>(&saved).unwrap(),
+            state
+        );
+    }
+}
@@ -0,0 +1,290 @@
+use chrono::{DateTime, Utc};
+use serde::{de::DeserializeOwned, Serialize};
+
+#[cfg(feature = "connect")]
+use {
+    crate::{
+        commands::CommandMetadata,
+        events::EventParseError,
+        projections::{
+            process_manager_state::ProcessManagerState, snapshot_position::SnapshotPosition,
+        },
+    },
+    log::{error, warn},
+};
+
+use crate::{commands::Command, projections::snapshot_position::SnapshotPositionKey};
+
+pub mod deadlines;
+pub mod jobsite_archival;
+
+/// How many times an event is handled before its process manager stops
+#[cfg(feature = "connect")]
+const HANDLE_ATTEMPTS: u32 = 5;
+
+/// How long to wait before handling a failed event again, doubled after
+/// every attempt
+#[cfg(feature = "connect")]
+const RETRY_BACKOFF: std::time::Duration = std::time::Duration::from_millis(500);
+
+/// A rule that spans aggregates. It subscribes to events like a read model
+/// does, folds them into state of its own and answers with the commands the
+/// rule calls for. Deciding is kept free of IO so rules can be tested on
+/// plain events.
+pub trait ProcessManager {
+    /// What the process manager remembers between events
+    type State: Serialize + DeserializeOwned + Default;
+    /// The events it subscribes to
+    type Event;
+
+    /// Where its state and snapshot position are kept
+    const KEY: SnapshotPositionKey;
+
+    #[cfg(feature = "connect")]
+    fn subscription_filter() -> eventstore::SubscriptionFilter;
+
+    /// Fold an event recorded at `recorded_at` into the state, returning the
+    /// commands it calls for
+    fn handle(
+        &self,
+        state: &mut Self::State,
+        event: &Self::Event,
+        recorded_at: DateTime<Utc>,
+    ) -> Vec<Command>;
+}
+
+/**
+ * Process manager runner
+ * Subscribes a process manager to the event store, keeps its state and
+ * snapshot position, and issues its commands with the causing event set
+ */
+#[cfg(feature = "connect")]
+pub struct ProcessManagerRunner<P> {
+    eventstore: std::sync::Arc<eventstore::Client>,
+    db_pool: std::sync::Arc<sqlx::PgPool>,
+    process_manager: P,
+}
+
+#[cfg(feature = "connect")]
+impl<P> ProcessManagerRunner<P>
+where
+    P: ProcessManager,
+    P::Event: TryFrom<eventstore::ResolvedEvent, Error = EventParseError>,
+{
+    pub fn new(
+        eventstore: std::sync::Arc<eventstore::Client>,
+        db_pool: std::sync::Arc<sqlx::PgPool>,
+        process_manager: P,
+    ) -> Self {
+        Self {
+            eventstore,
+            db_pool,
+            process_manager,
+        }
+    }
+
+    /**
+     * Subscribe to the process manager's events and handle them in order.
+     * An event that fails is handled again after a backoff, and the
+     * subscription stops if it keeps failing rather than saving a later
+     * position past it.
+     */
+    pub async fn subscribe(&self) {
+        let snapshot_position = match self.get_snapshot_position().await {
+            Ok(position) => position,
+            Err(e) => {
+                error!("Failed to get snapshot position: {}", e);
+                return;
+            }
+        };
+
+        let mut subscription = self
+            .eventstore
+            .subscribe_to_all(
+                &eventstore::SubscribeToAllOptions::default()
+                    .position(eventstore::StreamPosition::Position(eventstore::Position {
+                        commit: snapshot_position as u64,
+                        prepare: snapshot_position as u64,
+                    }))
+                    .filter(P::subscription_filter()),
+            )
+            .await;
+
+        while let Ok(resolved_event) = subscription.next().await {
+            let Some(recorded) = resolved_event.event.as_ref() else {
+                continue;
+            };
+
+            let event_id = recorded.id;
+            let position = recorded.position.commit;
+            let recorded_at = recorded.created;
+            let caused_by =
+                serde_json::from_slice::<CommandMetadata>(&recorded.custom_metadata).ok();
+
+            let event: P::Event = match resolved_event.try_into() {
+                Ok(event) => event,
+                Err(e) => {
+                    error!("Failed to parse event: {}", e);
+                    continue;
+                }
+            };
+
+            let metadata = CommandMetadata::caused_by(event_id, caused_by);
+
+            let mut attempt = 1;
+            while let Err(e) = self.handle(&event, recorded_at, &metadata, position).await {
+                if attempt == HANDLE_ATTEMPTS {
+                    error!(
+                        "Failed to handle event {}, stopping {}: {}",
+                        event_id,
+                        P::KEY.to_string(),
+                        e
+                    );
+                    return;
+                }
+
+                warn!("Failed to handle event {}, retrying: {}", event_id, e);
+                tokio::time::sleep(RETRY_BACKOFF * 2u32.pow(attempt - 1)).await;
+                attempt += 1;
+            }
+        }
+    }
+
+    /**
+     * Fold the event into the saved state and issue the commands it calls
+     * for, then save the state along with the event's position. A restart in
+     * between issues the commands again, with the same event ids, so they
+     * have to be safe to repeat. A command the domain turns away doesn't
+     * stop the ones after it.
+     */
+    async fn handle(
+        &self,
+        event: &P::Event,
+        recorded_at: DateTime<Utc>,
+        metadata: &CommandMetadata,
+        position: u64,
+    ) -> anyhow::Result<()> {
+        let mut transaction = self.db_pool.begin().await?;
+
+        let mut state = match ProcessManagerState::get_by_key(&mut transaction, P::KEY).await? {
+            Some(state) => serde_json::from_str(&state)?,
+            None => P::State::default(),
+        };
+
+        let issuer = P::KEY.to_string();
+        let commands = self.process_manager.handle(&mut state, event, recorded_at);
+        for (index, command) in commands.into_iter().enumerate() {
+            let event_id = metadata.command_event_id(&issuer, index);
+            let result = command
+                .execute(&self.eventstore, &mut transaction, metadata, event_id)
+                .await;
+
+            settle(&command, result)?;
+        }
+
+        ProcessManagerState::save(&mut transaction, P::KEY, &serde_json::to_string(&state)?)
+            .await?;
+
+        SnapshotPosition {
+            key: P::KEY,
+            value: position as i64,
+        }
+        .insert(&mut transaction)
+        .await?;
+
+        transaction.commit().await?;
+
+        Ok(())
+    }
+
+    /**
+     * Get the current snapshot position for the process manager
+     */
+    pub async fn get_snapshot_position(&self) -> anyhow::Result<i64> {
+        let mut transaction = self.db_pool.begin().await?;
+
+        let snapshot_position = SnapshotPosition::get_by_key(&mut transaction, P::KEY).await?;
+
+        transaction.commit().await?;
+
+        Ok(snapshot_position.map_or(0, |position| position.value))
+    }
+}
+
+/// The outcome of one command in a batch. One the domain turned away, like
+/// clocking out a shift that was punched out at the same moment or
+/// cancelling an assignment twice, is done with, anything else fails the
+/// event so it is handled again.
+#[cfg(feature = "connect")]
+fn settle(command: &Command, result: anyhow::Result<()>) -> anyhow::Result<()> {
+    use crate::commands::{
+        crew_assignment::AssignmentCancelled, daily_log::DailyLogLocked, shift::ShiftConflict,
+    };
+
+    match result {
+        Err(e)
+            if e.is::<ShiftConflict>()
+                || e.is::<DailyLogLocked>()
+                || e.is::<AssignmentCancelled>() =>
+        {
+            warn!("Command {:?} was turned away: {}", command, e);
+            Ok(())
+        }
+        result => result,
+    }
+}
+
+#[cfg(all(test, feature = "connect"))]
+mod tests {
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    use super::settle;
+    use crate::commands::{
+        crew_assignment::AssignmentCancelled, daily_log::DailyLogLocked, shift::ShiftConflict,
+        Command,
+    };
+
+    fn clock_out() -> Command {
+        Command::ClockOut {
+            shift_id: Uuid::new_v4(),
+            employee_id: Uuid::new_v4(),
+            clocked_out_at: Utc::now(),
+        }
+    }
+
+    /// Settle each command's result in order as the runner does, stopping
+    /// at the first one that fails the event
+    fn settle_all(batch: Vec<anyhow::Result<()>>) -> anyhow::Result<()> {
+        batch
+            .into_iter()
+            .try_for_each(|result| settle(&clock_out(), result))
+    }
+
+    #[test]
+    fn a_command_turned_away_leaves_the_rest_of_the_batch() {
+        let batch = vec![
+            Ok(()),
+            Err(ShiftConflict::AlreadyClockedIn.into()),
+            Err(AssignmentCancelled.into()),
+            Err(DailyLogLocked.into()),
+            Ok(()),
+        ];
+
+        assert!(settle_all(batch).is_ok());
+    }
+
+    #[test]
+    fn a_command_that_fails_fails_the_batch() {
+        let batch = vec![
+            Ok(()),
+            Err(anyhow::anyhow!("connection reset")),
+            Err(ShiftConflict::NotClockedIn.into()),
+        ];
+
+        assert_eq!(
+            settle_all(batch).unwrap_err().to_string(),
+            "connection reset"
+        );
+    }
+}
@@ -0,0 +1,144 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "connect")]
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[cfg(feature = "connect")]
+use crate::events::attachment::{AttachmentAdded, AttachmentRemoved};
+
+/// A photo or document attached to a jobsite, or to one of its daily logs
+/// when `log_date` is set. The file itself lives in attachment storage under
+/// `content_hash`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Attachment {
+    pub id: Uuid,
+    pub jobsite_id: Uuid,
+    pub log_date: Option<NaiveDate>,
+    pub file_name: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub content_hash: String,
+    pub thumbnail_hash: Option<String>,
+    pub added_at: DateTime<Utc>,
+    pub removed_at: Option<DateTime<Utc>>,
+}
+
+impl Attachment {
+    pub fn stream_name(id: &Uuid) -> String {
+        format!("attachment-{}", id)
+    }
+
+    pub fn is_image(&self) -> bool {
+        self.content_type.starts_with("image/")
+    }
+}
+
+#[cfg(feature = "connect")]
+impl Attachment {
+    pub async fn add(
+        transaction: &mut Transaction<'_, Postgres>,
+        added_event: &AttachmentAdded,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            INSERT INTO attachments (id, jobsite_id, log_date, file_name, content_type, size_bytes,
+                content_hash, thumbnail_hash, added_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            RETURNING id, jobsite_id, log_date, file_name, content_type, size_bytes, content_hash,
+                thumbnail_hash, added_at, removed_at;
+            "#,
+            added_event.id,
+            added_event.jobsite_id,
+            added_event.log_date,
+            added_event.file_name,
+            added_event.content_type,
+            added_event.size_bytes,
+            added_event.content_hash,
+            added_event.thumbnail_hash,
+            added_event.added_at
+        )
+        .fetch_one(&mut **transaction)
+        .await
+    }
+
+    pub async fn remove(
+        transaction: &mut Transaction<'_, Postgres>,
+        removed_event: &AttachmentRemoved,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            UPDATE attachments
+            SET removed_at = $2
+            WHERE id = $1
+            RETURNING id, jobsite_id, log_date, file_name, content_type, size_bytes, content_hash,
+                thumbnail_hash, added_at, removed_at;
+            "#,
+            removed_event.id,
+            removed_event.removed_at
+        )
+        .fetch_one(&mut **transaction)
+        .await
+    }
+
+    pub async fn get_by_id(
+        transaction: &mut Transaction<'_, Postgres>,
+        id: &Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            SELECT id, jobsite_id, log_date, file_name, content_type, size_bytes, content_hash,
+                thumbnail_hash, added_at, removed_at
+            FROM attachments
+            WHERE id = $1
+            "#,
+            id
+        )
+        .fetch_optional(&mut **transaction)
+        .await
+    }
+
+    /// Attachments on the jobsite itself, newest first
+    pub async fn get_list_by_jobsite(
+        transaction: &mut Transaction<'_, Postgres>,
+        jobsite_id: &Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            SELECT id, jobsite_id, log_date, file_name, content_type, size_bytes, content_hash,
+                thumbnail_hash, added_at, removed_at
+            FROM attachments
+            WHERE jobsite_id = $1 AND log_date IS NULL AND removed_at IS NULL
+            ORDER BY added_at DESC
+            "#,
+            jobsite_id
+        )
+        .fetch_all(&mut **transaction)
+        .await
+    }
+
+    /// Attachments on any of a jobsite's daily logs, in the order they were
+    /// added
+    pub async fn get_list_by_daily_logs(
+        transaction: &mut Transaction<'_, Postgres>,
+        jobsite_id: &Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            SELECT id, jobsite_id, log_date, file_name, content_type, size_bytes, content_hash,
+                thumbnail_hash, added_at, removed_at
+            FROM attachments
+            WHERE jobsite_id = $1 AND log_date IS NOT NULL AND removed_at IS NULL
+            ORDER BY added_at
+            "#,
+            jobsite_id
+        )
+        .fetch_all(&mut **transaction)
+        .await
+    }
+}
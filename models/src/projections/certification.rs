@@ -0,0 +1,333 @@
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "connect")]
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[cfg(feature = "connect")]
+use crate::events::certification::{
+    CertificationExpired, CertificationExpiryWarned, CertificationRecorded,
+};
+
+/// How many days ahead of expiry a certification is warned about
+pub const CERTIFICATION_WARNING_DAYS: i64 = 30;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum CertificationType {
+    FirstAid,
+    FallProtection,
+    ConfinedSpace,
+    Scaffolding,
+    Forklift,
+    AerialLift,
+    Crane,
+}
+
+impl CertificationType {
+    pub const ALL: [CertificationType; 7] = [
+        CertificationType::FirstAid,
+        CertificationType::FallProtection,
+        CertificationType::ConfinedSpace,
+        CertificationType::Scaffolding,
+        CertificationType::Forklift,
+        CertificationType::AerialLift,
+        CertificationType::Crane,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CertificationType::FirstAid => "first_aid",
+            CertificationType::FallProtection => "fall_protection",
+            CertificationType::ConfinedSpace => "confined_space",
+            CertificationType::Scaffolding => "scaffolding",
+            CertificationType::Forklift => "forklift",
+            CertificationType::AerialLift => "aerial_lift",
+            CertificationType::Crane => "crane",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            CertificationType::FirstAid => "First aid",
+            CertificationType::FallProtection => "Fall protection",
+            CertificationType::ConfinedSpace => "Confined space",
+            CertificationType::Scaffolding => "Scaffolding",
+            CertificationType::Forklift => "Forklift",
+            CertificationType::AerialLift => "Aerial lift",
+            CertificationType::Crane => "Crane",
+        }
+    }
+}
+
+impl From<String> for CertificationType {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "first_aid" => CertificationType::FirstAid,
+            "fall_protection" => CertificationType::FallProtection,
+            "confined_space" => CertificationType::ConfinedSpace,
+            "scaffolding" => CertificationType::Scaffolding,
+            "forklift" => CertificationType::Forklift,
+            "aerial_lift" => CertificationType::AerialLift,
+            "crane" => CertificationType::Crane,
+            _ => panic!("Invalid CertificationType"),
+        }
+    }
+}
+
+/// A certification an employee holds. Renewing one records a new
+/// certification, the old one stays on record until it expires.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Certification {
+    pub id: Uuid,
+    pub employee_id: Uuid,
+    pub employee_name: String,
+    pub certification_type: CertificationType,
+    pub certificate_number: Option<String>,
+    pub issued_on: NaiveDate,
+    /// Certifications without an expiry date never lapse
+    pub expires_on: Option<NaiveDate>,
+    pub recorded_at: DateTime<Utc>,
+    pub warned_at: Option<DateTime<Utc>>,
+    pub expired_at: Option<DateTime<Utc>>,
+}
+
+impl Certification {
+    pub fn stream_name(employee_id: &Uuid) -> String {
+        format!("certification-{}", employee_id)
+    }
+
+    pub fn is_expired_on(&self, date: NaiveDate) -> bool {
+        self.expires_on.is_some_and(|expires_on| expires_on < date)
+    }
+
+    /// Whether the certification lapses within the warning window after
+    /// `date`
+    pub fn is_expiring_on(&self, date: NaiveDate) -> bool {
+        self.expires_on.is_some_and(|expires_on| {
+            expires_on >= date && expires_on <= date + Duration::days(CERTIFICATION_WARNING_DAYS)
+        })
+    }
+}
+
+#[cfg(feature = "connect")]
+impl Certification {
+    pub async fn record(
+        transaction: &mut Transaction<'_, Postgres>,
+        recorded_event: &CertificationRecorded,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query!(
+            r#"
+            INSERT INTO certifications (id, employee_id, certification_type, certificate_number,
+                issued_on, expires_on, recorded_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            "#,
+            recorded_event.id,
+            recorded_event.employee_id,
+            recorded_event.certification_type.as_str(),
+            recorded_event.certificate_number,
+            recorded_event.issued_on,
+            recorded_event.expires_on,
+            recorded_event.recorded_at
+        )
+        .execute(&mut **transaction)
+        .await?;
+
+        Self::get_by_id(transaction, &recorded_event.id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)
+    }
+
+    pub async fn warn_expiry(
+        transaction: &mut Transaction<'_, Postgres>,
+        warned_event: &CertificationExpiryWarned,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query!(
+            r#"
+            UPDATE certifications
+            SET warned_at = $2
+            WHERE id = $1
+            "#,
+            warned_event.id,
+            warned_event.warned_at
+        )
+        .execute(&mut **transaction)
+        .await?;
+
+        Self::get_by_id(transaction, &warned_event.id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)
+    }
+
+    pub async fn expire(
+        transaction: &mut Transaction<'_, Postgres>,
+        expired_event: &CertificationExpired,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query!(
+            r#"
+            UPDATE certifications
+            SET expired_at = $2
+            WHERE id = $1
+            "#,
+            expired_event.id,
+            expired_event.expired_at
+        )
+        .execute(&mut **transaction)
+        .await?;
+
+        Self::get_by_id(transaction, &expired_event.id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)
+    }
+
+    pub async fn get_by_id(
+        transaction: &mut Transaction<'_, Postgres>,
+        id: &Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            SELECT c.id, c.employee_id, e.name AS employee_name, c.certification_type,
+                c.certificate_number, c.issued_on, c.expires_on, c.recorded_at, c.warned_at,
+                c.expired_at
+            FROM certifications c
+            JOIN employees e ON e.id = c.employee_id
+            WHERE c.id = $1
+            "#,
+            id
+        )
+        .fetch_optional(&mut **transaction)
+        .await
+    }
+
+    /// Every certification on record, by employee and then most recent first
+    pub async fn get_list(
+        transaction: &mut Transaction<'_, Postgres>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            SELECT c.id, c.employee_id, e.name AS employee_name, c.certification_type,
+                c.certificate_number, c.issued_on, c.expires_on, c.recorded_at, c.warned_at,
+                c.expired_at
+            FROM certifications c
+            JOIN employees e ON e.id = c.employee_id
+            ORDER BY e.name, c.certification_type, c.issued_on DESC
+            "#
+        )
+        .fetch_all(&mut **transaction)
+        .await
+    }
+
+    /// Warned or expired certifications that haven't been renewed, soonest
+    /// expiry first
+    pub async fn get_alerts(
+        transaction: &mut Transaction<'_, Postgres>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            SELECT c.id, c.employee_id, e.name AS employee_name, c.certification_type,
+                c.certificate_number, c.issued_on, c.expires_on, c.recorded_at, c.warned_at,
+                c.expired_at
+            FROM certifications c
+            JOIN employees e ON e.id = c.employee_id
+            WHERE (c.warned_at IS NOT NULL OR c.expired_at IS NOT NULL)
+                AND NOT EXISTS (
+                    SELECT 1 FROM certifications r
+                    WHERE r.employee_id = c.employee_id
+                        AND r.certification_type = c.certification_type
+                        AND r.id != c.id
+                        AND (r.expires_on IS NULL OR r.expires_on > c.expires_on)
+                )
+            ORDER BY c.expires_on, e.name
+            "#
+        )
+        .fetch_all(&mut **transaction)
+        .await
+    }
+
+    /// Whether the employee holds a certification of the same type that
+    /// lasts longer, so this one lapsing needs no warning
+    pub async fn is_renewed(
+        transaction: &mut Transaction<'_, Postgres>,
+        certification: &Certification,
+    ) -> Result<bool, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM certifications r
+                WHERE r.employee_id = $1
+                    AND r.certification_type = $2
+                    AND r.id != $3
+                    AND (r.expires_on IS NULL OR r.expires_on > $4)
+            ) AS "renewed!"
+            "#,
+            certification.employee_id,
+            certification.certification_type.as_str(),
+            certification.id,
+            certification.expires_on
+        )
+        .fetch_one(&mut **transaction)
+        .await
+    }
+
+    /// Certifications a jobsite requires its crew to hold
+    pub async fn get_required_by_jobsite(
+        transaction: &mut Transaction<'_, Postgres>,
+        jobsite_id: &Uuid,
+    ) -> Result<Vec<CertificationType>, sqlx::Error> {
+        let certification_types = sqlx::query_scalar!(
+            r#"
+            SELECT certification_type
+            FROM jobsite_certifications
+            WHERE jobsite_id = $1
+            ORDER BY certification_type
+            "#,
+            jobsite_id
+        )
+        .fetch_all(&mut **transaction)
+        .await?;
+
+        Ok(certification_types
+            .into_iter()
+            .map(CertificationType::from)
+            .collect())
+    }
+
+    /// Certifications a jobsite requires that the employee won't hold for
+    /// the whole of `start_date` to `end_date`
+    pub async fn get_missing_for_assignment(
+        transaction: &mut Transaction<'_, Postgres>,
+        employee_id: &Uuid,
+        jobsite_id: &Uuid,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> Result<Vec<CertificationType>, sqlx::Error> {
+        let certification_types = sqlx::query_scalar!(
+            r#"
+            SELECT jc.certification_type
+            FROM jobsite_certifications jc
+            WHERE jc.jobsite_id = $2
+                AND NOT EXISTS (
+                    SELECT 1 FROM certifications c
+                    WHERE c.employee_id = $1
+                        AND c.certification_type = jc.certification_type
+                        AND c.issued_on <= $3
+                        AND (c.expires_on IS NULL OR c.expires_on >= $4)
+                )
+            ORDER BY jc.certification_type
+            "#,
+            employee_id,
+            jobsite_id,
+            start_date,
+            end_date
+        )
+        .fetch_all(&mut **transaction)
+        .await?;
+
+        Ok(certification_types
+            .into_iter()
+            .map(CertificationType::from)
+            .collect())
+    }
+}
@@ -0,0 +1,211 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "connect")]
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[cfg(feature = "connect")]
+use crate::events::change_order::{
+    ChangeOrderApproved, ChangeOrderDrafted, ChangeOrderRejected, ChangeOrderSubmitted,
+};
+
+/// Change orders are drafted, submitted to the client, then either approved
+/// or rejected
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum ChangeOrderStatus {
+    Drafted,
+    Submitted,
+    Approved,
+    Rejected,
+}
+
+impl ChangeOrderStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChangeOrderStatus::Drafted => "drafted",
+            ChangeOrderStatus::Submitted => "submitted",
+            ChangeOrderStatus::Approved => "approved",
+            ChangeOrderStatus::Rejected => "rejected",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ChangeOrderStatus::Drafted => "Draft",
+            ChangeOrderStatus::Submitted => "Submitted to client",
+            ChangeOrderStatus::Approved => "Approved",
+            ChangeOrderStatus::Rejected => "Rejected",
+        }
+    }
+}
+
+impl From<String> for ChangeOrderStatus {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "drafted" => ChangeOrderStatus::Drafted,
+            "submitted" => ChangeOrderStatus::Submitted,
+            "approved" => ChangeOrderStatus::Approved,
+            "rejected" => ChangeOrderStatus::Rejected,
+            _ => panic!("Invalid ChangeOrderStatus"),
+        }
+    }
+}
+
+/// A change to a jobsite's scope. The amount and hours are negative when
+/// scope is taken away.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ChangeOrder {
+    pub id: Uuid,
+    pub jobsite_id: Uuid,
+    pub title: String,
+    pub description: Option<String>,
+    pub amount_cents: i64,
+    pub minutes: i64,
+    pub status: ChangeOrderStatus,
+    pub rejection_reason: Option<String>,
+    pub drafted_at: DateTime<Utc>,
+    pub submitted_at: Option<DateTime<Utc>>,
+    pub approved_at: Option<DateTime<Utc>>,
+    pub rejected_at: Option<DateTime<Utc>>,
+}
+
+impl ChangeOrder {
+    pub fn stream_name(id: &Uuid) -> String {
+        format!("change_order-{}", id)
+    }
+}
+
+#[cfg(feature = "connect")]
+impl ChangeOrder {
+    pub async fn draft(
+        transaction: &mut Transaction<'_, Postgres>,
+        drafted_event: &ChangeOrderDrafted,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query!(
+            r#"
+            INSERT INTO change_orders (id, jobsite_id, title, description, amount_cents, minutes,
+                status, drafted_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            "#,
+            drafted_event.id,
+            drafted_event.jobsite_id,
+            drafted_event.title,
+            drafted_event.description,
+            drafted_event.amount_cents,
+            drafted_event.minutes,
+            ChangeOrderStatus::Drafted.as_str(),
+            drafted_event.drafted_at
+        )
+        .execute(&mut **transaction)
+        .await?;
+
+        Self::get_by_id(transaction, &drafted_event.id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)
+    }
+
+    pub async fn submit(
+        transaction: &mut Transaction<'_, Postgres>,
+        submitted_event: &ChangeOrderSubmitted,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query!(
+            r#"
+            UPDATE change_orders
+            SET status = $2, submitted_at = $3
+            WHERE id = $1
+            "#,
+            submitted_event.id,
+            ChangeOrderStatus::Submitted.as_str(),
+            submitted_event.submitted_at
+        )
+        .execute(&mut **transaction)
+        .await?;
+
+        Self::get_by_id(transaction, &submitted_event.id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)
+    }
+
+    pub async fn approve(
+        transaction: &mut Transaction<'_, Postgres>,
+        approved_event: &ChangeOrderApproved,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query!(
+            r#"
+            UPDATE change_orders
+            SET status = $2, approved_at = $3
+            WHERE id = $1
+            "#,
+            approved_event.id,
+            ChangeOrderStatus::Approved.as_str(),
+            approved_event.approved_at
+        )
+        .execute(&mut **transaction)
+        .await?;
+
+        Self::get_by_id(transaction, &approved_event.id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)
+    }
+
+    pub async fn reject(
+        transaction: &mut Transaction<'_, Postgres>,
+        rejected_event: &ChangeOrderRejected,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query!(
+            r#"
+            UPDATE change_orders
+            SET status = $2, rejection_reason = $3, rejected_at = $4
+            WHERE id = $1
+            "#,
+            rejected_event.id,
+            ChangeOrderStatus::Rejected.as_str(),
+            rejected_event.reason,
+            rejected_event.rejected_at
+        )
+        .execute(&mut **transaction)
+        .await?;
+
+        Self::get_by_id(transaction, &rejected_event.id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)
+    }
+
+    pub async fn get_by_id(
+        transaction: &mut Transaction<'_, Postgres>,
+        id: &Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            SELECT id, jobsite_id, title, description, amount_cents, minutes, status,
+                rejection_reason, drafted_at, submitted_at, approved_at, rejected_at
+            FROM change_orders
+            WHERE id = $1
+            "#,
+            id
+        )
+        .fetch_optional(&mut **transaction)
+        .await
+    }
+
+    /// A jobsite's change orders in the order they were drafted
+    pub async fn get_list_by_jobsite(
+        transaction: &mut Transaction<'_, Postgres>,
+        jobsite_id: &Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            SELECT id, jobsite_id, title, description, amount_cents, minutes, status,
+                rejection_reason, drafted_at, submitted_at, approved_at, rejected_at
+            FROM change_orders
+            WHERE jobsite_id = $1
+            ORDER BY drafted_at
+            "#,
+            jobsite_id
+        )
+        .fetch_all(&mut **transaction)
+        .await
+    }
+}
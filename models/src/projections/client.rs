@@ -0,0 +1,83 @@
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "connect")]
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[cfg(feature = "connect")]
+use crate::events::client::ClientCreated;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Client {
+    pub id: Uuid,
+    pub name: String,
+}
+
+#[cfg(feature = "connect")]
+impl Client {
+    pub async fn create(
+        transaction: &mut Transaction<'_, Postgres>,
+        created_event: &ClientCreated,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            INSERT INTO clients (id, name)
+            VALUES ($1, $2)
+            RETURNING *;
+            "#,
+            created_event.id,
+            created_event.name
+        )
+        .fetch_one(&mut **transaction)
+        .await
+    }
+
+    pub async fn get_by_id(
+        transaction: &mut Transaction<'_, Postgres>,
+        id: &Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            SELECT id, name
+            FROM clients
+            WHERE id = $1
+            "#,
+            id
+        )
+        .fetch_optional(&mut **transaction)
+        .await
+    }
+
+    pub async fn get_by_name(
+        transaction: &mut Transaction<'_, Postgres>,
+        name: &str,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            SELECT id, name
+            FROM clients
+            WHERE name = $1
+            "#,
+            name
+        )
+        .fetch_optional(&mut **transaction)
+        .await
+    }
+
+    pub async fn get_list(
+        transaction: &mut Transaction<'_, Postgres>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            SELECT id, name
+            FROM clients
+            ORDER BY name
+            "#,
+        )
+        .fetch_all(&mut **transaction)
+        .await
+    }
+}
@@ -0,0 +1,280 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "connect")]
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[cfg(feature = "connect")]
+use crate::events::comment::{CommentDeleted, CommentEdited, CommentPosted};
+use crate::projections::employee::Employee;
+
+/// Longest comment that can be posted, in characters
+pub const MAX_COMMENT_LENGTH: usize = 2000;
+
+/// The employees named with `@` in a comment, in the order they're first
+/// named. Where names overlap, such as `@Sam` and `@Sam Lee`, the longest
+/// one that fits is the one meant.
+pub fn find_mentions(body: &str, employees: &[Employee]) -> Vec<Uuid> {
+    let mut mentions = Vec::new();
+
+    for (at, _) in body.match_indices('@') {
+        let rest = &body[at + 1..];
+
+        let mentioned = employees
+            .iter()
+            .filter(|employee| is_mention_of(rest, &employee.name))
+            .max_by_key(|employee| employee.name.len());
+
+        if let Some(employee) = mentioned {
+            if !mentions.contains(&employee.id) {
+                mentions.push(employee.id);
+            }
+        }
+    }
+
+    mentions
+}
+
+/// Whether the text after an `@` starts with the name, as a whole word
+fn is_mention_of(rest: &str, name: &str) -> bool {
+    if name.is_empty() {
+        return false;
+    }
+
+    match rest.get(..name.len()) {
+        Some(candidate) => {
+            candidate.eq_ignore_ascii_case(name)
+                && !rest[name.len()..].starts_with(|c: char| c.is_alphanumeric())
+        }
+        None => false,
+    }
+}
+
+/// A comment in a jobsite's thread
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Comment {
+    pub id: Uuid,
+    pub jobsite_id: Uuid,
+    /// Missing for comments posted without an author in their metadata
+    pub author_id: Option<Uuid>,
+    pub author_name: Option<String>,
+    pub body: String,
+    /// Names of the employees mentioned
+    pub mentions: Vec<String>,
+    pub posted_at: DateTime<Utc>,
+    pub edited_at: Option<DateTime<Utc>>,
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+impl Comment {
+    pub fn stream_name(id: &Uuid) -> String {
+        format!("comment-{}", id)
+    }
+
+    pub fn is_deleted(&self) -> bool {
+        self.deleted_at.is_some()
+    }
+
+    /// Only the author can edit or delete a comment
+    pub fn is_written_by(&self, employee_id: &Uuid) -> bool {
+        self.author_id == Some(*employee_id)
+    }
+}
+
+#[cfg(feature = "connect")]
+impl Comment {
+    pub async fn post(
+        transaction: &mut Transaction<'_, Postgres>,
+        posted_event: &CommentPosted,
+        author_id: Option<Uuid>,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query!(
+            r#"
+            INSERT INTO comments (id, jobsite_id, author_id, body, posted_at)
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+            posted_event.id,
+            posted_event.jobsite_id,
+            author_id,
+            posted_event.body,
+            posted_event.posted_at
+        )
+        .execute(&mut **transaction)
+        .await?;
+
+        Self::set_mentions(transaction, &posted_event.id, &posted_event.mentions).await?;
+
+        Self::get_by_id(transaction, &posted_event.id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)
+    }
+
+    pub async fn edit(
+        transaction: &mut Transaction<'_, Postgres>,
+        edited_event: &CommentEdited,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query!(
+            r#"
+            UPDATE comments
+            SET body = $2, edited_at = $3
+            WHERE id = $1
+            "#,
+            edited_event.id,
+            edited_event.body,
+            edited_event.edited_at
+        )
+        .execute(&mut **transaction)
+        .await?;
+
+        Self::set_mentions(transaction, &edited_event.id, &edited_event.mentions).await?;
+
+        Self::get_by_id(transaction, &edited_event.id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)
+    }
+
+    pub async fn delete(
+        transaction: &mut Transaction<'_, Postgres>,
+        deleted_event: &CommentDeleted,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query!(
+            r#"
+            UPDATE comments
+            SET deleted_at = $2
+            WHERE id = $1
+            "#,
+            deleted_event.id,
+            deleted_event.deleted_at
+        )
+        .execute(&mut **transaction)
+        .await?;
+
+        Self::get_by_id(transaction, &deleted_event.id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)
+    }
+
+    async fn set_mentions(
+        transaction: &mut Transaction<'_, Postgres>,
+        id: &Uuid,
+        mentions: &[Uuid],
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+            DELETE FROM comment_mentions
+            WHERE comment_id = $1
+            "#,
+            id
+        )
+        .execute(&mut **transaction)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO comment_mentions (comment_id, employee_id)
+            SELECT $1, UNNEST($2::UUID[])
+            ON CONFLICT DO NOTHING
+            "#,
+            id,
+            mentions
+        )
+        .execute(&mut **transaction)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn get_by_id(
+        transaction: &mut Transaction<'_, Postgres>,
+        id: &Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            SELECT c.id, c.jobsite_id, c.author_id, a.name AS "author_name?", c.body,
+                ARRAY(
+                    SELECT e.name
+                    FROM comment_mentions m
+                    JOIN employees e ON e.id = m.employee_id
+                    WHERE m.comment_id = c.id
+                    ORDER BY e.name
+                ) AS "mentions!",
+                c.posted_at, c.edited_at, c.deleted_at
+            FROM comments c
+            LEFT JOIN employees a ON a.id = c.author_id
+            WHERE c.id = $1
+            "#,
+            id
+        )
+        .fetch_optional(&mut **transaction)
+        .await
+    }
+
+    /// A jobsite's thread, oldest first, without deleted comments
+    pub async fn get_list_by_jobsite(
+        transaction: &mut Transaction<'_, Postgres>,
+        jobsite_id: &Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            SELECT c.id, c.jobsite_id, c.author_id, a.name AS "author_name?", c.body,
+                ARRAY(
+                    SELECT e.name
+                    FROM comment_mentions m
+                    JOIN employees e ON e.id = m.employee_id
+                    WHERE m.comment_id = c.id
+                    ORDER BY e.name
+                ) AS "mentions!",
+                c.posted_at, c.edited_at, c.deleted_at
+            FROM comments c
+            LEFT JOIN employees a ON a.id = c.author_id
+            WHERE c.jobsite_id = $1 AND c.deleted_at IS NULL
+            ORDER BY c.posted_at
+            "#,
+            jobsite_id
+        )
+        .fetch_all(&mut **transaction)
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::find_mentions;
+    use crate::projections::employee::Employee;
+
+    fn employee(name: &str) -> Employee {
+        Employee {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            trade: None,
+            terminated_on: None,
+        }
+    }
+
+    #[test]
+    fn mentions_are_whole_names_after_an_at() {
+        let sam = employee("Sam");
+        let sam_lee = employee("Sam Lee");
+        let maria = employee("Maria Ortiz");
+        let employees = vec![sam.clone(), sam_lee.clone(), maria.clone()];
+
+        assert_eq!(
+            find_mentions(
+                "@sam lee can you check the pour? cc @Maria Ortiz",
+                &employees
+            ),
+            vec![sam_lee.id, maria.id]
+        );
+        assert_eq!(
+            find_mentions("@Sam, then @Sam again", &employees),
+            vec![sam.id]
+        );
+        // Part of a longer word, or no @ at all
+        assert!(find_mentions("@Samantha and Sam Lee", &employees).is_empty());
+        assert!(find_mentions("mail sam@example.com", &employees).is_empty());
+    }
+}
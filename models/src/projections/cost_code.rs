@@ -0,0 +1,367 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "connect")]
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[cfg(feature = "connect")]
+use crate::events::cost_code::{CostCodeAdded, CostCodeRetired};
+
+/// A cost code on a jobsite, tasks are cost codes with a parent
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CostCode {
+    pub id: Uuid,
+    pub jobsite_id: Uuid,
+    pub parent_id: Option<Uuid>,
+    pub code: String,
+    pub name: String,
+    pub added_at: DateTime<Utc>,
+    pub retired_at: Option<DateTime<Utc>>,
+}
+
+impl CostCode {
+    pub fn is_retired(&self) -> bool {
+        self.retired_at.is_some()
+    }
+
+    /// The ids of a cost code and every task beneath it, however deep
+    pub fn subtree(codes: &[Self], root_id: Uuid) -> Vec<Uuid> {
+        let links = codes
+            .iter()
+            .map(|code| (code.id, code.parent_id))
+            .collect::<Vec<_>>();
+
+        subtree(&links, root_id)
+    }
+}
+
+/// Walk `(id, parent_id)` links down from `root_id`, the root comes first
+fn subtree(links: &[(Uuid, Option<Uuid>)], root_id: Uuid) -> Vec<Uuid> {
+    let mut ids = vec![root_id];
+    let mut next = 0;
+
+    while let Some(&parent_id) = ids.get(next) {
+        let children = links
+            .iter()
+            .filter(|(id, parent)| *parent == Some(parent_id) && !ids.contains(id))
+            .map(|(id, _)| *id)
+            .collect::<Vec<_>>();
+        ids.extend(children);
+        next += 1;
+    }
+
+    ids
+}
+
+/// Hours logged against a cost code, both directly and rolled up from every
+/// task beneath it
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CostCodeHours {
+    pub id: Uuid,
+    pub parent_id: Option<Uuid>,
+    pub code: String,
+    pub name: String,
+    pub retired_at: Option<DateTime<Utc>>,
+    pub minutes: i64,
+    pub total_minutes: i64,
+}
+
+impl CostCodeHours {
+    /// Order codes so each is followed by its tasks, paired with how deep in
+    /// the hierarchy it sits
+    pub fn tree(codes: Vec<Self>) -> Vec<(usize, Self)> {
+        fn visit(
+            parent_id: Option<Uuid>,
+            depth: usize,
+            codes: &[CostCodeHours],
+            ordered: &mut Vec<(usize, CostCodeHours)>,
+        ) {
+            for code in codes.iter().filter(|code| code.parent_id == parent_id) {
+                ordered.push((depth, code.clone()));
+                visit(Some(code.id), depth + 1, codes, ordered);
+            }
+        }
+
+        let mut ordered = Vec::with_capacity(codes.len());
+        visit(None, 0, &codes, &mut ordered);
+        ordered
+    }
+
+    /// Set each code's total to its own minutes plus those of every task
+    /// beneath it
+    pub fn roll_up(mut codes: Vec<Self>) -> Vec<Self> {
+        let links = codes
+            .iter()
+            .map(|code| (code.id, code.parent_id))
+            .collect::<Vec<_>>();
+        let minutes = codes
+            .iter()
+            .map(|code| (code.id, code.minutes))
+            .collect::<HashMap<_, _>>();
+
+        for code in codes.iter_mut() {
+            code.total_minutes = subtree(&links, code.id)
+                .iter()
+                .filter_map(|id| minutes.get(id))
+                .sum();
+        }
+
+        codes
+    }
+}
+
+#[cfg(feature = "connect")]
+impl CostCode {
+    pub async fn add(
+        transaction: &mut Transaction<'_, Postgres>,
+        added_event: &CostCodeAdded,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            INSERT INTO cost_codes (id, jobsite_id, parent_id, code, name, added_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING *;
+            "#,
+            added_event.id,
+            added_event.jobsite_id,
+            added_event.parent_id,
+            added_event.code,
+            added_event.name,
+            added_event.added_at
+        )
+        .fetch_one(&mut **transaction)
+        .await
+    }
+
+    /// Retire a cost code and every task beneath it
+    pub async fn retire(
+        transaction: &mut Transaction<'_, Postgres>,
+        retired_event: &CostCodeRetired,
+    ) -> Result<Self, sqlx::Error> {
+        let cost_code = Self::get_by_id(transaction, &retired_event.id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)?;
+        let codes = sqlx::query_as!(
+            Self,
+            r#"
+            SELECT id, jobsite_id, parent_id, code, name, added_at, retired_at
+            FROM cost_codes
+            WHERE jobsite_id = $1
+            "#,
+            cost_code.jobsite_id
+        )
+        .fetch_all(&mut **transaction)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            UPDATE cost_codes
+            SET retired_at = $2
+            WHERE id = ANY($1) AND retired_at IS NULL
+            "#,
+            &Self::subtree(&codes, cost_code.id),
+            retired_event.retired_at
+        )
+        .execute(&mut **transaction)
+        .await?;
+
+        Self::get_by_id(transaction, &retired_event.id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)
+    }
+
+    pub async fn get_by_id(
+        transaction: &mut Transaction<'_, Postgres>,
+        id: &Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            SELECT id, jobsite_id, parent_id, code, name, added_at, retired_at
+            FROM cost_codes
+            WHERE id = $1
+            "#,
+            id
+        )
+        .fetch_optional(&mut **transaction)
+        .await
+    }
+
+    pub async fn get_by_code(
+        transaction: &mut Transaction<'_, Postgres>,
+        jobsite_id: &Uuid,
+        code: &str,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            SELECT id, jobsite_id, parent_id, code, name, added_at, retired_at
+            FROM cost_codes
+            WHERE jobsite_id = $1 AND code = $2
+            "#,
+            jobsite_id,
+            code
+        )
+        .fetch_optional(&mut **transaction)
+        .await
+    }
+
+    /// A jobsite's cost codes that still accept time
+    pub async fn get_active_by_jobsite(
+        transaction: &mut Transaction<'_, Postgres>,
+        jobsite_id: &Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            SELECT id, jobsite_id, parent_id, code, name, added_at, retired_at
+            FROM cost_codes
+            WHERE jobsite_id = $1 AND retired_at IS NULL
+            ORDER BY code
+            "#,
+            jobsite_id
+        )
+        .fetch_all(&mut **transaction)
+        .await
+    }
+
+    /// Every cost code on a jobsite with the hours logged against it
+    pub async fn get_hours_by_jobsite(
+        transaction: &mut Transaction<'_, Postgres>,
+        jobsite_id: &Uuid,
+    ) -> Result<Vec<CostCodeHours>, sqlx::Error> {
+        sqlx::query_as!(
+            CostCodeHours,
+            r#"
+            WITH code_minutes AS (
+                SELECT s.cost_code_id, SUM(h.minutes_worked) AS minutes
+                FROM employee_hours h
+                JOIN shifts s ON s.id = h.shift_id
+                WHERE h.jobsite_id = $1 AND s.cost_code_id IS NOT NULL
+                GROUP BY s.cost_code_id
+            )
+            SELECT c.id, c.parent_id, c.code, c.name, c.retired_at,
+                COALESCE(m.minutes, 0)::BIGINT AS "minutes!",
+                0::BIGINT AS "total_minutes!"
+            FROM cost_codes c
+            LEFT JOIN code_minutes m ON m.cost_code_id = c.id
+            WHERE c.jobsite_id = $1
+            ORDER BY c.code
+            "#,
+            jobsite_id
+        )
+        .fetch_all(&mut **transaction)
+        .await
+        .map(CostCodeHours::roll_up)
+    }
+
+    /// Hours logged on a jobsite without a cost code
+    pub async fn get_uncoded_minutes(
+        transaction: &mut Transaction<'_, Postgres>,
+        jobsite_id: &Uuid,
+    ) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"
+            SELECT COALESCE(SUM(h.minutes_worked), 0)::BIGINT AS "minutes!"
+            FROM employee_hours h
+            JOIN shifts s ON s.id = h.shift_id
+            WHERE h.jobsite_id = $1 AND s.cost_code_id IS NULL
+            "#,
+            jobsite_id
+        )
+        .fetch_one(&mut **transaction)
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    use super::{CostCode, CostCodeHours};
+
+    fn code(parent_id: Option<Uuid>, code: &str) -> CostCode {
+        CostCode {
+            id: Uuid::new_v4(),
+            jobsite_id: Uuid::nil(),
+            parent_id,
+            code: code.to_string(),
+            name: code.to_string(),
+            added_at: Utc::now(),
+            retired_at: None,
+        }
+    }
+
+    fn hours(code: &CostCode, minutes: i64) -> CostCodeHours {
+        CostCodeHours {
+            id: code.id,
+            parent_id: code.parent_id,
+            code: code.code.clone(),
+            name: code.name.clone(),
+            retired_at: None,
+            minutes,
+            total_minutes: 0,
+        }
+    }
+
+    #[test]
+    fn subtree_reaches_every_task_beneath_a_code() {
+        let concrete = code(None, "03");
+        let forming = code(Some(concrete.id), "03-10");
+        let footings = code(Some(forming.id), "03-10-01");
+        let pouring = code(Some(concrete.id), "03-30");
+        let framing = code(None, "06");
+        let codes = vec![
+            concrete.clone(),
+            forming.clone(),
+            footings.clone(),
+            pouring.clone(),
+            framing.clone(),
+        ];
+
+        let mut subtree = CostCode::subtree(&codes, concrete.id);
+        assert_eq!(subtree.remove(0), concrete.id);
+        subtree.sort();
+        let mut expected = vec![forming.id, footings.id, pouring.id];
+        expected.sort();
+        assert_eq!(subtree, expected);
+
+        assert_eq!(
+            CostCode::subtree(&codes, forming.id),
+            vec![forming.id, footings.id]
+        );
+        assert_eq!(CostCode::subtree(&codes, framing.id), vec![framing.id]);
+    }
+
+    #[test]
+    fn rolls_hours_up_to_every_ancestor() {
+        let concrete = code(None, "03");
+        let forming = code(Some(concrete.id), "03-10");
+        let footings = code(Some(forming.id), "03-10-01");
+        let framing = code(None, "06");
+
+        let totals = CostCodeHours::roll_up(vec![
+            hours(&concrete, 30),
+            hours(&forming, 60),
+            hours(&footings, 120),
+            hours(&framing, 45),
+        ])
+        .into_iter()
+        .map(|code| (code.code, code.minutes, code.total_minutes))
+        .collect::<Vec<_>>();
+
+        assert_eq!(
+            totals,
+            vec![
+                ("03".to_string(), 30, 210),
+                ("03-10".to_string(), 60, 180),
+                ("03-10-01".to_string(), 120, 120),
+                ("06".to_string(), 45, 45),
+            ]
+        );
+    }
+}
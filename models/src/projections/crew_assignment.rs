@@ -0,0 +1,326 @@
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "connect")]
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[cfg(feature = "connect")]
+use crate::events::crew_assignment::{CrewMemberAssigned, CrewMemberUnassigned};
+use crate::projections::{
+    employee::Employee,
+    leave::{Leave, LeaveType},
+};
+
+/// An employee scheduled onto a jobsite for a range of days
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CrewAssignment {
+    pub id: Uuid,
+    pub employee_id: Uuid,
+    pub employee_name: String,
+    pub jobsite_id: Uuid,
+    pub jobsite_name: String,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub assigned_at: DateTime<Utc>,
+    pub unassigned_at: Option<DateTime<Utc>>,
+}
+
+impl CrewAssignment {
+    pub fn stream_name(employee_id: &Uuid) -> String {
+        format!("crew_schedule-{}", employee_id)
+    }
+
+    pub fn covers(&self, date: NaiveDate) -> bool {
+        self.start_date <= date && date <= self.end_date
+    }
+
+    pub fn overlaps(&self, start_date: NaiveDate, end_date: NaiveDate) -> bool {
+        self.start_date <= end_date && start_date <= self.end_date
+    }
+}
+
+/// Why a scheduled day can't be worked as planned
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum CrewConflict {
+    /// The employee is assigned to more than one jobsite that day
+    DoubleBooked,
+    /// The employee has left the company by that day
+    Terminated,
+    /// The employee is on approved leave that day
+    OnLeave,
+}
+
+impl CrewConflict {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CrewConflict::DoubleBooked => "Double booked",
+            CrewConflict::Terminated => "Terminated",
+            CrewConflict::OnLeave => "On leave",
+        }
+    }
+}
+
+/// One employee's day in the weekly schedule
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CrewDay {
+    pub date: NaiveDate,
+    pub assignments: Vec<CrewAssignment>,
+    /// Approved leave the employee is away on
+    pub leave: Option<LeaveType>,
+    pub conflict: Option<CrewConflict>,
+}
+
+/// An employee's row in the weekly schedule, one day per column
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CrewWeekRow {
+    pub employee: Employee,
+    pub days: Vec<CrewDay>,
+}
+
+/// Who is scheduled where for each day of a week, weeks start on Monday
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CrewWeek {
+    pub week_start: NaiveDate,
+    pub rows: Vec<CrewWeekRow>,
+}
+
+impl CrewWeek {
+    /// Lay `assignments` and approved `leave` out by employee and day,
+    /// employees terminated before the week starts are left out unless
+    /// they're still scheduled
+    pub fn new(
+        week_start: NaiveDate,
+        employees: Vec<Employee>,
+        assignments: Vec<CrewAssignment>,
+        leave: Vec<Leave>,
+    ) -> Self {
+        let dates = (0..7)
+            .map(|offset| week_start + Duration::days(offset))
+            .collect::<Vec<_>>();
+
+        let rows = employees
+            .into_iter()
+            .filter_map(|employee| {
+                let scheduled = assignments
+                    .iter()
+                    .filter(|assignment| assignment.employee_id == employee.id)
+                    .collect::<Vec<_>>();
+
+                if scheduled.is_empty() && employee.is_terminated_on(week_start) {
+                    return None;
+                }
+
+                let days = dates
+                    .iter()
+                    .map(|date| {
+                        let assignments = scheduled
+                            .iter()
+                            .filter(|assignment| assignment.covers(*date))
+                            .map(|assignment| (*assignment).clone())
+                            .collect::<Vec<_>>();
+
+                        let leave = leave
+                            .iter()
+                            .find(|leave| leave.employee_id == employee.id && leave.covers(*date))
+                            .map(|leave| leave.leave_type);
+
+                        let conflict = if assignments.is_empty() {
+                            None
+                        } else if employee.is_terminated_on(*date) {
+                            Some(CrewConflict::Terminated)
+                        } else if leave.is_some() {
+                            Some(CrewConflict::OnLeave)
+                        } else if assignments.len() > 1 {
+                            Some(CrewConflict::DoubleBooked)
+                        } else {
+                            None
+                        };
+
+                        CrewDay {
+                            date: *date,
+                            assignments,
+                            leave,
+                            conflict,
+                        }
+                    })
+                    .collect();
+
+                Some(CrewWeekRow { employee, days })
+            })
+            .collect();
+
+        Self { week_start, rows }
+    }
+}
+
+#[cfg(feature = "connect")]
+impl CrewAssignment {
+    pub async fn assign(
+        transaction: &mut Transaction<'_, Postgres>,
+        assigned_event: &CrewMemberAssigned,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query!(
+            r#"
+            INSERT INTO crew_assignments (id, employee_id, jobsite_id, start_date, end_date, assigned_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            "#,
+            assigned_event.id,
+            assigned_event.employee_id,
+            assigned_event.jobsite_id,
+            assigned_event.start_date,
+            assigned_event.end_date,
+            assigned_event.assigned_at
+        )
+        .execute(&mut **transaction)
+        .await?;
+
+        Self::get_by_id(transaction, &assigned_event.id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)
+    }
+
+    pub async fn unassign(
+        transaction: &mut Transaction<'_, Postgres>,
+        unassigned_event: &CrewMemberUnassigned,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query!(
+            r#"
+            UPDATE crew_assignments
+            SET unassigned_at = $2
+            WHERE id = $1
+            "#,
+            unassigned_event.id,
+            unassigned_event.unassigned_at
+        )
+        .execute(&mut **transaction)
+        .await?;
+
+        Self::get_by_id(transaction, &unassigned_event.id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)
+    }
+
+    pub async fn get_by_id(
+        transaction: &mut Transaction<'_, Postgres>,
+        id: &Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            SELECT a.id, a.employee_id, e.name AS employee_name, a.jobsite_id,
+                j.name AS jobsite_name, a.start_date, a.end_date, a.assigned_at, a.unassigned_at
+            FROM crew_assignments a
+            JOIN employees e ON e.id = a.employee_id
+            JOIN jobsites j ON j.id = a.jobsite_id
+            WHERE a.id = $1
+            "#,
+            id
+        )
+        .fetch_optional(&mut **transaction)
+        .await
+    }
+
+    /// An employee's active assignments that share at least one day with the
+    /// given range
+    pub async fn get_overlapping(
+        transaction: &mut Transaction<'_, Postgres>,
+        employee_id: &Uuid,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            SELECT a.id, a.employee_id, e.name AS employee_name, a.jobsite_id,
+                j.name AS jobsite_name, a.start_date, a.end_date, a.assigned_at, a.unassigned_at
+            FROM crew_assignments a
+            JOIN employees e ON e.id = a.employee_id
+            JOIN jobsites j ON j.id = a.jobsite_id
+            WHERE a.employee_id = $1 AND a.unassigned_at IS NULL
+            AND a.start_date <= $3 AND a.end_date >= $2
+            ORDER BY a.start_date
+            "#,
+            employee_id,
+            start_date,
+            end_date
+        )
+        .fetch_all(&mut **transaction)
+        .await
+    }
+
+    /// Every active assignment that falls within the week starting on
+    /// `week_start`
+    pub async fn get_week(
+        transaction: &mut Transaction<'_, Postgres>,
+        week_start: NaiveDate,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        let week_end = week_start + Duration::days(6);
+
+        sqlx::query_as!(
+            Self,
+            r#"
+            SELECT a.id, a.employee_id, e.name AS employee_name, a.jobsite_id,
+                j.name AS jobsite_name, a.start_date, a.end_date, a.assigned_at, a.unassigned_at
+            FROM crew_assignments a
+            JOIN employees e ON e.id = a.employee_id
+            JOIN jobsites j ON j.id = a.jobsite_id
+            WHERE a.unassigned_at IS NULL AND a.start_date <= $2 AND a.end_date >= $1
+            ORDER BY j.name
+            "#,
+            week_start,
+            week_end
+        )
+        .fetch_all(&mut **transaction)
+        .await
+    }
+
+    /// An employee's active assignments ending on or after `since`, for their
+    /// calendar feed
+    pub async fn get_list_by_employee_since(
+        transaction: &mut Transaction<'_, Postgres>,
+        employee_id: &Uuid,
+        since: NaiveDate,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            SELECT a.id, a.employee_id, e.name AS employee_name, a.jobsite_id,
+                j.name AS jobsite_name, a.start_date, a.end_date, a.assigned_at, a.unassigned_at
+            FROM crew_assignments a
+            JOIN employees e ON e.id = a.employee_id
+            JOIN jobsites j ON j.id = a.jobsite_id
+            WHERE a.employee_id = $1 AND a.unassigned_at IS NULL AND a.end_date >= $2
+            ORDER BY a.start_date
+            "#,
+            employee_id,
+            since
+        )
+        .fetch_all(&mut **transaction)
+        .await
+    }
+
+    /// A jobsite's active assignments ending on or after `since`, for its
+    /// calendar feed
+    pub async fn get_list_by_jobsite_since(
+        transaction: &mut Transaction<'_, Postgres>,
+        jobsite_id: &Uuid,
+        since: NaiveDate,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            SELECT a.id, a.employee_id, e.name AS employee_name, a.jobsite_id,
+                j.name AS jobsite_name, a.start_date, a.end_date, a.assigned_at, a.unassigned_at
+            FROM crew_assignments a
+            JOIN employees e ON e.id = a.employee_id
+            JOIN jobsites j ON j.id = a.jobsite_id
+            WHERE a.jobsite_id = $1 AND a.unassigned_at IS NULL AND a.end_date >= $2
+            ORDER BY a.start_date, e.name
+            "#,
+            jobsite_id,
+            since
+        )
+        .fetch_all(&mut **transaction)
+        .await
+    }
+}
@@ -0,0 +1,333 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "connect")]
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[cfg(feature = "connect")]
+use crate::events::daily_log::{DailyLogDelayRecorded, DailyLogSaved, DailyLogSubmitted};
+
+/// The weather on site for the day, as the foreman saw it
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum WeatherCondition {
+    Clear,
+    Cloudy,
+    Rain,
+    Snow,
+    Wind,
+    Heat,
+    Cold,
+}
+
+impl WeatherCondition {
+    pub const ALL: [WeatherCondition; 7] = [
+        WeatherCondition::Clear,
+        WeatherCondition::Cloudy,
+        WeatherCondition::Rain,
+        WeatherCondition::Snow,
+        WeatherCondition::Wind,
+        WeatherCondition::Heat,
+        WeatherCondition::Cold,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WeatherCondition::Clear => "clear",
+            WeatherCondition::Cloudy => "cloudy",
+            WeatherCondition::Rain => "rain",
+            WeatherCondition::Snow => "snow",
+            WeatherCondition::Wind => "wind",
+            WeatherCondition::Heat => "heat",
+            WeatherCondition::Cold => "cold",
+        }
+    }
+}
+
+impl From<String> for WeatherCondition {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "clear" => WeatherCondition::Clear,
+            "cloudy" => WeatherCondition::Cloudy,
+            "rain" => WeatherCondition::Rain,
+            "snow" => WeatherCondition::Snow,
+            "wind" => WeatherCondition::Wind,
+            "heat" => WeatherCondition::Heat,
+            "cold" => WeatherCondition::Cold,
+            _ => panic!("Invalid WeatherCondition"),
+        }
+    }
+}
+
+/// What held up work on site
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum DelayCause {
+    Weather,
+    Materials,
+    Equipment,
+    Inspection,
+    Labor,
+    Other,
+}
+
+impl DelayCause {
+    pub const ALL: [DelayCause; 6] = [
+        DelayCause::Weather,
+        DelayCause::Materials,
+        DelayCause::Equipment,
+        DelayCause::Inspection,
+        DelayCause::Labor,
+        DelayCause::Other,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DelayCause::Weather => "weather",
+            DelayCause::Materials => "materials",
+            DelayCause::Equipment => "equipment",
+            DelayCause::Inspection => "inspection",
+            DelayCause::Labor => "labor",
+            DelayCause::Other => "other",
+        }
+    }
+}
+
+impl From<String> for DelayCause {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "weather" => DelayCause::Weather,
+            "materials" => DelayCause::Materials,
+            "equipment" => DelayCause::Equipment,
+            "inspection" => DelayCause::Inspection,
+            "labor" => DelayCause::Labor,
+            "other" => DelayCause::Other,
+            _ => panic!("Invalid DelayCause"),
+        }
+    }
+}
+
+/// A foreman's log of one day on a jobsite
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DailyLog {
+    pub jobsite_id: Uuid,
+    pub log_date: NaiveDate,
+    pub crew_count: i32,
+    pub weather: WeatherCondition,
+    pub notes: Option<String>,
+    pub saved_at: DateTime<Utc>,
+    pub submitted_at: Option<DateTime<Utc>>,
+}
+
+/// Time lost on a logged day
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DailyLogDelay {
+    pub id: Uuid,
+    pub jobsite_id: Uuid,
+    pub log_date: NaiveDate,
+    pub cause: DelayCause,
+    pub minutes_lost: i64,
+    pub reason: String,
+    pub recorded_at: DateTime<Utc>,
+}
+
+impl DailyLog {
+    pub fn stream_name(jobsite_id: &Uuid, log_date: &NaiveDate) -> String {
+        format!("daily_log-{}_{}", jobsite_id, log_date)
+    }
+
+    /// Submitted logs can't be changed or have delays added
+    pub fn is_locked(&self) -> bool {
+        self.submitted_at.is_some()
+    }
+}
+
+#[cfg(feature = "connect")]
+impl DailyLog {
+    /// Write the day's log, a day that's been submitted is left as it was
+    pub async fn save(
+        transaction: &mut Transaction<'_, Postgres>,
+        saved_event: &DailyLogSaved,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            INSERT INTO daily_logs (jobsite_id, log_date, crew_count, weather, notes, saved_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (jobsite_id, log_date) DO UPDATE
+            SET crew_count = excluded.crew_count,
+                weather = excluded.weather,
+                notes = excluded.notes,
+                saved_at = excluded.saved_at
+            WHERE daily_logs.submitted_at IS NULL
+            RETURNING *;
+            "#,
+            saved_event.jobsite_id,
+            saved_event.log_date,
+            saved_event.crew_count,
+            saved_event.weather.as_str(),
+            saved_event.notes,
+            saved_event.saved_at
+        )
+        .fetch_optional(&mut **transaction)
+        .await
+    }
+
+    pub async fn record_delay(
+        transaction: &mut Transaction<'_, Postgres>,
+        recorded_event: &DailyLogDelayRecorded,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query!(
+            r#"
+            INSERT INTO daily_log_delays (id, jobsite_id, log_date, cause, minutes_lost, reason, recorded_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            "#,
+            recorded_event.id,
+            recorded_event.jobsite_id,
+            recorded_event.log_date,
+            recorded_event.cause.as_str(),
+            recorded_event.minutes_lost,
+            recorded_event.reason,
+            recorded_event.recorded_at
+        )
+        .execute(&mut **transaction)
+        .await?;
+
+        Self::get(
+            transaction,
+            &recorded_event.jobsite_id,
+            &recorded_event.log_date,
+        )
+        .await?
+        .ok_or(sqlx::Error::RowNotFound)
+    }
+
+    pub async fn submit(
+        transaction: &mut Transaction<'_, Postgres>,
+        submitted_event: &DailyLogSubmitted,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            UPDATE daily_logs
+            SET submitted_at = $3
+            WHERE jobsite_id = $1 AND log_date = $2
+            RETURNING *;
+            "#,
+            submitted_event.jobsite_id,
+            submitted_event.log_date,
+            submitted_event.submitted_at
+        )
+        .fetch_one(&mut **transaction)
+        .await
+    }
+
+    pub async fn get(
+        transaction: &mut Transaction<'_, Postgres>,
+        jobsite_id: &Uuid,
+        log_date: &NaiveDate,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            SELECT jobsite_id, log_date, crew_count, weather, notes, saved_at, submitted_at
+            FROM daily_logs
+            WHERE jobsite_id = $1 AND log_date = $2
+            "#,
+            jobsite_id,
+            log_date
+        )
+        .fetch_optional(&mut **transaction)
+        .await
+    }
+
+    /// A jobsite's logs, most recent day first
+    pub async fn get_list_by_jobsite(
+        transaction: &mut Transaction<'_, Postgres>,
+        jobsite_id: &Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            SELECT jobsite_id, log_date, crew_count, weather, notes, saved_at, submitted_at
+            FROM daily_logs
+            WHERE jobsite_id = $1
+            ORDER BY log_date DESC
+            "#,
+            jobsite_id
+        )
+        .fetch_all(&mut **transaction)
+        .await
+    }
+}
+
+#[cfg(feature = "connect")]
+impl DailyLogDelay {
+    /// Every delay logged on a jobsite, in the order they were recorded
+    pub async fn get_list_by_jobsite(
+        transaction: &mut Transaction<'_, Postgres>,
+        jobsite_id: &Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            SELECT id, jobsite_id, log_date, cause, minutes_lost, reason, recorded_at
+            FROM daily_log_delays
+            WHERE jobsite_id = $1
+            ORDER BY recorded_at
+            "#,
+            jobsite_id
+        )
+        .fetch_all(&mut **transaction)
+        .await
+    }
+}
+
+#[cfg(all(test, feature = "connect"))]
+mod tests {
+    use chrono::{Duration, NaiveDate, Utc};
+    use sqlx::PgPool;
+    use uuid::Uuid;
+
+    use super::{DailyLog, WeatherCondition};
+    use crate::events::daily_log::{DailyLogSaved, DailyLogSubmitted};
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn submitted_days_are_not_rewritten(db_pool: PgPool) -> sqlx::Result<()> {
+        let mut transaction = db_pool.begin().await?;
+
+        let (jobsite_id, log_date) = (Uuid::new_v4(), NaiveDate::from_ymd_opt(2025, 3, 4).unwrap());
+        let saved_at = Utc::now();
+        let saved = |crew_count, saved_at| DailyLogSaved {
+            jobsite_id,
+            log_date,
+            crew_count,
+            weather: WeatherCondition::Clear,
+            notes: None,
+            saved_at,
+        };
+
+        assert!(DailyLog::save(&mut transaction, &saved(6, saved_at))
+            .await?
+            .is_some());
+        DailyLog::submit(
+            &mut transaction,
+            &DailyLogSubmitted {
+                jobsite_id,
+                log_date,
+                submitted_at: saved_at + Duration::minutes(5),
+            },
+        )
+        .await?;
+
+        // A save recorded after the submit, before the stream was checked
+        let late = saved(9, saved_at + Duration::minutes(10));
+        assert!(DailyLog::save(&mut transaction, &late).await?.is_none());
+
+        let daily_log = DailyLog::get(&mut transaction, &jobsite_id, &log_date)
+            .await?
+            .unwrap();
+        assert_eq!(daily_log.crew_count, 6);
+        assert!(daily_log.is_locked());
+
+        Ok(())
+    }
+}
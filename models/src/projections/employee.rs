@@ -0,0 +1,210 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "connect")]
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[cfg(feature = "connect")]
+use crate::events::employee::{EmployeeCreated, EmployeePinSet, EmployeeTerminated};
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Employee {
+    pub id: Uuid,
+    pub name: String,
+    pub trade: Option<String>,
+    pub terminated_on: Option<NaiveDate>,
+}
+
+impl Employee {
+    /// Whether the employee is no longer with the company on `date`
+    pub fn is_terminated_on(&self, date: NaiveDate) -> bool {
+        self.terminated_on.is_some_and(|terminated_on| terminated_on <= date)
+    }
+}
+
+#[cfg(feature = "connect")]
+impl Employee {
+    pub async fn create(
+        transaction: &mut Transaction<'_, Postgres>,
+        created_event: &EmployeeCreated,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            INSERT INTO employees (id, name, trade)
+            VALUES ($1, $2, $3)
+            RETURNING id, name, trade, terminated_on;
+            "#,
+            created_event.id,
+            created_event.name,
+            created_event.trade
+        )
+        .fetch_one(&mut **transaction)
+        .await
+    }
+
+    pub async fn terminate(
+        transaction: &mut Transaction<'_, Postgres>,
+        terminated_event: &EmployeeTerminated,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            UPDATE employees
+            SET terminated_on = $2
+            WHERE id = $1
+            RETURNING id, name, trade, terminated_on;
+            "#,
+            terminated_event.id,
+            terminated_event.terminated_on
+        )
+        .fetch_one(&mut **transaction)
+        .await
+    }
+
+    pub async fn set_pin(
+        transaction: &mut Transaction<'_, Postgres>,
+        pin_event: &EmployeePinSet,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+            INSERT INTO employee_pins (employee_id, pin_hash, set_at)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (employee_id) DO UPDATE
+            SET pin_hash = EXCLUDED.pin_hash, set_at = EXCLUDED.set_at
+            "#,
+            pin_event.id,
+            pin_event.pin_hash,
+            pin_event.set_at
+        )
+        .execute(&mut **transaction)
+        .await?;
+
+        Ok(())
+    }
+
+    /// The signature of the employee's kiosk PIN, if they have one
+    pub async fn get_pin_hash(
+        transaction: &mut Transaction<'_, Postgres>,
+        id: &Uuid,
+    ) -> Result<Option<String>, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"
+            SELECT pin_hash
+            FROM employee_pins
+            WHERE employee_id = $1
+            "#,
+            id
+        )
+        .fetch_optional(&mut **transaction)
+        .await
+    }
+
+    /// Employees who can clock in at a kiosk on `date`, i.e. those with a PIN
+    pub async fn get_list_with_pin_active_on(
+        transaction: &mut Transaction<'_, Postgres>,
+        date: NaiveDate,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            SELECT e.id, e.name, e.trade, e.terminated_on
+            FROM employees e
+            JOIN employee_pins p ON p.employee_id = e.id
+            WHERE e.terminated_on IS NULL OR e.terminated_on > $1
+            ORDER BY e.name
+            "#,
+            date
+        )
+        .fetch_all(&mut **transaction)
+        .await
+    }
+
+    pub async fn get_by_id(
+        transaction: &mut Transaction<'_, Postgres>,
+        id: &Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            SELECT id, name, trade, terminated_on
+            FROM employees
+            WHERE id = $1
+            "#,
+            id
+        )
+        .fetch_optional(&mut **transaction)
+        .await
+    }
+
+    pub async fn get_list(
+        transaction: &mut Transaction<'_, Postgres>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            SELECT id, name, trade, terminated_on
+            FROM employees
+            ORDER BY name
+            "#,
+        )
+        .fetch_all(&mut **transaction)
+        .await
+    }
+
+    /// Employees that can still be scheduled or clock in on `date`
+    pub async fn get_list_active_on(
+        transaction: &mut Transaction<'_, Postgres>,
+        date: NaiveDate,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            SELECT id, name, trade, terminated_on
+            FROM employees
+            WHERE terminated_on IS NULL OR terminated_on > $1
+            ORDER BY name
+            "#,
+            date
+        )
+        .fetch_all(&mut **transaction)
+        .await
+    }
+
+    /// The distinct trades employees have been given
+    pub async fn get_trades(
+        transaction: &mut Transaction<'_, Postgres>,
+    ) -> Result<Vec<String>, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"
+            SELECT DISTINCT trade AS "trade!"
+            FROM employees
+            WHERE trade IS NOT NULL
+            ORDER BY 1
+            "#,
+        )
+        .fetch_all(&mut **transaction)
+        .await
+    }
+
+    /// Employees that are free to clock in, i.e. without an open shift anywhere
+    pub async fn get_list_without_open_shift(
+        transaction: &mut Transaction<'_, Postgres>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            SELECT e.id, e.name, e.trade, e.terminated_on
+            FROM employees e
+            WHERE (e.terminated_on IS NULL OR e.terminated_on > CURRENT_DATE)
+            AND NOT EXISTS (
+                SELECT 1 FROM shifts s
+                WHERE s.employee_id = e.id AND s.clocked_out_at IS NULL
+            )
+            ORDER BY e.name
+            "#,
+        )
+        .fetch_all(&mut **transaction)
+        .await
+    }
+}
@@ -0,0 +1,107 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "connect")]
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[cfg(feature = "connect")]
+use crate::projections::shift::Shift;
+
+/// Hours worked, derived from a closed shift
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct EmployeeHours {
+    pub shift_id: Uuid,
+    pub employee_id: Uuid,
+    pub jobsite_id: Uuid,
+    pub work_date: NaiveDate,
+    pub minutes_worked: i64,
+}
+
+#[cfg(feature = "connect")]
+impl EmployeeHours {
+    /// Record the worked hours of a closed shift, the shift is attributed to
+    /// the local day it was started on. Hours of a shift moved by a jobsite
+    /// merge remember where they came from so an unmerge can move them back.
+    pub async fn record(
+        transaction: &mut Transaction<'_, Postgres>,
+        shift: &Shift,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        let Some(clocked_out_at) = shift.clocked_out_at else {
+            return Ok(None);
+        };
+
+        let minutes_worked = (clocked_out_at - shift.clocked_in_at).num_minutes().max(0);
+
+        sqlx::query_as!(
+            Self,
+            r#"
+            INSERT INTO employee_hours (shift_id, employee_id, jobsite_id, work_date, minutes_worked,
+                merged_from_id)
+            SELECT $1, $2, $3, $4, $5, s.merged_from_id
+            FROM shifts s
+            WHERE s.id = $1
+            ON CONFLICT (shift_id) DO UPDATE
+            SET work_date = excluded.work_date, minutes_worked = excluded.minutes_worked
+            RETURNING shift_id, employee_id, jobsite_id, work_date, minutes_worked;
+            "#,
+            shift.id,
+            shift.employee_id,
+            shift.jobsite_id,
+            shift.work_date(),
+            minutes_worked
+        )
+        .fetch_one(&mut **transaction)
+        .await
+        .map(Some)
+    }
+
+    pub async fn get_list_by_employee(
+        transaction: &mut Transaction<'_, Postgres>,
+        employee_id: &Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            SELECT shift_id, employee_id, jobsite_id, work_date, minutes_worked
+            FROM employee_hours
+            WHERE employee_id = $1 AND work_date BETWEEN $2 AND $3
+            ORDER BY work_date
+            "#,
+            employee_id,
+            from,
+            to
+        )
+        .fetch_all(&mut **transaction)
+        .await
+    }
+
+    /// The jobsites hours were worked at from a date on, by an employee or
+    /// anyone in a trade when given, and through a date when given
+    pub async fn get_jobsite_ids(
+        transaction: &mut Transaction<'_, Postgres>,
+        employee_id: Option<&Uuid>,
+        trade: Option<&str>,
+        from: NaiveDate,
+        to: Option<NaiveDate>,
+    ) -> Result<Vec<Uuid>, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"
+            SELECT DISTINCT h.jobsite_id
+            FROM employee_hours h
+            JOIN employees e ON e.id = h.employee_id
+            WHERE h.work_date >= $1
+                AND ($2::DATE IS NULL OR h.work_date <= $2)
+                AND ($3::UUID IS NULL OR h.employee_id = $3)
+                AND ($4::VARCHAR IS NULL OR e.trade = $4)
+            "#,
+            from,
+            to,
+            employee_id,
+            trade
+        )
+        .fetch_all(&mut **transaction)
+        .await
+    }
+}
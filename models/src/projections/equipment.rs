@@ -0,0 +1,173 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "connect")]
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[cfg(feature = "connect")]
+use crate::events::equipment::{EquipmentAdded, EquipmentCheckedOut, EquipmentReturned};
+
+/// A piece of equipment in the catalog and where it currently is, equipment
+/// that isn't checked out to a jobsite is in the yard
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Equipment {
+    pub id: Uuid,
+    pub name: String,
+    pub serial_number: Option<String>,
+    pub added_at: DateTime<Utc>,
+    pub checkout_id: Option<Uuid>,
+    pub jobsite_id: Option<Uuid>,
+    pub jobsite_name: Option<String>,
+    pub employee_id: Option<Uuid>,
+    pub employee_name: Option<String>,
+    pub checked_out_at: Option<DateTime<Utc>>,
+}
+
+impl Equipment {
+    pub fn stream_name(equipment_id: &Uuid) -> String {
+        format!("equipment-{}", equipment_id)
+    }
+
+    pub fn is_checked_out(&self) -> bool {
+        self.checkout_id.is_some()
+    }
+}
+
+#[cfg(feature = "connect")]
+impl Equipment {
+    pub async fn add(
+        transaction: &mut Transaction<'_, Postgres>,
+        added_event: &EquipmentAdded,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query!(
+            r#"
+            INSERT INTO equipment (id, name, serial_number, added_at)
+            VALUES ($1, $2, $3, $4)
+            "#,
+            added_event.id,
+            added_event.name,
+            added_event.serial_number,
+            added_event.added_at
+        )
+        .execute(&mut **transaction)
+        .await?;
+
+        Self::get_by_id(transaction, &added_event.id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)
+    }
+
+    pub async fn check_out(
+        transaction: &mut Transaction<'_, Postgres>,
+        checked_out_event: &EquipmentCheckedOut,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query!(
+            r#"
+            INSERT INTO equipment_checkouts (id, equipment_id, jobsite_id, employee_id, checked_out_at)
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+            checked_out_event.checkout_id,
+            checked_out_event.id,
+            checked_out_event.jobsite_id,
+            checked_out_event.employee_id,
+            checked_out_event.checked_out_at
+        )
+        .execute(&mut **transaction)
+        .await?;
+
+        Self::get_by_id(transaction, &checked_out_event.id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)
+    }
+
+    pub async fn check_in(
+        transaction: &mut Transaction<'_, Postgres>,
+        returned_event: &EquipmentReturned,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query!(
+            r#"
+            UPDATE equipment_checkouts
+            SET returned_at = $2
+            WHERE id = $1
+            "#,
+            returned_event.checkout_id,
+            returned_event.returned_at
+        )
+        .execute(&mut **transaction)
+        .await?;
+
+        Self::get_by_id(transaction, &returned_event.id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)
+    }
+
+    pub async fn get_by_id(
+        transaction: &mut Transaction<'_, Postgres>,
+        id: &Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            SELECT q.id, q.name, q.serial_number, q.added_at,
+                c.id AS "checkout_id?", c.jobsite_id AS "jobsite_id?",
+                j.name AS "jobsite_name?", c.employee_id, e.name AS "employee_name?",
+                c.checked_out_at AS "checked_out_at?"
+            FROM equipment q
+            LEFT JOIN equipment_checkouts c ON c.equipment_id = q.id AND c.returned_at IS NULL
+            LEFT JOIN jobsites j ON j.id = c.jobsite_id
+            LEFT JOIN employees e ON e.id = c.employee_id
+            WHERE q.id = $1
+            "#,
+            id
+        )
+        .fetch_optional(&mut **transaction)
+        .await
+    }
+
+    /// The whole catalog by name, with where each piece currently is
+    pub async fn get_list(
+        transaction: &mut Transaction<'_, Postgres>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            SELECT q.id, q.name, q.serial_number, q.added_at,
+                c.id AS "checkout_id?", c.jobsite_id AS "jobsite_id?",
+                j.name AS "jobsite_name?", c.employee_id, e.name AS "employee_name?",
+                c.checked_out_at AS "checked_out_at?"
+            FROM equipment q
+            LEFT JOIN equipment_checkouts c ON c.equipment_id = q.id AND c.returned_at IS NULL
+            LEFT JOIN jobsites j ON j.id = c.jobsite_id
+            LEFT JOIN employees e ON e.id = c.employee_id
+            ORDER BY q.name
+            "#
+        )
+        .fetch_all(&mut **transaction)
+        .await
+    }
+
+    /// Equipment currently checked out to a jobsite
+    pub async fn get_by_jobsite(
+        transaction: &mut Transaction<'_, Postgres>,
+        jobsite_id: &Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            SELECT q.id, q.name, q.serial_number, q.added_at,
+                c.id AS "checkout_id?", c.jobsite_id AS "jobsite_id?",
+                j.name AS "jobsite_name?", c.employee_id, e.name AS "employee_name?",
+                c.checked_out_at AS "checked_out_at?"
+            FROM equipment q
+            JOIN equipment_checkouts c ON c.equipment_id = q.id AND c.returned_at IS NULL
+            JOIN jobsites j ON j.id = c.jobsite_id
+            LEFT JOIN employees e ON e.id = c.employee_id
+            WHERE c.jobsite_id = $1
+            ORDER BY q.name
+            "#,
+            jobsite_id
+        )
+        .fetch_all(&mut **transaction)
+        .await
+    }
+}
@@ -0,0 +1,372 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "connect")]
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[cfg(feature = "connect")]
+use crate::events::incident::{
+    IncidentClosed, IncidentCorrectiveActionAssigned, IncidentInvestigated, IncidentReported,
+};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum IncidentSeverity {
+    /// Nobody was hurt, but could have been
+    NearMiss,
+    /// Treated on site
+    FirstAid,
+    /// Needed treatment beyond first aid
+    Recordable,
+    /// Kept someone off work
+    LostTime,
+}
+
+impl IncidentSeverity {
+    pub const ALL: [IncidentSeverity; 4] = [
+        IncidentSeverity::NearMiss,
+        IncidentSeverity::FirstAid,
+        IncidentSeverity::Recordable,
+        IncidentSeverity::LostTime,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IncidentSeverity::NearMiss => "near_miss",
+            IncidentSeverity::FirstAid => "first_aid",
+            IncidentSeverity::Recordable => "recordable",
+            IncidentSeverity::LostTime => "lost_time",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            IncidentSeverity::NearMiss => "Near miss",
+            IncidentSeverity::FirstAid => "First aid",
+            IncidentSeverity::Recordable => "Recordable",
+            IncidentSeverity::LostTime => "Lost time",
+        }
+    }
+}
+
+impl From<String> for IncidentSeverity {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "near_miss" => IncidentSeverity::NearMiss,
+            "first_aid" => IncidentSeverity::FirstAid,
+            "recordable" => IncidentSeverity::Recordable,
+            "lost_time" => IncidentSeverity::LostTime,
+            _ => panic!("Invalid IncidentSeverity"),
+        }
+    }
+}
+
+/// Incidents move through each status in order
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum IncidentStatus {
+    Reported,
+    Investigated,
+    ActionAssigned,
+    Closed,
+}
+
+impl IncidentStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IncidentStatus::Reported => "reported",
+            IncidentStatus::Investigated => "investigated",
+            IncidentStatus::ActionAssigned => "action_assigned",
+            IncidentStatus::Closed => "closed",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            IncidentStatus::Reported => "Reported",
+            IncidentStatus::Investigated => "Investigated",
+            IncidentStatus::ActionAssigned => "Corrective action assigned",
+            IncidentStatus::Closed => "Closed",
+        }
+    }
+}
+
+impl From<String> for IncidentStatus {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "reported" => IncidentStatus::Reported,
+            "investigated" => IncidentStatus::Investigated,
+            "action_assigned" => IncidentStatus::ActionAssigned,
+            "closed" => IncidentStatus::Closed,
+            _ => panic!("Invalid IncidentStatus"),
+        }
+    }
+}
+
+/// A safety incident on a jobsite
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Incident {
+    pub id: Uuid,
+    pub jobsite_id: Uuid,
+    pub jobsite_name: String,
+    pub severity: IncidentSeverity,
+    pub status: IncidentStatus,
+    pub occurred_on: NaiveDate,
+    pub description: String,
+    pub findings: Option<String>,
+    pub corrective_action: Option<String>,
+    pub action_assignee_id: Option<Uuid>,
+    pub action_assignee_name: Option<String>,
+    pub action_due_on: Option<NaiveDate>,
+    pub reported_at: DateTime<Utc>,
+    pub investigated_at: Option<DateTime<Utc>>,
+    pub action_assigned_at: Option<DateTime<Utc>>,
+    pub closed_at: Option<DateTime<Utc>>,
+}
+
+/// An employee involved in an incident
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct IncidentEmployee {
+    pub incident_id: Uuid,
+    pub employee_id: Uuid,
+    pub employee_name: String,
+}
+
+/// How long a jobsite has gone without an incident
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct JobsiteSafety {
+    pub jobsite_id: Uuid,
+    pub jobsite_name: String,
+    /// The last day someone was hurt, near misses are tracked but don't
+    /// reset the count
+    pub last_incident_on: Option<NaiveDate>,
+    pub open_incidents: i64,
+}
+
+impl Incident {
+    pub fn stream_name(id: &Uuid) -> String {
+        format!("incident-{}", id)
+    }
+}
+
+impl JobsiteSafety {
+    /// Days since the last incident as of `today`, `None` if there's never
+    /// been one
+    pub fn days_since_incident(&self, today: NaiveDate) -> Option<i64> {
+        self.last_incident_on
+            .map(|last_incident_on| (today - last_incident_on).num_days().max(0))
+    }
+}
+
+#[cfg(feature = "connect")]
+impl Incident {
+    pub async fn report(
+        transaction: &mut Transaction<'_, Postgres>,
+        reported_event: &IncidentReported,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query!(
+            r#"
+            INSERT INTO incidents (id, jobsite_id, severity, status, occurred_on, description, reported_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            "#,
+            reported_event.id,
+            reported_event.jobsite_id,
+            reported_event.severity.as_str(),
+            IncidentStatus::Reported.as_str(),
+            reported_event.occurred_on,
+            reported_event.description,
+            reported_event.reported_at
+        )
+        .execute(&mut **transaction)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO incident_employees (incident_id, employee_id)
+            SELECT $1, employee_id FROM UNNEST($2::UUID[]) AS employee_id
+            ON CONFLICT DO NOTHING
+            "#,
+            reported_event.id,
+            &reported_event.employee_ids
+        )
+        .execute(&mut **transaction)
+        .await?;
+
+        Self::get_by_id(transaction, &reported_event.id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)
+    }
+
+    pub async fn investigate(
+        transaction: &mut Transaction<'_, Postgres>,
+        investigated_event: &IncidentInvestigated,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query!(
+            r#"
+            UPDATE incidents
+            SET status = $2, findings = $3, investigated_at = $4
+            WHERE id = $1
+            "#,
+            investigated_event.id,
+            IncidentStatus::Investigated.as_str(),
+            investigated_event.findings,
+            investigated_event.investigated_at
+        )
+        .execute(&mut **transaction)
+        .await?;
+
+        Self::get_by_id(transaction, &investigated_event.id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)
+    }
+
+    pub async fn assign_corrective_action(
+        transaction: &mut Transaction<'_, Postgres>,
+        assigned_event: &IncidentCorrectiveActionAssigned,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query!(
+            r#"
+            UPDATE incidents
+            SET status = $2, corrective_action = $3, action_assignee_id = $4, action_due_on = $5,
+                action_assigned_at = $6
+            WHERE id = $1
+            "#,
+            assigned_event.id,
+            IncidentStatus::ActionAssigned.as_str(),
+            assigned_event.action,
+            assigned_event.assignee_id,
+            assigned_event.due_on,
+            assigned_event.assigned_at
+        )
+        .execute(&mut **transaction)
+        .await?;
+
+        Self::get_by_id(transaction, &assigned_event.id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)
+    }
+
+    pub async fn close(
+        transaction: &mut Transaction<'_, Postgres>,
+        closed_event: &IncidentClosed,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query!(
+            r#"
+            UPDATE incidents
+            SET status = $2, closed_at = $3
+            WHERE id = $1
+            "#,
+            closed_event.id,
+            IncidentStatus::Closed.as_str(),
+            closed_event.closed_at
+        )
+        .execute(&mut **transaction)
+        .await?;
+
+        Self::get_by_id(transaction, &closed_event.id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)
+    }
+
+    pub async fn get_by_id(
+        transaction: &mut Transaction<'_, Postgres>,
+        id: &Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            SELECT i.id, i.jobsite_id, j.name AS jobsite_name, i.severity, i.status, i.occurred_on,
+                i.description, i.findings, i.corrective_action, i.action_assignee_id,
+                e.name AS "action_assignee_name?", i.action_due_on, i.reported_at,
+                i.investigated_at, i.action_assigned_at, i.closed_at
+            FROM incidents i
+            JOIN jobsites j ON j.id = i.jobsite_id
+            LEFT JOIN employees e ON e.id = i.action_assignee_id
+            WHERE i.id = $1
+            "#,
+            id
+        )
+        .fetch_optional(&mut **transaction)
+        .await
+    }
+
+    /// Every incident, open ones first and then the most recent
+    pub async fn get_list(
+        transaction: &mut Transaction<'_, Postgres>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            SELECT i.id, i.jobsite_id, j.name AS jobsite_name, i.severity, i.status, i.occurred_on,
+                i.description, i.findings, i.corrective_action, i.action_assignee_id,
+                e.name AS "action_assignee_name?", i.action_due_on, i.reported_at,
+                i.investigated_at, i.action_assigned_at, i.closed_at
+            FROM incidents i
+            JOIN jobsites j ON j.id = i.jobsite_id
+            LEFT JOIN employees e ON e.id = i.action_assignee_id
+            ORDER BY i.status = $1, i.occurred_on DESC, i.reported_at DESC
+            "#,
+            IncidentStatus::Closed.as_str()
+        )
+        .fetch_all(&mut **transaction)
+        .await
+    }
+
+    /// Days without an incident for every jobsite, longest streak last
+    pub async fn get_jobsite_safety(
+        transaction: &mut Transaction<'_, Postgres>,
+    ) -> Result<Vec<JobsiteSafety>, sqlx::Error> {
+        sqlx::query_as!(
+            JobsiteSafety,
+            r#"
+            SELECT j.id AS jobsite_id, j.name AS jobsite_name,
+                MAX(i.occurred_on) FILTER (WHERE i.severity != $1) AS last_incident_on,
+                COUNT(i.id) FILTER (WHERE i.status != $2) AS "open_incidents!"
+            FROM jobsites j
+            LEFT JOIN incidents i ON i.jobsite_id = j.id
+            GROUP BY j.id, j.name
+            ORDER BY MAX(i.occurred_on) FILTER (WHERE i.severity != $1) DESC NULLS LAST, j.name
+            "#,
+            IncidentSeverity::NearMiss.as_str(),
+            IncidentStatus::Closed.as_str()
+        )
+        .fetch_all(&mut **transaction)
+        .await
+    }
+}
+
+#[cfg(feature = "connect")]
+impl IncidentEmployee {
+    pub async fn get_list_by_incident(
+        transaction: &mut Transaction<'_, Postgres>,
+        incident_id: &Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            SELECT ie.incident_id, ie.employee_id, e.name AS employee_name
+            FROM incident_employees ie
+            JOIN employees e ON e.id = ie.employee_id
+            WHERE ie.incident_id = $1
+            ORDER BY e.name
+            "#,
+            incident_id
+        )
+        .fetch_all(&mut **transaction)
+        .await
+    }
+
+    pub async fn get_list(
+        transaction: &mut Transaction<'_, Postgres>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            SELECT ie.incident_id, ie.employee_id, e.name AS employee_name
+            FROM incident_employees ie
+            JOIN employees e ON e.id = ie.employee_id
+            ORDER BY e.name
+            "#
+        )
+        .fetch_all(&mut **transaction)
+        .await
+    }
+}
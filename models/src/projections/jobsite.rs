@@ -1,15 +1,62 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
 #[cfg(feature = "connect")]
 use sqlx::{Postgres, Transaction};
 use uuid::Uuid;
 
 #[cfg(feature = "connect")]
-use crate::events::jobsite::{JobsiteCreated, JobsiteUpdated};
+use crate::events::jobsite::{
+    JobsiteArchived, JobsiteAssignedToProject, JobsiteCertificationsRequired, JobsiteCreated,
+    JobsiteGeofenceSet, JobsiteUpdated, JobsitesMerged, JobsitesUnmerged,
+};
+use crate::{
+    geofence::{Coordinates, Geofence},
+    local_time::{local_date, time_zone},
+};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Jobsite {
     pub id: Uuid,
     pub name: String,
+    pub project_id: Option<Uuid>,
+    /// The jobsite this one was merged into as a duplicate, its hours,
+    /// assignments and attachments are recorded there until it is unmerged
+    pub merged_into_id: Option<Uuid>,
+    /// Set once work at the jobsite is over, it can no longer be clocked into
+    /// or have crew assigned
+    pub archived_at: Option<DateTime<Utc>>,
+    /// IANA name of the zone the jobsite's days are counted in
+    pub time_zone: String,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub geofence_radius_meters: Option<i32>,
+}
+
+impl Jobsite {
+    /// The area clock-ins are checked against, once a location is set
+    pub fn geofence(&self) -> Option<Geofence> {
+        Some(Geofence {
+            center: Coordinates::new(self.latitude?, self.longitude?)?,
+            radius_meters: self.geofence_radius_meters?,
+        })
+    }
+
+    pub fn tz(&self) -> Tz {
+        time_zone(&self.time_zone)
+    }
+
+    /// The day it is at the jobsite at `at`
+    pub fn local_date(&self, at: DateTime<Utc>) -> NaiveDate {
+        local_date(at, &self.tz())
+    }
+}
+
+/// A duplicate jobsite and the jobsite it was merged into
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct JobsiteMerge {
+    pub source: Jobsite,
+    pub target: Jobsite,
 }
 
 #[cfg(feature = "connect")]
@@ -21,12 +68,14 @@ impl Jobsite {
         sqlx::query_as!(
             Self,
             r#"
-            INSERT INTO jobsites (id, name)
-            VALUES ($1, $2)
+            INSERT INTO jobsites (id, name, project_id, time_zone)
+            VALUES ($1, $2, $3, $4)
             RETURNING *;
             "#,
             created_event.id,
-            created_event.name
+            created_event.name,
+            created_event.project_id,
+            created_event.time_zone
         )
         .fetch_one(&mut **transaction)
         .await
@@ -40,14 +89,268 @@ impl Jobsite {
             Self,
             r#"
             UPDATE jobsites
-            SET name = $2
+            SET name = $2, time_zone = $3
             WHERE id = $1
             RETURNING *;
             "#,
             updated_event.id,
-            updated_event.name
+            updated_event.name,
+            updated_event.time_zone
+        )
+        .fetch_one(&mut **transaction)
+        .await
+    }
+
+    pub async fn assign_to_project(
+        transaction: &mut Transaction<'_, Postgres>,
+        assigned_event: &JobsiteAssignedToProject,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            UPDATE jobsites
+            SET project_id = $2
+            WHERE id = $1
+            RETURNING *;
+            "#,
+            assigned_event.id,
+            assigned_event.project_id
+        )
+        .fetch_one(&mut **transaction)
+        .await
+    }
+
+    /// Replace the certifications the jobsite requires its crew to hold
+    pub async fn require_certifications(
+        transaction: &mut Transaction<'_, Postgres>,
+        required_event: &JobsiteCertificationsRequired,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query!(
+            r#"
+            DELETE FROM jobsite_certifications
+            WHERE jobsite_id = $1
+            "#,
+            required_event.id
+        )
+        .execute(&mut **transaction)
+        .await?;
+
+        let certification_types = required_event
+            .certification_types
+            .iter()
+            .map(|certification_type| certification_type.as_str().to_string())
+            .collect::<Vec<_>>();
+
+        sqlx::query!(
+            r#"
+            INSERT INTO jobsite_certifications (jobsite_id, certification_type)
+            SELECT $1, certification_type FROM UNNEST($2::VARCHAR[]) AS certification_type
+            ON CONFLICT DO NOTHING
+            "#,
+            required_event.id,
+            &certification_types
+        )
+        .execute(&mut **transaction)
+        .await?;
+
+        Self::get_by_id(transaction, &required_event.id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)
+    }
+
+    /// Set or clear the area clock-ins are checked against
+    pub async fn set_geofence(
+        transaction: &mut Transaction<'_, Postgres>,
+        geofence_event: &JobsiteGeofenceSet,
+    ) -> Result<Self, sqlx::Error> {
+        let geofence = geofence_event.geofence;
+
+        sqlx::query_as!(
+            Self,
+            r#"
+            UPDATE jobsites
+            SET latitude = $2, longitude = $3, geofence_radius_meters = $4
+            WHERE id = $1
+            RETURNING *;
+            "#,
+            geofence_event.id,
+            geofence.map(|geofence| geofence.center.latitude),
+            geofence.map(|geofence| geofence.center.longitude),
+            geofence.map(|geofence| geofence.radius_meters)
+        )
+        .fetch_one(&mut **transaction)
+        .await
+    }
+
+    pub async fn archive(
+        transaction: &mut Transaction<'_, Postgres>,
+        archived_event: &JobsiteArchived,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            UPDATE jobsites
+            SET archived_at = $2
+            WHERE id = $1
+            RETURNING *;
+            "#,
+            archived_event.id,
+            archived_event.archived_at
+        )
+        .fetch_one(&mut **transaction)
+        .await
+    }
+
+    /// Mark the source as merged into the target and re-point everything
+    /// recorded against the source, each row remembers where it came from so
+    /// the merge can be undone. Returns the earliest work date moved, labor
+    /// costs have to be recomputed from there.
+    pub async fn merge(
+        transaction: &mut Transaction<'_, Postgres>,
+        merged_event: &JobsitesMerged,
+    ) -> Result<(Self, Option<NaiveDate>), sqlx::Error> {
+        let moved_from = sqlx::query_scalar!(
+            r#"
+            SELECT MIN(work_date)
+            FROM labor_classifications
+            WHERE jobsite_id = $1
+            "#,
+            merged_event.source_id
         )
         .fetch_one(&mut **transaction)
+        .await?;
+
+        let jobsite = sqlx::query_as!(
+            Self,
+            r#"
+            UPDATE jobsites
+            SET merged_into_id = $2
+            WHERE id = $1
+            RETURNING *;
+            "#,
+            merged_event.source_id,
+            merged_event.target_id
+        )
+        .fetch_one(&mut **transaction)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            WITH moved_shifts AS (
+                UPDATE shifts
+                SET merged_from_id = jobsite_id, jobsite_id = $2
+                WHERE jobsite_id = $1
+            ),
+            moved_hours AS (
+                UPDATE employee_hours
+                SET merged_from_id = jobsite_id, jobsite_id = $2
+                WHERE jobsite_id = $1
+            ),
+            moved_assignments AS (
+                UPDATE crew_assignments
+                SET merged_from_id = jobsite_id, jobsite_id = $2
+                WHERE jobsite_id = $1
+            ),
+            moved_attachments AS (
+                UPDATE attachments
+                SET merged_from_id = jobsite_id, jobsite_id = $2
+                WHERE jobsite_id = $1
+            )
+            UPDATE labor_classifications
+            SET merged_from_id = jobsite_id, jobsite_id = $2
+            WHERE jobsite_id = $1
+            "#,
+            merged_event.source_id,
+            merged_event.target_id
+        )
+        .execute(&mut **transaction)
+        .await?;
+
+        Ok((jobsite, moved_from))
+    }
+
+    /// Undo a merge, sending everything the merge re-pointed back to the
+    /// source. Returns the earliest work date moved back, labor costs have to
+    /// be recomputed from there.
+    pub async fn unmerge(
+        transaction: &mut Transaction<'_, Postgres>,
+        unmerged_event: &JobsitesUnmerged,
+    ) -> Result<(Self, Option<NaiveDate>), sqlx::Error> {
+        let moved_from = sqlx::query_scalar!(
+            r#"
+            SELECT MIN(work_date)
+            FROM labor_classifications
+            WHERE merged_from_id = $1
+            "#,
+            unmerged_event.source_id
+        )
+        .fetch_one(&mut **transaction)
+        .await?;
+
+        let jobsite = sqlx::query_as!(
+            Self,
+            r#"
+            UPDATE jobsites
+            SET merged_into_id = NULL
+            WHERE id = $1
+            RETURNING *;
+            "#,
+            unmerged_event.source_id
+        )
+        .fetch_one(&mut **transaction)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            WITH moved_shifts AS (
+                UPDATE shifts
+                SET jobsite_id = merged_from_id, merged_from_id = NULL
+                WHERE merged_from_id = $1
+            ),
+            moved_hours AS (
+                UPDATE employee_hours
+                SET jobsite_id = merged_from_id, merged_from_id = NULL
+                WHERE merged_from_id = $1
+            ),
+            moved_assignments AS (
+                UPDATE crew_assignments
+                SET jobsite_id = merged_from_id, merged_from_id = NULL
+                WHERE merged_from_id = $1
+            ),
+            moved_attachments AS (
+                UPDATE attachments
+                SET jobsite_id = merged_from_id, merged_from_id = NULL
+                WHERE merged_from_id = $1
+            )
+            UPDATE labor_classifications
+            SET jobsite_id = merged_from_id, merged_from_id = NULL
+            WHERE merged_from_id = $1
+            "#,
+            unmerged_event.source_id
+        )
+        .execute(&mut **transaction)
+        .await?;
+
+        Ok((jobsite, moved_from))
+    }
+
+    /// Jobsites currently merged into this one
+    pub async fn get_merged_into(
+        transaction: &mut Transaction<'_, Postgres>,
+        id: &Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            SELECT id, name, project_id, merged_into_id, archived_at, time_zone, latitude, longitude,
+                geofence_radius_meters
+            FROM jobsites
+            WHERE merged_into_id = $1
+            ORDER BY name
+            "#,
+            id
+        )
+        .fetch_all(&mut **transaction)
         .await
     }
 
@@ -58,7 +361,8 @@ impl Jobsite {
         sqlx::query_as!(
             Self,
             r#"
-            SELECT id, name
+            SELECT id, name, project_id, merged_into_id, archived_at, time_zone, latitude, longitude,
+                geofence_radius_meters
             FROM jobsites
             WHERE id = $1
             "#,
@@ -68,17 +372,21 @@ impl Jobsite {
         .await
     }
 
+    /// Jobsite names are unique within a project
     pub async fn get_by_name(
         transaction: &mut Transaction<'_, Postgres>,
+        project_id: Option<Uuid>,
         name: String,
     ) -> Result<Option<Self>, sqlx::Error> {
         sqlx::query_as!(
             Self,
             r#"
-            SELECT id, name
+            SELECT id, name, project_id, merged_into_id, archived_at, time_zone, latitude, longitude,
+                geofence_radius_meters
             FROM jobsites
-            WHERE name = $1
+            WHERE project_id IS NOT DISTINCT FROM $1 AND name = $2
             "#,
+            project_id,
             name
         )
         .fetch_optional(&mut **transaction)
@@ -91,11 +399,153 @@ impl Jobsite {
         sqlx::query_as!(
             Self,
             r#"
-            SELECT id, name
+            SELECT id, name, project_id, merged_into_id, archived_at, time_zone, latitude, longitude,
+                geofence_radius_meters
             FROM jobsites
+            WHERE merged_into_id IS NULL AND archived_at IS NULL
+            ORDER BY name
             "#,
         )
         .fetch_all(&mut **transaction)
         .await
     }
 }
+
+#[cfg(all(test, feature = "connect"))]
+mod tests {
+    use chrono::{Duration, TimeZone, Utc};
+    use sqlx::PgPool;
+    use uuid::Uuid;
+
+    use super::Jobsite;
+    use crate::{
+        events::{
+            jobsite::{JobsitesMerged, JobsitesUnmerged},
+            shift::{ClockedIn, ClockedOut},
+        },
+        geofence::GeofenceCheck,
+        labor::overtime::PayRules,
+        projections::{
+            employee_hours::EmployeeHours, labor_classification::LaborClassification, shift::Shift,
+            timesheet::Timesheet,
+        },
+    };
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn unmerging_moves_back_hours_closed_while_merged(db_pool: PgPool) -> sqlx::Result<()> {
+        let mut transaction = db_pool.begin().await?;
+
+        let (source_id, target_id, employee_id) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        sqlx::query!(
+            "INSERT INTO jobsites (id, name) VALUES ($1, 'Main St'), ($2, 'Main Street')",
+            source_id,
+            target_id
+        )
+        .execute(&mut *transaction)
+        .await?;
+        sqlx::query!(
+            "INSERT INTO employees (id, name) VALUES ($1, 'Sam Lee')",
+            employee_id
+        )
+        .execute(&mut *transaction)
+        .await?;
+
+        // Clocked in at the duplicate, which is merged before the shift ends
+        let shift_id = Uuid::new_v4();
+        let clocked_in_at = Utc.with_ymd_and_hms(2025, 1, 14, 15, 0, 0).unwrap();
+        Shift::clock_in(
+            &mut transaction,
+            &ClockedIn {
+                shift_id,
+                employee_id,
+                jobsite_id: source_id,
+                cost_code_id: None,
+                clocked_in_at,
+                time_zone: "UTC".to_string(),
+                coordinates: None,
+                geofence: GeofenceCheck::default(),
+            },
+        )
+        .await?;
+
+        Jobsite::merge(
+            &mut transaction,
+            &JobsitesMerged {
+                source_id,
+                target_id,
+                merged_at: clocked_in_at + Duration::hours(1),
+            },
+        )
+        .await?;
+
+        let shift = Shift::clock_out(
+            &mut transaction,
+            &ClockedOut {
+                shift_id,
+                employee_id,
+                clocked_out_at: clocked_in_at + Duration::hours(8),
+            },
+        )
+        .await?;
+        EmployeeHours::record(&mut transaction, &shift).await?;
+        let week_start = Timesheet::week_start_of(shift.work_date());
+        let classifications = LaborClassification::reclassify_week(
+            &mut transaction,
+            &PayRules::none(),
+            &employee_id,
+            week_start,
+        )
+        .await?;
+        assert_eq!(classifications[0].jobsite_id, target_id);
+
+        Jobsite::unmerge(
+            &mut transaction,
+            &JobsitesUnmerged {
+                source_id,
+                target_id,
+                unmerged_at: clocked_in_at + Duration::days(2),
+            },
+        )
+        .await?;
+
+        let shift = Shift::get_by_id(&mut transaction, &shift_id)
+            .await?
+            .unwrap();
+        assert_eq!(shift.jobsite_id, source_id);
+
+        let hours = EmployeeHours::get_list_by_employee(
+            &mut transaction,
+            &employee_id,
+            week_start,
+            Timesheet::week_end_of(week_start),
+        )
+        .await?;
+        assert_eq!(hours.len(), 1);
+        assert_eq!(hours[0].jobsite_id, source_id);
+
+        // Reclassifying after the unmerge keeps the hours where they are
+        let classifications = LaborClassification::reclassify_week(
+            &mut transaction,
+            &PayRules::none(),
+            &employee_id,
+            week_start,
+        )
+        .await?;
+        assert_eq!(classifications.len(), 1);
+        assert_eq!(classifications[0].jobsite_id, source_id);
+
+        let still_merged = sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*) AS "count!"
+            FROM labor_classifications
+            WHERE shift_id = $1 AND merged_from_id IS NOT NULL
+            "#,
+            shift_id
+        )
+        .fetch_one(&mut *transaction)
+        .await?;
+        assert_eq!(still_merged, 0);
+
+        Ok(())
+    }
+}
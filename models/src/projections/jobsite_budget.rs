@@ -0,0 +1,464 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "connect")]
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[cfg(feature = "connect")]
+use crate::{
+    events::jobsite_budget::{JobsiteBudgetSet, JobsiteBudgetThresholdReached},
+    projections::change_order::ChangeOrderStatus,
+};
+
+/// Percentages of a budget that raise an alert when reached
+pub const BUDGET_ALERT_THRESHOLDS: [i32; 2] = [80, 100];
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum BudgetMeasure {
+    Hours,
+    Cost,
+}
+
+impl BudgetMeasure {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BudgetMeasure::Hours => "hours",
+            BudgetMeasure::Cost => "cost",
+        }
+    }
+}
+
+impl From<String> for BudgetMeasure {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "hours" => BudgetMeasure::Hours,
+            "cost" => BudgetMeasure::Cost,
+            _ => panic!("Invalid BudgetMeasure"),
+        }
+    }
+}
+
+/// A labor budget for a jobsite, or a single cost code on it, alongside what
+/// has actually been spent against it
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct JobsiteBudget {
+    pub id: Uuid,
+    pub jobsite_id: Uuid,
+    pub cost_code: Option<String>,
+    pub budget_minutes: Option<i64>,
+    pub budget_cents: Option<i64>,
+    /// Added by the jobsite's approved change orders, only ever set on the
+    /// jobsite wide budget
+    pub change_order_minutes: i64,
+    pub change_order_cents: i64,
+    pub actual_minutes: i64,
+    pub actual_cents: i64,
+    pub set_at: DateTime<Utc>,
+}
+
+impl JobsiteBudget {
+    /// The hours budget once approved change orders are applied
+    pub fn revised_minutes(&self) -> Option<i64> {
+        self.budget_minutes
+            .map(|budget_minutes| budget_minutes + self.change_order_minutes)
+    }
+
+    /// The cost budget once approved change orders are applied
+    pub fn revised_cents(&self) -> Option<i64> {
+        self.budget_cents
+            .map(|budget_cents| budget_cents + self.change_order_cents)
+    }
+
+    /// How much of the revised budget has been used, `None` when nothing was
+    /// budgeted for `measure`
+    pub fn percent_used(&self, measure: BudgetMeasure) -> Option<i64> {
+        let (budget, actual) = match measure {
+            BudgetMeasure::Hours => (self.revised_minutes(), self.actual_minutes),
+            BudgetMeasure::Cost => (self.revised_cents(), self.actual_cents),
+        };
+
+        budget
+            .filter(|budget| *budget > 0)
+            .map(|budget| actual * 100 / budget)
+    }
+
+    /// Every alert threshold this budget has reached against what is
+    /// budgeted now
+    pub fn reached_thresholds(&self) -> Vec<ReachedThreshold> {
+        [
+            (BudgetMeasure::Hours, self.revised_minutes()),
+            (BudgetMeasure::Cost, self.revised_cents()),
+        ]
+        .into_iter()
+        .filter_map(|(measure, budgeted)| Some((measure, budgeted?, self.percent_used(measure)?)))
+        .flat_map(|(measure, budgeted, percent_used)| {
+            BUDGET_ALERT_THRESHOLDS
+                .into_iter()
+                .filter(move |threshold| percent_used >= *threshold as i64)
+                .map(move |threshold| ReachedThreshold {
+                    measure,
+                    budgeted,
+                    threshold,
+                    percent_used,
+                })
+        })
+        .collect()
+    }
+}
+
+/// A threshold reached against one revision of a budget. Setting the budget
+/// again gives it a new id and approved change orders move what is budgeted,
+/// either way its thresholds can be reached again.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReachedThreshold {
+    pub measure: BudgetMeasure,
+    /// The revised budget, in minutes or cents
+    pub budgeted: i64,
+    pub threshold: i32,
+    pub percent_used: i64,
+}
+
+/// A budget threshold that has been reached
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct JobsiteBudgetAlert {
+    pub budget_id: Uuid,
+    pub jobsite_id: Uuid,
+    pub cost_code: Option<String>,
+    pub measure: BudgetMeasure,
+    pub budgeted: i64,
+    pub threshold: i32,
+    pub percent_used: i64,
+    pub reached_at: DateTime<Utc>,
+}
+
+#[cfg(feature = "connect")]
+impl JobsiteBudget {
+    pub async fn set(
+        transaction: &mut Transaction<'_, Postgres>,
+        set_event: &JobsiteBudgetSet,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query!(
+            r#"
+            INSERT INTO jobsite_budgets (id, jobsite_id, cost_code, budget_minutes, budget_cents, set_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (jobsite_id, COALESCE(cost_code, '')) DO UPDATE
+            SET id = excluded.id,
+                budget_minutes = excluded.budget_minutes,
+                budget_cents = excluded.budget_cents,
+                set_at = excluded.set_at
+            "#,
+            set_event.id,
+            set_event.jobsite_id,
+            set_event.cost_code,
+            set_event.budget_minutes,
+            set_event.budget_cents,
+            set_event.set_at
+        )
+        .execute(&mut **transaction)
+        .await?;
+
+        Self::refresh_actuals(transaction, &set_event.jobsite_id).await?;
+        Self::refresh_change_orders(transaction, &set_event.jobsite_id).await?;
+
+        Self::get_by_id(transaction, &set_event.id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)
+    }
+
+    /// Bring a jobsite's budget actuals up to date with the hours and labor
+    /// costs logged against it, or against a budget's cost code and the
+    /// tasks beneath it
+    pub async fn refresh_actuals(
+        transaction: &mut Transaction<'_, Postgres>,
+        jobsite_id: &Uuid,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+            WITH RECURSIVE code_tree (root_id, id) AS (
+                SELECT id, id FROM cost_codes WHERE jobsite_id = $1
+                UNION ALL
+                SELECT t.root_id, c.id FROM cost_codes c JOIN code_tree t ON c.parent_id = t.id
+            ),
+            budget_shifts AS (
+                SELECT b.id AS budget_id, s.id AS shift_id
+                FROM jobsite_budgets b
+                JOIN shifts s ON s.jobsite_id = b.jobsite_id
+                WHERE b.jobsite_id = $1 AND (b.cost_code IS NULL OR s.cost_code_id IN (
+                    SELECT t.id FROM code_tree t
+                    JOIN cost_codes r ON r.id = t.root_id
+                    WHERE r.code = b.cost_code
+                ))
+            )
+            UPDATE jobsite_budgets b
+            SET actual_minutes = COALESCE((
+                    SELECT SUM(h.minutes_worked) FROM budget_shifts bs
+                    JOIN employee_hours h ON h.shift_id = bs.shift_id
+                    WHERE bs.budget_id = b.id
+                ), 0),
+                actual_cents = COALESCE((
+                    SELECT SUM(c.cost_cents) FROM budget_shifts bs
+                    JOIN labor_classifications c ON c.shift_id = bs.shift_id
+                    WHERE bs.budget_id = b.id
+                ), 0)
+            WHERE b.jobsite_id = $1
+            "#,
+            jobsite_id
+        )
+        .execute(&mut **transaction)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Bring a jobsite wide budget up to date with the jobsite's approved
+    /// change orders
+    pub async fn refresh_change_orders(
+        transaction: &mut Transaction<'_, Postgres>,
+        jobsite_id: &Uuid,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+            UPDATE jobsite_budgets b
+            SET change_order_minutes = COALESCE((
+                    SELECT SUM(c.minutes) FROM change_orders c
+                    WHERE c.jobsite_id = b.jobsite_id AND c.status = $1
+                ), 0),
+                change_order_cents = COALESCE((
+                    SELECT SUM(c.amount_cents) FROM change_orders c
+                    WHERE c.jobsite_id = b.jobsite_id AND c.status = $1
+                ), 0)
+            WHERE b.jobsite_id = $2 AND b.cost_code IS NULL
+            "#,
+            ChangeOrderStatus::Approved.as_str(),
+            jobsite_id
+        )
+        .execute(&mut **transaction)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn get_by_id(
+        transaction: &mut Transaction<'_, Postgres>,
+        id: &Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            SELECT id, jobsite_id, cost_code, budget_minutes, budget_cents,
+                change_order_minutes, change_order_cents, actual_minutes, actual_cents, set_at
+            FROM jobsite_budgets
+            WHERE id = $1
+            "#,
+            id
+        )
+        .fetch_optional(&mut **transaction)
+        .await
+    }
+
+    /// A jobsite's budgets, the jobsite wide budget first
+    pub async fn get_list_by_jobsite(
+        transaction: &mut Transaction<'_, Postgres>,
+        jobsite_id: &Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            SELECT id, jobsite_id, cost_code, budget_minutes, budget_cents,
+                change_order_minutes, change_order_cents, actual_minutes, actual_cents, set_at
+            FROM jobsite_budgets
+            WHERE jobsite_id = $1
+            ORDER BY cost_code NULLS FIRST
+            "#,
+            jobsite_id
+        )
+        .fetch_all(&mut **transaction)
+        .await
+    }
+}
+
+#[cfg(feature = "connect")]
+impl JobsiteBudgetAlert {
+    pub async fn create(
+        transaction: &mut Transaction<'_, Postgres>,
+        reached_event: &JobsiteBudgetThresholdReached,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            INSERT INTO jobsite_budget_alerts (budget_id, jobsite_id, cost_code, measure, budgeted, threshold, percent_used, reached_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            ON CONFLICT (budget_id, measure, budgeted, threshold) DO UPDATE
+            SET percent_used = excluded.percent_used
+            RETURNING budget_id, jobsite_id, cost_code, measure, budgeted, threshold, percent_used,
+                reached_at;
+            "#,
+            reached_event.budget_id,
+            reached_event.jobsite_id,
+            reached_event.cost_code,
+            reached_event.measure.as_str(),
+            reached_event.budgeted,
+            reached_event.threshold,
+            reached_event.percent_used,
+            reached_event.reached_at
+        )
+        .fetch_one(&mut **transaction)
+        .await
+    }
+
+    pub async fn exists(
+        transaction: &mut Transaction<'_, Postgres>,
+        budget_id: &Uuid,
+        reached: &ReachedThreshold,
+    ) -> Result<bool, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM jobsite_budget_alerts
+                WHERE budget_id = $1 AND measure = $2 AND budgeted = $3 AND threshold = $4
+            ) AS "exists!"
+            "#,
+            budget_id,
+            reached.measure.as_str(),
+            reached.budgeted,
+            reached.threshold
+        )
+        .fetch_one(&mut **transaction)
+        .await
+    }
+
+    /// Alerts raised for a jobsite's current budgets, latest first
+    pub async fn get_list_by_jobsite(
+        transaction: &mut Transaction<'_, Postgres>,
+        jobsite_id: &Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            SELECT a.budget_id, a.jobsite_id, a.cost_code, a.measure, a.budgeted, a.threshold,
+                a.percent_used, a.reached_at
+            FROM jobsite_budget_alerts a
+            JOIN jobsite_budgets b ON b.id = a.budget_id
+            WHERE a.jobsite_id = $1
+            ORDER BY a.reached_at DESC
+            "#,
+            jobsite_id
+        )
+        .fetch_all(&mut **transaction)
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    use super::{BudgetMeasure, JobsiteBudget, ReachedThreshold};
+    use crate::events::jobsite_budget::JobsiteBudgetThresholdReached;
+
+    fn budget(budget_minutes: Option<i64>, budget_cents: Option<i64>) -> JobsiteBudget {
+        JobsiteBudget {
+            id: Uuid::new_v4(),
+            jobsite_id: Uuid::nil(),
+            cost_code: None,
+            budget_minutes,
+            budget_cents,
+            change_order_minutes: 0,
+            change_order_cents: 0,
+            actual_minutes: 0,
+            actual_cents: 0,
+            set_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn change_orders_revise_what_was_budgeted() {
+        let mut budget = budget(Some(100 * 60), Some(1_000_000));
+        budget.change_order_minutes = 20 * 60;
+        budget.change_order_cents = -200_000;
+        budget.actual_minutes = 60 * 60;
+        budget.actual_cents = 800_000;
+
+        assert_eq!(budget.revised_minutes(), Some(120 * 60));
+        assert_eq!(budget.revised_cents(), Some(800_000));
+        assert_eq!(budget.percent_used(BudgetMeasure::Hours), Some(50));
+        assert_eq!(budget.percent_used(BudgetMeasure::Cost), Some(100));
+    }
+
+    #[test]
+    fn change_orders_do_not_budget_an_unbudgeted_measure() {
+        let mut budget = budget(None, Some(1_000_000));
+        budget.change_order_minutes = 20 * 60;
+
+        assert_eq!(budget.revised_minutes(), None);
+        assert_eq!(budget.percent_used(BudgetMeasure::Hours), None);
+    }
+
+    fn alert_streams(budget: &JobsiteBudget) -> Vec<String> {
+        budget
+            .reached_thresholds()
+            .into_iter()
+            .map(|reached| JobsiteBudgetThresholdReached::new(budget, reached, Utc::now()))
+            .map(|reached_event| reached_event.stream_name())
+            .collect()
+    }
+
+    #[test]
+    fn thresholds_are_reached_as_the_budget_is_used() {
+        let mut budget = budget(Some(100 * 60), None);
+
+        budget.actual_minutes = 79 * 60;
+        assert!(budget.reached_thresholds().is_empty());
+
+        budget.actual_minutes = 80 * 60;
+        assert_eq!(
+            budget.reached_thresholds(),
+            vec![ReachedThreshold {
+                measure: BudgetMeasure::Hours,
+                budgeted: 100 * 60,
+                threshold: 80,
+                percent_used: 80,
+            }]
+        );
+
+        budget.actual_minutes = 110 * 60;
+        let thresholds = budget
+            .reached_thresholds()
+            .into_iter()
+            .map(|reached| reached.threshold)
+            .collect::<Vec<_>>();
+        assert_eq!(thresholds, vec![80, 100]);
+    }
+
+    #[test]
+    fn raising_a_budget_lets_its_thresholds_be_reached_again() {
+        let mut budget = budget(Some(100 * 60), None);
+        budget.actual_minutes = 100 * 60;
+        let first_alerts = alert_streams(&budget);
+
+        // A change order takes the budget back under both thresholds
+        budget.change_order_minutes = 50 * 60;
+        assert!(budget.reached_thresholds().is_empty());
+
+        budget.actual_minutes = 150 * 60;
+        let second_alerts = alert_streams(&budget);
+
+        assert_eq!(second_alerts.len(), 2);
+        assert!(second_alerts
+            .iter()
+            .all(|stream| !first_alerts.contains(stream)));
+    }
+
+    #[test]
+    fn checking_the_same_budget_again_alerts_on_the_same_streams() {
+        let mut budget = budget(Some(100 * 60), Some(1_000_000));
+        budget.actual_minutes = 90 * 60;
+        budget.actual_cents = 1_000_000;
+
+        // As a read model rebuild would, the alerts land on streams that
+        // already exist so nothing is appended twice
+        assert_eq!(alert_streams(&budget).len(), 3);
+        assert_eq!(alert_streams(&budget), alert_streams(&budget.clone()));
+    }
+}
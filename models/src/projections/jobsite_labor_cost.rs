@@ -0,0 +1,120 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "connect")]
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[cfg(feature = "connect")]
+use crate::labor::overtime::PayClass;
+
+/// What a jobsite cost in labor on a single day, priced from the classified
+/// hours worked there and the pay rates in effect on that day
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct JobsiteLaborCost {
+    pub jobsite_id: Uuid,
+    pub work_date: NaiveDate,
+    pub regular_minutes: i64,
+    pub overtime_minutes: i64,
+    pub double_time_minutes: i64,
+    pub unrated_minutes: i64,
+    pub cost_cents: i64,
+}
+
+#[cfg(feature = "connect")]
+impl JobsiteLaborCost {
+    /// Reprice every classified shift between two dates, inclusive, or every
+    /// day from `from` onwards when `to` is `None`, then total each jobsite
+    /// day from the priced shifts
+    pub async fn recompute(
+        transaction: &mut Transaction<'_, Postgres>,
+        from: NaiveDate,
+        to: Option<NaiveDate>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+            UPDATE labor_classifications c
+            SET cost_cents = (
+                SELECT ROUND(p.hourly_rate_cents * (
+                    c.regular_minutes * $3::FLOAT8
+                    + c.overtime_minutes * $4::FLOAT8
+                    + c.double_time_minutes * $5::FLOAT8
+                ) / 60.0)::BIGINT
+                FROM pay_rates p
+                JOIN employees e ON e.id = c.employee_id
+                WHERE p.effective_date <= c.work_date
+                    AND (p.employee_id = c.employee_id OR (p.employee_id IS NULL AND p.trade = e.trade))
+                    AND (p.jobsite_id IS NULL OR p.jobsite_id = c.jobsite_id)
+                ORDER BY p.employee_id IS NOT NULL DESC, p.jobsite_id IS NOT NULL DESC,
+                    p.effective_date DESC, p.set_at DESC
+                LIMIT 1
+            )
+            WHERE c.work_date >= $1 AND ($2::DATE IS NULL OR c.work_date <= $2)
+            "#,
+            from,
+            to,
+            PayClass::Regular.multiplier(),
+            PayClass::Overtime.multiplier(),
+            PayClass::DoubleTime.multiplier()
+        )
+        .execute(&mut **transaction)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            DELETE FROM jobsite_labor_costs
+            WHERE work_date >= $1 AND ($2::DATE IS NULL OR work_date <= $2)
+            "#,
+            from,
+            to
+        )
+        .execute(&mut **transaction)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO jobsite_labor_costs (jobsite_id, work_date, regular_minutes, overtime_minutes, double_time_minutes, unrated_minutes, cost_cents)
+            SELECT c.jobsite_id, c.work_date,
+                SUM(c.regular_minutes)::BIGINT,
+                SUM(c.overtime_minutes)::BIGINT,
+                SUM(c.double_time_minutes)::BIGINT,
+                SUM(CASE WHEN c.cost_cents IS NULL
+                    THEN c.regular_minutes + c.overtime_minutes + c.double_time_minutes
+                    ELSE 0 END)::BIGINT,
+                COALESCE(SUM(c.cost_cents), 0)::BIGINT
+            FROM labor_classifications c
+            WHERE c.work_date >= $1 AND ($2::DATE IS NULL OR c.work_date <= $2)
+            GROUP BY c.jobsite_id, c.work_date
+            "#,
+            from,
+            to
+        )
+        .execute(&mut **transaction)
+        .await?;
+
+        Ok(())
+    }
+
+    /// A jobsite's daily costs between two dates, latest first
+    pub async fn get_list_by_jobsite(
+        transaction: &mut Transaction<'_, Postgres>,
+        jobsite_id: &Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            SELECT jobsite_id, work_date, regular_minutes, overtime_minutes, double_time_minutes,
+                unrated_minutes, cost_cents
+            FROM jobsite_labor_costs
+            WHERE jobsite_id = $1 AND work_date BETWEEN $2 AND $3
+            ORDER BY work_date DESC
+            "#,
+            jobsite_id,
+            from,
+            to
+        )
+        .fetch_all(&mut **transaction)
+        .await
+    }
+}
@@ -0,0 +1,267 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "connect")]
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[cfg(feature = "connect")]
+use crate::events::jobsite::{JobsiteKioskRegistered, JobsiteKioskRevoked};
+
+/// How far back wrong PINs are counted
+pub const PIN_FAILURE_WINDOW: Duration = Duration::minutes(15);
+/// Wrong PINs for one employee at a kiosk before their PIN stops being
+/// accepted there for the rest of the window
+pub const MAX_EMPLOYEE_PIN_FAILURES: i64 = 5;
+/// Wrong PINs for anyone at a kiosk before it stops accepting PINs at all,
+/// so guessing can't just move on to the next employee
+pub const MAX_KIOSK_PIN_FAILURES: i64 = 20;
+
+/// Whether a kiosk still accepts PINs given the wrong ones entered there
+/// within the window
+pub fn accepts_pins(employee_failures: i64, kiosk_failures: i64) -> bool {
+    employee_failures < MAX_EMPLOYEE_PIN_FAILURES && kiosk_failures < MAX_KIOSK_PIN_FAILURES
+}
+
+/// A shared tablet registered to a jobsite
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Kiosk {
+    pub id: Uuid,
+    pub jobsite_id: Uuid,
+    pub name: String,
+    pub registered_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl Kiosk {
+    pub fn is_revoked(&self) -> bool {
+        self.revoked_at.is_some()
+    }
+}
+
+#[cfg(feature = "connect")]
+impl Kiosk {
+    pub async fn register(
+        transaction: &mut Transaction<'_, Postgres>,
+        registered_event: &JobsiteKioskRegistered,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            INSERT INTO kiosks (id, jobsite_id, name, registered_at)
+            VALUES ($1, $2, $3, $4)
+            RETURNING id, jobsite_id, name, registered_at, revoked_at;
+            "#,
+            registered_event.kiosk_id,
+            registered_event.id,
+            registered_event.name,
+            registered_event.registered_at
+        )
+        .fetch_one(&mut **transaction)
+        .await
+    }
+
+    pub async fn revoke(
+        transaction: &mut Transaction<'_, Postgres>,
+        revoked_event: &JobsiteKioskRevoked,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            UPDATE kiosks
+            SET revoked_at = $2
+            WHERE id = $1
+            RETURNING id, jobsite_id, name, registered_at, revoked_at;
+            "#,
+            revoked_event.kiosk_id,
+            revoked_event.revoked_at
+        )
+        .fetch_one(&mut **transaction)
+        .await
+    }
+
+    pub async fn get_by_id(
+        transaction: &mut Transaction<'_, Postgres>,
+        id: &Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            SELECT id, jobsite_id, name, registered_at, revoked_at
+            FROM kiosks
+            WHERE id = $1
+            "#,
+            id
+        )
+        .fetch_optional(&mut **transaction)
+        .await
+    }
+
+    /// Kiosks at the jobsite that haven't been revoked
+    pub async fn get_active_by_jobsite(
+        transaction: &mut Transaction<'_, Postgres>,
+        jobsite_id: &Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            SELECT id, jobsite_id, name, registered_at, revoked_at
+            FROM kiosks
+            WHERE jobsite_id = $1 AND revoked_at IS NULL
+            ORDER BY registered_at
+            "#,
+            jobsite_id
+        )
+        .fetch_all(&mut **transaction)
+        .await
+    }
+
+    /// Note a wrong PIN entered for the employee at the kiosk. These aren't
+    /// events, they only matter for as long as the window lasts.
+    pub async fn record_pin_failure(
+        transaction: &mut Transaction<'_, Postgres>,
+        id: &Uuid,
+        employee_id: &Uuid,
+        failed_at: DateTime<Utc>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+            INSERT INTO kiosk_pin_failures (id, kiosk_id, employee_id, failed_at)
+            VALUES ($1, $2, $3, $4)
+            "#,
+            Uuid::new_v4(),
+            id,
+            employee_id,
+            failed_at
+        )
+        .execute(&mut **transaction)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Wrong PINs entered at the kiosk since `since`, for the employee and
+    /// for everyone. The kiosk stays locked until the transaction ends, so
+    /// PINs entered at once are counted one after the other instead of all
+    /// seeing the same count.
+    pub async fn count_pin_failures(
+        transaction: &mut Transaction<'_, Postgres>,
+        id: &Uuid,
+        employee_id: &Uuid,
+        since: DateTime<Utc>,
+    ) -> Result<(i64, i64), sqlx::Error> {
+        sqlx::query!(
+            r#"
+            SELECT 1 AS "locked"
+            FROM pg_advisory_xact_lock(hashtextextended('kiosk_pin:' || $1::uuid, 0))
+            "#,
+            id
+        )
+        .fetch_one(&mut **transaction)
+        .await?;
+
+        let counts = sqlx::query!(
+            r#"
+            SELECT
+                COUNT(*) FILTER (WHERE employee_id = $2) AS "employee_failures!",
+                COUNT(*) AS "kiosk_failures!"
+            FROM kiosk_pin_failures
+            WHERE kiosk_id = $1 AND failed_at >= $3
+            "#,
+            id,
+            employee_id,
+            since
+        )
+        .fetch_one(&mut **transaction)
+        .await?;
+
+        Ok((counts.employee_failures, counts.kiosk_failures))
+    }
+
+    /// Forget the employee's wrong PINs at the kiosk once they get it right
+    pub async fn clear_pin_failures(
+        transaction: &mut Transaction<'_, Postgres>,
+        id: &Uuid,
+        employee_id: &Uuid,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+            DELETE FROM kiosk_pin_failures
+            WHERE kiosk_id = $1 AND employee_id = $2
+            "#,
+            id,
+            employee_id
+        )
+        .execute(&mut **transaction)
+        .await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{accepts_pins, MAX_EMPLOYEE_PIN_FAILURES, MAX_KIOSK_PIN_FAILURES};
+
+    #[test]
+    fn employees_are_locked_out_after_too_many_wrong_pins() {
+        assert!(accepts_pins(0, 0));
+        assert!(accepts_pins(MAX_EMPLOYEE_PIN_FAILURES - 1, 10));
+        assert!(!accepts_pins(MAX_EMPLOYEE_PIN_FAILURES, 0));
+    }
+
+    #[test]
+    fn kiosks_stop_taking_pins_when_guesses_are_spread_across_employees() {
+        // Four wrong guesses each for five employees
+        assert!(!accepts_pins(0, MAX_KIOSK_PIN_FAILURES));
+        assert!(accepts_pins(0, MAX_KIOSK_PIN_FAILURES - 1));
+    }
+
+    #[cfg(feature = "connect")]
+    mod pin_failures {
+        use std::time::Duration as StdDuration;
+
+        use chrono::{Duration, Utc};
+        use sqlx::PgPool;
+        use uuid::Uuid;
+
+        use super::super::Kiosk;
+
+        #[sqlx::test(migrations = "../migrations")]
+        async fn pins_entered_at_once_are_counted_one_at_a_time(
+            db_pool: PgPool,
+        ) -> sqlx::Result<()> {
+            let (kiosk_id, employee_id) = (Uuid::new_v4(), Uuid::new_v4());
+            sqlx::query!(
+                "INSERT INTO employees (id, name) VALUES ($1, 'Sam Lee')",
+                employee_id
+            )
+            .execute(&db_pool)
+            .await?;
+
+            let since = Utc::now() - Duration::minutes(15);
+
+            let mut first = db_pool.begin().await?;
+            let counts =
+                Kiosk::count_pin_failures(&mut first, &kiosk_id, &employee_id, since).await?;
+            assert_eq!(counts, (0, 0));
+
+            // A second guess has to wait for the first to be recorded
+            let second = tokio::spawn({
+                let db_pool = db_pool.clone();
+                async move {
+                    let mut second = db_pool.begin().await?;
+                    Kiosk::count_pin_failures(&mut second, &kiosk_id, &employee_id, since).await
+                }
+            });
+            tokio::time::sleep(StdDuration::from_millis(200)).await;
+            assert!(!second.is_finished());
+
+            Kiosk::record_pin_failure(&mut first, &kiosk_id, &employee_id, Utc::now()).await?;
+            first.commit().await?;
+
+            assert_eq!(second.await.unwrap()?, (1, 1));
+
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,135 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "connect")]
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[cfg(feature = "connect")]
+use crate::{
+    labor::overtime::{PayRules, TimeEntry},
+    projections::{shift::Shift, timesheet::Timesheet},
+};
+
+/// A closed shift's minutes broken down into regular, overtime and double
+/// time, the single source of classified hours for reports and exports
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct LaborClassification {
+    pub shift_id: Uuid,
+    pub employee_id: Uuid,
+    pub jobsite_id: Uuid,
+    pub work_date: NaiveDate,
+    pub regular_minutes: i64,
+    pub overtime_minutes: i64,
+    pub double_time_minutes: i64,
+    /// What the shift cost at the pay rate in effect, `None` until priced
+    /// or when no rate applies
+    pub cost_cents: Option<i64>,
+}
+
+/// Classified minutes summed over some period
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct LaborTotals {
+    pub regular_minutes: i64,
+    pub overtime_minutes: i64,
+    pub double_time_minutes: i64,
+}
+
+#[cfg(feature = "connect")]
+impl LaborClassification {
+    /// Reclassify every closed shift an employee started in a week. Overtime
+    /// depends on everything else worked that week, so any change to a shift
+    /// reclassifies its whole week.
+    pub async fn reclassify_week(
+        transaction: &mut Transaction<'_, Postgres>,
+        rules: &PayRules,
+        employee_id: &Uuid,
+        week_start: NaiveDate,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        let week_end = Timesheet::week_end_of(week_start);
+
+        let entries = Shift::get_list_by_employee(transaction, employee_id, week_start, week_end)
+            .await?
+            .into_iter()
+            .filter_map(|shift| {
+                shift.clocked_out_at.map(|clocked_out_at| TimeEntry {
+                    shift_id: shift.id,
+                    jobsite_id: shift.jobsite_id,
+                    work_date: shift.work_date(),
+                    minutes: (clocked_out_at - shift.clocked_in_at).num_minutes().max(0),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        sqlx::query!(
+            r#"
+            DELETE FROM labor_classifications
+            WHERE employee_id = $1 AND work_date BETWEEN $2 AND $3
+            "#,
+            employee_id,
+            week_start,
+            week_end
+        )
+        .execute(&mut **transaction)
+        .await?;
+
+        let mut classifications = vec![];
+        for classification in rules.classify_week(&entries) {
+            let classification = sqlx::query_as!(
+                Self,
+                r#"
+                INSERT INTO labor_classifications (shift_id, employee_id, jobsite_id, work_date, regular_minutes, overtime_minutes, double_time_minutes, merged_from_id)
+                SELECT $1, $2, $3, $4, $5, $6, $7, s.merged_from_id
+                FROM shifts s
+                WHERE s.id = $1
+                ON CONFLICT (shift_id) DO UPDATE
+                SET employee_id = excluded.employee_id,
+                    jobsite_id = excluded.jobsite_id,
+                    merged_from_id = excluded.merged_from_id,
+                    work_date = excluded.work_date,
+                    regular_minutes = excluded.regular_minutes,
+                    overtime_minutes = excluded.overtime_minutes,
+                    double_time_minutes = excluded.double_time_minutes
+                RETURNING shift_id, employee_id, jobsite_id, work_date, regular_minutes, overtime_minutes,
+                    double_time_minutes, cost_cents;
+                "#,
+                classification.shift_id,
+                employee_id,
+                classification.jobsite_id,
+                classification.work_date,
+                classification.regular_minutes,
+                classification.overtime_minutes,
+                classification.double_time_minutes
+            )
+            .fetch_one(&mut **transaction)
+            .await?;
+
+            classifications.push(classification);
+        }
+
+        Ok(classifications)
+    }
+
+    pub async fn get_totals_by_employee(
+        transaction: &mut Transaction<'_, Postgres>,
+        employee_id: &Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<LaborTotals, sqlx::Error> {
+        sqlx::query_as!(
+            LaborTotals,
+            r#"
+            SELECT
+                COALESCE(SUM(regular_minutes), 0)::BIGINT AS "regular_minutes!",
+                COALESCE(SUM(overtime_minutes), 0)::BIGINT AS "overtime_minutes!",
+                COALESCE(SUM(double_time_minutes), 0)::BIGINT AS "double_time_minutes!"
+            FROM labor_classifications
+            WHERE employee_id = $1 AND work_date BETWEEN $2 AND $3
+            "#,
+            employee_id,
+            from,
+            to
+        )
+        .fetch_one(&mut **transaction)
+        .await
+    }
+}
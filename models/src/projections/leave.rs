@@ -0,0 +1,597 @@
+#[cfg(feature = "connect")]
+use chrono::Duration;
+use chrono::{DateTime, Datelike, NaiveDate, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "connect")]
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[cfg(feature = "connect")]
+use crate::events::leave::{LeaveApproved, LeaveDenied, LeaveRequested};
+
+/// A full day of leave, the default for a request
+pub const LEAVE_DAY_MINUTES: i64 = 8 * 60;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum LeaveType {
+    Vacation,
+    Sick,
+    Personal,
+}
+
+impl LeaveType {
+    pub const ALL: [LeaveType; 3] = [LeaveType::Vacation, LeaveType::Sick, LeaveType::Personal];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LeaveType::Vacation => "vacation",
+            LeaveType::Sick => "sick",
+            LeaveType::Personal => "personal",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            LeaveType::Vacation => "Vacation",
+            LeaveType::Sick => "Sick",
+            LeaveType::Personal => "Personal",
+        }
+    }
+
+    /// Paid leave of this type every employee gets each calendar year
+    pub fn annual_allowance_minutes(&self) -> i64 {
+        match self {
+            LeaveType::Vacation => 80 * 60,
+            LeaveType::Sick => 40 * 60,
+            LeaveType::Personal => 16 * 60,
+        }
+    }
+}
+
+impl From<String> for LeaveType {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "vacation" => LeaveType::Vacation,
+            "sick" => LeaveType::Sick,
+            "personal" => LeaveType::Personal,
+            _ => panic!("Invalid LeaveType"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum LeaveStatus {
+    Requested,
+    Approved,
+    Denied,
+}
+
+impl LeaveStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LeaveStatus::Requested => "requested",
+            LeaveStatus::Approved => "approved",
+            LeaveStatus::Denied => "denied",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            LeaveStatus::Requested => "Requested",
+            LeaveStatus::Approved => "Approved",
+            LeaveStatus::Denied => "Denied",
+        }
+    }
+}
+
+impl From<String> for LeaveStatus {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "requested" => LeaveStatus::Requested,
+            "approved" => LeaveStatus::Approved,
+            "denied" => LeaveStatus::Denied,
+            _ => panic!("Invalid LeaveStatus"),
+        }
+    }
+}
+
+/// Paid time off an employee asked for. Leave is only taken on weekdays, so
+/// a request spanning a weekend doesn't use any balance for it.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Leave {
+    pub id: Uuid,
+    pub employee_id: Uuid,
+    pub employee_name: String,
+    pub leave_type: LeaveType,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub minutes_per_day: i64,
+    pub total_minutes: i64,
+    pub note: Option<String>,
+    pub status: LeaveStatus,
+    pub denial_reason: Option<String>,
+    pub requested_at: DateTime<Utc>,
+    pub reviewed_at: Option<DateTime<Utc>>,
+}
+
+impl Leave {
+    pub fn stream_name(employee_id: &Uuid) -> String {
+        format!("leave-{}", employee_id)
+    }
+
+    /// The days from `start_date` to `end_date` that leave is taken on
+    pub fn leave_dates(start_date: NaiveDate, end_date: NaiveDate) -> Vec<NaiveDate> {
+        start_date
+            .iter_days()
+            .take_while(|date| *date <= end_date)
+            .filter(|date| !matches!(date.weekday(), Weekday::Sat | Weekday::Sun))
+            .collect()
+    }
+
+    /// What a request takes from the employee's balance
+    pub fn total_minutes(start_date: NaiveDate, end_date: NaiveDate, minutes_per_day: i64) -> i64 {
+        minutes_per_day * Self::leave_dates(start_date, end_date).len() as i64
+    }
+
+    /// Whether the employee is away on `date`, weekends included
+    pub fn covers(&self, date: NaiveDate) -> bool {
+        self.start_date <= date && date <= self.end_date
+    }
+
+    /// Whether this leave stands in the way of another request or an
+    /// assignment from `start_date` to `end_date`, the same rule
+    /// [`Leave::get_overlapping`] applies. Denied leave never does.
+    pub fn overlaps(&self, start_date: NaiveDate, end_date: NaiveDate) -> bool {
+        self.status != LeaveStatus::Denied
+            && self.start_date <= end_date
+            && start_date <= self.end_date
+    }
+}
+
+/// How much of one type of leave an employee has used in a calendar year
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct LeaveBalance {
+    pub employee_id: Uuid,
+    pub leave_type: LeaveType,
+    pub year: i32,
+    /// Requested but not yet approved or denied
+    pub pending_minutes: i64,
+    pub approved_minutes: i64,
+}
+
+impl LeaveBalance {
+    /// A balance nothing has been taken from yet
+    pub fn unused(employee_id: Uuid, leave_type: LeaveType, year: i32) -> Self {
+        Self {
+            employee_id,
+            leave_type,
+            year,
+            pending_minutes: 0,
+            approved_minutes: 0,
+        }
+    }
+
+    /// Find an employee's balance in `balances`, or an unused one
+    pub fn find(
+        balances: &[LeaveBalance],
+        employee_id: Uuid,
+        leave_type: LeaveType,
+        year: i32,
+    ) -> Self {
+        balances
+            .iter()
+            .find(|balance| {
+                balance.employee_id == employee_id
+                    && balance.leave_type == leave_type
+                    && balance.year == year
+            })
+            .cloned()
+            .unwrap_or_else(|| Self::unused(employee_id, leave_type, year))
+    }
+
+    /// What's left once approved leave is taken
+    pub fn remaining_minutes(&self) -> i64 {
+        self.leave_type.annual_allowance_minutes() - self.approved_minutes
+    }
+
+    /// What can still be requested, pending requests are held against it
+    pub fn available_minutes(&self) -> i64 {
+        self.remaining_minutes() - self.pending_minutes
+    }
+}
+
+#[cfg(feature = "connect")]
+impl Leave {
+    pub async fn request(
+        transaction: &mut Transaction<'_, Postgres>,
+        requested_event: &LeaveRequested,
+    ) -> Result<Self, sqlx::Error> {
+        let leave_dates = Self::leave_dates(requested_event.start_date, requested_event.end_date);
+        let total_minutes = Self::total_minutes(
+            requested_event.start_date,
+            requested_event.end_date,
+            requested_event.minutes_per_day,
+        );
+
+        sqlx::query!(
+            r#"
+            INSERT INTO leave_requests (id, employee_id, leave_type, start_date, end_date,
+                minutes_per_day, total_minutes, note, status, requested_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            "#,
+            requested_event.id,
+            requested_event.employee_id,
+            requested_event.leave_type.as_str(),
+            requested_event.start_date,
+            requested_event.end_date,
+            requested_event.minutes_per_day,
+            total_minutes,
+            requested_event.note,
+            LeaveStatus::Requested.as_str(),
+            requested_event.requested_at
+        )
+        .execute(&mut **transaction)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO leave_days (leave_id, employee_id, leave_date, minutes)
+            SELECT $1, $2, leave_date, $4
+            FROM UNNEST($3::DATE[]) AS leave_date
+            "#,
+            requested_event.id,
+            requested_event.employee_id,
+            &leave_dates,
+            requested_event.minutes_per_day
+        )
+        .execute(&mut **transaction)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO leave_balances (employee_id, leave_type, year, pending_minutes)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (employee_id, leave_type, year) DO UPDATE
+            SET pending_minutes = leave_balances.pending_minutes + excluded.pending_minutes
+            "#,
+            requested_event.employee_id,
+            requested_event.leave_type.as_str(),
+            requested_event.start_date.year(),
+            total_minutes
+        )
+        .execute(&mut **transaction)
+        .await?;
+
+        Self::get_by_id(transaction, &requested_event.id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)
+    }
+
+    pub async fn approve(
+        transaction: &mut Transaction<'_, Postgres>,
+        approved_event: &LeaveApproved,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query!(
+            r#"
+            UPDATE leave_requests
+            SET status = $2, reviewed_at = $3
+            WHERE id = $1
+            "#,
+            approved_event.id,
+            LeaveStatus::Approved.as_str(),
+            approved_event.approved_at
+        )
+        .execute(&mut **transaction)
+        .await?;
+
+        let leave = Self::get_by_id(transaction, &approved_event.id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)?;
+
+        sqlx::query!(
+            r#"
+            UPDATE leave_balances
+            SET pending_minutes = pending_minutes - $4,
+                approved_minutes = approved_minutes + $4
+            WHERE employee_id = $1 AND leave_type = $2 AND year = $3
+            "#,
+            leave.employee_id,
+            leave.leave_type.as_str(),
+            leave.start_date.year(),
+            leave.total_minutes
+        )
+        .execute(&mut **transaction)
+        .await?;
+
+        Ok(leave)
+    }
+
+    pub async fn deny(
+        transaction: &mut Transaction<'_, Postgres>,
+        denied_event: &LeaveDenied,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query!(
+            r#"
+            UPDATE leave_requests
+            SET status = $2, denial_reason = $3, reviewed_at = $4
+            WHERE id = $1
+            "#,
+            denied_event.id,
+            LeaveStatus::Denied.as_str(),
+            denied_event.reason,
+            denied_event.denied_at
+        )
+        .execute(&mut **transaction)
+        .await?;
+
+        let leave = Self::get_by_id(transaction, &denied_event.id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)?;
+
+        sqlx::query!(
+            r#"
+            UPDATE leave_balances
+            SET pending_minutes = pending_minutes - $4
+            WHERE employee_id = $1 AND leave_type = $2 AND year = $3
+            "#,
+            leave.employee_id,
+            leave.leave_type.as_str(),
+            leave.start_date.year(),
+            leave.total_minutes
+        )
+        .execute(&mut **transaction)
+        .await?;
+
+        Ok(leave)
+    }
+
+    pub async fn get_by_id(
+        transaction: &mut Transaction<'_, Postgres>,
+        id: &Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            SELECT l.id, l.employee_id, e.name AS employee_name, l.leave_type, l.start_date,
+                l.end_date, l.minutes_per_day, l.total_minutes, l.note, l.status, l.denial_reason,
+                l.requested_at, l.reviewed_at
+            FROM leave_requests l
+            JOIN employees e ON e.id = l.employee_id
+            WHERE l.id = $1
+            "#,
+            id
+        )
+        .fetch_optional(&mut **transaction)
+        .await
+    }
+
+    /// Every leave request, most recently requested first
+    pub async fn get_list(
+        transaction: &mut Transaction<'_, Postgres>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            SELECT l.id, l.employee_id, e.name AS employee_name, l.leave_type, l.start_date,
+                l.end_date, l.minutes_per_day, l.total_minutes, l.note, l.status, l.denial_reason,
+                l.requested_at, l.reviewed_at
+            FROM leave_requests l
+            JOIN employees e ON e.id = l.employee_id
+            ORDER BY l.requested_at DESC
+            "#
+        )
+        .fetch_all(&mut **transaction)
+        .await
+    }
+
+    /// Requested or approved leave an employee takes on any day from
+    /// `start_date` to `end_date`, see [`Leave::overlaps`]
+    pub async fn get_overlapping(
+        transaction: &mut Transaction<'_, Postgres>,
+        employee_id: &Uuid,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            SELECT l.id, l.employee_id, e.name AS employee_name, l.leave_type, l.start_date,
+                l.end_date, l.minutes_per_day, l.total_minutes, l.note, l.status, l.denial_reason,
+                l.requested_at, l.reviewed_at
+            FROM leave_requests l
+            JOIN employees e ON e.id = l.employee_id
+            WHERE l.employee_id = $1
+                AND l.status != $4
+                AND l.start_date <= $3
+                AND $2 <= l.end_date
+            ORDER BY l.start_date
+            "#,
+            employee_id,
+            start_date,
+            end_date,
+            LeaveStatus::Denied.as_str()
+        )
+        .fetch_all(&mut **transaction)
+        .await
+    }
+
+    /// Approved leave anyone is away on during the week starting on
+    /// `week_start`
+    pub async fn get_approved_week(
+        transaction: &mut Transaction<'_, Postgres>,
+        week_start: NaiveDate,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            SELECT l.id, l.employee_id, e.name AS employee_name, l.leave_type, l.start_date,
+                l.end_date, l.minutes_per_day, l.total_minutes, l.note, l.status, l.denial_reason,
+                l.requested_at, l.reviewed_at
+            FROM leave_requests l
+            JOIN employees e ON e.id = l.employee_id
+            WHERE l.status = $3
+                AND l.start_date <= $2
+                AND $1 <= l.end_date
+            ORDER BY l.start_date
+            "#,
+            week_start,
+            week_start + Duration::days(6),
+            LeaveStatus::Approved.as_str()
+        )
+        .fetch_all(&mut **transaction)
+        .await
+    }
+
+    /// Approved leave minutes an employee takes from `from` to `to`, the
+    /// paid non-worked time on their timesheet
+    pub async fn get_minutes_by_employee(
+        transaction: &mut Transaction<'_, Postgres>,
+        employee_id: &Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"
+            SELECT COALESCE(SUM(d.minutes), 0)::BIGINT AS "minutes!"
+            FROM leave_days d
+            JOIN leave_requests l ON l.id = d.leave_id
+            WHERE d.employee_id = $1
+                AND d.leave_date BETWEEN $2 AND $3
+                AND l.status = $4
+            "#,
+            employee_id,
+            from,
+            to,
+            LeaveStatus::Approved.as_str()
+        )
+        .fetch_one(&mut **transaction)
+        .await
+    }
+}
+
+#[cfg(feature = "connect")]
+impl LeaveBalance {
+    pub async fn get_list(
+        transaction: &mut Transaction<'_, Postgres>,
+        year: i32,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            SELECT employee_id, leave_type, year, pending_minutes, approved_minutes
+            FROM leave_balances
+            WHERE year = $1
+            "#,
+            year
+        )
+        .fetch_all(&mut **transaction)
+        .await
+    }
+
+    pub async fn get(
+        transaction: &mut Transaction<'_, Postgres>,
+        employee_id: &Uuid,
+        leave_type: LeaveType,
+        year: i32,
+    ) -> Result<Self, sqlx::Error> {
+        Ok(sqlx::query_as!(
+            Self,
+            r#"
+            SELECT employee_id, leave_type, year, pending_minutes, approved_minutes
+            FROM leave_balances
+            WHERE employee_id = $1 AND leave_type = $2 AND year = $3
+            "#,
+            employee_id,
+            leave_type.as_str(),
+            year
+        )
+        .fetch_optional(&mut **transaction)
+        .await?
+        .unwrap_or_else(|| Self::unused(*employee_id, leave_type, year)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{NaiveDate, Utc};
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn date(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, month, day).unwrap()
+    }
+
+    fn leave(start_date: NaiveDate, end_date: NaiveDate, status: LeaveStatus) -> Leave {
+        Leave {
+            id: Uuid::new_v4(),
+            employee_id: Uuid::new_v4(),
+            employee_name: "Sam Lee".to_string(),
+            leave_type: LeaveType::Vacation,
+            start_date,
+            end_date,
+            minutes_per_day: LEAVE_DAY_MINUTES,
+            total_minutes: Leave::total_minutes(start_date, end_date, LEAVE_DAY_MINUTES),
+            note: None,
+            status,
+            denial_reason: None,
+            requested_at: Utc::now(),
+            reviewed_at: None,
+        }
+    }
+
+    #[test]
+    fn leave_is_only_taken_on_weekdays() {
+        // Thursday Jun 5 to Tuesday Jun 10
+        assert_eq!(
+            Leave::leave_dates(date(6, 5), date(6, 10)),
+            vec![date(6, 5), date(6, 6), date(6, 9), date(6, 10)]
+        );
+        assert_eq!(
+            Leave::total_minutes(date(6, 5), date(6, 10), 4 * 60),
+            16 * 60
+        );
+        assert_eq!(Leave::total_minutes(date(6, 7), date(6, 8), 8 * 60), 0);
+    }
+
+    #[test]
+    fn pending_leave_is_held_against_the_yearly_balance() {
+        let employee_id = Uuid::new_v4();
+        let balance = LeaveBalance {
+            employee_id,
+            leave_type: LeaveType::Vacation,
+            year: 2025,
+            pending_minutes: 16 * 60,
+            approved_minutes: 40 * 60,
+        };
+
+        assert_eq!(balance.remaining_minutes(), 40 * 60);
+        assert_eq!(balance.available_minutes(), 24 * 60);
+
+        // A year or type nothing was taken from has the whole allowance
+        let balances = vec![balance];
+        let next_year = LeaveBalance::find(&balances, employee_id, LeaveType::Vacation, 2026);
+        assert_eq!(next_year.available_minutes(), 80 * 60);
+        let sick = LeaveBalance::find(&balances, employee_id, LeaveType::Sick, 2025);
+        assert_eq!(sick.available_minutes(), 40 * 60);
+        let found = LeaveBalance::find(&balances, employee_id, LeaveType::Vacation, 2025);
+        assert_eq!(found.available_minutes(), 24 * 60);
+    }
+
+    #[test]
+    fn leave_overlaps_when_any_day_is_shared() {
+        let requested = leave(date(6, 9), date(6, 13), LeaveStatus::Requested);
+
+        assert!(requested.overlaps(date(6, 13), date(6, 20)));
+        assert!(requested.overlaps(date(6, 2), date(6, 9)));
+        assert!(requested.overlaps(date(6, 10), date(6, 11)));
+        assert!(!requested.overlaps(date(6, 14), date(6, 20)));
+        assert!(!requested.overlaps(date(6, 2), date(6, 8)));
+
+        let approved = leave(date(6, 9), date(6, 13), LeaveStatus::Approved);
+        assert!(approved.overlaps(date(6, 10), date(6, 10)));
+
+        let denied = leave(date(6, 9), date(6, 13), LeaveStatus::Denied);
+        assert!(!denied.overlaps(date(6, 10), date(6, 10)));
+    }
+}
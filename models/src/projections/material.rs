@@ -0,0 +1,254 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "connect")]
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[cfg(feature = "connect")]
+use crate::events::material::{MaterialAdded, MaterialConsumed};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum MaterialUnit {
+    Each,
+    LinearFoot,
+    SquareFoot,
+    CubicYard,
+    Pound,
+    Ton,
+    Gallon,
+    Bag,
+}
+
+impl MaterialUnit {
+    pub const ALL: [MaterialUnit; 8] = [
+        MaterialUnit::Each,
+        MaterialUnit::LinearFoot,
+        MaterialUnit::SquareFoot,
+        MaterialUnit::CubicYard,
+        MaterialUnit::Pound,
+        MaterialUnit::Ton,
+        MaterialUnit::Gallon,
+        MaterialUnit::Bag,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MaterialUnit::Each => "each",
+            MaterialUnit::LinearFoot => "linear_foot",
+            MaterialUnit::SquareFoot => "square_foot",
+            MaterialUnit::CubicYard => "cubic_yard",
+            MaterialUnit::Pound => "pound",
+            MaterialUnit::Ton => "ton",
+            MaterialUnit::Gallon => "gallon",
+            MaterialUnit::Bag => "bag",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            MaterialUnit::Each => "Each",
+            MaterialUnit::LinearFoot => "Linear foot",
+            MaterialUnit::SquareFoot => "Square foot",
+            MaterialUnit::CubicYard => "Cubic yard",
+            MaterialUnit::Pound => "Pound",
+            MaterialUnit::Ton => "Ton",
+            MaterialUnit::Gallon => "Gallon",
+            MaterialUnit::Bag => "Bag",
+        }
+    }
+
+    /// The short form shown after a quantity
+    pub fn abbreviation(&self) -> &'static str {
+        match self {
+            MaterialUnit::Each => "ea",
+            MaterialUnit::LinearFoot => "lf",
+            MaterialUnit::SquareFoot => "sf",
+            MaterialUnit::CubicYard => "cy",
+            MaterialUnit::Pound => "lb",
+            MaterialUnit::Ton => "ton",
+            MaterialUnit::Gallon => "gal",
+            MaterialUnit::Bag => "bag",
+        }
+    }
+}
+
+impl From<String> for MaterialUnit {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "each" => MaterialUnit::Each,
+            "linear_foot" => MaterialUnit::LinearFoot,
+            "square_foot" => MaterialUnit::SquareFoot,
+            "cubic_yard" => MaterialUnit::CubicYard,
+            "pound" => MaterialUnit::Pound,
+            "ton" => MaterialUnit::Ton,
+            "gallon" => MaterialUnit::Gallon,
+            "bag" => MaterialUnit::Bag,
+            _ => panic!("Invalid MaterialUnit"),
+        }
+    }
+}
+
+/// A material in the catalog
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Material {
+    pub id: Uuid,
+    pub name: String,
+    pub unit: MaterialUnit,
+    pub unit_cost_cents: Option<i64>,
+    pub added_at: DateTime<Utc>,
+}
+
+/// Some amount of a material used up on a jobsite
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MaterialConsumption {
+    pub id: Uuid,
+    pub material_id: Uuid,
+    pub jobsite_id: Uuid,
+    pub quantity: f64,
+    pub consumed_on: NaiveDate,
+    pub recorded_at: DateTime<Utc>,
+}
+
+/// Everything of one material a jobsite has used
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct JobsiteMaterialUsage {
+    pub material_id: Uuid,
+    pub material_name: String,
+    pub unit: MaterialUnit,
+    pub quantity: f64,
+    /// What was used at the material's current unit cost, `None` when the
+    /// material has no cost
+    pub cost_cents: Option<i64>,
+    pub last_consumed_on: NaiveDate,
+}
+
+impl Material {
+    pub fn stream_name(material_id: &Uuid) -> String {
+        format!("material-{}", material_id)
+    }
+}
+
+#[cfg(feature = "connect")]
+impl Material {
+    pub async fn add(
+        transaction: &mut Transaction<'_, Postgres>,
+        added_event: &MaterialAdded,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            INSERT INTO materials (id, name, unit, unit_cost_cents, added_at)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING *;
+            "#,
+            added_event.id,
+            added_event.name,
+            added_event.unit.as_str(),
+            added_event.unit_cost_cents,
+            added_event.added_at
+        )
+        .fetch_one(&mut **transaction)
+        .await
+    }
+
+    pub async fn get_by_id(
+        transaction: &mut Transaction<'_, Postgres>,
+        id: &Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            SELECT id, name, unit, unit_cost_cents, added_at
+            FROM materials
+            WHERE id = $1
+            "#,
+            id
+        )
+        .fetch_optional(&mut **transaction)
+        .await
+    }
+
+    pub async fn get_by_name(
+        transaction: &mut Transaction<'_, Postgres>,
+        name: &str,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            SELECT id, name, unit, unit_cost_cents, added_at
+            FROM materials
+            WHERE LOWER(name) = LOWER($1)
+            "#,
+            name
+        )
+        .fetch_optional(&mut **transaction)
+        .await
+    }
+
+    pub async fn get_list(
+        transaction: &mut Transaction<'_, Postgres>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            SELECT id, name, unit, unit_cost_cents, added_at
+            FROM materials
+            ORDER BY name
+            "#
+        )
+        .fetch_all(&mut **transaction)
+        .await
+    }
+}
+
+#[cfg(feature = "connect")]
+impl MaterialConsumption {
+    pub async fn record(
+        transaction: &mut Transaction<'_, Postgres>,
+        consumed_event: &MaterialConsumed,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            INSERT INTO material_consumptions (id, material_id, jobsite_id, quantity, consumed_on, recorded_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING *;
+            "#,
+            consumed_event.consumption_id,
+            consumed_event.id,
+            consumed_event.jobsite_id,
+            consumed_event.quantity,
+            consumed_event.consumed_on,
+            consumed_event.recorded_at
+        )
+        .fetch_one(&mut **transaction)
+        .await
+    }
+}
+
+#[cfg(feature = "connect")]
+impl JobsiteMaterialUsage {
+    /// What a jobsite has used of each material, by name
+    pub async fn get_by_jobsite(
+        transaction: &mut Transaction<'_, Postgres>,
+        jobsite_id: &Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            SELECT m.id AS material_id, m.name AS material_name, m.unit,
+                SUM(c.quantity) AS "quantity!",
+                ROUND(SUM(c.quantity) * m.unit_cost_cents)::BIGINT AS cost_cents,
+                MAX(c.consumed_on) AS "last_consumed_on!"
+            FROM material_consumptions c
+            JOIN materials m ON m.id = c.material_id
+            WHERE c.jobsite_id = $1
+            GROUP BY m.id
+            ORDER BY m.name
+            "#,
+            jobsite_id
+        )
+        .fetch_all(&mut **transaction)
+        .await
+    }
+}
@@ -1,2 +1,27 @@
+pub mod attachment;
+pub mod certification;
+pub mod change_order;
+pub mod client;
+pub mod comment;
+pub mod cost_code;
+pub mod crew_assignment;
+pub mod daily_log;
+pub mod employee;
+pub mod employee_hours;
+pub mod equipment;
+pub mod incident;
 pub mod jobsite;
+pub mod jobsite_budget;
+pub mod jobsite_labor_cost;
+pub mod kiosk;
+pub mod labor_classification;
+pub mod leave;
+pub mod material;
+pub mod pay_rate;
+pub mod process_manager_state;
+pub mod project;
+pub mod scheduled_trigger;
+pub mod shift;
 pub mod snapshot_position;
+pub mod synced_command;
+pub mod timesheet;
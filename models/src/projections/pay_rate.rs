@@ -0,0 +1,103 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "connect")]
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[cfg(feature = "connect")]
+use crate::events::pay_rate::PayRateSet;
+
+/// An hourly rate for an employee or a whole trade, optionally only at a
+/// single jobsite, from its effective date until a later rate replaces it.
+/// The most specific rate wins: employee at the jobsite, then employee,
+/// then trade at the jobsite, then trade.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PayRate {
+    pub id: Uuid,
+    pub employee_id: Option<Uuid>,
+    pub employee_name: Option<String>,
+    pub trade: Option<String>,
+    pub jobsite_id: Option<Uuid>,
+    pub jobsite_name: Option<String>,
+    pub hourly_rate_cents: i64,
+    pub effective_date: NaiveDate,
+    pub set_at: DateTime<Utc>,
+}
+
+impl PayRate {
+    /// Rates for an employee and rates for a trade are separate streams
+    pub fn stream_name(employee_id: Option<&Uuid>, trade: Option<&str>) -> String {
+        match (employee_id, trade) {
+            (Some(employee_id), _) => format!("pay_rate-employee_{}", employee_id),
+            (None, Some(trade)) => format!("pay_rate-trade_{}", trade),
+            (None, None) => String::from("pay_rate-default"),
+        }
+    }
+}
+
+#[cfg(feature = "connect")]
+impl PayRate {
+    pub async fn create(
+        transaction: &mut Transaction<'_, Postgres>,
+        set_event: &PayRateSet,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query!(
+            r#"
+            INSERT INTO pay_rates (id, employee_id, trade, jobsite_id, hourly_rate_cents, effective_date, set_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            "#,
+            set_event.id,
+            set_event.employee_id,
+            set_event.trade,
+            set_event.jobsite_id,
+            set_event.hourly_rate_cents,
+            set_event.effective_date,
+            set_event.set_at
+        )
+        .execute(&mut **transaction)
+        .await?;
+
+        Self::get_by_id(transaction, &set_event.id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)
+    }
+
+    pub async fn get_by_id(
+        transaction: &mut Transaction<'_, Postgres>,
+        id: &Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            SELECT p.id, p.employee_id, e.name AS "employee_name?", p.trade, p.jobsite_id,
+                j.name AS "jobsite_name?", p.hourly_rate_cents, p.effective_date, p.set_at
+            FROM pay_rates p
+            LEFT JOIN employees e ON e.id = p.employee_id
+            LEFT JOIN jobsites j ON j.id = p.jobsite_id
+            WHERE p.id = $1
+            "#,
+            id
+        )
+        .fetch_optional(&mut **transaction)
+        .await
+    }
+
+    /// Every rate ever set, latest effective date first
+    pub async fn get_list(
+        transaction: &mut Transaction<'_, Postgres>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            SELECT p.id, p.employee_id, e.name AS "employee_name?", p.trade, p.jobsite_id,
+                j.name AS "jobsite_name?", p.hourly_rate_cents, p.effective_date, p.set_at
+            FROM pay_rates p
+            LEFT JOIN employees e ON e.id = p.employee_id
+            LEFT JOIN jobsites j ON j.id = p.jobsite_id
+            ORDER BY p.effective_date DESC, p.set_at DESC
+            "#,
+        )
+        .fetch_all(&mut **transaction)
+        .await
+    }
+}
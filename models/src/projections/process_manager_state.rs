@@ -0,0 +1,47 @@
+#[cfg(feature = "connect")]
+use sqlx::{Postgres, Transaction};
+
+#[cfg(feature = "connect")]
+use crate::projections::snapshot_position::SnapshotPositionKey;
+
+/// A process manager's folded state, kept as JSON under the same key as its
+/// snapshot position
+pub struct ProcessManagerState;
+
+#[cfg(feature = "connect")]
+impl ProcessManagerState {
+    pub async fn get_by_key(
+        transaction: &mut Transaction<'_, Postgres>,
+        key: SnapshotPositionKey,
+    ) -> Result<Option<String>, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"
+            SELECT state::TEXT AS "state!"
+            FROM process_manager_states
+            WHERE key = $1
+            "#,
+            key.to_string()
+        )
+        .fetch_optional(&mut **transaction)
+        .await
+    }
+
+    pub async fn save(
+        transaction: &mut Transaction<'_, Postgres>,
+        key: SnapshotPositionKey,
+        state: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+            INSERT INTO process_manager_states (key, state) VALUES ($1, $2::TEXT::JSONB)
+            ON CONFLICT (key) DO UPDATE SET state = excluded.state
+            "#,
+            key.to_string(),
+            state
+        )
+        .execute(&mut **transaction)
+        .await?;
+
+        Ok(())
+    }
+}
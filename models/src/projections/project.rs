@@ -0,0 +1,95 @@
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "connect")]
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[cfg(feature = "connect")]
+use crate::events::project::ProjectCreated;
+
+/// A project for a client, jobsites are grouped under projects
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Project {
+    pub id: Uuid,
+    pub client_id: Uuid,
+    pub client_name: String,
+    pub name: String,
+}
+
+#[cfg(feature = "connect")]
+impl Project {
+    pub async fn create(
+        transaction: &mut Transaction<'_, Postgres>,
+        created_event: &ProjectCreated,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query!(
+            r#"
+            INSERT INTO projects (id, client_id, name)
+            VALUES ($1, $2, $3)
+            "#,
+            created_event.id,
+            created_event.client_id,
+            created_event.name
+        )
+        .execute(&mut **transaction)
+        .await?;
+
+        Self::get_by_id(transaction, &created_event.id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)
+    }
+
+    pub async fn get_by_id(
+        transaction: &mut Transaction<'_, Postgres>,
+        id: &Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            SELECT p.id, p.client_id, c.name AS client_name, p.name
+            FROM projects p
+            JOIN clients c ON c.id = p.client_id
+            WHERE p.id = $1
+            "#,
+            id
+        )
+        .fetch_optional(&mut **transaction)
+        .await
+    }
+
+    pub async fn get_by_name(
+        transaction: &mut Transaction<'_, Postgres>,
+        client_id: &Uuid,
+        name: &str,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            SELECT p.id, p.client_id, c.name AS client_name, p.name
+            FROM projects p
+            JOIN clients c ON c.id = p.client_id
+            WHERE p.client_id = $1 AND p.name = $2
+            "#,
+            client_id,
+            name
+        )
+        .fetch_optional(&mut **transaction)
+        .await
+    }
+
+    /// Every project, grouped by client
+    pub async fn get_list(
+        transaction: &mut Transaction<'_, Postgres>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            SELECT p.id, p.client_id, c.name AS client_name, p.name
+            FROM projects p
+            JOIN clients c ON c.id = p.client_id
+            ORDER BY c.name, p.name
+            "#,
+        )
+        .fetch_all(&mut **transaction)
+        .await
+    }
+}
@@ -0,0 +1,129 @@
+use chrono::{DateTime, Utc};
+#[cfg(feature = "connect")]
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[cfg(feature = "connect")]
+use crate::scheduler::Trigger;
+
+/// A trigger waiting to come due, the trigger itself is kept as JSON
+#[derive(Debug, Clone)]
+pub struct ScheduledTrigger {
+    pub id: Uuid,
+    pub key: String,
+    pub trigger: String,
+    pub due_at: DateTime<Utc>,
+    /// The chain of events that scheduled the trigger
+    pub correlation_id: Uuid,
+    pub fired_at: Option<DateTime<Utc>>,
+}
+
+#[cfg(feature = "connect")]
+impl ScheduledTrigger {
+    /// Schedule a trigger, or move one that hasn't fired yet. Triggers that
+    /// already fired stay fired.
+    pub async fn schedule(
+        transaction: &mut Transaction<'_, Postgres>,
+        trigger: &Trigger,
+        due_at: DateTime<Utc>,
+        correlation_id: Uuid,
+    ) -> anyhow::Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO scheduled_triggers (id, key, trigger, due_at, correlation_id)
+            VALUES ($1, $2, $3::TEXT::JSONB, $4, $5)
+            ON CONFLICT (key) DO UPDATE SET trigger = excluded.trigger, due_at = excluded.due_at
+            WHERE scheduled_triggers.fired_at IS NULL
+            "#,
+            Uuid::new_v4(),
+            trigger.key(),
+            serde_json::to_string(trigger)?,
+            due_at,
+            correlation_id
+        )
+        .execute(&mut **transaction)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Drop a trigger that hasn't fired yet
+    pub async fn cancel(
+        transaction: &mut Transaction<'_, Postgres>,
+        trigger: &Trigger,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+            DELETE FROM scheduled_triggers
+            WHERE key = $1 AND fired_at IS NULL
+            "#,
+            trigger.key()
+        )
+        .execute(&mut **transaction)
+        .await?;
+
+        Ok(())
+    }
+
+    /// The earliest trigger due by `now`, locked until the transaction ends
+    /// so that only one scheduler fires it
+    pub async fn get_next_due(
+        transaction: &mut Transaction<'_, Postgres>,
+        now: DateTime<Utc>,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            SELECT id, key, trigger::TEXT AS "trigger!", due_at, correlation_id, fired_at
+            FROM scheduled_triggers
+            WHERE fired_at IS NULL AND due_at <= $1
+            ORDER BY due_at
+            LIMIT 1
+            FOR UPDATE SKIP LOCKED
+            "#,
+            now
+        )
+        .fetch_optional(&mut **transaction)
+        .await
+    }
+
+    pub async fn mark_fired(
+        transaction: &mut Transaction<'_, Postgres>,
+        id: &Uuid,
+        fired_at: DateTime<Utc>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+            UPDATE scheduled_triggers
+            SET fired_at = $2
+            WHERE id = $1
+            "#,
+            id,
+            fired_at
+        )
+        .execute(&mut **transaction)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn postpone(
+        transaction: &mut Transaction<'_, Postgres>,
+        id: &Uuid,
+        due_at: DateTime<Utc>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+            UPDATE scheduled_triggers
+            SET due_at = $2
+            WHERE id = $1
+            "#,
+            id,
+            due_at
+        )
+        .execute(&mut **transaction)
+        .await?;
+
+        Ok(())
+    }
+}
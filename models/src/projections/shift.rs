@@ -0,0 +1,212 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "connect")]
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[cfg(feature = "connect")]
+use crate::events::shift::{ClockedIn, ClockedOut, ShiftCorrected};
+use crate::{
+    geofence::GeofenceStatus,
+    local_time::{local_date, time_zone},
+};
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Shift {
+    pub id: Uuid,
+    pub employee_id: Uuid,
+    pub jobsite_id: Uuid,
+    pub cost_code_id: Option<Uuid>,
+    pub clocked_in_at: DateTime<Utc>,
+    pub clocked_out_at: Option<DateTime<Utc>>,
+    /// The zone the shift's days are counted in
+    pub time_zone: String,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    /// Clock-ins outside the jobsite's geofence are flagged for review
+    pub geofence_status: GeofenceStatus,
+    pub geofence_distance_meters: Option<f64>,
+}
+
+impl Shift {
+    pub fn is_open(&self) -> bool {
+        self.clocked_out_at.is_none()
+    }
+
+    pub fn is_flagged(&self) -> bool {
+        self.geofence_status == GeofenceStatus::Outside
+    }
+
+    pub fn tz(&self) -> Tz {
+        time_zone(&self.time_zone)
+    }
+
+    /// The day the shift's hours are worked on, the local day it started
+    pub fn work_date(&self) -> NaiveDate {
+        local_date(self.clocked_in_at, &self.tz())
+    }
+}
+
+/// An open shift joined with the employee working it, used for the
+/// "currently on site" view of a jobsite
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct OnSiteEmployee {
+    pub shift_id: Uuid,
+    pub employee_id: Uuid,
+    pub employee_name: String,
+    pub clocked_in_at: DateTime<Utc>,
+    pub time_zone: String,
+}
+
+#[cfg(feature = "connect")]
+impl Shift {
+    pub async fn clock_in(
+        transaction: &mut Transaction<'_, Postgres>,
+        clocked_in_event: &ClockedIn,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            INSERT INTO shifts (id, employee_id, jobsite_id, cost_code_id, clocked_in_at, time_zone,
+                latitude, longitude, geofence_status, geofence_distance_meters)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            RETURNING id, employee_id, jobsite_id, cost_code_id, clocked_in_at, clocked_out_at, time_zone,
+                latitude, longitude, geofence_status, geofence_distance_meters;
+            "#,
+            clocked_in_event.shift_id,
+            clocked_in_event.employee_id,
+            clocked_in_event.jobsite_id,
+            clocked_in_event.cost_code_id,
+            clocked_in_event.clocked_in_at,
+            clocked_in_event.time_zone,
+            clocked_in_event.coordinates.map(|coordinates| coordinates.latitude),
+            clocked_in_event.coordinates.map(|coordinates| coordinates.longitude),
+            clocked_in_event.geofence.status.as_str(),
+            clocked_in_event.geofence.distance_meters
+        )
+        .fetch_one(&mut **transaction)
+        .await
+    }
+
+    pub async fn clock_out(
+        transaction: &mut Transaction<'_, Postgres>,
+        clocked_out_event: &ClockedOut,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            UPDATE shifts
+            SET clocked_out_at = $2
+            WHERE id = $1
+            RETURNING id, employee_id, jobsite_id, cost_code_id, clocked_in_at, clocked_out_at, time_zone,
+                latitude, longitude, geofence_status, geofence_distance_meters;
+            "#,
+            clocked_out_event.shift_id,
+            clocked_out_event.clocked_out_at
+        )
+        .fetch_one(&mut **transaction)
+        .await
+    }
+
+    pub async fn correct(
+        transaction: &mut Transaction<'_, Postgres>,
+        corrected_event: &ShiftCorrected,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            UPDATE shifts
+            SET clocked_in_at = $2, clocked_out_at = $3
+            WHERE id = $1
+            RETURNING id, employee_id, jobsite_id, cost_code_id, clocked_in_at, clocked_out_at, time_zone,
+                latitude, longitude, geofence_status, geofence_distance_meters;
+            "#,
+            corrected_event.shift_id,
+            corrected_event.clocked_in_at,
+            corrected_event.clocked_out_at
+        )
+        .fetch_one(&mut **transaction)
+        .await
+    }
+
+    pub async fn get_by_id(
+        transaction: &mut Transaction<'_, Postgres>,
+        id: &Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            SELECT id, employee_id, jobsite_id, cost_code_id, clocked_in_at, clocked_out_at, time_zone,
+                latitude, longitude, geofence_status, geofence_distance_meters
+            FROM shifts
+            WHERE id = $1
+            "#,
+            id
+        )
+        .fetch_optional(&mut **transaction)
+        .await
+    }
+
+    pub async fn get_open_by_employee(
+        transaction: &mut Transaction<'_, Postgres>,
+        employee_id: &Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            SELECT id, employee_id, jobsite_id, cost_code_id, clocked_in_at, clocked_out_at, time_zone,
+                latitude, longitude, geofence_status, geofence_distance_meters
+            FROM shifts
+            WHERE employee_id = $1 AND clocked_out_at IS NULL
+            "#,
+            employee_id
+        )
+        .fetch_optional(&mut **transaction)
+        .await
+    }
+
+    /// Shifts an employee started between two dates, inclusive
+    pub async fn get_list_by_employee(
+        transaction: &mut Transaction<'_, Postgres>,
+        employee_id: &Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            SELECT id, employee_id, jobsite_id, cost_code_id, clocked_in_at, clocked_out_at, time_zone,
+                latitude, longitude, geofence_status, geofence_distance_meters
+            FROM shifts
+            WHERE employee_id = $1 AND (clocked_in_at AT TIME ZONE time_zone)::DATE BETWEEN $2 AND $3
+            ORDER BY clocked_in_at
+            "#,
+            employee_id,
+            from,
+            to
+        )
+        .fetch_all(&mut **transaction)
+        .await
+    }
+
+    pub async fn get_on_site(
+        transaction: &mut Transaction<'_, Postgres>,
+        jobsite_id: &Uuid,
+    ) -> Result<Vec<OnSiteEmployee>, sqlx::Error> {
+        sqlx::query_as!(
+            OnSiteEmployee,
+            r#"
+            SELECT s.id AS shift_id, s.employee_id, e.name AS employee_name, s.clocked_in_at,
+                s.time_zone
+            FROM shifts s
+            JOIN employees e ON e.id = s.employee_id
+            WHERE s.jobsite_id = $1 AND s.clocked_out_at IS NULL
+            ORDER BY s.clocked_in_at
+            "#,
+            jobsite_id
+        )
+        .fetch_all(&mut **transaction)
+        .await
+    }
+}
@@ -11,12 +11,52 @@ pub struct SnapshotPosition {
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub enum SnapshotPositionKey {
     Jobsite,
+    Employee,
+    Shift,
+    Timesheet,
+    PayRate,
+    JobsiteBudget,
+    CostCode,
+    Client,
+    Project,
+    CrewAssignment,
+    DailyLog,
+    Attachment,
+    Incident,
+    Certification,
+    Leave,
+    Equipment,
+    Material,
+    ChangeOrder,
+    Comment,
+    JobsiteArchival,
+    Deadlines,
 }
 
 impl SnapshotPositionKey {
     pub fn to_string(&self) -> String {
         match self {
             SnapshotPositionKey::Jobsite => "jobsite".to_string(),
+            SnapshotPositionKey::Employee => "employee".to_string(),
+            SnapshotPositionKey::Shift => "shift".to_string(),
+            SnapshotPositionKey::Timesheet => "timesheet".to_string(),
+            SnapshotPositionKey::PayRate => "pay_rate".to_string(),
+            SnapshotPositionKey::JobsiteBudget => "jobsite_budget".to_string(),
+            SnapshotPositionKey::CostCode => "cost_code".to_string(),
+            SnapshotPositionKey::Client => "client".to_string(),
+            SnapshotPositionKey::Project => "project".to_string(),
+            SnapshotPositionKey::CrewAssignment => "crew_assignment".to_string(),
+            SnapshotPositionKey::DailyLog => "daily_log".to_string(),
+            SnapshotPositionKey::Attachment => "attachment".to_string(),
+            SnapshotPositionKey::Incident => "incident".to_string(),
+            SnapshotPositionKey::Certification => "certification".to_string(),
+            SnapshotPositionKey::Leave => "leave".to_string(),
+            SnapshotPositionKey::Equipment => "equipment".to_string(),
+            SnapshotPositionKey::Material => "material".to_string(),
+            SnapshotPositionKey::ChangeOrder => "change_order".to_string(),
+            SnapshotPositionKey::Comment => "comment".to_string(),
+            SnapshotPositionKey::JobsiteArchival => "jobsite_archival".to_string(),
+            SnapshotPositionKey::Deadlines => "deadlines".to_string(),
         }
     }
 }
@@ -25,6 +65,26 @@ impl Into<SnapshotPositionKey> for String {
     fn into(self) -> SnapshotPositionKey {
         match self.as_str() {
             "jobsite" => SnapshotPositionKey::Jobsite,
+            "employee" => SnapshotPositionKey::Employee,
+            "shift" => SnapshotPositionKey::Shift,
+            "timesheet" => SnapshotPositionKey::Timesheet,
+            "pay_rate" => SnapshotPositionKey::PayRate,
+            "jobsite_budget" => SnapshotPositionKey::JobsiteBudget,
+            "cost_code" => SnapshotPositionKey::CostCode,
+            "client" => SnapshotPositionKey::Client,
+            "project" => SnapshotPositionKey::Project,
+            "crew_assignment" => SnapshotPositionKey::CrewAssignment,
+            "daily_log" => SnapshotPositionKey::DailyLog,
+            "attachment" => SnapshotPositionKey::Attachment,
+            "incident" => SnapshotPositionKey::Incident,
+            "certification" => SnapshotPositionKey::Certification,
+            "leave" => SnapshotPositionKey::Leave,
+            "equipment" => SnapshotPositionKey::Equipment,
+            "material" => SnapshotPositionKey::Material,
+            "change_order" => SnapshotPositionKey::ChangeOrder,
+            "comment" => SnapshotPositionKey::Comment,
+            "jobsite_archival" => SnapshotPositionKey::JobsiteArchival,
+            "deadlines" => SnapshotPositionKey::Deadlines,
             _ => panic!("Invalid SnapshotPositionKey"),
         }
     }
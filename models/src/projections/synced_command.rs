@@ -0,0 +1,59 @@
+use chrono::{DateTime, Utc};
+#[cfg(feature = "connect")]
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+/// A command a field device queued offline that has since been applied
+#[derive(Debug, Clone)]
+pub struct SyncedCommand {
+    pub idempotency_key: Uuid,
+    pub command_type: String,
+    pub client_timestamp: DateTime<Utc>,
+    pub synced_at: DateTime<Utc>,
+}
+
+#[cfg(feature = "connect")]
+impl SyncedCommand {
+    /// Note the command as applied. These aren't events, the command's own
+    /// event is stored under the same key.
+    pub async fn record(
+        transaction: &mut Transaction<'_, Postgres>,
+        idempotency_key: &Uuid,
+        command_type: &str,
+        client_timestamp: DateTime<Utc>,
+        synced_at: DateTime<Utc>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+            INSERT INTO synced_commands (idempotency_key, command_type, client_timestamp, synced_at)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (idempotency_key) DO NOTHING
+            "#,
+            idempotency_key,
+            command_type,
+            client_timestamp,
+            synced_at
+        )
+        .execute(&mut **transaction)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn get(
+        transaction: &mut Transaction<'_, Postgres>,
+        idempotency_key: &Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            SELECT idempotency_key, command_type, client_timestamp, synced_at
+            FROM synced_commands
+            WHERE idempotency_key = $1
+            "#,
+            idempotency_key
+        )
+        .fetch_optional(&mut **transaction)
+        .await
+    }
+}
@@ -0,0 +1,313 @@
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "connect")]
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+#[cfg(feature = "connect")]
+use crate::{
+    events::timesheet::{
+        TimesheetApproved, TimesheetRejected, TimesheetReopened, TimesheetSubmitted,
+    },
+    geofence::GeofenceStatus,
+    projections::leave::LeaveStatus,
+};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum TimesheetStatus {
+    Open,
+    Submitted,
+    Approved,
+    Rejected,
+}
+
+impl TimesheetStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TimesheetStatus::Open => "open",
+            TimesheetStatus::Submitted => "submitted",
+            TimesheetStatus::Approved => "approved",
+            TimesheetStatus::Rejected => "rejected",
+        }
+    }
+
+    /// Hours can only be submitted while the week is still being worked on
+    pub fn can_submit(&self) -> bool {
+        matches!(self, TimesheetStatus::Open | TimesheetStatus::Rejected)
+    }
+
+    /// Approved weeks lock their hours against any further corrections
+    pub fn is_locked(&self) -> bool {
+        matches!(self, TimesheetStatus::Approved)
+    }
+}
+
+impl From<String> for TimesheetStatus {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "open" => TimesheetStatus::Open,
+            "submitted" => TimesheetStatus::Submitted,
+            "approved" => TimesheetStatus::Approved,
+            "rejected" => TimesheetStatus::Rejected,
+            _ => panic!("Invalid TimesheetStatus"),
+        }
+    }
+}
+
+/// A weekly timesheet for an employee, weeks start on Monday
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Timesheet {
+    pub employee_id: Uuid,
+    pub week_start: NaiveDate,
+    pub status: TimesheetStatus,
+    pub rejection_reason: Option<String>,
+    pub submitted_at: Option<DateTime<Utc>>,
+    pub reviewed_at: Option<DateTime<Utc>>,
+}
+
+/// A submitted timesheet waiting on supervisor review
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TimesheetReview {
+    pub employee_id: Uuid,
+    pub employee_name: String,
+    pub week_start: NaiveDate,
+    pub submitted_at: Option<DateTime<Utc>>,
+    pub minutes_worked: i64,
+    /// Approved paid leave taken during the week, not part of
+    /// `minutes_worked`
+    pub leave_minutes: i64,
+    /// Clock-ins during the week made outside the jobsite's geofence
+    pub flagged_punches: i64,
+}
+
+impl Timesheet {
+    /// The Monday starting the week that contains `date`
+    pub fn week_start_of(date: NaiveDate) -> NaiveDate {
+        date - Duration::days(date.weekday().num_days_from_monday() as i64)
+    }
+
+    /// The Sunday ending the week that starts on `week_start`
+    pub fn week_end_of(week_start: NaiveDate) -> NaiveDate {
+        week_start + Duration::days(6)
+    }
+
+    pub fn stream_name(employee_id: &Uuid, week_start: &NaiveDate) -> String {
+        format!("timesheet-{}_{}", employee_id, week_start)
+    }
+}
+
+#[cfg(feature = "connect")]
+impl Timesheet {
+    pub async fn submit(
+        transaction: &mut Transaction<'_, Postgres>,
+        submitted_event: &TimesheetSubmitted,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            INSERT INTO timesheets (employee_id, week_start, status, submitted_at)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (employee_id, week_start) DO UPDATE
+            SET status = excluded.status,
+                submitted_at = excluded.submitted_at,
+                rejection_reason = NULL,
+                reviewed_at = NULL
+            RETURNING *;
+            "#,
+            submitted_event.employee_id,
+            submitted_event.week_start,
+            TimesheetStatus::Submitted.as_str(),
+            submitted_event.submitted_at
+        )
+        .fetch_one(&mut **transaction)
+        .await
+    }
+
+    pub async fn approve(
+        transaction: &mut Transaction<'_, Postgres>,
+        approved_event: &TimesheetApproved,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            UPDATE timesheets
+            SET status = $3, reviewed_at = $4
+            WHERE employee_id = $1 AND week_start = $2
+            RETURNING *;
+            "#,
+            approved_event.employee_id,
+            approved_event.week_start,
+            TimesheetStatus::Approved.as_str(),
+            approved_event.approved_at
+        )
+        .fetch_one(&mut **transaction)
+        .await
+    }
+
+    pub async fn reject(
+        transaction: &mut Transaction<'_, Postgres>,
+        rejected_event: &TimesheetRejected,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            UPDATE timesheets
+            SET status = $3, rejection_reason = $4, reviewed_at = $5
+            WHERE employee_id = $1 AND week_start = $2
+            RETURNING *;
+            "#,
+            rejected_event.employee_id,
+            rejected_event.week_start,
+            TimesheetStatus::Rejected.as_str(),
+            rejected_event.reason,
+            rejected_event.rejected_at
+        )
+        .fetch_one(&mut **transaction)
+        .await
+    }
+
+    pub async fn reopen(
+        transaction: &mut Transaction<'_, Postgres>,
+        reopened_event: &TimesheetReopened,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            UPDATE timesheets
+            SET status = $3, rejection_reason = NULL
+            WHERE employee_id = $1 AND week_start = $2
+            RETURNING *;
+            "#,
+            reopened_event.employee_id,
+            reopened_event.week_start,
+            TimesheetStatus::Open.as_str()
+        )
+        .fetch_one(&mut **transaction)
+        .await
+    }
+
+    pub async fn get(
+        transaction: &mut Transaction<'_, Postgres>,
+        employee_id: &Uuid,
+        week_start: &NaiveDate,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Self,
+            r#"
+            SELECT employee_id, week_start, status, rejection_reason, submitted_at, reviewed_at
+            FROM timesheets
+            WHERE employee_id = $1 AND week_start = $2
+            "#,
+            employee_id,
+            week_start
+        )
+        .fetch_optional(&mut **transaction)
+        .await
+    }
+
+    /// The status of a week, weeks without a timesheet yet are open
+    pub async fn get_status(
+        transaction: &mut Transaction<'_, Postgres>,
+        employee_id: &Uuid,
+        week_start: &NaiveDate,
+    ) -> Result<TimesheetStatus, sqlx::Error> {
+        Ok(Self::get(transaction, employee_id, week_start)
+            .await?
+            .map(|timesheet| timesheet.status)
+            .unwrap_or(TimesheetStatus::Open))
+    }
+
+    /// Whether hours worked on `date` are locked by an approved timesheet
+    pub async fn is_locked(
+        transaction: &mut Transaction<'_, Postgres>,
+        employee_id: &Uuid,
+        date: NaiveDate,
+    ) -> Result<bool, sqlx::Error> {
+        Ok(
+            Self::get_status(transaction, employee_id, &Self::week_start_of(date))
+                .await?
+                .is_locked(),
+        )
+    }
+
+    pub async fn get_review(
+        transaction: &mut Transaction<'_, Postgres>,
+        employee_id: &Uuid,
+        week_start: &NaiveDate,
+    ) -> Result<Option<TimesheetReview>, sqlx::Error> {
+        sqlx::query_as!(
+            TimesheetReview,
+            r#"
+            SELECT t.employee_id, e.name AS employee_name, t.week_start, t.submitted_at,
+                COALESCE((
+                    SELECT SUM(h.minutes_worked) FROM employee_hours h
+                    WHERE h.employee_id = t.employee_id
+                        AND h.work_date BETWEEN t.week_start AND t.week_start + 6
+                ), 0)::BIGINT AS "minutes_worked!",
+                COALESCE((
+                    SELECT SUM(d.minutes) FROM leave_days d
+                    JOIN leave_requests l ON l.id = d.leave_id
+                    WHERE d.employee_id = t.employee_id
+                        AND d.leave_date BETWEEN t.week_start AND t.week_start + 6
+                        AND l.status = $3
+                ), 0)::BIGINT AS "leave_minutes!",
+                (
+                    SELECT COUNT(*) FROM shifts s
+                    WHERE s.employee_id = t.employee_id
+                        AND (s.clocked_in_at AT TIME ZONE s.time_zone)::DATE
+                            BETWEEN t.week_start AND t.week_start + 6
+                        AND s.geofence_status = $4
+                ) AS "flagged_punches!"
+            FROM timesheets t
+            JOIN employees e ON e.id = t.employee_id
+            WHERE t.employee_id = $1 AND t.week_start = $2
+            "#,
+            employee_id,
+            week_start,
+            LeaveStatus::Approved.as_str(),
+            GeofenceStatus::Outside.as_str()
+        )
+        .fetch_optional(&mut **transaction)
+        .await
+    }
+
+    /// Submitted timesheets, oldest submission first
+    pub async fn get_review_queue(
+        transaction: &mut Transaction<'_, Postgres>,
+    ) -> Result<Vec<TimesheetReview>, sqlx::Error> {
+        sqlx::query_as!(
+            TimesheetReview,
+            r#"
+            SELECT t.employee_id, e.name AS employee_name, t.week_start, t.submitted_at,
+                COALESCE((
+                    SELECT SUM(h.minutes_worked) FROM employee_hours h
+                    WHERE h.employee_id = t.employee_id
+                        AND h.work_date BETWEEN t.week_start AND t.week_start + 6
+                ), 0)::BIGINT AS "minutes_worked!",
+                COALESCE((
+                    SELECT SUM(d.minutes) FROM leave_days d
+                    JOIN leave_requests l ON l.id = d.leave_id
+                    WHERE d.employee_id = t.employee_id
+                        AND d.leave_date BETWEEN t.week_start AND t.week_start + 6
+                        AND l.status = $2
+                ), 0)::BIGINT AS "leave_minutes!",
+                (
+                    SELECT COUNT(*) FROM shifts s
+                    WHERE s.employee_id = t.employee_id
+                        AND (s.clocked_in_at AT TIME ZONE s.time_zone)::DATE
+                            BETWEEN t.week_start AND t.week_start + 6
+                        AND s.geofence_status = $3
+                ) AS "flagged_punches!"
+            FROM timesheets t
+            JOIN employees e ON e.id = t.employee_id
+            WHERE t.status = $1
+            ORDER BY t.submitted_at
+            "#,
+            TimesheetStatus::Submitted.as_str(),
+            LeaveStatus::Approved.as_str(),
+            GeofenceStatus::Outside.as_str()
+        )
+        .fetch_all(&mut **transaction)
+        .await
+    }
+}
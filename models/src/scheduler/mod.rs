@@ -0,0 +1,472 @@
+use std::sync::Mutex;
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::commands::Command;
+#[cfg(feature = "connect")]
+use crate::{
+    commands::CommandMetadata,
+    projections::{
+        certification::Certification,
+        scheduled_trigger::ScheduledTrigger,
+        shift::Shift,
+        timesheet::{Timesheet, TimesheetStatus},
+    },
+};
+
+/// How long a trigger that can't fire yet waits before it is tried again
+pub const RETRY_AFTER: Duration = Duration::minutes(15);
+
+/// Where the scheduler gets the time from, tests move a fake one by hand
+pub trait Clock: Send + Sync {
+    fn now(&self) -> DateTime<Utc>;
+}
+
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// A clock that only moves when told to
+pub struct FakeClock {
+    now: Mutex<DateTime<Utc>>,
+}
+
+impl FakeClock {
+    pub fn new(now: DateTime<Utc>) -> Self {
+        Self {
+            now: Mutex::new(now),
+        }
+    }
+
+    pub fn advance(&self, by: Duration) {
+        *self.now.lock().unwrap() += by;
+    }
+}
+
+impl Clock for FakeClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.now.lock().unwrap()
+    }
+}
+
+/// A clock shared with the scheduler, so a test can still move it
+impl<C: Clock + ?Sized> Clock for std::sync::Arc<C> {
+    fn now(&self) -> DateTime<Utc> {
+        (**self).now()
+    }
+}
+
+/// Something that should happen at a set time. Each trigger has a key, so
+/// scheduling the same one twice moves it rather than firing it twice.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum Trigger {
+    /// Clock out a shift nobody remembered to end
+    AutoClockOut { shift_id: Uuid, employee_id: Uuid },
+    /// Submit a week's timesheet nobody submitted by hand
+    AutoSubmitTimesheet {
+        employee_id: Uuid,
+        week_start: NaiveDate,
+    },
+    WarnCertificationExpiry {
+        certification_id: Uuid,
+        employee_id: Uuid,
+        expires_on: NaiveDate,
+    },
+    ExpireCertification {
+        certification_id: Uuid,
+        employee_id: Uuid,
+        expires_on: NaiveDate,
+    },
+}
+
+impl Trigger {
+    pub fn key(&self) -> String {
+        match self {
+            Trigger::AutoClockOut { shift_id, .. } => format!("auto_clock_out-{}", shift_id),
+            Trigger::AutoSubmitTimesheet {
+                employee_id,
+                week_start,
+            } => format!("auto_submit_timesheet-{}_{}", employee_id, week_start),
+            Trigger::WarnCertificationExpiry {
+                certification_id, ..
+            } => format!("warn_certification_expiry-{}", certification_id),
+            Trigger::ExpireCertification {
+                certification_id, ..
+            } => format!("expire_certification-{}", certification_id),
+        }
+    }
+
+    /// The command issued when the trigger fires. It is dated when the
+    /// trigger came due, not when the scheduler got around to it.
+    pub fn command(&self, due_at: DateTime<Utc>) -> Command {
+        match self {
+            Trigger::AutoClockOut {
+                shift_id,
+                employee_id,
+            } => Command::ClockOut {
+                shift_id: *shift_id,
+                employee_id: *employee_id,
+                clocked_out_at: due_at,
+            },
+            Trigger::AutoSubmitTimesheet {
+                employee_id,
+                week_start,
+            } => Command::SubmitTimesheet {
+                employee_id: *employee_id,
+                week_start: *week_start,
+                submitted_at: due_at,
+            },
+            Trigger::WarnCertificationExpiry {
+                certification_id,
+                employee_id,
+                expires_on,
+            } => Command::WarnCertificationExpiry {
+                certification_id: *certification_id,
+                employee_id: *employee_id,
+                expires_on: *expires_on,
+                warned_at: due_at,
+            },
+            Trigger::ExpireCertification {
+                certification_id,
+                employee_id,
+                expires_on,
+            } => Command::ExpireCertification {
+                certification_id: *certification_id,
+                employee_id: *employee_id,
+                expired_on: *expires_on,
+                expired_at: due_at,
+            },
+        }
+    }
+}
+
+/// What to do with a trigger that has come due
+#[cfg(feature = "connect")]
+enum Readiness {
+    Fire,
+    /// Try again after `RETRY_AFTER`
+    Wait,
+    /// The read models show the command is no longer needed
+    Moot,
+}
+
+#[cfg(feature = "connect")]
+impl Trigger {
+    /// Check the trigger against the read models before firing it, they may
+    /// have moved on since it was scheduled
+    async fn readiness(
+        &self,
+        transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    ) -> Result<Readiness, sqlx::Error> {
+        let readiness = match self {
+            Trigger::AutoClockOut { shift_id, .. } => {
+                match Shift::get_by_id(transaction, shift_id).await? {
+                    Some(shift) if shift.is_open() => Readiness::Fire,
+                    _ => Readiness::Moot,
+                }
+            }
+            Trigger::AutoSubmitTimesheet {
+                employee_id,
+                week_start,
+            } => {
+                if Timesheet::get_status(transaction, employee_id, week_start).await?
+                    != TimesheetStatus::Open
+                {
+                    Readiness::Moot
+                } else if Shift::get_open_by_employee(transaction, employee_id)
+                    .await?
+                    .is_some_and(|shift| Timesheet::week_start_of(shift.work_date()) == *week_start)
+                {
+                    // Still on the clock, wait for the shift to be clocked out
+                    Readiness::Wait
+                } else {
+                    Readiness::Fire
+                }
+            }
+            Trigger::WarnCertificationExpiry {
+                certification_id, ..
+            } => match Certification::get_by_id(transaction, certification_id).await? {
+                Some(certification)
+                    if certification.warned_at.is_none() && certification.expired_at.is_none() =>
+                {
+                    if Certification::is_renewed(transaction, &certification).await? {
+                        Readiness::Moot
+                    } else {
+                        Readiness::Fire
+                    }
+                }
+                _ => Readiness::Moot,
+            },
+            Trigger::ExpireCertification {
+                certification_id, ..
+            } => match Certification::get_by_id(transaction, certification_id).await? {
+                Some(certification) if certification.expired_at.is_none() => Readiness::Fire,
+                _ => Readiness::Moot,
+            },
+        };
+
+        Ok(readiness)
+    }
+}
+
+/**
+ * Scheduler
+ * Fires scheduled triggers once they come due. The command a trigger issues
+ * is recorded with the trigger's id as its event id, so firing it again
+ * after a restart is dropped by the event store.
+ */
+#[cfg(feature = "connect")]
+pub struct Scheduler<C> {
+    eventstore: std::sync::Arc<eventstore::Client>,
+    db_pool: std::sync::Arc<sqlx::PgPool>,
+    clock: C,
+}
+
+#[cfg(feature = "connect")]
+impl<C: Clock> Scheduler<C> {
+    pub fn new(
+        eventstore: std::sync::Arc<eventstore::Client>,
+        db_pool: std::sync::Arc<sqlx::PgPool>,
+        clock: C,
+    ) -> Self {
+        Self {
+            eventstore,
+            db_pool,
+            clock,
+        }
+    }
+
+    /**
+     * Fire every trigger that is due by now, returning how many were
+     * handled
+     */
+    pub async fn fire_due(&self) -> anyhow::Result<usize> {
+        let mut handled = 0;
+
+        while self.fire_next().await? {
+            handled += 1;
+        }
+
+        Ok(handled)
+    }
+
+    /**
+     * Fire the earliest due trigger and mark it fired in the same
+     * transaction that locked it
+     */
+    async fn fire_next(&self) -> anyhow::Result<bool> {
+        let now = self.clock.now();
+
+        let mut transaction = self.db_pool.begin().await?;
+
+        let Some(scheduled) = ScheduledTrigger::get_next_due(&mut transaction, now).await? else {
+            return Ok(false);
+        };
+
+        let trigger: Trigger = serde_json::from_str(&scheduled.trigger)?;
+
+        match trigger.readiness(&mut transaction).await? {
+            Readiness::Fire => {
+                let metadata = CommandMetadata {
+                    causation_id: scheduled.id,
+                    correlation_id: scheduled.correlation_id,
+                };
+
+                trigger
+                    .command(scheduled.due_at)
+                    .execute(&self.eventstore, &mut transaction, &metadata, scheduled.id)
+                    .await?;
+
+                ScheduledTrigger::mark_fired(&mut transaction, &scheduled.id, now).await?;
+            }
+            Readiness::Wait => {
+                ScheduledTrigger::postpone(&mut transaction, &scheduled.id, now + RETRY_AFTER)
+                    .await?;
+            }
+            Readiness::Moot => {
+                ScheduledTrigger::mark_fired(&mut transaction, &scheduled.id, now).await?;
+            }
+        }
+
+        transaction.commit().await?;
+
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, NaiveDate, TimeZone, Utc};
+    use uuid::Uuid;
+
+    use super::{Clock, FakeClock, Trigger};
+    use crate::commands::Command;
+
+    #[test]
+    fn fired_commands_are_dated_when_the_trigger_came_due() {
+        let due_at = Utc.with_ymd_and_hms(2025, 2, 4, 0, 0, 0).unwrap();
+        let clock = FakeClock::new(due_at - Duration::minutes(1));
+        let (shift_id, employee_id) = (Uuid::new_v4(), Uuid::new_v4());
+
+        assert!(clock.now() < due_at);
+
+        // The scheduler was down for an hour, the shift still ends at midnight
+        clock.advance(Duration::hours(1));
+        assert!(clock.now() >= due_at);
+        assert_eq!(
+            Trigger::AutoClockOut {
+                shift_id,
+                employee_id,
+            }
+            .command(due_at),
+            Command::ClockOut {
+                shift_id,
+                employee_id,
+                clocked_out_at: due_at,
+            }
+        );
+    }
+
+    #[test]
+    fn triggers_keep_their_key_when_rescheduled() {
+        let employee_id = Uuid::new_v4();
+        let week_start = NaiveDate::from_ymd_opt(2025, 2, 3).unwrap();
+        let trigger = Trigger::AutoSubmitTimesheet {
+            employee_id,
+            week_start,
+        };
+
+        let saved = serde_json::to_string(&trigger).unwrap();
+        let loaded = serde_json::from_str::<Trigger>(&saved).unwrap();
+
+        assert_eq!(loaded.key(), trigger.key());
+        assert_ne!(
+            trigger.key(),
+            Trigger::AutoSubmitTimesheet {
+                employee_id,
+                week_start: week_start + Duration::days(7),
+            }
+            .key()
+        );
+    }
+
+    #[cfg(feature = "connect")]
+    mod firing {
+        use std::sync::Arc;
+
+        use chrono::{DateTime, Duration, TimeZone, Utc};
+        use sqlx::PgPool;
+        use uuid::Uuid;
+
+        use crate::{
+            projections::scheduled_trigger::ScheduledTrigger,
+            scheduler::{Clock, FakeClock, Scheduler, Trigger},
+        };
+
+        fn due_at() -> DateTime<Utc> {
+            Utc.with_ymd_and_hms(2025, 2, 4, 5, 0, 0).unwrap()
+        }
+
+        /// A scheduler on a clock a minute before `due_at`. The event store
+        /// is never reached, the triggers here are for shifts that don't
+        /// exist so they are marked fired without issuing anything.
+        fn scheduler(db_pool: PgPool) -> (Scheduler<Arc<FakeClock>>, Arc<FakeClock>) {
+            let settings = "esdb://localhost:2113?tls=false".parse().unwrap();
+            let eventstore = eventstore::Client::new(settings).unwrap();
+            let clock = Arc::new(FakeClock::new(due_at() - Duration::minutes(1)));
+
+            (
+                Scheduler::new(Arc::new(eventstore), Arc::new(db_pool), clock.clone()),
+                clock,
+            )
+        }
+
+        fn auto_clock_out() -> Trigger {
+            Trigger::AutoClockOut {
+                shift_id: Uuid::new_v4(),
+                employee_id: Uuid::new_v4(),
+            }
+        }
+
+        async fn schedule(
+            db_pool: &PgPool,
+            trigger: &Trigger,
+            due_at: DateTime<Utc>,
+        ) -> anyhow::Result<()> {
+            let mut transaction = db_pool.begin().await?;
+            ScheduledTrigger::schedule(&mut transaction, trigger, due_at, Uuid::new_v4()).await?;
+            transaction.commit().await?;
+
+            Ok(())
+        }
+
+        #[sqlx::test(migrations = "../migrations")]
+        async fn triggers_fire_once_they_are_due(db_pool: PgPool) -> anyhow::Result<()> {
+            let (scheduler, clock) = scheduler(db_pool.clone());
+            schedule(&db_pool, &auto_clock_out(), due_at()).await?;
+
+            assert_eq!(scheduler.fire_due().await?, 0);
+
+            clock.advance(Duration::minutes(1));
+            assert_eq!(scheduler.fire_due().await?, 1);
+
+            Ok(())
+        }
+
+        #[sqlx::test(migrations = "../migrations")]
+        async fn rescheduling_a_trigger_postpones_it(db_pool: PgPool) -> anyhow::Result<()> {
+            let (scheduler, clock) = scheduler(db_pool.clone());
+            let trigger = auto_clock_out();
+            schedule(&db_pool, &trigger, due_at()).await?;
+            schedule(&db_pool, &trigger, due_at() + Duration::hours(1)).await?;
+
+            clock.advance(Duration::minutes(1));
+            assert_eq!(scheduler.fire_due().await?, 0);
+
+            clock.advance(Duration::hours(1));
+            assert_eq!(scheduler.fire_due().await?, 1);
+
+            Ok(())
+        }
+
+        #[sqlx::test(migrations = "../migrations")]
+        async fn cancelled_triggers_never_fire(db_pool: PgPool) -> anyhow::Result<()> {
+            let (scheduler, clock) = scheduler(db_pool.clone());
+            let trigger = auto_clock_out();
+            schedule(&db_pool, &trigger, due_at()).await?;
+
+            let mut transaction = db_pool.begin().await?;
+            ScheduledTrigger::cancel(&mut transaction, &trigger).await?;
+            transaction.commit().await?;
+
+            clock.advance(Duration::days(1));
+            assert_eq!(scheduler.fire_due().await?, 0);
+
+            Ok(())
+        }
+
+        #[sqlx::test(migrations = "../migrations")]
+        async fn fired_triggers_do_not_fire_again(db_pool: PgPool) -> anyhow::Result<()> {
+            let (scheduler, clock) = scheduler(db_pool.clone());
+            let trigger = auto_clock_out();
+            schedule(&db_pool, &trigger, due_at()).await?;
+            schedule(&db_pool, &auto_clock_out(), due_at() + Duration::hours(1)).await?;
+
+            clock.advance(Duration::hours(2));
+            assert_eq!(scheduler.fire_due().await?, 2);
+            assert_eq!(scheduler.fire_due().await?, 0);
+
+            // Scheduling a fired trigger again leaves it fired
+            schedule(&db_pool, &trigger, clock.now()).await?;
+            assert_eq!(scheduler.fire_due().await?, 0);
+
+            Ok(())
+        }
+    }
+}
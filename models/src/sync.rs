@@ -0,0 +1,281 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{commands::Command, geofence::Coordinates, projections::daily_log::WeatherCondition};
+
+/// Most commands a device can send in one batch, longer queues are sent in
+/// several
+pub const MAX_SYNC_BATCH: usize = 200;
+/// How far ahead of the server a device's clock can run before its
+/// timestamps are refused
+pub const MAX_CLOCK_SKEW: Duration = Duration::minutes(5);
+
+/// A field device's queue, oldest first
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SyncRequest {
+    pub commands: Vec<QueuedCommand>,
+}
+
+/// Something done on a device while it was offline
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct QueuedCommand {
+    /// Picked by the device, sending the same command again with the same
+    /// key never applies it twice
+    pub idempotency_key: Uuid,
+    /// When it was done on the device, which is when it is recorded as done
+    pub client_timestamp: DateTime<Utc>,
+    pub command: QueuedAction,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum QueuedAction {
+    /// The device picks the shift id, so the clock-out can be queued too
+    ClockIn {
+        shift_id: Uuid,
+        employee_id: Uuid,
+        jobsite_id: Uuid,
+        #[serde(default)]
+        cost_code_id: Option<Uuid>,
+        #[serde(default)]
+        coordinates: Option<Coordinates>,
+    },
+    /// Closing a shift is what logs its hours
+    ClockOut { shift_id: Uuid },
+    SaveDailyLog {
+        jobsite_id: Uuid,
+        log_date: NaiveDate,
+        crew_count: i32,
+        weather: WeatherCondition,
+        #[serde(default)]
+        notes: Option<String>,
+    },
+}
+
+impl QueuedAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            QueuedAction::ClockIn { .. } => "clock_in",
+            QueuedAction::ClockOut { .. } => "clock_out",
+            QueuedAction::SaveDailyLog { .. } => "save_daily_log",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncStatus {
+    Applied,
+    /// The server's records moved on while the device was offline, the
+    /// device has to sort it out and queue something new
+    Conflict,
+    /// The command could never be applied as sent
+    Rejected,
+}
+
+/// What became of one queued command
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SyncResult {
+    pub idempotency_key: Uuid,
+    pub status: SyncStatus,
+    pub reason: Option<String>,
+    /// Applied by an earlier sync, nothing was done this time
+    pub duplicate: bool,
+}
+
+impl SyncResult {
+    pub fn applied(idempotency_key: Uuid) -> Self {
+        Self {
+            idempotency_key,
+            status: SyncStatus::Applied,
+            reason: None,
+            duplicate: false,
+        }
+    }
+
+    pub fn duplicate(idempotency_key: Uuid) -> Self {
+        Self {
+            duplicate: true,
+            ..Self::applied(idempotency_key)
+        }
+    }
+
+    pub fn conflict(idempotency_key: Uuid, reason: &str) -> Self {
+        Self {
+            idempotency_key,
+            status: SyncStatus::Conflict,
+            reason: Some(reason.to_string()),
+            duplicate: false,
+        }
+    }
+
+    pub fn rejected(idempotency_key: Uuid, reason: &str) -> Self {
+        Self {
+            idempotency_key,
+            status: SyncStatus::Rejected,
+            reason: Some(reason.to_string()),
+            duplicate: false,
+        }
+    }
+}
+
+/// Results in the order the commands were sent
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SyncResponse {
+    pub results: Vec<SyncResult>,
+}
+
+/// A shift clocked in earlier in the batch
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PendingShift {
+    pub employee_id: Uuid,
+    pub clocked_in_at: DateTime<Utc>,
+}
+
+/// Shifts clocked in and out earlier in a batch. Their events haven't
+/// reached the read models yet, so later commands in the batch are checked
+/// against these as well.
+#[derive(Debug, Default)]
+pub struct PendingShifts {
+    clocked_in: HashMap<Uuid, PendingShift>,
+    clocked_out: HashSet<Uuid>,
+}
+
+impl PendingShifts {
+    /// Keep track of an applied command
+    pub fn apply(&mut self, command: &Command) {
+        match command {
+            Command::ClockIn {
+                shift_id,
+                employee_id,
+                clocked_in_at,
+                ..
+            } => {
+                self.clocked_in.insert(
+                    *shift_id,
+                    PendingShift {
+                        employee_id: *employee_id,
+                        clocked_in_at: *clocked_in_at,
+                    },
+                );
+            }
+            Command::ClockOut { shift_id, .. } => {
+                self.clocked_out.insert(*shift_id);
+            }
+            _ => {}
+        }
+    }
+
+    /// A shift clocked in earlier in the batch, clocked out since or not
+    pub fn get(&self, shift_id: &Uuid) -> Option<&PendingShift> {
+        self.clocked_in.get(shift_id)
+    }
+
+    pub fn is_clocked_out(&self, shift_id: &Uuid) -> bool {
+        self.clocked_out.contains(shift_id)
+    }
+
+    /// Whether the employee is on the clock, given the open shift the read
+    /// model has for them
+    pub fn is_on_the_clock(&self, employee_id: &Uuid, open_shift_id: Option<Uuid>) -> bool {
+        let open_in_read_model =
+            open_shift_id.is_some_and(|shift_id| !self.is_clocked_out(&shift_id));
+
+        open_in_read_model
+            || self.clocked_in.iter().any(|(shift_id, shift)| {
+                shift.employee_id == *employee_id && !self.is_clocked_out(shift_id)
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use uuid::Uuid;
+
+    use super::{PendingShifts, QueuedAction, SyncRequest};
+    use crate::{commands::Command, geofence::GeofenceCheck};
+
+    #[test]
+    fn queued_commands_are_tagged_by_type() {
+        let request: SyncRequest = serde_json::from_str(
+            r#"{
+                "commands": [
+                    {
+                        "idempotency_key": "7c9e6679-7425-40de-944b-e07fc1f90ae7",
+                        "client_timestamp": "2025-03-03T14:30:00Z",
+                        "command": {
+                            "type": "clock_out",
+                            "shift_id": "1b9d6bcd-bbfd-4b2d-9b5d-ab8dfbbd4bed"
+                        }
+                    },
+                    {
+                        "idempotency_key": "f47ac10b-58cc-4372-a567-0e02b2c3d479",
+                        "client_timestamp": "2025-03-03T15:00:00Z",
+                        "command": {
+                            "type": "save_daily_log",
+                            "jobsite_id": "1b9d6bcd-bbfd-4b2d-9b5d-ab8dfbbd4bed",
+                            "log_date": "2025-03-03",
+                            "crew_count": 4,
+                            "weather": "Rain"
+                        }
+                    }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(request.commands.len(), 2);
+        assert_eq!(request.commands[0].command.as_str(), "clock_out");
+        assert!(matches!(
+            request.commands[1].command,
+            QueuedAction::SaveDailyLog {
+                crew_count: 4,
+                notes: None,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn shifts_clocked_out_in_the_batch_free_the_employee() {
+        let employee_id = Uuid::new_v4();
+        let read_model_shift_id = Uuid::new_v4();
+        let shift_id = Uuid::new_v4();
+        let at = Utc.with_ymd_and_hms(2025, 3, 3, 14, 0, 0).unwrap();
+        let mut pending = PendingShifts::default();
+
+        assert!(pending.is_on_the_clock(&employee_id, Some(read_model_shift_id)));
+
+        pending.apply(&Command::ClockOut {
+            shift_id: read_model_shift_id,
+            employee_id,
+            clocked_out_at: at,
+        });
+        assert!(!pending.is_on_the_clock(&employee_id, Some(read_model_shift_id)));
+
+        pending.apply(&Command::ClockIn {
+            shift_id,
+            employee_id,
+            jobsite_id: Uuid::new_v4(),
+            cost_code_id: None,
+            clocked_in_at: at,
+            time_zone: "UTC".to_string(),
+            coordinates: None,
+            geofence: GeofenceCheck::default(),
+        });
+        assert!(pending.is_on_the_clock(&employee_id, None));
+        assert!(!pending.is_on_the_clock(&Uuid::new_v4(), None));
+
+        pending.apply(&Command::ClockOut {
+            shift_id,
+            employee_id,
+            clocked_out_at: at,
+        });
+        assert!(!pending.is_on_the_clock(&employee_id, None));
+        assert_eq!(pending.get(&shift_id).unwrap().clocked_in_at, at);
+    }
+}
@@ -7,6 +7,8 @@ pub struct Settings {
     pub application: ApplicationSettings,
     pub database: DatabaseSettings,
     pub eventstore: EventStoreSettings,
+    pub labor: LaborSettings,
+    pub storage: StorageSettings,
 }
 
 #[derive(serde::Deserialize, Clone, Debug)]
@@ -16,6 +18,17 @@ pub struct ApplicationSettings {
     pub host: String,
     pub domain: String,
     pub secure: bool,
+    /// Address the app is reached at from outside, like
+    /// `https://time.example.com`, links that leave the app such as QR codes
+    /// and calendar feeds are built on it rather than on the request's Host
+    pub base_url: String,
+    /// Key used to sign links that are shared outside the app, such as
+    /// calendar feed URLs
+    pub signing_key: Secret<String>,
+    /// IANA name of the zone new jobsites and certification deadlines are
+    /// kept in, like `America/Chicago`, also the zone "today" is counted in
+    /// on pages that span jobsites
+    pub time_zone: String,
 }
 
 #[derive(serde::Deserialize, Clone, Debug)]
@@ -23,6 +36,38 @@ pub struct EventStoreSettings {
     pub url: String,
 }
 
+#[derive(serde::Deserialize, Clone, Debug)]
+pub struct StorageSettings {
+    /// Directory attachments are stored in
+    pub path: String,
+    /// Largest attachment that can be uploaded, in bytes
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub max_upload_bytes: usize,
+}
+
+#[derive(serde::Deserialize, Clone, Debug)]
+pub struct LaborSettings {
+    /// The preset pay rules to classify hours with, one of `none`, `federal`
+    /// or `california`
+    pub pay_rules: String,
+    /// Thresholds that replace the preset's
+    #[serde(default)]
+    pub overtime: OvertimeSettings,
+    /// Dates worked at the holiday rate
+    #[serde(default)]
+    pub holidays: Vec<chrono::NaiveDate>,
+}
+
+/// Overtime thresholds in hours, each one left out keeps the preset's. Use
+/// the `none` preset to leave out a threshold the other presets have.
+#[derive(serde::Deserialize, Clone, Debug, Default)]
+pub struct OvertimeSettings {
+    pub daily_overtime_after_hours: Option<f64>,
+    pub daily_double_time_after_hours: Option<f64>,
+    pub weekly_overtime_after_hours: Option<f64>,
+    pub seventh_day_double_time_after_hours: Option<f64>,
+}
+
 fn workspace_dir() -> std::path::PathBuf {
     let output = std::process::Command::new(env!("CARGO"))
         .arg("locate-project")
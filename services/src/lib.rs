@@ -2,6 +2,9 @@ use configuration::{DatabaseSettings, EventStoreSettings};
 use sqlx::{postgres::PgPoolOptions, PgPool};
 
 pub mod configuration;
+pub mod qr_code;
+pub mod signing;
+pub mod storage;
 
 pub fn get_connection_pool(configuration: &DatabaseSettings) -> PgPool {
     PgPoolOptions::new()
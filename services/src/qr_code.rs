@@ -0,0 +1,39 @@
+use std::io::Cursor;
+
+use image::{ImageFormat, Luma};
+use qrcode::{render::svg, EcLevel, QrCode};
+
+/// Smallest side of a rendered code, in pixels, big enough to scan from a
+/// printed sheet at arm's length
+pub const QR_CODE_SIZE: u32 = 512;
+
+/// Codes are printed and put up outdoors, so they're encoded to survive a
+/// quarter of them being dirty or torn
+fn encode(data: &str) -> Option<QrCode> {
+    QrCode::with_error_correction_level(data, EcLevel::Q).ok()
+}
+
+/// `data` as an SVG QR code, `None` when it's too long to fit in one
+pub fn qr_code_svg(data: &str) -> Option<String> {
+    Some(
+        encode(data)?
+            .render::<svg::Color>()
+            .min_dimensions(QR_CODE_SIZE, QR_CODE_SIZE)
+            .build(),
+    )
+}
+
+/// `data` as a PNG QR code, `None` when it's too long to fit in one
+pub fn qr_code_png(data: &str) -> Option<Vec<u8>> {
+    let image = encode(data)?
+        .render::<Luma<u8>>()
+        .min_dimensions(QR_CODE_SIZE, QR_CODE_SIZE)
+        .build();
+
+    let mut png = Vec::new();
+    image
+        .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+        .ok()?;
+
+    Some(png)
+}
@@ -0,0 +1,25 @@
+use hmac::{Hmac, Mac};
+use secrecy::{ExposeSecret, Secret};
+use sha2::Sha256;
+
+type HmacSha256 = Hmac<Sha256>;
+
+fn mac(key: &Secret<String>, message: &str) -> HmacSha256 {
+    let mut mac = HmacSha256::new_from_slice(key.expose_secret().as_bytes())
+        .expect("HMAC accepts keys of any length");
+    mac.update(message.as_bytes());
+    mac
+}
+
+/// Sign `message`, returning a hex token that can be put in a URL
+pub fn sign(key: &Secret<String>, message: &str) -> String {
+    hex::encode(mac(key, message).finalize().into_bytes())
+}
+
+/// Check a token produced by [`sign`] in constant time
+pub fn verify(key: &Secret<String>, message: &str, token: &str) -> bool {
+    match hex::decode(token) {
+        Ok(signature) => mac(key, message).verify_slice(&signature).is_ok(),
+        Err(_) => false,
+    }
+}
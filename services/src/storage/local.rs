@@ -0,0 +1,58 @@
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use super::{content_hash, is_content_hash, AttachmentStorage, StorageError};
+
+/// Attachment storage in a directory on the local filesystem, files are
+/// fanned out into subdirectories by the first two characters of their hash
+#[derive(Clone, Debug)]
+pub struct LocalFileStorage {
+    root: PathBuf,
+}
+
+impl LocalFileStorage {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    fn path_for(&self, hash: &str) -> Result<PathBuf, StorageError> {
+        if !is_content_hash(hash) {
+            return Err(StorageError::InvalidHash(hash.to_string()));
+        }
+
+        Ok(self.root.join(&hash[..2]).join(hash))
+    }
+}
+
+#[async_trait]
+impl AttachmentStorage for LocalFileStorage {
+    async fn put(&self, content: &[u8]) -> Result<String, StorageError> {
+        let hash = content_hash(content);
+        let path = self.path_for(&hash)?;
+
+        if tokio::fs::try_exists(&path).await? {
+            return Ok(hash);
+        }
+
+        let directory = path.parent().expect("Content paths are always nested");
+        tokio::fs::create_dir_all(directory).await?;
+
+        // Write to a temporary file first so a half written file is never
+        // served under the hash
+        let temporary = directory.join(format!(".{}.tmp", Uuid::new_v4()));
+        tokio::fs::write(&temporary, content).await?;
+        tokio::fs::rename(&temporary, &path).await?;
+
+        Ok(hash)
+    }
+
+    async fn get(&self, hash: &str) -> Result<Option<Vec<u8>>, StorageError> {
+        match tokio::fs::read(self.path_for(hash)?).await {
+            Ok(content) => Ok(Some(content)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
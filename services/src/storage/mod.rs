@@ -0,0 +1,120 @@
+use async_trait::async_trait;
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+mod local;
+mod thumbnail;
+
+pub use local::*;
+pub use thumbnail::*;
+
+#[derive(Error, Debug)]
+pub enum StorageError {
+    #[error("Invalid content hash: {0}")]
+    InvalidHash(String),
+    #[error("Storage IO error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Where attachment content is kept. Content is addressed by the hex SHA-256
+/// of its bytes, so the same file uploaded twice is only stored once and a
+/// hash always refers to the same bytes.
+#[async_trait]
+pub trait AttachmentStorage: Send + Sync {
+    /// Store `content`, returning its hash
+    async fn put(&self, content: &[u8]) -> Result<String, StorageError>;
+
+    /// The content stored under `hash`, if any
+    async fn get(&self, hash: &str) -> Result<Option<Vec<u8>>, StorageError>;
+}
+
+/// The hex SHA-256 of `content`, the key it's stored under
+pub fn content_hash(content: &[u8]) -> String {
+    hex::encode(Sha256::digest(content))
+}
+
+/// Hashes come from URLs and events, only ever accept the shape
+/// [`content_hash`] produces so they're safe to build paths from
+pub fn is_content_hash(hash: &str) -> bool {
+    hash.len() == 64
+        && hash
+            .bytes()
+            .all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b))
+}
+
+/// The content types attachments are accepted in
+pub const ALLOWED_CONTENT_TYPES: [&str; 5] = [
+    "image/jpeg",
+    "image/png",
+    "image/gif",
+    "image/webp",
+    "application/pdf",
+];
+
+/// Work out the content type from the file's leading bytes rather than
+/// trusting what the browser sent, `None` when it isn't an allowed type
+pub fn detect_content_type(content: &[u8]) -> Option<&'static str> {
+    if content.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        Some("image/jpeg")
+    } else if content.starts_with(b"\x89PNG\r\n\x1a\n") {
+        Some("image/png")
+    } else if content.starts_with(b"GIF87a") || content.starts_with(b"GIF89a") {
+        Some("image/gif")
+    } else if content.len() >= 12 && &content[..4] == b"RIFF" && &content[8..12] == b"WEBP" {
+        Some("image/webp")
+    } else if content.starts_with(b"%PDF-") {
+        Some("application/pdf")
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{content_hash, detect_content_type, is_content_hash};
+
+    #[test]
+    fn content_is_hashed_with_sha256() {
+        assert_eq!(
+            content_hash(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert!(is_content_hash(&content_hash(b"")));
+    }
+
+    #[test]
+    fn only_lowercase_hex_hashes_are_accepted() {
+        let hash = content_hash(b"abc");
+        assert!(is_content_hash(&hash));
+
+        assert!(!is_content_hash(&hash.to_uppercase()));
+        assert!(!is_content_hash(&hash[..63]));
+        assert!(!is_content_hash(&format!("{}0", hash)));
+        assert!(!is_content_hash(&format!("../../{}", &hash[6..])));
+        assert!(!is_content_hash(&format!(
+            "{}/{}",
+            &hash[..31],
+            &hash[32..]
+        )));
+        assert!(!is_content_hash(&"g".repeat(64)));
+        assert!(!is_content_hash(""));
+    }
+
+    #[test]
+    fn content_types_come_from_magic_bytes() {
+        assert_eq!(
+            detect_content_type(b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR"),
+            Some("image/png")
+        );
+        assert_eq!(
+            detect_content_type(&[0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x10]),
+            Some("image/jpeg")
+        );
+        assert_eq!(detect_content_type(b"%PDF-1.7\n"), Some("application/pdf"));
+
+        // Anything else is turned away, whatever it claims to be
+        assert_eq!(detect_content_type(b"<html><body>"), None);
+        assert_eq!(detect_content_type(b"\x89PN"), None);
+        assert_eq!(detect_content_type(b""), None);
+    }
+}
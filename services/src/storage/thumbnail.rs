@@ -0,0 +1,24 @@
+use std::io::Cursor;
+
+use image::{ImageFormat, ImageReader};
+
+/// Longest side of a generated thumbnail, in pixels
+pub const THUMBNAIL_SIZE: u32 = 240;
+
+/// A PNG thumbnail of an uploaded image, `None` when the content isn't an
+/// image that can be decoded
+pub fn generate_thumbnail(content: &[u8]) -> Option<Vec<u8>> {
+    let image = ImageReader::new(Cursor::new(content))
+        .with_guessed_format()
+        .ok()?
+        .decode()
+        .ok()?;
+
+    let mut thumbnail = Vec::new();
+    image
+        .thumbnail(THUMBNAIL_SIZE, THUMBNAIL_SIZE)
+        .write_to(&mut Cursor::new(&mut thumbnail), ImageFormat::Png)
+        .ok()?;
+
+    Some(thumbnail)
+}